@@ -88,9 +88,75 @@ fn compute_swap_step_benchmark_ref(c: &mut Criterion) {
     });
 }
 
+/// A tick list with liquidity in nested ranges around tick 0, so that a large swap crosses many
+/// initialized ticks
+fn generate_ticks() -> (TickListDataProvider, u128) {
+    const LIQUIDITY: u128 = 1_000_000_000_000_000_000;
+    let tick_spacing = FeeAmount::MEDIUM.tick_spacing().as_i32();
+    let mut ticks = Vec::new();
+    for i in (1..=50).rev() {
+        ticks.push(Tick::new(
+            -tick_spacing * 10 * i,
+            LIQUIDITY,
+            LIQUIDITY as i128,
+        ));
+    }
+    for i in 1..=50 {
+        ticks.push(Tick::new(
+            tick_spacing * 10 * i,
+            LIQUIDITY,
+            -(LIQUIDITY as i128),
+        ));
+    }
+    (
+        TickListDataProvider::new(ticks, tick_spacing),
+        LIQUIDITY * 50,
+    )
+}
+
+fn v3_swap_benchmark(c: &mut Criterion) {
+    let (tick_data_provider, liquidity) = generate_ticks();
+    let tick_spacing = FeeAmount::MEDIUM.tick_spacing().as_i32();
+    let sqrt_price_x96 = encode_sqrt_ratio_x96(1, 1);
+    let amount_specified = I256::from_raw(U256::from(10_u128.pow(20)));
+    c.bench_function("v3_swap", |b| {
+        b.iter(|| {
+            v3_swap(
+                FeeAmount::MEDIUM.into(),
+                sqrt_price_x96,
+                0,
+                liquidity,
+                tick_spacing,
+                &tick_data_provider,
+                true,
+                amount_specified,
+                None,
+            )
+            .unwrap()
+        })
+    });
+    c.bench_function("v3_swap_with_trace", |b| {
+        b.iter(|| {
+            v3_swap_with_trace(
+                FeeAmount::MEDIUM.into(),
+                sqrt_price_x96,
+                0,
+                liquidity,
+                tick_spacing,
+                &tick_data_provider,
+                true,
+                amount_specified,
+                None,
+            )
+            .unwrap()
+        })
+    });
+}
+
 criterion_group!(
     benches,
     compute_swap_step_benchmark,
     // compute_swap_step_benchmark_ref,
+    v3_swap_benchmark,
 );
 criterion_main!(benches);
@@ -29,6 +29,36 @@ fn get_sqrt_ratio_at_tick_benchmark_ref(c: &mut Criterion) {
     });
 }
 
+/// Ticks spread over the full range, so that the set-bit walk multiplies up to 19 factors
+fn generate_full_range_inputs() -> Vec<i32> {
+    (-128..=128).map(|i| i * (MAX_TICK_I32 / 128)).collect()
+}
+
+fn get_sqrt_ratio_at_tick_full_range_benchmark(c: &mut Criterion) {
+    let inputs: Vec<I24> = generate_full_range_inputs()
+        .into_iter()
+        .map(|i| I24::try_from(i).unwrap())
+        .collect();
+    c.bench_function("get_sqrt_ratio_at_tick_full_range", |b| {
+        b.iter(|| {
+            for i in &inputs {
+                let _ = get_sqrt_ratio_at_tick(*i);
+            }
+        })
+    });
+}
+
+fn get_sqrt_ratio_at_tick_full_range_benchmark_ref(c: &mut Criterion) {
+    let inputs = generate_full_range_inputs();
+    c.bench_function("get_sqrt_ratio_at_tick_full_range_ref", |b| {
+        b.iter(|| {
+            for i in &inputs {
+                let _ = tick_math::get_sqrt_ratio_at_tick(*i);
+            }
+        })
+    });
+}
+
 fn get_tick_at_sqrt_ratio_benchmark(c: &mut Criterion) {
     c.bench_function("get_tick_at_sqrt_ratio", |b| {
         b.iter(|| {
@@ -53,6 +83,8 @@ criterion_group!(
     benches,
     get_sqrt_ratio_at_tick_benchmark,
     get_sqrt_ratio_at_tick_benchmark_ref,
+    get_sqrt_ratio_at_tick_full_range_benchmark,
+    get_sqrt_ratio_at_tick_full_range_benchmark_ref,
     get_tick_at_sqrt_ratio_benchmark,
     get_tick_at_sqrt_ratio_benchmark_ref
 );
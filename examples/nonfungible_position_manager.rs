@@ -70,6 +70,7 @@ async fn main() {
             npm,
             amount0,
             &provider,
+            None,
         )
         .await
         .unwrap();
@@ -80,6 +81,7 @@ async fn main() {
                 npm,
                 amount1,
                 &provider,
+                None,
             )
             .await
             .unwrap(),
@@ -135,6 +137,7 @@ where
             recipient: from,
             create_pool: false,
         }),
+        chain_id: None,
     };
     let params = add_call_parameters(position, options).unwrap();
     let tx = N::TransactionRequest::default()
@@ -209,6 +212,7 @@ async fn burn_liquidity<N, P>(
             .unwrap(),
             recipient: owner.address(),
         },
+        chain_id: None,
     };
     let params = remove_call_parameters(position, options).unwrap();
     let tx = N::TransactionRequest::default()
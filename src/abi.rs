@@ -6,6 +6,34 @@ sol! {
     }
 }
 
+sol! {
+    interface IMulticallExtended {
+        function multicall(uint256 deadline, bytes[] calldata data) external payable returns (bytes[] memory results);
+    }
+}
+
+sol! {
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls)
+            external
+            payable
+            returns (Call3Result[] memory returnData);
+
+        function getCurrentBlockTimestamp() external view returns (uint256 timestamp);
+    }
+}
+
 sol! {
     interface INonfungiblePositionManager {
         function createAndInitializePoolIfNecessary(
@@ -88,6 +116,10 @@ sol! {
         function safeTransferFrom(address from, address to, uint256 tokenId) external;
 
         function safeTransferFrom(address from, address to, uint256 tokenId, bytes calldata data) external;
+
+        function approve(address to, uint256 tokenId) external;
+
+        function setApprovalForAll(address operator, bool approved) external;
     }
 
     interface IERC721Permit {
@@ -322,3 +354,126 @@ sol! {
         function exactOutput(ExactOutputParams calldata params) external payable returns (uint256 amountIn);
     }
 }
+
+sol! {
+    interface IApproveAndCall {
+        function approveMax(address token) external payable;
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct MintParams {
+            address token0;
+            address token1;
+            uint24 fee;
+            int24 tickLower;
+            int24 tickUpper;
+            uint256 amount0Min;
+            uint256 amount1Min;
+            address recipient;
+        }
+
+        function mint(MintParams calldata params) external payable returns (bytes memory result);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct IncreaseLiquidityParams {
+            address token0;
+            address token1;
+            uint256 tokenId;
+            uint256 amount0Min;
+            uint256 amount1Min;
+        }
+
+        function increaseLiquidity(IncreaseLiquidityParams calldata params) external payable returns (bytes memory result);
+
+        function callPositionManager(bytes memory data) external payable returns (bytes memory result);
+    }
+
+    interface IPeripheryPaymentsExtended {
+        function pull(address token, uint256 value) external payable;
+    }
+}
+
+sol! {
+    interface IV3Migrator {
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct MigrateParams {
+            address pair;
+            uint256 liquidityToMigrate;
+            uint8 percentageToMigrate;
+            address token0;
+            address token1;
+            uint24 fee;
+            int24 tickLower;
+            int24 tickUpper;
+            uint256 amount0Min;
+            uint256 amount1Min;
+            address recipient;
+            uint256 deadline;
+            bool refundAsETH;
+        }
+
+        function migrate(MigrateParams calldata params) external;
+    }
+}
+
+sol! {
+    interface IUniswapV3PoolActions {
+        function swap(
+            address recipient,
+            bool zeroForOne,
+            int256 amountSpecified,
+            uint160 sqrtPriceLimitX96,
+            bytes calldata data
+        ) external returns (int256 amount0, int256 amount1);
+
+        function flash(
+            address recipient,
+            uint256 amount0,
+            uint256 amount1,
+            bytes calldata data
+        ) external;
+    }
+
+    interface IUniswapV3SwapCallback {
+        function uniswapV3SwapCallback(
+            int256 amount0Delta,
+            int256 amount1Delta,
+            bytes calldata data
+        ) external;
+    }
+
+    interface IUniswapV3FlashCallback {
+        function uniswapV3FlashCallback(
+            uint256 fee0,
+            uint256 fee1,
+            bytes calldata data
+        ) external;
+    }
+
+    /// The callback data of `SwapRouter`, where the path of an exact output swap is reversed
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct SwapCallbackData {
+        bytes path;
+        address payer;
+    }
+
+    /// The callback data of a single pool, e.g. `PoolAddress.PoolKey` followed by the payer
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct PoolCallbackData {
+        address token0;
+        address token1;
+        uint24 fee;
+        address payer;
+    }
+
+    /// The callback data of a flash loan, e.g. the amounts and payer followed by the
+    /// `PoolAddress.PoolKey` of the pool as in the `PairFlash` example of the periphery contracts
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct FlashCallbackData {
+        uint256 amount0;
+        uint256 amount1;
+        address payer;
+        address token0;
+        address token1;
+        uint24 fee;
+    }
+}
@@ -0,0 +1,124 @@
+//! ## Chains
+//! The addresses of the Uniswap V3 contracts and of the wrapped native currency on each chain
+//! where Uniswap Labs deployed them.
+
+use crate::prelude::*;
+use alloy_primitives::{address, Address, ChainId};
+
+/// The addresses of the Uniswap V3 deployment on a chain, see [`get_chain_addresses`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainAddresses {
+    pub factory: Address,
+    pub nonfungible_position_manager: Address,
+    pub swap_router02: Address,
+    pub quoter_v2: Address,
+    pub tick_lens: Address,
+    /// The wrapped native currency, i.e. `WETH9` of the periphery contracts, which is WBNB on BNB
+    /// Chain and WMATIC on Polygon
+    pub weth: Address,
+}
+
+/// The addresses shared by the original deployments on Ethereum, Optimism, Polygon and Arbitrum
+const fn original_deployment(weth: Address) -> ChainAddresses {
+    ChainAddresses {
+        factory: FACTORY_ADDRESS,
+        nonfungible_position_manager: address!("C36442b4a4522E871399CD717aBDD847Ab11FE88"),
+        swap_router02: address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"),
+        quoter_v2: address!("61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        tick_lens: address!("bfd8137f7d1516D3ea5cA83523914859ec47F573"),
+        weth,
+    }
+}
+
+pub const MAINNET_ADDRESSES: ChainAddresses =
+    original_deployment(address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+pub const OPTIMISM_ADDRESSES: ChainAddresses =
+    original_deployment(address!("4200000000000000000000000000000000000006"));
+
+pub const POLYGON_ADDRESSES: ChainAddresses =
+    original_deployment(address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"));
+
+pub const ARBITRUM_ADDRESSES: ChainAddresses =
+    original_deployment(address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"));
+
+pub const BASE_ADDRESSES: ChainAddresses = ChainAddresses {
+    factory: address!("33128a8fC17869897dcE68Ed026d694621f6FDfD"),
+    nonfungible_position_manager: address!("03a520b32C04BF3bEEf7BEb72E919cf822Ed34f1"),
+    swap_router02: address!("2626664c2603336E57B271c5C0b26F421741e481"),
+    quoter_v2: address!("3d4e44Eb1374240CE5F1B871ab261CD16335B76a"),
+    tick_lens: address!("0CdeE061c75D43c82520eD998C23ac2991c9ac6d"),
+    weth: address!("4200000000000000000000000000000000000006"),
+};
+
+pub const BNB_ADDRESSES: ChainAddresses = ChainAddresses {
+    factory: address!("dB1d10011AD0Ff90774D0C6Bb92e5C5c8b4461F7"),
+    nonfungible_position_manager: address!("7b8A01B39D58278b5DE7e48c8449c9f4F5170613"),
+    swap_router02: address!("B971eF87ede563556b2ED4b1C0b0019111Dd85d2"),
+    quoter_v2: address!("78D78E420Da98ad378D7799bE8f4AF69033EB077"),
+    tick_lens: address!("D9270014D396281579760619CCf4c3af0501A47C"),
+    weth: address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+};
+
+/// The deployment on zkSync Era, whose pool addresses are derived by the zkSync `CREATE2` scheme,
+/// see [`compute_pool_address`]
+pub const ZKSYNC_ADDRESSES: ChainAddresses = ChainAddresses {
+    factory: address!("8FdA5a7a8dCA67BBcDd10F02Fa0649A937215422"),
+    nonfungible_position_manager: address!("0616e5762c1E7Dc3723c50663dF10a162D690a86"),
+    swap_router02: address!("99c56385daBCE3E81d8499d0b8d0257aBC07E8A3"),
+    quoter_v2: address!("8Cb537fc92E26d8EBBb760E632c95484b6Ea3e28"),
+    tick_lens: address!("e10FF11b809f8EE07b056B452c3B2caa7FE24f89"),
+    weth: address!("5AEa5775959fBC2557Cc8789bC1bf90A239D9a91"),
+};
+
+/// Returns the addresses of the Uniswap V3 deployment on a chain, if known
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+#[inline]
+#[must_use]
+pub const fn get_chain_addresses(chain_id: ChainId) -> Option<ChainAddresses> {
+    match chain_id {
+        1 => Some(MAINNET_ADDRESSES),
+        10 => Some(OPTIMISM_ADDRESSES),
+        56 => Some(BNB_ADDRESSES),
+        137 => Some(POLYGON_ADDRESSES),
+        324 => Some(ZKSYNC_ADDRESSES),
+        8453 => Some(BASE_ADDRESSES),
+        42161 => Some(ARBITRUM_ADDRESSES),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uniswap_sdk_core::prelude::{
+        NONFUNGIBLE_POSITION_MANAGER_ADDRESSES, SWAP_ROUTER_02_ADDRESSES,
+    };
+
+    #[test]
+    fn test_get_chain_addresses() {
+        assert_eq!(get_chain_addresses(1), Some(MAINNET_ADDRESSES));
+        assert_eq!(get_chain_addresses(8453), Some(BASE_ADDRESSES));
+        assert_eq!(get_chain_addresses(324), Some(ZKSYNC_ADDRESSES));
+        assert_eq!(get_chain_addresses(0), None);
+    }
+
+    #[test]
+    fn test_matches_sdk_core() {
+        for chain_id in [1, 10, 56, 137, 8453, 42161] {
+            let addresses = get_chain_addresses(chain_id).unwrap();
+            assert_eq!(
+                Some(&addresses.nonfungible_position_manager),
+                NONFUNGIBLE_POSITION_MANAGER_ADDRESSES.get(&chain_id)
+            );
+            assert_eq!(
+                Some(&addresses.swap_router02),
+                SWAP_ROUTER_02_ADDRESSES.get(&chain_id)
+            );
+        }
+    }
+}
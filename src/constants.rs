@@ -6,11 +6,20 @@ use alloy_primitives::{
 
 pub const FACTORY_ADDRESS: Address = address!("1F98431c8aD98523631AE4a59f267346ea31F984");
 
+/// The number of seconds in a year of 365 days, used to annualize rates
+pub(crate) const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
 pub const POOL_INIT_CODE_HASH: B256 =
     b256!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54");
 
+/// The hash of the bytecode of the pools on zkSync Era, whose `CREATE2` addresses are derived by
+/// the zkSync scheme, see [`compute_pool_address`].
+pub const ZKSYNC_POOL_INIT_CODE_HASH: B256 =
+    b256!("010013f177ea1fcbc4520f9a3ca7cd2d1d77959e05aa66484027cb38e712aeed");
+
 /// The default factory enabled fee amounts, denominated in hundredths of bips.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 #[allow(non_camel_case_types)]
 pub enum FeeAmount {
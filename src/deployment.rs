@@ -0,0 +1,156 @@
+//! ## Deployment
+//! The addresses of a deployment of the Uniswap V3 contracts or of a fork of them, with a registry
+//! of the known deployments, to compute the pool addresses and target the calldata of the other
+//! modules at the right contracts.
+
+use crate::prelude::*;
+use alloy_primitives::{address, b256, Address, ChainId, B256};
+
+/// The contracts of a deployment of Uniswap V3 or of a fork on a chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeploymentConfig {
+    /// The factory that creates the pools
+    pub factory: Address,
+    /// The hash of the init code of the pools
+    pub pool_init_code_hash: B256,
+    /// The contract that deploys the pools with `CREATE2`, which is the factory unless the fork
+    /// splits it out like PancakeSwap V3
+    pub deployer: Address,
+    /// The nonfungible position manager targeted by the calldata of
+    /// [`nonfungible_position_manager`](crate::nonfungible_position_manager)
+    pub nonfungible_position_manager: Address,
+    /// The swap router targeted by the calldata of [`swap_router`](crate::swap_router)
+    pub swap_router: Address,
+}
+
+/// The known forks of Uniswap V3, see [`DeploymentConfig::get`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fork {
+    UniswapV3,
+    PancakeSwapV3,
+}
+
+const PANCAKESWAP_V3: DeploymentConfig = DeploymentConfig {
+    factory: address!("0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865"),
+    pool_init_code_hash: b256!("6ce8eb472fa82df5469c6ab6d485f17c3ad13c8cd7af59b3d4a8026c5ce0f7e2"),
+    deployer: address!("41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9"),
+    nonfungible_position_manager: address!("46A15B0b27311cedF172AB29E4f4766fbE7F4364"),
+    swap_router: address!("13f4EA83D0bd40E75C8222255bc855a974568Dd4"),
+};
+
+impl DeploymentConfig {
+    /// Returns the deployment of a known fork on a chain, if any. The deployments of other forks,
+    /// e.g. SushiSwap V3 or Ramses, can be constructed directly.
+    ///
+    /// ## Arguments
+    ///
+    /// * `fork`: The fork
+    /// * `chain_id`: The chain id
+    #[inline]
+    #[must_use]
+    pub const fn get(fork: Fork, chain_id: ChainId) -> Option<Self> {
+        match fork {
+            // zkSync Era derives the pool addresses with its own `CREATE2` scheme, which
+            // `pool_address` does not compute
+            Fork::UniswapV3 if chain_id == 324 => None,
+            Fork::UniswapV3 => match get_chain_addresses(chain_id) {
+                Some(addresses) => Some(Self {
+                    factory: addresses.factory,
+                    pool_init_code_hash: POOL_INIT_CODE_HASH,
+                    deployer: addresses.factory,
+                    nonfungible_position_manager: addresses.nonfungible_position_manager,
+                    swap_router: addresses.swap_router02,
+                }),
+                None => None,
+            },
+            // Ethereum and BNB Chain
+            Fork::PancakeSwapV3 => match chain_id {
+                1 | 56 => Some(PANCAKESWAP_V3),
+                _ => None,
+            },
+        }
+    }
+
+    /// Computes the address of a pool of the deployment, see [`compute_pool_address`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_a`: The first token of the pair, irrespective of sort order
+    /// * `token_b`: The second token of the pair, irrespective of sort order
+    /// * `fee`: The fee tier of the pool
+    #[inline]
+    #[must_use]
+    pub fn pool_address(&self, token_a: Address, token_b: Address, fee: FeeAmount) -> Address {
+        compute_pool_address(
+            self.deployer,
+            token_a,
+            token_b,
+            fee,
+            Some(self.pool_init_code_hash),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_get() {
+        let deployment = DeploymentConfig::get(Fork::UniswapV3, 8453).unwrap();
+        assert_eq!(deployment.factory, BASE_ADDRESSES.factory);
+        assert_eq!(deployment.deployer, BASE_ADDRESSES.factory);
+        assert_eq!(deployment.swap_router, BASE_ADDRESSES.swap_router02);
+        assert_eq!(
+            DeploymentConfig::get(Fork::PancakeSwapV3, 56),
+            Some(PANCAKESWAP_V3)
+        );
+        assert_eq!(DeploymentConfig::get(Fork::PancakeSwapV3, 10), None);
+    }
+
+    #[test]
+    fn test_pool_address() {
+        let deployment = DeploymentConfig::get(Fork::UniswapV3, 1).unwrap();
+        assert_eq!(
+            deployment.pool_address(USDC.address(), WETH.address(), FeeAmount::LOW),
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640")
+        );
+        assert_eq!(
+            deployment.pool_address(USDC.address(), WETH.address(), FeeAmount::LOW),
+            Pool::get_address(&USDC, &WETH, FeeAmount::LOW, None, None)
+        );
+    }
+
+    #[test]
+    fn test_pool_address_deployer() {
+        // the pools of PancakeSwap V3 are deployed by a contract other than the factory
+        let deployment = DeploymentConfig::get(Fork::PancakeSwapV3, 56).unwrap();
+        let pool = deployment.pool_address(USDC.address(), WETH.address(), FeeAmount::CUSTOM(2500));
+        assert_eq!(
+            pool,
+            compute_pool_address(
+                deployment.deployer,
+                WETH.address(),
+                USDC.address(),
+                FeeAmount::CUSTOM(2500),
+                Some(deployment.pool_init_code_hash),
+                None,
+            )
+        );
+        assert_ne!(
+            pool,
+            compute_pool_address(
+                deployment.factory,
+                USDC.address(),
+                WETH.address(),
+                FeeAmount::CUSTOM(2500),
+                Some(deployment.pool_init_code_hash),
+                None,
+            )
+        );
+    }
+}
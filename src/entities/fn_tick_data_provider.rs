@@ -0,0 +1,216 @@
+use crate::prelude::{Error, *};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+use once_cell::sync::OnceCell;
+
+/// A tick data provider backed by closures, for plugging in custom data sources, e.g. databases or
+/// remote services, without defining a new type for each.
+///
+/// The closures are called with the same arguments as the methods of [`TickDataProvider`], except
+/// that the `get_tick` closure returns the tick by value. Since the provider hands out references,
+/// the ticks are kept after the first lookup, so the closures should describe a fixed state of the
+/// pool, and a new provider should be created when the state changes. A tick returned for another
+/// index, or a next initialized tick in the wrong direction, is rejected with
+/// [`Error::InvalidTick`].
+#[derive(Clone)]
+pub struct FnTickDataProvider<I, G, N> {
+    tick_spacing: I,
+    get_tick: G,
+    next_initialized_tick_within_one_word: N,
+    /// The position of the first tick bitmap word
+    min_word_pos: I,
+    /// The ticks looked up so far by tick bitmap word
+    words: Vec<OnceCell<Box<[OnceCell<Tick<I>>]>>>,
+}
+
+impl<I, G, N> fmt::Debug for FnTickDataProvider<I, G, N>
+where
+    I: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnTickDataProvider")
+            .field("tick_spacing", &self.tick_spacing)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, G, N> FnTickDataProvider<I, G, N>
+where
+    I: TickIndex,
+    G: Fn(I) -> Result<Tick<I>, Error>,
+    N: Fn(I, bool, I) -> Result<(I, bool), Error>,
+{
+    /// Creates a tick data provider from closures
+    ///
+    /// ## Arguments
+    ///
+    /// * `tick_spacing`: The tick spacing of the pool
+    /// * `get_tick`: Returns the initialized tick at an index, see [`TickDataProvider::get_tick`]
+    /// * `next_initialized_tick_within_one_word`: Returns the next initialized tick within one
+    ///   word, see [`TickDataProvider::next_initialized_tick_within_one_word`]
+    #[inline]
+    pub fn new(
+        tick_spacing: I,
+        get_tick: G,
+        next_initialized_tick_within_one_word: N,
+    ) -> Result<Self, Error> {
+        if tick_spacing <= I::ZERO {
+            return Err(Error::InvalidTick(tick_spacing.to_i24()));
+        }
+        let (min_word_pos, _) = I::from_i24(MIN_TICK).compress(tick_spacing).position();
+        let (max_word_pos, _) = I::from_i24(MAX_TICK).compress(tick_spacing).position();
+        let num_words: i32 = (max_word_pos - min_word_pos)
+            .try_into()
+            .map_err(|_| Error::InvalidTick(tick_spacing.to_i24()))?;
+        Ok(Self {
+            tick_spacing,
+            get_tick,
+            next_initialized_tick_within_one_word,
+            min_word_pos,
+            words: (0..=num_words).map(|_| OnceCell::new()).collect(),
+        })
+    }
+}
+
+impl<I, G, N> TickDataProvider for FnTickDataProvider<I, G, N>
+where
+    I: TickIndex,
+    G: Fn(I) -> Result<Tick<I>, Error>,
+    N: Fn(I, bool, I) -> Result<(I, bool), Error>,
+{
+    type Index = I;
+
+    #[inline]
+    fn get_tick(&self, tick: I) -> Result<&Tick<I>, Error> {
+        if tick < I::from_i24(MIN_TICK) {
+            return Err(Error::InvalidTick(MIN_TICK));
+        }
+        if tick > I::from_i24(MAX_TICK) {
+            return Err(Error::InvalidTick(MAX_TICK));
+        }
+        if tick % self.tick_spacing != I::ZERO {
+            return Err(Error::InvalidTick(tick.to_i24()));
+        }
+        let (word_pos, bit_pos) = tick.compress(self.tick_spacing).position();
+        let index: i32 = (word_pos - self.min_word_pos)
+            .try_into()
+            .map_err(|_| Error::InvalidTick(tick.to_i24()))?;
+        let word =
+            self.words[index as usize].get_or_init(|| (0..256).map(|_| OnceCell::new()).collect());
+        word[bit_pos as usize].get_or_try_init(|| {
+            let tick_data = (self.get_tick)(tick)?;
+            if tick_data.index != tick {
+                return Err(Error::InvalidTick(tick_data.index.to_i24()));
+            }
+            Ok(tick_data)
+        })
+    }
+
+    #[inline]
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: I,
+        lte: bool,
+        tick_spacing: I,
+    ) -> Result<(I, bool), Error> {
+        let (next, initialized) =
+            (self.next_initialized_tick_within_one_word)(tick, lte, tick_spacing)?;
+        if (lte && next > tick) || (!lte && next <= tick) {
+            return Err(Error::InvalidTick(next.to_i24()));
+        }
+        Ok((next, initialized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_get_tick() {
+        let calls = Cell::new(0);
+        let provider = FnTickDataProvider::new(
+            10,
+            |tick| {
+                calls.set(calls.get() + 1);
+                Ok(Tick::new(tick, 1, 1))
+            },
+            |_, _, _| Err(Error::NoTickDataError),
+        )
+        .unwrap();
+        assert_eq!(provider.get_tick(-887270).unwrap().index, -887270);
+        assert_eq!(provider.get_tick(-887270).unwrap().index, -887270);
+        assert_eq!(provider.get_tick(887270).unwrap().index, 887270);
+        assert_eq!(calls.get(), 2);
+        assert!(matches!(provider.get_tick(5), Err(Error::InvalidTick(_))));
+        assert!(matches!(
+            provider.get_tick(887280),
+            Err(Error::InvalidTick(tick)) if tick == MAX_TICK
+        ));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_invalid_closure_output() {
+        assert!(matches!(
+            FnTickDataProvider::new(
+                0,
+                |tick| Ok(Tick::new(tick, 1, 1)),
+                |tick, _, _| Ok((tick, false))
+            ),
+            Err(Error::InvalidTick(_))
+        ));
+        let provider = FnTickDataProvider::new(
+            10,
+            |tick| Ok(Tick::new(tick + 10, 1, 1)),
+            |tick, _, _| Ok((tick + 10, false)),
+        )
+        .unwrap();
+        assert!(matches!(provider.get_tick(0), Err(Error::InvalidTick(_))));
+        assert!(provider
+            .next_initialized_tick_within_one_word(0, false, 10)
+            .is_ok());
+        assert!(matches!(
+            provider.next_initialized_tick_within_one_word(0, true, 10),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_output_amount() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let ticks = pool.tick_data_provider.clone();
+        let words = pool.tick_data_provider.clone();
+        let fn_pool = Pool::new_with_tick_data_provider(
+            pool.token0.clone(),
+            pool.token1.clone(),
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            FnTickDataProvider::new(
+                pool.tick_spacing(),
+                move |tick| ticks.get_tick(tick).copied(),
+                move |tick, lte, tick_spacing| {
+                    words.next_initialized_tick_within_one_word(tick, lte, tick_spacing)
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        for amount in [100, 10_000, 1_000_000] {
+            let input_amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount).unwrap();
+            assert_eq!(
+                fn_pool
+                    .get_output_amount(&input_amount, None)
+                    .unwrap()
+                    .quotient(),
+                pool.get_output_amount(&input_amount, None)
+                    .unwrap()
+                    .quotient()
+            );
+        }
+    }
+}
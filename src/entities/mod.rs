@@ -1,14 +1,24 @@
+pub mod fn_tick_data_provider;
 pub mod pool;
+pub mod pool_price_cache;
+pub mod portfolio;
 pub mod position;
+pub mod position_builder;
 pub mod route;
+pub mod sqrt_ratio_cache;
 pub mod tick;
 pub mod tick_data_provider;
 pub mod tick_list_data_provider;
 pub mod trade;
 
-pub use pool::Pool;
-pub use position::{MintAmounts, Position};
+pub use fn_tick_data_provider::FnTickDataProvider;
+pub use pool::{ExactOutputQuote, Pool, PoolSnapshot};
+pub use pool_price_cache::PoolPriceCache;
+pub use portfolio::{Portfolio, PortfolioPosition};
+pub use position::{MintAmounts, Position, RangeDistance, RangeStatus};
+pub use position_builder::PositionBuilder;
 pub use route::Route;
+pub use sqrt_ratio_cache::SqrtRatioCache;
 pub use tick::{Tick, TickIndex};
 pub use tick_data_provider::*;
 pub use tick_list_data_provider::TickListDataProvider;
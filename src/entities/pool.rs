@@ -1,23 +1,107 @@
-use crate::prelude::{Error, *};
-use alloy_primitives::{ChainId, B256, I256, U160};
+use crate::{
+    prelude::{Error, *},
+    utils::swap_math::_v3_swap,
+};
+use alloc::{sync::Arc, vec::Vec};
+use alloy_primitives::{aliases::I24, ChainId, B256, I256, U160, U256};
 use once_cell::sync::Lazy;
 use uniswap_sdk_core::prelude::*;
 
 static _Q192: Lazy<BigUint> = Lazy::new(|| Q192.to_big_uint());
 
+/// The exclusive upper bound of the tick spacing of a fee tier enabled by the factory, see
+/// [`UniswapV3Factory`](https://github.com/Uniswap/v3-core/blob/main/contracts/UniswapV3Factory.sol#L61).
+const MAX_TICK_SPACING: I24 = I24::from_limbs([16384]);
+
 /// Represents a V3 pool
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "TP: serde::Serialize, TP::Index: serde::Serialize",
+        deserialize = "TP: serde::Deserialize<'de>, TP::Index: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Pool<TP = NoTickDataProvider>
 where
     TP: TickDataProvider,
 {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_helpers::token"))]
     pub token0: Token,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_helpers::token"))]
     pub token1: Token,
     pub fee: FeeAmount,
     pub sqrt_ratio_x96: U160,
     pub liquidity: u128,
     pub tick_current: TP::Index,
     pub tick_data_provider: TP,
+    /// The tick spacing of the fee tier if the factory enabled it with a tick spacing other than
+    /// the default of the fee, e.g. on a fork, see [`Pool::with_tick_spacing`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub custom_tick_spacing: Option<I24>,
+}
+
+/// The result of quoting the input amount for an exact output on a single pool, see
+/// [`Pool::get_input_amount_for_exact_output`].
+#[derive(Clone, Debug)]
+pub struct ExactOutputQuote {
+    /// The input amount required for the filled output amount
+    pub input_amount: CurrencyAmount<Token>,
+    /// The output amount filled, less than the requested amount on a partial fill
+    pub output_amount: CurrencyAmount<Token>,
+    /// The Q64.96 sqrt price of the pool after the swap
+    pub sqrt_price_x96_after: U160,
+    /// Whether the swap ran out of liquidity or hit the price limit before filling the requested
+    /// output amount
+    pub partial_fill: bool,
+}
+
+/// The result of a swap simulated on a pool without cloning it or its tick data provider, see
+/// [`Pool::simulate_swap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapResult<'a, I = i32> {
+    /// The input token, borrowed from the pool
+    pub input_token: &'a Token,
+    /// The output token, borrowed from the pool
+    pub output_token: &'a Token,
+    /// The raw input amount spent, including the transfer fee of the input token
+    pub amount_in: BigInt,
+    /// The raw output amount received, net of the transfer fee of the output token
+    pub amount_out: BigInt,
+    /// The raw fee paid to the liquidity providers of the pool, in the input token
+    pub fee_amount: BigInt,
+    /// The state of the pool after the swap, which can be applied with [`Pool::restore`]
+    pub state_after: PoolSnapshot<I>,
+    /// The initialized ticks crossed by the swap, in order
+    pub ticks_crossed: Vec<I>,
+    /// Whether the swap ran out of liquidity or hit the price limit before the amount specified
+    /// was fully swapped
+    pub partial_fill: bool,
+}
+
+impl<I> SwapResult<'_, I> {
+    /// Returns the input amount spent
+    #[inline]
+    pub fn input_amount(&self) -> Result<CurrencyAmount<Token>, Error> {
+        CurrencyAmount::from_raw_amount(self.input_token.clone(), self.amount_in.clone())
+            .map_err(Error::Core)
+    }
+
+    /// Returns the output amount received
+    #[inline]
+    pub fn output_amount(&self) -> Result<CurrencyAmount<Token>, Error> {
+        CurrencyAmount::from_raw_amount(self.output_token.clone(), self.amount_out.clone())
+            .map_err(Error::Core)
+    }
+}
+
+/// The mutable swap state of a pool, see [`Pool::snapshot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolSnapshot<I = i32> {
+    pub sqrt_ratio_x96: U160,
+    pub liquidity: u128,
+    pub tick_current: I,
 }
 
 impl<TP> PartialEq for Pool<TP>
@@ -32,6 +116,7 @@ where
             && self.sqrt_ratio_x96 == other.sqrt_ratio_x96
             && self.liquidity == other.liquidity
             && self.tick_current == other.tick_current
+            && self.custom_tick_spacing == other.custom_tick_spacing
     }
 }
 
@@ -75,7 +160,8 @@ impl Pool {
     /// * `init_code_hash_manual_override`: Override the init code hash used to compute the pool
     ///   address if necessary
     /// * `factory_address_override`: Override the factory address used to compute the pool address
-    ///   if necessary
+    ///   if necessary, by default [`FACTORY_ADDRESS`]. The factory of other chains can be looked up
+    ///   with [`get_chain_addresses`]
     ///
     /// ## Returns
     ///
@@ -137,7 +223,56 @@ impl<TP: TickDataProvider> Pool<TP> {
 
     #[inline]
     pub fn tick_spacing(&self) -> TP::Index {
-        TP::Index::from_i24(self.fee.tick_spacing())
+        TP::Index::from_i24(
+            self.custom_tick_spacing
+                .unwrap_or_else(|| self.fee.tick_spacing()),
+        )
+    }
+
+    /// Sets the tick spacing of the fee tier, for a pool whose factory enabled its fee with a tick
+    /// spacing other than the default, e.g. on a fork
+    ///
+    /// ## Arguments
+    ///
+    /// * `tick_spacing`: The tick spacing of the fee tier
+    #[inline]
+    pub fn with_tick_spacing(mut self, tick_spacing: I24) -> Result<Self, Error> {
+        if tick_spacing <= I24::ZERO || tick_spacing >= MAX_TICK_SPACING {
+            return Err(Error::InvalidTick(tick_spacing));
+        }
+        self.custom_tick_spacing = if tick_spacing == self.fee.tick_spacing() {
+            None
+        } else {
+            Some(tick_spacing)
+        };
+        Ok(self)
+    }
+
+    /// Returns a copy of the state mutated by swaps, i.e. the price, the in-range liquidity and the
+    /// current tick, without cloning the tokens or the tick data provider.
+    ///
+    /// Use with [`Self::restore`] to simulate many hypothetical swaps, e.g. with
+    /// [`Self::get_output_amount_mut`], from the same base state.
+    #[inline]
+    #[must_use]
+    pub const fn snapshot(&self) -> PoolSnapshot<TP::Index> {
+        PoolSnapshot {
+            sqrt_ratio_x96: self.sqrt_ratio_x96,
+            liquidity: self.liquidity,
+            tick_current: self.tick_current,
+        }
+    }
+
+    /// Restores the state mutated by swaps from a snapshot taken by [`Self::snapshot`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `snapshot`: The snapshot to restore
+    #[inline]
+    pub const fn restore(&mut self, snapshot: PoolSnapshot<TP::Index>) {
+        self.sqrt_ratio_x96 = snapshot.sqrt_ratio_x96;
+        self.liquidity = snapshot.liquidity;
+        self.tick_current = snapshot.tick_current;
     }
 
     /// Returns true if the token is either token0 or token1
@@ -230,10 +365,31 @@ impl<TP: TickDataProvider> Pool<TP> {
             liquidity,
             tick_current: TP::Index::from_i24(sqrt_ratio_x96.get_tick_at_sqrt_ratio()?),
             tick_data_provider,
+            custom_tick_spacing: None,
         })
     }
 
-    fn _swap(
+    /// Erases the type of the tick data provider, so that pools with different providers can be
+    /// stored together, see [`DynTickDataProvider`].
+    #[inline]
+    #[must_use]
+    pub fn into_dyn(self) -> Pool<DynTickDataProvider<TP::Index>>
+    where
+        TP: Send + Sync + 'static,
+    {
+        Pool {
+            token0: self.token0,
+            token1: self.token1,
+            fee: self.fee,
+            sqrt_ratio_x96: self.sqrt_ratio_x96,
+            liquidity: self.liquidity,
+            tick_current: self.tick_current,
+            tick_data_provider: Arc::new(self.tick_data_provider),
+            custom_tick_spacing: self.custom_tick_spacing,
+        }
+    }
+
+    pub(crate) fn _swap(
         &self,
         zero_for_one: bool,
         amount_specified: I256,
@@ -251,10 +407,152 @@ impl<TP: TickDataProvider> Pool<TP> {
             sqrt_price_limit_x96,
         )
     }
+
+    /// Simulates a swap and returns each step of it, i.e. the ticks crossed, the liquidity after
+    /// crossing, the fee accrued per step and the final state of the swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `zero_for_one`: Whether the amount in is token0 or token1
+    /// * `amount_specified`: The amount of the swap, which implicitly configures the swap as exact
+    ///   input (positive), or exact output (negative)
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit. If zero for one, the price cannot be
+    ///   less than this value after the swap. If one for zero, the price cannot be greater than
+    ///   this value after the swap
+    #[inline]
+    pub fn simulate_swap_with_trace(
+        &self,
+        zero_for_one: bool,
+        amount_specified: I256,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<SwapTrace<TP::Index>, Error> {
+        v3_swap_with_trace(
+            self.fee.into(),
+            self.sqrt_ratio_x96,
+            self.tick_current,
+            self.liquidity,
+            self.tick_spacing(),
+            &self.tick_data_provider,
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+        )
+    }
 }
 
-impl<TP: Clone + TickDataProvider> Pool<TP> {
-    /// Given an input amount of a token, return the computed output amount
+impl<TP: TickDataProvider> Pool<TP> {
+    /// Simulates a swap of an exact input or output amount and returns its amounts, the state of
+    /// the pool after it and the initialized ticks it crossed, borrowing the pool instead of
+    /// cloning it or its tick data provider.
+    ///
+    /// ## Notes
+    ///
+    /// Unlike [`Pool::get_output_amount`] and [`Pool::get_input_amount`], this doesn't fail when
+    /// the pool runs out of liquidity or hits the price limit, but reports a partial fill instead.
+    /// The amounts account for the transfer fees of the tokens like
+    /// [`Pool::get_output_amount_with_transfer_fees`]. The tokens are only cloned by
+    /// [`SwapResult::input_amount`] and [`SwapResult::output_amount`], and the state after the
+    /// swap can be applied with [`Pool::restore`] once the result is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `amount`: The input amount for an exact input swap, or the output amount for an exact
+    ///   output swap
+    /// * `trade_type`: Whether the swap is exact input or exact output
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit. If zero for one, the price cannot be
+    ///   less than this value after the swap. If one for zero, the price cannot be greater than
+    ///   this value after the swap
+    #[inline]
+    pub fn simulate_swap(
+        &self,
+        amount: &CurrencyAmount<impl BaseCurrency>,
+        trade_type: TradeType,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<SwapResult<'_, TP::Index>, Error> {
+        if !self.involves_token(&amount.currency) {
+            return Err(Error::InvalidToken);
+        }
+
+        let exact_input = trade_type == TradeType::ExactInput;
+        let zero_for_one = amount.currency.equals(&self.token0) == exact_input;
+        let (input_token, output_token) = if zero_for_one {
+            (&self.token0, &self.token1)
+        } else {
+            (&self.token1, &self.token0)
+        };
+        let amount_specified = if exact_input {
+            I256::from_big_int(after_transfer_fee(
+                amount.quotient(),
+                input_token.sell_fee_bps.as_ref(),
+            ))
+        } else {
+            -I256::from_big_int(before_transfer_fee(
+                amount.quotient(),
+                output_token.buy_fee_bps.as_ref(),
+            )?)
+        };
+
+        let mut ticks_crossed = Vec::new();
+        let mut fee_amount = U256::ZERO;
+        let state = _v3_swap(
+            self.fee.into(),
+            self.sqrt_ratio_x96,
+            self.tick_current,
+            self.liquidity,
+            self.tick_spacing(),
+            &self.tick_data_provider,
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+            |step| {
+                fee_amount += step.fee_amount;
+                if step.crossed {
+                    ticks_crossed.push(step.tick_next);
+                }
+            },
+        )?;
+
+        let partial_fill = !state.amount_specified_remaining.is_zero();
+        // the amount specified less the remaining amount is the amount swapped in the pool
+        let swapped = (amount_specified - state.amount_specified_remaining).to_big_int();
+        let calculated = state.amount_calculated.to_big_int();
+        let (amount_in, amount_out) = if !exact_input {
+            (
+                before_transfer_fee(calculated, input_token.sell_fee_bps.as_ref())?,
+                -swapped,
+            )
+        } else if partial_fill {
+            (
+                before_transfer_fee(swapped, input_token.sell_fee_bps.as_ref())?,
+                -calculated,
+            )
+        } else {
+            (amount.quotient(), -calculated)
+        };
+        Ok(SwapResult {
+            input_token,
+            output_token,
+            amount_in,
+            amount_out: after_transfer_fee(amount_out, output_token.buy_fee_bps.as_ref()),
+            fee_amount: fee_amount.to_big_int(),
+            state_after: PoolSnapshot {
+                sqrt_ratio_x96: state.sqrt_price_x96,
+                liquidity: state.liquidity,
+                tick_current: state.tick_current,
+            },
+            ticks_crossed,
+            partial_fill,
+        })
+    }
+
+    /// Given an input amount of a token, return the computed output amount net of the transfer
+    /// fees of the tokens
+    ///
+    /// ## Notes
+    ///
+    /// Unlike [`Pool::get_output_amount`], the `sell_fee_bps` of the input token is deducted from
+    /// the amount received by the pool and the `buy_fee_bps` of the output token from the amount
+    /// received from the pool, for tokens that charge a fee on transfer.
     ///
     /// ## Arguments
     ///
@@ -263,10 +561,110 @@ impl<TP: Clone + TickDataProvider> Pool<TP> {
     ///
     /// returns: The output amount
     #[inline]
-    pub fn get_output_amount(
+    pub fn get_output_amount_with_transfer_fees(
         &self,
         input_amount: &CurrencyAmount<impl BaseCurrency>,
         sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<CurrencyAmount<Token>, Error> {
+        let result =
+            self.simulate_swap(input_amount, TradeType::ExactInput, sqrt_price_limit_x96)?;
+        if result.partial_fill && sqrt_price_limit_x96.is_none() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        result.output_amount()
+    }
+
+    /// Given a desired output amount of a token, return the computed input amount covering the
+    /// transfer fees of the tokens, see [`Pool::get_output_amount_with_transfer_fees`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `output_amount`: the output amount for which to quote the input amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit. If zero for one, the price cannot be
+    ///   less than this value after the swap. If one for zero, the price cannot be greater than
+    ///   this value after the swap
+    ///
+    /// returns: The input amount
+    #[inline]
+    pub fn get_input_amount_with_transfer_fees(
+        &self,
+        output_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<CurrencyAmount<Token>, Error> {
+        let result =
+            self.simulate_swap(output_amount, TradeType::ExactOutput, sqrt_price_limit_x96)?;
+        if result.partial_fill && sqrt_price_limit_x96.is_none() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        result.input_amount()
+    }
+
+    /// Given a desired output amount of a token, return the computed input amount along with the
+    /// output amount actually filled.
+    ///
+    /// Unlike [`Pool::get_input_amount`], this doesn't fail when the pool runs out of liquidity
+    /// before the output amount is filled, but reports a partial fill instead. The quote is taken
+    /// from [`Pool::simulate_swap`], so it accounts for the transfer fees of the tokens.
+    ///
+    /// ## Arguments
+    ///
+    /// * `output_amount`: the output amount for which to quote the input amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit. If zero for one, the price cannot be
+    ///   less than this value after the swap. If one for zero, the price cannot be greater than
+    ///   this value after the swap
+    ///
+    /// ## Returns
+    ///
+    /// An [`ExactOutputQuote`] with the input amount, the filled output amount, the sqrt price after
+    /// the swap and whether the fill is partial
+    #[inline]
+    pub fn get_input_amount_for_exact_output(
+        &self,
+        output_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<ExactOutputQuote, Error> {
+        let result =
+            self.simulate_swap(output_amount, TradeType::ExactOutput, sqrt_price_limit_x96)?;
+        Ok(ExactOutputQuote {
+            input_amount: result.input_amount()?,
+            output_amount: result.output_amount()?,
+            sqrt_price_x96_after: result.state_after.sqrt_ratio_x96,
+            partial_fill: result.partial_fill,
+        })
+    }
+
+    /// Given an input amount of a token, return the computed output amount along with the
+    /// estimated gas cost of the swap derived from the number of initialized ticks crossed
+    ///
+    /// ## Arguments
+    ///
+    /// * `input_amount`: The input amount for which to quote the output amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit
+    ///
+    /// returns: The output amount and the estimated gas cost
+    #[inline]
+    pub fn get_output_amount_with_gas_estimate(
+        &self,
+        input_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<(CurrencyAmount<Token>, u64), Error> {
+        let mut initialized_ticks_crossed = 0;
+        let output_amount =
+            self._get_output_amount(input_amount, sqrt_price_limit_x96, |step| {
+                initialized_ticks_crossed += u64::from(step.crossed);
+            })?;
+        let gas_estimate =
+            SwapGasModel::for_chain(self.chain_id()).estimate(1, initialized_ticks_crossed);
+        Ok((output_amount, gas_estimate))
+    }
+
+    /// Computes the output amount of an exact input swap like [`Pool::get_output_amount`], passing
+    /// each step of the swap to `on_step`
+    fn _get_output_amount(
+        &self,
+        input_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+        on_step: impl FnMut(SwapStep<TP::Index>),
     ) -> Result<CurrencyAmount<Token>, Error> {
         if !self.involves_token(&input_amount.currency) {
             return Err(Error::InvalidToken);
@@ -278,10 +676,17 @@ impl<TP: Clone + TickDataProvider> Pool<TP> {
             amount_specified_remaining,
             amount_calculated: output_amount,
             ..
-        } = self._swap(
+        } = _v3_swap(
+            self.fee.into(),
+            self.sqrt_ratio_x96,
+            self.tick_current,
+            self.liquidity,
+            self.tick_spacing(),
+            &self.tick_data_provider,
             zero_for_one,
             I256::from_big_int(input_amount.quotient()),
             sqrt_price_limit_x96,
+            on_step,
         )?;
 
         if !amount_specified_remaining.is_zero() && sqrt_price_limit_x96.is_none() {
@@ -296,6 +701,47 @@ impl<TP: Clone + TickDataProvider> Pool<TP> {
         CurrencyAmount::from_raw_amount(output_token.clone(), -output_amount.to_big_int())
             .map_err(Error::Core)
     }
+}
+
+/// The amount received from a transfer of a token with a fee in basis points, rounded down
+pub(crate) fn after_transfer_fee(amount: BigInt, fee_bps: Option<&BigUint>) -> BigInt {
+    match fee_bps {
+        Some(fee_bps) => amount * (BigInt::from(10000) - BigInt::from(fee_bps.clone())) / 10000,
+        None => amount,
+    }
+}
+
+/// The amount to transfer of a token with a fee in basis points to receive an amount, rounded up
+fn before_transfer_fee(amount: BigInt, fee_bps: Option<&BigUint>) -> Result<BigInt, Error> {
+    match fee_bps {
+        Some(fee_bps) => {
+            let complement = BigInt::from(10000) - BigInt::from(fee_bps.clone());
+            if complement <= BigInt::ZERO {
+                return Err(Error::InvalidFee);
+            }
+            Ok((amount * 10000 + &complement - 1) / complement)
+        }
+        None => Ok(amount),
+    }
+}
+
+impl<TP: Clone + TickDataProvider> Pool<TP> {
+    /// Given an input amount of a token, return the computed output amount
+    ///
+    /// ## Arguments
+    ///
+    /// * `input_amount`: The input amount for which to quote the output amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit
+    ///
+    /// returns: The output amount
+    #[inline]
+    pub fn get_output_amount(
+        &self,
+        input_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+    ) -> Result<CurrencyAmount<Token>, Error> {
+        self._get_output_amount(input_amount, sqrt_price_limit_x96, |_| {})
+    }
 
     /// Given an input amount of a token, return the computed output amount, updating the pool state
     ///
@@ -450,7 +896,8 @@ impl<TP: Clone + TickDataProvider> Pool<TP> {
 mod tests {
     use super::*;
     use crate::tests::*;
-    use alloy_primitives::address;
+    use alloy_primitives::{address, aliases::U24, U256};
+    use uniswap_sdk_core::token;
 
     const ONE_ETHER: U160 = U160::from_limbs([10_u64.pow(18), 0, 0]);
 
@@ -496,6 +943,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_tick_spacing() {
+        let pool = Pool::new(
+            USDC.clone(),
+            DAI.clone(),
+            FeeAmount::HIGH,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        let custom = pool
+            .clone()
+            .with_tick_spacing(I24::from_limbs([100]))
+            .unwrap();
+        assert_eq!(custom.tick_spacing(), 100);
+        assert_eq!(U24::from(custom.fee), U24::from_limbs([10000]));
+        assert_eq!(nearest_usable_tick(150, custom.tick_spacing()), 200);
+        assert_eq!(
+            pool.clone()
+                .with_tick_spacing(I24::from_limbs([200]))
+                .unwrap(),
+            pool
+        );
+        for tick_spacing in [I24::ZERO, I24::MINUS_ONE, MAX_TICK_SPACING] {
+            assert!(matches!(
+                pool.clone().with_tick_spacing(tick_spacing),
+                Err(Error::InvalidTick(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn get_address_on_zksync() {
+        let token_a = token!(324, "1d17CBcF0D6D143135aE902365D2E5e2A16538D4", 6);
+        let token_b = token!(324, "5AEa5775959fBC2557Cc8789bC1bf90A239D9a91", 18);
+        let result = Pool::get_address(
+            &token_a,
+            &token_b,
+            FeeAmount::LOW,
+            None,
+            Some(ZKSYNC_ADDRESSES.factory),
+        );
+        assert_eq!(
+            result,
+            compute_pool_address(
+                ZKSYNC_ADDRESSES.factory,
+                token_a.address(),
+                token_b.address(),
+                FeeAmount::LOW,
+                None,
+                Some(324),
+            )
+        );
+        // not the standard `CREATE2` address
+        assert_ne!(
+            result,
+            compute_pool_address(
+                ZKSYNC_ADDRESSES.factory,
+                token_a.address(),
+                token_b.address(),
+                FeeAmount::LOW,
+                None,
+                None,
+            )
+        );
+    }
+
     #[test]
     fn get_address_matches_an_example() {
         let result = Pool::get_address(&USDC, &DAI, FeeAmount::LOW, None, None);
@@ -716,6 +1230,207 @@ mod tests {
             assert_eq!(output_amount.quotient(), 98.into());
         }
 
+        #[test]
+        fn get_amounts_with_transfer_fees() {
+            // 1% fee on transfers out of the pool and 2% on transfers into the pool
+            let taxed = Token::new(
+                1,
+                TOKEN0.address(),
+                18,
+                None,
+                None,
+                Some(BigUint::from(100_u32)),
+                Some(BigUint::from(200_u32)),
+            );
+            let pool = make_pool(taxed.clone(), TOKEN1.clone());
+            let untaxed = make_pool(TOKEN0.clone(), TOKEN1.clone());
+
+            // the fees are opt-in
+            let amount_in = CurrencyAmount::from_raw_amount(taxed.clone(), 10000).unwrap();
+            assert_eq!(
+                pool.get_output_amount(&amount_in, None).unwrap().quotient(),
+                untaxed
+                    .get_output_amount(
+                        &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                        None
+                    )
+                    .unwrap()
+                    .quotient()
+            );
+
+            // the pool receives 2% less of the taxed token
+            let output_amount = pool
+                .get_output_amount_with_transfer_fees(&amount_in, None)
+                .unwrap();
+            let expected = untaxed
+                .get_output_amount(
+                    &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 9800).unwrap(),
+                    None,
+                )
+                .unwrap();
+            assert_eq!(output_amount.quotient(), expected.quotient());
+
+            // the recipient receives 1% less of the taxed token
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 10000).unwrap();
+            let output_amount = pool
+                .get_output_amount_with_transfer_fees(&amount_in, None)
+                .unwrap();
+            let expected = untaxed.get_output_amount(&amount_in, None).unwrap();
+            assert_eq!(output_amount.quotient(), expected.quotient() * 99 / 100);
+
+            // the input of an exact output covers the fee
+            let input_amount = pool
+                .get_input_amount_with_transfer_fees(
+                    &CurrencyAmount::from_raw_amount(taxed, 9900).unwrap(),
+                    None,
+                )
+                .unwrap();
+            let expected = untaxed
+                .get_input_amount(
+                    &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                    None,
+                )
+                .unwrap();
+            assert_eq!(input_amount.quotient(), expected.quotient());
+
+            // no amount can be received of a token with a 100% transfer fee
+            let confiscatory = Token::new(
+                1,
+                TOKEN0.address(),
+                18,
+                None,
+                None,
+                Some(BigUint::from(10000_u32)),
+                None,
+            );
+            let pool = make_pool(confiscatory.clone(), TOKEN1.clone());
+            assert!(matches!(
+                pool.get_input_amount_with_transfer_fees(
+                    &CurrencyAmount::from_raw_amount(confiscatory, 100).unwrap(),
+                    None,
+                ),
+                Err(Error::InvalidFee)
+            ));
+        }
+
+        #[test]
+        fn get_output_amount_with_gas_estimate() {
+            let (output_amount, gas_estimate) = POOL
+                .get_output_amount_with_gas_estimate(
+                    &CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap(),
+                    None,
+                )
+                .unwrap();
+            assert_eq!(output_amount.quotient(), 98.into());
+            assert_eq!(gas_estimate, 82000);
+        }
+
+        #[test]
+        fn snapshot_and_restore() {
+            let mut pool = POOL.clone();
+            let snapshot = pool.snapshot();
+            let amount = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+            let first = pool.get_output_amount_mut(&amount, None).unwrap();
+            assert_ne!(pool.snapshot(), snapshot);
+            pool.restore(snapshot);
+            assert_eq!(pool, *POOL);
+            let second = pool.get_output_amount_mut(&amount, None).unwrap();
+            assert_eq!(first, second);
+        }
+
+        #[test]
+        fn simulate_swap_with_trace() {
+            let trace = POOL
+                .simulate_swap_with_trace(false, I256::from_raw(U256::from(100)), None)
+                .unwrap();
+            let SwapTrace { steps, state } = &trace;
+            assert_eq!(steps.len(), 1);
+            let step = steps[0];
+            assert!(!step.crossed);
+            assert_eq!(step.sqrt_price_start_x96, POOL.sqrt_ratio_x96);
+            assert_eq!(step.sqrt_price_x96, state.sqrt_price_x96);
+            assert_eq!(step.liquidity, POOL.liquidity);
+            assert_eq!(step.amount_in + step.fee_amount, U256::from(100));
+            assert_eq!(step.amount_out, U256::from(98));
+            assert_eq!(trace.fee_amount(), step.fee_amount);
+        }
+
+        #[test]
+        fn simulate_swap() {
+            let input_amount = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+            let result = POOL
+                .simulate_swap(&input_amount, TradeType::ExactInput, None)
+                .unwrap();
+            assert_eq!(result.output_token, &*DAI);
+            assert_eq!(result.input_amount().unwrap(), input_amount);
+            assert_eq!(
+                result.output_amount().unwrap(),
+                POOL.get_output_amount(&input_amount, None).unwrap()
+            );
+            assert!(result.ticks_crossed.is_empty());
+            assert!(!result.partial_fill);
+            let mut pool = POOL.clone();
+            pool.get_output_amount_mut(&input_amount, None).unwrap();
+            assert_eq!(result.state_after.sqrt_ratio_x96, pool.sqrt_ratio_x96);
+            assert_eq!(result.state_after.liquidity, pool.liquidity);
+
+            let output_amount = CurrencyAmount::from_raw_amount(DAI.clone(), 98).unwrap();
+            let result = POOL
+                .simulate_swap(&output_amount, TradeType::ExactOutput, None)
+                .unwrap();
+            assert_eq!(result.output_amount().unwrap(), output_amount);
+            assert_eq!(
+                result.input_amount().unwrap(),
+                POOL.get_input_amount(&output_amount, None).unwrap()
+            );
+        }
+
+        #[test]
+        fn simulate_swap_crossing_ticks() {
+            let pool = Pool::new_with_tick_data_provider(
+                USDC.clone(),
+                DAI.clone(),
+                FeeAmount::LOW,
+                encode_sqrt_ratio_x96(1, 1),
+                ONE_ETHER.into_limbs()[0] as u128,
+                TickListDataProvider::new(
+                    vec![
+                        Tick::new(
+                            -600,
+                            ONE_ETHER.into_limbs()[0] as u128,
+                            ONE_ETHER.into_limbs()[0] as i128,
+                        ),
+                        Tick::new(
+                            600,
+                            ONE_ETHER.into_limbs()[0] as u128,
+                            -(ONE_ETHER.into_limbs()[0] as i128),
+                        ),
+                    ],
+                    FeeAmount::LOW.tick_spacing().as_i32(),
+                ),
+            )
+            .unwrap();
+            let sqrt_price_limit_x96 = get_sqrt_ratio_at_tick((-1200_i32).to_i24()).unwrap();
+            let input_amount =
+                CurrencyAmount::from_raw_amount(USDC.clone(), ONE_ETHER.into_limbs()[0]).unwrap();
+            let result = pool
+                .simulate_swap(
+                    &input_amount,
+                    TradeType::ExactInput,
+                    Some(sqrt_price_limit_x96),
+                )
+                .unwrap();
+            assert_eq!(result.ticks_crossed, vec![-600]);
+            assert!(result.partial_fill);
+            assert!(result.amount_in < input_amount.quotient());
+            assert_eq!(result.state_after.sqrt_ratio_x96, sqrt_price_limit_x96);
+            assert_eq!(result.state_after.liquidity, 0);
+            let state_after = result.state_after;
+            let mut pool = pool;
+            pool.restore(state_after);
+            assert_eq!(pool.liquidity, 0);
+        }
+
         #[test]
         fn get_input_amount_usdc_to_dai() {
             let input_amount = POOL
@@ -728,6 +1443,66 @@ mod tests {
             assert_eq!(input_amount.quotient(), 100.into());
         }
 
+        #[test]
+        fn get_input_amount_for_exact_output_full_fill() {
+            let quote = POOL
+                .get_input_amount_for_exact_output(
+                    &CurrencyAmount::from_raw_amount(DAI.clone(), 98).unwrap(),
+                    None,
+                )
+                .unwrap();
+            assert!(!quote.partial_fill);
+            assert!(quote.input_amount.currency.equals(&USDC.clone()));
+            assert_eq!(quote.input_amount.quotient(), 100.into());
+            assert_eq!(quote.output_amount.quotient(), 98.into());
+        }
+
+        #[test]
+        fn get_input_amount_for_exact_output_partial_fill() {
+            let requested = BigInt::from(ONE_ETHER.into_limbs()[0]) * 2;
+            let quote = POOL
+                .get_input_amount_for_exact_output(
+                    &CurrencyAmount::from_raw_amount(DAI.clone(), requested.clone()).unwrap(),
+                    None,
+                )
+                .unwrap();
+            assert!(quote.partial_fill);
+            assert!(quote.output_amount.currency.equals(&DAI.clone()));
+            assert!(quote.output_amount.quotient() < requested);
+            assert!(quote.output_amount.quotient() > BigInt::ZERO);
+            assert!(POOL
+                .get_input_amount(
+                    &CurrencyAmount::from_raw_amount(DAI.clone(), requested).unwrap(),
+                    None,
+                )
+                .is_err());
+        }
+
+        #[test]
+        fn get_output_amount_dyn() {
+            // a provider behind another pointer
+            let tick_data_provider: DynTickDataProvider =
+                Arc::new(Arc::new(POOL.tick_data_provider.clone()));
+            let pools = [
+                POOL.clone().into_dyn(),
+                Pool::new_with_tick_data_provider(
+                    POOL.token0.clone(),
+                    POOL.token1.clone(),
+                    POOL.fee,
+                    POOL.sqrt_ratio_x96,
+                    POOL.liquidity,
+                    tick_data_provider,
+                )
+                .unwrap(),
+            ];
+            let input_amount = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+            for pool in &pools {
+                let output_amount = pool.get_output_amount(&input_amount, None).unwrap();
+                assert!(output_amount.currency.equals(&DAI.clone()));
+                assert_eq!(output_amount.quotient(), 98.into());
+            }
+        }
+
         #[test]
         fn get_input_amount_dai_to_usdc() {
             let input_amount = POOL
@@ -0,0 +1,143 @@
+use crate::prelude::{Error, *};
+use alloy_primitives::U160;
+
+/// Caches the mid prices of a pool until its sqrt price changes, so that routing loops reading the
+/// mid prices many times per block neither recompute the ratios nor clone the tokens.
+///
+/// The cache is kept beside the pool instead of in it, so the pool can still be read through a
+/// shared reference. It is keyed on the sqrt price and the tokens of the pool, so reading another
+/// pool through the same cache recomputes the prices.
+#[derive(Clone, Debug, Default)]
+pub struct PoolPriceCache {
+    mid_prices: Option<(U160, Price<Token, Token>, Price<Token, Token>)>,
+}
+
+impl PoolPriceCache {
+    /// Creates an empty cache
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { mid_prices: None }
+    }
+
+    /// Evicts the cached prices
+    #[inline]
+    pub fn clear(&mut self) {
+        self.mid_prices = None;
+    }
+
+    /// Returns the mid prices of the pool in terms of token0 and token1, computed once per sqrt
+    /// price
+    fn mid_prices<TP: TickDataProvider>(
+        &mut self,
+        pool: &Pool<TP>,
+    ) -> &(U160, Price<Token, Token>, Price<Token, Token>) {
+        if !matches!(
+            &self.mid_prices,
+            Some((sqrt_ratio_x96, token0_price, _))
+                if *sqrt_ratio_x96 == pool.sqrt_ratio_x96
+                    && token0_price.base_currency.equals(&pool.token0)
+                    && token0_price.quote_currency.equals(&pool.token1)
+        ) {
+            let token0_price = pool.token0_price();
+            let token1_price = token0_price.invert();
+            self.mid_prices = Some((pool.sqrt_ratio_x96, token0_price, token1_price));
+        }
+        self.mid_prices.as_ref().unwrap()
+    }
+
+    /// Returns the current mid price of the pool in terms of token0 like [`Pool::token0_price`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to price
+    #[inline]
+    pub fn token0_price<TP: TickDataProvider>(&mut self, pool: &Pool<TP>) -> &Price<Token, Token> {
+        &self.mid_prices(pool).1
+    }
+
+    /// Returns the current mid price of the pool in terms of token1 like [`Pool::token1_price`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to price
+    #[inline]
+    pub fn token1_price<TP: TickDataProvider>(&mut self, pool: &Pool<TP>) -> &Price<Token, Token> {
+        &self.mid_prices(pool).2
+    }
+
+    /// Returns the price of the given token in terms of the other token like [`Pool::price_of`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to price
+    /// * `token`: The token to return price of
+    #[inline]
+    pub fn price_of<TP: TickDataProvider>(
+        &mut self,
+        pool: &Pool<TP>,
+        token: &Token,
+    ) -> Result<&Price<Token, Token>, Error> {
+        if pool.token0.equals(token) {
+            Ok(self.token0_price(pool))
+        } else if pool.token1.equals(token) {
+            Ok(self.token1_price(pool))
+        } else {
+            Err(Error::InvalidToken)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn mid_prices_are_cached_until_the_sqrt_price_changes() {
+        let mut pool = Pool::new(
+            USDC.clone(),
+            DAI.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(101e6 as u128, 100e18 as u128),
+            0,
+        )
+        .unwrap();
+        let mut cache = PoolPriceCache::new();
+        assert_eq!(*cache.token0_price(&pool), pool.token0_price());
+        assert_eq!(*cache.token1_price(&pool), pool.token1_price());
+        assert_eq!(
+            *cache.price_of(&pool, &DAI).unwrap(),
+            pool.price_of(&DAI).unwrap()
+        );
+        assert!(cache.price_of(&pool, &WETH).is_err());
+        pool.sqrt_ratio_x96 = encode_sqrt_ratio_x96(1, 1);
+        assert_eq!(*cache.token0_price(&pool), pool.token0_price());
+        assert_eq!(*cache.token1_price(&pool), pool.token1_price());
+    }
+
+    #[test]
+    fn mid_prices_are_recomputed_for_another_pool() {
+        let pool = Pool::new(
+            USDC.clone(),
+            DAI.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        let other = Pool::new(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        let mut cache = PoolPriceCache::new();
+        assert_eq!(*cache.token0_price(&pool), pool.token0_price());
+        assert_eq!(*cache.token0_price(&other), other.token0_price());
+        cache.clear();
+        assert!(cache.mid_prices.is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use uniswap_sdk_core::prelude::*;
+
+/// A position in a [`Portfolio`] with the fees it has earned and not yet collected
+#[derive(Clone, Debug)]
+pub struct PortfolioPosition<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    pub position: Position<TP>,
+    /// The unclaimed fees in token0, see [`get_unclaimed_fees`]
+    pub fees0: U256,
+    /// The unclaimed fees in token1, see [`get_unclaimed_fees`]
+    pub fees1: U256,
+}
+
+impl<TP: TickDataProvider> PortfolioPosition<TP> {
+    /// Whether the current tick of the pool is within the range of the position
+    #[inline]
+    #[must_use]
+    pub fn in_range(&self) -> bool {
+        self.position.range_status() == RangeStatus::InRange
+    }
+}
+
+/// A collection of positions, possibly in different pools and on different chains, with their
+/// aggregate amounts, fees and value.
+///
+/// The amounts of the same token are summed across the positions, where tokens on different
+/// chains are distinct.
+#[derive(Clone, Debug)]
+pub struct Portfolio<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    pub positions: Vec<PortfolioPosition<TP>>,
+}
+
+impl<TP: TickDataProvider> Default for Portfolio<TP> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            positions: Vec::new(),
+        }
+    }
+}
+
+/// Adds an amount to the total of its token
+fn accumulate(
+    totals: &mut Vec<CurrencyAmount<Token>>,
+    amount: CurrencyAmount<Token>,
+) -> Result<(), Error> {
+    match totals
+        .iter_mut()
+        .find(|total| total.currency.equals(&amount.currency))
+    {
+        Some(total) => *total = total.add(&amount)?,
+        None => totals.push(amount),
+    }
+    Ok(())
+}
+
+impl<TP: TickDataProvider> Portfolio<TP> {
+    /// Creates an empty portfolio
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a position and its unclaimed fees to the portfolio
+    ///
+    /// ## Arguments
+    ///
+    /// * `position`: The position
+    /// * `fees0`: The unclaimed fees in token0
+    /// * `fees1`: The unclaimed fees in token1
+    #[inline]
+    pub fn add(&mut self, position: Position<TP>, fees0: U256, fees1: U256) {
+        self.positions.push(PortfolioPosition {
+            position,
+            fees0,
+            fees1,
+        });
+    }
+
+    /// The positions whose range contains the current tick of their pool
+    #[inline]
+    pub fn in_range(&self) -> impl Iterator<Item = &PortfolioPosition<TP>> {
+        self.positions.iter().filter(|position| position.in_range())
+    }
+
+    /// The positions whose range does not contain the current tick of their pool
+    #[inline]
+    pub fn out_of_range(&self) -> impl Iterator<Item = &PortfolioPosition<TP>> {
+        self.positions
+            .iter()
+            .filter(|position| !position.in_range())
+    }
+
+    /// The total amounts of each token in the liquidity of the positions, excluding the fees
+    #[inline]
+    pub fn amounts(&self) -> Result<Vec<CurrencyAmount<Token>>, Error> {
+        let mut totals = Vec::new();
+        for PortfolioPosition { position, .. } in &self.positions {
+            accumulate(&mut totals, position.amount0()?)?;
+            accumulate(&mut totals, position.amount1()?)?;
+        }
+        Ok(totals)
+    }
+
+    /// The total unclaimed fees of each token
+    #[inline]
+    pub fn unclaimed_fees(&self) -> Result<Vec<CurrencyAmount<Token>>, Error> {
+        let mut totals = Vec::new();
+        for PortfolioPosition {
+            position,
+            fees0,
+            fees1,
+        } in &self.positions
+        {
+            accumulate(
+                &mut totals,
+                CurrencyAmount::from_raw_amount(position.pool.token0.clone(), fees0.to_big_int())?,
+            )?;
+            accumulate(
+                &mut totals,
+                CurrencyAmount::from_raw_amount(position.pool.token1.clone(), fees1.to_big_int())?,
+            )?;
+        }
+        Ok(totals)
+    }
+
+    /// Values the amounts and the unclaimed fees of the positions in a numeraire.
+    ///
+    /// ## Arguments
+    ///
+    /// * `prices`: The prices of the tokens of the positions in the numeraire
+    /// * `numeraire`: The currency to value the portfolio in
+    ///
+    /// ## Returns
+    ///
+    /// The total value, or [`Error::InvalidToken`] if a token has no price
+    #[inline]
+    pub fn value<TNumeraire: BaseCurrency>(
+        &self,
+        prices: &[Price<Token, TNumeraire>],
+        numeraire: TNumeraire,
+    ) -> Result<CurrencyAmount<TNumeraire>, Error> {
+        let mut value = CurrencyAmount::from_raw_amount(numeraire, 0)?;
+        for amount in self.amounts()?.into_iter().chain(self.unclaimed_fees()?) {
+            let price = prices
+                .iter()
+                .find(|price| price.base_currency.equals(&amount.currency))
+                .ok_or(Error::InvalidToken)?;
+            value = value.add(&price.quote(&amount)?)?;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn position(tick_lower: i32, tick_upper: i32) -> Position {
+        Position::new(POOL_0_1.clone(), 1_000_000, tick_lower, tick_upper)
+    }
+
+    fn portfolio() -> Portfolio {
+        let mut portfolio = Portfolio::new();
+        portfolio.add(position(-60, 60), U256::from(10), U256::from(20));
+        portfolio.add(position(60, 120), U256::ZERO, U256::ZERO);
+        portfolio
+    }
+
+    #[test]
+    fn test_in_range() {
+        let portfolio = portfolio();
+        assert_eq!(portfolio.in_range().count(), 1);
+        assert_eq!(portfolio.out_of_range().count(), 1);
+        assert_eq!(
+            portfolio.out_of_range().next().unwrap().position.tick_lower,
+            60
+        );
+    }
+
+    #[test]
+    fn test_amounts() {
+        let portfolio = portfolio();
+        let amounts = portfolio.amounts().unwrap();
+        assert_eq!(amounts.len(), 2);
+        let positions = &portfolio.positions;
+        assert_eq!(
+            amounts[0].quotient(),
+            positions[0].position.amount0().unwrap().quotient()
+                + positions[1].position.amount0().unwrap().quotient()
+        );
+        assert_eq!(
+            amounts[1].quotient(),
+            positions[0].position.amount1().unwrap().quotient()
+        );
+        let fees = portfolio.unclaimed_fees().unwrap();
+        assert_eq!(fees[0].quotient(), BigInt::from(10));
+        assert_eq!(fees[1].quotient(), BigInt::from(20));
+    }
+
+    #[test]
+    fn test_value() {
+        let portfolio = portfolio();
+        let prices = [
+            Price::new(TOKEN0.clone(), TOKEN1.clone(), 1, 2),
+            Price::new(TOKEN1.clone(), TOKEN1.clone(), 1, 1),
+        ];
+        let value = portfolio.value(&prices, TOKEN1.clone()).unwrap();
+        let amounts = portfolio.amounts().unwrap();
+        assert_eq!(
+            value.quotient(),
+            amounts[0].quotient() * 2 + amounts[1].quotient() + 10 * 2 + 20
+        );
+        assert!(matches!(
+            portfolio.value(&prices[1..], TOKEN1.clone()),
+            Err(Error::InvalidToken)
+        ));
+    }
+}
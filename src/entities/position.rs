@@ -5,6 +5,14 @@ use uniswap_sdk_core::prelude::*;
 
 /// Represents a position on a Uniswap V3 Pool
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "TP: serde::Serialize, TP::Index: serde::Serialize",
+        deserialize = "TP: serde::Deserialize<'de>, TP::Index: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Position<TP = NoTickDataProvider>
 where
     TP: TickDataProvider,
@@ -13,8 +21,11 @@ where
     pub tick_lower: TP::Index,
     pub tick_upper: TP::Index,
     pub liquidity: u128,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _token0_amount: Option<CurrencyAmount<Token>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _token1_amount: Option<CurrencyAmount<Token>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _mint_amounts: Option<MintAmounts>,
 }
 
@@ -24,6 +35,39 @@ pub struct MintAmounts {
     pub amount1: U256,
 }
 
+/// Where the current tick of the pool is relative to the range of a position, see
+/// [`Position::range_status`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeStatus<I> {
+    /// The lower tick is at or below the current tick and the upper tick is above it
+    InRange,
+    /// The current tick is below the lower tick by a number of ticks
+    BelowBy(I),
+    /// The current tick is at or above the upper tick by a number of ticks
+    AboveBy(I),
+}
+
+/// The distance from the current price of the pool to a bound of a position, see
+/// [`Position::distance_to_lower`] and [`Position::distance_to_upper`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeDistance<I> {
+    /// The distance in ticks, negative if the price is beyond the bound
+    pub ticks: I,
+    /// The relative change of the current price that reaches the bound, negative if the price is
+    /// beyond the bound
+    pub percent: Percent,
+}
+
+impl<I> RangeDistance<I> {
+    /// The relative change of the current price that reaches the bound in basis points, rounded
+    /// toward zero
+    #[inline]
+    #[must_use]
+    pub fn bips(&self) -> BigInt {
+        &self.percent.numerator * BigInt::from(10000) / &self.percent.denominator
+    }
+}
+
 impl<TP> PartialEq for Position<TP>
 where
     TP: TickDataProvider<Index: PartialEq>,
@@ -81,7 +125,7 @@ impl<TP: TickDataProvider> Position<TP> {
         tick_to_price(
             self.pool.token0.clone(),
             self.pool.token1.clone(),
-            self.tick_lower.to_i24(),
+            self.tick_lower,
         )
     }
 
@@ -91,7 +135,7 @@ impl<TP: TickDataProvider> Position<TP> {
         tick_to_price(
             self.pool.token0.clone(),
             self.pool.token1.clone(),
-            self.tick_upper.to_i24(),
+            self.tick_upper,
         )
     }
 
@@ -183,6 +227,101 @@ impl<TP: TickDataProvider> Position<TP> {
         Ok(amount)
     }
 
+    /// Returns whether the current tick of the pool is in the range of the position, or how far
+    /// below or above it
+    #[inline]
+    pub fn range_status(&self) -> RangeStatus<TP::Index> {
+        let tick_current = self.pool.tick_current;
+        if tick_current < self.tick_lower {
+            RangeStatus::BelowBy(self.tick_lower - tick_current)
+        } else if tick_current >= self.tick_upper {
+            RangeStatus::AboveBy(tick_current - self.tick_upper)
+        } else {
+            RangeStatus::InRange
+        }
+    }
+
+    /// Returns the distance from the current price of the pool down to the lower bound of the
+    /// position, where the percent is the relative fall of the price to the bound
+    #[inline]
+    pub fn distance_to_lower(&self) -> Result<RangeDistance<TP::Index>, Error> {
+        let price_current = self.pool.sqrt_ratio_x96.to_big_int().pow(2);
+        let price_lower = get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?
+            .to_big_int()
+            .pow(2);
+        Ok(RangeDistance {
+            ticks: self.pool.tick_current - self.tick_lower,
+            percent: Percent::new(&price_current - price_lower, price_current),
+        })
+    }
+
+    /// Returns the distance from the current price of the pool up to the upper bound of the
+    /// position, where the percent is the relative rise of the price to the bound
+    #[inline]
+    pub fn distance_to_upper(&self) -> Result<RangeDistance<TP::Index>, Error> {
+        let price_current = self.pool.sqrt_ratio_x96.to_big_int().pow(2);
+        let price_upper = get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?
+            .to_big_int()
+            .pow(2);
+        Ok(RangeDistance {
+            ticks: self.tick_upper - self.pool.tick_current,
+            percent: Percent::new(price_upper - &price_current, price_current),
+        })
+    }
+
+    /// Values the amounts of the position at the current pool price and its unclaimed fees in a
+    /// numeraire
+    ///
+    /// ## Arguments
+    ///
+    /// * `price0`: The price of token0 in the numeraire
+    /// * `price1`: The price of token1 in the numeraire
+    /// * `fees0`: The unclaimed fees in token0, see [`get_unclaimed_fees`]
+    /// * `fees1`: The unclaimed fees in token1, see [`get_unclaimed_fees`]
+    #[inline]
+    pub fn value_in<TNumeraire: BaseCurrency>(
+        &self,
+        price0: &Price<Token, TNumeraire>,
+        price1: &Price<Token, TNumeraire>,
+        fees0: U256,
+        fees1: U256,
+    ) -> Result<CurrencyAmount<TNumeraire>, Error> {
+        let amount0 = self.amount0()?.add(&CurrencyAmount::from_raw_amount(
+            self.pool.token0.clone(),
+            fees0.to_big_int(),
+        )?)?;
+        let amount1 = self.amount1()?.add(&CurrencyAmount::from_raw_amount(
+            self.pool.token1.clone(),
+            fees1.to_big_int(),
+        )?)?;
+        Ok(price0.quote(&amount0)?.add(&price1.quote(&amount1)?)?)
+    }
+
+    /// Values the amounts of the position and its unclaimed fees in one of the tokens of the pool
+    /// at the current pool price, see [`Position::value_in`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `numeraire`: token0 or token1 of the pool
+    /// * `fees0`: The unclaimed fees in token0
+    /// * `fees1`: The unclaimed fees in token1
+    #[inline]
+    pub fn value_in_token(
+        &self,
+        numeraire: &Token,
+        fees0: U256,
+        fees1: U256,
+    ) -> Result<CurrencyAmount<Token>, Error> {
+        let identity = Price::new(numeraire.clone(), numeraire.clone(), 1, 1);
+        if numeraire.equals(&self.pool.token0) {
+            self.value_in(&identity, &self.pool.token1_price(), fees0, fees1)
+        } else if numeraire.equals(&self.pool.token1) {
+            self.value_in(&self.pool.token0_price(), &identity, fees0, fees1)
+        } else {
+            Err(Error::InvalidToken)
+        }
+    }
+
     /// Returns the lower and upper sqrt ratios if the price 'slips' up to slippage tolerance
     /// percentage
     ///
@@ -195,26 +334,7 @@ impl<TP: TickDataProvider> Position<TP> {
     ///
     /// (sqrt_ratio_x96_lower, sqrt_ratio_x96_upper)
     fn ratios_after_slippage(&self, slippage_tolerance: &Percent) -> (U160, U160) {
-        let one = Percent::new(1, 1);
-        let token0_price = self.pool.token0_price().as_fraction();
-        let price_lower = (one.clone() - slippage_tolerance).as_fraction() * &token0_price;
-        let price_upper = token0_price * ((one + slippage_tolerance).as_fraction());
-
-        let mut sqrt_ratio_x96_lower =
-            encode_sqrt_ratio_x96(price_lower.numerator, price_lower.denominator);
-        if sqrt_ratio_x96_lower <= MIN_SQRT_RATIO {
-            sqrt_ratio_x96_lower = MIN_SQRT_RATIO + ONE;
-        }
-
-        let sqrt_ratio_x96_upper = if price_upper
-            >= Fraction::new(MAX_SQRT_RATIO.to_big_int().pow(2), Q192.to_big_int())
-        {
-            MAX_SQRT_RATIO - ONE
-        } else {
-            encode_sqrt_ratio_x96(price_upper.numerator, price_upper.denominator)
-        };
-
-        (sqrt_ratio_x96_lower, sqrt_ratio_x96_upper)
+        ratios_after_slippage(self.pool.sqrt_ratio_x96, slippage_tolerance)
     }
 
     /// Returns the minimum amounts that must be sent in order to safely mint the amount of
@@ -351,6 +471,47 @@ impl<TP: TickDataProvider> Position<TP> {
         Ok((U256::from_big_int(amount0), U256::from_big_int(amount1)))
     }
 
+    /// Returns the minimum amounts that should be requested in order to safely burn the amount of
+    /// liquidity held by the position if the pool were at a hypothetical price, without
+    /// constructing a pool at that price
+    ///
+    /// ## Arguments
+    ///
+    /// * `sqrt_price_x96`: The hypothetical sqrt price of the pool
+    /// * `slippage_tolerance`: tolerance of unfavorable slippage from the hypothetical price
+    ///
+    /// ## Returns
+    ///
+    /// The amounts, with slippage
+    #[inline]
+    pub fn burn_amounts_at_price(
+        &self,
+        sqrt_price_x96: U160,
+        slippage_tolerance: &Percent,
+    ) -> Result<(U256, U256), Error> {
+        if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+            return Err(Error::InvalidSqrtPrice(sqrt_price_x96));
+        }
+        let (sqrt_ratio_x96_lower, sqrt_ratio_x96_upper) =
+            ratios_after_slippage(sqrt_price_x96, slippage_tolerance);
+        let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?;
+        let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?;
+        // the smaller amount0 occurs at the upper price and the smaller amount1 at the lower
+        let amount0 = get_amount_0_delta(
+            sqrt_ratio_x96_upper.clamp(sqrt_ratio_a_x96, sqrt_ratio_b_x96),
+            sqrt_ratio_b_x96,
+            self.liquidity,
+            false,
+        )?;
+        let amount1 = get_amount_1_delta(
+            sqrt_ratio_a_x96,
+            sqrt_ratio_x96_lower.clamp(sqrt_ratio_a_x96, sqrt_ratio_b_x96),
+            self.liquidity,
+            false,
+        )?;
+        Ok((amount0, amount1))
+    }
+
     /// Returns the minimum amounts that must be sent in order to mint the amount of liquidity held
     /// by the position at the current price for the pool
     #[inline]
@@ -498,11 +659,36 @@ impl<TP: TickDataProvider> Position<TP> {
     }
 }
 
+/// Returns the lower and upper sqrt ratios if the price at a sqrt ratio 'slips' up to slippage
+/// tolerance percentage, see [`Position::burn_amounts_at_price`]
+fn ratios_after_slippage(sqrt_ratio_x96: U160, slippage_tolerance: &Percent) -> (U160, U160) {
+    let one = Percent::new(1, 1);
+    let token0_price = Fraction::new(sqrt_ratio_x96.to_big_int().pow(2), Q192.to_big_int());
+    let price_lower = (one.clone() - slippage_tolerance).as_fraction() * &token0_price;
+    let price_upper = token0_price * ((one + slippage_tolerance).as_fraction());
+
+    let mut sqrt_ratio_x96_lower =
+        encode_sqrt_ratio_x96(price_lower.numerator, price_lower.denominator);
+    if sqrt_ratio_x96_lower <= MIN_SQRT_RATIO {
+        sqrt_ratio_x96_lower = MIN_SQRT_RATIO + ONE;
+    }
+
+    let sqrt_ratio_x96_upper =
+        if price_upper >= Fraction::new(MAX_SQRT_RATIO.to_big_int().pow(2), Q192.to_big_int()) {
+            MAX_SQRT_RATIO - ONE
+        } else {
+            encode_sqrt_ratio_x96(price_upper.numerator, price_upper.denominator)
+        };
+
+    (sqrt_ratio_x96_lower, sqrt_ratio_x96_upper)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::*;
     use alloy_primitives::aliases::I24;
+    use num_traits::Signed;
     use once_cell::sync::Lazy;
 
     static POOL_SQRT_RATIO_START: Lazy<U160> =
@@ -541,6 +727,68 @@ mod tests {
         assert_eq!(position.liquidity, 1);
     }
 
+    #[test]
+    fn range_status_and_distances() {
+        let tick_current = POOL_TICK_CURRENT.as_i32();
+        let tick_lower = nearest_usable_tick(*POOL_TICK_CURRENT, TICK_SPACING).as_i32() - 20;
+        let position = Position::new(DAI_USDC_POOL.clone(), 1, tick_lower, tick_lower + 40);
+        assert_eq!(position.range_status(), RangeStatus::InRange);
+        let lower = position.distance_to_lower().unwrap();
+        assert_eq!(lower.ticks, tick_current - tick_lower);
+        assert!(lower.bips() > BigInt::ZERO);
+        let upper = position.distance_to_upper().unwrap();
+        assert_eq!(upper.ticks, tick_lower + 40 - tick_current);
+        // a tick is about one basis point
+        assert!((upper.bips() - BigInt::from(upper.ticks)).abs() <= BigInt::from(1));
+
+        let position = Position::new(DAI_USDC_POOL.clone(), 1, tick_lower - 40, tick_lower);
+        assert_eq!(
+            position.range_status(),
+            RangeStatus::AboveBy(tick_current - tick_lower)
+        );
+        assert!(position.distance_to_upper().unwrap().bips() < BigInt::ZERO);
+
+        let position = Position::new(DAI_USDC_POOL.clone(), 1, tick_lower + 40, tick_lower + 80);
+        assert_eq!(
+            position.range_status(),
+            RangeStatus::BelowBy(tick_lower + 40 - tick_current)
+        );
+        assert!(position.distance_to_lower().unwrap().percent.numerator < BigInt::ZERO);
+    }
+
+    #[test]
+    fn value_in_pool_tokens() {
+        let position = Position::new(
+            DAI_USDC_POOL.clone(),
+            100_000_000_000_000_000_000,
+            nearest_usable_tick(*POOL_TICK_CURRENT, TICK_SPACING).as_i32()
+                - TICK_SPACING.as_i32() * 2,
+            nearest_usable_tick(*POOL_TICK_CURRENT, TICK_SPACING).as_i32()
+                + TICK_SPACING.as_i32() * 2,
+        );
+        let fees0 = U256::from(10).pow(U256::from(18));
+        let fees1 = U256::from(10).pow(U256::from(6));
+        let value = position.value_in_token(&USDC, fees0, fees1).unwrap();
+        let amount0 = position.amount0().unwrap().quotient() + fees0.to_big_int();
+        let amount1 = position.amount1().unwrap().quotient() + fees1.to_big_int();
+        let expected = DAI_USDC_POOL
+            .token0_price()
+            .quote(&CurrencyAmount::from_raw_amount(DAI.clone(), amount0).unwrap())
+            .unwrap()
+            .quotient()
+            + amount1;
+        assert!(value.currency.equals(&*USDC));
+        assert_eq!(value.quotient(), expected);
+        assert!(
+            position
+                .value_in_token(&USDC, U256::ZERO, U256::ZERO)
+                .unwrap()
+                .quotient()
+                < value.quotient()
+        );
+        assert!(position.value_in_token(&WETH, fees0, fees1).is_err());
+    }
+
     #[test]
     #[should_panic(expected = "TICK_ORDER")]
     fn tick_lower_must_be_less_than_tick_upper() {
@@ -848,6 +1096,72 @@ mod tests {
         assert_eq!(amount1.to_string(), "79831926242");
     }
 
+    #[test]
+    fn burn_amounts_at_price_matches_burn_amounts_with_slippage() {
+        let tick = nearest_usable_tick(*POOL_TICK_CURRENT, TICK_SPACING);
+        for (tick_lower, tick_upper) in [
+            (tick + TICK_SPACING, tick + TICK_SPACING * TWO),
+            (tick - TICK_SPACING * TWO, tick - TICK_SPACING),
+            (tick - TICK_SPACING * TWO, tick + TICK_SPACING * TWO),
+        ] {
+            let position = Position::new(
+                DAI_USDC_POOL.clone(),
+                100e18 as u128,
+                tick_lower.as_i32(),
+                tick_upper.as_i32(),
+            );
+            for slippage_tolerance in [Percent::default(), Percent::new(5, 10000)] {
+                assert_eq!(
+                    position
+                        .burn_amounts_at_price(*POOL_SQRT_RATIO_START, &slippage_tolerance)
+                        .unwrap(),
+                    position
+                        .burn_amounts_with_slippage(&slippage_tolerance)
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn burn_amounts_at_price_is_correct_for_hypothetical_prices() {
+        let tick = nearest_usable_tick(*POOL_TICK_CURRENT, TICK_SPACING);
+        let position = Position::new(
+            DAI_USDC_POOL.clone(),
+            100e18 as u128,
+            (tick - TICK_SPACING * TWO).as_i32(),
+            (tick + TICK_SPACING * TWO).as_i32(),
+        );
+        let slippage_tolerance = Percent::new(5, 10000);
+        for sqrt_price_x96 in [
+            MIN_SQRT_RATIO,
+            get_sqrt_ratio_at_tick(tick - TICK_SPACING).unwrap(),
+            get_sqrt_ratio_at_tick(tick + TICK_SPACING).unwrap(),
+            MAX_SQRT_RATIO - ONE,
+        ] {
+            let moved = Position::new(
+                Pool::new(DAI.clone(), USDC.clone(), FeeAmount::LOW, sqrt_price_x96, 0).unwrap(),
+                position.liquidity,
+                position.tick_lower,
+                position.tick_upper,
+            );
+            assert_eq!(
+                position
+                    .burn_amounts_at_price(sqrt_price_x96, &slippage_tolerance)
+                    .unwrap(),
+                moved
+                    .burn_amounts_with_slippage(&slippage_tolerance)
+                    .unwrap()
+            );
+        }
+        // the pool is unchanged
+        assert_eq!(position.pool.sqrt_ratio_x96, *POOL_SQRT_RATIO_START);
+        assert!(matches!(
+            position.burn_amounts_at_price(MAX_SQRT_RATIO, &slippage_tolerance),
+            Err(Error::InvalidSqrtPrice(_))
+        ));
+    }
+
     #[test]
     fn burn_amounts_with_slippage_is_correct_for_positions_below_05_percent_slippage() {
         let position = Position::new(
@@ -0,0 +1,466 @@
+use crate::prelude::{Error, *};
+use alloy_primitives::U256;
+use num_traits::{Signed, ToPrimitive};
+use uniswap_sdk_core::prelude::*;
+
+/// How the range of a [`PositionBuilder`] is specified
+#[derive(Clone, Debug)]
+enum Range<I> {
+    Ticks(I, I),
+    Prices(Price<Token, Token>, Price<Token, Token>),
+    Percents(Percent, Percent),
+}
+
+/// How the size of a [`PositionBuilder`] is specified
+#[derive(Clone, Copy, Debug)]
+enum Size {
+    Liquidity(u128),
+    Amounts(U256, U256, bool),
+    Amount0(U256, bool),
+    Amount1(U256),
+}
+
+/// A fluent builder of a [`Position`] that validates the range and size of the position before
+/// producing it, where the constructors of [`Position`] panic or silently create a position that
+/// would revert on mint.
+///
+/// ## Examples
+///
+/// ```
+/// use alloy_primitives::U256;
+/// use uniswap_sdk_core::{prelude::Token, token};
+/// use uniswap_v3_sdk::prelude::*;
+///
+/// let usdc = token!(1, "A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", 6);
+/// let dai = token!(1, "6B175474E89094C44Da98b954EedeAC495271d0F", 18);
+/// let pool = Pool::new(dai, usdc, FeeAmount::LOW, encode_sqrt_ratio_x96(1, 1), 0).unwrap();
+/// let position = PositionBuilder::new(pool.clone())
+///     .ticks(-100, 100)
+///     .amounts(U256::from(1_000_000), U256::from(1_000_000))
+///     .build()
+///     .unwrap();
+/// assert_eq!((position.tick_lower, position.tick_upper), (-100, 100));
+/// // the ticks of a pool with a tick spacing of 10 must be multiples of 10
+/// assert!(PositionBuilder::new(pool)
+///     .ticks(-105, 100)
+///     .liquidity(1000)
+///     .build()
+///     .is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PositionBuilder<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    pool: Pool<TP>,
+    range: Option<Range<TP::Index>>,
+    size: Option<Size>,
+    slippage_tolerance: Option<Percent>,
+}
+
+impl<TP: TickDataProvider> PositionBuilder<TP> {
+    /// Creates a builder of a position in a pool
+    #[inline]
+    #[must_use]
+    pub const fn new(pool: Pool<TP>) -> Self {
+        Self {
+            pool,
+            range: None,
+            size: None,
+            slippage_tolerance: None,
+        }
+    }
+
+    /// Sets the range by its ticks, which must be usable for the tick spacing of the pool.
+    #[inline]
+    #[must_use]
+    pub fn ticks(mut self, tick_lower: TP::Index, tick_upper: TP::Index) -> Self {
+        self.range = Some(Range::Ticks(tick_lower, tick_upper));
+        self
+    }
+
+    /// Sets the range by its bound prices in either direction, which are rounded to the nearest
+    /// usable ticks.
+    #[inline]
+    #[must_use]
+    pub fn prices(
+        mut self,
+        price_lower: Price<Token, Token>,
+        price_upper: Price<Token, Token>,
+    ) -> Self {
+        self.range = Some(Range::Prices(price_lower, price_upper));
+        self
+    }
+
+    /// Sets the range by how far the price of token0 may fall and rise from the current price of
+    /// the pool, e.g. 10% and 20% for a range from 0.9 to 1.2 times the current price, which are
+    /// rounded to the nearest usable ticks.
+    #[inline]
+    #[must_use]
+    pub fn percents(mut self, below: Percent, above: Percent) -> Self {
+        self.range = Some(Range::Percents(below, above));
+        self
+    }
+
+    /// Sets the liquidity of the position.
+    #[inline]
+    #[must_use]
+    pub const fn liquidity(mut self, liquidity: u128) -> Self {
+        self.size = Some(Size::Liquidity(liquidity));
+        self
+    }
+
+    /// Sets the size of the position to the maximum liquidity for the amounts of both tokens, see
+    /// [`Position::from_amounts`].
+    #[inline]
+    #[must_use]
+    pub const fn amounts(mut self, amount0: U256, amount1: U256) -> Self {
+        self.size = Some(Size::Amounts(amount0, amount1, true));
+        self
+    }
+
+    /// Sets the size of the position to the maximum liquidity for an amount of token0, see
+    /// [`Position::from_amount0`].
+    #[inline]
+    #[must_use]
+    pub const fn amount0(mut self, amount0: U256) -> Self {
+        self.size = Some(Size::Amount0(amount0, true));
+        self
+    }
+
+    /// Sets the size of the position to the maximum liquidity for an amount of token1, see
+    /// [`Position::from_amount1`].
+    #[inline]
+    #[must_use]
+    pub const fn amount1(mut self, amount1: U256) -> Self {
+        self.size = Some(Size::Amount1(amount1));
+        self
+    }
+
+    /// Sets whether the liquidity for the amounts is maximized according to what core can
+    /// theoretically support rather than what the router can calculate, by default true.
+    #[inline]
+    #[must_use]
+    pub const fn use_full_precision(mut self, use_full_precision: bool) -> Self {
+        self.size = match self.size {
+            Some(Size::Amounts(amount0, amount1, _)) => {
+                Some(Size::Amounts(amount0, amount1, use_full_precision))
+            }
+            Some(Size::Amount0(amount0, _)) => Some(Size::Amount0(amount0, use_full_precision)),
+            size => size,
+        };
+        self
+    }
+
+    /// Sets the slippage tolerance of the mint amounts, see
+    /// [`PositionBuilder::build_with_mint_amounts`].
+    #[inline]
+    #[must_use]
+    pub fn slippage_tolerance(mut self, slippage_tolerance: Percent) -> Self {
+        self.slippage_tolerance = Some(slippage_tolerance);
+        self
+    }
+
+    /// Validates the range and size and produces the position.
+    ///
+    /// ## Returns
+    ///
+    /// * [`Error::InvalidRange`] if no range is set, or the upper tick is not above the lower
+    ///   tick
+    /// * [`Error::InvalidTick`] if a tick is outside the tick bounds or not a multiple of the tick
+    ///   spacing
+    /// * [`Error::InvalidAmounts`] if no size is set, the position holds no liquidity, or an amount
+    ///   of a token is given for a range that does not hold it at the current price
+    #[inline]
+    pub fn build(self) -> Result<Position<TP>, Error> {
+        let (tick_lower, tick_upper) = self.tick_range()?;
+        let tick_current = self.pool.tick_current;
+        let liquidity = match self.size.ok_or(Error::InvalidAmounts)? {
+            Size::Liquidity(liquidity) => liquidity,
+            Size::Amounts(amount0, amount1, use_full_precision) => max_liquidity(
+                &self.pool,
+                tick_lower,
+                tick_upper,
+                amount0,
+                amount1,
+                use_full_precision,
+            )?,
+            // a range below the current price holds no token0
+            Size::Amount0(_, _) if tick_current >= tick_upper => return Err(Error::InvalidAmounts),
+            Size::Amount0(amount0, use_full_precision) => max_liquidity(
+                &self.pool,
+                tick_lower,
+                tick_upper,
+                amount0,
+                U256::MAX,
+                use_full_precision,
+            )?,
+            // a range above the current price holds no token1
+            Size::Amount1(_) if tick_current < tick_lower => return Err(Error::InvalidAmounts),
+            Size::Amount1(amount1) => {
+                max_liquidity(&self.pool, tick_lower, tick_upper, U256::MAX, amount1, true)?
+            }
+        };
+        if liquidity == 0 {
+            return Err(Error::InvalidAmounts);
+        }
+        Ok(Position::new(self.pool, liquidity, tick_lower, tick_upper))
+    }
+
+    /// Validates the range and size and produces the position together with the amounts to send
+    /// to mint it, which are the minimum amounts under the slippage tolerance if set.
+    ///
+    /// ## Returns
+    ///
+    /// The errors of [`PositionBuilder::build`], or [`Error::InvalidSlippageTolerance`] if the
+    /// slippage tolerance is not between 0 and 100%, exclusive of 100%
+    #[inline]
+    pub fn build_with_mint_amounts(mut self) -> Result<(Position<TP>, MintAmounts), Error> {
+        let slippage_tolerance = self.slippage_tolerance.take();
+        let mut position = self.build()?;
+        let mint_amounts = match slippage_tolerance {
+            Some(slippage_tolerance) => {
+                if slippage_tolerance.numerator.is_negative()
+                    || slippage_tolerance.numerator >= slippage_tolerance.denominator
+                {
+                    return Err(Error::InvalidSlippageTolerance);
+                }
+                position.mint_amounts_with_slippage(&slippage_tolerance)?
+            }
+            None => position.mint_amounts()?,
+        };
+        Ok((position, mint_amounts))
+    }
+
+    /// Resolves the range to its ticks and validates them.
+    fn tick_range(&self) -> Result<(TP::Index, TP::Index), Error> {
+        let tick_spacing = self.pool.tick_spacing();
+        let (tick_lower, tick_upper) = match self.range.as_ref().ok_or(Error::InvalidRange)? {
+            &Range::Ticks(tick_lower, tick_upper) => (tick_lower, tick_upper),
+            Range::Prices(price_lower, price_upper) => {
+                let tick_a = nearest_usable_tick(
+                    TP::Index::from_i24(price_to_closest_tick(price_lower)?),
+                    tick_spacing,
+                );
+                let tick_b = nearest_usable_tick(
+                    TP::Index::from_i24(price_to_closest_tick(price_upper)?),
+                    tick_spacing,
+                );
+                // the prices of token1 fall as the ticks rise
+                (tick_a.min(tick_b), tick_a.max(tick_b))
+            }
+            Range::Percents(below, above) => {
+                if below.numerator.is_negative()
+                    || below.numerator >= below.denominator
+                    || above.numerator.is_negative()
+                {
+                    return Err(Error::InvalidRange);
+                }
+                let tick_at = |percent: &Percent, sign: BigInt| -> Result<TP::Index, Error> {
+                    let sqrt_ratio_x96: U256 = encode_sqrt_ratio_x96(
+                        self.pool.sqrt_ratio_x96.to_big_int().pow(2)
+                            * (&percent.denominator + sign * &percent.numerator),
+                        &percent.denominator << 192,
+                    );
+                    Ok(nearest_usable_tick(
+                        TP::Index::from_i24(sqrt_ratio_x96.get_tick_at_sqrt_ratio()?),
+                        tick_spacing,
+                    ))
+                };
+                (
+                    tick_at(below, BigInt::from(-1))?,
+                    tick_at(above, BigInt::from(1))?,
+                )
+            }
+        };
+        if tick_lower < TP::Index::from_i24(MIN_TICK) {
+            return Err(Error::InvalidTick(tick_lower.to_i24()));
+        }
+        if tick_upper > TP::Index::from_i24(MAX_TICK) {
+            return Err(Error::InvalidTick(tick_upper.to_i24()));
+        }
+        for tick in [tick_lower, tick_upper] {
+            if !(tick % tick_spacing).is_zero() {
+                return Err(Error::InvalidTick(tick.to_i24()));
+            }
+        }
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidRange);
+        }
+        Ok((tick_lower, tick_upper))
+    }
+}
+
+/// The maximum liquidity for the amounts, or [`Error::InvalidAmounts`] if it overflows
+fn max_liquidity<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    amount0: U256,
+    amount1: U256,
+    use_full_precision: bool,
+) -> Result<u128, Error> {
+    max_liquidity_for_amounts(
+        pool.sqrt_ratio_x96,
+        get_sqrt_ratio_at_tick(tick_lower.to_i24())?,
+        get_sqrt_ratio_at_tick(tick_upper.to_i24())?,
+        amount0,
+        amount1,
+        use_full_precision,
+    )
+    .to_u128()
+    .ok_or(Error::InvalidAmounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn pool() -> Pool<TickListDataProvider> {
+        make_pool(TOKEN0.clone(), TOKEN1.clone())
+    }
+
+    #[test]
+    fn test_ticks() {
+        let position = PositionBuilder::new(pool())
+            .ticks(-120, 120)
+            .liquidity(1000)
+            .build()
+            .unwrap();
+        assert_eq!(position, Position::new(pool(), 1000, -120, 120));
+        // misaligned ticks
+        assert!(matches!(
+            PositionBuilder::new(pool()).ticks(-100, 120).liquidity(1000).build(),
+            Err(Error::InvalidTick(tick)) if tick.as_i32() == -100
+        ));
+        // out of bounds
+        assert!(matches!(
+            PositionBuilder::new(pool()).ticks(-120, 887280).liquidity(1000).build(),
+            Err(Error::InvalidTick(tick)) if tick.as_i32() == 887280
+        ));
+        // misordered and missing ticks
+        assert!(matches!(
+            PositionBuilder::new(pool())
+                .ticks(120, -120)
+                .liquidity(1000)
+                .build(),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            PositionBuilder::new(pool()).liquidity(1000).build(),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_prices() {
+        let price_lower = tick_to_price(TOKEN0.clone(), TOKEN1.clone(), -125).unwrap();
+        let price_upper = tick_to_price(TOKEN0.clone(), TOKEN1.clone(), 115).unwrap();
+        let position = PositionBuilder::new(pool())
+            .prices(price_lower.clone(), price_upper.clone())
+            .liquidity(1000)
+            .build()
+            .unwrap();
+        assert_eq!((position.tick_lower, position.tick_upper), (-120, 120));
+        // the prices of token1 in token0
+        let position = PositionBuilder::new(pool())
+            .prices(price_lower.invert(), price_upper.invert())
+            .liquidity(1000)
+            .build()
+            .unwrap();
+        assert_eq!((position.tick_lower, position.tick_upper), (-120, 120));
+    }
+
+    #[test]
+    fn test_percents() {
+        let position = PositionBuilder::new(pool())
+            .percents(Percent::new(10, 100), Percent::new(10, 100))
+            .liquidity(1000)
+            .build()
+            .unwrap();
+        // ln(0.9) / ln(1.0001) = -1053.66 and ln(1.1) / ln(1.0001) = 953.15
+        assert_eq!((position.tick_lower, position.tick_upper), (-1080, 960));
+        assert!(matches!(
+            PositionBuilder::new(pool())
+                .percents(Percent::new(100, 100), Percent::new(10, 100))
+                .liquidity(1000)
+                .build(),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_amounts() {
+        let amount = U256::from(1_000_000);
+        let position = PositionBuilder::new(pool())
+            .ticks(-120, 120)
+            .amounts(amount, amount)
+            .build()
+            .unwrap();
+        let expected = Position::from_amounts(pool(), -120, 120, amount, amount, true).unwrap();
+        assert_eq!(position, expected);
+        let position = PositionBuilder::new(pool())
+            .ticks(60, 120)
+            .amount0(amount)
+            .build()
+            .unwrap();
+        assert_eq!(
+            position,
+            Position::from_amount0(pool(), 60, 120, amount, true).unwrap()
+        );
+        // a range above the current price holds no token1
+        assert!(matches!(
+            PositionBuilder::new(pool())
+                .ticks(60, 120)
+                .amount1(amount)
+                .build(),
+            Err(Error::InvalidAmounts)
+        ));
+        // a range below the current price holds no token0
+        assert!(matches!(
+            PositionBuilder::new(pool())
+                .ticks(-120, -60)
+                .amount0(amount)
+                .build(),
+            Err(Error::InvalidAmounts)
+        ));
+        assert!(matches!(
+            PositionBuilder::new(pool())
+                .ticks(-120, 120)
+                .amounts(U256::ZERO, U256::ZERO)
+                .build(),
+            Err(Error::InvalidAmounts)
+        ));
+        assert!(matches!(
+            PositionBuilder::new(pool()).ticks(-120, 120).build(),
+            Err(Error::InvalidAmounts)
+        ));
+    }
+
+    #[test]
+    fn test_build_with_mint_amounts() {
+        let builder = PositionBuilder::new(pool())
+            .ticks(-120, 120)
+            .liquidity(1_000_000_000);
+        let (mut position, mint_amounts) = builder.clone().build_with_mint_amounts().unwrap();
+        assert_eq!(mint_amounts, position.mint_amounts().unwrap());
+        let (_, min_amounts) = builder
+            .clone()
+            .slippage_tolerance(Percent::new(5, 10000))
+            .build_with_mint_amounts()
+            .unwrap();
+        assert_eq!(
+            min_amounts,
+            position
+                .mint_amounts_with_slippage(&Percent::new(5, 10000))
+                .unwrap()
+        );
+        assert!(matches!(
+            builder
+                .slippage_tolerance(Percent::new(1, 1))
+                .build_with_mint_amounts(),
+            Err(Error::InvalidSlippageTolerance)
+        ));
+    }
+}
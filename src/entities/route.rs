@@ -1,9 +1,19 @@
 use crate::prelude::{Error, *};
-use alloy_primitives::ChainId;
+use alloy_primitives::{ChainId, U160};
 use uniswap_sdk_core::prelude::*;
 
 /// Represents a list of pools through which a swap can occur
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "TP: serde::Serialize, TP::Index: serde::Serialize",
+        deserialize = "TInput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TOutput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TP: serde::Deserialize<'de>, TP::Index: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Route<TInput, TOutput, TP>
 where
     TInput: BaseCurrency,
@@ -12,10 +22,20 @@ where
 {
     pub pools: Vec<Pool<TP>>,
     /// The input token
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::serde_helpers::currency")
+    )]
     pub input: TInput,
     /// The output token
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::serde_helpers::currency")
+    )]
     pub output: TOutput,
-    _mid_price: Option<Price<TInput, TOutput>>,
+    /// The mid price of the route at the sqrt prices of its pools
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _mid_price: Option<(Vec<U160>, Price<TInput, TOutput>)>,
 }
 
 impl<TInput, TOutput, TP> Route<TInput, TOutput, TP>
@@ -68,6 +88,83 @@ where
         }
     }
 
+    /// Creates an instance of route, returning an error instead of panicking if the route is
+    /// malformed, see [`Route::validate`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: An array of [`Pool`] objects, ordered by the route the swap will take
+    /// * `input`: The input token
+    /// * `output`: The output token
+    #[inline]
+    pub fn try_new(pools: Vec<Pool<TP>>, input: TInput, output: TOutput) -> Result<Self, Error> {
+        let route = Self {
+            pools,
+            input,
+            output,
+            _mid_price: None,
+        };
+        route.validate()?;
+        Ok(route)
+    }
+
+    /// Checks that the pools of the route form a path between its input and output currencies.
+    ///
+    /// ## Notes
+    ///
+    /// The pools must be on the chain of the currencies, have sorted tokens, and be distinct. The
+    /// wrapped input must be a token of the first pool, each pool must share a token with the
+    /// previous one, and the path must end at the wrapped output.
+    #[inline]
+    pub fn validate(&self) -> Result<(), Error> {
+        let Some(first) = self.pools.first() else {
+            return Err(RouteError::NoPools.into());
+        };
+        let chain_id = first.chain_id();
+        if self.input.chain_id() != chain_id
+            || self.output.chain_id() != chain_id
+            || self.pools.iter().any(|pool| pool.chain_id() != chain_id)
+        {
+            return Err(RouteError::ChainIdMismatch.into());
+        }
+        for (i, pool) in self.pools.iter().enumerate() {
+            if !matches!(pool.token0.sorts_before(&pool.token1), Ok(true)) {
+                return Err(RouteError::UnsortedTokens.into());
+            }
+            if self.pools[..i].iter().any(|other| {
+                other.fee == pool.fee
+                    && other.token0.equals(&pool.token0)
+                    && other.token1.equals(&pool.token1)
+            }) {
+                return Err(RouteError::DuplicatePool.into());
+            }
+        }
+
+        let wrapped_input = self.input.wrapped();
+        if !first.involves_token(wrapped_input) {
+            return Err(RouteError::InvalidInput.into());
+        }
+        let wrapped_output = self.output.wrapped();
+        if !self.pools[self.pools.len() - 1].involves_token(wrapped_output) {
+            return Err(RouteError::InvalidOutput.into());
+        }
+
+        let mut current_input_token = wrapped_input;
+        for pool in &self.pools {
+            current_input_token = if current_input_token.equals(&pool.token0) {
+                &pool.token1
+            } else if current_input_token.equals(&pool.token1) {
+                &pool.token0
+            } else {
+                return Err(RouteError::InvalidPath.into());
+            };
+        }
+        if !current_input_token.equals(wrapped_output) {
+            return Err(RouteError::InvalidPath.into());
+        }
+        Ok(())
+    }
+
     /// Returns the path of tokens that the route will take
     #[inline]
     pub fn token_path(&self) -> Vec<Token> {
@@ -104,14 +201,21 @@ where
         ))
     }
 
-    /// Returns the cached mid price of the route
+    /// Returns the cached mid price of the route, recomputed only when the sqrt price of one of
+    /// its pools changes
     #[inline]
     pub fn mid_price_cached(&mut self) -> Result<Price<TInput, TOutput>, Error> {
-        if let Some(mid_price) = &self._mid_price {
-            return Ok(mid_price.clone());
+        if let Some((sqrt_prices, mid_price)) = &self._mid_price {
+            if sqrt_prices
+                .iter()
+                .eq(self.pools.iter().map(|pool| &pool.sqrt_ratio_x96))
+            {
+                return Ok(mid_price.clone());
+            }
         }
         let mid_price = self.mid_price()?;
-        self._mid_price = Some(mid_price.clone());
+        let sqrt_prices = self.pools.iter().map(|pool| pool.sqrt_ratio_x96).collect();
+        self._mid_price = Some((sqrt_prices, mid_price.clone()));
         Ok(mid_price)
     }
 }
@@ -177,6 +281,70 @@ mod tests {
             assert_eq!(route.input, *TOKEN0);
             assert_eq!(route.output, *ETHER);
         }
+
+        #[test]
+        fn validates_the_route() {
+            let validate = |pools: Vec<Pool>, input: &Token, output: &Token| {
+                Route::try_new(pools, input.clone(), output.clone()).map(|_| ())
+            };
+            assert!(validate(
+                vec![POOL_0_WETH.clone(), POOL_0_1.clone(), POOL_1_WETH.clone()],
+                &WETH,
+                &WETH
+            )
+            .is_ok());
+            assert!(
+                Route::try_new(vec![POOL_0_WETH.clone()], ETHER.clone(), TOKEN0.clone()).is_ok()
+            );
+            assert!(matches!(
+                validate(vec![], &TOKEN0, &TOKEN1),
+                Err(Error::RouteError(RouteError::NoPools))
+            ));
+            let other_chain =
+                uniswap_sdk_core::token!(5, "0000000000000000000000000000000000000001", 18, "t0");
+            assert!(matches!(
+                validate(vec![POOL_0_1.clone()], &other_chain, &TOKEN1),
+                Err(Error::RouteError(RouteError::ChainIdMismatch))
+            ));
+            let mut unsorted = POOL_0_1.clone();
+            core::mem::swap(&mut unsorted.token0, &mut unsorted.token1);
+            assert!(matches!(
+                validate(vec![unsorted], &TOKEN0, &TOKEN1),
+                Err(Error::RouteError(RouteError::UnsortedTokens))
+            ));
+            assert!(matches!(
+                validate(
+                    vec![POOL_0_1.clone(), POOL_0_1.clone(), POOL_0_1.clone()],
+                    &TOKEN0,
+                    &TOKEN1
+                ),
+                Err(Error::RouteError(RouteError::DuplicatePool))
+            ));
+            assert!(matches!(
+                validate(vec![POOL_0_1.clone()], &WETH, &TOKEN1),
+                Err(Error::RouteError(RouteError::InvalidInput))
+            ));
+            assert!(matches!(
+                validate(vec![POOL_0_1.clone()], &TOKEN0, &WETH),
+                Err(Error::RouteError(RouteError::InvalidOutput))
+            ));
+            assert!(matches!(
+                validate(
+                    vec![POOL_0_1.clone(), POOL_0_WETH.clone()],
+                    &TOKEN1,
+                    &TOKEN0
+                ),
+                Err(Error::RouteError(RouteError::InvalidPath))
+            ));
+            assert!(matches!(
+                validate(
+                    vec![POOL_0_1.clone(), POOL_1_WETH.clone()],
+                    &TOKEN0,
+                    &TOKEN1
+                ),
+                Err(Error::RouteError(RouteError::InvalidPath))
+            ));
+        }
     }
 
     mod mid_price {
@@ -236,7 +404,17 @@ mod tests {
         fn is_cached() {
             let mut route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone());
             let price = route.mid_price_cached().unwrap();
-            assert_eq!(price, route._mid_price.unwrap());
+            assert_eq!(price, route._mid_price.clone().unwrap().1);
+        }
+
+        #[test]
+        fn is_recomputed_when_the_sqrt_price_changes() {
+            let mut route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone());
+            route.mid_price_cached().unwrap();
+            route.pools[0].sqrt_ratio_x96 = encode_sqrt_ratio_x96(1, 1);
+            let price = route.mid_price_cached().unwrap();
+            assert_eq!(price, route.mid_price().unwrap());
+            assert_eq!(price.to_fixed(4, None), "1.0000");
         }
 
         #[test]
@@ -0,0 +1,138 @@
+use crate::prelude::{Error, *};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::U160;
+use core::cell::RefCell;
+
+/// A tick data provider that caches the sqrt ratios of the ticks crossed by swaps, so that repeated
+/// simulations over the same pool, e.g. quoting many sizes in a router, reuse the ratios across
+/// calls instead of recomputing them.
+///
+/// The cache is direct-mapped with a fixed number of slots, where a tick evicts the tick in its
+/// slot. It is not `Sync` because of the interior mutability, so clone it for each thread instead.
+#[derive(Clone, Debug)]
+pub struct SqrtRatioCache<TP: TickDataProvider> {
+    pub tick_data_provider: TP,
+    slots: RefCell<Vec<Option<(TP::Index, U160)>>>,
+}
+
+impl<TP: TickDataProvider> SqrtRatioCache<TP> {
+    /// Wraps a tick data provider with a cache of 256 slots
+    #[inline]
+    #[must_use]
+    pub fn new(tick_data_provider: TP) -> Self {
+        Self::with_capacity(tick_data_provider, 256)
+    }
+
+    /// Wraps a tick data provider with a cache of at least `capacity` slots, rounded up to a power
+    /// of two
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(tick_data_provider: TP, capacity: usize) -> Self {
+        Self {
+            tick_data_provider,
+            slots: RefCell::new(vec![None; capacity.max(1).next_power_of_two()]),
+        }
+    }
+
+    /// Evicts all the cached sqrt ratios
+    #[inline]
+    pub fn clear(&self) {
+        self.slots.borrow_mut().fill(None);
+    }
+
+    /// The slot of a tick by Fibonacci hashing, which spreads the multiples of a tick spacing
+    fn slot(&self, tick: TP::Index) -> usize {
+        let bits = self.slots.borrow().len().trailing_zeros();
+        let hash = (tick.to_i24().as_i32() as u32 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        hash.checked_shr(64 - bits).unwrap_or(0) as usize
+    }
+}
+
+impl<TP: TickDataProvider> TickDataProvider for SqrtRatioCache<TP> {
+    type Index = TP::Index;
+
+    #[inline]
+    fn get_tick(&self, tick: Self::Index) -> Result<&Tick<Self::Index>, Error> {
+        self.tick_data_provider.get_tick(tick)
+    }
+
+    #[inline]
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: Self::Index,
+        lte: bool,
+        tick_spacing: Self::Index,
+    ) -> Result<(Self::Index, bool), Error> {
+        self.tick_data_provider
+            .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
+    }
+
+    #[inline]
+    fn sqrt_ratio_at_tick(&self, tick: Self::Index) -> Result<U160, Error> {
+        let slot = self.slot(tick);
+        if let Some((cached, sqrt_ratio_x96)) = self.slots.borrow()[slot] {
+            if cached == tick {
+                return Ok(sqrt_ratio_x96);
+            }
+        }
+        let sqrt_ratio_x96 = self.tick_data_provider.sqrt_ratio_at_tick(tick)?;
+        self.slots.borrow_mut()[slot] = Some((tick, sqrt_ratio_x96));
+        Ok(sqrt_ratio_x96)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::aliases::I24;
+
+    #[test]
+    fn test_sqrt_ratio_at_tick() {
+        let cache = SqrtRatioCache::with_capacity(NoTickDataProvider, 3);
+        assert_eq!(cache.slots.borrow().len(), 4);
+        for _ in 0..2 {
+            for tick in [-887220, -60, 0, 60, 120, 887220] {
+                assert_eq!(
+                    cache.sqrt_ratio_at_tick(tick).unwrap(),
+                    get_sqrt_ratio_at_tick(I24::try_from(tick).unwrap()).unwrap()
+                );
+            }
+        }
+        assert!(cache.slots.borrow().iter().any(Option::is_some));
+        cache.clear();
+        assert!(cache.slots.borrow().iter().all(Option::is_none));
+        assert!(matches!(
+            cache.sqrt_ratio_at_tick(887273),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_output_amount() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let cached = Pool::new_with_tick_data_provider(
+            pool.token0.clone(),
+            pool.token1.clone(),
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            SqrtRatioCache::new(pool.tick_data_provider.clone()),
+        )
+        .unwrap();
+        for amount in [100, 10_000, 1_000_000] {
+            for token in [&*TOKEN0, &*TOKEN1] {
+                let input_amount = CurrencyAmount::from_raw_amount(token.clone(), amount).unwrap();
+                assert_eq!(
+                    cached
+                        .get_output_amount(&input_amount, None)
+                        .unwrap()
+                        .quotient(),
+                    pool.get_output_amount(&input_amount, None)
+                        .unwrap()
+                        .quotient()
+                );
+            }
+        }
+    }
+}
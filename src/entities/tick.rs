@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::prelude::{Error, *};
 use alloy_primitives::{aliases::I24, Signed};
 use core::{
     fmt::Debug,
@@ -7,6 +7,7 @@ use core::{
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tick<I = i32> {
     pub index: I,
     pub liquidity_gross: u128,
@@ -28,6 +29,39 @@ impl<I: TickIndex> Tick<I> {
     }
 }
 
+impl From<Tick<I24>> for Tick<i32> {
+    #[inline]
+    fn from(tick: Tick<I24>) -> Self {
+        Self {
+            index: tick.index.as_i32(),
+            liquidity_gross: tick.liquidity_gross,
+            liquidity_net: tick.liquidity_net,
+        }
+    }
+}
+
+impl TryFrom<Tick<i32>> for Tick<I24> {
+    type Error = Error;
+
+    /// Converts a tick with an [`i32`] index, or returns [`Error::InvalidTick`] if the index is
+    /// outside the tick bounds.
+    #[inline]
+    fn try_from(tick: Tick<i32>) -> Result<Self, Self::Error> {
+        if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick.index) {
+            return Err(Error::InvalidTick(if tick.index < 0 {
+                MIN_TICK
+            } else {
+                MAX_TICK
+            }));
+        }
+        Ok(Self {
+            index: tick.index.to_i24(),
+            liquidity_gross: tick.liquidity_gross,
+            liquidity_net: tick.liquidity_net,
+        })
+    }
+}
+
 /// The trait for tick indexes used across [`Tick`], [`TickDataProvider`], and [`TickList`].
 ///
 /// Implemented for [`i32`] and [`Signed`].
@@ -148,6 +182,23 @@ mod tests {
         assert_eq!(I32::from_i24(MAX_TICK).to_i24(), MAX_TICK);
     }
 
+    #[test]
+    fn test_tick_conversions() {
+        let tick = Tick::new(-887220, 1, -1);
+        let tick_i24 = Tick::<I24>::try_from(tick).unwrap();
+        assert_eq!(tick_i24.index, I24::try_from(-887220).unwrap());
+        assert_eq!(Tick::<i32>::from(tick_i24), tick);
+        let tick = Tick {
+            index: MAX_TICK_I32 + 1,
+            liquidity_gross: 0,
+            liquidity_net: 0,
+        };
+        assert!(matches!(
+            Tick::<I24>::try_from(tick),
+            Err(Error::InvalidTick(index)) if index == MAX_TICK
+        ));
+    }
+
     #[test]
     fn test_compress() {
         assert_eq!(42.compress(60), 0);
@@ -1,4 +1,6 @@
 use crate::prelude::*;
+use alloc::sync::Arc;
+use alloy_primitives::U160;
 use core::ops::Deref;
 
 /// Provides information about ticks
@@ -29,6 +31,17 @@ pub trait TickDataProvider {
         lte: bool,
         tick_spacing: Self::Index,
     ) -> Result<(Self::Index, bool), Error>;
+
+    /// Return the sqrt ratio at a tick, see [`get_sqrt_ratio_at_tick`]. Providers may override
+    /// it to reuse the ratios across swaps, e.g. [`SqrtRatioCache`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `tick`: The tick for which to compute the sqrt ratio
+    #[inline]
+    fn sqrt_ratio_at_tick(&self, tick: Self::Index) -> Result<U160, Error> {
+        get_sqrt_ratio_at_tick(tick.to_i24())
+    }
 }
 
 /// Implements the [`TickDataProvider`] trait for any type that dereferences to a
@@ -54,11 +67,22 @@ where
         self.deref()
             .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
     }
+
+    #[inline]
+    fn sqrt_ratio_at_tick(&self, tick: Self::Index) -> Result<U160, Error> {
+        self.deref().sqrt_ratio_at_tick(tick)
+    }
 }
 
+/// A type-erased [`TickDataProvider`], so that pools with different providers, e.g. RPC-backed,
+/// cached and static ones, can be stored in one collection as `Pool<DynTickDataProvider>`, see
+/// [`Pool::into_dyn`].
+pub type DynTickDataProvider<I = i32> = Arc<dyn TickDataProvider<Index = I> + Send + Sync>;
+
 /// This tick data provider does not know how to fetch any tick data. It throws whenever it is
 /// required. Useful if you do not need to load tick data for your use case.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoTickDataProvider;
 
 impl TickDataProvider for NoTickDataProvider {
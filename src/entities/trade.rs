@@ -1,7 +1,9 @@
 use crate::prelude::{Error, *};
 use alloc::vec;
-use alloy_primitives::map::rustc_hash::FxHashSet;
+use alloy_primitives::{map::rustc_hash::FxHashSet, U160, U256};
 use core::cmp::Ordering;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use uniswap_sdk_core::prelude::{sorted_insert, *};
 
 /// Trades comparator, an extension of the input output comparator that also considers other
@@ -65,6 +67,26 @@ where
     }
 }
 
+/// The execution breakdown of a single hop of a trade, see [`Trade::hop_breakdown`].
+#[derive(Clone, Debug)]
+pub struct HopBreakdown<I = i32> {
+    /// The amount of token spent on the hop, including the fee and the transfer fee of the token
+    pub input_amount: CurrencyAmount<Token>,
+    /// The amount of token received from the hop, net of the transfer fee of the token
+    pub output_amount: CurrencyAmount<Token>,
+    /// The fee paid to the liquidity providers of the pool, in the input token
+    pub fee_amount: CurrencyAmount<Token>,
+    /// The percent difference between the mid price of the pool before the hop and the execution
+    /// price of the hop
+    pub price_impact: Percent,
+    /// The Q64.96 sqrt price of the pool after the hop
+    pub sqrt_price_x96_after: U160,
+    /// The current tick of the pool after the hop
+    pub tick_after: I,
+    /// The in-range liquidity of the pool after the hop
+    pub liquidity_after: u128,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct BestTradeOptions {
     /// how many results to return
@@ -75,6 +97,16 @@ pub struct BestTradeOptions {
 
 /// Represents a swap through a route
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "TP: serde::Serialize, TP::Index: serde::Serialize",
+        deserialize = "TInput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TOutput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TP: serde::Deserialize<'de>, TP::Index: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Swap<TInput, TOutput, TP>
 where
     TInput: BaseCurrency,
@@ -82,7 +114,15 @@ where
     TP: TickDataProvider,
 {
     pub route: Route<TInput, TOutput, TP>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::serde_helpers::currency_amount")
+    )]
     pub input_amount: CurrencyAmount<TInput>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::serde_helpers::currency_amount")
+    )]
     pub output_amount: CurrencyAmount<TOutput>,
 }
 
@@ -133,6 +173,16 @@ where
 /// Does not account for slippage, i.e., changes in price environment that can occur between the
 /// time the trade is submitted and when it is executed.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "TP: serde::Serialize, TP::Index: serde::Serialize",
+        deserialize = "TInput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TOutput: crate::utils::serde_helpers::SerdeCurrency, \
+                       TP: serde::Deserialize<'de>, TP::Index: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Trade<TInput, TOutput, TP>
 where
     TInput: BaseCurrency,
@@ -143,14 +193,22 @@ where
     /// trade.
     pub swaps: Vec<Swap<TInput, TOutput, TP>>,
     /// The type of the trade, either exact in or exact out.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::serde_helpers::TradeTypeDef")
+    )]
     pub trade_type: TradeType,
     /// The cached result of the input amount computation
+    #[cfg_attr(feature = "serde", serde(skip))]
     _input_amount: Option<CurrencyAmount<TInput>>,
     /// The cached result of the output amount computation
+    #[cfg_attr(feature = "serde", serde(skip))]
     _output_amount: Option<CurrencyAmount<TOutput>>,
     /// The cached result of the computed execution price
+    #[cfg_attr(feature = "serde", serde(skip))]
     _execution_price: Option<Price<TInput, TOutput>>,
     /// The cached result of the price impact computation
+    #[cfg_attr(feature = "serde", serde(skip))]
     _price_impact: Option<Percent>,
 }
 
@@ -225,6 +283,63 @@ where
         Self::new(swaps, trade_type)
     }
 
+    /// Creates a trade from a quote of the route without computing the result of swapping through
+    /// it, e.g. from the amount quoted by the on-chain quoter, see [`decode_quote_result`].
+    /// Useful when you trust the quote and do not have any tick data
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route that was quoted
+    /// * `amount`: The amount specified, either input or output, depending on `trade_type`
+    /// * `quoted_amount`: The raw quoted amount, the amount out for an exact input trade, or the
+    ///   amount in for an exact output trade
+    /// * `trade_type`: Whether the trade is an exact input or exact output swap
+    #[inline]
+    pub fn from_quote(
+        route: Route<TInput, TOutput, TP>,
+        amount: CurrencyAmount<impl BaseCurrency>,
+        quoted_amount: U256,
+        trade_type: TradeType,
+    ) -> Result<Self, Error> {
+        let (input_amount, output_amount) = match trade_type {
+            TradeType::ExactInput => {
+                assert!(
+                    amount.currency.wrapped().equals(route.input.wrapped()),
+                    "INPUT"
+                );
+                (
+                    CurrencyAmount::from_fractional_amount(
+                        route.input.clone(),
+                        amount.numerator,
+                        amount.denominator,
+                    )?,
+                    CurrencyAmount::from_raw_amount(
+                        route.output.clone(),
+                        quoted_amount.to_big_int(),
+                    )?,
+                )
+            }
+            TradeType::ExactOutput => {
+                assert!(
+                    amount.currency.wrapped().equals(route.output.wrapped()),
+                    "OUTPUT"
+                );
+                (
+                    CurrencyAmount::from_raw_amount(
+                        route.input.clone(),
+                        quoted_amount.to_big_int(),
+                    )?,
+                    CurrencyAmount::from_fractional_amount(
+                        route.output.clone(),
+                        amount.numerator,
+                        amount.denominator,
+                    )?,
+                )
+            }
+        };
+        Self::create_unchecked_trade(route, input_amount, output_amount, trade_type)
+    }
+
     /// When the trade consists of just a single route, this returns the route of the trade.
     #[inline]
     pub fn route(&self) -> &Route<TInput, TOutput, TP> {
@@ -367,6 +482,48 @@ where
         Ok(self._price_impact.clone().unwrap())
     }
 
+    /// Returns the execution breakdown of each hop of each swap of the trade, simulated against
+    /// the pools in their current state.
+    ///
+    /// ## Notes
+    ///
+    /// The hops are simulated with [`Pool::simulate_swap`], so their amounts account for the
+    /// transfer fees of the tokens.
+    ///
+    /// ## Returns
+    ///
+    /// The hops of each swap, in the same order as [`Self::swaps`] and the pools of their routes
+    #[inline]
+    pub fn hop_breakdown(&self) -> Result<Vec<Vec<HopBreakdown<TP::Index>>>, Error> {
+        let mut breakdown = Vec::with_capacity(self.swaps.len());
+        for swap in &self.swaps {
+            let pools = &swap.route.pools;
+            let mut hops = Vec::with_capacity(pools.len());
+            match self.trade_type {
+                TradeType::ExactInput => {
+                    let mut amount_in: CurrencyAmount<Token> = swap.input_amount.wrapped_owned()?;
+                    for pool in pools {
+                        let hop = simulate_hop(pool, amount_in, true)?;
+                        amount_in = hop.output_amount.clone();
+                        hops.push(hop);
+                    }
+                }
+                TradeType::ExactOutput => {
+                    let mut amount_out: CurrencyAmount<Token> =
+                        swap.output_amount.wrapped_owned()?;
+                    for pool in pools.iter().rev() {
+                        let hop = simulate_hop(pool, amount_out, false)?;
+                        amount_out = hop.input_amount.clone();
+                        hops.push(hop);
+                    }
+                    hops.reverse();
+                }
+            }
+            breakdown.push(hops);
+        }
+        Ok(breakdown)
+    }
+
     /// Get the minimum amount that must be received from this trade for the given slippage
     /// tolerance
     ///
@@ -813,6 +970,213 @@ where
     }
 }
 
+/// Simulates a single hop of a trade
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to swap in
+/// * `amount`: The input amount if `exact_input`, otherwise the output amount
+/// * `exact_input`: Whether the amount specified is the input amount
+fn simulate_hop<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    amount: CurrencyAmount<Token>,
+    exact_input: bool,
+) -> Result<HopBreakdown<TP::Index>, Error> {
+    let result = pool.simulate_swap(
+        &amount,
+        if exact_input {
+            TradeType::ExactInput
+        } else {
+            TradeType::ExactOutput
+        },
+        None,
+    )?;
+    if result.partial_fill {
+        return Err(Error::InsufficientLiquidity);
+    }
+    let input_amount = result.input_amount()?;
+    let output_amount = result.output_amount()?;
+    let spot_output_amount = pool.price_of(result.input_token)?.quote(&input_amount)?;
+    let price_impact = spot_output_amount
+        .subtract(&output_amount)?
+        .divide(&spot_output_amount)?;
+    Ok(HopBreakdown {
+        fee_amount: CurrencyAmount::from_raw_amount(
+            result.input_token.clone(),
+            result.fee_amount.clone(),
+        )?,
+        input_amount,
+        output_amount,
+        price_impact: Percent::new(price_impact.numerator, price_impact.denominator),
+        sqrt_price_x96_after: result.state_after.sqrt_ratio_x96,
+        tick_after: result.state_after.tick_current,
+        liquidity_after: result.state_after.liquidity,
+    })
+}
+
+#[cfg(feature = "rayon")]
+impl<TInput, TOutput, TP> Trade<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency + Send + Sync,
+    TOutput: BaseCurrency + Send + Sync,
+    TP: Clone + TickDataProvider + Send + Sync,
+    TP::Index: Send + Sync,
+{
+    /// Same as [`Self::best_trade_exact_in`], but explores the candidate routes starting from each
+    /// first-hop pool in parallel on the rayon thread pool.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools to consider in finding the best trade
+    /// * `currency_amount_in`: The exact amount of input currency to spend
+    /// * `currency_out`: The desired currency out
+    /// * `best_trade_options`: Maximum number of results to return and maximum number of hops a
+    ///   returned trade can make, e.g. 1 hop goes through a single pool
+    #[inline]
+    pub fn best_trade_exact_in_parallel(
+        pools: &[Pool<TP>],
+        currency_amount_in: &CurrencyAmount<TInput>,
+        currency_out: &TOutput,
+        best_trade_options: BestTradeOptions,
+    ) -> Result<Vec<Self>, Error> {
+        assert!(!pools.is_empty(), "POOLS");
+        let max_num_results = best_trade_options.max_num_results.unwrap_or(3);
+        let max_hops = best_trade_options.max_hops.unwrap_or(3);
+        assert!(max_hops > 0, "MAX_HOPS");
+        let amount_in = currency_amount_in.wrapped()?;
+        let token_out = currency_out.wrapped();
+        let branches = pools
+            .par_iter()
+            .enumerate()
+            .map(|(i, pool)| {
+                let mut best_trades = Vec::new();
+                if !pool.involves_token(&amount_in.currency) {
+                    return Ok(best_trades);
+                }
+                let amount_out = match pool.get_output_amount(&amount_in, None) {
+                    Ok(amount_out) => amount_out,
+                    Err(Error::InsufficientLiquidity) => return Ok(best_trades),
+                    Err(e) => return Err(e),
+                };
+                if !amount_out.currency.is_native() && amount_out.currency.equals(token_out) {
+                    let trade = Self::from_route(
+                        Route::new(
+                            vec![pool.clone()],
+                            currency_amount_in.currency.clone(),
+                            currency_out.clone(),
+                        ),
+                        currency_amount_in.wrapped()?,
+                        TradeType::ExactInput,
+                    )?;
+                    best_trades.push(trade);
+                } else if max_hops > 1 && pools.len() > 1 {
+                    let pools_excluding_this_pool = pools
+                        .iter()
+                        .take(i)
+                        .chain(pools.iter().skip(i + 1))
+                        .cloned()
+                        .collect();
+                    Self::best_trade_exact_in(
+                        pools_excluding_this_pool,
+                        currency_amount_in,
+                        currency_out,
+                        BestTradeOptions {
+                            max_num_results: Some(max_num_results),
+                            max_hops: Some(max_hops - 1),
+                        },
+                        vec![pool.clone()],
+                        Some(amount_out.wrapped()?),
+                        &mut best_trades,
+                    )?;
+                }
+                Ok(best_trades)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut best_trades = Vec::with_capacity(max_num_results);
+        for trade in branches.into_iter().flatten() {
+            sorted_insert(&mut best_trades, trade, max_num_results, trade_comparator);
+        }
+        Ok(best_trades)
+    }
+
+    /// Same as [`Self::best_trade_exact_out`], but explores the candidate routes ending with each
+    /// last-hop pool in parallel on the rayon thread pool.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools to consider in finding the best trade
+    /// * `currency_in`: The currency to spend
+    /// * `currency_amount_out`: The desired currency amount out
+    /// * `best_trade_options`: Maximum number of results to return and maximum number of hops a
+    ///   returned trade can make, e.g. 1 hop goes through a single pool
+    #[inline]
+    pub fn best_trade_exact_out_parallel(
+        pools: &[Pool<TP>],
+        currency_in: &TInput,
+        currency_amount_out: &CurrencyAmount<TOutput>,
+        best_trade_options: BestTradeOptions,
+    ) -> Result<Vec<Self>, Error> {
+        assert!(!pools.is_empty(), "POOLS");
+        let max_num_results = best_trade_options.max_num_results.unwrap_or(3);
+        let max_hops = best_trade_options.max_hops.unwrap_or(3);
+        assert!(max_hops > 0, "MAX_HOPS");
+        let amount_out = currency_amount_out.wrapped()?;
+        let token_in = currency_in.wrapped();
+        let branches = pools
+            .par_iter()
+            .enumerate()
+            .map(|(i, pool)| {
+                let mut best_trades = Vec::new();
+                if !pool.involves_token(&amount_out.currency) {
+                    return Ok(best_trades);
+                }
+                let amount_in = match pool.get_input_amount(&amount_out, None) {
+                    Ok(amount_in) => amount_in,
+                    Err(Error::InsufficientLiquidity) => return Ok(best_trades),
+                    Err(e) => return Err(e),
+                };
+                if amount_in.currency.equals(token_in) {
+                    let trade = Self::from_route(
+                        Route::new(
+                            vec![pool.clone()],
+                            currency_in.clone(),
+                            currency_amount_out.currency.clone(),
+                        ),
+                        currency_amount_out.wrapped()?,
+                        TradeType::ExactOutput,
+                    )?;
+                    best_trades.push(trade);
+                } else if max_hops > 1 && pools.len() > 1 {
+                    let pools_excluding_this_pool = pools
+                        .iter()
+                        .take(i)
+                        .chain(pools.iter().skip(i + 1))
+                        .cloned()
+                        .collect();
+                    Self::best_trade_exact_out(
+                        pools_excluding_this_pool,
+                        currency_in,
+                        currency_amount_out,
+                        BestTradeOptions {
+                            max_num_results: Some(max_num_results),
+                            max_hops: Some(max_hops - 1),
+                        },
+                        vec![pool.clone()],
+                        Some(amount_in.wrapped()?),
+                        &mut best_trades,
+                    )?;
+                }
+                Ok(best_trades)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut best_trades = Vec::with_capacity(max_num_results);
+        for trade in branches.into_iter().flatten() {
+            sorted_insert(&mut best_trades, trade, max_num_results, trade_comparator);
+        }
+        Ok(best_trades)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1130,6 +1494,58 @@ mod tests {
         }
     }
 
+    mod from_quote {
+        use super::*;
+
+        #[test]
+        fn exact_input() {
+            let trade = Trade::from_quote(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                U256::from(9000),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_amount().unwrap().quotient(), 10000.into());
+            assert_eq!(trade.output_amount().unwrap().quotient(), 9000.into());
+            assert_eq!(
+                trade
+                    .minimum_amount_out(Percent::new(1, 100), None)
+                    .unwrap()
+                    .quotient(),
+                8910.into()
+            );
+        }
+
+        #[test]
+        fn exact_output() {
+            let trade = Trade::from_quote(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 9000).unwrap(),
+                U256::from(10000),
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_amount().unwrap().quotient(), 10000.into());
+            assert_eq!(trade.output_amount().unwrap().quotient(), 9000.into());
+            assert_eq!(
+                trade.execution_price().unwrap(),
+                Price::new(TOKEN0.clone(), TOKEN1.clone(), 10000, 9000)
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "INPUT")]
+        fn throws_if_amount_is_not_the_input() {
+            let _ = Trade::from_quote(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 10000).unwrap(),
+                U256::from(9000),
+                TradeType::ExactInput,
+            );
+        }
+    }
+
     mod create_unchecked_trade_with_multiple_routes {
         use super::*;
 
@@ -2412,4 +2828,183 @@ mod tests {
             assert_eq!(result[1].output_amount().unwrap().currency, ETHER.clone());
         }
     }
+
+    mod hop_breakdown {
+        use super::*;
+
+        #[test]
+        fn exact_input() {
+            let trade = Trade::exact_in(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                ),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+            )
+            .unwrap();
+            let breakdown = trade.hop_breakdown().unwrap();
+            assert_eq!(breakdown.len(), 1);
+            let hops = &breakdown[0];
+            assert_eq!(hops.len(), 2);
+            assert_eq!(
+                hops[0].input_amount.quotient(),
+                trade.input_amount().unwrap().quotient()
+            );
+            assert_eq!(hops[0].fee_amount.currency, TOKEN0.clone());
+            assert_eq!(hops[0].fee_amount.quotient(), 30.into());
+            assert_eq!(hops[1].input_amount, hops[0].output_amount);
+            assert_eq!(hops[1].fee_amount.currency, TOKEN1.clone());
+            assert_eq!(
+                hops[1].output_amount.quotient(),
+                trade.output_amount().unwrap().quotient()
+            );
+            assert!(hops[0].sqrt_price_x96_after < POOL_0_1.sqrt_ratio_x96);
+            assert!(hops[0].price_impact.numerator > BigInt::ZERO);
+        }
+
+        #[test]
+        fn exact_output() {
+            let trade = Trade::exact_out(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                ),
+                CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap(),
+            )
+            .unwrap();
+            let hops = &trade.hop_breakdown().unwrap()[0];
+            assert_eq!(hops.len(), 2);
+            assert_eq!(
+                hops[0].input_amount.quotient(),
+                trade.input_amount().unwrap().quotient()
+            );
+            assert_eq!(hops[1].input_amount, hops[0].output_amount);
+            assert_eq!(hops[1].output_amount.quotient(), 10000.into());
+        }
+
+        #[test]
+        fn transfer_fees() {
+            // 1% fee on transfers out of the pool and 2% on transfers into the pool
+            let taxed = Token::new(
+                1,
+                TOKEN0.address(),
+                18,
+                None,
+                None,
+                Some(BigUint::from(100_u32)),
+                Some(BigUint::from(200_u32)),
+            );
+            let pool = make_pool(taxed.clone(), TOKEN1.clone());
+            let amount_in = CurrencyAmount::from_raw_amount(taxed.clone(), 10000).unwrap();
+            let trade = Trade::exact_in(
+                Route::new(vec![pool.clone()], taxed.clone(), TOKEN1.clone()),
+                amount_in.clone(),
+            )
+            .unwrap();
+            let hop = &trade.hop_breakdown().unwrap()[0][0];
+            assert_eq!(hop.input_amount, amount_in);
+            assert_eq!(
+                hop.output_amount,
+                pool.get_output_amount_with_transfer_fees(&amount_in, None)
+                    .unwrap()
+            );
+            assert!(hop.output_amount.quotient() < trade.output_amount().unwrap().quotient());
+            assert_eq!(hop.fee_amount.currency, taxed);
+
+            // the input amount covers the transfer fee of the taxed token
+            let amount_out = CurrencyAmount::from_raw_amount(taxed.clone(), 10000).unwrap();
+            let trade = Trade::exact_out(
+                Route::new(vec![pool.clone()], TOKEN1.clone(), taxed.clone()),
+                amount_out.clone(),
+            )
+            .unwrap();
+            let hop = &trade.hop_breakdown().unwrap()[0][0];
+            assert_eq!(hop.output_amount, amount_out);
+            assert_eq!(
+                hop.input_amount,
+                pool.get_input_amount_with_transfer_fees(&amount_out, None)
+                    .unwrap()
+            );
+            assert!(hop.input_amount.quotient() > trade.input_amount().unwrap().quotient());
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    mod best_trade_parallel {
+        use super::*;
+
+        static POOLS: Lazy<Vec<Pool<TickListDataProvider>>> = Lazy::new(|| {
+            vec![
+                POOL_0_1.clone(),
+                POOL_0_2.clone(),
+                POOL_0_3.clone(),
+                POOL_1_2.clone(),
+                POOL_1_3.clone(),
+            ]
+        });
+
+        #[test]
+        fn exact_in_matches_sequential() {
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap();
+            let mut expected = vec![];
+            Trade::best_trade_exact_in(
+                POOLS.clone(),
+                &amount_in,
+                &TOKEN3.clone(),
+                BestTradeOptions::default(),
+                vec![],
+                None,
+                &mut expected,
+            )
+            .unwrap();
+            let result = Trade::best_trade_exact_in_parallel(
+                &POOLS,
+                &amount_in,
+                &TOKEN3.clone(),
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), expected.len());
+            for (a, b) in result.iter().zip(&expected) {
+                assert_eq!(a.swaps[0].route.token_path(), b.swaps[0].route.token_path());
+                assert_eq!(
+                    a.output_amount().unwrap().quotient(),
+                    b.output_amount().unwrap().quotient()
+                );
+            }
+        }
+
+        #[test]
+        fn exact_out_matches_sequential() {
+            let amount_out = CurrencyAmount::from_raw_amount(TOKEN3.clone(), 10000).unwrap();
+            let mut expected = vec![];
+            Trade::best_trade_exact_out(
+                POOLS.clone(),
+                &TOKEN0.clone(),
+                &amount_out,
+                BestTradeOptions::default(),
+                vec![],
+                None,
+                &mut expected,
+            )
+            .unwrap();
+            let result = Trade::best_trade_exact_out_parallel(
+                &POOLS,
+                &TOKEN0.clone(),
+                &amount_out,
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), expected.len());
+            for (a, b) in result.iter().zip(&expected) {
+                assert_eq!(a.swaps[0].route.token_path(), b.swaps[0].route.token_path());
+                assert_eq!(
+                    a.input_amount().unwrap().quotient(),
+                    b.input_amount().unwrap().quotient()
+                );
+            }
+        }
+    }
 }
@@ -53,16 +53,112 @@ pub enum Error {
     #[error("Insufficient liquidity")]
     InsufficientLiquidity,
 
+    /// Thrown when the transfer fee of a token is 100% or more, so that no amount of it can be
+    /// received.
+    #[error("Invalid transfer fee")]
+    InvalidFee,
+
     #[error("No tick data provider was given")]
     NoTickDataError,
 
+    /// Thrown when no route is found between the input and output currencies.
+    #[error("No route found")]
+    NoRouteFound,
+
+    /// Thrown when no pool matching the tokens and fee tier is found.
+    #[error("Pool not found")]
+    PoolNotFound,
+
+    /// Thrown by [`decode_path`] when the packed path is not a token followed by fee and token
+    /// pairs.
+    #[error("Invalid path")]
+    InvalidPath,
+
     #[error("{0}")]
     TickListError(#[from] TickListError),
 
-    #[cfg(feature = "extensions")]
+    /// Thrown by [`Route::validate`] when the pools of a route do not form a path between its
+    /// input and output currencies.
+    #[error("{0}")]
+    RouteError(#[from] RouteError),
+
+    /// Thrown when the upper tick of a range is not above its lower tick, or a range width is not
+    /// positive.
     #[error("Invalid tick range")]
     InvalidRange,
 
+    /// Thrown by [`PositionBuilder::build`] when the size of a position is missing or inconsistent
+    /// with its range, e.g. an amount of a token that the range does not hold, or zero liquidity,
+    /// by the zaps when the amounts make a position of zero or overflowing liquidity, and by
+    /// [`get_positions_for_owner`] when the owner's position balance overflows `usize`.
+    #[error("Invalid position amounts")]
+    InvalidAmounts,
+
+    /// Thrown by [`PositionBuilder::build_with_mint_amounts`] when the slippage tolerance is not
+    /// between 0 and 100%, exclusive of 100%.
+    #[error("Invalid slippage tolerance")]
+    InvalidSlippageTolerance,
+
+    /// Thrown by [`swap_call_parameters`] when the price impact of a trade exceeds the
+    /// [`SwapOptions::max_price_impact`].
+    #[error("Price impact too high")]
+    PriceImpactTooHigh,
+
+    /// Thrown by [`swap_batch_call_parameters`] when no trades are given.
+    #[error("No trades")]
+    NoTrades,
+
+    /// Thrown when the proportion of a position value held in token0 is not between 0 and 1,
+    /// inclusive.
+    #[error("Invalid token0 ratio: must be a value between 0 and 1, inclusive")]
+    InvalidToken0Ratio,
+
+    /// Thrown by [`price_to_sqrt_ratio_x96`] and [`token0_price_to_ratio`] when the price is
+    /// negative.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid price: must be non-negative")]
+    NegativePrice,
+
+    /// Thrown by [`parse_price`] when the price string is not a decimal number.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid price string")]
+    InvalidPriceString,
+
+    /// Thrown when a sampling interval is zero or does not divide its window, or the window splits
+    /// into more samples than can be fetched at once.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid sampling interval")]
+    InvalidInterval,
+
+    /// Thrown when the end of a period is before its start, events are not in chronological order,
+    /// or the cumulatives of an oracle did not grow over their window.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid time range")]
+    InvalidTimeRange,
+
+    /// Thrown by [`plan_observation_cardinality_increase`] when the gas limit of a transaction
+    /// cannot cover a single slot of the observation array.
+    #[cfg(feature = "extensions")]
+    #[error("Gas limit too low")]
+    GasLimitTooLow,
+
+    /// Thrown when a pool fetched from the chain has been created but not yet initialized.
+    #[cfg(feature = "extensions")]
+    #[error("Pool has been created but not yet initialized")]
+    PoolNotInitialized,
+
+    /// Thrown by [`zap_in_call_parameters`] when asked to create the pool, which the swap router
+    /// cannot do.
+    #[cfg(feature = "extensions")]
+    #[error("Pool creation is not supported")]
+    CreatePoolNotSupported,
+
+    /// Thrown by [`LazyTickDataProvider`] when a swap walks into a tick bitmap word that has not
+    /// been fetched yet.
+    #[cfg(feature = "extensions")]
+    #[error("Tick bitmap word not fetched: {0}")]
+    WordNotFetched(I24),
+
     #[cfg(feature = "extensions")]
     #[error("{0}")]
     ContractError(#[from] ContractError),
@@ -71,9 +167,50 @@ pub enum Error {
     #[error("{0}")]
     LensError(#[from] LensError),
 
+    /// Thrown by [`ticks_from_csv`] and [`ticks_from_json`] when a record is malformed or a
+    /// required column is missing, or when oracle cumulatives imply a tick out of range.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid tick data: {0}")]
+    InvalidTickData(alloc::string::String),
+
+    /// Thrown by [`PoolEvent::decode_log`] when the log is not an event of a pool.
+    #[cfg(feature = "extensions")]
+    #[error("Unknown pool event")]
+    UnknownEvent,
+
     #[cfg(feature = "extensions")]
     #[error("Invalid access list")]
     InvalidAccessList,
+
+    /// Thrown by [`get_erc20_permit_domain`] when no known domain of the token matches its
+    /// `DOMAIN_SEPARATOR`.
+    #[cfg(feature = "extensions")]
+    #[error("Unknown EIP-712 domain")]
+    UnknownDomain,
+
+    /// Thrown by [`verify_permit`] and [`verify_nft_permit`] when a permit would be rejected.
+    #[cfg(feature = "extensions")]
+    #[error("{0}")]
+    PermitError(#[from] PermitError),
+
+    #[cfg(feature = "signer")]
+    #[error("{0}")]
+    SignerError(#[from] alloy::signers::Error),
+
+    /// Thrown when a query to the subgraph fails or returns an unexpected response.
+    #[cfg(feature = "subgraph")]
+    #[error("Subgraph error: {0}")]
+    SubgraphError(alloc::string::String),
+
+    /// Thrown by [`TokenList::from_json`] when the JSON is not a valid token list.
+    #[cfg(feature = "token_list")]
+    #[error("Token list error: {0}")]
+    TokenListError(alloc::string::String),
+
+    /// Thrown when a tick snapshot cannot be encoded, or its bytes are not a valid snapshot.
+    #[cfg(feature = "bincode")]
+    #[error("Snapshot error: {0}")]
+    SnapshotError(alloc::string::String),
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, thiserror::Error)]
@@ -86,9 +223,65 @@ pub enum TickListError {
     NotContained,
 }
 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, thiserror::Error)]
+pub enum RouteError {
+    #[error("Route has no pools")]
+    NoPools,
+    #[error("Pools and currencies are not on the same chain")]
+    ChainIdMismatch,
+    #[error("Pool tokens are not sorted")]
+    UnsortedTokens,
+    #[error("Duplicate pool in route")]
+    DuplicatePool,
+    #[error("Input currency not in the first pool")]
+    InvalidInput,
+    #[error("Output currency not in the last pool")]
+    InvalidOutput,
+    #[error("Pools do not form a path")]
+    InvalidPath,
+}
+
+#[cfg(feature = "extensions")]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, thiserror::Error)]
+pub enum PermitError {
+    #[error("Invalid permit signature")]
+    InvalidSignature,
+    #[error("Permit signed by {0}")]
+    WrongSigner(alloy_primitives::Address),
+    #[error("Permit expired")]
+    Expired,
+    #[error("Invalid permit nonce")]
+    InvalidNonce,
+}
+
 #[cfg(feature = "extensions")]
 impl From<alloy::transports::TransportError> for Error {
     fn from(e: alloy::transports::TransportError) -> Self {
         Self::ContractError(ContractError::TransportError(e))
     }
 }
+
+#[cfg(feature = "extensions")]
+impl Error {
+    /// Whether the error is a transient failure of the RPC, e.g. a rate limit or a dropped
+    /// connection, rather than a failure of the call itself, so that retrying the call may succeed.
+    /// See [`RetryPolicy`].
+    #[inline]
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        use alloy::transports::RpcError;
+
+        match self {
+            Self::ContractError(ContractError::TransportError(error))
+            | Self::LensError(LensError::ContractError(ContractError::TransportError(error))) => {
+                match error {
+                    RpcError::Transport(kind) => kind.is_retry_err(),
+                    RpcError::ErrorResp(payload) => payload.is_retry_err(),
+                    RpcError::NullResp => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
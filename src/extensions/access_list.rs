@@ -0,0 +1,95 @@
+//! ## Access List
+//! This module provides functions to generate an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)
+//! access list for the [`MethodParameters`] produced by the SDK, e.g. swaps crossing many ticks or
+//! position mints, so that the storage slots touched are pre-warmed at a discount.
+
+use crate::prelude::{Error, MethodParameters};
+use alloy::{
+    eips::{eip2930::AccessList, BlockId, BlockNumberOrTag},
+    network::{Network, TransactionBuilder},
+    providers::Provider,
+};
+use alloy_primitives::Address;
+
+/// Generate an access list for the transaction described by the method parameters using
+/// `eth_createAccessList`.
+///
+/// Entries for the sender and the target without any storage keys are dropped since both accounts
+/// are already warm at the start of the transaction and listing them would only add cost.
+///
+/// ## Arguments
+///
+/// * `method_parameters`: The calldata and value of the transaction
+/// * `from`: The sender of the transaction
+/// * `to`: The contract to call, e.g. the swap router or the nonfungible position manager
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_access_list<N, P>(
+    method_parameters: &MethodParameters,
+    from: Address,
+    to: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<AccessList, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let tx = N::TransactionRequest::default()
+        .with_from(from)
+        .with_to(to)
+        .with_input(method_parameters.calldata.clone())
+        .with_value(method_parameters.value);
+    let access_list = provider
+        .create_access_list(&tx)
+        .block_id(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .await?
+        .access_list;
+    Ok(AccessList(
+        access_list
+            .0
+            .into_iter()
+            .filter(|item| {
+                !(item.storage_keys.is_empty() && (item.address == from || item.address == to))
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+    use uniswap_sdk_core::prelude::*;
+
+    const QUOTER: Address = address!("b27308f9F90D607463bb33eA1BeBb41C27CE5AB6");
+
+    #[tokio::test]
+    async fn test_get_access_list() {
+        let pool = Pool::new(
+            USDC.clone(),
+            WETH.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        let pool_address = pool.address(None, None);
+        let route = Route::new(vec![pool], USDC.clone(), WETH.clone());
+        let amount = CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000_000_000).unwrap();
+        let params = quote_call_parameters(&route, &amount, TradeType::ExactInput, None);
+        let access_list =
+            get_access_list(&params, Address::ZERO, QUOTER, &PROVIDER.clone(), *BLOCK_ID)
+                .await
+                .unwrap();
+        let pool_item = access_list
+            .0
+            .iter()
+            .find(|item| item.address == pool_address)
+            .unwrap();
+        assert!(!pool_item.storage_keys.is_empty());
+        assert!(access_list.0.iter().all(|item| item.address != QUOTER));
+    }
+}
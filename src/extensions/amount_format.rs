@@ -0,0 +1,246 @@
+//! ## Amount Format
+//! Display helpers for the [`CurrencyAmount`] results of the SDK: fixed decimals, significant
+//! figures, thousands grouping and compact notation. The amounts are formatted from their raw
+//! quotient and the decimals of their currency, rounding half up like the TypeScript SDK.
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use uniswap_sdk_core::prelude::*;
+
+/// The suffixes of the compact notation, by power of a thousand
+const COMPACT_SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+/// Formatting of a [`CurrencyAmount`] in units of its currency, rounding half up.
+pub trait AmountFormat {
+    /// Formats the amount with exactly `decimal_places` decimals, e.g. `1234.50`.
+    ///
+    /// ## Notes
+    ///
+    /// The amount is padded with zeros if `decimal_places` exceeds the decimals of the currency.
+    #[must_use]
+    fn format_fixed(&self, decimal_places: u8) -> String;
+
+    /// Formats the amount to `significant_digits` significant figures without trailing zeros,
+    /// e.g. `1234.5` or `0.0012346`, like `toSignificant` of the TypeScript SDK.
+    #[must_use]
+    fn format_significant(&self, significant_digits: u8) -> String;
+
+    /// Formats the amount with exactly `decimal_places` decimals and the integer part grouped by
+    /// thousands with commas, e.g. `1,234,567.89`.
+    #[must_use]
+    fn format_grouped(&self, decimal_places: u8) -> String;
+
+    /// Formats the amount to `significant_digits` significant figures in compact notation with the
+    /// suffixes `K`, `M`, `B` and `T`, e.g. `1.23M`. Amounts below a thousand are formatted as by
+    /// [`AmountFormat::format_significant`].
+    #[must_use]
+    fn format_compact(&self, significant_digits: u8) -> String;
+}
+
+impl<T: BaseCurrency> AmountFormat for CurrencyAmount<T> {
+    #[inline]
+    fn format_fixed(&self, decimal_places: u8) -> String {
+        Digits::new(self)
+            .round(decimal_places.into())
+            .render(decimal_places.into(), false)
+    }
+
+    #[inline]
+    fn format_significant(&self, significant_digits: u8) -> String {
+        let digits = Digits::new(self)
+            .round_significant(significant_digits)
+            .trim();
+        digits.render(digits.scale.max(0), false)
+    }
+
+    #[inline]
+    fn format_grouped(&self, decimal_places: u8) -> String {
+        Digits::new(self)
+            .round(decimal_places.into())
+            .render(decimal_places.into(), true)
+    }
+
+    #[inline]
+    fn format_compact(&self, significant_digits: u8) -> String {
+        let mut digits = Digits::new(self).round_significant(significant_digits);
+        // the tier is chosen after rounding, so that 999,950 to 4 figures is 1M and not 1000K
+        let exponent = digits.exponent();
+        let tier = if exponent > 3 {
+            ((exponent - 1) / 3).min(COMPACT_SUFFIXES.len() as i64 - 1)
+        } else {
+            0
+        };
+        digits.scale += 3 * tier;
+        let digits = digits.trim();
+        let mut formatted = digits.render(digits.scale.max(0), false);
+        formatted.push_str(COMPACT_SUFFIXES[tier as usize]);
+        formatted
+    }
+}
+
+/// A decimal number as its digits times ten to the power of minus `scale`
+#[derive(Clone, Debug)]
+struct Digits {
+    negative: bool,
+    /// The decimal digits, most significant first, without leading zeros, empty for zero
+    digits: Vec<u8>,
+    /// The number of digits after the decimal point, negative for trailing integer zeros
+    scale: i64,
+}
+
+impl Digits {
+    fn new<T: BaseCurrency>(amount: &CurrencyAmount<T>) -> Self {
+        let raw = amount.quotient().to_string();
+        let (negative, magnitude) = match raw.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, raw.as_str()),
+        };
+        Self {
+            negative,
+            digits: magnitude
+                .bytes()
+                .map(|digit| digit - b'0')
+                .skip_while(|&digit| digit == 0)
+                .collect(),
+            scale: amount.currency.decimals().into(),
+        }
+    }
+
+    /// The number of digits before the decimal point, zero or negative below 0.1
+    fn exponent(&self) -> i64 {
+        self.digits.len() as i64 - self.scale
+    }
+
+    /// Rounds half up, away from zero, to at most `scale` digits after the decimal point
+    fn round(mut self, scale: i64) -> Self {
+        if scale >= self.scale {
+            return self;
+        }
+        let dropped = (self.scale - scale) as usize;
+        let len = self.digits.len();
+        let round_up = dropped <= len && self.digits[len - dropped] >= 5;
+        self.digits.truncate(len.saturating_sub(dropped));
+        if round_up {
+            // add one to the last digit, carrying over the nines
+            match self.digits.iter().rposition(|&digit| digit != 9) {
+                Some(index) => {
+                    self.digits[index] += 1;
+                    self.digits[index + 1..].fill(0);
+                }
+                None => {
+                    self.digits.fill(0);
+                    self.digits.insert(0, 1);
+                }
+            }
+        }
+        self.scale = scale;
+        self
+    }
+
+    /// Rounds half up to `significant_digits` significant figures
+    fn round_significant(self, significant_digits: u8) -> Self {
+        let scale = i64::from(significant_digits) - self.exponent();
+        self.round(scale)
+    }
+
+    /// Removes the trailing zeros after the decimal point
+    fn trim(mut self) -> Self {
+        if self.digits.is_empty() {
+            self.scale = 0;
+        }
+        while self.scale > 0 && self.digits.last() == Some(&0) {
+            self.digits.pop();
+            self.scale -= 1;
+        }
+        self
+    }
+
+    /// Renders the number with exactly `decimal_places` decimals, which must be at least `scale`
+    fn render(&self, decimal_places: i64, grouped: bool) -> String {
+        let mut digits: Vec<u8> = self.digits.iter().map(|digit| digit + b'0').collect();
+        digits.resize(digits.len() + (decimal_places - self.scale) as usize, b'0');
+        let decimal_places = decimal_places as usize;
+        if digits.len() <= decimal_places {
+            let mut padded = vec![b'0'; decimal_places + 1 - digits.len()];
+            padded.append(&mut digits);
+            digits = padded;
+        }
+        let (integer, fraction) = digits.split_at(digits.len() - decimal_places);
+        let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 2);
+        if self.negative && !self.digits.is_empty() {
+            formatted.push('-');
+        }
+        for (i, &digit) in integer.iter().enumerate() {
+            if grouped && i > 0 && (integer.len() - i) % 3 == 0 {
+                formatted.push(',');
+            }
+            formatted.push(digit as char);
+        }
+        if !fraction.is_empty() {
+            formatted.push('.');
+            formatted.extend(fraction.iter().map(|&digit| digit as char));
+        }
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn usdc(raw: i128) -> CurrencyAmount<Token> {
+        CurrencyAmount::from_raw_amount(USDC.clone(), raw).unwrap()
+    }
+
+    #[test]
+    fn test_format_fixed() {
+        assert_eq!(usdc(1_234_567_890).format_fixed(2), "1234.57");
+        assert_eq!(usdc(1_234_564_999).format_fixed(2), "1234.56");
+        assert_eq!(usdc(999_995).format_fixed(2), "1.00");
+        assert_eq!(usdc(1_500_000).format_fixed(0), "2");
+        assert_eq!(usdc(123).format_fixed(6), "0.000123");
+        assert_eq!(usdc(123).format_fixed(8), "0.00012300");
+        assert_eq!(usdc(0).format_fixed(2), "0.00");
+        assert_eq!(usdc(-1_234_567).format_fixed(1), "-1.2");
+        assert_eq!(usdc(-1).format_fixed(2), "0.00");
+    }
+
+    #[test]
+    fn test_format_significant() {
+        assert_eq!(usdc(1_234_567_890).format_significant(6), "1234.57");
+        assert_eq!(usdc(1_234_567_890).format_significant(2), "1200");
+        assert_eq!(usdc(1_500_000).format_significant(6), "1.5");
+        assert_eq!(usdc(1_234).format_significant(2), "0.0012");
+        assert_eq!(usdc(9_999_999).format_significant(3), "10");
+        assert_eq!(usdc(0).format_significant(6), "0");
+        let amount = CurrencyAmount::from_raw_amount(WETH.clone(), 10_u128.pow(18) / 3).unwrap();
+        assert_eq!(amount.format_significant(4), "0.3333");
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        assert_eq!(usdc(1_234_567_890_000).format_grouped(2), "1,234,567.89");
+        assert_eq!(usdc(123_456_000_000).format_grouped(0), "123,456");
+        assert_eq!(usdc(999_000_000).format_grouped(1), "999.0");
+        assert_eq!(usdc(-1_000_000_000).format_grouped(0), "-1,000");
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(usdc(999_000_000).format_compact(3), "999");
+        assert_eq!(usdc(1_234_000_000).format_compact(3), "1.23K");
+        assert_eq!(usdc(12_000_000_000_000).format_compact(3), "12M");
+        assert_eq!(usdc(999_950_000_000).format_compact(4), "1M");
+        assert_eq!(usdc(4_560_000_000_000_000).format_compact(3), "4.56B");
+        assert_eq!(
+            usdc(1_230_000_000_000_000_000_000).format_compact(3),
+            "1230T"
+        );
+        assert_eq!(usdc(1_234).format_compact(3), "0.00123");
+        assert_eq!(usdc(-2_500_000_000).format_compact(2), "-2.5K");
+    }
+}
@@ -0,0 +1,260 @@
+//! ## Arbitrage
+//! Detects and sizes the arbitrage between two pools of the same pair, e.g. of different fee tiers
+//! or forks: buying in the pool where the price is lower and selling in the other until their
+//! marginal prices net of fees are equal.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::U24, I256, U160, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// An arbitrage between two pools, see [`get_arbitrage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Arbitrage {
+    /// Whether the input is swapped in `pool_a` and the intermediate amount in `pool_b`, otherwise
+    /// the reverse
+    pub a_to_b: bool,
+    /// Whether the input is token0, in which case the first swap sells token0 for token1 and the
+    /// second swap buys it back
+    pub zero_for_one: bool,
+    /// The amount of the input token swapped in the first pool
+    pub amount_in: U256,
+    /// The amount of the other token received from the first pool and swapped in the second
+    pub amount_intermediate: U256,
+    /// The amount of the input token received from the second pool
+    pub amount_out: U256,
+    /// The profit in the input token before gas, i.e. `amount_out - amount_in`
+    pub profit: U256,
+}
+
+/// Whether swapping more of the input token in the first pool and selling the output in the second
+/// pool is profitable at the margin, i.e. whether the price of token0 net of the fees of both pools
+/// is higher in the pool where it is sold than in the pool where it is bought
+fn is_profitable(
+    zero_for_one: bool,
+    sqrt_price_first_x96: U160,
+    sqrt_price_second_x96: U160,
+    fee_first: FeeAmount,
+    fee_second: FeeAmount,
+) -> bool {
+    let (sqrt_price_sell, sqrt_price_buy) = if zero_for_one {
+        (sqrt_price_first_x96, sqrt_price_second_x96)
+    } else {
+        (sqrt_price_second_x96, sqrt_price_first_x96)
+    };
+    let fee_complement = |fee: FeeAmount| BigInt::from(1_000_000 - U24::from(fee).to::<u32>());
+    let sqrt_price_sell = sqrt_price_sell.to_big_int();
+    let sqrt_price_buy = sqrt_price_buy.to_big_int();
+    &sqrt_price_sell * &sqrt_price_sell * fee_complement(fee_first) * fee_complement(fee_second)
+        > &sqrt_price_buy * &sqrt_price_buy * BigInt::from(1_000_000_000_000_u64)
+}
+
+/// Solves for the input of the arbitrage that swaps in `first` then in `second`, if profitable
+fn solve<TP1: TickDataProvider, TP2: TickDataProvider>(
+    first: &Pool<TP1>,
+    second: &Pool<TP2>,
+    zero_for_one: bool,
+) -> Result<Option<(U256, U256, U256)>, Error> {
+    if !is_profitable(
+        zero_for_one,
+        first.sqrt_ratio_x96,
+        second.sqrt_ratio_x96,
+        first.fee,
+        second.fee,
+    ) {
+        return Ok(None);
+    }
+
+    // Simulates the two swaps for an input amount and returns the intermediate and output
+    // amounts, and whether a larger input is still profitable at the margin.
+    let simulate = |amount_in: U256| -> Result<(U256, U256, bool), Error> {
+        let state = first._swap(zero_for_one, I256::from_raw(amount_in), None)?;
+        if !state.amount_specified_remaining.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let amount_intermediate = (-state.amount_calculated).into_raw();
+        let state_second =
+            second._swap(!zero_for_one, I256::from_raw(amount_intermediate), None)?;
+        if !state_second.amount_specified_remaining.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let amount_out = (-state_second.amount_calculated).into_raw();
+        let profitable = is_profitable(
+            zero_for_one,
+            state.sqrt_price_x96,
+            state_second.sqrt_price_x96,
+            first.fee,
+            second.fee,
+        );
+        Ok((amount_intermediate, amount_out, profitable))
+    };
+
+    // moving the price of the first pool to the price of the second is never profitable at the
+    // margin, which bounds the input
+    let state = first._swap(zero_for_one, I256::MAX, Some(second.sqrt_ratio_x96))?;
+    let (mut lo, mut hi) = (
+        U256::ZERO,
+        (I256::MAX - state.amount_specified_remaining).into_raw(),
+    );
+    while hi - lo > U256::from(1) {
+        let mid = lo + ((hi - lo) >> 1);
+        match simulate(mid) {
+            Ok((_, _, true)) => lo = mid,
+            // a swap running out of liquidity is never profitable
+            Ok(_) | Err(Error::InsufficientLiquidity) => hi = mid,
+            Err(e) => return Err(e),
+        }
+    }
+    let (amount_intermediate, amount_out, _) = simulate(lo)?;
+    Ok((amount_out > lo).then_some((lo, amount_intermediate, amount_out)))
+}
+
+/// Computes whether an arbitrage exists between two pools of the same pair and the input that
+/// maximizes its profit, where the marginal prices of the pools net of their fees are equal.
+///
+/// The input token is sold in the pool where it is more expensive and bought back in the other.
+///
+/// ## Arguments
+///
+/// * `pool_a`: A pool with a tick data provider to simulate swaps
+/// * `pool_b`: Another pool of the same pair with a tick data provider to simulate swaps
+/// * `token_in`: The token to borrow and take the profit in
+///
+/// ## Returns
+///
+/// The arbitrage, or `None` if the difference between the prices of the pools does not cover
+/// their fees
+#[inline]
+pub fn get_arbitrage<TP1: TickDataProvider, TP2: TickDataProvider>(
+    pool_a: &Pool<TP1>,
+    pool_b: &Pool<TP2>,
+    token_in: &Token,
+) -> Result<Option<Arbitrage>, Error> {
+    assert!(
+        pool_a.token0.equals(&pool_b.token0) && pool_a.token1.equals(&pool_b.token1),
+        "TOKENS"
+    );
+    if !pool_a.involves_token(token_in) {
+        return Err(Error::InvalidToken);
+    }
+    let zero_for_one = token_in.equals(&pool_a.token0);
+    let arbitrage = |a_to_b: bool, solution: Option<(U256, U256, U256)>| {
+        solution.map(|(amount_in, amount_intermediate, amount_out)| Arbitrage {
+            a_to_b,
+            zero_for_one,
+            amount_in,
+            amount_intermediate,
+            amount_out,
+            profit: amount_out - amount_in,
+        })
+    };
+    if let Some(arbitrage) = arbitrage(true, solve(pool_a, pool_b, zero_for_one)?) {
+        return Ok(Some(arbitrage));
+    }
+    Ok(arbitrage(false, solve(pool_b, pool_a, zero_for_one)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn pool(fee: FeeAmount, sqrt_price_x96: U160) -> Pool<TickListDataProvider> {
+        let tick_spacing = fee.tick_spacing().as_i32();
+        let liquidity = 1_000_000_000;
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            fee,
+            sqrt_price_x96,
+            liquidity,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(
+                        nearest_usable_tick(MIN_TICK_I32, tick_spacing),
+                        liquidity,
+                        liquidity as i128,
+                    ),
+                    Tick::new(
+                        nearest_usable_tick(MAX_TICK_I32, tick_spacing),
+                        liquidity,
+                        -(liquidity as i128),
+                    ),
+                ],
+                tick_spacing,
+            ),
+        )
+        .unwrap()
+    }
+
+    /// The profit of swapping an input in the first pool and the output in the second
+    fn profit(
+        first: &Pool<TickListDataProvider>,
+        second: &Pool<TickListDataProvider>,
+        token_in: &Token,
+        amount_in: U256,
+    ) -> BigInt {
+        let amount_in =
+            CurrencyAmount::from_raw_amount(token_in.clone(), amount_in.to_big_int()).unwrap();
+        let amount_intermediate = first.get_output_amount(&amount_in, None).unwrap();
+        let amount_out = second
+            .get_output_amount(&amount_intermediate, None)
+            .unwrap();
+        amount_out.quotient() - amount_in.quotient()
+    }
+
+    #[test]
+    fn test_get_arbitrage() {
+        let pool_a = pool(FeeAmount::MEDIUM, encode_sqrt_ratio_x96(1, 1));
+        let pool_b = pool(FeeAmount::LOW, encode_sqrt_ratio_x96(121, 100));
+        // token0 is cheaper in pool_a, so token1 buys it there and sells it in pool_b
+        let arbitrage = get_arbitrage(&pool_a, &pool_b, &TOKEN1).unwrap().unwrap();
+        assert!(arbitrage.a_to_b);
+        assert!(!arbitrage.zero_for_one);
+        assert!(arbitrage.profit > U256::ZERO);
+        let best = profit(&pool_a, &pool_b, &TOKEN1, arbitrage.amount_in);
+        assert_eq!(best, arbitrage.profit.to_big_int());
+        // the profit is maximal
+        for amount_in in [
+            arbitrage.amount_in * U256::from(99) / U256::from(100),
+            arbitrage.amount_in * U256::from(101) / U256::from(100),
+        ] {
+            assert!(profit(&pool_a, &pool_b, &TOKEN1, amount_in) <= best);
+        }
+        // with token0 as the input, it is sold in pool_b and bought back in pool_a
+        let arbitrage = get_arbitrage(&pool_a, &pool_b, &TOKEN0).unwrap().unwrap();
+        assert!(!arbitrage.a_to_b);
+        assert!(arbitrage.zero_for_one);
+        assert!(arbitrage.profit > U256::ZERO);
+        // the pools can be given in any order
+        let reversed = get_arbitrage(&pool_b, &pool_a, &TOKEN1).unwrap().unwrap();
+        assert!(!reversed.a_to_b);
+        assert_eq!(
+            reversed.profit,
+            get_arbitrage(&pool_a, &pool_b, &TOKEN1)
+                .unwrap()
+                .unwrap()
+                .profit
+        );
+    }
+
+    #[test]
+    fn test_get_arbitrage_none() {
+        let pool_a = pool(FeeAmount::MEDIUM, encode_sqrt_ratio_x96(1, 1));
+        let pool_b = pool(FeeAmount::MEDIUM, encode_sqrt_ratio_x96(1, 1));
+        assert_eq!(get_arbitrage(&pool_a, &pool_b, &TOKEN1).unwrap(), None);
+        // a price difference of 0.4% does not cover two fees of 0.3%
+        let pool_b = pool(FeeAmount::MEDIUM, encode_sqrt_ratio_x96(1004, 1000));
+        assert_eq!(get_arbitrage(&pool_a, &pool_b, &TOKEN1).unwrap(), None);
+        assert_eq!(get_arbitrage(&pool_a, &pool_b, &TOKEN0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_arbitrage_invalid_token() {
+        let pool_a = pool(FeeAmount::MEDIUM, encode_sqrt_ratio_x96(1, 1));
+        let pool_b = pool(FeeAmount::LOW, encode_sqrt_ratio_x96(121, 100));
+        assert!(matches!(
+            get_arbitrage(&pool_a, &pool_b, &TOKEN2),
+            Err(Error::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,729 @@
+//! ## Backtest
+//! Replays the historical swaps of a pool against a liquidity management [`Strategy`] and reports
+//! the fees earned, the impermanent loss and the gas spent by the positions it manages, or against
+//! a fixed position with [`backtest_fees`] for the fees it would have earned.
+
+use crate::prelude::{Error, *};
+use alloc::vec;
+use alloy_primitives::{aliases::I24, I256, U160, U256};
+use num_traits::ToPrimitive;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// A swap of the pool replayed by [`backtest`], e.g. from a decoded `Swap` log or fetched by
+/// `SubgraphClient::get_swaps` with the `subgraph` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapEvent {
+    pub block_number: u64,
+    pub timestamp: u64,
+    /// The amount of token0 into the pool, negative if out of the pool
+    pub amount0: I256,
+    /// The amount of token1 into the pool, negative if out of the pool
+    pub amount1: I256,
+    /// The sqrt price of the pool after the swap
+    pub sqrt_price_x96: U160,
+    /// The active liquidity of the pool after the swap
+    pub liquidity: u128,
+    /// The tick of the pool after the swap
+    pub tick: I24,
+}
+
+impl SwapEvent {
+    /// Creates a swap event from a decoded `Swap` log
+    ///
+    /// ## Arguments
+    ///
+    /// * `block_number`: The block number of the log
+    /// * `timestamp`: The timestamp of the block
+    /// * `swap`: The decoded log
+    #[inline]
+    #[must_use]
+    pub const fn from_log(block_number: u64, timestamp: u64, swap: &IUniswapV3Pool::Swap) -> Self {
+        Self {
+            block_number,
+            timestamp,
+            amount0: swap.amount0,
+            amount1: swap.amount1,
+            sqrt_price_x96: swap.sqrtPriceX96,
+            liquidity: swap.liquidity,
+            tick: swap.tick,
+        }
+    }
+}
+
+/// The state of the pool and of the managed position seen by a [`Strategy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BacktestState {
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub sqrt_price_x96: U160,
+    pub tick: I24,
+    /// The lower tick of the position, meaningless before the first position is opened
+    pub tick_lower: I24,
+    /// The upper tick of the position, meaningless before the first position is opened
+    pub tick_upper: I24,
+    /// The liquidity of the position
+    pub liquidity: u128,
+}
+
+/// A liquidity management strategy replayed by [`backtest`].
+pub trait Strategy {
+    /// Called at the end of each block with swaps, returns whether to rebalance the position
+    fn on_block(&mut self, state: &BacktestState) -> bool;
+
+    /// Returns the range of the new position, called to open the first position and on each
+    /// rebalance
+    fn rebalance(&mut self, state: &BacktestState) -> (I24, I24);
+}
+
+/// A strategy that centers a range of a fixed width around the current tick and recenters it when
+/// the price leaves it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecenterStrategy {
+    /// The width of the range in ticks, a multiple of twice the tick spacing
+    pub width: I24,
+    pub tick_spacing: I24,
+}
+
+impl Strategy for RecenterStrategy {
+    #[inline]
+    fn on_block(&mut self, state: &BacktestState) -> bool {
+        state.tick < state.tick_lower || state.tick >= state.tick_upper
+    }
+
+    #[inline]
+    fn rebalance(&mut self, state: &BacktestState) -> (I24, I24) {
+        let half_width = self.width / I24::from_limbs([2]);
+        let tick = nearest_usable_tick(state.tick, self.tick_spacing);
+        let min_tick = nearest_usable_tick(MIN_TICK, self.tick_spacing);
+        let max_tick = nearest_usable_tick(MAX_TICK, self.tick_spacing);
+        (
+            (tick - half_width).max(min_tick),
+            (tick + half_width).min(max_tick),
+        )
+    }
+}
+
+/// A strategy that holds a single position over a fixed range and never rebalances.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedRangeStrategy {
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+}
+
+impl Strategy for FixedRangeStrategy {
+    #[inline]
+    fn on_block(&mut self, _state: &BacktestState) -> bool {
+        false
+    }
+
+    #[inline]
+    fn rebalance(&mut self, _state: &BacktestState) -> (I24, I24) {
+        (self.tick_lower, self.tick_upper)
+    }
+}
+
+/// The inputs of [`backtest`].
+#[derive(Clone, Debug)]
+pub struct BacktestConfig {
+    /// The pool at the start of the backtest
+    pub pool: Pool,
+    /// The balance of token0 to provide liquidity with
+    pub amount0: U256,
+    /// The balance of token1 to provide liquidity with
+    pub amount1: U256,
+    /// The gas used by the transaction that opens the first position or rebalances
+    pub gas_per_rebalance: u64,
+    /// The gas price in wei
+    pub gas_price: U256,
+}
+
+/// The result of [`backtest`], with the values in token1 at the final price.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacktestReport {
+    /// The fees of token0 earned by the positions
+    pub fees0: U256,
+    /// The fees of token1 earned by the positions
+    pub fees1: U256,
+    /// The balance of token0 after withdrawing the last position and its fees
+    pub amount0: U256,
+    /// The balance of token1 after withdrawing the last position and its fees
+    pub amount1: U256,
+    /// The value of the final balances
+    pub value: BigDecimal,
+    /// The value of the initial balances
+    pub hodl_value: BigDecimal,
+    /// The value of the fees earned
+    pub fees_value: BigDecimal,
+    /// The value of the final balances without the fees minus the value of the initial balances,
+    /// including the cost of the rebalancing swaps
+    pub impermanent_loss: BigDecimal,
+    /// The number of rebalances after the first position is opened
+    pub rebalances: u32,
+    /// The gas used by the first position and the rebalances
+    pub gas_used: u64,
+    /// The cost of the gas used in wei
+    pub gas_cost: U256,
+}
+
+/// The result of [`backtest_fees`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeBacktestReport {
+    /// The fees of token0 earned by the position
+    pub fees0: U256,
+    /// The fees of token1 earned by the position
+    pub fees1: U256,
+    /// The value of the fees in token1 at the final price
+    pub fees_value: BigDecimal,
+    /// The number of seconds of the window during which the position was in range
+    pub time_in_range: u64,
+    /// The number of seconds of the window
+    pub duration: u64,
+    /// The number of swaps replayed, i.e. within the window
+    pub swaps: usize,
+}
+
+/// The state of a backtest
+struct Simulation<'a> {
+    config: &'a BacktestConfig,
+    state: BacktestState,
+    pool_liquidity: u128,
+    balance0: U256,
+    balance1: U256,
+    fees_owed0: BigDecimal,
+    fees_owed1: BigDecimal,
+    fees0: U256,
+    fees1: U256,
+    rebalances: u32,
+    gas_used: u64,
+}
+
+impl Simulation<'_> {
+    /// Credits the position with its share of the fees of a swap and moves the pool to the state
+    /// after the swap.
+    ///
+    /// The share of the liquidity is computed against the liquidity of the pool after the swap,
+    /// and the share of the input amount swapped within the range assumes that the liquidity is
+    /// constant along the swap.
+    fn swap(&mut self, event: &SwapEvent) -> Result<(), Error> {
+        let zero_for_one = event.amount0.is_positive();
+        let amount_in = if zero_for_one {
+            event.amount0
+        } else {
+            event.amount1
+        };
+        if self.state.liquidity > 0 && amount_in.is_positive() {
+            let sqrt_price_lower = get_sqrt_ratio_at_tick(self.state.tick_lower)?.to_big_int();
+            let sqrt_price_upper = get_sqrt_ratio_at_tick(self.state.tick_upper)?.to_big_int();
+            let sqrt_price_before = self.state.sqrt_price_x96.to_big_int();
+            let sqrt_price_after = event.sqrt_price_x96.to_big_int();
+            let (start, end) = if zero_for_one {
+                (&sqrt_price_after, &sqrt_price_before)
+            } else {
+                (&sqrt_price_before, &sqrt_price_after)
+            };
+            // the share of the input amount swapped within the range
+            let share_in_range = if start == end {
+                if *start >= sqrt_price_lower && *start < sqrt_price_upper {
+                    BigDecimal::from(1)
+                } else {
+                    BigDecimal::from(0)
+                }
+            } else {
+                let lower = start.max(&sqrt_price_lower);
+                let upper = end.min(&sqrt_price_upper);
+                if lower >= upper {
+                    BigDecimal::from(0)
+                } else if zero_for_one {
+                    // the amount of token0 is proportional to 1 / sp_lower - 1 / sp_upper
+                    BigDecimal::from((upper - lower) * start * end)
+                        / BigDecimal::from((end - start) * lower * upper)
+                } else {
+                    // the amount of token1 is proportional to sp_upper - sp_lower
+                    BigDecimal::from(upper - lower) / BigDecimal::from(end - start)
+                }
+            };
+            let fees = BigDecimal::from(amount_in.to_big_int())
+                * fee_rate(self.config.pool.fee)
+                * share_in_range
+                * BigDecimal::from(self.state.liquidity)
+                / BigDecimal::from(event.liquidity + self.state.liquidity);
+            if zero_for_one {
+                self.fees_owed0 += fees;
+            } else {
+                self.fees_owed1 += fees;
+            }
+        }
+        self.state.block_number = event.block_number;
+        self.state.timestamp = event.timestamp;
+        self.state.sqrt_price_x96 = event.sqrt_price_x96;
+        self.state.tick = event.tick;
+        self.pool_liquidity = event.liquidity;
+        Ok(())
+    }
+
+    /// Burns the position and collects its fees into the balances
+    fn withdraw(&mut self) -> Result<(), Error> {
+        if self.state.liquidity > 0 {
+            let sqrt_price_lower_x96 = get_sqrt_ratio_at_tick(self.state.tick_lower)?;
+            let sqrt_price_upper_x96 = get_sqrt_ratio_at_tick(self.state.tick_upper)?;
+            let sqrt_price_x96 = self
+                .state
+                .sqrt_price_x96
+                .clamp(sqrt_price_lower_x96, sqrt_price_upper_x96);
+            self.balance0 += get_amount_0_delta(
+                sqrt_price_x96,
+                sqrt_price_upper_x96,
+                self.state.liquidity,
+                false,
+            )?;
+            self.balance1 += get_amount_1_delta(
+                sqrt_price_lower_x96,
+                sqrt_price_x96,
+                self.state.liquidity,
+                false,
+            )?;
+            self.state.liquidity = 0;
+        }
+        let fees0 = U256::from_big_int(self.fees_owed0.with_scale(0).into_bigint_and_exponent().0);
+        let fees1 = U256::from_big_int(self.fees_owed1.with_scale(0).into_bigint_and_exponent().0);
+        self.fees_owed0 = BigDecimal::from(0);
+        self.fees_owed1 = BigDecimal::from(0);
+        self.fees0 += fees0;
+        self.fees1 += fees1;
+        self.balance0 += fees0;
+        self.balance1 += fees1;
+        Ok(())
+    }
+
+    /// Withdraws the position, swaps the balances to the ratio of the new range in a pool
+    /// approximated by full range liquidity of the active liquidity, and mints the new position
+    fn rebalance<S: Strategy>(&mut self, strategy: &mut S) -> Result<(), Error> {
+        self.withdraw()?;
+        let (tick_lower, tick_upper) = strategy.rebalance(&self.state);
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidRange);
+        }
+        let mut sqrt_price_x96 = self.state.sqrt_price_x96;
+        if self.pool_liquidity > 0 {
+            let tick_spacing = self.config.pool.tick_spacing();
+            let liquidity = self.pool_liquidity;
+            let pool = Pool::new_with_tick_data_provider(
+                self.config.pool.token0.clone(),
+                self.config.pool.token1.clone(),
+                self.config.pool.fee,
+                sqrt_price_x96,
+                liquidity,
+                TickListDataProvider::new(
+                    vec![
+                        Tick::new(
+                            nearest_usable_tick(MIN_TICK_I32, tick_spacing),
+                            liquidity,
+                            liquidity as i128,
+                        ),
+                        Tick::new(
+                            nearest_usable_tick(MAX_TICK_I32, tick_spacing),
+                            liquidity,
+                            -(liquidity as i128),
+                        ),
+                    ],
+                    tick_spacing,
+                ),
+            )?;
+            let swap = get_optimal_swap(
+                &pool,
+                tick_lower.as_i32(),
+                tick_upper.as_i32(),
+                self.balance0,
+                self.balance1,
+            )?;
+            if swap.zero_for_one {
+                self.balance0 -= swap.amount_in;
+                self.balance1 += swap.amount_out;
+            } else {
+                self.balance1 -= swap.amount_in;
+                self.balance0 += swap.amount_out;
+            }
+            sqrt_price_x96 = swap.sqrt_price_x96_after;
+        }
+        let sqrt_price_lower_x96 = get_sqrt_ratio_at_tick(tick_lower)?;
+        let sqrt_price_upper_x96 = get_sqrt_ratio_at_tick(tick_upper)?;
+        let liquidity = max_liquidity_for_amounts(
+            sqrt_price_x96,
+            sqrt_price_lower_x96,
+            sqrt_price_upper_x96,
+            self.balance0,
+            self.balance1,
+            true,
+        )
+        .to_u128()
+        .ok_or(Error::InvalidAmounts)?;
+        let sqrt_price_x96 = sqrt_price_x96.clamp(sqrt_price_lower_x96, sqrt_price_upper_x96);
+        let amount0 = get_amount_0_delta(sqrt_price_x96, sqrt_price_upper_x96, liquidity, true)?;
+        let amount1 = get_amount_1_delta(sqrt_price_lower_x96, sqrt_price_x96, liquidity, true)?;
+        self.balance0 = self.balance0.saturating_sub(amount0);
+        self.balance1 = self.balance1.saturating_sub(amount1);
+        self.state.tick_lower = tick_lower;
+        self.state.tick_upper = tick_upper;
+        self.state.liquidity = liquidity;
+        self.gas_used += self.config.gas_per_rebalance;
+        Ok(())
+    }
+}
+
+/// Replays swaps of a pool against a strategy that manages a single position.
+///
+/// The first position is opened before the first swap. After the swaps of each block, the
+/// strategy is asked whether to rebalance, in which case the position is burned with its fees,
+/// the balances are swapped to the ratio of the new range and the new position is minted.
+///
+/// ## Notes
+///
+/// The positions do not affect the replayed swaps. The fees are earned in proportion to the share
+/// of the position in the active liquidity after each swap, and the rebalancing swaps are
+/// simulated in a pool with the active liquidity over the full range.
+///
+/// ## Arguments
+///
+/// * `strategy`: The strategy managing the position
+/// * `events`: The swaps of the pool in chronological order
+/// * `config`: The initial state of the pool, the balances and the gas costs
+#[inline]
+pub fn backtest<S: Strategy>(
+    strategy: &mut S,
+    events: &[SwapEvent],
+    config: &BacktestConfig,
+) -> Result<BacktestReport, Error> {
+    let mut simulation = Simulation {
+        config,
+        state: BacktestState {
+            block_number: events.first().map_or(0, |event| event.block_number),
+            timestamp: events.first().map_or(0, |event| event.timestamp),
+            sqrt_price_x96: config.pool.sqrt_ratio_x96,
+            tick: config.pool.tick_current.to_i24(),
+            tick_lower: I24::ZERO,
+            tick_upper: I24::ZERO,
+            liquidity: 0,
+        },
+        pool_liquidity: config.pool.liquidity,
+        balance0: config.amount0,
+        balance1: config.amount1,
+        fees_owed0: BigDecimal::from(0),
+        fees_owed1: BigDecimal::from(0),
+        fees0: U256::ZERO,
+        fees1: U256::ZERO,
+        rebalances: 0,
+        gas_used: 0,
+    };
+    simulation.rebalance(strategy)?;
+    for (i, event) in events.iter().enumerate() {
+        if event.block_number < simulation.state.block_number {
+            return Err(Error::InvalidTimeRange);
+        }
+        simulation.swap(event)?;
+        let end_of_block =
+            events.get(i + 1).map(|next| next.block_number) != Some(event.block_number);
+        if end_of_block && strategy.on_block(&simulation.state) {
+            simulation.rebalance(strategy)?;
+            simulation.rebalances += 1;
+        }
+    }
+    simulation.withdraw()?;
+
+    let price = (simulation.state.sqrt_price_x96.to_big_decimal() / Q96.to_big_decimal()).square();
+    let value =
+        |amount0: U256, amount1: U256| amount0.to_big_decimal() * &price + amount1.to_big_decimal();
+    let final_value = value(simulation.balance0, simulation.balance1);
+    let hodl_value = value(config.amount0, config.amount1);
+    let fees_value = value(simulation.fees0, simulation.fees1);
+    Ok(BacktestReport {
+        fees0: simulation.fees0,
+        fees1: simulation.fees1,
+        amount0: simulation.balance0,
+        amount1: simulation.balance1,
+        impermanent_loss: &final_value - &fees_value - &hodl_value,
+        value: final_value,
+        hodl_value,
+        fees_value,
+        rebalances: simulation.rebalances,
+        gas_used: simulation.gas_used,
+        gas_cost: U256::from(simulation.gas_used) * config.gas_price,
+    })
+}
+
+/// Replays swaps of a pool over a window against a fixed position, and computes the fees it would
+/// have earned and the time it spent in range.
+///
+/// ## Notes
+///
+/// The fees are estimated as in [`backtest`]: the position does not affect the replayed swaps and
+/// earns in proportion to its share of the active liquidity after each swap. The price is assumed
+/// constant between swaps for the time in range.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool at the start of the window
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `liquidity`: The liquidity of the position
+/// * `events`: The swaps of the pool in chronological order, of which those within the window are
+///   replayed
+/// * `timestamp_start`: The start of the window, inclusive
+/// * `timestamp_end`: The end of the window, exclusive
+#[inline]
+pub fn backtest_fees(
+    pool: &Pool,
+    tick_lower: I24,
+    tick_upper: I24,
+    liquidity: u128,
+    events: &[SwapEvent],
+    timestamp_start: u64,
+    timestamp_end: u64,
+) -> Result<FeeBacktestReport, Error> {
+    if tick_lower >= tick_upper {
+        return Err(Error::InvalidRange);
+    }
+    if timestamp_start > timestamp_end {
+        return Err(Error::InvalidTimeRange);
+    }
+    let config = BacktestConfig {
+        pool: pool.clone(),
+        amount0: U256::ZERO,
+        amount1: U256::ZERO,
+        gas_per_rebalance: 0,
+        gas_price: U256::ZERO,
+    };
+    let mut simulation = Simulation {
+        config: &config,
+        state: BacktestState {
+            block_number: events.first().map_or(0, |event| event.block_number),
+            timestamp: timestamp_start,
+            sqrt_price_x96: pool.sqrt_ratio_x96,
+            tick: pool.tick_current.to_i24(),
+            tick_lower,
+            tick_upper,
+            liquidity,
+        },
+        pool_liquidity: pool.liquidity,
+        balance0: U256::ZERO,
+        balance1: U256::ZERO,
+        fees_owed0: BigDecimal::from(0),
+        fees_owed1: BigDecimal::from(0),
+        fees0: U256::ZERO,
+        fees1: U256::ZERO,
+        rebalances: 0,
+        gas_used: 0,
+    };
+    let in_range = |state: &BacktestState| state.tick >= tick_lower && state.tick < tick_upper;
+    let mut time_in_range = 0;
+    let mut swaps = 0;
+    for event in events
+        .iter()
+        .filter(|event| (timestamp_start..timestamp_end).contains(&event.timestamp))
+    {
+        if event.timestamp < simulation.state.timestamp {
+            return Err(Error::InvalidTimeRange);
+        }
+        if in_range(&simulation.state) {
+            time_in_range += event.timestamp - simulation.state.timestamp;
+        }
+        simulation.swap(event)?;
+        swaps += 1;
+    }
+    if in_range(&simulation.state) {
+        time_in_range += timestamp_end - simulation.state.timestamp;
+    }
+    simulation.withdraw()?;
+
+    let price = (simulation.state.sqrt_price_x96.to_big_decimal() / Q96.to_big_decimal()).square();
+    Ok(FeeBacktestReport {
+        fees0: simulation.fees0,
+        fees1: simulation.fees1,
+        fees_value: simulation.fees0.to_big_decimal() * price + simulation.fees1.to_big_decimal(),
+        time_in_range,
+        duration: timestamp_end - timestamp_start,
+        swaps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloc::vec::Vec;
+    use alloy_primitives::Address;
+
+    fn config() -> BacktestConfig {
+        BacktestConfig {
+            pool: Pool::new(
+                TOKEN0.clone(),
+                TOKEN1.clone(),
+                FeeAmount::MEDIUM,
+                encode_sqrt_ratio_x96(1, 1),
+                1_000_000_000,
+            )
+            .unwrap(),
+            amount0: U256::from(1_000_000),
+            amount1: U256::from(1_000_000),
+            gas_per_rebalance: 200_000,
+            gas_price: U256::from(10_000_000_000_u64),
+        }
+    }
+
+    fn strategy() -> RecenterStrategy {
+        RecenterStrategy {
+            width: I24::from_limbs([1200]),
+            tick_spacing: I24::from_limbs([60]),
+        }
+    }
+
+    /// A swap that moves the price to a tick with a liquidity of 1e9
+    fn swap_to(block_number: u64, tick: i32, amount0: i64, amount1: i64) -> SwapEvent {
+        let tick = I24::try_from(tick).unwrap();
+        SwapEvent {
+            block_number,
+            timestamp: block_number * 12,
+            amount0: I256::try_from(amount0).unwrap(),
+            amount1: I256::try_from(amount1).unwrap(),
+            sqrt_price_x96: get_sqrt_ratio_at_tick(tick).unwrap(),
+            liquidity: 1_000_000_000,
+            tick,
+        }
+    }
+
+    #[test]
+    fn test_backtest_in_range() {
+        // swaps back and forth within the range
+        let events: Vec<SwapEvent> = (1..=10)
+            .map(|i| {
+                if i % 2 == 1 {
+                    swap_to(i, -10, 500_000, -499_000)
+                } else {
+                    swap_to(i, 0, -499_000, 500_000)
+                }
+            })
+            .collect();
+        let report = backtest(&mut strategy(), &events, &config()).unwrap();
+        assert_eq!(report.rebalances, 0);
+        assert_eq!(report.gas_used, 200_000);
+        assert_eq!(report.gas_cost, U256::from(2_000_000_000_000_000_u64));
+        // 0.3% of 2.5e6 of each token, shared with 1e9 of liquidity
+        assert!(report.fees0 > U256::ZERO && report.fees0 < U256::from(7_500));
+        assert!(report.fees1 > U256::ZERO && report.fees1 < U256::from(7_500));
+        assert_eq!(
+            report.fees_value,
+            report.fees0.to_big_decimal() + report.fees1.to_big_decimal()
+        );
+        // back to the initial price, only rounding and the fees change the value
+        assert!(report.value > report.hodl_value);
+    }
+
+    #[test]
+    fn test_backtest_rebalance() {
+        let events = [
+            swap_to(1, 300, -1_000_000, 1_000_000),
+            swap_to(1, 900, -1_000_000, 1_000_000),
+            swap_to(2, 1200, -1_000_000, 1_000_000),
+        ];
+        let report = backtest(&mut strategy(), &events, &config()).unwrap();
+        // the price leaves the range [-600, 600) at the end of block 1 and of block 2
+        assert_eq!(report.rebalances, 1);
+        assert_eq!(report.gas_used, 400_000);
+        // the price rose, so the position sold token0 below the final price
+        assert!(report.impermanent_loss < BigDecimal::from(0));
+        assert_eq!(report.fees0, U256::ZERO);
+        assert!(report.fees1 > U256::ZERO);
+    }
+
+    #[test]
+    fn test_backtest_fees() {
+        let pool = config().pool;
+        let events = [
+            swap_to(1, -10, 500_000, -499_000),
+            swap_to(2, 900, -1_000_000, 1_000_000),
+            swap_to(3, 0, 1_000_000, -1_000_000),
+            swap_to(5, 0, 1_000_000, -1_000_000),
+        ];
+        let report = backtest_fees(
+            &pool,
+            I24::try_from(-600).unwrap(),
+            I24::from_limbs([600]),
+            1_000_000_000,
+            &events,
+            0,
+            48,
+        )
+        .unwrap();
+        // the swap at 60 is outside the window
+        assert_eq!(report.swaps, 3);
+        assert_eq!(report.duration, 48);
+        // out of range between the swaps at 24 and 36
+        assert_eq!(report.time_in_range, 36);
+        assert!(report.fees0 > U256::ZERO && report.fees1 > U256::ZERO);
+        // the position holds half of the active liquidity, so earns at most half of the 0.3% fee
+        assert!(report.fees0 <= U256::from(1_500_000 * 3 / 1000 / 2));
+        assert!(report.fees1 <= U256::from(1_000_000 * 3 / 1000 / 2));
+
+        // a position that is never in range earns nothing
+        let report = backtest_fees(
+            &pool,
+            I24::from_limbs([1200]),
+            I24::from_limbs([1800]),
+            1_000_000_000,
+            &events,
+            0,
+            48,
+        )
+        .unwrap();
+        assert_eq!(report.time_in_range, 0);
+        assert_eq!(report.fees0, U256::ZERO);
+        assert_eq!(report.fees1, U256::ZERO);
+    }
+
+    #[test]
+    fn test_backtest_invalid_input() {
+        let pool = config().pool;
+        let tick_lower = I24::try_from(-600).unwrap();
+        let tick_upper = I24::from_limbs([600]);
+        // swaps out of chronological order
+        let events = [
+            swap_to(2, -10, 500_000, -499_000),
+            swap_to(1, 0, -499_000, 500_000),
+        ];
+        assert!(matches!(
+            backtest(&mut strategy(), &events, &config()),
+            Err(Error::InvalidTimeRange)
+        ));
+        assert!(matches!(
+            backtest_fees(&pool, tick_lower, tick_upper, 1_000_000_000, &events, 0, 48),
+            Err(Error::InvalidTimeRange)
+        ));
+        // a window ending before it starts
+        assert!(matches!(
+            backtest_fees(&pool, tick_lower, tick_upper, 1_000_000_000, &[], 48, 0),
+            Err(Error::InvalidTimeRange)
+        ));
+        // an empty range
+        assert!(matches!(
+            backtest_fees(&pool, tick_upper, tick_lower, 1_000_000_000, &[], 0, 48),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_from_log() {
+        let swap = IUniswapV3Pool::Swap {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: I256::ONE,
+            amount1: I256::MINUS_ONE,
+            sqrtPriceX96: encode_sqrt_ratio_x96(1, 1),
+            liquidity: 100,
+            tick: I24::ZERO,
+        };
+        let event = SwapEvent::from_log(17_000_000, 1_681_000_000, &swap);
+        assert_eq!(event.amount0, I256::ONE);
+        assert_eq!(event.amount1, I256::MINUS_ONE);
+        assert_eq!(event.liquidity, 100);
+    }
+}
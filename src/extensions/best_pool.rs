@@ -0,0 +1,263 @@
+//! ## Best Pool
+//! Quotes a trade in every fee tier pool of a pair and ranks the pools by their output, with and
+//! without the cost of the gas of the swap.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{Address, ChainId, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// The quote of a trade in a pool, see [`rank_pools`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolQuote {
+    pub fee: FeeAmount,
+    pub amount_out: CurrencyAmount<Token>,
+    /// The estimated gas of the swap, see [`SwapGasModel`]
+    pub gas_estimate: u64,
+    /// The raw output minus the cost of the gas in raw units of the output token, which may be
+    /// negative
+    pub gas_adjusted_amount_out: BigDecimal,
+}
+
+/// The quotes of a trade in the pools of a pair, in descending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolRanking {
+    /// The quotes ranked by output
+    pub by_output: Vec<PoolQuote>,
+    /// The quotes ranked by output net of the gas cost
+    pub by_gas_adjusted_output: Vec<PoolQuote>,
+}
+
+impl PoolRanking {
+    /// The pool with the highest output net of the gas cost, if any pool can fill the trade
+    #[inline]
+    #[must_use]
+    pub fn best(&self) -> Option<&PoolQuote> {
+        self.by_gas_adjusted_output.first()
+    }
+}
+
+/// Quotes an exact input trade in each pool and ranks the pools by output.
+///
+/// ## Notes
+///
+/// The pools without enough liquidity to fill the trade are left out.
+///
+/// ## Arguments
+///
+/// * `pools`: The pools of the pair with a tick data provider covering the trade
+/// * `amount_in`: The input amount of the trade
+/// * `gas_price`: The price of a unit of gas in raw units of the output token, e.g. the gas price
+///   in wei times the price of the native currency in the output token
+#[inline]
+pub fn rank_pools<TP: Clone + TickDataProvider>(
+    pools: &[Pool<TP>],
+    amount_in: &CurrencyAmount<Token>,
+    gas_price: &BigDecimal,
+) -> Result<PoolRanking, Error> {
+    let mut by_output = Vec::with_capacity(pools.len());
+    for pool in pools {
+        let (amount_out, gas_estimate) =
+            match pool.get_output_amount_with_gas_estimate(amount_in, None) {
+                Ok(quote) => quote,
+                Err(Error::InsufficientLiquidity) => continue,
+                Err(e) => return Err(e),
+            };
+        let gas_adjusted_amount_out =
+            BigDecimal::from(amount_out.quotient()) - gas_price * BigDecimal::from(gas_estimate);
+        by_output.push(PoolQuote {
+            fee: pool.fee,
+            amount_out,
+            gas_estimate,
+            gas_adjusted_amount_out,
+        });
+    }
+    by_output.sort_by(|a, b| b.amount_out.quotient().cmp(&a.amount_out.quotient()));
+    let mut by_gas_adjusted_output = by_output.clone();
+    by_gas_adjusted_output
+        .sort_by(|a, b| b.gas_adjusted_amount_out.cmp(&a.gas_adjusted_amount_out));
+    Ok(PoolRanking {
+        by_output,
+        by_gas_adjusted_output,
+    })
+}
+
+/// Fetches the pools of a pair in every standard fee tier and ranks them for an exact input
+/// trade, see [`rank_pools`].
+///
+/// The pool addresses are derived with [`compute_pool_address`], the undeployed pools are skipped,
+/// and the states of the deployed pools are fetched in a single batch with
+/// [`Pool::from_pool_keys`] before their ticks.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `factory`: The factory address
+/// * `token_in`: The input token
+/// * `token_out`: The output token
+/// * `amount_in`: The raw input amount
+/// * `gas_price`: The price of a unit of gas in raw units of the output token
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_best_pools<N, P>(
+    chain_id: ChainId,
+    factory: Address,
+    token_in: Address,
+    token_out: Address,
+    amount_in: U256,
+    gas_price: &BigDecimal,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PoolRanking, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let mut pool_keys = Vec::with_capacity(STANDARD_FEE_TIERS.len());
+    for fee in STANDARD_FEE_TIERS {
+        let pool = compute_pool_address(factory, token_in, token_out, fee, None, Some(chain_id));
+        // the pool of the tier may not have been deployed
+        let mut code = provider.get_code_at(pool);
+        if let Some(block_id) = block_id {
+            code = code.block_id(block_id);
+        }
+        if !code.await?.is_empty() {
+            pool_keys.push((token_in, token_out, fee));
+        }
+    }
+    let mut pools = Vec::with_capacity(pool_keys.len());
+    for pool in
+        Pool::from_pool_keys(chain_id, factory, &pool_keys, provider.root(), block_id).await?
+    {
+        let tick_data_provider = EphemeralTickMapDataProvider::new(
+            pool.address(None, Some(factory)),
+            provider.root(),
+            None,
+            None,
+            block_id,
+        )
+        .await?;
+        pools.push(Pool::new_with_tick_data_provider(
+            pool.token0,
+            pool.token1,
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            tick_data_provider,
+        )?);
+    }
+    let Some(pool) = pools.first() else {
+        return Ok(PoolRanking {
+            by_output: Vec::new(),
+            by_gas_adjusted_output: Vec::new(),
+        });
+    };
+    let token = if pool.token0.address() == token_in {
+        pool.token0.clone()
+    } else {
+        pool.token1.clone()
+    };
+    let amount_in = CurrencyAmount::from_raw_amount(token, amount_in.to_big_int())?;
+    rank_pools(&pools, &amount_in, gas_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use core::str::FromStr;
+
+    fn pool(fee: FeeAmount, liquidity: u128) -> Pool<TickListDataProvider> {
+        let tick_spacing = fee.tick_spacing().as_i32();
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            fee,
+            encode_sqrt_ratio_x96(1, 1),
+            liquidity,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(
+                        nearest_usable_tick(MIN_TICK_I32, tick_spacing),
+                        liquidity,
+                        liquidity as i128,
+                    ),
+                    Tick::new(
+                        nearest_usable_tick(MAX_TICK_I32, tick_spacing),
+                        liquidity,
+                        -(liquidity as i128),
+                    ),
+                ],
+                tick_spacing,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rank_pools() {
+        let pools = [
+            pool(FeeAmount::HIGH, 1_000_000_000_000),
+            pool(FeeAmount::LOW, 1_000_000_000),
+            pool(FeeAmount::MEDIUM, 1_000_000_000_000),
+        ];
+        let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000_000).unwrap();
+        let ranking = rank_pools(&pools, &amount_in, &BigDecimal::from(0)).unwrap();
+        let fees: Vec<FeeAmount> = ranking.by_output.iter().map(|quote| quote.fee).collect();
+        // the low fee pool is too shallow, so the price impact outweighs its lower fee
+        assert_eq!(
+            fees,
+            vec![FeeAmount::MEDIUM, FeeAmount::HIGH, FeeAmount::LOW]
+        );
+        assert_eq!(ranking.by_output, ranking.by_gas_adjusted_output);
+        assert_eq!(
+            ranking.by_output[0].amount_out,
+            pools[2].get_output_amount(&amount_in, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rank_pools_gas_adjusted() {
+        let pools = [
+            pool(FeeAmount::MEDIUM, 1_000_000_000_000),
+            pool(FeeAmount::LOW, 1_000_000_000_000),
+        ];
+        let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let gas_price = BigDecimal::from_str("0.001").unwrap();
+        let ranking = rank_pools(&pools, &amount_in, &gas_price).unwrap();
+        assert_eq!(ranking.best().unwrap().fee, FeeAmount::LOW);
+        for quote in &ranking.by_gas_adjusted_output {
+            assert_eq!(
+                quote.gas_adjusted_amount_out,
+                BigDecimal::from(quote.amount_out.quotient())
+                    - &gas_price * BigDecimal::from(quote.gas_estimate)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_best_pools() {
+        let ranking = get_best_pools(
+            1,
+            FACTORY_ADDRESS,
+            WETH.address(),
+            USDC.address(),
+            U256::from(10_u128.pow(18)),
+            &BigDecimal::from(0),
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(ranking.by_output.len() >= 2);
+        let best = ranking.best().unwrap();
+        assert!(best.amount_out.quotient() > BigInt::ZERO);
+        assert!(ranking
+            .by_output
+            .windows(2)
+            .all(|quotes| quotes[0].amount_out.quotient() >= quotes[1].amount_out.quotient()));
+    }
+}
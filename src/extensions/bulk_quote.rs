@@ -0,0 +1,216 @@
+//! ## Bulk Quote
+//! Quotes many exact input trades of many pairs in one pass, e.g. for a price feed: the candidate
+//! pools of every pair are resolved in every standard fee tier, the deployed ones and their states
+//! are fetched in batched multicalls, their ticks concurrently, and each trade is simulated in the
+//! pools of its pair.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{aliases::I24, Address, ChainId, U256};
+use alloy_sol_types::SolCall;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// An exact input trade quoted by [`bulk_quote`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuoteRequest {
+    pub token_in: Address,
+    pub token_out: Address,
+    /// The raw input amount
+    pub amount_in: U256,
+}
+
+/// Quotes many exact input trades in the pools of their pairs in every standard fee tier, and
+/// ranks the pools of each trade, see [`rank_pools`].
+///
+/// ## Notes
+///
+/// The pools are fetched once for all the trades of a pair: their deployment is checked in a
+/// single multicall, the states of the deployed pools and their tokens are fetched in another
+/// with [`Pool::from_pool_keys`], and their ticks concurrently with
+/// [`EphemeralTickMapDataProvider::new_many`]. The trades of a pair without pools get an empty
+/// ranking.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `factory`: The factory address
+/// * `requests`: The trades to quote
+/// * `gas_price`: The price of a unit of gas in raw units of the output tokens, which is only
+///   meaningful if the trades share the output token
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+/// * `concurrency`: The maximum number of pools whose ticks are fetched at a time
+///
+/// ## Returns
+///
+/// The ranking of the pools of each trade, in the order of the requests
+#[inline]
+pub async fn bulk_quote<N, P>(
+    chain_id: ChainId,
+    factory: Address,
+    requests: &[QuoteRequest],
+    gas_price: &BigDecimal,
+    provider: P,
+    block_id: Option<BlockId>,
+    concurrency: usize,
+) -> Result<Vec<PoolRanking>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    // the candidate pools of the distinct pairs
+    let mut pairs: Vec<(Address, Address)> = requests
+        .iter()
+        .map(|request| sort_tokens(request.token_in, request.token_out))
+        .collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+    let candidates: Vec<(Address, Address, FeeAmount, Address)> = pairs
+        .iter()
+        .flat_map(|&(token0, token1)| {
+            STANDARD_FEE_TIERS.map(|fee| {
+                let pool = compute_pool_address(factory, token0, token1, fee, None, Some(chain_id));
+                (token0, token1, fee, pool)
+            })
+        })
+        .collect();
+
+    // an undeployed pool has no code, so the call succeeds without return data
+    let results = multicall3_try_aggregate(
+        candidates
+            .iter()
+            .map(|&(.., pool)| (pool, IUniswapV3Pool::liquidityCall {}.abi_encode().into())),
+        provider.root(),
+        block_id,
+    )
+    .await?;
+    let mut deployed = Vec::with_capacity(candidates.len());
+    for (&(token0, token1, fee, pool), result) in candidates.iter().zip(results) {
+        if result.is_some_and(|data| !data.is_empty()) {
+            deployed.push(((token0, token1, fee), pool));
+        }
+    }
+    let (pool_keys, addresses): (Vec<_>, Vec<_>) = deployed.into_iter().unzip();
+
+    let states =
+        Pool::from_pool_keys(chain_id, factory, &pool_keys, provider.root(), block_id).await?;
+    let tick_data_providers = EphemeralTickMapDataProvider::<I24>::new_many(
+        &addresses,
+        provider.root(),
+        block_id,
+        concurrency,
+    )
+    .await;
+    let mut pools = Vec::with_capacity(states.len());
+    for (pool, tick_data_provider) in states.into_iter().zip(tick_data_providers) {
+        pools.push(Pool::new_with_tick_data_provider(
+            pool.token0,
+            pool.token1,
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            tick_data_provider?,
+        )?);
+    }
+
+    requests
+        .iter()
+        .map(|request| {
+            let (token0, token1) = sort_tokens(request.token_in, request.token_out);
+            let pair_pools: Vec<_> = pools
+                .iter()
+                .filter(|pool| pool.token0.address() == token0 && pool.token1.address() == token1)
+                .cloned()
+                .collect();
+            let Some(pool) = pair_pools.first() else {
+                return Ok(PoolRanking {
+                    by_output: Vec::new(),
+                    by_gas_adjusted_output: Vec::new(),
+                });
+            };
+            let token = if pool.token0.address() == request.token_in {
+                pool.token0.clone()
+            } else {
+                pool.token1.clone()
+            };
+            let amount_in = CurrencyAmount::from_raw_amount(token, request.amount_in.to_big_int())?;
+            rank_pools(&pair_pools, &amount_in, gas_price)
+        })
+        .collect()
+}
+
+/// Sorts the addresses of a pair of tokens
+fn sort_tokens(token_a: Address, token_b: Address) -> (Address, Address) {
+    if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[tokio::test]
+    async fn test_bulk_quote() {
+        let requests = [
+            QuoteRequest {
+                token_in: WETH.address(),
+                token_out: USDC.address(),
+                amount_in: U256::from(10_u128.pow(18)),
+            },
+            QuoteRequest {
+                token_in: USDC.address(),
+                token_out: WETH.address(),
+                amount_in: U256::from(1_000_000_000),
+            },
+            QuoteRequest {
+                token_in: DAI.address(),
+                token_out: USDC.address(),
+                amount_in: U256::from(10_u128.pow(21)),
+            },
+            // no pools
+            QuoteRequest {
+                token_in: TOKEN0.address(),
+                token_out: TOKEN1.address(),
+                amount_in: U256::from(1),
+            },
+        ];
+        let rankings = bulk_quote(
+            1,
+            FACTORY_ADDRESS,
+            &requests,
+            &BigDecimal::from(0),
+            PROVIDER.clone(),
+            *BLOCK_ID,
+            4,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rankings.len(), requests.len());
+        for (ranking, request) in rankings.iter().zip(&requests).take(3) {
+            let best = ranking.best().unwrap();
+            assert_eq!(best.amount_out.currency.address(), request.token_out);
+            assert!(best.amount_out.quotient() > BigInt::ZERO);
+        }
+        assert!(rankings[3].best().is_none());
+        // the same pools as a single pair
+        let ranking = get_best_pools(
+            1,
+            FACTORY_ADDRESS,
+            WETH.address(),
+            USDC.address(),
+            U256::from(10_u128.pow(18)),
+            &BigDecimal::from(0),
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rankings[0], ranking);
+    }
+}
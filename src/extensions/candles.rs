@@ -0,0 +1,276 @@
+//! ## Candles
+//! OHLC candles of the price of a pool, built from the mean ticks of its oracle observations
+//! sampled at fixed intervals.
+
+use super::multicall3::get_block_timestamp;
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{
+    aliases::{I24, I56},
+    Address,
+};
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// An OHLC candle of the price of token0 in token1, in the raw units of the tokens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candle {
+    /// The timestamp of the start of the candle
+    pub timestamp: u64,
+    /// The price of the first sample
+    pub open: BigDecimal,
+    /// The highest sampled price
+    pub high: BigDecimal,
+    /// The lowest sampled price
+    pub low: BigDecimal,
+    /// The price of the last sample
+    pub close: BigDecimal,
+}
+
+/// Builds candles from tick cumulatives sampled at a fixed interval, where each sample is the
+/// arithmetic mean tick between two consecutive cumulatives.
+///
+/// ## Arguments
+///
+/// * `tick_cumulatives`: The tick cumulatives in chronological order
+/// * `start_timestamp`: The timestamp of the first tick cumulative
+/// * `sample_interval`: The number of seconds between two consecutive tick cumulatives
+/// * `samples_per_candle`: The number of samples in a candle
+///
+/// ## Returns
+///
+/// The candles in chronological order, the last of which may have fewer samples
+#[inline]
+pub fn candles_from_tick_cumulatives(
+    tick_cumulatives: &[I56],
+    start_timestamp: u64,
+    sample_interval: u32,
+    samples_per_candle: usize,
+) -> Result<Vec<Candle>, Error> {
+    if sample_interval == 0 || samples_per_candle == 0 {
+        return Err(Error::InvalidInterval);
+    }
+    let mean_ticks: Vec<I24> = tick_cumulatives
+        .windows(2)
+        .map(|window| get_arithmetic_mean_tick(window[0], window[1], sample_interval))
+        .collect::<Result<_, _>>()?;
+    let candle_interval = u64::from(sample_interval) * samples_per_candle as u64;
+    let mut candles = Vec::with_capacity(mean_ticks.len().div_ceil(samples_per_candle));
+    for (i, ticks) in mean_ticks.chunks(samples_per_candle).enumerate() {
+        let (Some(&open), Some(&close), Some(&high), Some(&low)) = (
+            ticks.first(),
+            ticks.last(),
+            ticks.iter().max(),
+            ticks.iter().min(),
+        ) else {
+            continue;
+        };
+        candles.push(Candle {
+            timestamp: start_timestamp + i as u64 * candle_interval,
+            open: tick_to_big_price(open)?,
+            high: tick_to_big_price(high)?,
+            low: tick_to_big_price(low)?,
+            close: tick_to_big_price(close)?,
+        });
+    }
+    Ok(candles)
+}
+
+/// Fetches the OHLC candles of the price of a pool between two blocks from its oracle
+/// observations, in a single `observe` call at the end block.
+///
+/// ## Notes
+///
+/// The observation array of the pool must be large enough to cover the range, see
+/// [`get_observation_cardinality`].
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `block_start`: The block from which to build the candles
+/// * `block_end`: The block until which to build the candles, not before the start block
+/// * `candle_interval`: The length of a candle in seconds
+/// * `samples_per_candle`: The number of mean ticks sampled in a candle, which must divide the
+///   candle interval
+/// * `provider`: The alloy provider
+///
+/// ## Returns
+///
+/// The whole candles ending at the end block, in chronological order
+#[inline]
+pub async fn get_candles<N, P>(
+    pool: Address,
+    block_start: BlockId,
+    block_end: BlockId,
+    candle_interval: u32,
+    samples_per_candle: u32,
+    provider: P,
+) -> Result<Vec<Candle>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if candle_interval == 0 || samples_per_candle == 0 || candle_interval % samples_per_candle != 0
+    {
+        return Err(Error::InvalidInterval);
+    }
+    let provider = provider.root();
+    let timestamp_start = get_block_timestamp(provider, block_start).await?;
+    let timestamp_end = get_block_timestamp(provider, block_end).await?;
+    if timestamp_end < timestamp_start {
+        return Err(Error::InvalidTimeRange);
+    }
+    let num_candles = (timestamp_end - timestamp_start) / u64::from(candle_interval);
+    if num_candles == 0 {
+        return Ok(Vec::new());
+    }
+    let sample_interval = candle_interval / samples_per_candle;
+    let seconds_ago = u32::try_from(num_candles * u64::from(candle_interval))
+        .map_err(|_| Error::InvalidInterval)?;
+    let num_samples = seconds_ago / sample_interval;
+    let seconds_agos: Vec<u32> = (0..=num_samples)
+        .rev()
+        .map(|i| i * sample_interval)
+        .collect();
+    let tick_cumulatives = IUniswapV3Pool::new(pool, provider)
+        .observe(seconds_agos)
+        .block(block_end)
+        .call()
+        .await?
+        .tickCumulatives;
+    candles_from_tick_cumulatives(
+        &tick_cumulatives,
+        timestamp_end - num_candles * u64::from(candle_interval),
+        sample_interval,
+        samples_per_candle as usize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy::eips::BlockNumberOrTag;
+
+    /// Returns the tick cumulatives of a price at each tick for a sample interval
+    fn tick_cumulatives(ticks: &[i64], sample_interval: i64) -> Vec<I56> {
+        let mut cumulative = 0;
+        let mut tick_cumulatives = vec![I56::ZERO];
+        for tick in ticks {
+            cumulative += tick * sample_interval;
+            tick_cumulatives.push(I56::try_from(cumulative).unwrap());
+        }
+        tick_cumulatives
+    }
+
+    #[test]
+    fn test_candles_from_tick_cumulatives() {
+        let ticks = [10, 30, -20, 0, 5, 100, -50];
+        let candles =
+            candles_from_tick_cumulatives(&tick_cumulatives(&ticks, 60), 1000, 60, 3).unwrap();
+        assert_eq!(candles.len(), 3);
+        let price = |tick: i32| tick_to_big_price(tick).unwrap();
+        assert_eq!(
+            candles[0],
+            Candle {
+                timestamp: 1000,
+                open: price(10),
+                high: price(30),
+                low: price(-20),
+                close: price(-20),
+            }
+        );
+        assert_eq!(
+            candles[1],
+            Candle {
+                timestamp: 1180,
+                open: price(0),
+                high: price(100),
+                low: price(0),
+                close: price(100),
+            }
+        );
+        // the last candle has a single sample
+        assert_eq!(candles[2].timestamp, 1360);
+        assert_eq!(candles[2].open, price(-50));
+        assert_eq!(candles[2].high, candles[2].low);
+    }
+
+    #[test]
+    fn test_candles_from_tick_cumulatives_invalid_input() {
+        let tick_cumulatives = tick_cumulatives(&[10, 30], 60);
+        assert!(candles_from_tick_cumulatives(&[], 1000, 60, 3)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            candles_from_tick_cumulatives(&tick_cumulatives, 1000, 0, 3),
+            Err(Error::InvalidInterval)
+        ));
+        assert!(matches!(
+            candles_from_tick_cumulatives(&tick_cumulatives, 1000, 60, 0),
+            Err(Error::InvalidInterval)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_candles() {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let block_end = BLOCK_ID.unwrap();
+        let block_start =
+            BlockId::Number(BlockNumberOrTag::Number(block_end.as_u64().unwrap() - 300));
+        let candles = get_candles(
+            pool.address(None, None),
+            block_start,
+            block_end,
+            900,
+            3,
+            PROVIDER.clone(),
+        )
+        .await
+        .unwrap();
+        // 300 blocks of 12 seconds
+        assert_eq!(candles.len(), 4);
+        for candle in &candles {
+            assert!(candle.low <= candle.open && candle.open <= candle.high);
+            assert!(candle.low <= candle.close && candle.close <= candle.high);
+        }
+        assert!(candles
+            .windows(2)
+            .all(|window| window[1].timestamp - window[0].timestamp == 900));
+        assert!(matches!(
+            get_candles(
+                pool.address(None, None),
+                block_start,
+                block_end,
+                900,
+                7,
+                PROVIDER.clone(),
+            )
+            .await,
+            Err(Error::InvalidInterval)
+        ));
+        assert!(matches!(
+            get_candles(
+                pool.address(None, None),
+                block_end,
+                block_start,
+                900,
+                3,
+                PROVIDER.clone(),
+            )
+            .await,
+            Err(Error::InvalidTimeRange)
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+//! ## Capital Efficiency
+//! Measures how concentrated a liquidity range is: the fee-earning liquidity it provides per unit
+//! of capital compared to a full-range position at the current price, and the share of time it
+//! must stay in range to earn as many fees as the full-range position.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::U160;
+use uniswap_sdk_core::prelude::*;
+
+/// The capital efficiency of a liquidity range at a price, see [`get_capital_efficiency`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapitalEfficiency {
+    /// The liquidity per unit of capital relative to a full-range position, zero if the price is
+    /// out of range
+    pub concentration_factor: Fraction,
+    /// The share of time the price must stay in range to earn as many fees as a full-range
+    /// position of the same value, i.e. the inverse of the concentration factor, or `None` if the
+    /// price is out of range
+    pub breakeven_time_in_range: Option<Percent>,
+}
+
+/// Computes the concentration factor of a liquidity range at a price and its breakeven time in
+/// range.
+///
+/// ## Notes
+///
+/// The full-range position spans all prices, as a Uniswap V2 position. A unit of liquidity at the
+/// sqrt price `s` in the range [`sa`, `sb`] is worth `2s - sa - s^2 / sb` in token1, and `2s`
+/// over the full range, so that the concentration factor is `2s / (2s - sa - s^2 / sb)`.
+///
+/// ## Arguments
+///
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `sqrt_price_x96`: The Q64.96 sqrt price
+#[inline]
+pub fn get_capital_efficiency<I: TickIndex>(
+    tick_lower: I,
+    tick_upper: I,
+    sqrt_price_x96: U160,
+) -> Result<CapitalEfficiency, Error> {
+    if tick_lower >= tick_upper {
+        return Err(Error::InvalidRange);
+    }
+    let sqrt_price_a = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+    let sqrt_price_b = get_sqrt_ratio_at_tick(tick_upper.to_i24())?;
+    if sqrt_price_x96 < sqrt_price_a || sqrt_price_x96 >= sqrt_price_b {
+        return Ok(CapitalEfficiency {
+            concentration_factor: Fraction::new(0, 1),
+            breakeven_time_in_range: None,
+        });
+    }
+    let sqrt_price = sqrt_price_x96.to_big_int();
+    let sqrt_price_a = sqrt_price_a.to_big_int();
+    let sqrt_price_b = sqrt_price_b.to_big_int();
+    // 2s / (2s - sa - s^2 / sb) multiplied by sb, where the Q96 scaling cancels out
+    let full_range = BigInt::from(2) * &sqrt_price * &sqrt_price_b;
+    let range =
+        (BigInt::from(2) * &sqrt_price - sqrt_price_a) * sqrt_price_b - &sqrt_price * &sqrt_price;
+    Ok(CapitalEfficiency {
+        concentration_factor: Fraction::new(full_range.clone(), range.clone()),
+        breakeven_time_in_range: Some(Percent::new(range, full_range)),
+    })
+}
+
+impl<TP: TickDataProvider> Position<TP> {
+    /// Computes the concentration factor of the range of the position at the current price of the
+    /// pool and its breakeven time in range, see [`get_capital_efficiency`].
+    #[inline]
+    pub fn capital_efficiency(&self) -> Result<CapitalEfficiency, Error> {
+        get_capital_efficiency(self.tick_lower, self.tick_upper, self.pool.sqrt_ratio_x96)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_get_capital_efficiency() {
+        let sqrt_price = encode_sqrt_ratio_x96(1, 1);
+        // a range from 1 / k to k times the price has a concentration factor of
+        // 1 / (1 - 1 / sqrt(k))
+        let efficiency = get_capital_efficiency(-6932, 6932, sqrt_price).unwrap();
+        assert_eq!(efficiency.concentration_factor.to_fixed(3, None), "3.414");
+        assert_eq!(
+            efficiency
+                .breakeven_time_in_range
+                .unwrap()
+                .to_fixed(2, None),
+            "29.29"
+        );
+        let efficiency = get_capital_efficiency(-600, 600, sqrt_price).unwrap();
+        assert_eq!(efficiency.concentration_factor.to_fixed(3, None), "33.837");
+        assert_eq!(
+            efficiency
+                .breakeven_time_in_range
+                .unwrap()
+                .to_fixed(2, None),
+            "2.96"
+        );
+        let efficiency = get_capital_efficiency(MIN_TICK, MAX_TICK, sqrt_price).unwrap();
+        assert_eq!(efficiency.concentration_factor.to_fixed(3, None), "1.000");
+        // out of range
+        let efficiency = get_capital_efficiency(60, 120, sqrt_price).unwrap();
+        assert_eq!(efficiency.concentration_factor, Fraction::new(0, 1));
+        assert!(efficiency.breakeven_time_in_range.is_none());
+        assert!(matches!(
+            get_capital_efficiency(60, 60, sqrt_price),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_position_capital_efficiency() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000, -600, 600);
+        let efficiency = position.capital_efficiency().unwrap();
+        assert_eq!(
+            efficiency,
+            get_capital_efficiency(-600, 600, POOL_0_1.sqrt_ratio_x96).unwrap()
+        );
+        assert!(efficiency.concentration_factor > Fraction::new(1, 1));
+    }
+}
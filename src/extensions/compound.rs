@@ -0,0 +1,315 @@
+//! ## Compound
+//! Reinvesting the fees accrued by a position into its liquidity, rebalancing them to the ratio of
+//! the position with a swap in its pool, and the projection of the value of a position with and
+//! without compounding its fees.
+
+use super::zap::encode_zap_in;
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Bytes, U256};
+use alloy_sol_types::SolCall;
+use uniswap_sdk_core::prelude::*;
+
+/// The calldata to compound the fees of a position, see [`compound_call_parameters`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompoundParameters<I = i32> {
+    /// The calldata of the `SwapRouter02` multicall, sent by the recipient, that collects the fees,
+    /// swaps the excess and increases the liquidity of the position
+    pub parameters: MethodParameters,
+    /// The swap that rebalances the fees to the ratio of the position
+    pub swap: OptimalSwap<I>,
+}
+
+/// Produces the calldata for compounding the fees accrued by a position into its liquidity in a
+/// single `SwapRouter02` multicall.
+///
+/// The router collects the fees to the recipient of the collect options with
+/// `callPositionManager`, then the excess of one token given by [`get_optimal_swap`] is swapped in
+/// the pool of the position and the liquidity of the position is increased with the balances as in
+/// [`zap_in_call_parameters`]. The leftovers are returned to the recipient.
+///
+/// ## Notes
+///
+/// The multicall must be sent by the recipient, which must approve the router to spend both tokens
+/// and, since the router is the caller of `collect`, to manage the position, e.g. with `approve` or
+/// `permit` on the position manager. The expected amounts owed should include the fees not yet
+/// accounted for by the position manager, see [`get_collectable_token_amounts`].
+///
+/// ## Arguments
+///
+/// * `position`: The position to compound, with a tick data provider to simulate the swap
+/// * `options`: The token ID, the expected fees and the recipient of the fees
+/// * `slippage_tolerance`: How much the swap output and the amounts added to the position are
+///   allowed to fall short of the simulated amounts
+#[inline]
+pub fn compound_call_parameters<TP>(
+    position: &Position<TP>,
+    options: &CollectOptions<Token, Token>,
+    slippage_tolerance: &Percent,
+) -> Result<CompoundParameters<TP::Index>, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let pool = &position.pool;
+    if !options
+        .expected_currency_owed0
+        .currency
+        .equals(&pool.token0)
+        || !options
+            .expected_currency_owed1
+            .currency
+            .equals(&pool.token1)
+    {
+        return Err(Error::InvalidToken);
+    }
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(8);
+    calldatas.push(
+        IApproveAndCall::callPositionManagerCall {
+            data: INonfungiblePositionManager::collectCall {
+                params: INonfungiblePositionManager::CollectParams {
+                    tokenId: options.token_id,
+                    recipient: options.recipient,
+                    amount0Max: u128::MAX,
+                    amount1Max: u128::MAX,
+                },
+            }
+            .abi_encode()
+            .into(),
+        }
+        .abi_encode()
+        .into(),
+    );
+    let swap = encode_zap_in(
+        pool,
+        position.tick_lower,
+        position.tick_upper,
+        U256::from_big_int(options.expected_currency_owed0.quotient()),
+        U256::from_big_int(options.expected_currency_owed1.quotient()),
+        slippage_tolerance,
+        AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions {
+            token_id: options.token_id,
+        }),
+        options.recipient,
+        &mut calldatas,
+    )?;
+    Ok(CompoundParameters {
+        parameters: MethodParameters {
+            calldata: encode_multicall(calldatas),
+            value: U256::ZERO,
+        },
+        swap,
+    })
+}
+
+/// A point of the schedule of [`project_compounding`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompoundingPoint<T: BaseCurrency> {
+    /// The seconds elapsed since the start of the projection
+    pub elapsed: u64,
+    /// The value of the position if the fees are collected but not reinvested
+    pub value: CurrencyAmount<T>,
+    /// The value of the position if the fees are reinvested at each interval
+    pub compounded_value: CurrencyAmount<T>,
+}
+
+/// The fees earned by a value at the fee APR over a period, rounded down
+fn accrued_fees(value: &BigInt, fee_apr: &Percent, seconds: u64) -> BigInt {
+    value * &fee_apr.numerator * BigInt::from(seconds)
+        / (&fee_apr.denominator * BigInt::from(SECONDS_PER_YEAR))
+}
+
+/// Projects the value of a position over time with and without compounding its fees.
+///
+/// ## Notes
+///
+/// The fees accrue at a constant APR on the value of the position, e.g. as estimated by
+/// [`get_position_fee_apr`], ignoring price changes and impermanent loss. Without compounding, the
+/// fees accrue on the initial value only. With compounding, the fees net of the cost of compounding
+/// are added to the value at the end of each interval, as by [`compound_call_parameters`].
+///
+/// ## Arguments
+///
+/// * `value`: The value of the position at the start
+/// * `fee_apr`: The annualized fee APR of the position
+/// * `compound_interval`: The seconds between two compoundings
+/// * `compound_cost`: The cost of each compounding, e.g. gas, in raw units of the value currency
+/// * `periods`: The number of intervals to project
+///
+/// ## Returns
+///
+/// The values at the start and at the end of each interval, or [`Error::InvalidInterval`] if the
+/// interval is zero
+#[inline]
+pub fn project_compounding<T: BaseCurrency>(
+    value: &CurrencyAmount<T>,
+    fee_apr: &Percent,
+    compound_interval: u64,
+    compound_cost: U256,
+    periods: usize,
+) -> Result<Vec<CompoundingPoint<T>>, Error> {
+    if compound_interval == 0 {
+        return Err(Error::InvalidInterval);
+    }
+    let initial = value.quotient();
+    let cost = compound_cost.to_big_int();
+    let mut compounded = initial.clone();
+    let mut schedule = Vec::with_capacity(periods + 1);
+    schedule.push(CompoundingPoint {
+        elapsed: 0,
+        value: value.clone(),
+        compounded_value: value.clone(),
+    });
+    for period in 1..=periods as u64 {
+        let elapsed = period * compound_interval;
+        let fees = accrued_fees(&compounded, fee_apr, compound_interval);
+        compounded = (compounded + fees - &cost).max(BigInt::ZERO);
+        schedule.push(CompoundingPoint {
+            elapsed,
+            value: CurrencyAmount::from_raw_amount(
+                value.currency.clone(),
+                &initial + accrued_fees(&initial, fee_apr, elapsed),
+            )?,
+            compounded_value: CurrencyAmount::from_raw_amount(
+                value.currency.clone(),
+                compounded.clone(),
+            )?,
+        });
+    }
+    Ok(schedule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    fn collect_options(owed0: u64, owed1: u64) -> CollectOptions<Token, Token> {
+        CollectOptions {
+            token_id: U256::from(1),
+            expected_currency_owed0: CurrencyAmount::from_raw_amount(TOKEN0.clone(), owed0)
+                .unwrap(),
+            expected_currency_owed1: CurrencyAmount::from_raw_amount(TOKEN1.clone(), owed1)
+                .unwrap(),
+            recipient: RECIPIENT,
+        }
+    }
+
+    #[test]
+    fn test_compound_call_parameters() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            100_000,
+            -600,
+            600,
+        );
+        let options = collect_options(10_000, 1_000);
+        let params = compound_call_parameters(&position, &options, &Percent::new(1, 100)).unwrap();
+        assert_eq!(params.parameters.value, U256::ZERO);
+        let calldatas: Vec<Bytes> = decode_multicall(&params.parameters.calldata).unwrap();
+        assert_eq!(calldatas.len(), 9);
+
+        // the fees are collected to the recipient by the router
+        let collect = IApproveAndCall::callPositionManagerCall::abi_decode(&calldatas[0], true)
+            .unwrap()
+            .data;
+        let collect_params = INonfungiblePositionManager::collectCall::abi_decode(&collect, true)
+            .unwrap()
+            .params;
+        assert_eq!(collect_params.tokenId, U256::from(1));
+        assert_eq!(collect_params.recipient, RECIPIENT);
+        assert_eq!(collect_params.amount0Max, u128::MAX);
+
+        // the excess token0 is swapped for token1
+        assert!(params.swap.zero_for_one);
+        assert!(params.swap.amount_in > U256::ZERO);
+        let swap_params = IV3SwapRouter::exactInputSingleCall::abi_decode(&calldatas[1], true)
+            .unwrap()
+            .params;
+        assert_eq!(swap_params.amountIn, params.swap.amount_in);
+        let pull0 = IPeripheryPaymentsExtended::pullCall::abi_decode(&calldatas[2], true).unwrap();
+        assert_eq!(pull0.value, U256::from(10_000) - params.swap.amount_in);
+        let pull1 = IPeripheryPaymentsExtended::pullCall::abi_decode(&calldatas[3], true).unwrap();
+        assert_eq!(pull1.value, U256::from(1_000));
+        let increase_params =
+            IApproveAndCall::increaseLiquidityCall::abi_decode(&calldatas[6], true)
+                .unwrap()
+                .params;
+        assert_eq!(increase_params.tokenId, U256::from(1));
+        assert!(increase_params.amount0Min > U256::ZERO);
+        assert!(increase_params.amount1Min > U256::ZERO);
+    }
+
+    #[test]
+    fn test_compound_out_of_range_skips_swap() {
+        // the range is above the price so the position only holds token0
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            100_000,
+            600,
+            1200,
+        );
+        let params =
+            compound_call_parameters(&position, &collect_options(1_000, 0), &Percent::new(1, 100))
+                .unwrap();
+        assert_eq!(params.swap.amount_in, U256::ZERO);
+        let calldatas: Vec<Bytes> = decode_multicall(&params.parameters.calldata).unwrap();
+        assert_eq!(calldatas.len(), 7);
+        let pull0 = IPeripheryPaymentsExtended::pullCall::abi_decode(&calldatas[1], true).unwrap();
+        assert_eq!(pull0.value, U256::from(1_000));
+    }
+
+    #[test]
+    fn test_compound_invalid_tokens() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            100_000,
+            -600,
+            600,
+        );
+        let options = CollectOptions {
+            expected_currency_owed0: CurrencyAmount::from_raw_amount(TOKEN2.clone(), 1).unwrap(),
+            ..collect_options(0, 0)
+        };
+        assert!(matches!(
+            compound_call_parameters(&position, &options, &Percent::new(1, 100)),
+            Err(Error::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_project_compounding() {
+        let value = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000_000_000_i64).unwrap();
+        let fee_apr = Percent::new(10, 100);
+        // compound daily for a year
+        let schedule = project_compounding(&value, &fee_apr, 86_400, U256::ZERO, 365).unwrap();
+        assert_eq!(schedule.len(), 366);
+        assert_eq!(schedule[0].value, value);
+        let last = schedule.last().unwrap();
+        assert_eq!(last.elapsed, SECONDS_PER_YEAR);
+        assert_eq!(last.value.quotient(), BigInt::from(1_100_000_000_000_i64));
+        // (1 + 10% / 365) ^ 365 ~ 1.10516
+        let compounded = last.compounded_value.quotient();
+        assert!(compounded > BigInt::from(1_105_150_000_000_i64));
+        assert!(compounded < BigInt::from(1_105_160_000_000_i64));
+        assert!(schedule
+            .windows(2)
+            .all(|w| w[0].compounded_value.quotient() < w[1].compounded_value.quotient()));
+
+        // the cost exceeds the daily fees, so compounding daily loses value
+        let schedule =
+            project_compounding(&value, &fee_apr, 86_400, U256::from(300_000_000), 365).unwrap();
+        let last = schedule.last().unwrap();
+        assert!(last.compounded_value.quotient() < value.quotient());
+
+        assert!(matches!(
+            project_compounding(&value, &fee_apr, 0, U256::ZERO, 365),
+            Err(Error::InvalidInterval)
+        ));
+    }
+}
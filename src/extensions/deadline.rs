@@ -0,0 +1,82 @@
+//! ## Deadline
+//! Resolves relative deadlines, i.e. "now + N seconds", into the absolute epoch seconds expected by
+//! the `deadline` of [`AddLiquidityOptions`], [`RemoveLiquidityOptions`], [`MigrateOptions`] and of
+//! the permits, either from the timestamp of a block or, with the `std` feature, from the system
+//! clock.
+
+use super::multicall3::get_block_timestamp;
+use crate::prelude::{Error, *};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+};
+use alloy_primitives::U256;
+
+/// Returns the deadline `seconds` after the timestamp of a block.
+///
+/// The chain time is preferred over the system clock, which may drift from it.
+///
+/// ## Arguments
+///
+/// * `seconds`: The number of seconds after the block until the deadline
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query, by default the latest block
+#[inline]
+pub async fn deadline_after<N, P>(
+    seconds: u64,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<U256, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let timestamp = get_block_timestamp(
+        provider,
+        block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+    )
+    .await?;
+    Ok(U256::from(timestamp.saturating_add(seconds)))
+}
+
+/// Returns the deadline `seconds` after the current time of the system clock.
+///
+/// ## Arguments
+///
+/// * `seconds`: The number of seconds from now until the deadline
+#[cfg(feature = "std")]
+#[inline]
+#[must_use]
+pub fn deadline_from_now(seconds: u64) -> U256 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    U256::from(now.saturating_add(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[tokio::test]
+    async fn test_deadline_after() {
+        let timestamp = get_block_timestamp(PROVIDER.clone(), BLOCK_ID.unwrap())
+            .await
+            .unwrap();
+        let deadline = deadline_after(1800, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(deadline, U256::from(timestamp + 1800));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_deadline_from_now() {
+        let before = deadline_from_now(0);
+        let deadline = deadline_from_now(600);
+        assert!(deadline >= before + U256::from(600));
+        assert!(deadline <= deadline_from_now(600));
+    }
+}
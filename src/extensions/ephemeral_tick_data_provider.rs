@@ -4,11 +4,13 @@
 use crate::prelude::*;
 use alloy::{eips::BlockId, network::Network, providers::Provider};
 use alloy_primitives::{aliases::I24, Address};
+use core::{future::Future, time::Duration};
 use derive_more::Deref;
 use uniswap_lens::pool_lens;
 
 /// A data provider that fetches ticks using an ephemeral contract in a single `eth_call`.
 #[derive(Clone, Debug, PartialEq, Deref)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EphemeralTickDataProvider<I = I24> {
     pub pool: Address,
     pub tick_lower: I,
@@ -21,6 +23,10 @@ pub struct EphemeralTickDataProvider<I = I24> {
 
 impl<I: TickIndex> EphemeralTickDataProvider<I> {
     #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(%pool, ?block_id))
+    )]
     pub async fn new<N, P>(
         pool: Address,
         provider: P,
@@ -49,6 +55,145 @@ impl<I: TickIndex> EphemeralTickDataProvider<I> {
                 )
             })
             .collect();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(ticks = ticks.len(), "fetched ticks");
+        Ok(Self {
+            pool,
+            tick_lower: I::from_i24(tick_lower),
+            tick_upper: I::from_i24(tick_upper),
+            tick_spacing: I::from_i24(tick_spacing),
+            block_id,
+            ticks,
+        })
+    }
+
+    /// Fetch the ticks in the range in multiple `eth_call`s of at most `chunk_size` ticks each, for
+    /// pools whose ticks are too dense to be fetched within the gas or response size limit of a
+    /// single call
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `provider`: The alloy provider
+    /// * `tick_lower`: The lower tick of the range, defaults to [`MIN_TICK`]
+    /// * `tick_upper`: The upper tick of the range, defaults to [`MAX_TICK`]
+    /// * `chunk_size`: The number of ticks covered by each call, which must be positive
+    /// * `block_id`: Optional block number to query
+    #[inline]
+    pub async fn new_chunked<N, P>(
+        pool: Address,
+        provider: P,
+        tick_lower: Option<I>,
+        tick_upper: Option<I>,
+        chunk_size: I,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        Self::new_chunked_with_retry(
+            pool,
+            provider,
+            tick_lower,
+            tick_upper,
+            chunk_size,
+            block_id,
+            &RetryPolicy::NONE,
+            |_| core::future::ready(()),
+        )
+        .await
+    }
+
+    /// Fetch the ticks in the range in multiple `eth_call`s like
+    /// [`EphemeralTickDataProvider::new_chunked`], retrying the calls that fail with a transient
+    /// error and spacing them within the call budget of a [`RetryPolicy`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `provider`: The alloy provider
+    /// * `tick_lower`: The lower tick of the range, defaults to [`MIN_TICK`]
+    /// * `tick_upper`: The upper tick of the range, defaults to [`MAX_TICK`]
+    /// * `chunk_size`: The number of ticks covered by each call, which must be positive
+    /// * `block_id`: Optional block number to query
+    /// * `retry_policy`: How to retry and space the calls
+    /// * `sleep`: The sleep function of the async runtime, e.g. `tokio::time::sleep`, or
+    ///   `gloo_timers::future::sleep` in a browser
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(%pool, ?block_id))
+    )]
+    pub async fn new_chunked_with_retry<N, P, S, SFut>(
+        pool: Address,
+        provider: P,
+        tick_lower: Option<I>,
+        tick_upper: Option<I>,
+        chunk_size: I,
+        block_id: Option<BlockId>,
+        retry_policy: &RetryPolicy,
+        sleep: S,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+        S: Fn(Duration) -> SFut,
+        SFut: Future<Output = ()>,
+    {
+        let chunk_size = chunk_size.to_i24().as_i32();
+        if chunk_size <= 0 {
+            return Err(Error::InvalidRange);
+        }
+        let tick_lower = tick_lower.map_or(MIN_TICK, I::to_i24);
+        let tick_upper = tick_upper.map_or(MAX_TICK, I::to_i24);
+        let mut ticks = Vec::new();
+        let mut tick_spacing = I24::ZERO;
+        let provider = &provider;
+        let mut chunk_lower = tick_lower.as_i32();
+        while chunk_lower <= tick_upper.as_i32() {
+            let chunk_upper = chunk_lower
+                .saturating_add(chunk_size - 1)
+                .min(tick_upper.as_i32());
+            if chunk_lower != tick_lower.as_i32() && !retry_policy.call_interval().is_zero() {
+                sleep(retry_policy.call_interval()).await;
+            }
+            let (lower, upper) = (
+                I24::try_from(chunk_lower).unwrap(),
+                I24::try_from(chunk_upper).unwrap(),
+            );
+            let (chunk, spacing) = retry_policy
+                .call(
+                    move || async move {
+                        pool_lens::get_populated_ticks_in_range(
+                            pool, lower, upper, provider, block_id,
+                        )
+                        .await
+                        .map_err(Error::LensError)
+                    },
+                    &sleep,
+                )
+                .await?;
+            tick_spacing = spacing;
+            // the lens scans whole tick bitmap words, so a word shared by adjacent chunks is
+            // returned twice
+            ticks.extend(
+                chunk
+                    .into_iter()
+                    .filter(|tick| (chunk_lower..=chunk_upper).contains(&tick.tick.as_i32()))
+                    .map(|tick| {
+                        Tick::new(
+                            I::from_i24(tick.tick),
+                            tick.liquidityGross,
+                            tick.liquidityNet,
+                        )
+                    }),
+            );
+            chunk_lower = chunk_upper + 1;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(ticks = ticks.len(), "fetched ticks");
         Ok(Self {
             pool,
             tick_lower: I::from_i24(tick_lower),
@@ -108,4 +253,39 @@ mod tests {
         assert_eq!(tick.liquidity_net, 398290794261);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_ephemeral_tick_data_provider_chunked() -> Result<(), Error> {
+        let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+        let provider =
+            EphemeralTickDataProvider::new(pool, PROVIDER.clone(), None, None, *BLOCK_ID).await?;
+        let chunked = EphemeralTickDataProvider::new_chunked(
+            pool,
+            PROVIDER.clone(),
+            None,
+            None,
+            100_000,
+            *BLOCK_ID,
+        )
+        .await?;
+        assert_eq!(chunked.tick_spacing, TICK_SPACING);
+        assert_eq!(chunked.ticks, provider.ticks);
+        chunked.ticks.validate_list(TICK_SPACING);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_tick_data_provider_chunked_invalid_chunk_size() {
+        let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+        let result = EphemeralTickDataProvider::<I24>::new_chunked(
+            pool,
+            PROVIDER.clone(),
+            None,
+            None,
+            I24::ZERO,
+            *BLOCK_ID,
+        )
+        .await;
+        assert!(matches!(result, Err(Error::InvalidRange)));
+    }
 }
@@ -1,13 +1,16 @@
 //! ## Ephemeral Tick Map Data Provider
 //! A data provider that fetches ticks using an [ephemeral contract](https://github.com/Aperture-Finance/Aperture-Lens/blob/904101e4daed59e02fd4b758b98b0749e70b583b/contracts/EphemeralGetPopulatedTicksInRange.sol) in a single `eth_call`.
 
-use crate::prelude::*;
+use crate::prelude::{Error, *};
 use alloy::{eips::BlockId, network::Network, providers::Provider};
-use alloy_primitives::{aliases::I24, Address};
+use alloy_primitives::{aliases::I24, Address, I256};
 use derive_more::Deref;
+use futures::stream::{self, StreamExt};
+use uniswap_sdk_core::prelude::*;
 
 /// A data provider that fetches ticks using an ephemeral contract in a single `eth_call`.
 #[derive(Clone, Debug, Deref)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EphemeralTickMapDataProvider<I = I24> {
     pub pool: Address,
     pub tick_lower: I,
@@ -43,6 +46,133 @@ impl<I: TickIndex> EphemeralTickMapDataProvider<I> {
             tick_map: TickMap::new(provider.ticks, provider.tick_spacing),
         })
     }
+
+    /// Fetch the ticks in the range in multiple `eth_call`s of at most `chunk_size` ticks each and
+    /// stitch them into one [`TickMap`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `provider`: The alloy provider
+    /// * `tick_lower`: The lower tick of the range, defaults to [`MIN_TICK`]
+    /// * `tick_upper`: The upper tick of the range, defaults to [`MAX_TICK`]
+    /// * `chunk_size`: The number of ticks covered by each call
+    /// * `block_id`: Optional block number to query
+    #[inline]
+    pub async fn new_chunked<N, P>(
+        pool: Address,
+        provider: P,
+        tick_lower: Option<I>,
+        tick_upper: Option<I>,
+        chunk_size: I,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let provider = EphemeralTickDataProvider::new_chunked(
+            pool, provider, tick_lower, tick_upper, chunk_size, block_id,
+        )
+        .await?;
+        Ok(Self {
+            pool,
+            tick_lower: provider.tick_lower,
+            tick_upper: provider.tick_upper,
+            tick_spacing: provider.tick_spacing,
+            block_id,
+            tick_map: TickMap::new(provider.ticks, provider.tick_spacing),
+        })
+    }
+
+    /// Fetch the ticks of many pools concurrently, sharing the provider
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pool addresses
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query
+    /// * `concurrency`: The maximum number of pools fetched at a time
+    ///
+    /// ## Returns
+    ///
+    /// The result for each pool in the order of the pools, so that a pool that fails to be fetched
+    /// does not fail the others
+    #[inline]
+    pub async fn new_many<N, P>(
+        pools: &[Address],
+        provider: P,
+        block_id: Option<BlockId>,
+        concurrency: usize,
+    ) -> Vec<Result<Self, Error>>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        assert!(concurrency > 0, "CONCURRENCY");
+        let provider = &provider;
+        stream::iter(pools)
+            .map(move |&pool| Self::new::<N, _>(pool, provider, None, None, block_id))
+            .buffered(concurrency)
+            .collect()
+            .await
+    }
+
+    /// Fetch the ticks of a pool within the range to prefetch for a swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to swap in
+    /// * `provider`: The alloy provider
+    /// * `radius`: How many tick bitmap words around the current tick to prefetch
+    /// * `amount_in`: The input amount of the swap
+    /// * `block_id`: Optional block number to query
+    ///
+    /// ## Notes
+    ///
+    /// With [`PrefetchRadius::Auto`], the swap is simulated on the fetched ticks and the tick it
+    /// reaches is recorded to tune the range of the next prefetch. Ticks beyond the fetched range
+    /// are treated as uninitialized, so a swap walking further than prefetched raises the
+    /// multiplier.
+    #[inline]
+    pub async fn prefetch<TP, N, P>(
+        pool: &Pool<TP>,
+        provider: P,
+        radius: &mut PrefetchRadius,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        TP: TickDataProvider<Index = I>,
+        N: Network,
+        P: Provider<N>,
+    {
+        let (tick_lower, tick_upper) = radius.tick_range(pool, amount_in)?;
+        let provider = Self::new(
+            pool.address(None, None),
+            provider,
+            Some(tick_lower),
+            Some(tick_upper),
+            block_id,
+        )
+        .await?;
+        if let PrefetchRadius::Auto(adaptive) = radius {
+            if let Ok(swap) = v3_swap(
+                pool.fee.into(),
+                pool.sqrt_ratio_x96,
+                pool.tick_current,
+                pool.liquidity,
+                pool.tick_spacing(),
+                &provider.tick_map,
+                amount_in.currency.equals(&pool.token0),
+                I256::from_big_int(amount_in.quotient()),
+                None,
+            ) {
+                adaptive.record(pool, amount_in, swap.tick_current)?;
+            }
+        }
+        Ok(provider)
+    }
 }
 
 #[cfg(test)]
@@ -88,4 +218,50 @@ mod tests {
         assert!(!initialized);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_prefetch_auto() -> Result<(), Error> {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            WETH.address(),
+            USDC.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await?;
+        let amount_in = CurrencyAmount::from_raw_amount(WETH.clone(), 10_i128.pow(21))?;
+        let mut radius = PrefetchRadius::Auto(AdaptivePrefetch::default());
+        let provider = EphemeralTickMapDataProvider::prefetch(
+            &pool,
+            PROVIDER.clone(),
+            &mut radius,
+            &amount_in,
+            *BLOCK_ID,
+        )
+        .await?;
+        assert!(!provider.tick_map.inner.is_empty());
+        // the outcome of the swap is recorded in the radius itself
+        assert_ne!(radius, PrefetchRadius::Auto(AdaptivePrefetch::default()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_new_many() -> Result<(), Error> {
+        let pools = [
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"),
+            address!("CBCdF9626bC03E24f779434178A73a0B4bad62eD"),
+        ];
+        let providers =
+            EphemeralTickMapDataProvider::<i32>::new_many(&pools, PROVIDER.clone(), *BLOCK_ID, 2)
+                .await;
+        assert_eq!(providers.len(), 2);
+        for (provider, pool) in providers.into_iter().zip(pools) {
+            let provider = provider?;
+            assert_eq!(provider.pool, pool);
+            assert!(!provider.tick_map.inner.is_empty());
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,263 @@
+//! ## Fee APR
+//! Estimates the annualized fee APR of a position or a hypothetical price range from the fee growth
+//! of the pool sampled at two blocks.
+
+use super::multicall3::decode_returns;
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+};
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{aliases::I24, Address, U160, U256};
+use alloy_sol_types::SolCall;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// The fee growth inside a price range sampled at a block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FeeGrowthSample {
+    fee_growth_inside0_x128: U256,
+    fee_growth_inside1_x128: U256,
+    sqrt_price_x96: U160,
+    timestamp: U256,
+}
+
+async fn sample_fee_growth<N, P>(
+    pool: Address,
+    tick_lower: I24,
+    tick_upper: I24,
+    provider: P,
+    block_id: BlockId,
+) -> Result<FeeGrowthSample, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let results = multicall3_aggregate(
+        [
+            (pool, IUniswapV3Pool::slot0Call {}.abi_encode()),
+            (
+                pool,
+                IUniswapV3Pool::feeGrowthGlobal0X128Call {}.abi_encode(),
+            ),
+            (
+                pool,
+                IUniswapV3Pool::feeGrowthGlobal1X128Call {}.abi_encode(),
+            ),
+            (
+                pool,
+                IUniswapV3Pool::ticksCall { tick: tick_lower }.abi_encode(),
+            ),
+            (
+                pool,
+                IUniswapV3Pool::ticksCall { tick: tick_upper }.abi_encode(),
+            ),
+            (
+                MULTICALL3_ADDRESS,
+                IMulticall3::getCurrentBlockTimestampCall {}.abi_encode(),
+            ),
+        ]
+        .map(|(target, call_data)| (target, call_data.into())),
+        provider,
+        Some(block_id),
+    )
+    .await?;
+    let slot0 = decode_returns::<IUniswapV3Pool::slot0Call>(&results[0])?;
+    let fee_growth_global0_x128 =
+        decode_returns::<IUniswapV3Pool::feeGrowthGlobal0X128Call>(&results[1])?._0;
+    let fee_growth_global1_x128 =
+        decode_returns::<IUniswapV3Pool::feeGrowthGlobal1X128Call>(&results[2])?._0;
+    let lower = decode_returns::<IUniswapV3Pool::ticksCall>(&results[3])?;
+    let upper = decode_returns::<IUniswapV3Pool::ticksCall>(&results[4])?;
+    let timestamp =
+        decode_returns::<IMulticall3::getCurrentBlockTimestampCall>(&results[5])?.timestamp;
+    let (fee_growth_inside0_x128, fee_growth_inside1_x128) = get_fee_growth_inside(
+        FeeGrowthOutside {
+            fee_growth_outside0_x128: lower.feeGrowthOutside0X128,
+            fee_growth_outside1_x128: lower.feeGrowthOutside1X128,
+        },
+        FeeGrowthOutside {
+            fee_growth_outside0_x128: upper.feeGrowthOutside0X128,
+            fee_growth_outside1_x128: upper.feeGrowthOutside1X128,
+        },
+        tick_lower,
+        tick_upper,
+        slot0.tick,
+        fee_growth_global0_x128,
+        fee_growth_global1_x128,
+    );
+    Ok(FeeGrowthSample {
+        fee_growth_inside0_x128,
+        fee_growth_inside1_x128,
+        sqrt_price_x96: slot0.sqrtPriceX96,
+        timestamp,
+    })
+}
+
+/// Estimates the annualized fee APR of a price range from the fee growth of the pool between two
+/// blocks.
+///
+/// The fees earned per unit of liquidity over the period and the value of a unit of liquidity in
+/// the range are both valued in token1 at the price of the end block. If the ticks of the range are
+/// not initialized, e.g. for a hypothetical range, the fee growth outside them is unknown and the
+/// fees are only accurate if the price did not cross the range bounds during the period.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool address
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `provider`: The alloy provider
+/// * `block_start`: The block at the start of the period
+/// * `block_end`: The block at the end of the period, later than the start block
+///
+/// ## Returns
+///
+/// The fee APR as a [`Percent`]
+#[inline]
+pub async fn get_fee_apr<I, N, P>(
+    pool: Address,
+    tick_lower: I,
+    tick_upper: I,
+    provider: P,
+    block_start: BlockId,
+    block_end: BlockId,
+) -> Result<Percent, Error>
+where
+    I: TickIndex,
+    N: Network,
+    P: Provider<N>,
+{
+    if tick_lower >= tick_upper {
+        return Err(Error::InvalidRange);
+    }
+    let tick_lower = tick_lower.to_i24();
+    let tick_upper = tick_upper.to_i24();
+    let start =
+        sample_fee_growth(pool, tick_lower, tick_upper, provider.root(), block_start).await?;
+    let end = sample_fee_growth(pool, tick_lower, tick_upper, provider.root(), block_end).await?;
+    if end.timestamp <= start.timestamp {
+        return Err(Error::InvalidTimeRange);
+    }
+
+    let delta0 =
+        fee_growth_sub(end.fee_growth_inside0_x128, start.fee_growth_inside0_x128).to_big_int();
+    let delta1 =
+        fee_growth_sub(end.fee_growth_inside1_x128, start.fee_growth_inside1_x128).to_big_int();
+    let sqrt_price = end.sqrt_price_x96.to_big_int();
+    let sqrt_price_a = get_sqrt_ratio_at_tick(tick_lower)?.to_big_int();
+    let sqrt_price_b = get_sqrt_ratio_at_tick(tick_upper)?.to_big_int();
+    let sqrt_price_clamped = sqrt_price
+        .clone()
+        .clamp(sqrt_price_a.clone(), sqrt_price_b.clone());
+    let price_x192 = &sqrt_price * &sqrt_price;
+
+    // fees per unit of liquidity in token1: (delta0 * P + delta1) / 2^128
+    // value of a unit of liquidity in token1: (1 / sp - 1 / sb) * P + (sp - sa)
+    // where P is the price and sp the sqrt price clamped to [sa, sb]
+    let fees = &delta0 * &price_x192 + &delta1 * Q192.to_big_int();
+    let value = (&sqrt_price_b - &sqrt_price_clamped) * &price_x192
+        + (&sqrt_price_clamped - &sqrt_price_a) * &sqrt_price_clamped * &sqrt_price_b;
+    let elapsed = (end.timestamp - start.timestamp).to_big_int();
+    Ok(Percent::new(
+        fees * sqrt_price_clamped
+            * sqrt_price_b
+            * Q96.to_big_int()
+            * BigInt::from(SECONDS_PER_YEAR),
+        Q192.to_big_int() * Q128.to_big_int() * value * elapsed,
+    ))
+}
+
+/// Estimates the annualized fee APR of a position from the fee growth of its pool between two
+/// blocks. See [`get_fee_apr`].
+///
+/// ## Arguments
+///
+/// * `position`: The position
+/// * `provider`: The alloy provider
+/// * `block_start`: The block at the start of the period
+/// * `block_end`: The block at the end of the period
+#[inline]
+pub async fn get_position_fee_apr<TP, N, P>(
+    position: &Position<TP>,
+    provider: P,
+    block_start: BlockId,
+    block_end: BlockId,
+) -> Result<Percent, Error>
+where
+    TP: TickDataProvider,
+    N: Network,
+    P: Provider<N>,
+{
+    get_fee_apr(
+        position.pool.address(None, None),
+        position.tick_lower,
+        position.tick_upper,
+        provider,
+        block_start,
+        block_end,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const POOL: Address = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+
+    #[tokio::test]
+    async fn test_get_fee_apr() {
+        let block_end = BLOCK_ID.unwrap();
+        let block_start = BlockId::from(16_993_000);
+        // a wide range around the price at block 17000000, tick ~ 201_000
+        let apr = get_fee_apr(
+            POOL,
+            190_000,
+            210_000,
+            PROVIDER.clone(),
+            block_start,
+            block_end,
+        )
+        .await
+        .unwrap();
+        assert!(apr.numerator > BigInt::ZERO);
+        assert!(apr.numerator < apr.denominator);
+        // a narrower range earns a higher APR while in range
+        let narrow_apr = get_fee_apr(
+            POOL,
+            200_000,
+            202_000,
+            PROVIDER.clone(),
+            block_start,
+            block_end,
+        )
+        .await
+        .unwrap();
+        assert!(narrow_apr.numerator * apr.denominator > apr.numerator * narrow_apr.denominator);
+        // a range far below the price earns nothing
+        let out_of_range_apr =
+            get_fee_apr(POOL, 0, 10_000, PROVIDER.clone(), block_start, block_end)
+                .await
+                .unwrap();
+        assert_eq!(out_of_range_apr.numerator, BigInt::ZERO);
+        assert!(matches!(
+            get_fee_apr(POOL, 10_000, 0, PROVIDER.clone(), block_start, block_end).await,
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            get_fee_apr(
+                POOL,
+                190_000,
+                210_000,
+                PROVIDER.clone(),
+                block_end,
+                block_start
+            )
+            .await,
+            Err(Error::InvalidTimeRange)
+        ));
+    }
+}
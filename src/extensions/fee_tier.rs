@@ -0,0 +1,299 @@
+//! ## Fee Tier
+//! Recommends a fee tier for a token pair from the volume and liquidity of its pools, or from the
+//! volatility of its price when it has no pools yet, and reports the expected fee yield of each
+//! tier.
+
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+};
+use alloc::vec::Vec;
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{aliases::U24, Address, ChainId};
+use uniswap_sdk_core::prelude::*;
+
+/// The fee tiers enabled on the factory by default, from the lowest to the highest fee
+pub const STANDARD_FEE_TIERS: [FeeAmount; 4] = [
+    FeeAmount::LOWEST,
+    FeeAmount::LOW,
+    FeeAmount::MEDIUM,
+    FeeAmount::HIGH,
+];
+
+/// The volume and liquidity of the pool of a fee tier over a period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeTierStats {
+    pub fee: FeeAmount,
+    /// The volume swapped through the pool over the period, in token1
+    pub volume: BigDecimal,
+    /// The value of the liquidity of the pool, in token1
+    pub liquidity_value: BigDecimal,
+}
+
+/// The expected annualized yield of a full range position in a fee tier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeTierEstimate {
+    pub fee: FeeAmount,
+    /// The fees earned as a proportion of the position value, annualized
+    pub fee_yield: BigDecimal,
+    /// The loss versus rebalancing of the position, `volatility^2 / 8`, annualized
+    pub loss_versus_rebalancing: BigDecimal,
+    /// The fee yield minus the loss versus rebalancing
+    pub net_yield: BigDecimal,
+}
+
+impl FeeTierEstimate {
+    /// Creates an estimate from the fee yield of a tier and the annualized volatility of the price
+    ///
+    /// ## Arguments
+    ///
+    /// * `fee`: The fee tier
+    /// * `fee_yield`: The annualized fee yield of a full range position
+    /// * `volatility`: The annualized volatility of the price, e.g. from
+    ///   [`get_realized_volatility`]
+    #[inline]
+    #[must_use]
+    pub fn new(fee: FeeAmount, fee_yield: BigDecimal, volatility: &BigDecimal) -> Self {
+        let loss_versus_rebalancing = volatility.square() / BigDecimal::from(8);
+        Self {
+            fee,
+            net_yield: &fee_yield - &loss_versus_rebalancing,
+            fee_yield,
+            loss_versus_rebalancing,
+        }
+    }
+}
+
+/// The fee tier recommended for a token pair, see [`recommend_fee_tier`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeTierRecommendation {
+    pub fee: FeeAmount,
+    /// The estimates of the tiers with liquidity sorted by the net yield in descending order
+    pub estimates: Vec<FeeTierEstimate>,
+}
+
+/// Returns the fee rate of a fee tier, e.g. 0.003 for [`FeeAmount::MEDIUM`]
+#[inline]
+#[must_use]
+pub fn fee_rate(fee: FeeAmount) -> BigDecimal {
+    BigDecimal::new(BigInt::from(U24::from(fee).to::<u32>()), 6)
+}
+
+/// Returns the conventional fee tier for the volatility of a pair without pools, from stable pairs
+/// in [`FeeAmount::LOWEST`] to exotic pairs in [`FeeAmount::HIGH`].
+///
+/// ## Arguments
+///
+/// * `volatility`: The annualized volatility of the price
+#[inline]
+#[must_use]
+pub fn default_fee_tier(volatility: &BigDecimal) -> FeeAmount {
+    if volatility < &BigDecimal::new(5.into(), 2) {
+        FeeAmount::LOWEST
+    } else if volatility < &BigDecimal::new(4.into(), 1) {
+        FeeAmount::LOW
+    } else if volatility < &BigDecimal::new(12.into(), 1) {
+        FeeAmount::MEDIUM
+    } else {
+        FeeAmount::HIGH
+    }
+}
+
+/// Recommends the fee tier with the highest expected net yield for a full range position.
+///
+/// The fee yield of a tier is its fee rate times its volume over the value of its liquidity,
+/// annualized. Tiers without liquidity are ignored, and the [`default_fee_tier`] is recommended if
+/// no tier earns fees.
+///
+/// ## Arguments
+///
+/// * `stats`: The volume and liquidity of the pool of each fee tier
+/// * `period`: The length of the period of the volumes in seconds
+/// * `volatility`: The annualized volatility of the price
+#[inline]
+#[must_use]
+pub fn recommend_fee_tier(
+    stats: &[FeeTierStats],
+    period: u64,
+    volatility: &BigDecimal,
+) -> FeeTierRecommendation {
+    assert!(period > 0, "PERIOD");
+    let estimates = stats
+        .iter()
+        .filter(|stats| stats.liquidity_value > BigDecimal::from(0))
+        .map(|stats| {
+            let fee_yield = fee_rate(stats.fee) * &stats.volume / &stats.liquidity_value
+                * BigDecimal::from(SECONDS_PER_YEAR)
+                / BigDecimal::from(period);
+            FeeTierEstimate::new(stats.fee, fee_yield, volatility)
+        })
+        .collect();
+    recommend_from_estimates(estimates, volatility)
+}
+
+fn recommend_from_estimates(
+    mut estimates: Vec<FeeTierEstimate>,
+    volatility: &BigDecimal,
+) -> FeeTierRecommendation {
+    estimates.sort_by(|a, b| b.net_yield.cmp(&a.net_yield));
+    let fee = match estimates.first() {
+        Some(estimate) if estimate.fee_yield > BigDecimal::from(0) => estimate.fee,
+        _ => default_fee_tier(volatility),
+    };
+    FeeTierRecommendation { fee, estimates }
+}
+
+/// Recommends a fee tier for a token pair from the full range fee APR of its pool in each of the
+/// [`STANDARD_FEE_TIERS`] between two blocks, see [`get_fee_apr`] and [`recommend_fee_tier`].
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `factory`: The factory address
+/// * `token_a`: One of the tokens of the pair
+/// * `token_b`: The other token of the pair
+/// * `volatility`: The annualized volatility of the price, e.g. from [`get_realized_volatility`]
+/// * `provider`: The alloy provider
+/// * `block_start`: The block at the start of the period
+/// * `block_end`: The block at the end of the period
+#[inline]
+pub async fn get_fee_tier_recommendation<N, P>(
+    chain_id: ChainId,
+    factory: Address,
+    token_a: Address,
+    token_b: Address,
+    volatility: &BigDecimal,
+    provider: P,
+    block_start: BlockId,
+    block_end: BlockId,
+) -> Result<FeeTierRecommendation, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let provider = provider.root();
+    let mut estimates = Vec::with_capacity(STANDARD_FEE_TIERS.len());
+    for fee in STANDARD_FEE_TIERS {
+        let pool = compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id));
+        // the pool of the tier may not have been deployed
+        if provider
+            .get_code_at(pool)
+            .block_id(block_start)
+            .await?
+            .is_empty()
+        {
+            continue;
+        }
+        let tick_spacing = fee.tick_spacing();
+        let apr = get_fee_apr(
+            pool,
+            nearest_usable_tick(MIN_TICK, tick_spacing),
+            nearest_usable_tick(MAX_TICK, tick_spacing),
+            provider,
+            block_start,
+            block_end,
+        )
+        .await?;
+        estimates.push(FeeTierEstimate::new(fee, apr.to_decimal(), volatility));
+    }
+    Ok(recommend_from_estimates(estimates, volatility))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use core::str::FromStr;
+
+    fn stats(fee: FeeAmount, volume: u64, liquidity_value: u64) -> FeeTierStats {
+        FeeTierStats {
+            fee,
+            volume: BigDecimal::from(volume),
+            liquidity_value: BigDecimal::from(liquidity_value),
+        }
+    }
+
+    #[test]
+    fn test_fee_rate() {
+        assert_eq!(
+            fee_rate(FeeAmount::MEDIUM),
+            BigDecimal::from_str("0.003").unwrap()
+        );
+        assert_eq!(
+            fee_rate(FeeAmount::LOWEST),
+            BigDecimal::from_str("0.0001").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_default_fee_tier() {
+        let tier = |volatility: &str| default_fee_tier(&BigDecimal::from_str(volatility).unwrap());
+        assert_eq!(tier("0.01"), FeeAmount::LOWEST);
+        assert_eq!(tier("0.2"), FeeAmount::LOW);
+        assert_eq!(tier("0.8"), FeeAmount::MEDIUM);
+        assert_eq!(tier("2"), FeeAmount::HIGH);
+    }
+
+    #[test]
+    fn test_recommend_fee_tier() {
+        let volatility = BigDecimal::from_str("0.8").unwrap();
+        let recommendation = recommend_fee_tier(
+            &[
+                // 0.05% of 365 times the liquidity a year
+                stats(FeeAmount::LOW, 1_000_000, 1_000_000),
+                // 0.3% of 73 times the liquidity a year
+                stats(FeeAmount::MEDIUM, 200_000, 1_000_000),
+                stats(FeeAmount::HIGH, 0, 0),
+            ],
+            24 * 60 * 60,
+            &volatility,
+        );
+        assert_eq!(recommendation.fee, FeeAmount::MEDIUM);
+        assert_eq!(recommendation.estimates.len(), 2);
+        let medium = &recommendation.estimates[0];
+        assert_eq!(medium.fee_yield, BigDecimal::from_str("0.219").unwrap());
+        assert_eq!(
+            medium.loss_versus_rebalancing,
+            BigDecimal::from_str("0.08").unwrap()
+        );
+        assert_eq!(medium.net_yield, BigDecimal::from_str("0.139").unwrap());
+        assert_eq!(
+            recommendation.estimates[1].fee_yield,
+            BigDecimal::from_str("0.1825").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_recommend_fee_tier_without_volume() {
+        let recommendation = recommend_fee_tier(
+            &[stats(FeeAmount::LOW, 0, 1_000_000)],
+            24 * 60 * 60,
+            &BigDecimal::from_str("0.01").unwrap(),
+        );
+        assert_eq!(recommendation.fee, FeeAmount::LOWEST);
+        assert_eq!(recommendation.estimates.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_fee_tier_recommendation() {
+        let recommendation = get_fee_tier_recommendation(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            &BigDecimal::from_str("0.6").unwrap(),
+            PROVIDER.clone(),
+            BlockId::from(16_993_000),
+            BLOCK_ID.unwrap(),
+        )
+        .await
+        .unwrap();
+        let fees: Vec<FeeAmount> = recommendation.estimates.iter().map(|e| e.fee).collect();
+        assert!(fees.contains(&FeeAmount::LOW) && fees.contains(&FeeAmount::MEDIUM));
+        assert!(recommendation
+            .estimates
+            .windows(2)
+            .all(|window| window[0].net_yield >= window[1].net_yield));
+        assert_eq!(recommendation.fee, recommendation.estimates[0].fee);
+    }
+}
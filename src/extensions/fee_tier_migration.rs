@@ -0,0 +1,263 @@
+//! ## Fee Tier Migration
+//! Moving the liquidity of a position to a pool of the same token pair with another fee tier, in a
+//! single `NonfungiblePositionManager` multicall.
+
+use crate::{
+    nonfungible_position_manager::{encode_create, encode_remove},
+    prelude::{Error, *},
+};
+use alloy_primitives::U256;
+use alloy_sol_types::SolCall;
+use uniswap_sdk_core::prelude::*;
+
+/// The plan to move a position to another fee tier, see [`migrate_fee_tier_call_parameters`].
+#[derive(Clone, Debug)]
+pub struct FeeTierMigration<TP: TickDataProvider> {
+    /// The minimum amounts of token0 and token1 collected from the position, including the fees
+    /// owed
+    pub collected_amounts: (U256, U256),
+    /// The swap in the target pool that matches the collected amounts to the ratio of the new
+    /// range
+    pub swap: OptimalSwap<TP::Index>,
+    /// The position minted in the target pool from the collected amounts
+    pub position: Position<TP>,
+    /// The calldata of the `NonfungiblePositionManager` multicall that removes the liquidity,
+    /// collects the tokens and mints the new position
+    pub parameters: MethodParameters,
+}
+
+/// Produces the calldata for moving the liquidity of a position to a pool with another fee tier.
+///
+/// The liquidity is removed and collected to the recipient of the collect options as in
+/// [`remove_call_parameters`], then a position is minted in the target pool from the minimum
+/// collected amounts, paid by the sender, so that the whole migration is atomic.
+///
+/// ## Notes
+///
+/// The position manager cannot swap, so the position is minted without the swap and the excess of
+/// one token is left to the sender. The swap returned matches the collected amounts to the ratio
+/// of the new range if executed before minting, e.g. through [`zap_in_call_parameters`]. The
+/// sender must be the recipient of the collect options and approve the position manager to spend
+/// both tokens.
+///
+/// ## Arguments
+///
+/// * `position`: The position to move
+/// * `pool`: The target pool, with a tick data provider to simulate the swap
+/// * `tick_lower`: The lower tick of the new position
+/// * `tick_upper`: The upper tick of the new position
+/// * `remove_options`: Options for removing the liquidity, whose slippage tolerance and deadline
+///   also apply to the mint
+/// * `mint_options`: Options for minting the new position
+#[inline]
+pub fn migrate_fee_tier_call_parameters<TP, TP2>(
+    position: &Position<TP>,
+    pool: Pool<TP2>,
+    tick_lower: TP2::Index,
+    tick_upper: TP2::Index,
+    remove_options: RemoveLiquidityOptions<Token, Token>,
+    mint_options: MintSpecificOptions,
+) -> Result<FeeTierMigration<TP2>, Error>
+where
+    TP: TickDataProvider,
+    TP2: Clone + TickDataProvider,
+{
+    assert!(
+        pool.token0.equals(&position.pool.token0) && pool.token1.equals(&position.pool.token1),
+        "TOKEN"
+    );
+    assert!(pool.fee != position.pool.fee, "SAME_FEE");
+
+    let slippage_tolerance = remove_options.slippage_tolerance.clone();
+    let deadline = remove_options.deadline;
+    let owed0 = U256::from_big_int(
+        remove_options
+            .collect_options
+            .expected_currency_owed0
+            .quotient(),
+    );
+    let owed1 = U256::from_big_int(
+        remove_options
+            .collect_options
+            .expected_currency_owed1
+            .quotient(),
+    );
+    let (mut calldatas, (amount0_min, amount1_min)) = encode_remove(position, remove_options)?;
+    // the amounts collected in the worst case allowed by the slippage
+    let collected0 = amount0_min + owed0;
+    let collected1 = amount1_min + owed1;
+
+    let swap = get_optimal_swap(&pool, tick_lower, tick_upper, collected0, collected1)?;
+    let mut new_position =
+        Position::from_amounts(pool, tick_lower, tick_upper, collected0, collected1, true)?;
+    assert!(new_position.liquidity > 0, "ZERO_LIQUIDITY");
+    let MintAmounts {
+        amount0: amount0_desired,
+        amount1: amount1_desired,
+    } = new_position.mint_amounts_cached()?;
+    let MintAmounts {
+        amount0: amount0_min,
+        amount1: amount1_min,
+    } = new_position.mint_amounts_with_slippage(&slippage_tolerance)?;
+
+    // mint with the collected tokens
+    if mint_options.create_pool {
+        calldatas.push(encode_create(&new_position.pool));
+    }
+    calldatas.push(
+        INonfungiblePositionManager::mintCall {
+            params: INonfungiblePositionManager::MintParams {
+                token0: new_position.pool.token0.address(),
+                token1: new_position.pool.token1.address(),
+                fee: new_position.pool.fee.into(),
+                tickLower: tick_lower.to_i24(),
+                tickUpper: tick_upper.to_i24(),
+                amount0Desired: amount0_desired,
+                amount1Desired: amount1_desired,
+                amount0Min: amount0_min,
+                amount1Min: amount1_min,
+                recipient: mint_options.recipient,
+                deadline,
+            },
+        }
+        .abi_encode()
+        .into(),
+    );
+
+    Ok(FeeTierMigration {
+        collected_amounts: (collected0, collected1),
+        swap,
+        position: new_position,
+        parameters: MethodParameters {
+            calldata: encode_multicall(calldatas),
+            value: U256::ZERO,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, Bytes};
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    fn remove_options() -> RemoveLiquidityOptions<Token, Token> {
+        RemoveLiquidityOptions {
+            token_id: U256::from(1),
+            liquidity_percentage: Percent::new(1, 1),
+            slippage_tolerance: Percent::new(1, 100),
+            deadline: U256::from(123),
+            burn_token: true,
+            permit: None,
+            collect_options: CollectOptions {
+                token_id: U256::from(1),
+                expected_currency_owed0: CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100)
+                    .unwrap(),
+                expected_currency_owed1: CurrencyAmount::from_raw_amount(TOKEN1.clone(), 0)
+                    .unwrap(),
+                recipient: RECIPIENT,
+            },
+            chain_id: None,
+            deployment: None,
+        }
+    }
+
+    fn low_fee_pool() -> Pool<TickListDataProvider> {
+        let tick_spacing = FeeAmount::LOW.tick_spacing();
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(1, 1),
+            1_000_000,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(
+                        nearest_usable_tick(MIN_TICK, tick_spacing).as_i32(),
+                        1_000_000,
+                        1_000_000,
+                    ),
+                    Tick::new(
+                        nearest_usable_tick(MAX_TICK, tick_spacing).as_i32(),
+                        1_000_000,
+                        -1_000_000,
+                    ),
+                ],
+                tick_spacing.as_i32(),
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_migrate_fee_tier_call_parameters() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            100_000,
+            -600,
+            600,
+        );
+        let migration = migrate_fee_tier_call_parameters(
+            &position,
+            low_fee_pool(),
+            -600,
+            600,
+            remove_options(),
+            MintSpecificOptions {
+                recipient: RECIPIENT,
+                create_pool: false,
+            },
+        )
+        .unwrap();
+        let (amount0_min, amount1_min) = position
+            .burn_amounts_with_slippage(&Percent::new(1, 100))
+            .unwrap();
+        assert_eq!(
+            migration.collected_amounts,
+            (amount0_min + U256::from(100), amount1_min)
+        );
+        // the extra token0 owed is swapped to match the ratio of the same range
+        assert!(migration.swap.zero_for_one);
+
+        // decrease liquidity, collect, burn and mint
+        let calldatas: Vec<Bytes> = decode_multicall(&migration.parameters.calldata).unwrap();
+        assert_eq!(calldatas.len(), 4);
+        let remove = remove_call_parameters(&position, remove_options()).unwrap();
+        let remove_calldatas: Vec<Bytes> = decode_multicall(&remove.calldata).unwrap();
+        assert_eq!(calldatas[..3], remove_calldatas[..]);
+        let params = INonfungiblePositionManager::mintCall::abi_decode(&calldatas[3], true)
+            .unwrap()
+            .params;
+        assert_eq!(params.fee, FeeAmount::LOW.into());
+        assert_eq!(params.recipient, RECIPIENT);
+        let MintAmounts { amount0, amount1 } = migration.position.mint_amounts().unwrap();
+        assert_eq!(params.amount0Desired, amount0);
+        assert_eq!(params.amount1Desired, amount1);
+        assert!(amount0 <= migration.collected_amounts.0);
+        assert!(amount1 <= migration.collected_amounts.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "SAME_FEE")]
+    fn test_migrate_fee_tier_same_fee() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            100_000,
+            -600,
+            600,
+        );
+        let _ = migrate_fee_tier_call_parameters(
+            &position,
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            -600,
+            600,
+            remove_options(),
+            MintSpecificOptions {
+                recipient: RECIPIENT,
+                create_pool: false,
+            },
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! ## Fixture
+//! A JSON fixture of a pool and its initialized ticks at a block, to run deterministic tests
+//! against a snapshot of a real pool without an RPC: [`PoolFixture::fetch`] dumps a live pool, and
+//! [`PoolFixture::tick_list_pool`] loads it back into a [`Pool`] backed by a
+//! [`TickListDataProvider`].
+//!
+//! The fixture is an object with the fields `chain_id`, `block_number`, `token0`, `token1`, `fee`,
+//! `tick_spacing`, `sqrt_price_x96`, `liquidity` and `ticks`, where the tokens are objects with the
+//! fields `address`, `decimals`, `symbol` and `name`, and the ticks are in the JSON format of the
+//! `tick_export` module, see [`ticks_to_json`].
+
+use crate::prelude::{Error, *};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{
+    aliases::{I24, U24},
+    Address, ChainId, U160,
+};
+use core::str::FromStr;
+use serde_json::{json, Map, Value};
+use uniswap_sdk_core::prelude::*;
+
+/// A pool and its initialized ticks at a block, loaded from or dumped to a JSON fixture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolFixture<I = I24> {
+    /// The block number at which the pool was dumped
+    pub block_number: u64,
+    /// The metadata and state of the pool, without its ticks
+    pub pool: Pool,
+    /// The initialized ticks of the pool with their fee growth, sorted by index
+    pub ticks: Vec<TickRecord<I>>,
+}
+
+impl<I: TickIndex> PoolFixture<I> {
+    /// Fetches the state of a pool and all its initialized ticks at a block
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address
+    /// * `token_a`: One of the tokens in the pool
+    /// * `token_b`: The other token in the pool
+    /// * `fee`: Fee tier of the pool
+    /// * `provider`: The alloy provider
+    /// * `block_number`: The block number to dump the pool at
+    #[inline]
+    pub async fn fetch<N, P>(
+        chain_id: ChainId,
+        factory: Address,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        provider: P,
+        block_number: u64,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let block_id = Some(BlockId::from(block_number));
+        let pool = Pool::from_pool_key(
+            chain_id,
+            factory,
+            token_a,
+            token_b,
+            fee,
+            provider.root(),
+            block_id,
+        )
+        .await?;
+        let address = compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id));
+        let ticks = get_tick_records(address, provider, None, None, block_id).await?;
+        Ok(Self {
+            block_number,
+            pool,
+            ticks,
+        })
+    }
+
+    /// The tick spacing of the pool
+    #[inline]
+    #[must_use]
+    pub fn tick_spacing(&self) -> I {
+        I::from_i24(self.pool.tick_spacing().to_i24())
+    }
+
+    /// Restores the pool backed by a [`TickListDataProvider`]
+    #[inline]
+    pub fn tick_list_pool(&self) -> Result<Pool<TickListDataProvider<I>>, Error> {
+        let mut pool = Pool::new_with_tick_data_provider(
+            self.pool.token0.clone(),
+            self.pool.token1.clone(),
+            self.pool.fee,
+            self.pool.sqrt_ratio_x96,
+            self.pool.liquidity,
+            if self.ticks.is_empty() {
+                TickListDataProvider::default()
+            } else {
+                TickListDataProvider::new(
+                    self.ticks.iter().map(|record| record.tick).collect(),
+                    self.tick_spacing(),
+                )
+            },
+        )?;
+        pool.custom_tick_spacing = self.pool.custom_tick_spacing;
+        Ok(pool)
+    }
+
+    /// Writes the fixture as JSON
+    #[inline]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let token = |token: &Token| {
+            json!({
+                "address": token.address().to_string(),
+                "decimals": token.decimals(),
+                "symbol": token.symbol(),
+                "name": token.name(),
+            })
+        };
+        let ticks: Value = serde_json::from_str(&ticks_to_json(&self.ticks)).unwrap();
+        json!({
+            "chain_id": self.pool.chain_id(),
+            "block_number": self.block_number,
+            "token0": token(&self.pool.token0),
+            "token1": token(&self.pool.token1),
+            "fee": U24::from(self.pool.fee).to::<u32>(),
+            "tick_spacing": self.pool.tick_spacing(),
+            "sqrt_price_x96": self.pool.sqrt_ratio_x96.to_string(),
+            "liquidity": self.pool.liquidity.to_string(),
+            "ticks": ticks,
+        })
+        .to_string()
+    }
+
+    /// Reads a fixture from JSON
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: Value = serde_json::from_str(json).map_err(|e| invalid(&e.to_string()))?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid("expected an object"))?;
+        let chain_id: ChainId = parse(object, "chain_id")?;
+        let token = |field: &str| -> Result<Token, Error> {
+            let token = object
+                .get(field)
+                .and_then(Value::as_object)
+                .ok_or_else(|| invalid(field))?;
+            let text = |key: &str| {
+                token
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string)
+            };
+            Ok(Token::new(
+                chain_id,
+                parse(token, "address")?,
+                parse(token, "decimals")?,
+                text("symbol"),
+                text("name"),
+                None,
+                None,
+            ))
+        };
+        let ticks = object.get("ticks").ok_or_else(|| invalid("ticks"))?;
+        Ok(Self {
+            block_number: parse(object, "block_number")?,
+            pool: Pool::new(
+                token("token0")?,
+                token("token1")?,
+                FeeAmount::from(parse::<u32>(object, "fee")?),
+                parse::<U160>(object, "sqrt_price_x96")?,
+                parse(object, "liquidity")?,
+            )?
+            .with_tick_spacing(parse::<I24>(object, "tick_spacing")?)?,
+            ticks: ticks_from_json(&ticks.to_string())?,
+        })
+    }
+
+    /// Reads a fixture from a JSON file
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The path of the file
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path).map_err(|e| invalid(&e.to_string()))?)
+    }
+
+    /// Writes the fixture to a JSON file
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The path of the file
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+        std::fs::write(path, self.to_json()).map_err(|e| invalid(&e.to_string()))
+    }
+}
+
+/// Parses a field given as a string or a number
+fn parse<T: FromStr>(object: &Map<String, Value>, field: &str) -> Result<T, Error> {
+    match object.get(field) {
+        Some(Value::String(value)) => value.parse().ok(),
+        Some(Value::Number(value)) => value.to_string().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(field))
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidTickData(format!("fixture: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn fixture() -> PoolFixture<i32> {
+        PoolFixture {
+            block_number: 17_000_000,
+            pool: Pool::new(
+                USDC.clone(),
+                WETH.clone(),
+                FeeAmount::LOW,
+                encode_sqrt_ratio_x96(1, 1),
+                1_000_000,
+            )
+            .unwrap(),
+            ticks: vec![
+                Tick::new(-887270, 1_000_000, 1_000_000).into(),
+                Tick::new(887270, 1_000_000, -1_000_000).into(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        let fixture = fixture();
+        let json = fixture.to_json();
+        assert_eq!(PoolFixture::<i32>::from_json(&json).unwrap(), fixture);
+        let pool = fixture.tick_list_pool().unwrap();
+        assert_eq!(pool.tick_data_provider.len(), 2);
+        assert!(matches!(
+            PoolFixture::<i32>::from_json("[]"),
+            Err(Error::InvalidTickData(_))
+        ));
+        assert!(matches!(
+            PoolFixture::<i32>::from_json(&json.replace("\"fee\"", "\"fees\"")),
+            Err(Error::InvalidTickData(_))
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join("uniswap_v3_sdk_pool_fixture.json");
+        fixture().save(&path).unwrap();
+        assert_eq!(PoolFixture::<i32>::load(&path).unwrap(), fixture());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let fixture = PoolFixture::<I24>::fetch(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            17_000_000,
+        )
+        .await
+        .unwrap();
+        assert!(fixture.ticks.len() > 100);
+        let restored = PoolFixture::<I24>::from_json(&fixture.to_json()).unwrap();
+        assert_eq!(restored, fixture);
+        // the restored pool quotes offline
+        let pool = restored.tick_list_pool().unwrap();
+        let amount_in = CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000_000).unwrap();
+        assert!(pool.get_output_amount(&amount_in, None).is_ok());
+    }
+}
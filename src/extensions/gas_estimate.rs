@@ -0,0 +1,140 @@
+//! ## Gas Estimate
+//! This module provides functions to estimate the gas used by the transaction described by the
+//! [`MethodParameters`] produced by the SDK with `eth_estimateGas`, optionally overriding the token
+//! balances and allowances of the sender that the transaction needs but may not be set yet, e.g.
+//! to quote the gas cost of a swap before approving the router.
+
+use crate::prelude::{Error, *};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::{Network, TransactionBuilder},
+    providers::Provider,
+    rpc::types::state::StateOverride,
+};
+use alloy_primitives::{Address, U256};
+
+/// Estimate the gas used by the transaction described by the method parameters using
+/// `eth_estimateGas`.
+///
+/// ## Arguments
+///
+/// * `method_parameters`: The calldata and value of the transaction
+/// * `from`: The sender of the transaction
+/// * `to`: The contract to call, e.g. the swap router or the nonfungible position manager
+/// * `state_overrides`: Optional state overrides, e.g. from [`get_erc20_state_overrides`]
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn estimate_gas<N, P>(
+    method_parameters: &MethodParameters,
+    from: Address,
+    to: Address,
+    state_overrides: Option<&StateOverride>,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<u64, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let tx = N::TransactionRequest::default()
+        .with_from(from)
+        .with_to(to)
+        .with_input(method_parameters.calldata.clone())
+        .with_value(method_parameters.value);
+    let call = provider
+        .estimate_gas(&tx)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)));
+    Ok(match state_overrides {
+        Some(state_overrides) => call.overrides(state_overrides).await?,
+        None => call.await?,
+    })
+}
+
+/// Estimate the gas used by the transaction described by the method parameters, with the balances
+/// of ERC20 tokens of the sender and its allowances to the target overridden.
+///
+/// ## Arguments
+///
+/// * `method_parameters`: The calldata and value of the transaction
+/// * `from`: The sender of the transaction
+/// * `to`: The contract to call, which is also the spender of the tokens
+/// * `balances`: The tokens and the balance and allowance of each to set, e.g. the input token of
+///   a swap or both tokens of a mint
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn estimate_gas_with_balances<N, P>(
+    method_parameters: &MethodParameters,
+    from: Address,
+    to: Address,
+    balances: &[(Address, U256)],
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<u64, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let mut state_overrides = StateOverride::default();
+    for &(token, amount) in balances {
+        state_overrides.extend(
+            get_erc20_state_overrides(token, from, to, amount, provider.root(), block_id).await?,
+        );
+    }
+    estimate_gas(
+        method_parameters,
+        from,
+        to,
+        Some(&state_overrides),
+        provider,
+        block_id,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+    use alloy_sol_types::SolCall;
+    use uniswap_lens::bindings::ierc20::IERC20;
+    use uniswap_sdk_core::prelude::BaseCurrency;
+
+    #[tokio::test]
+    async fn test_estimate_gas() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let params = MethodParameters {
+            calldata: IERC20::transferCall::new((
+                address!("2222222222222222222222222222222222222222"),
+                U256::from(1_000_000),
+            ))
+            .abi_encode()
+            .into(),
+            value: U256::ZERO,
+        };
+        // the sender holds no USDC
+        assert!(estimate_gas(
+            &params,
+            from,
+            USDC.address(),
+            None,
+            PROVIDER.clone(),
+            *BLOCK_ID
+        )
+        .await
+        .is_err());
+        let gas = estimate_gas_with_balances(
+            &params,
+            from,
+            USDC.address(),
+            &[(USDC.address(), U256::from(1_000_000))],
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(gas > 21_000 && gas < 100_000);
+    }
+}
@@ -0,0 +1,189 @@
+//! ## Greeks
+//! Analytical sensitivities of the value of a concentrated liquidity position with respect to the
+//! pool price, and the size of the short of token0 that hedges the delta of a position.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::U160;
+use uniswap_sdk_core::prelude::*;
+
+/// The sensitivities of the value of a position in token1 with respect to the price of token0 in
+/// token1, both in the raw units of the tokens.
+#[derive(Clone, Debug)]
+pub struct PositionGreeks {
+    /// The first derivative of the value with respect to the price, which is the amount of token0
+    /// held by the position
+    pub delta: Fraction,
+    /// The second derivative of the value with respect to the price, which is non-positive
+    pub gamma: Fraction,
+}
+
+/// The hedge of a position at a price of a grid, see [`Position::hedge_grid`].
+#[derive(Clone, Debug)]
+pub struct HedgePoint {
+    /// The sqrt price of the grid
+    pub sqrt_price_x96: U160,
+    /// The size of the short of token0 in raw units that makes the position delta-neutral at the
+    /// price
+    pub size: Fraction,
+    /// The change of the size from the hedge at the current pool price, positive if the short
+    /// must be increased
+    pub adjustment: Fraction,
+}
+
+impl<TP: TickDataProvider> Position<TP> {
+    /// Returns the delta and gamma of the position at the current pool price
+    ///
+    /// The value of liquidity `L` in the range `[pa, pb]` at the price `P` is
+    /// `L * (2 * sqrt(P) - sqrt(pa) - P / sqrt(pb))` in range, so the delta is
+    /// `L * (1 / sqrt(P) - 1 / sqrt(pb))` and the gamma is `-L / (2 * P^(3/2))`. Below the range
+    /// the position only holds token0 and its delta is constant, while above the range it only
+    /// holds token1 and both are zero.
+    #[inline]
+    pub fn greeks(&self) -> Result<PositionGreeks, Error> {
+        let liquidity = BigInt::from(self.liquidity);
+        let q96 = Q96.to_big_int();
+        let sqrt_price_upper = get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?.to_big_int();
+        let zero = || Fraction::new(0, 1);
+        Ok(if self.pool.tick_current < self.tick_lower {
+            let sqrt_price_lower = get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?.to_big_int();
+            PositionGreeks {
+                delta: Fraction::new(
+                    liquidity * q96 * (&sqrt_price_upper - &sqrt_price_lower),
+                    sqrt_price_lower * sqrt_price_upper,
+                ),
+                gamma: zero(),
+            }
+        } else if self.pool.tick_current < self.tick_upper {
+            let sqrt_price = self.pool.sqrt_ratio_x96.to_big_int();
+            PositionGreeks {
+                delta: Fraction::new(
+                    &liquidity * &q96 * (&sqrt_price_upper - &sqrt_price),
+                    &sqrt_price * sqrt_price_upper,
+                ),
+                gamma: Fraction::new(-liquidity * q96.pow(3), BigInt::from(2) * sqrt_price.pow(3)),
+            }
+        } else {
+            PositionGreeks {
+                delta: zero(),
+                gamma: zero(),
+            }
+        })
+    }
+
+    /// Returns the size of the short of token0, e.g. of a perpetual, in raw units that
+    /// delta-hedges the position at the current pool price, which is its delta
+    #[inline]
+    pub fn hedge_size(&self) -> Result<Fraction, Error> {
+        Ok(self.greeks()?.delta)
+    }
+
+    /// Returns the size of the short of token0 that delta-hedges the position at each price of a
+    /// grid, and its change from the hedge at the current pool price.
+    ///
+    /// The size decreases as the price rises through the range, from the amount of token0 held
+    /// below the range to zero above it, so a hedge is rebalanced by buying back token0 as the
+    /// price rises and by selling it as the price falls.
+    ///
+    /// ## Arguments
+    ///
+    /// * `sqrt_prices_x96`: The sqrt prices of the grid
+    #[inline]
+    pub fn hedge_grid(&self, sqrt_prices_x96: &[U160]) -> Result<Vec<HedgePoint>, Error> {
+        let current = self.hedge_size()?;
+        let liquidity = BigInt::from(self.liquidity);
+        let q96 = Q96.to_big_int();
+        let sqrt_price_lower = get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?.to_big_int();
+        let sqrt_price_upper = get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?.to_big_int();
+        Ok(sqrt_prices_x96
+            .iter()
+            .map(|&sqrt_price_x96| {
+                // the amount of token0 held at the price, L * (1 / sqrt(P) - 1 / sqrt(pb))
+                let sqrt_price = sqrt_price_x96
+                    .to_big_int()
+                    .clamp(sqrt_price_lower.clone(), sqrt_price_upper.clone());
+                let size = Fraction::new(
+                    &liquidity * &q96 * (&sqrt_price_upper - &sqrt_price),
+                    sqrt_price * &sqrt_price_upper,
+                );
+                let adjustment = Fraction::new(
+                    &size.numerator * &current.denominator - &current.numerator * &size.denominator,
+                    &size.denominator * &current.denominator,
+                );
+                HedgePoint {
+                    sqrt_price_x96,
+                    size,
+                    adjustment,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::aliases::I24;
+
+    #[test]
+    fn test_greeks_in_range() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            1_000_000_000_000,
+            -600,
+            600,
+        );
+        let greeks = position.greeks().unwrap();
+        // the delta is the amount of token0, which is rounded down
+        assert_eq!(
+            greeks.delta.quotient(),
+            position.amount0().unwrap().quotient()
+        );
+        // at a price of 1 the gamma is -L / 2
+        assert_eq!(greeks.gamma.quotient(), BigInt::from(-500_000_000_000_i64));
+    }
+
+    #[test]
+    fn test_greeks_out_of_range() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let below = Position::new(pool.clone(), 1_000_000_000_000, 60, 600);
+        let greeks = below.greeks().unwrap();
+        assert_eq!(greeks.delta.quotient(), below.amount0().unwrap().quotient());
+        assert_eq!(greeks.gamma.numerator, BigInt::ZERO);
+
+        let above = Position::new(pool, 1_000_000_000_000, -600, -60);
+        let greeks = above.greeks().unwrap();
+        assert_eq!(greeks.delta.numerator, BigInt::ZERO);
+        assert_eq!(greeks.gamma.numerator, BigInt::ZERO);
+    }
+
+    #[test]
+    fn test_hedge_grid() {
+        let position = Position::new(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            1_000_000_000_000,
+            -600,
+            600,
+        );
+        let size = position.hedge_size().unwrap();
+        assert_eq!(size.quotient(), position.amount0().unwrap().quotient());
+        let grid: Vec<U160> = [-1200, -600, -60, 0, 60, 600, 1200]
+            .into_iter()
+            .map(|tick| get_sqrt_ratio_at_tick(I24::try_from(tick).unwrap()).unwrap())
+            .collect();
+        let points = position.hedge_grid(&grid).unwrap();
+        assert_eq!(points.len(), grid.len());
+        // the hedge is constant below the range and zero above it
+        assert_eq!(points[0].size.quotient(), points[1].size.quotient());
+        assert_eq!(points[5].size.numerator, BigInt::ZERO);
+        assert_eq!(points[6].size.numerator, BigInt::ZERO);
+        // the hedge shrinks as the price rises
+        assert!(points.windows(2).all(|w| w[0].size >= w[1].size));
+        // no adjustment at the current price
+        assert_eq!(points[3].size.quotient(), size.quotient());
+        assert_eq!(points[3].adjustment.numerator, BigInt::ZERO);
+        assert!(points[2].adjustment > Fraction::new(0, 1));
+        assert!(points[4].adjustment < Fraction::new(0, 1));
+    }
+}
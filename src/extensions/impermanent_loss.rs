@@ -0,0 +1,172 @@
+//! ## Impermanent Loss
+//! Utilities for measuring the impermanent loss of a concentrated liquidity range, i.e. the value
+//! of a position compared to holding the tokens it was opened with.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::U160;
+use uniswap_sdk_core::prelude::*;
+
+/// Computes the impermanent loss of a concentrated liquidity range between the entry price and the
+/// current price.
+///
+/// The impermanent loss is the value of the position relative to the value of the tokens it was
+/// opened with, both valued at the current price, minus one. It does not depend on the liquidity
+/// and does not account for the fees earned.
+///
+/// ## Arguments
+///
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `sqrt_price_entry_x96`: The Q64.96 sqrt price when the position was opened
+/// * `sqrt_price_current_x96`: The current Q64.96 sqrt price
+///
+/// ## Returns
+///
+/// The impermanent loss as a non-positive [`Percent`]
+#[inline]
+pub fn get_impermanent_loss<I: TickIndex>(
+    tick_lower: I,
+    tick_upper: I,
+    sqrt_price_entry_x96: U160,
+    sqrt_price_current_x96: U160,
+) -> Result<Percent, Error> {
+    assert!(tick_lower < tick_upper, "TICK_ORDER");
+    let sqrt_price_a = get_sqrt_ratio_at_tick(tick_lower.to_i24())?.to_big_int();
+    let sqrt_price_b = get_sqrt_ratio_at_tick(tick_upper.to_i24())?.to_big_int();
+    let sqrt_price = sqrt_price_current_x96.to_big_int();
+    let price_x192 = &sqrt_price * &sqrt_price;
+    let clamp = |sqrt_price: U160| {
+        sqrt_price
+            .to_big_int()
+            .clamp(sqrt_price_a.clone(), sqrt_price_b.clone())
+    };
+    let sqrt_price_entry = clamp(sqrt_price_entry_x96);
+    let sqrt_price_current = clamp(sqrt_price_current_x96);
+    // The amounts of a unit of liquidity at sqrt price `sp` clamped to [sa, sb] are
+    // (1 / sp - 1 / sb) of token0 and (sp - sa) of token1, whose value at the price P is
+    // ((sb - sp) * P + (sp - sa) * sp * sb) / (sp * sb).
+    let value_numerator = |sp: &BigInt| {
+        (&sqrt_price_b - sp) * &price_x192 + (sp - &sqrt_price_a) * sp * &sqrt_price_b
+    };
+    let hodl = value_numerator(&sqrt_price_entry) * &sqrt_price_current;
+    let position = value_numerator(&sqrt_price_current) * &sqrt_price_entry;
+    Ok(Percent::new(position - &hodl, hodl))
+}
+
+/// The value of a position compared to holding the tokens it was opened with.
+#[derive(Clone, Debug)]
+pub struct PositionVsHodl {
+    /// The value of the tokens the position was opened with at the current price, in token1
+    pub hodl_value: CurrencyAmount<Token>,
+    /// The value of the position at the current price, in token1
+    pub position_value: CurrencyAmount<Token>,
+    /// The value of the position relative to the hodl value, minus one
+    pub impermanent_loss: Percent,
+}
+
+/// Compares the value of a position at the current price of its pool with holding the tokens it
+/// was opened with, excluding the fees earned.
+///
+/// ## Arguments
+///
+/// * `position`: The position at the current price
+/// * `sqrt_price_entry_x96`: The Q64.96 sqrt price when the position was opened
+#[inline]
+pub fn position_vs_hodl<TP>(
+    position: &Position<TP>,
+    sqrt_price_entry_x96: U160,
+) -> Result<PositionVsHodl, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let pool = &position.pool;
+    let entry = Position::new(
+        Pool::new_with_tick_data_provider(
+            pool.token0.clone(),
+            pool.token1.clone(),
+            pool.fee,
+            sqrt_price_entry_x96,
+            pool.liquidity,
+            pool.tick_data_provider.clone(),
+        )?,
+        position.liquidity,
+        position.tick_lower,
+        position.tick_upper,
+    );
+    let price = pool.token0_price();
+    let hodl_value = price.quote(&entry.amount0()?)?.add(&entry.amount1()?)?;
+    let position_value = price
+        .quote(&position.amount0()?)?
+        .add(&position.amount1()?)?;
+    let impermanent_loss = Percent::new(
+        position_value.quotient() - hodl_value.quotient(),
+        hodl_value.quotient(),
+    );
+    Ok(PositionVsHodl {
+        hodl_value,
+        position_value,
+        impermanent_loss,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use num_traits::Signed;
+
+    /// Asserts that the percent is within 1e-6 of `numerator / denominator`
+    fn assert_approx_eq(percent: &Percent, numerator: i64, denominator: i64) {
+        let error = (&percent.numerator * BigInt::from(denominator)
+            - BigInt::from(numerator) * &percent.denominator)
+            .abs();
+        let scale = (&percent.denominator * BigInt::from(denominator)).abs();
+        assert!(error * BigInt::from(1_000_000) < scale);
+    }
+
+    #[test]
+    fn test_no_price_change() {
+        let sqrt_price = encode_sqrt_ratio_x96(1, 1);
+        let il = get_impermanent_loss(-60, 60, sqrt_price, sqrt_price).unwrap();
+        assert_eq!(il.numerator, BigInt::ZERO);
+    }
+
+    #[test]
+    fn test_full_range() {
+        // 2 * sqrt(4) / (1 + 4) - 1
+        let il = get_impermanent_loss(
+            -887220,
+            887220,
+            encode_sqrt_ratio_x96(1, 1),
+            encode_sqrt_ratio_x96(4, 1),
+        )
+        .unwrap();
+        assert_approx_eq(&il, -1, 5);
+    }
+
+    #[test]
+    fn test_concentrated_range_amplifies_loss() {
+        let entry = encode_sqrt_ratio_x96(1, 1);
+        let current = encode_sqrt_ratio_x96(4, 1);
+        let full_range = get_impermanent_loss(-887220, 887220, entry, current).unwrap();
+        let concentrated = get_impermanent_loss(-6960, 6960, entry, current).unwrap();
+        assert!(concentrated.numerator < BigInt::ZERO);
+        assert!(
+            concentrated.numerator * &full_range.denominator
+                < full_range.numerator * &concentrated.denominator
+        );
+    }
+
+    #[test]
+    fn test_position_vs_hodl() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let position = Position::new(pool, 1_000_000_000, -600, 600);
+        let result = position_vs_hodl(&position, encode_sqrt_ratio_x96(110, 100)).unwrap();
+        assert!(result.position_value.quotient() < result.hodl_value.quotient());
+        assert!(result.impermanent_loss.numerator < BigInt::ZERO);
+
+        let result = position_vs_hodl(&position, position.pool.sqrt_ratio_x96).unwrap();
+        assert_eq!(result.position_value, result.hodl_value);
+        assert_eq!(result.impermanent_loss.numerator, BigInt::ZERO);
+    }
+}
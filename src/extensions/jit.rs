@@ -0,0 +1,279 @@
+//! ## JIT Liquidity
+//! Simulates just-in-time liquidity: a position minted right before a pending swap and burned right
+//! after it, to capture a share of its fees.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::U24, I256, U160, U256};
+
+/// The outcome of a just-in-time position around a swap, see [`simulate_jit_liquidity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JitLiquidity {
+    /// The amount of token0 required to mint the position before the swap, rounded up
+    pub amount0: U256,
+    /// The amount of token1 required to mint the position before the swap, rounded up
+    pub amount1: U256,
+    /// The amount of token0 withdrawn by burning the position after the swap, rounded down
+    pub amount0_after: U256,
+    /// The amount of token1 withdrawn by burning the position after the swap, rounded down
+    pub amount1_after: U256,
+    /// The fees captured by the position in the input token of the swap, rounded down
+    pub fees: U256,
+    /// The amount swapped in by the swap, including the fees
+    pub amount_in: U256,
+    /// The amount swapped out by the swap
+    pub amount_out: U256,
+    /// The sqrt price of the pool after the swap
+    pub sqrt_price_x96_after: U160,
+}
+
+/// A tick data provider that adds the liquidity of a position to the ticks of another provider
+struct InjectedTickDataProvider<'a, TP: TickDataProvider> {
+    inner: &'a TP,
+    tick_lower: Tick<TP::Index>,
+    tick_upper: Tick<TP::Index>,
+}
+
+impl<'a, TP: TickDataProvider> InjectedTickDataProvider<'a, TP> {
+    fn new(inner: &'a TP, tick_lower: TP::Index, tick_upper: TP::Index, liquidity: u128) -> Self {
+        let inject = |index: TP::Index, liquidity_net: i128| {
+            let (liquidity_gross, inner_net) = inner
+                .get_tick(index)
+                .map_or((0, 0), |tick| (tick.liquidity_gross, tick.liquidity_net));
+            Tick {
+                index,
+                liquidity_gross: liquidity_gross + liquidity,
+                liquidity_net: inner_net + liquidity_net,
+            }
+        };
+        Self {
+            inner,
+            tick_lower: inject(tick_lower, liquidity as i128),
+            tick_upper: inject(tick_upper, -(liquidity as i128)),
+        }
+    }
+}
+
+impl<TP: TickDataProvider> TickDataProvider for InjectedTickDataProvider<'_, TP> {
+    type Index = TP::Index;
+
+    #[inline]
+    fn get_tick(&self, tick: Self::Index) -> Result<&Tick<Self::Index>, Error> {
+        if tick == self.tick_lower.index {
+            Ok(&self.tick_lower)
+        } else if tick == self.tick_upper.index {
+            Ok(&self.tick_upper)
+        } else {
+            self.inner.get_tick(tick)
+        }
+    }
+
+    #[inline]
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: Self::Index,
+        lte: bool,
+        tick_spacing: Self::Index,
+    ) -> Result<(Self::Index, bool), Error> {
+        let mut next = self
+            .inner
+            .next_initialized_tick_within_one_word(tick, lte, tick_spacing)?;
+        // the injected ticks are closer if they are between the tick and the next tick
+        for index in [self.tick_lower.index, self.tick_upper.index] {
+            if (lte && index <= tick && index >= next.0)
+                || (!lte && index > tick && index <= next.0)
+            {
+                next = (index, true);
+            }
+        }
+        Ok(next)
+    }
+
+    #[inline]
+    fn sqrt_ratio_at_tick(&self, tick: Self::Index) -> Result<U160, Error> {
+        self.inner.sqrt_ratio_at_tick(tick)
+    }
+}
+
+/// Simulates a just-in-time position minted in a pool right before a pending swap and burned
+/// right after it, by replaying the swap with the liquidity of the position injected into the
+/// pool.
+///
+/// ## Notes
+///
+/// The fees are the share of the position in the fees of each step of the swap in its range,
+/// before any protocol fee.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool before the swap, with a tick data provider covering the swap
+/// * `zero_for_one`: Whether the swap sells token0 for token1
+/// * `amount_specified`: The amount of the swap, positive for an exact input and negative for an
+///   exact output
+/// * `sqrt_price_limit_x96`: The price limit of the swap, if any
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `liquidity`: The liquidity of the position
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_jit_liquidity<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    zero_for_one: bool,
+    amount_specified: I256,
+    sqrt_price_limit_x96: Option<U160>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    liquidity: u128,
+) -> Result<JitLiquidity, Error> {
+    assert!(tick_lower < tick_upper, "TICK_ORDER");
+    let tick_spacing = pool.tick_spacing();
+    assert!(
+        tick_lower % tick_spacing == TP::Index::ZERO
+            && tick_upper % tick_spacing == TP::Index::ZERO,
+        "TICK_SPACING"
+    );
+    let sqrt_price_lower_x96 = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+    let sqrt_price_upper_x96 = get_sqrt_ratio_at_tick(tick_upper.to_i24())?;
+    let in_range = pool.tick_current >= tick_lower && pool.tick_current < tick_upper;
+    let tick_data_provider =
+        InjectedTickDataProvider::new(&pool.tick_data_provider, tick_lower, tick_upper, liquidity);
+    let trace = v3_swap_with_trace(
+        U24::from(pool.fee),
+        pool.sqrt_ratio_x96,
+        pool.tick_current,
+        if in_range {
+            pool.liquidity + liquidity
+        } else {
+            pool.liquidity
+        },
+        tick_spacing,
+        &tick_data_provider,
+        zero_for_one,
+        amount_specified,
+        sqrt_price_limit_x96,
+    )?;
+
+    let mut fees = U256::ZERO;
+    let mut liquidity_step = if in_range {
+        pool.liquidity + liquidity
+    } else {
+        pool.liquidity
+    };
+    for step in &trace.steps {
+        let (low, high) = if zero_for_one {
+            (step.sqrt_price_x96, step.sqrt_price_start_x96)
+        } else {
+            (step.sqrt_price_start_x96, step.sqrt_price_x96)
+        };
+        // the steps end at the injected ticks, so they are either within or outside the range
+        if low >= sqrt_price_lower_x96 && high <= sqrt_price_upper_x96 && liquidity_step > 0 {
+            fees += mul_div(
+                step.fee_amount,
+                U256::from(liquidity),
+                U256::from(liquidity_step),
+            )?;
+        }
+        liquidity_step = step.liquidity;
+    }
+
+    let amounts = |sqrt_price_x96: U160, round_up: bool| -> Result<(U256, U256), Error> {
+        let sqrt_price_x96 = sqrt_price_x96.clamp(sqrt_price_lower_x96, sqrt_price_upper_x96);
+        Ok((
+            get_amount_0_delta(sqrt_price_x96, sqrt_price_upper_x96, liquidity, round_up)?,
+            get_amount_1_delta(sqrt_price_lower_x96, sqrt_price_x96, liquidity, round_up)?,
+        ))
+    };
+    let (amount0, amount1) = amounts(pool.sqrt_ratio_x96, true)?;
+    let (amount0_after, amount1_after) = amounts(trace.state.sqrt_price_x96, false)?;
+
+    let amount_specified_used = amount_specified - trace.state.amount_specified_remaining;
+    let (amount_in, amount_out) = if amount_specified.is_positive() {
+        (
+            amount_specified_used.into_raw(),
+            (-trace.state.amount_calculated).into_raw(),
+        )
+    } else {
+        (
+            trace.state.amount_calculated.into_raw(),
+            (-amount_specified_used).into_raw(),
+        )
+    };
+    Ok(JitLiquidity {
+        amount0,
+        amount1,
+        amount0_after,
+        amount1_after,
+        fees,
+        amount_in,
+        amount_out,
+        sqrt_price_x96_after: trace.state.sqrt_price_x96,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn simulate(tick_lower: i32, tick_upper: i32, liquidity: u128) -> JitLiquidity {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        simulate_jit_liquidity(
+            &pool,
+            true,
+            I256::from_raw(U256::from(100_000)),
+            None,
+            tick_lower,
+            tick_upper,
+            liquidity,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_simulate_jit_liquidity() {
+        let jit = simulate(-6000, 6000, 1_000_000);
+        // the position doubles the liquidity in range, so it captures half of the 0.3% fee
+        assert!(jit.fees == U256::from(149) || jit.fees == U256::from(150));
+        assert_eq!(jit.amount_in, U256::from(100_000));
+        assert!(jit.amount0 > U256::ZERO && jit.amount1 > U256::ZERO);
+        // the position bought token0 with token1
+        assert!(jit.amount0_after > jit.amount0);
+        assert!(jit.amount1_after < jit.amount1);
+        // the swap gets a better price with the injected liquidity
+        let no_jit = simulate(-6000, 6000, 0);
+        assert_eq!(no_jit.fees, U256::ZERO);
+        assert!(jit.amount_out > no_jit.amount_out);
+        assert!(jit.sqrt_price_x96_after > no_jit.sqrt_price_x96_after);
+    }
+
+    #[test]
+    fn test_simulate_jit_liquidity_matches_pool() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let no_jit = simulate(-6000, 6000, 0);
+        let amount_out = pool
+            .get_output_amount(
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(no_jit.amount_out, U256::from_big_int(amount_out.quotient()));
+    }
+
+    #[test]
+    fn test_simulate_jit_liquidity_out_of_range() {
+        // the price moves down away from the position
+        let jit = simulate(600, 1200, 1_000_000);
+        assert_eq!(jit.fees, U256::ZERO);
+        assert_eq!(jit.amount1, U256::ZERO);
+        assert!(jit.amount0 - jit.amount0_after <= U256::from(1));
+        assert_eq!(jit.amount1_after, U256::ZERO);
+    }
+
+    #[test]
+    fn test_simulate_jit_liquidity_crossed() {
+        // the price leaves the position during the swap
+        let narrow = simulate(-60, 60, 1_000_000);
+        let wide = simulate(-6000, 6000, 1_000_000);
+        assert!(narrow.fees > U256::ZERO && narrow.fees < wide.fees);
+        assert_eq!(narrow.amount1_after, U256::ZERO);
+    }
+}
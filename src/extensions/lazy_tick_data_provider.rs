@@ -0,0 +1,235 @@
+//! ## Lazy Tick Data Provider
+//! A data provider that fetches the ticks of a tick bitmap word on demand as a simulated swap walks
+//! the price, instead of fetching the whole tick range up front. Only the words actually walked by
+//! the swap are fetched, each in a single `eth_call` to an [ephemeral contract](https://github.com/Aperture-Finance/Aperture-Lens/blob/904101e4daed59e02fd4b758b98b0749e70b583b/contracts/EphemeralGetPopulatedTicksInRange.sol).
+
+use crate::prelude::{Error, *};
+use alloy::{eips::BlockId, network::Network, providers::Provider, uint};
+use alloy_primitives::{
+    aliases::I24,
+    map::rustc_hash::{FxHashMap, FxHashSet},
+    Address, U160, U256,
+};
+use uniswap_lens::pool_lens;
+use uniswap_sdk_core::prelude::*;
+
+/// A tick data provider that only knows the ticks of the tick bitmap words fetched so far.
+///
+/// [`TickDataProvider::next_initialized_tick_within_one_word`] fails with
+/// [`Error::WordNotFetched`] when the swap walks into a word that has not been fetched yet. The
+/// `*_lazy` methods of [`Pool`] fetch the missing word and retry the simulation until it completes.
+#[derive(Clone, Debug)]
+pub struct LazyTickDataProvider<I = I24> {
+    pub pool: Address,
+    pub block_id: Option<BlockId>,
+    pub tick_map: TickMap<I>,
+    /// The positions of the tick bitmap words fetched so far
+    pub fetched_words: FxHashSet<I>,
+}
+
+impl<I: TickIndex> LazyTickDataProvider<I> {
+    #[inline]
+    #[must_use]
+    pub fn new(pool: Address, tick_spacing: I, block_id: Option<BlockId>) -> Self {
+        Self {
+            pool,
+            block_id,
+            tick_map: TickMap {
+                bitmap: TickBitMap::default(),
+                inner: FxHashMap::default(),
+                tick_spacing,
+            },
+            fetched_words: FxHashSet::default(),
+        }
+    }
+
+    /// Fetch the initialized ticks of a tick bitmap word if not fetched yet
+    ///
+    /// ## Arguments
+    ///
+    /// * `word_pos`: The position of the word in the tick bitmap
+    /// * `provider`: The alloy provider
+    #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(pool = %self.pool, ?word_pos, block_id = ?self.block_id))
+    )]
+    pub async fn fetch_word<N, P>(&mut self, word_pos: I, provider: P) -> Result<(), Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        if self.fetched_words.contains(&word_pos) {
+            return Ok(());
+        }
+        let tick_spacing = self.tick_map.tick_spacing;
+        let word_span = i64::from(tick_spacing.to_i24().as_i32()) << 8;
+        let word_start = i64::from(word_pos.to_i24().as_i32()) * word_span;
+        let tick_lower = word_start.max(i64::from(MIN_TICK_I32));
+        let tick_upper = (word_start + word_span - 1).min(i64::from(MAX_TICK_I32));
+        let (ticks, _) = pool_lens::get_populated_ticks_in_range(
+            self.pool,
+            I24::try_from(tick_lower).unwrap(),
+            I24::try_from(tick_upper).unwrap(),
+            provider,
+            self.block_id,
+        )
+        .await
+        .map_err(Error::LensError)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(ticks = ticks.len(), "fetched ticks");
+        for tick in ticks {
+            let index = I::from_i24(tick.tick);
+            let (word, bit_pos) = index.compress(tick_spacing).position();
+            *self.tick_map.bitmap.entry(word).or_insert(U256::ZERO) |= uint!(1_U256) << bit_pos;
+            self.tick_map.inner.insert(
+                index,
+                Tick::new(index, tick.liquidityGross, tick.liquidityNet),
+            );
+        }
+        self.fetched_words.insert(word_pos);
+        Ok(())
+    }
+}
+
+impl<I: TickIndex> TickDataProvider for LazyTickDataProvider<I> {
+    type Index = I;
+
+    #[inline]
+    fn get_tick(&self, tick: Self::Index) -> Result<&Tick<Self::Index>, Error> {
+        self.tick_map.get_tick(tick)
+    }
+
+    #[inline]
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: Self::Index,
+        lte: bool,
+        tick_spacing: Self::Index,
+    ) -> Result<(Self::Index, bool), Error> {
+        let compressed = tick.compress(tick_spacing);
+        // the word searched, see `TickBitMapProvider::next_initialized_tick_within_one_word`
+        let (word_pos, _) = if lte { compressed } else { compressed + I::ONE }.position();
+        if !self.fetched_words.contains(&word_pos) {
+            return Err(Error::WordNotFetched(word_pos.to_i24()));
+        }
+        self.tick_map
+            .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
+    }
+}
+
+impl<I: TickIndex> Pool<LazyTickDataProvider<I>> {
+    /// Given an input amount of a token, return the computed output amount, fetching the tick
+    /// bitmap words walked by the swap on demand
+    ///
+    /// ## Arguments
+    ///
+    /// * `input_amount`: The input amount for which to quote the output amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit
+    /// * `provider`: The alloy provider
+    #[inline]
+    pub async fn get_output_amount_lazy<N, P>(
+        &mut self,
+        input_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+        provider: &P,
+    ) -> Result<CurrencyAmount<Token>, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        loop {
+            match self.get_output_amount(input_amount, sqrt_price_limit_x96) {
+                Err(Error::WordNotFetched(word_pos)) => {
+                    self.tick_data_provider
+                        .fetch_word(I::from_i24(word_pos), provider)
+                        .await?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Given a desired output amount of a token, return the computed input amount, fetching the
+    /// tick bitmap words walked by the swap on demand
+    ///
+    /// ## Arguments
+    ///
+    /// * `output_amount`: the output amount for which to quote the input amount
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit
+    /// * `provider`: The alloy provider
+    #[inline]
+    pub async fn get_input_amount_lazy<N, P>(
+        &mut self,
+        output_amount: &CurrencyAmount<impl BaseCurrency>,
+        sqrt_price_limit_x96: Option<U160>,
+        provider: &P,
+    ) -> Result<CurrencyAmount<Token>, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        loop {
+            match self.get_input_amount(output_amount, sqrt_price_limit_x96) {
+                Err(Error::WordNotFetched(word_pos)) => {
+                    self.tick_data_provider
+                        .fetch_word(I::from_i24(word_pos), provider)
+                        .await?;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const POOL: Address = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+
+    #[tokio::test]
+    async fn test_get_output_amount_lazy() -> Result<(), Error> {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await?;
+        assert_eq!(pool.address(None, None), POOL);
+        let amount_in = CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000_000_000).unwrap();
+        let provider =
+            EphemeralTickMapDataProvider::new(POOL, PROVIDER.clone(), None, None, *BLOCK_ID)
+                .await?;
+        let expected = Pool::new_with_tick_data_provider(
+            pool.token0.clone(),
+            pool.token1.clone(),
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            provider,
+        )?
+        .get_output_amount(&amount_in, None)?;
+
+        let mut pool = Pool::new_with_tick_data_provider(
+            pool.token0,
+            pool.token1,
+            pool.fee,
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            LazyTickDataProvider::new(POOL, I24::from_limbs([10]), *BLOCK_ID),
+        )?;
+        let output = pool
+            .get_output_amount_lazy(&amount_in, None, &PROVIDER.clone())
+            .await?;
+        assert_eq!(output, expected);
+        assert!(!pool.tick_data_provider.fetched_words.is_empty());
+        Ok(())
+    }
+}
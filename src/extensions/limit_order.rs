@@ -0,0 +1,243 @@
+//! ## Limit Order
+//! Range limit orders: a position one tick spacing wide just beyond the current price, which holds
+//! only the token sold and is fully converted to the other token once the price crosses the range.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::U256;
+use uniswap_sdk_core::prelude::*;
+
+/// Computes the one tick spacing wide range of a limit order at a tick.
+///
+/// ## Arguments
+///
+/// * `limit_tick`: The tick of the limit price
+/// * `tick_current`: The current tick of the pool
+/// * `tick_spacing`: The tick spacing of the pool
+/// * `zero_for_one`: Whether token0 is sold for token1
+///
+/// ## Returns
+///
+/// The lower and upper ticks of the range starting at the usable tick closest to the limit tick,
+/// above the current tick when selling token0 and below it when selling token1, or
+/// [`Error::InvalidRange`] if the range would contain the current price
+#[inline]
+pub fn get_limit_order_ticks<I: TickIndex>(
+    limit_tick: I,
+    tick_current: I,
+    tick_spacing: I,
+    zero_for_one: bool,
+) -> Result<(I, I), Error> {
+    let tick = nearest_usable_tick(limit_tick, tick_spacing);
+    if zero_for_one {
+        // the position only holds token0 while the price is below the range
+        if tick <= tick_current || tick + tick_spacing > I::from_i24(MAX_TICK) {
+            return Err(Error::InvalidRange);
+        }
+        Ok((tick, tick + tick_spacing))
+    } else {
+        // the position only holds token1 while the price is at or above the range
+        if tick > tick_current || tick - tick_spacing < I::from_i24(MIN_TICK) {
+            return Err(Error::InvalidRange);
+        }
+        Ok((tick - tick_spacing, tick))
+    }
+}
+
+/// A range limit order, see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct LimitOrder<TP: TickDataProvider> {
+    /// The position holding the order
+    pub position: Position<TP>,
+    /// Whether token0 is sold for token1
+    pub zero_for_one: bool,
+}
+
+impl<TP: TickDataProvider> LimitOrder<TP> {
+    /// Creates a limit order selling an amount of a token of the pool at a limit price.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to place the order in
+    /// * `amount`: The amount of token0 or token1 to sell
+    /// * `limit_price`: The price at which to sell, in either direction
+    #[inline]
+    pub fn new(
+        pool: Pool<TP>,
+        amount: &CurrencyAmount<Token>,
+        limit_price: &Price<Token, Token>,
+    ) -> Result<Self, Error> {
+        assert!(pool.involves_token(&amount.currency), "TOKEN");
+        let zero_for_one = amount.currency.equals(&pool.token0);
+        let (tick_lower, tick_upper) = get_limit_order_ticks(
+            TP::Index::from_i24(price_to_closest_tick_safe(limit_price)?),
+            pool.tick_current,
+            pool.tick_spacing(),
+            zero_for_one,
+        )?;
+        let amount = U256::from_big_int(amount.quotient());
+        let position = if zero_for_one {
+            Position::from_amount0(pool, tick_lower, tick_upper, amount, true)?
+        } else {
+            Position::from_amount1(pool, tick_lower, tick_upper, amount)?
+        };
+        assert!(position.liquidity > 0, "ZERO_LIQUIDITY");
+        Ok(Self {
+            position,
+            zero_for_one,
+        })
+    }
+
+    /// Returns the amount of the token bought once the order is fully filled, without fees
+    #[inline]
+    pub fn amount_out(&self) -> Result<U256, Error> {
+        let sqrt_ratio_lower_x96 = get_sqrt_ratio_at_tick(self.position.tick_lower.to_i24())?;
+        let sqrt_ratio_upper_x96 = get_sqrt_ratio_at_tick(self.position.tick_upper.to_i24())?;
+        if self.zero_for_one {
+            get_amount_1_delta(
+                sqrt_ratio_lower_x96,
+                sqrt_ratio_upper_x96,
+                self.position.liquidity,
+                false,
+            )
+        } else {
+            get_amount_0_delta(
+                sqrt_ratio_lower_x96,
+                sqrt_ratio_upper_x96,
+                self.position.liquidity,
+                false,
+            )
+        }
+    }
+
+    /// Returns whether the price has crossed the range so that the order is fully filled and the
+    /// position only holds the token bought.
+    ///
+    /// ## Arguments
+    ///
+    /// * `tick_current`: The current tick of the pool
+    #[inline]
+    #[must_use]
+    pub fn is_filled(&self, tick_current: TP::Index) -> bool {
+        if self.zero_for_one {
+            tick_current >= self.position.tick_upper
+        } else {
+            tick_current < self.position.tick_lower
+        }
+    }
+
+    /// Produces the calldata to place the order by minting or increasing the position, see
+    /// [`add_call_parameters`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `options`: Options for adding the liquidity
+    #[inline]
+    pub fn call_parameters(
+        &mut self,
+        options: AddLiquidityOptions,
+    ) -> Result<MethodParameters, Error> {
+        add_call_parameters(&mut self.position, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_get_limit_order_ticks() {
+        assert_eq!(get_limit_order_ticks(100, 0, 60, true).unwrap(), (120, 180));
+        assert_eq!(
+            get_limit_order_ticks(-100, 0, 60, false).unwrap(),
+            (-180, -120)
+        );
+        // the range must not contain the current price
+        assert!(matches!(
+            get_limit_order_ticks(10, 0, 60, true),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            get_limit_order_ticks(40, 0, 60, false),
+            Err(Error::InvalidRange)
+        ));
+        assert_eq!(get_limit_order_ticks(0, 0, 60, false).unwrap(), (-60, 0));
+    }
+
+    #[test]
+    fn test_sell_token0() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+        // sell token0 at 1.05 token1
+        let limit_price = Price::new(TOKEN0.clone(), TOKEN1.clone(), 100, 105);
+        let order = LimitOrder::new(pool, &amount, &limit_price).unwrap();
+        assert!(order.zero_for_one);
+        assert_eq!(
+            (order.position.tick_lower, order.position.tick_upper),
+            (480, 540)
+        );
+        let MintAmounts { amount0, amount1 } = order.position.mint_amounts().unwrap();
+        assert!(amount0 <= U256::from(100_000));
+        assert_eq!(amount1, U256::ZERO);
+        // filled at an average price of about 1.05
+        let amount_out = order.amount_out().unwrap();
+        assert!(amount_out > U256::from(104_000) && amount_out < U256::from(106_000));
+        assert!(!order.is_filled(0));
+        assert!(!order.is_filled(539));
+        assert!(order.is_filled(540));
+    }
+
+    #[test]
+    fn test_sell_token1() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100_000).unwrap();
+        // sell token1 at 1.05 token0, either direction of the price works
+        let limit_price = Price::new(TOKEN1.clone(), TOKEN0.clone(), 100, 105);
+        let order = LimitOrder::new(pool, &amount, &limit_price).unwrap();
+        assert!(!order.zero_for_one);
+        assert_eq!(
+            (order.position.tick_lower, order.position.tick_upper),
+            (-540, -480)
+        );
+        assert!(!order.is_filled(-540));
+        assert!(order.is_filled(-541));
+    }
+
+    #[test]
+    fn test_limit_price_below_current_price() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+        let limit_price = Price::new(TOKEN0.clone(), TOKEN1.clone(), 105, 100);
+        assert!(matches!(
+            LimitOrder::new(pool, &amount, &limit_price),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_call_parameters() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+        let limit_price = Price::new(TOKEN0.clone(), TOKEN1.clone(), 100, 105);
+        let mut order = LimitOrder::new(pool, &amount, &limit_price).unwrap();
+        let mut position = order.position.clone();
+        let options = AddLiquidityOptions {
+            slippage_tolerance: Percent::new(1, 100),
+            deadline: U256::from(123),
+            use_native: None,
+            token0_permit: None,
+            token1_permit: None,
+            specific_opts: AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                recipient: address!("0000000000000000000000000000000000000003"),
+                create_pool: false,
+            }),
+            chain_id: None,
+            deployment: None,
+        };
+        assert_eq!(
+            order.call_parameters(options.clone()).unwrap(),
+            add_call_parameters(&mut position, options).unwrap()
+        );
+    }
+}
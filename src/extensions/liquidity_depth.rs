@@ -0,0 +1,206 @@
+//! ## Liquidity Depth
+//! Converts the tick liquidity of a pool into an orderbook-like depth chart: the amounts swapped
+//! to move the price to each of a list of price levels.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::{U160, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// A level of the depth chart of a pool, see [`get_liquidity_depth`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepthLevel {
+    /// The price of token0 in token1, in the raw units of the tokens
+    pub price: BigDecimal,
+    /// Whether the level is at or below the current price, where token0 is sold for token1 (a
+    /// bid), or above it, where token1 is sold for token0 (an ask)
+    pub zero_for_one: bool,
+    /// The amount of token0 swapped in to reach the price if `zero_for_one`, or obtainable out of
+    /// the pool otherwise
+    pub amount0: U256,
+    /// The amount of token1 obtainable out of the pool to reach the price if `zero_for_one`, or
+    /// swapped in otherwise
+    pub amount1: U256,
+}
+
+/// Computes the cumulative amounts to move the price of the pool to each target, walking the
+/// initialized ticks once in one direction.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool with a tick data provider
+/// * `zero_for_one`: Whether the price moves down
+/// * `targets`: The target sqrt prices sorted by distance from the current price
+fn walk_liquidity<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    zero_for_one: bool,
+    targets: &[U160],
+) -> Result<Vec<(U256, U256)>, Error> {
+    let tick_spacing = pool.tick_spacing();
+    let mut sqrt_price_x96 = pool.sqrt_ratio_x96;
+    let mut tick_current = pool.tick_current;
+    let mut liquidity = pool.liquidity;
+    let mut amount0 = U256::ZERO;
+    let mut amount1 = U256::ZERO;
+    let mut amounts = Vec::with_capacity(targets.len());
+    for &target in targets {
+        while sqrt_price_x96 != target {
+            let (tick_next, initialized) = pool
+                .tick_data_provider
+                .next_initialized_tick_within_one_word(tick_current, zero_for_one, tick_spacing)?;
+            let tick_next = TP::Index::from_i24(tick_next.to_i24().clamp(MIN_TICK, MAX_TICK));
+            let sqrt_price_next_x96 = get_sqrt_ratio_at_tick(tick_next.to_i24())?;
+            // amounts in are rounded up and amounts out are rounded down, without fees
+            let sqrt_price_step_x96 = if zero_for_one {
+                let sqrt_price_step_x96 = sqrt_price_next_x96.max(target);
+                amount0 +=
+                    get_amount_0_delta(sqrt_price_step_x96, sqrt_price_x96, liquidity, true)?;
+                amount1 +=
+                    get_amount_1_delta(sqrt_price_step_x96, sqrt_price_x96, liquidity, false)?;
+                sqrt_price_step_x96
+            } else {
+                let sqrt_price_step_x96 = sqrt_price_next_x96.min(target);
+                amount0 +=
+                    get_amount_0_delta(sqrt_price_x96, sqrt_price_step_x96, liquidity, false)?;
+                amount1 +=
+                    get_amount_1_delta(sqrt_price_x96, sqrt_price_step_x96, liquidity, true)?;
+                sqrt_price_step_x96
+            };
+            sqrt_price_x96 = sqrt_price_step_x96;
+            if sqrt_price_x96 == sqrt_price_next_x96 {
+                if initialized {
+                    let mut liquidity_net =
+                        pool.tick_data_provider.get_tick(tick_next)?.liquidity_net;
+                    if zero_for_one {
+                        liquidity_net = -liquidity_net;
+                    }
+                    liquidity = add_delta(liquidity, liquidity_net)?;
+                }
+                tick_current = if zero_for_one {
+                    tick_next - TP::Index::ONE
+                } else {
+                    tick_next
+                };
+            }
+        }
+        amounts.push((amount0, amount1));
+    }
+    Ok(amounts)
+}
+
+/// Computes the depth chart of a pool at a list of price levels.
+///
+/// For each price, the amounts are those swapped to move the price of the pool from its current
+/// price to that price through the liquidity in range, excluding fees, as by
+/// [`get_amount_0_delta`] and [`get_amount_1_delta`]. Prices beyond the range of Uniswap are
+/// clamped to [`MIN_SQRT_RATIO`] and [`MAX_SQRT_RATIO`].
+///
+/// ## Arguments
+///
+/// * `pool`: The pool with a tick data provider covering the price levels
+/// * `prices`: The prices of token0 in token1, in the raw units of the tokens
+///
+/// ## Returns
+///
+/// The depth level of each price, in the order of the prices
+#[inline]
+pub fn get_liquidity_depth<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    prices: &[BigDecimal],
+) -> Result<Vec<DepthLevel>, Error> {
+    let sqrt_prices = prices
+        .iter()
+        .map(price_to_sqrt_ratio_x96)
+        .collect::<Result<Vec<U160>, _>>()?;
+    let mut levels: Vec<Option<DepthLevel>> = (0..prices.len()).map(|_| None).collect();
+    for zero_for_one in [true, false] {
+        let mut indices: Vec<usize> = (0..prices.len())
+            .filter(|&i| (sqrt_prices[i] <= pool.sqrt_ratio_x96) == zero_for_one)
+            .collect();
+        // sort by distance from the current price
+        if zero_for_one {
+            indices.sort_by(|&a, &b| sqrt_prices[b].cmp(&sqrt_prices[a]));
+        } else {
+            indices.sort_by_key(|&i| sqrt_prices[i]);
+        }
+        let targets: Vec<U160> = indices.iter().map(|&i| sqrt_prices[i]).collect();
+        for (i, (amount0, amount1)) in
+            indices
+                .into_iter()
+                .zip(walk_liquidity(pool, zero_for_one, &targets)?)
+        {
+            levels[i] = Some(DepthLevel {
+                price: prices[i].clone(),
+                zero_for_one,
+                amount0,
+                amount1,
+            });
+        }
+    }
+    Ok(levels.into_iter().map(Option::unwrap).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use core::str::FromStr;
+
+    fn price(price: &str) -> BigDecimal {
+        BigDecimal::from_str(price).unwrap()
+    }
+
+    #[test]
+    fn test_get_liquidity_depth() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let levels = get_liquidity_depth(
+            &pool,
+            &[price("1.21"), price("0.81"), price("1"), price("1.1")],
+        )
+        .unwrap();
+        assert!(!levels[0].zero_for_one && levels[1].zero_for_one && levels[2].zero_for_one);
+        assert_eq!(levels[0].price, price("1.21"));
+        // the current price needs no swap
+        assert_eq!(
+            (levels[2].amount0, levels[2].amount1),
+            (U256::ZERO, U256::ZERO)
+        );
+        // with a liquidity of 1e6 over the full range, moving the sqrt price from 1 to 1.1
+        // exchanges 1e6 * 0.1 token1 for 1e6 * (1 - 1 / 1.1) token0
+        assert_eq!(levels[0].amount0, U256::from(90_909));
+        assert_eq!(levels[0].amount1, U256::from(100_000));
+        // and from 1 to 0.9 exchanges 1e6 * (1 / 0.9 - 1) token0 for 1e6 * 0.1 token1
+        assert_eq!(levels[1].amount0, U256::from(111_112));
+        assert_eq!(levels[1].amount1, U256::from(100_000));
+        // the depth increases with the distance from the current price
+        assert!(levels[3].amount0 < levels[0].amount0);
+        assert!(levels[3].amount1 < levels[0].amount1);
+    }
+
+    #[test]
+    fn test_get_liquidity_depth_matches_swap() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let prices = [price("0.5"), price("4")];
+        let levels = get_liquidity_depth(&pool, &prices).unwrap();
+        for (level, price) in levels.iter().zip(&prices) {
+            // the output of a swap limited to the price does not depend on the fees
+            let input_token = if level.zero_for_one {
+                TOKEN0.clone()
+            } else {
+                TOKEN1.clone()
+            };
+            let amount_in = CurrencyAmount::from_raw_amount(input_token, 10_000_000).unwrap();
+            let amount_out = pool
+                .get_output_amount(&amount_in, Some(price_to_sqrt_ratio_x96(price).unwrap()))
+                .unwrap();
+            assert_eq!(
+                U256::from_big_int(amount_out.quotient()),
+                if level.zero_for_one {
+                    level.amount1
+                } else {
+                    level.amount0
+                }
+            );
+        }
+    }
+}
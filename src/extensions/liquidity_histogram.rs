@@ -0,0 +1,239 @@
+//! ## Liquidity Histogram
+//! Aggregates the tick data of a pool into the liquidity and token amounts of each tick spacing
+//! interval over a range, as rendered by the familiar liquidity histogram.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+
+/// The liquidity of a tick spacing interval of a pool, see [`get_liquidity_histogram`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityBucket<I = i32> {
+    pub tick_lower: I,
+    pub tick_upper: I,
+    /// The active liquidity while the price is in the interval
+    pub liquidity: u128,
+    /// The amount of token0 locked in the interval at the current price
+    pub amount0: U256,
+    /// The amount of token1 locked in the interval at the current price
+    pub amount1: U256,
+}
+
+/// Computes the liquidity histogram of a pool over a tick range, with a bucket per tick spacing.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool with a tick data provider covering the range, e.g. an
+///   [`EphemeralTickMapDataProvider`]
+/// * `tick_lower`: The lower tick of the range, rounded down to the tick spacing
+/// * `tick_upper`: The upper tick of the range, rounded up to the tick spacing
+///
+/// ## Returns
+///
+/// The buckets from the lowest to the highest tick, with the token amounts of their liquidity at
+/// the current price rounded down
+#[inline]
+pub fn get_liquidity_histogram<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+) -> Result<Vec<LiquidityBucket<TP::Index>>, Error> {
+    assert!(tick_lower < tick_upper, "TICK_ORDER");
+    let tick_spacing = pool.tick_spacing();
+    let min_tick = nearest_usable_tick(TP::Index::from_i24(MIN_TICK), tick_spacing);
+    let max_tick = nearest_usable_tick(TP::Index::from_i24(MAX_TICK), tick_spacing);
+    let align_down = |tick: TP::Index| tick.compress(tick_spacing) * tick_spacing;
+    let tick_current_aligned = align_down(pool.tick_current).clamp(min_tick, max_tick);
+    let tick_lower = align_down(tick_lower).max(min_tick);
+    let tick_upper = {
+        let aligned = align_down(tick_upper);
+        if aligned < tick_upper {
+            aligned + tick_spacing
+        } else {
+            aligned
+        }
+    }
+    .min(max_tick);
+
+    // the initialized ticks between the range and the current tick, sorted
+    let walk_lower = tick_lower.min(tick_current_aligned);
+    let walk_upper = tick_upper.max(tick_current_aligned + tick_spacing);
+    let mut initialized_ticks: Vec<(TP::Index, i128)> = Vec::new();
+    let mut tick = walk_lower - TP::Index::ONE;
+    while tick < walk_upper {
+        let (tick_next, initialized) = pool
+            .tick_data_provider
+            .next_initialized_tick_within_one_word(tick, false, tick_spacing)?;
+        let tick_next = tick_next.min(walk_upper);
+        if initialized {
+            let liquidity_net = pool.tick_data_provider.get_tick(tick_next)?.liquidity_net;
+            initialized_ticks.push((tick_next, liquidity_net));
+        }
+        tick = tick_next;
+    }
+    let liquidity_net = |tick: TP::Index| {
+        initialized_ticks
+            .binary_search_by(|(index, _)| index.cmp(&tick))
+            .map_or(0, |i| initialized_ticks[i].1)
+    };
+
+    // the liquidity of each interval from the lowest to the highest
+    let mut liquidities: Vec<(TP::Index, u128)> = Vec::new();
+    let mut liquidity = pool.liquidity;
+    let mut tick = tick_current_aligned;
+    while tick > walk_lower {
+        // subtracted when the tick is crossed from right to left
+        liquidity = add_delta(liquidity, -liquidity_net(tick))?;
+        tick = tick - tick_spacing;
+        liquidities.push((tick, liquidity));
+    }
+    liquidities.reverse();
+    liquidities.push((tick_current_aligned, pool.liquidity));
+    liquidity = pool.liquidity;
+    tick = tick_current_aligned + tick_spacing;
+    while tick < walk_upper {
+        // added when the tick is crossed from left to right
+        liquidity = add_delta(liquidity, liquidity_net(tick))?;
+        liquidities.push((tick, liquidity));
+        tick = tick + tick_spacing;
+    }
+
+    liquidities
+        .into_iter()
+        .filter(|&(tick, _)| tick >= tick_lower && tick < tick_upper)
+        .map(|(tick_lower, liquidity)| {
+            let tick_upper = tick_lower + tick_spacing;
+            let sqrt_ratio_lower_x96 = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+            let sqrt_ratio_upper_x96 = get_sqrt_ratio_at_tick(tick_upper.to_i24())?;
+            let (amount0, amount1) = if pool.tick_current < tick_lower {
+                (
+                    get_amount_0_delta(
+                        sqrt_ratio_lower_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?,
+                    U256::ZERO,
+                )
+            } else if pool.tick_current < tick_upper {
+                (
+                    get_amount_0_delta(
+                        pool.sqrt_ratio_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?,
+                    get_amount_1_delta(
+                        sqrt_ratio_lower_x96,
+                        pool.sqrt_ratio_x96,
+                        liquidity,
+                        false,
+                    )?,
+                )
+            } else {
+                (
+                    U256::ZERO,
+                    get_amount_1_delta(
+                        sqrt_ratio_lower_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?,
+                )
+            };
+            Ok(LiquidityBucket {
+                tick_lower,
+                tick_upper,
+                liquidity,
+                amount0,
+                amount1,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn pool() -> Pool<TickListDataProvider> {
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            3_000_000,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(-120, 1_000_000, 1_000_000),
+                    Tick::new(-60, 2_000_000, 2_000_000),
+                    Tick::new(60, 2_000_000, -2_000_000),
+                    Tick::new(120, 1_000_000, -1_000_000),
+                    Tick::new(180, 5_000_000, 5_000_000),
+                    Tick::new(240, 5_000_000, -5_000_000),
+                ],
+                60,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_liquidity_histogram() {
+        let pool = pool();
+        let histogram = get_liquidity_histogram(&pool, -170, 300).unwrap();
+        let liquidities: Vec<(i32, u128)> = histogram
+            .iter()
+            .map(|bucket| (bucket.tick_lower, bucket.liquidity))
+            .collect();
+        assert_eq!(
+            liquidities,
+            vec![
+                (-180, 0),
+                (-120, 1_000_000),
+                (-60, 3_000_000),
+                (0, 3_000_000),
+                (60, 1_000_000),
+                (120, 0),
+                (180, 5_000_000),
+                (240, 0),
+            ]
+        );
+        assert!(histogram
+            .iter()
+            .all(|bucket| bucket.tick_upper == bucket.tick_lower + 60));
+        // the buckets above the price hold token0 and those below hold token1
+        assert_eq!(histogram[1].amount0, U256::ZERO);
+        assert!(histogram[1].amount1 > U256::ZERO);
+        assert_eq!(histogram[6].amount1, U256::ZERO);
+        assert!(histogram[6].amount0 > U256::ZERO);
+        // the bucket at the price holds only token0 at its lower bound
+        assert_eq!(histogram[3].amount1, U256::ZERO);
+    }
+
+    #[test]
+    fn test_get_liquidity_histogram_matches_tvl() {
+        let pool = pool();
+        let histogram = get_liquidity_histogram(&pool, MIN_TICK_I32, MAX_TICK_I32).unwrap();
+        let tvl = get_pool_tvl(&pool).unwrap();
+        let amount0: U256 = histogram.iter().map(|bucket| bucket.amount0).sum();
+        let amount1: U256 = histogram.iter().map(|bucket| bucket.amount1).sum();
+        // up to rounding in each bucket
+        let tvl0 = U256::from_big_int(tvl.amount0.quotient());
+        let tvl1 = U256::from_big_int(tvl.amount1.quotient());
+        let error = |a: U256, b: U256| a.max(b) - a.min(b) <= U256::from(histogram.len());
+        assert!(error(tvl0, amount0));
+        assert!(error(tvl1, amount1));
+    }
+
+    #[test]
+    fn test_get_liquidity_histogram_full_range() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let histogram = get_liquidity_histogram(&pool, -600, 600).unwrap();
+        assert_eq!(histogram.len(), 20);
+        assert!(histogram
+            .iter()
+            .all(|bucket| bucket.liquidity == pool.liquidity));
+    }
+}
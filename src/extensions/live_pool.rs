@@ -0,0 +1,265 @@
+//! ## Live Pool
+//! Keeps the state of a pool up to date in place across blocks instead of fetching a new [`Pool`]
+//! at each block: [`Pool::refresh`] re-reads the price and liquidity, and [`LivePool`] also applies
+//! the `Mint` and `Burn` logs since the last refresh to its ticks.
+
+use super::multicall3::decode_returns;
+use crate::prelude::{Error, *};
+use alloy::{eips::BlockId, network::Network, providers::Provider, rpc::types::Filter};
+use alloy_primitives::{aliases::I24, Address, ChainId};
+use alloy_sol_types::{SolCall, SolEvent};
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+
+/// Fetches the price, tick and in-range liquidity of a pool in a single multicall
+async fn get_slot0_and_liquidity<N, P>(
+    pool: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<(IUniswapV3Pool::slot0Return, u128), Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let results = multicall3_aggregate(
+        [
+            (pool, IUniswapV3Pool::slot0Call {}.abi_encode()),
+            (pool, IUniswapV3Pool::liquidityCall {}.abi_encode()),
+        ]
+        .map(|(target, call_data)| (target, call_data.into())),
+        provider,
+        block_id,
+    )
+    .await?;
+    Ok((
+        decode_returns::<IUniswapV3Pool::slot0Call>(&results[0])?,
+        decode_returns::<IUniswapV3Pool::liquidityCall>(&results[1])?._0,
+    ))
+}
+
+impl<TP: TickDataProvider> Pool<TP> {
+    /// Re-reads the price, the current tick and the in-range liquidity of the pool in a single
+    /// `eth_call`, keeping its tokens and tick data provider.
+    ///
+    /// ## Notes
+    ///
+    /// The pool address is derived by [`Pool::address`] from the factory of the chain of the
+    /// tokens. The ticks of the tick data provider are not updated, see [`LivePool`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query
+    ///
+    /// ## Returns
+    ///
+    /// Whether the state of the pool changed
+    #[inline]
+    pub async fn refresh<N, P>(
+        &mut self,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<bool, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let (slot0, liquidity) =
+            get_slot0_and_liquidity(self.address(None, None), provider, block_id).await?;
+        Ok(self.set_state(slot0, liquidity))
+    }
+
+    fn set_state(&mut self, slot0: IUniswapV3Pool::slot0Return, liquidity: u128) -> bool {
+        let tick_current = TP::Index::from_i24(slot0.tick);
+        let changed = self.sqrt_ratio_x96 != slot0.sqrtPriceX96
+            || self.liquidity != liquidity
+            || self.tick_current != tick_current;
+        self.sqrt_ratio_x96 = slot0.sqrtPriceX96;
+        self.tick_current = tick_current;
+        self.liquidity = liquidity;
+        changed
+    }
+}
+
+/// A pool backed by a [`TickMap`] kept up to date across blocks, see [`LivePool::refresh`].
+#[derive(Clone, Debug)]
+pub struct LivePool<I = I24> {
+    /// The pool address
+    pub address: Address,
+    /// The pool with all its initialized ticks
+    pub pool: Pool<TickMap<I>>,
+    /// The block number of the state of the pool
+    pub block_number: u64,
+}
+
+impl<I: TickIndex> LivePool<I> {
+    /// Fetches a pool and all its initialized ticks at a block
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address
+    /// * `token_a`: One of the tokens in the pool
+    /// * `token_b`: The other token in the pool
+    /// * `fee`: Fee tier of the pool
+    /// * `provider`: The alloy provider
+    /// * `block_number`: The block number to fetch the pool at
+    #[inline]
+    pub async fn new<N, P>(
+        chain_id: ChainId,
+        factory: Address,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        provider: P,
+        block_number: u64,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let block_id = Some(BlockId::from(block_number));
+        let address = compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id));
+        let pool = Pool::from_pool_key(
+            chain_id,
+            factory,
+            token_a,
+            token_b,
+            fee,
+            provider.root(),
+            block_id,
+        )
+        .await?;
+        let ticks = EphemeralTickDataProvider::new(address, provider, None, None, block_id).await?;
+        let tick_spacing = I::from_i24(fee.tick_spacing());
+        Ok(Self {
+            address,
+            pool: Pool::new_with_tick_data_provider(
+                pool.token0,
+                pool.token1,
+                pool.fee,
+                pool.sqrt_ratio_x96,
+                pool.liquidity,
+                TickMap::new(ticks.ticks, tick_spacing),
+            )?,
+            block_number,
+        })
+    }
+
+    /// Advances the pool to a later block: the `Mint` and `Burn` logs of the pool since the last
+    /// refresh are applied to the ticks with [`Pool::apply_event_with_ticks`], then the price and
+    /// in-range liquidity are re-read.
+    ///
+    /// ## Arguments
+    ///
+    /// * `provider`: The alloy provider
+    /// * `block_number`: The block number to advance to, ignored if not after the current one
+    ///
+    /// ## Returns
+    ///
+    /// Whether the state or the ticks of the pool changed
+    #[inline]
+    pub async fn refresh<N, P>(&mut self, provider: P, block_number: u64) -> Result<bool, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        if block_number <= self.block_number {
+            return Ok(false);
+        }
+        let filter = Filter::new()
+            .address(self.address)
+            .event_signature(vec![
+                IUniswapV3Pool::Mint::SIGNATURE_HASH,
+                IUniswapV3Pool::Burn::SIGNATURE_HASH,
+            ])
+            .from_block(self.block_number + 1)
+            .to_block(block_number);
+        let logs = provider.get_logs(&filter).await?;
+        for log in &logs {
+            self.pool
+                .apply_event_with_ticks(&PoolEvent::decode_log(&log.inner)?)?;
+        }
+        let (slot0, liquidity) =
+            get_slot0_and_liquidity(self.address, provider, Some(BlockId::from(block_number)))
+                .await?;
+        self.block_number = block_number;
+        Ok(self.pool.set_state(slot0, liquidity) || !logs.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[tokio::test]
+    async fn test_pool_refresh() {
+        let mut pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            Some(BlockId::from(16_999_000)),
+        )
+        .await
+        .unwrap();
+        assert!(pool.refresh(PROVIDER.clone(), *BLOCK_ID).await.unwrap());
+        assert!(!pool.refresh(PROVIDER.clone(), *BLOCK_ID).await.unwrap());
+        let expected = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(pool, expected);
+    }
+
+    #[tokio::test]
+    async fn test_live_pool_refresh() {
+        let new = |block_number| {
+            LivePool::<I24>::new(
+                1,
+                FACTORY_ADDRESS,
+                USDC.address(),
+                WETH.address(),
+                FeeAmount::LOW,
+                PROVIDER.clone(),
+                block_number,
+            )
+        };
+        let mut live = new(16_999_900).await.unwrap();
+        assert!(live.refresh(PROVIDER.clone(), 17_000_000).await.unwrap());
+        assert_eq!(live.block_number, 17_000_000);
+        assert!(!live.refresh(PROVIDER.clone(), 16_999_000).await.unwrap());
+        let expected = new(17_000_000).await.unwrap();
+        assert_eq!(live.pool, expected.pool);
+        let mut ticks: Vec<_> = live
+            .pool
+            .tick_data_provider
+            .inner
+            .values()
+            .copied()
+            .collect();
+        let mut expected_ticks: Vec<_> = expected
+            .pool
+            .tick_data_provider
+            .inner
+            .values()
+            .copied()
+            .collect();
+        ticks.sort_unstable_by_key(|tick| tick.index);
+        expected_ticks.sort_unstable_by_key(|tick| tick.index);
+        assert_eq!(ticks, expected_ticks);
+        assert_eq!(
+            live.pool.tick_data_provider.bitmap,
+            expected.pool.tick_data_provider.bitmap
+        );
+    }
+}
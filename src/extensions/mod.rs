@@ -1,21 +1,139 @@
 //! Extensions to the core library.
 
+mod access_list;
+mod amount_format;
+mod arbitrage;
+#[cfg(feature = "bigdecimal")]
+mod backtest;
+#[cfg(feature = "bigdecimal")]
+mod best_pool;
+#[cfg(feature = "bigdecimal")]
+mod bulk_quote;
+#[cfg(feature = "bigdecimal")]
+mod candles;
+mod capital_efficiency;
+mod compound;
+mod deadline;
 mod ephemeral_tick_data_provider;
 mod ephemeral_tick_map_data_provider;
+mod fee_apr;
+#[cfg(feature = "bigdecimal")]
+mod fee_tier;
+mod fee_tier_migration;
+mod fixture;
+mod gas_estimate;
+mod greeks;
+mod impermanent_loss;
+mod jit;
+mod lazy_tick_data_provider;
+mod limit_order;
+#[cfg(feature = "bigdecimal")]
+mod liquidity_depth;
+mod liquidity_histogram;
+mod live_pool;
+#[cfg(feature = "bigdecimal")]
+mod monte_carlo;
+mod multicall3;
+mod oracle;
+mod permit;
 mod pool;
+mod pool_diff;
+mod pool_events;
+mod pool_launch;
+mod pool_tvl;
 mod position;
+#[cfg(feature = "bigdecimal")]
+mod price_deviation;
 mod price_tick_conversions;
+mod quote_batch;
+mod quoted_trade;
+#[cfg(feature = "bigdecimal")]
+mod range_optimizer;
+mod rebalance;
+mod retry;
+mod router;
+mod slippage_curve;
 mod state_overrides;
+#[cfg(feature = "subgraph")]
+mod subgraph;
 mod tick_bit_map;
+mod tick_export;
 mod tick_map;
+mod tick_prefetch;
+#[cfg(feature = "bincode")]
+mod tick_snapshot;
+#[cfg(feature = "token_list")]
+mod token_list;
+#[cfg(feature = "bigdecimal")]
+mod volatility;
+mod zap;
 
+pub use access_list::*;
+pub use amount_format::*;
+pub use arbitrage::*;
+#[cfg(feature = "bigdecimal")]
+pub use backtest::*;
+#[cfg(feature = "bigdecimal")]
+pub use best_pool::*;
+#[cfg(feature = "bigdecimal")]
+pub use bulk_quote::*;
+#[cfg(feature = "bigdecimal")]
+pub use candles::*;
+pub use capital_efficiency::*;
+pub use compound::*;
+pub use deadline::*;
 pub use ephemeral_tick_data_provider::EphemeralTickDataProvider;
 pub use ephemeral_tick_map_data_provider::EphemeralTickMapDataProvider;
+pub use fee_apr::*;
+#[cfg(feature = "bigdecimal")]
+pub use fee_tier::*;
+pub use fee_tier_migration::*;
+pub use fixture::*;
+pub use gas_estimate::*;
+pub use greeks::*;
+pub use impermanent_loss::*;
+pub use jit::*;
+pub use lazy_tick_data_provider::LazyTickDataProvider;
+pub use limit_order::*;
+#[cfg(feature = "bigdecimal")]
+pub use liquidity_depth::*;
+pub use liquidity_histogram::*;
+pub use live_pool::*;
+#[cfg(feature = "bigdecimal")]
+pub use monte_carlo::*;
+pub use multicall3::*;
+pub use oracle::*;
+pub use permit::*;
 pub use pool::*;
+pub use pool_diff::*;
+pub use pool_events::*;
+pub use pool_launch::*;
+pub use pool_tvl::*;
 pub use position::*;
+#[cfg(feature = "bigdecimal")]
+pub use price_deviation::*;
 pub use price_tick_conversions::*;
+pub use quote_batch::*;
+pub use quoted_trade::*;
+#[cfg(feature = "bigdecimal")]
+pub use range_optimizer::*;
+pub use rebalance::*;
+pub use retry::*;
+pub use router::*;
+pub use slippage_curve::*;
 pub use state_overrides::*;
+#[cfg(feature = "subgraph")]
+pub use subgraph::*;
 pub use tick_bit_map::*;
+pub use tick_export::*;
 pub use tick_map::*;
+pub use tick_prefetch::*;
+#[cfg(feature = "bincode")]
+pub use tick_snapshot::*;
+#[cfg(feature = "token_list")]
+pub use token_list::*;
+#[cfg(feature = "bigdecimal")]
+pub use volatility::*;
+pub use zap::*;
 
 pub use uniswap_lens as lens;
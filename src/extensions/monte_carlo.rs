@@ -0,0 +1,330 @@
+//! ## Monte Carlo
+//! Simulates many price paths of a pool, by default with a geometric Brownian motion, and replays
+//! each against a liquidity management [`Strategy`] with [`backtest`], for the distribution of the
+//! fees, impermanent loss and terminal value of a position or a strategy rather than the outcome of
+//! a single scenario.
+
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+    utils::tick_math_f64::LN_TICK_BASE,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{
+    aliases::{I24, U24},
+    I256, U256,
+};
+use num_traits::ToPrimitive;
+
+/// A generator of the price paths simulated by [`monte_carlo`].
+///
+/// It is implemented for closures taking the tick at the start of the path.
+pub trait PricePaths {
+    /// Returns the ticks of the pool at the end of each interval of the next path
+    fn next_path(&mut self, start_tick: I24) -> Vec<I24>;
+}
+
+impl<F: FnMut(I24) -> Vec<I24>> PricePaths for F {
+    #[inline]
+    fn next_path(&mut self, start_tick: I24) -> Vec<I24> {
+        self(start_tick)
+    }
+}
+
+/// Price paths following a geometric Brownian motion, generated from a seeded pseudorandom number
+/// generator so that the simulations are reproducible.
+///
+/// The log price moves by `(drift - volatility^2 / 2) * dt + volatility * sqrt(dt) * Z` each
+/// interval, where `Z` is approximated by the sum of 12 uniform samples minus 6.
+#[derive(Clone, Debug)]
+pub struct GbmPaths {
+    /// The mean move of the tick per interval
+    tick_drift: f64,
+    /// The standard deviation of the move of the tick per interval
+    tick_volatility: f64,
+    steps: usize,
+    state: u64,
+}
+
+impl GbmPaths {
+    /// Creates a generator of GBM price paths
+    ///
+    /// ## Arguments
+    ///
+    /// * `drift`: The annualized drift of the price, e.g. 0 for a martingale
+    /// * `volatility`: The annualized volatility of the price, e.g. from
+    ///   [`get_realized_volatility`]
+    /// * `interval`: The seconds between two points of a path
+    /// * `steps`: The number of intervals of a path
+    /// * `seed`: The seed of the pseudorandom number generator
+    #[inline]
+    #[must_use]
+    pub fn new(drift: f64, volatility: f64, interval: u64, steps: usize, seed: u64) -> Self {
+        let dt = interval as f64 / SECONDS_PER_YEAR as f64;
+        let sqrt_dt = (BigDecimal::from(interval) / BigDecimal::from(SECONDS_PER_YEAR))
+            .sqrt()
+            .and_then(|sqrt_dt| sqrt_dt.to_f64())
+            .unwrap_or_default();
+        Self {
+            tick_drift: (drift - volatility * volatility / 2.0) * dt / LN_TICK_BASE,
+            tick_volatility: volatility * sqrt_dt / LN_TICK_BASE,
+            steps,
+            state: seed,
+        }
+    }
+
+    /// SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// An approximately standard normal sample
+    fn next_normal(&mut self) -> f64 {
+        (0..12)
+            .map(|_| (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64)
+            .sum::<f64>()
+            - 6.0
+    }
+}
+
+impl PricePaths for GbmPaths {
+    #[inline]
+    fn next_path(&mut self, start_tick: I24) -> Vec<I24> {
+        let mut tick = start_tick.as_i32() as f64;
+        (0..self.steps)
+            .map(|_| {
+                tick += self.tick_drift + self.tick_volatility * self.next_normal();
+                tick = tick.clamp(MIN_TICK_I32 as f64, MAX_TICK_I32 as f64);
+                I24::try_from(tick as i32).unwrap()
+            })
+            .collect()
+    }
+}
+
+/// Converts a path of ticks into the swaps that move a pool along it, to replay with [`backtest`].
+///
+/// ## Notes
+///
+/// The pool is assumed to keep its active liquidity over the full range, and each move of the price
+/// to be made by a single swap with its fee on top of the amount that moves the price, i.e. the
+/// volume is the minimum that arbitrageurs would trade.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool at the start of the path
+/// * `ticks`: The ticks of the pool at the end of each interval
+/// * `interval`: The seconds between two points of the path
+#[inline]
+pub fn path_to_swaps(pool: &Pool, ticks: &[I24], interval: u64) -> Result<Vec<SwapEvent>, Error> {
+    let fee = U256::from(U24::from(pool.fee).to::<u32>());
+    let fee_denominator = U256::from(1_000_000);
+    let mut sqrt_price_x96 = pool.sqrt_ratio_x96;
+    let mut events = Vec::with_capacity(ticks.len());
+    for (i, &tick) in ticks.iter().enumerate() {
+        let sqrt_price_next_x96 = get_sqrt_ratio_at_tick(tick)?;
+        let zero_for_one = sqrt_price_next_x96 < sqrt_price_x96;
+        let (sqrt_price_lower_x96, sqrt_price_upper_x96) = if zero_for_one {
+            (sqrt_price_next_x96, sqrt_price_x96)
+        } else {
+            (sqrt_price_x96, sqrt_price_next_x96)
+        };
+        let amount0 = get_amount_0_delta(
+            sqrt_price_lower_x96,
+            sqrt_price_upper_x96,
+            pool.liquidity,
+            zero_for_one,
+        )?;
+        let amount1 = get_amount_1_delta(
+            sqrt_price_lower_x96,
+            sqrt_price_upper_x96,
+            pool.liquidity,
+            !zero_for_one,
+        )?;
+        let with_fee = |amount: U256| amount * fee_denominator / (fee_denominator - fee);
+        let (amount0, amount1) = if zero_for_one {
+            (I256::from_raw(with_fee(amount0)), -I256::from_raw(amount1))
+        } else {
+            (-I256::from_raw(amount0), I256::from_raw(with_fee(amount1)))
+        };
+        let block_number = i as u64 + 1;
+        events.push(SwapEvent {
+            block_number,
+            timestamp: block_number * interval,
+            amount0,
+            amount1,
+            sqrt_price_x96: sqrt_price_next_x96,
+            liquidity: pool.liquidity,
+            tick,
+        });
+        sqrt_price_x96 = sqrt_price_next_x96;
+    }
+    Ok(events)
+}
+
+/// The distribution of the outcomes of [`monte_carlo`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonteCarloReport {
+    /// The report of the backtest of each path
+    pub runs: Vec<BacktestReport>,
+}
+
+impl MonteCarloReport {
+    /// Returns the mean of a metric of the runs, e.g. `|run| run.fees_value.clone()`, or `None` if
+    /// there are no runs
+    ///
+    /// ## Arguments
+    ///
+    /// * `metric`: The metric of a run
+    #[inline]
+    #[must_use]
+    pub fn mean<F: Fn(&BacktestReport) -> BigDecimal>(&self, metric: F) -> Option<BigDecimal> {
+        if self.runs.is_empty() {
+            return None;
+        }
+        let sum: BigDecimal = self.runs.iter().map(metric).sum();
+        Some(sum / BigDecimal::from(self.runs.len() as u64))
+    }
+
+    /// Returns the nearest-rank percentile of a metric of the runs, or `None` if there are no runs
+    ///
+    /// ## Arguments
+    ///
+    /// * `metric`: The metric of a run
+    /// * `percentile`: The percentile, from 0 to 100
+    #[inline]
+    #[must_use]
+    pub fn percentile<F: Fn(&BacktestReport) -> BigDecimal>(
+        &self,
+        metric: F,
+        percentile: u8,
+    ) -> Option<BigDecimal> {
+        assert!(percentile <= 100, "PERCENTILE");
+        let mut values: Vec<BigDecimal> = self.runs.iter().map(metric).collect();
+        values.sort_unstable();
+        let rank = (values.len() * percentile as usize).div_ceil(100);
+        values.into_iter().nth(rank.saturating_sub(1))
+    }
+}
+
+/// Simulates price paths of a pool and replays each against a strategy.
+///
+/// ## Notes
+///
+/// Each path is converted into swaps by [`path_to_swaps`] and replayed by [`backtest`] with a fresh
+/// copy of the strategy, e.g. a [`FixedRangeStrategy`] to evaluate a single position.
+///
+/// ## Arguments
+///
+/// * `strategy`: The strategy managing the position, cloned for each path
+/// * `paths`: The generator of the price paths, e.g. [`GbmPaths`]
+/// * `runs`: The number of paths to simulate
+/// * `interval`: The seconds between two points of a path
+/// * `config`: The initial state of the pool, the balances and the gas costs
+#[inline]
+pub fn monte_carlo<S, G>(
+    strategy: &S,
+    paths: &mut G,
+    runs: usize,
+    interval: u64,
+    config: &BacktestConfig,
+) -> Result<MonteCarloReport, Error>
+where
+    S: Clone + Strategy,
+    G: PricePaths,
+{
+    let start_tick = config.pool.tick_current.to_i24();
+    let runs = (0..runs)
+        .map(|_| {
+            let events = path_to_swaps(&config.pool, &paths.next_path(start_tick), interval)?;
+            backtest(&mut strategy.clone(), &events, config)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(MonteCarloReport { runs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn config() -> BacktestConfig {
+        BacktestConfig {
+            pool: Pool::new(
+                TOKEN0.clone(),
+                TOKEN1.clone(),
+                FeeAmount::MEDIUM,
+                encode_sqrt_ratio_x96(1, 1),
+                1_000_000_000_000,
+            )
+            .unwrap(),
+            amount0: U256::from(1_000_000),
+            amount1: U256::from(1_000_000),
+            gas_per_rebalance: 0,
+            gas_price: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_gbm_paths() {
+        let mut paths = GbmPaths::new(0.0, 0.8, 3600, 24 * 30, 42);
+        let path = paths.next_path(I24::ZERO);
+        assert_eq!(path.len(), 24 * 30);
+        // reproducible from the seed
+        assert_eq!(
+            GbmPaths::new(0.0, 0.8, 3600, 24 * 30, 42).next_path(I24::ZERO),
+            path
+        );
+        assert_ne!(paths.next_path(I24::ZERO), path);
+        // the tick moves by about 0.8 * sqrt(1 / 8760) / ln(1.0001) ~ 85 per hour
+        let moves: Vec<i32> = path
+            .windows(2)
+            .map(|w| (w[1] - w[0]).as_i32().abs())
+            .collect();
+        let mean_move = moves.iter().sum::<i32>() / moves.len() as i32;
+        assert!((40..100).contains(&mean_move));
+    }
+
+    #[test]
+    fn test_path_to_swaps() {
+        let config = config();
+        let ticks = [I24::from_limbs([60]), I24::try_from(-60).unwrap()];
+        let events = path_to_swaps(&config.pool, &ticks, 12).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].amount0.is_negative() && events[0].amount1.is_positive());
+        assert!(events[1].amount0.is_positive() && events[1].amount1.is_negative());
+        assert_eq!(events[1].timestamp, 24);
+        assert_eq!(
+            events[1].sqrt_price_x96,
+            get_sqrt_ratio_at_tick(ticks[1]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let config = config();
+        let strategy = FixedRangeStrategy {
+            tick_lower: I24::try_from(-600).unwrap(),
+            tick_upper: I24::from_limbs([600]),
+        };
+        let mut paths = GbmPaths::new(0.0, 0.5, 3600, 24 * 7, 7);
+        let report = monte_carlo(&strategy, &mut paths, 20, 3600, &config).unwrap();
+        assert_eq!(report.runs.len(), 20);
+        assert!(report.runs.iter().all(|run| run.rebalances == 0));
+        let fees = |run: &BacktestReport| run.fees_value.clone();
+        let median = report.percentile(fees, 50).unwrap();
+        assert!(median > BigDecimal::from(0));
+        assert!(report.percentile(fees, 0).unwrap() <= median);
+        assert!(report.percentile(fees, 100).unwrap() >= median);
+        assert!(report.mean(fees).unwrap() > BigDecimal::from(0));
+        // a user-supplied path that stays at the initial price earns no fees
+        let flat = monte_carlo(&strategy, &mut |tick| vec![tick; 10], 2, 3600, &config).unwrap();
+        assert!(flat
+            .runs
+            .iter()
+            .all(|run| run.fees0.is_zero() && run.fees1.is_zero()));
+    }
+}
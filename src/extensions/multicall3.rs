@@ -0,0 +1,143 @@
+//! ## Multicall3
+//! Batches read-only calls into a single `eth_call` to the [Multicall3](https://github.com/mds1/multicall)
+//! contract, which is deployed at the same address on most chains.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{
+    contract::{Error as ContractError, SolCallBuilder},
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+};
+use alloy_primitives::{address, Address, Bytes};
+use alloy_sol_types::SolCall;
+
+/// The address of the Multicall3 contract
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// Executes the calls in a single `eth_call` to Multicall3, failing if any of the calls reverts.
+///
+/// ## Arguments
+///
+/// * `calls`: The target and calldata of each call
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The return data of each call, in the order of the calls
+#[inline]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(?block_id)))]
+pub async fn multicall3_aggregate<N, P>(
+    calls: impl IntoIterator<Item = (Address, Bytes)>,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Vec<Bytes>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let calls: Vec<_> = calls
+        .into_iter()
+        .map(|(target, call_data)| IMulticall3::Call3 {
+            target,
+            allowFailure: false,
+            callData: call_data,
+        })
+        .collect();
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(calls = calls.len(), "aggregating calls");
+    let results = SolCallBuilder::new_sol(
+        provider,
+        &MULTICALL3_ADDRESS,
+        &IMulticall3::aggregate3Call { calls },
+    )
+    .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+    .call()
+    .await?
+    .returnData;
+    Ok(results
+        .into_iter()
+        .map(|result| result.returnData)
+        .collect())
+}
+
+/// Executes the calls in a single `eth_call` to Multicall3, allowing any of the calls to revert.
+///
+/// ## Arguments
+///
+/// * `calls`: The target and calldata of each call
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The return data of each call, or `None` if it reverted, in the order of the calls
+#[inline]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(?block_id)))]
+pub async fn multicall3_try_aggregate<N, P>(
+    calls: impl IntoIterator<Item = (Address, Bytes)>,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Vec<Option<Bytes>>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let calls: Vec<_> = calls
+        .into_iter()
+        .map(|(target, call_data)| IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call_data,
+        })
+        .collect();
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(calls = calls.len(), "aggregating calls");
+    let results = SolCallBuilder::new_sol(
+        provider,
+        &MULTICALL3_ADDRESS,
+        &IMulticall3::aggregate3Call { calls },
+    )
+    .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+    .call()
+    .await?
+    .returnData;
+    Ok(results
+        .into_iter()
+        .map(|result| result.success.then_some(result.returnData))
+        .collect())
+}
+
+/// Fetches the timestamp of a block from Multicall3
+#[inline]
+pub(crate) async fn get_block_timestamp<N, P>(provider: P, block_id: BlockId) -> Result<u64, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    Ok(SolCallBuilder::new_sol(
+        provider,
+        &MULTICALL3_ADDRESS,
+        &IMulticall3::getCurrentBlockTimestampCall {},
+    )
+    .block(block_id)
+    .call()
+    .await?
+    .timestamp
+    .to())
+}
+
+/// Decodes the return data of a call aggregated by [`multicall3_aggregate`]
+#[inline]
+pub(crate) fn decode_returns<C: SolCall>(data: &[u8]) -> Result<C::Return, Error> {
+    C::abi_decode_returns(data, true)
+        .map_err(|e| Error::ContractError(ContractError::AbiError(e.into())))
+}
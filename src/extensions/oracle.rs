@@ -0,0 +1,828 @@
+//! ## Oracle
+//! Time-weighted averages of the price and liquidity of a pool over a window, computed from the
+//! cumulatives returned by `observe` as in the [`OracleLibrary`](https://github.com/Uniswap/v3-periphery/blob/main/contracts/libraries/OracleLibrary.sol),
+//! the time the price of a pool spent in a range, and helpers to grow the observation array of a
+//! pool.
+
+use super::multicall3::decode_returns;
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+};
+use alloy_primitives::{
+    aliases::{I24, I56},
+    Address, Bytes, U160, U256,
+};
+use alloy_sol_types::SolCall;
+use num_traits::ToPrimitive;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// The time-weighted averages of a pool over a window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeWeightedAverages {
+    /// The arithmetic mean tick, rounded towards negative infinity
+    pub arithmetic_mean_tick: I24,
+    /// The harmonic mean of the in-range liquidity
+    pub harmonic_mean_liquidity: u128,
+}
+
+impl TimeWeightedAverages {
+    /// Returns the time-weighted geometric mean price at the arithmetic mean tick
+    ///
+    /// ## Arguments
+    ///
+    /// * `base_token`: The base token of the price
+    /// * `quote_token`: The quote token of the price
+    #[inline]
+    pub fn price(
+        &self,
+        base_token: Token,
+        quote_token: Token,
+    ) -> Result<Price<Token, Token>, Error> {
+        tick_to_price(base_token, quote_token, self.arithmetic_mean_tick)
+    }
+}
+
+/// Encodes the calldata of `observe` on a pool
+///
+/// ## Arguments
+///
+/// * `seconds_agos`: From how long ago each cumulative should be returned
+#[inline]
+#[must_use]
+pub fn encode_observe(seconds_agos: Vec<u32>) -> Bytes {
+    IUniswapV3Pool::observeCall {
+        secondsAgos: seconds_agos,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the return data of `observe` on a pool
+///
+/// ## Returns
+///
+/// The tick cumulatives and the seconds per liquidity cumulatives, in the order of the
+/// `seconds_agos`
+#[inline]
+pub fn decode_observe(data: &[u8]) -> Result<(Vec<I56>, Vec<U160>), Error> {
+    let IUniswapV3Pool::observeReturn {
+        tickCumulatives,
+        secondsPerLiquidityCumulativeX128s,
+    } = decode_returns::<IUniswapV3Pool::observeCall>(data)?;
+    Ok((tickCumulatives, secondsPerLiquidityCumulativeX128s))
+}
+
+/// Computes the arithmetic mean tick over a window from the tick cumulatives at its start and end,
+/// rounded towards negative infinity.
+///
+/// ## Arguments
+///
+/// * `tick_cumulative_start`: The tick cumulative at the start of the window
+/// * `tick_cumulative_end`: The tick cumulative at the end of the window
+/// * `seconds`: The length of the window in seconds
+#[inline]
+pub fn get_arithmetic_mean_tick(
+    tick_cumulative_start: I56,
+    tick_cumulative_end: I56,
+    seconds: u32,
+) -> Result<I24, Error> {
+    if seconds == 0 {
+        return Err(Error::InvalidInterval);
+    }
+    let tick_cumulatives_delta = tick_cumulative_end.as_i64() - tick_cumulative_start.as_i64();
+    let seconds = i64::from(seconds);
+    let mut arithmetic_mean_tick = tick_cumulatives_delta / seconds;
+    if tick_cumulatives_delta < 0 && tick_cumulatives_delta % seconds != 0 {
+        arithmetic_mean_tick -= 1;
+    }
+    I24::try_from(arithmetic_mean_tick)
+        .ok()
+        .filter(|tick| (MIN_TICK..=MAX_TICK).contains(tick))
+        .ok_or_else(|| {
+            Error::InvalidTickData(alloc::format!(
+                "arithmetic mean tick {arithmetic_mean_tick} out of range"
+            ))
+        })
+}
+
+/// Computes the time-weighted averages over a window from the cumulatives at its start and end, as
+/// in `OracleLibrary.consult`.
+///
+/// ## Arguments
+///
+/// * `tick_cumulatives`: The tick cumulatives at the start and the end of the window
+/// * `seconds_per_liquidity_cumulative_x128s`: The seconds per liquidity cumulatives at the start
+///   and the end of the window
+/// * `seconds`: The length of the window in seconds
+///
+/// ## Returns
+///
+/// [`Error::InvalidTimeRange`] if the seconds per liquidity cumulative did not grow over the
+/// window, which it does over any window of a pool
+#[inline]
+pub fn consult_observations(
+    tick_cumulatives: [I56; 2],
+    seconds_per_liquidity_cumulative_x128s: [U160; 2],
+    seconds: u32,
+) -> Result<TimeWeightedAverages, Error> {
+    let arithmetic_mean_tick =
+        get_arithmetic_mean_tick(tick_cumulatives[0], tick_cumulatives[1], seconds)?;
+    let seconds_x160 = U256::from(seconds) * U256::from(U160::MAX);
+    let seconds_per_liquidity_cumulatives_delta = U256::from(
+        seconds_per_liquidity_cumulative_x128s[1]
+            .wrapping_sub(seconds_per_liquidity_cumulative_x128s[0]),
+    );
+    if seconds_per_liquidity_cumulatives_delta.is_zero() {
+        return Err(Error::InvalidTimeRange);
+    }
+    Ok(TimeWeightedAverages {
+        arithmetic_mean_tick,
+        harmonic_mean_liquidity: (seconds_x160 / (seconds_per_liquidity_cumulatives_delta << 32))
+            .saturating_to(),
+    })
+}
+
+/// Fetches the time-weighted averages of a pool over a window.
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `seconds_ago_start`: From how long ago the window starts
+/// * `seconds_ago_end`: From how long ago the window ends, zero for the current block
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_time_weighted_averages<N, P>(
+    pool: Address,
+    seconds_ago_start: u32,
+    seconds_ago_end: u32,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<TimeWeightedAverages, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if seconds_ago_start <= seconds_ago_end {
+        return Err(Error::InvalidTimeRange);
+    }
+    let IUniswapV3Pool::observeReturn {
+        tickCumulatives,
+        secondsPerLiquidityCumulativeX128s,
+    } = IUniswapV3Pool::new(pool, provider)
+        .observe(vec![seconds_ago_start, seconds_ago_end])
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?;
+    consult_observations(
+        [tickCumulatives[0], tickCumulatives[1]],
+        [
+            secondsPerLiquidityCumulativeX128s[0],
+            secondsPerLiquidityCumulativeX128s[1],
+        ],
+        seconds_ago_start - seconds_ago_end,
+    )
+}
+
+/// The divergence of the spot price of a pool from its time-weighted average price, see
+/// [`get_twap_divergence`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TwapDivergence {
+    /// The current sqrt price of the pool
+    pub sqrt_price_x96: U160,
+    /// The arithmetic mean tick over the window
+    pub arithmetic_mean_tick: I24,
+    /// The divergence of the spot price from the time-weighted average price in basis points,
+    /// positive if the spot price is above it
+    pub divergence_bps: i128,
+}
+
+impl TwapDivergence {
+    /// Whether the spot price diverges from the time-weighted average price by more than a
+    /// threshold in either direction, e.g. to reject quotes of a manipulated pool
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_divergence_bps`: The maximum divergence in basis points
+    #[inline]
+    #[must_use]
+    pub const fn exceeds(&self, max_divergence_bps: u32) -> bool {
+        self.divergence_bps.unsigned_abs() > max_divergence_bps as u128
+    }
+}
+
+/// Computes the divergence in basis points of a spot price from the price at a mean tick, rounded
+/// towards zero and saturated to the range of `i128`.
+///
+/// ## Arguments
+///
+/// * `sqrt_price_x96`: The spot sqrt price
+/// * `arithmetic_mean_tick`: The time-weighted arithmetic mean tick
+#[inline]
+pub fn get_price_divergence_bps(
+    sqrt_price_x96: U160,
+    arithmetic_mean_tick: I24,
+) -> Result<i128, Error> {
+    // the prices are the squares of the sqrt prices, which share the same Q96 scale
+    let spot = sqrt_price_x96.to_big_int().pow(2);
+    let twap = get_sqrt_ratio_at_tick(arithmetic_mean_tick)?
+        .to_big_int()
+        .pow(2);
+    let divergence = (spot - &twap) * 10000 / twap;
+    Ok(divergence.to_i128().unwrap_or(i128::MAX))
+}
+
+/// Fetches the spot price of a pool and its time-weighted average price over a window ending at
+/// the block, and computes the divergence between them.
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `seconds_ago`: The length of the window in seconds
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_twap_divergence<N, P>(
+    pool: Address,
+    seconds_ago: u32,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<TwapDivergence, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if seconds_ago == 0 {
+        return Err(Error::InvalidInterval);
+    }
+    let results = multicall3_aggregate(
+        [
+            (pool, IUniswapV3Pool::slot0Call {}.abi_encode().into()),
+            (pool, encode_observe(vec![seconds_ago, 0])),
+        ],
+        provider,
+        block_id,
+    )
+    .await?;
+    let slot0 = decode_returns::<IUniswapV3Pool::slot0Call>(&results[0])?;
+    let (tick_cumulatives, _) = decode_observe(&results[1])?;
+    let arithmetic_mean_tick =
+        get_arithmetic_mean_tick(tick_cumulatives[0], tick_cumulatives[1], seconds_ago)?;
+    Ok(TwapDivergence {
+        sqrt_price_x96: slot0.sqrtPriceX96,
+        arithmetic_mean_tick,
+        divergence_bps: get_price_divergence_bps(slot0.sqrtPriceX96, arithmetic_mean_tick)?,
+    })
+}
+
+/// The time the price of a pool spent in and outside a range over a window, see
+/// [`get_time_in_range`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeInRange {
+    /// The number of seconds the price was in the range
+    pub seconds_in_range: u64,
+    /// The number of seconds the price was outside the range
+    pub seconds_outside: u64,
+}
+
+impl TimeInRange {
+    /// Returns the share of the window the price was in the range, zero for an empty window
+    #[inline]
+    #[must_use]
+    pub fn percentage_in_range(&self) -> Percent {
+        let seconds = self.seconds_in_range + self.seconds_outside;
+        if seconds == 0 {
+            return Percent::new(0, 1);
+        }
+        Percent::new(self.seconds_in_range, seconds)
+    }
+}
+
+/// Computes the time the price spent in a range from tick cumulatives sampled at a fixed interval,
+/// where the price of each interval is the arithmetic mean tick between two consecutive
+/// cumulatives, in range if it is at least the lower tick and below the upper tick like the
+/// liquidity of a position.
+///
+/// ## Notes
+///
+/// The time in range is exact if the tick did not cross the range within an interval, e.g. if the
+/// cumulatives are those of consecutive observations, between which the tick is constant, and an
+/// estimate otherwise.
+///
+/// ## Arguments
+///
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `tick_cumulatives`: The tick cumulatives in chronological order
+/// * `sample_interval`: The number of seconds between two consecutive tick cumulatives
+#[inline]
+pub fn time_in_range_from_tick_cumulatives<I: TickIndex>(
+    tick_lower: I,
+    tick_upper: I,
+    tick_cumulatives: &[I56],
+    sample_interval: u32,
+) -> Result<TimeInRange, Error> {
+    if tick_lower >= tick_upper {
+        return Err(Error::InvalidRange);
+    }
+    if sample_interval == 0 {
+        return Err(Error::InvalidInterval);
+    }
+    let (tick_lower, tick_upper) = (tick_lower.to_i24(), tick_upper.to_i24());
+    let mut time_in_range = TimeInRange::default();
+    for window in tick_cumulatives.windows(2) {
+        let tick = get_arithmetic_mean_tick(window[0], window[1], sample_interval)?;
+        if tick_lower <= tick && tick < tick_upper {
+            time_in_range.seconds_in_range += u64::from(sample_interval);
+        } else {
+            time_in_range.seconds_outside += u64::from(sample_interval);
+        }
+    }
+    Ok(time_in_range)
+}
+
+/// The maximum number of samples [`get_time_in_range`] fetches in a single `observe` call, which
+/// keeps the call within the gas limit of `eth_call` of common nodes
+pub const MAX_TIME_IN_RANGE_SAMPLES: u32 = 1024;
+
+/// Fetches the time the price of a pool spent in and outside a range over a window ending at the
+/// block, from its tick cumulatives sampled at a fixed interval in a single `observe` call, see
+/// [`time_in_range_from_tick_cumulatives`].
+///
+/// ## Notes
+///
+/// The observation array of the pool must be large enough to cover the window, see
+/// [`get_observation_cardinality`].
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `seconds_ago`: The length of the window in seconds
+/// * `sample_interval`: The number of seconds between two samples, which must divide the window
+///   into at most [`MAX_TIME_IN_RANGE_SAMPLES`] samples
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_time_in_range<I, N, P>(
+    pool: Address,
+    tick_lower: I,
+    tick_upper: I,
+    seconds_ago: u32,
+    sample_interval: u32,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<TimeInRange, Error>
+where
+    I: TickIndex,
+    N: Network,
+    P: Provider<N>,
+{
+    if sample_interval == 0
+        || seconds_ago % sample_interval != 0
+        || seconds_ago / sample_interval > MAX_TIME_IN_RANGE_SAMPLES
+    {
+        return Err(Error::InvalidInterval);
+    }
+    let seconds_agos: Vec<u32> = (0..=seconds_ago / sample_interval)
+        .rev()
+        .map(|i| i * sample_interval)
+        .collect();
+    let tick_cumulatives = IUniswapV3Pool::new(pool, provider)
+        .observe(seconds_agos)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        .tickCumulatives;
+    time_in_range_from_tick_cumulatives(tick_lower, tick_upper, &tick_cumulatives, sample_interval)
+}
+
+/// The approximate gas to initialize one slot of the observation array, i.e. a cold `SSTORE` from
+/// zero to nonzero
+pub const GAS_PER_OBSERVATION_SLOT: u64 = 22_100;
+
+/// The approximate gas of a transaction calling `increaseObservationCardinalityNext`, excluding the
+/// slots initialized
+pub const INCREASE_CARDINALITY_BASE_GAS: u64 = 30_000;
+
+/// The observation cardinalities of a pool, see [`get_observation_cardinality`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ObservationCardinality {
+    /// The number of observations currently stored
+    pub cardinality: u16,
+    /// The number of observations to be stored, once the next observation is written
+    pub cardinality_next: u16,
+}
+
+/// Fetches the observation cardinalities of a pool.
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_observation_cardinality<N, P>(
+    pool: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ObservationCardinality, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let slot0 = IUniswapV3Pool::new(pool, provider)
+        .slot0()
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?;
+    Ok(ObservationCardinality {
+        cardinality: slot0.observationCardinality,
+        cardinality_next: slot0.observationCardinalityNext,
+    })
+}
+
+/// Produces the calldata to grow the observation array of a pool.
+///
+/// ## Arguments
+///
+/// * `observation_cardinality_next`: The desired minimum number of observations to store
+#[inline]
+#[must_use]
+pub fn increase_observation_cardinality_next_call_parameters(
+    observation_cardinality_next: u16,
+) -> MethodParameters {
+    MethodParameters {
+        calldata: IUniswapV3Pool::increaseObservationCardinalityNextCall {
+            observationCardinalityNext: observation_cardinality_next,
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// The transactions to grow the observation array of a pool to a target cardinality, see
+/// [`plan_observation_cardinality_increase`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CardinalityIncreasePlan {
+    /// The number of slots of the observation array to initialize
+    pub slots: u16,
+    /// The estimated gas of all the transactions
+    pub gas: u64,
+    /// The calldata of each transaction, to be sent to the pool in order
+    pub transactions: Vec<MethodParameters>,
+}
+
+/// Plans the transactions to grow the observation array of a pool to a target cardinality, splitting
+/// the slots to initialize so that each transaction stays within a gas limit.
+///
+/// ## Arguments
+///
+/// * `cardinality_next`: The current `observationCardinalityNext` of the pool
+/// * `target`: The desired observation cardinality
+/// * `max_gas_per_transaction`: The gas limit of each transaction
+///
+/// ## Returns
+///
+/// An empty plan if the observation array already has the target cardinality, or
+/// [`Error::GasLimitTooLow`] if a transaction cannot initialize a single slot within the gas limit
+#[inline]
+pub fn plan_observation_cardinality_increase(
+    cardinality_next: u16,
+    target: u16,
+    max_gas_per_transaction: u64,
+) -> Result<CardinalityIncreasePlan, Error> {
+    let slots = target.saturating_sub(cardinality_next);
+    if slots == 0 {
+        return Ok(CardinalityIncreasePlan::default());
+    }
+    let slots_per_transaction = max_gas_per_transaction
+        .saturating_sub(INCREASE_CARDINALITY_BASE_GAS)
+        / GAS_PER_OBSERVATION_SLOT;
+    if slots_per_transaction == 0 {
+        return Err(Error::GasLimitTooLow);
+    }
+    let slots_per_transaction = slots_per_transaction.min(u64::from(u16::MAX)) as u16;
+    let mut transactions = Vec::with_capacity(slots.div_ceil(slots_per_transaction) as usize);
+    let mut next = cardinality_next;
+    while next < target {
+        next = next.saturating_add(slots_per_transaction).min(target);
+        transactions.push(increase_observation_cardinality_next_call_parameters(next));
+    }
+    Ok(CardinalityIncreasePlan {
+        slots,
+        gas: transactions.len() as u64 * INCREASE_CARDINALITY_BASE_GAS
+            + u64::from(slots) * GAS_PER_OBSERVATION_SLOT,
+        transactions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    fn i56(value: i64) -> I56 {
+        I56::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_consult_observations_mean_tick() {
+        let spl = [U160::ZERO, U160::from(1) << 128];
+        assert_eq!(
+            consult_observations([i56(0), i56(6000)], spl, 60)
+                .unwrap()
+                .arithmetic_mean_tick,
+            I24::try_from(100).unwrap()
+        );
+        // rounded towards negative infinity
+        assert_eq!(
+            consult_observations([i56(0), i56(-601)], spl, 60)
+                .unwrap()
+                .arithmetic_mean_tick,
+            I24::try_from(-11).unwrap()
+        );
+        assert_eq!(
+            consult_observations([i56(-600), i56(-1200)], spl, 60)
+                .unwrap()
+                .arithmetic_mean_tick,
+            I24::try_from(-10).unwrap()
+        );
+        assert!(matches!(
+            consult_observations([i56(0), i56(6000)], spl, 0),
+            Err(Error::InvalidInterval)
+        ));
+        // beyond the maximum tick
+        assert!(matches!(
+            consult_observations([i56(0), i56(887273 * 60)], spl, 60),
+            Err(Error::InvalidTickData(_))
+        ));
+    }
+
+    #[test]
+    fn test_consult_observations_harmonic_mean_liquidity() {
+        // a constant liquidity of 1e6 over 60 seconds
+        let delta = (U256::from(60) << 128) / U256::from(1_000_000);
+        let start = U160::from(12345);
+        let end = start + delta.to::<U160>();
+        let averages = consult_observations([i56(0), i56(0)], [start, end], 60).unwrap();
+        assert!(averages.harmonic_mean_liquidity.abs_diff(1_000_000) <= 1);
+        // the cumulative wraps around
+        let start = U160::MAX - U160::from(1);
+        let end = start.wrapping_add(delta.to::<U160>());
+        assert_eq!(
+            consult_observations([i56(0), i56(0)], [start, end], 60).unwrap(),
+            averages
+        );
+        // the cumulative did not grow
+        assert!(matches!(
+            consult_observations([i56(0), i56(0)], [start, start], 60),
+            Err(Error::InvalidTimeRange)
+        ));
+    }
+
+    #[test]
+    fn test_time_in_range_from_tick_cumulatives() {
+        // mean ticks of 100, -10, 60 and 59 over 60 seconds each
+        let tick_cumulatives = [0, 6000, 5400, 9000, 12540].map(i56);
+        let time_in_range =
+            time_in_range_from_tick_cumulatives(0, 60, &tick_cumulatives, 60).unwrap();
+        assert_eq!(
+            time_in_range,
+            TimeInRange {
+                seconds_in_range: 60,
+                seconds_outside: 180,
+            }
+        );
+        assert_eq!(time_in_range.percentage_in_range(), Percent::new(1, 4));
+        let time_in_range =
+            time_in_range_from_tick_cumulatives(-60, 120, &tick_cumulatives, 60).unwrap();
+        assert_eq!(time_in_range.seconds_outside, 0);
+        assert_eq!(time_in_range.percentage_in_range(), Percent::new(1, 1));
+        assert_eq!(
+            TimeInRange::default().percentage_in_range(),
+            Percent::new(0, 1)
+        );
+        assert!(matches!(
+            time_in_range_from_tick_cumulatives(60, 0, &tick_cumulatives, 60),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            time_in_range_from_tick_cumulatives(0, 60, &tick_cumulatives, 0),
+            Err(Error::InvalidInterval)
+        ));
+    }
+
+    #[test]
+    fn test_encode_decode_observe() {
+        let calldata = encode_observe(vec![60, 0]);
+        let decoded = IUniswapV3Pool::observeCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.secondsAgos, vec![60, 0]);
+
+        let data = IUniswapV3Pool::observeCall::abi_encode_returns(&(
+            vec![i56(1), i56(2)],
+            vec![U160::from(3), U160::from(4)],
+        ));
+        assert_eq!(
+            decode_observe(&data).unwrap(),
+            (vec![i56(1), i56(2)], vec![U160::from(3), U160::from(4)])
+        );
+    }
+
+    #[test]
+    fn test_plan_observation_cardinality_increase() {
+        assert_eq!(
+            plan_observation_cardinality_increase(100, 100, 30_000_000).unwrap(),
+            CardinalityIncreasePlan::default()
+        );
+
+        let plan = plan_observation_cardinality_increase(1, 101, 30_000_000).unwrap();
+        assert_eq!(plan.slots, 100);
+        assert_eq!(plan.transactions.len(), 1);
+        assert_eq!(
+            plan.gas,
+            INCREASE_CARDINALITY_BASE_GAS + 100 * GAS_PER_OBSERVATION_SLOT
+        );
+
+        // 10 slots per transaction
+        let max_gas = INCREASE_CARDINALITY_BASE_GAS + 10 * GAS_PER_OBSERVATION_SLOT;
+        let plan = plan_observation_cardinality_increase(1, 26, max_gas).unwrap();
+        assert_eq!(plan.slots, 25);
+        assert_eq!(
+            plan.transactions,
+            [11, 21, 26].map(increase_observation_cardinality_next_call_parameters)
+        );
+        assert_eq!(
+            plan.gas,
+            3 * INCREASE_CARDINALITY_BASE_GAS + 25 * GAS_PER_OBSERVATION_SLOT
+        );
+
+        assert!(matches!(
+            plan_observation_cardinality_increase(1, 26, INCREASE_CARDINALITY_BASE_GAS),
+            Err(Error::GasLimitTooLow)
+        ));
+    }
+
+    #[test]
+    fn test_get_price_divergence_bps() {
+        let tick = |tick: i32| I24::try_from(tick).unwrap();
+        assert_eq!(
+            get_price_divergence_bps(get_sqrt_ratio_at_tick(tick(100)).unwrap(), tick(100))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            get_price_divergence_bps(encode_sqrt_ratio_x96(4, 1), I24::ZERO).unwrap(),
+            30000
+        );
+        assert_eq!(
+            get_price_divergence_bps(encode_sqrt_ratio_x96(1, 4), I24::ZERO).unwrap(),
+            -7500
+        );
+        // a tick is a change of the price of 1 bp
+        assert_eq!(
+            get_price_divergence_bps(get_sqrt_ratio_at_tick(tick(-1)).unwrap(), I24::ZERO).unwrap(),
+            0
+        );
+        assert_eq!(
+            get_price_divergence_bps(get_sqrt_ratio_at_tick(tick(10)).unwrap(), I24::ZERO).unwrap(),
+            10
+        );
+        let divergence = TwapDivergence {
+            divergence_bps: -150,
+            ..Default::default()
+        };
+        assert!(divergence.exceeds(100));
+        assert!(!divergence.exceeds(150));
+    }
+
+    #[tokio::test]
+    async fn test_get_twap_divergence() {
+        let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+        let divergence = get_twap_divergence(pool, 1800, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        let averages = get_time_weighted_averages(pool, 1800, 0, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(
+            divergence.arithmetic_mean_tick,
+            averages.arithmetic_mean_tick
+        );
+        assert!(!divergence.exceeds(500));
+    }
+
+    #[tokio::test]
+    async fn test_get_time_in_range() {
+        let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+        let averages = get_time_weighted_averages(pool, 3600, 0, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        let tick = averages.arithmetic_mean_tick.as_i32();
+        // a wide range around the mean tick is in range the whole hour
+        let time_in_range = get_time_in_range(
+            pool,
+            tick - 10000,
+            tick + 10000,
+            3600,
+            60,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            time_in_range,
+            TimeInRange {
+                seconds_in_range: 3600,
+                seconds_outside: 0,
+            }
+        );
+        // a range above the mean tick is out of range the whole hour
+        let time_in_range = get_time_in_range(
+            pool,
+            tick + 10000,
+            tick + 20000,
+            3600,
+            60,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(time_in_range.seconds_outside, 3600);
+        // the interval must divide the window into at most `MAX_TIME_IN_RANGE_SAMPLES` samples
+        for (seconds_ago, sample_interval) in [(3600, 0), (3600, 7), (86400, 1)] {
+            assert!(matches!(
+                get_time_in_range(
+                    pool,
+                    tick - 10000,
+                    tick + 10000,
+                    seconds_ago,
+                    sample_interval,
+                    PROVIDER.clone(),
+                    *BLOCK_ID,
+                )
+                .await,
+                Err(Error::InvalidInterval)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_observation_cardinality() {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let cardinality =
+            get_observation_cardinality(pool.address(None, None), PROVIDER.clone(), *BLOCK_ID)
+                .await
+                .unwrap();
+        assert!(cardinality.cardinality > 1);
+        assert!(cardinality.cardinality_next >= cardinality.cardinality);
+    }
+
+    #[tokio::test]
+    async fn test_get_time_weighted_averages() {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let averages = get_time_weighted_averages(
+            pool.address(None, None),
+            3600,
+            0,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let tick_current = pool.tick_current.to_i24().as_i32();
+        assert!((averages.arithmetic_mean_tick.as_i32() - tick_current).abs() < 1000);
+        assert!(averages.harmonic_mean_liquidity > 0);
+        averages.price(USDC.clone(), WETH.clone()).unwrap();
+    }
+}
@@ -0,0 +1,818 @@
+//! ## Permit
+//! Fetches the EIP-712 domain and the nonce of an [EIP-2612](https://eips.ethereum.org/EIPS/eip-2612)
+//! or DAI-style token or of a position NFT to build the permit to sign, and with the `signer` feature, signs it
+//! into the [`PermitOptions`] of [`encode_permit`] and of the swap router and position manager
+//! options, or into the [`NFTPermitOptions`] of [`RemoveLiquidityOptions`]. [`verify_permit`] and
+//! [`verify_nft_permit`] check a signed permit before spending gas on it.
+
+use crate::prelude::{Error, *};
+use alloc::{borrow::Cow, string::String};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+    sol,
+};
+use alloy_primitives::{Address, ChainId, PrimitiveSignature, B256, U256};
+use alloy_sol_types::{Eip712Domain, SolStruct};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20PermitToken {
+        function name() external view returns (string memory);
+        function version() external view returns (string memory);
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function eip712Domain()
+            external
+            view
+            returns (
+                bytes1 fields,
+                string memory name,
+                string memory version,
+                uint256 chainId,
+                address verifyingContract,
+                bytes32 salt,
+                uint256[] memory extensions
+            );
+    }
+}
+
+/// Fetches the EIP-712 domain of a token.
+///
+/// The domain is read from the [EIP-5267](https://eips.ethereum.org/EIPS/eip-5267)
+/// `eip712Domain` if the token implements it. Otherwise it is built from the `name` of the token
+/// and its `version`, falling back to the versions "1" and "2", whichever matches the
+/// `DOMAIN_SEPARATOR` of the token.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `chain_id`: The chain id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_erc20_permit_domain<N, P>(
+    token: Address,
+    chain_id: ChainId,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Eip712Domain, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let contract = IERC20PermitToken::new(token, provider);
+    if let Ok(domain) = contract.eip712Domain().block(block_id).call().await {
+        // the bits of `fields` flag which of name, version, chain id, verifying contract and salt
+        // are part of the domain
+        let fields = domain.fields.0[0];
+        return Ok(Eip712Domain::new(
+            (fields & 0x01 != 0).then_some(Cow::Owned(domain.name)),
+            (fields & 0x02 != 0).then_some(Cow::Owned(domain.version)),
+            (fields & 0x04 != 0).then_some(domain.chainId),
+            (fields & 0x08 != 0).then_some(domain.verifyingContract),
+            (fields & 0x10 != 0).then_some(domain.salt),
+        ));
+    }
+    let name = contract.name().block(block_id).call().await?._0;
+    let separator = contract.DOMAIN_SEPARATOR().block(block_id).call().await?._0;
+    // `version` is not part of EIP-2612, so the usual versions are tried against the separator
+    let version = contract.version().block(block_id).call().await.ok();
+    for version in version
+        .map(|version| version._0)
+        .into_iter()
+        .chain([String::from("1"), String::from("2")])
+    {
+        let domain = Eip712Domain::new(
+            Some(Cow::Owned(name.clone())),
+            Some(Cow::Owned(version)),
+            Some(U256::from(chain_id)),
+            Some(token),
+            None,
+        );
+        if domain.separator() == separator {
+            return Ok(domain);
+        }
+    }
+    Err(Error::UnknownDomain)
+}
+
+/// Fetches the next permit nonce of an owner on a token.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_erc20_permit_nonce<N, P>(
+    token: Address,
+    owner: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<U256, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    Ok(IERC20PermitToken::new(token, provider)
+        .nonces(owner)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        ._0)
+}
+
+/// Fetches the domain of a token and the nonce of the owner, and builds the EIP-2612 permit to sign,
+/// see [`get_erc20_permit_data`].
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens, i.e. the signer
+/// * `spender`: The spender to approve, e.g. the swap router or the position manager
+/// * `value`: The amount to approve
+/// * `deadline`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_erc20_permit_data<N, P>(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    value: U256,
+    deadline: U256,
+    chain_id: ChainId,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ERC20PermitData<IERC20Permit::Permit>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let domain = get_erc20_permit_domain(token, chain_id, provider.root(), block_id).await?;
+    let nonce = get_erc20_permit_nonce(token, owner, provider.root(), block_id).await?;
+    Ok(ERC20PermitData {
+        domain,
+        values: IERC20Permit::Permit {
+            owner,
+            spender,
+            value,
+            nonce,
+            deadline,
+        },
+    })
+}
+
+/// Signs an EIP-2612 permit of a token with the current nonce of the signer, ready to be passed to
+/// [`encode_permit`] or as the input token permit of the swap router and position manager options.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `spender`: The spender to approve, e.g. the swap router or the position manager
+/// * `amount`: The amount to approve
+/// * `deadline`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `signer`: The alloy signer of the owner of the tokens
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[cfg(feature = "signer")]
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub async fn sign_erc20_permit<N, P, S>(
+    token: Address,
+    spender: Address,
+    amount: U256,
+    deadline: U256,
+    chain_id: ChainId,
+    signer: &S,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: alloy::signers::Signer + ?Sized,
+{
+    let permit_data = fetch_erc20_permit_data(
+        token,
+        signer.address(),
+        spender,
+        amount,
+        deadline,
+        chain_id,
+        provider,
+        block_id,
+    )
+    .await?;
+    let signature = signer.sign_hash(&permit_data.eip712_signing_hash()).await?;
+    Ok(PermitOptions::Standard(StandardPermitArguments {
+        signature,
+        amount,
+        deadline,
+    }))
+}
+
+/// Fetches the domain of a token and the nonce of the holder, and builds the DAI-style permit to
+/// sign, which approves the spender for an unlimited amount.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `holder`: The holder of the tokens, i.e. the signer
+/// * `spender`: The spender to approve, e.g. the swap router or the position manager
+/// * `expiry`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_allowed_permit_data<N, P>(
+    token: Address,
+    holder: Address,
+    spender: Address,
+    expiry: U256,
+    chain_id: ChainId,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ERC20PermitData<IDaiPermit::Permit>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let domain = get_erc20_permit_domain(token, chain_id, provider.root(), block_id).await?;
+    let nonce = get_erc20_permit_nonce(token, holder, provider.root(), block_id).await?;
+    Ok(ERC20PermitData {
+        domain,
+        values: IDaiPermit::Permit {
+            holder,
+            spender,
+            nonce,
+            expiry,
+            allowed: true,
+        },
+    })
+}
+
+/// Signs a DAI-style permit of a token with the current nonce of the signer, ready to be passed to
+/// [`encode_permit`] or as the input token permit of the swap router and position manager options.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `spender`: The spender to approve, e.g. the swap router or the position manager
+/// * `expiry`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `signer`: The alloy signer of the holder of the tokens
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[cfg(feature = "signer")]
+#[inline]
+pub async fn sign_allowed_permit<N, P, S>(
+    token: Address,
+    spender: Address,
+    expiry: U256,
+    chain_id: ChainId,
+    signer: &S,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: alloy::signers::Signer + ?Sized,
+{
+    let permit_data = fetch_allowed_permit_data(
+        token,
+        signer.address(),
+        spender,
+        expiry,
+        chain_id,
+        provider,
+        block_id,
+    )
+    .await?;
+    let signature = signer.sign_hash(&permit_data.eip712_signing_hash()).await?;
+    Ok(PermitOptions::Allowed(AllowedPermitArguments {
+        signature,
+        nonce: permit_data.values.nonce,
+        expiry,
+    }))
+}
+
+/// Fetches the next permit nonce of a position NFT, which the position manager increments on each
+/// permit.
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_id`: The token id of the position
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_nft_permit_nonce<N, P>(
+    nonfungible_position_manager: Address,
+    token_id: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<U256, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let position =
+        get_nonfungible_position_manager_contract(nonfungible_position_manager, provider)
+            .positions(token_id)
+            .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+            .call()
+            .await?;
+    Ok(U256::from(position.nonce))
+}
+
+/// Fetches the nonce of a position NFT and builds the permit of the position manager to sign, see
+/// [`get_permit_data`].
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `spender`: The spender to approve, e.g. a vault or a migrator
+/// * `token_id`: The token id of the position
+/// * `deadline`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_nft_permit_data<N, P>(
+    nonfungible_position_manager: Address,
+    spender: Address,
+    token_id: U256,
+    deadline: U256,
+    chain_id: ChainId,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<NFTPermitData, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let nonce =
+        get_nft_permit_nonce(nonfungible_position_manager, token_id, provider, block_id).await?;
+    Ok(get_permit_data(
+        NFTPermitValues {
+            spender,
+            tokenId: token_id,
+            nonce,
+            deadline,
+        },
+        nonfungible_position_manager,
+        chain_id,
+    ))
+}
+
+/// Signs the permit of a position NFT with its current nonce, ready to be passed as the permit of
+/// [`RemoveLiquidityOptions`] or used by a contract managing the position.
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `spender`: The spender to approve, e.g. a vault or a migrator
+/// * `token_id`: The token id of the position
+/// * `deadline`: The timestamp until which the permit is valid
+/// * `chain_id`: The chain id
+/// * `signer`: The alloy signer of the owner of the position
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[cfg(feature = "signer")]
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub async fn sign_nft_permit<N, P, S>(
+    nonfungible_position_manager: Address,
+    spender: Address,
+    token_id: U256,
+    deadline: U256,
+    chain_id: ChainId,
+    signer: &S,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<NFTPermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: alloy::signers::Signer + ?Sized,
+{
+    let permit_data = fetch_nft_permit_data(
+        nonfungible_position_manager,
+        spender,
+        token_id,
+        deadline,
+        chain_id,
+        provider,
+        block_id,
+    )
+    .await?;
+    let signature = signer.sign_hash(&permit_data.eip712_signing_hash()).await?;
+    Ok(NFTPermitOptions {
+        signature,
+        deadline,
+        spender,
+    })
+}
+
+/// Recovers the signer of an EIP-712 signing hash and checks that it is the expected one
+fn verify_signer(
+    signature: &PrimitiveSignature,
+    hash: &B256,
+    signer: Address,
+) -> Result<(), PermitError> {
+    let recovered = signature
+        .recover_address_from_prehash(hash)
+        .map_err(|_| PermitError::InvalidSignature)?;
+    if recovered != signer {
+        return Err(PermitError::WrongSigner(recovered));
+    }
+    Ok(())
+}
+
+/// Verifies an EIP-2612 or DAI-style permit of [`encode_permit`] as the token would: the signature
+/// must recover to the owner over the EIP-712 digest rebuilt from the domain and the nonce, and
+/// the permit must not have expired.
+///
+/// ## Arguments
+///
+/// * `options`: The signed permit
+/// * `domain`: The EIP-712 domain of the token, see [`get_erc20_permit_domain`]
+/// * `owner`: The owner of the tokens, i.e. the expected signer
+/// * `spender`: The approved spender, e.g. the swap router or the position manager
+/// * `nonce`: The current permit nonce of the owner, see [`get_erc20_permit_nonce`]
+/// * `timestamp`: The timestamp of the block the permit is expected in
+///
+/// ## Returns
+///
+/// * [`PermitError::Expired`] if the deadline is before the timestamp
+/// * [`PermitError::InvalidNonce`] if the nonce of a DAI-style permit is not the current nonce
+/// * [`PermitError::InvalidSignature`] if no signer can be recovered from the signature
+/// * [`PermitError::WrongSigner`] with the recovered signer if it is not the owner, e.g. if an
+///   EIP-2612 permit was signed with another nonce
+#[inline]
+pub fn verify_permit(
+    options: &PermitOptions,
+    domain: &Eip712Domain,
+    owner: Address,
+    spender: Address,
+    nonce: U256,
+    timestamp: u64,
+) -> Result<(), Error> {
+    let timestamp = U256::from(timestamp);
+    let (signature, hash) = match options {
+        PermitOptions::Standard(args) => {
+            if args.deadline < timestamp {
+                return Err(PermitError::Expired.into());
+            }
+            let permit = IERC20Permit::Permit {
+                owner,
+                spender,
+                value: args.amount,
+                nonce,
+                deadline: args.deadline,
+            };
+            (args.signature, permit.eip712_signing_hash(domain))
+        }
+        PermitOptions::Allowed(args) => {
+            // an expiry of zero never expires
+            if !args.expiry.is_zero() && args.expiry < timestamp {
+                return Err(PermitError::Expired.into());
+            }
+            if args.nonce != nonce {
+                return Err(PermitError::InvalidNonce.into());
+            }
+            let permit = IDaiPermit::Permit {
+                holder: owner,
+                spender,
+                nonce,
+                expiry: args.expiry,
+                allowed: true,
+            };
+            (args.signature, permit.eip712_signing_hash(domain))
+        }
+    };
+    Ok(verify_signer(&signature, &hash, owner)?)
+}
+
+/// Verifies the permit of a position NFT as the position manager would: the signature must
+/// recover to the expected signer over the EIP-712 digest rebuilt from the token id and the nonce,
+/// and the permit must not have expired.
+///
+/// ## Notes
+///
+/// The position manager accepts a permit signed by the owner of the position or by an operator
+/// approved for all its positions, which is not checked here.
+///
+/// ## Arguments
+///
+/// * `options`: The signed permit
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `chain_id`: The chain id
+/// * `token_id`: The token id of the position
+/// * `nonce`: The current permit nonce of the position, see [`get_nft_permit_nonce`]
+/// * `signer`: The expected signer
+/// * `timestamp`: The timestamp of the block the permit is expected in
+///
+/// ## Returns
+///
+/// * [`PermitError::Expired`] if the deadline is before the timestamp
+/// * [`PermitError::InvalidSignature`] if no signer can be recovered from the signature
+/// * [`PermitError::WrongSigner`] with the recovered signer if it is not the expected one, e.g.
+///   if the permit was signed with another nonce
+#[inline]
+pub fn verify_nft_permit(
+    options: &NFTPermitOptions,
+    nonfungible_position_manager: Address,
+    chain_id: ChainId,
+    token_id: U256,
+    nonce: U256,
+    signer: Address,
+    timestamp: u64,
+) -> Result<(), Error> {
+    if options.deadline < U256::from(timestamp) {
+        return Err(PermitError::Expired.into());
+    }
+    let permit_data = get_permit_data(
+        NFTPermitValues {
+            spender: options.spender,
+            tokenId: token_id,
+            nonce,
+            deadline: options.deadline,
+        },
+        nonfungible_position_manager,
+        chain_id,
+    );
+    Ok(verify_signer(
+        &options.signature,
+        &permit_data.eip712_signing_hash(),
+        signer,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+    use alloy_sol_types::eip712_domain;
+
+    #[test]
+    fn test_verify_permit() {
+        use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+        let signer = PrivateKeySigner::random();
+        let owner = signer.address();
+        let spender = address!("0000000000000000000000000000000000000002");
+        let domain = eip712_domain! {
+            name: "USD Coin",
+            version: "2",
+            chain_id: 1,
+            verifying_contract: USDC.address(),
+        };
+        let deadline = U256::from(1000);
+        let hash = IERC20Permit::Permit {
+            owner,
+            spender,
+            value: U256::from(1),
+            nonce: U256::from(5),
+            deadline,
+        }
+        .eip712_signing_hash(&domain);
+        let options = PermitOptions::Standard(StandardPermitArguments {
+            signature: signer.sign_hash_sync(&hash).unwrap(),
+            amount: U256::from(1),
+            deadline,
+        });
+        verify_permit(&options, &domain, owner, spender, U256::from(5), 1000).unwrap();
+        assert!(matches!(
+            verify_permit(&options, &domain, owner, spender, U256::from(5), 1001),
+            Err(Error::PermitError(PermitError::Expired))
+        ));
+        // a stale nonce or another spender changes the digest
+        for (spender, nonce) in [(spender, U256::from(6)), (owner, U256::from(5))] {
+            assert!(matches!(
+                verify_permit(&options, &domain, owner, spender, nonce, 0),
+                Err(Error::PermitError(PermitError::WrongSigner(recovered))) if recovered != owner
+            ));
+        }
+
+        let hash = IDaiPermit::Permit {
+            holder: owner,
+            spender,
+            nonce: U256::from(5),
+            expiry: U256::ZERO,
+            allowed: true,
+        }
+        .eip712_signing_hash(&domain);
+        let options = PermitOptions::Allowed(AllowedPermitArguments {
+            signature: signer.sign_hash_sync(&hash).unwrap(),
+            nonce: U256::from(5),
+            expiry: U256::ZERO,
+        });
+        verify_permit(&options, &domain, owner, spender, U256::from(5), u64::MAX).unwrap();
+        assert!(matches!(
+            verify_permit(&options, &domain, owner, spender, U256::from(4), 0),
+            Err(Error::PermitError(PermitError::InvalidNonce))
+        ));
+    }
+
+    #[test]
+    fn test_verify_nft_permit() {
+        use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+        let signer = PrivateKeySigner::random();
+        let npm = MAINNET_ADDRESSES.nonfungible_position_manager;
+        let spender = address!("0000000000000000000000000000000000000002");
+        let (token_id, nonce, deadline) = (U256::from(4), U256::from(1), U256::from(1000));
+        let permit_data = get_permit_data(
+            NFTPermitValues {
+                spender,
+                tokenId: token_id,
+                nonce,
+                deadline,
+            },
+            npm,
+            1,
+        );
+        let options = NFTPermitOptions {
+            signature: signer
+                .sign_hash_sync(&permit_data.eip712_signing_hash())
+                .unwrap(),
+            deadline,
+            spender,
+        };
+        verify_nft_permit(&options, npm, 1, token_id, nonce, signer.address(), 1000).unwrap();
+        assert!(matches!(
+            verify_nft_permit(&options, npm, 1, token_id, nonce, signer.address(), 1001),
+            Err(Error::PermitError(PermitError::Expired))
+        ));
+        assert!(matches!(
+            verify_nft_permit(&options, npm, 1, token_id, nonce, spender, 0),
+            Err(Error::PermitError(PermitError::WrongSigner(recovered)))
+                if recovered == signer.address()
+        ));
+        assert!(matches!(
+            verify_nft_permit(&options, npm, 10, token_id, nonce, signer.address(), 0),
+            Err(Error::PermitError(PermitError::WrongSigner(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_erc20_permit_domain() {
+        // USDC does not implement EIP-5267 and its version is "2"
+        let domain = get_erc20_permit_domain(USDC.address(), 1, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(
+            domain,
+            eip712_domain! {
+                name: "USD Coin",
+                version: "2",
+                chain_id: 1,
+                verifying_contract: USDC.address(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_erc20_permit_data() {
+        let owner = address!("0000000000000000000000000000000000000001");
+        let permit_data = fetch_erc20_permit_data(
+            USDC.address(),
+            owner,
+            FACTORY_ADDRESS,
+            U256::from(1),
+            U256::MAX,
+            1,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(permit_data.values.owner, owner);
+        assert_eq!(permit_data.values.nonce, U256::ZERO);
+        assert_eq!(
+            permit_data.domain.separator(),
+            IERC20PermitToken::new(USDC.address(), PROVIDER.clone())
+                .DOMAIN_SEPARATOR()
+                .block(BLOCK_ID.unwrap())
+                .call()
+                .await
+                .unwrap()
+                ._0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_allowed_permit_data() {
+        let holder = address!("0000000000000000000000000000000000000001");
+        let permit_data = fetch_allowed_permit_data(
+            DAI.address(),
+            holder,
+            FACTORY_ADDRESS,
+            U256::MAX,
+            1,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(permit_data.values.holder, holder);
+        assert_eq!(permit_data.values.nonce, U256::ZERO);
+        assert!(permit_data.values.allowed);
+        assert_eq!(
+            permit_data.domain,
+            eip712_domain! {
+                name: "Dai Stablecoin",
+                version: "1",
+                chain_id: 1,
+                verifying_contract: DAI.address(),
+            }
+        );
+    }
+
+    #[cfg(feature = "signer")]
+    #[tokio::test]
+    async fn test_sign_erc20_permit() {
+        use alloy::signers::{local::PrivateKeySigner, Signer};
+
+        let signer = PrivateKeySigner::random();
+        let PermitOptions::Standard(args) = sign_erc20_permit(
+            USDC.address(),
+            FACTORY_ADDRESS,
+            U256::from(1),
+            U256::MAX,
+            1,
+            &signer,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap() else {
+            panic!("expected a standard permit");
+        };
+        let permit_data = fetch_erc20_permit_data(
+            USDC.address(),
+            signer.address(),
+            FACTORY_ADDRESS,
+            U256::from(1),
+            U256::MAX,
+            1,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            args.signature
+                .recover_address_from_prehash(&permit_data.eip712_signing_hash())
+                .unwrap(),
+            signer.address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_nft_permit_data() {
+        let npm = MAINNET_ADDRESSES.nonfungible_position_manager;
+        let token_id = U256::from(4);
+        let permit_data = fetch_nft_permit_data(
+            npm,
+            FACTORY_ADDRESS,
+            token_id,
+            U256::MAX,
+            1,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            permit_data.values.nonce,
+            get_nft_permit_nonce(npm, token_id, PROVIDER.clone(), *BLOCK_ID)
+                .await
+                .unwrap()
+        );
+        assert_eq!(permit_data.values.tokenId, token_id);
+        // the domain matches the one of the position manager
+        assert_eq!(
+            permit_data.domain.separator(),
+            IERC20PermitToken::new(npm, PROVIDER.clone())
+                .DOMAIN_SEPARATOR()
+                .block(BLOCK_ID.unwrap())
+                .call()
+                .await
+                .unwrap()
+                ._0
+        );
+    }
+}
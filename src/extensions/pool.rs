@@ -3,16 +3,21 @@
 //! liquidity map within a tick range for the specified pool using an [ephemeral contract](https://github.com/Aperture-Finance/Aperture-Lens/blob/904101e4daed59e02fd4b758b98b0749e70b583b/contracts/EphemeralGetPopulatedTicksInRange.sol)
 //! in a single `eth_call`.
 
+use super::multicall3::decode_returns;
 use crate::prelude::*;
+use alloc::vec::Vec;
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
     network::Network,
     providers::Provider,
 };
 use alloy_primitives::{Address, ChainId, B256};
+use alloy_sol_types::SolCall;
+use futures::stream::{self, StreamExt};
 use uniswap_lens::{
     bindings::{
-        ierc20metadata::IERC20Metadata, iuniswapv3pool::IUniswapV3Pool::IUniswapV3PoolInstance,
+        ierc20metadata::IERC20Metadata,
+        iuniswapv3pool::IUniswapV3Pool::{self, IUniswapV3PoolInstance},
     },
     pool_lens,
 };
@@ -20,6 +25,7 @@ use uniswap_sdk_core::{prelude::Token, token};
 
 #[inline]
 pub fn get_pool_contract<N, P>(
+    chain_id: ChainId,
     factory: Address,
     token_a: Address,
     token_b: Address,
@@ -31,7 +37,7 @@ where
     P: Provider<N>,
 {
     IUniswapV3PoolInstance::new(
-        compute_pool_address(factory, token_a, token_b, fee, None, None),
+        compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id)),
         provider,
     )
 }
@@ -49,6 +55,10 @@ impl Pool {
     /// * `provider`: The alloy provider
     /// * `block_id`: Optional block number to query.
     #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(%token_a, %token_b, ?fee, ?block_id))
+    )]
     pub async fn from_pool_key<N, P>(
         chain_id: ChainId,
         factory: Address,
@@ -63,7 +73,10 @@ impl Pool {
         P: Provider<N>,
     {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
-        let pool_contract = get_pool_contract(factory, token_a, token_b, fee, provider.root());
+        let pool_contract = IUniswapV3PoolInstance::new(
+            compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id)),
+            provider.root(),
+        );
         let token_a_contract = IERC20Metadata::new(token_a, provider.root());
         let token_b_contract = IERC20Metadata::new(token_b, provider.root());
         // TODO: use multicall
@@ -76,10 +89,9 @@ impl Pool {
         let token_b_name = token_b_contract.name().block(block_id).call().await?._0;
         let token_b_symbol = token_b_contract.symbol().block(block_id).call().await?._0;
         let sqrt_price_x96 = slot_0.sqrtPriceX96;
-        assert!(
-            !sqrt_price_x96.is_zero(),
-            "Pool has been created but not yet initialized"
-        );
+        if sqrt_price_x96.is_zero() {
+            return Err(Error::PoolNotInitialized);
+        }
         Self::new(
             token!(
                 chain_id,
@@ -100,6 +112,91 @@ impl Pool {
             liquidity,
         )
     }
+
+    /// Get [`Pool`] structs from many pool keys at once, batching the pool states and the token
+    /// metadata into a single `eth_call` to [Multicall3](MULTICALL3_ADDRESS)
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address
+    /// * `pool_keys`: The tokens and fee tier of each pool
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query.
+    ///
+    /// ## Returns
+    ///
+    /// The pools in the order of the pool keys
+    #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(pools = pool_keys.len(), ?block_id))
+    )]
+    pub async fn from_pool_keys<N, P>(
+        chain_id: ChainId,
+        factory: Address,
+        pool_keys: &[(Address, Address, FeeAmount)],
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<Self>, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        // fetch the metadata of each token once even if it is shared by several pools
+        let mut token_addresses: Vec<Address> = Vec::new();
+        for &(token_a, token_b, _) in pool_keys {
+            for token in [token_a, token_b] {
+                if !token_addresses.contains(&token) {
+                    token_addresses.push(token);
+                }
+            }
+        }
+        let mut calls = Vec::with_capacity(2 * pool_keys.len() + 3 * token_addresses.len());
+        for &(token_a, token_b, fee) in pool_keys {
+            let pool = compute_pool_address(factory, token_a, token_b, fee, None, Some(chain_id));
+            calls.push((pool, IUniswapV3Pool::slot0Call {}.abi_encode().into()));
+            calls.push((pool, IUniswapV3Pool::liquidityCall {}.abi_encode().into()));
+        }
+        for &token in &token_addresses {
+            calls.push((token, IERC20Metadata::decimalsCall {}.abi_encode().into()));
+            calls.push((token, IERC20Metadata::nameCall {}.abi_encode().into()));
+            calls.push((token, IERC20Metadata::symbolCall {}.abi_encode().into()));
+        }
+        let results = multicall3_aggregate(calls, provider, block_id).await?;
+        let (pool_results, token_results) = results.split_at(2 * pool_keys.len());
+
+        let mut tokens = Vec::with_capacity(token_addresses.len());
+        for (&address, results) in token_addresses.iter().zip(token_results.chunks_exact(3)) {
+            let decimals = decode_returns::<IERC20Metadata::decimalsCall>(&results[0])?._0;
+            let name = decode_returns::<IERC20Metadata::nameCall>(&results[1])?._0;
+            let symbol = decode_returns::<IERC20Metadata::symbolCall>(&results[2])?._0;
+            tokens.push(token!(chain_id, address, decimals, symbol, name));
+        }
+        let token = |address: Address| {
+            tokens[token_addresses.iter().position(|&a| a == address).unwrap()].clone()
+        };
+
+        pool_keys
+            .iter()
+            .zip(pool_results.chunks_exact(2))
+            .map(|(&(token_a, token_b, fee), results)| {
+                let sqrt_price_x96 =
+                    decode_returns::<IUniswapV3Pool::slot0Call>(&results[0])?.sqrtPriceX96;
+                let liquidity = decode_returns::<IUniswapV3Pool::liquidityCall>(&results[1])?._0;
+                if sqrt_price_x96.is_zero() {
+                    return Err(Error::PoolNotInitialized);
+                }
+                Self::new(
+                    token(token_a),
+                    token(token_b),
+                    fee,
+                    sqrt_price_x96,
+                    liquidity,
+                )
+            })
+            .collect()
+    }
 }
 
 impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
@@ -146,6 +243,10 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
     /// }
     /// ```
     #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(%token_a, %token_b, ?fee, ?block_id))
+    )]
     pub async fn from_pool_key_with_tick_data_provider<N, P>(
         chain_id: ChainId,
         factory: Address,
@@ -170,7 +271,7 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
         )
         .await?;
         let tick_data_provider = EphemeralTickMapDataProvider::new(
-            pool.address(None, None),
+            pool.address(None, Some(factory)),
             provider,
             None,
             None,
@@ -186,6 +287,52 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
             tick_data_provider,
         )
     }
+
+    /// Get [`Pool`] structs with tick data providers from many pool keys concurrently, sharing
+    /// the provider. See [`Pool::from_pool_key_with_tick_data_provider`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address
+    /// * `pool_keys`: The tokens and fee tier of each pool
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query.
+    /// * `concurrency`: The maximum number of pools fetched at a time
+    ///
+    /// ## Returns
+    ///
+    /// The result for each pool in the order of the pool keys, so that a pool that fails to be
+    /// fetched does not fail the others
+    #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(pools = pool_keys.len(), ?block_id, concurrency = concurrency))
+    )]
+    pub async fn from_pool_keys_with_tick_data_provider<N, P>(
+        chain_id: ChainId,
+        factory: Address,
+        pool_keys: &[(Address, Address, FeeAmount)],
+        provider: P,
+        block_id: Option<BlockId>,
+        concurrency: usize,
+    ) -> Vec<Result<Self, Error>>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        assert!(concurrency > 0, "CONCURRENCY");
+        let provider = &provider;
+        stream::iter(pool_keys)
+            .map(move |&(token_a, token_b, fee)| {
+                Self::from_pool_key_with_tick_data_provider::<N, _>(
+                    chain_id, factory, token_a, token_b, fee, provider, block_id,
+                )
+            })
+            .buffered(concurrency)
+            .collect()
+            .await
+    }
 }
 
 /// Normalizes the specified tick range.
@@ -333,6 +480,56 @@ mod tests {
         assert_eq!(pool.liquidity, 786352807736110014);
     }
 
+    #[tokio::test]
+    async fn test_from_pool_keys() {
+        const WBTC: Address = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+        const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let pools = Pool::from_pool_keys(
+            1,
+            FACTORY_ADDRESS,
+            &[
+                (WBTC, WETH, FeeAmount::LOW),
+                (WETH, WBTC, FeeAmount::MEDIUM),
+            ],
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0], pool().await);
+        assert_eq!(pools[1].fee, FeeAmount::MEDIUM);
+        assert_eq!(pools[1].token0, pools[0].token0);
+        assert_eq!(pools[1].token1, pools[0].token1);
+    }
+
+    #[tokio::test]
+    async fn test_from_pool_keys_with_tick_data_provider() {
+        const WBTC: Address = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+        const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let pools = Pool::<EphemeralTickMapDataProvider>::from_pool_keys_with_tick_data_provider(
+            1,
+            FACTORY_ADDRESS,
+            &[
+                (WBTC, WETH, FeeAmount::LOW),
+                (WETH, WBTC, FeeAmount::MEDIUM),
+                (WBTC, Address::ZERO, FeeAmount::LOW),
+            ],
+            PROVIDER.clone(),
+            *BLOCK_ID,
+            2,
+        )
+        .await;
+        assert_eq!(pools.len(), 3);
+        let pool0 = pools[0].as_ref().unwrap();
+        assert_eq!(pool0.tick_current.as_i32(), pool().await.tick_current);
+        assert_eq!(pool0.fee, FeeAmount::LOW);
+        assert!(!pool0.tick_data_provider.tick_map.inner.is_empty());
+        assert_eq!(pools[1].as_ref().unwrap().fee, FeeAmount::MEDIUM);
+        // the pool of a token without code does not exist
+        assert!(pools[2].is_err());
+    }
+
     #[tokio::test]
     async fn test_get_liquidity_array_for_pool() {
         let pool = pool().await;
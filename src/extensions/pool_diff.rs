@@ -0,0 +1,296 @@
+//! ## Pool Diff
+//! Fetches the state of a pool and its initialized ticks at two blocks and summarizes the changes
+//! between them, e.g. for monitoring a pool or studying the liquidity added and removed around an
+//! event.
+
+use super::multicall3::decode_returns;
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{aliases::I24, Address, U160, U256};
+use alloy_sol_types::SolCall;
+use core::cmp::Ordering;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+
+/// The state of a pool at a block, see [`get_pool_state`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolState {
+    pub sqrt_price_x96: U160,
+    pub tick: I24,
+    pub liquidity: u128,
+    pub fee_growth_global0_x128: U256,
+    pub fee_growth_global1_x128: U256,
+    /// The initialized ticks, sorted by index
+    pub ticks: Vec<Tick<I24>>,
+}
+
+/// The changes of the state of a pool between two blocks, see [`PoolDiff::new`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolDiff {
+    pub sqrt_price_x96_before: U160,
+    pub sqrt_price_x96_after: U160,
+    pub tick_before: I24,
+    pub tick_after: I24,
+    pub liquidity_before: u128,
+    pub liquidity_after: u128,
+    /// The fee growth of token0 per unit of liquidity between the blocks, see [`fee_growth_sub`]
+    pub fee_growth_global0_delta_x128: U256,
+    /// The fee growth of token1 per unit of liquidity between the blocks, see [`fee_growth_sub`]
+    pub fee_growth_global1_delta_x128: U256,
+    /// The ticks initialized after the first block, as of the second block
+    pub ticks_initialized: Vec<Tick<I24>>,
+    /// The ticks cleared after the first block, as of the first block
+    pub ticks_cleared: Vec<Tick<I24>>,
+    /// The ticks initialized at both blocks whose liquidity changed, before and after
+    pub ticks_updated: Vec<(Tick<I24>, Tick<I24>)>,
+}
+
+impl PoolDiff {
+    /// Computes the changes between two states of a pool
+    ///
+    /// ## Arguments
+    ///
+    /// * `before`: The state at the first block
+    /// * `after`: The state at the second block
+    #[inline]
+    #[must_use]
+    pub fn new(before: &PoolState, after: &PoolState) -> Self {
+        let mut ticks_initialized = Vec::new();
+        let mut ticks_cleared = Vec::new();
+        let mut ticks_updated = Vec::new();
+        let (mut old, mut new) = (
+            before.ticks.iter().peekable(),
+            after.ticks.iter().peekable(),
+        );
+        loop {
+            match (old.peek(), new.peek()) {
+                (Some(&&a), Some(&&b)) => match a.index.cmp(&b.index) {
+                    Ordering::Less => {
+                        ticks_cleared.push(a);
+                        old.next();
+                    }
+                    Ordering::Greater => {
+                        ticks_initialized.push(b);
+                        new.next();
+                    }
+                    Ordering::Equal => {
+                        if a != b {
+                            ticks_updated.push((a, b));
+                        }
+                        old.next();
+                        new.next();
+                    }
+                },
+                (Some(&&a), None) => {
+                    ticks_cleared.push(a);
+                    old.next();
+                }
+                (None, Some(&&b)) => {
+                    ticks_initialized.push(b);
+                    new.next();
+                }
+                (None, None) => break,
+            }
+        }
+        Self {
+            sqrt_price_x96_before: before.sqrt_price_x96,
+            sqrt_price_x96_after: after.sqrt_price_x96,
+            tick_before: before.tick,
+            tick_after: after.tick,
+            liquidity_before: before.liquidity,
+            liquidity_after: after.liquidity,
+            fee_growth_global0_delta_x128: fee_growth_sub(
+                after.fee_growth_global0_x128,
+                before.fee_growth_global0_x128,
+            ),
+            fee_growth_global1_delta_x128: fee_growth_sub(
+                after.fee_growth_global1_x128,
+                before.fee_growth_global1_x128,
+            ),
+            ticks_initialized,
+            ticks_cleared,
+            ticks_updated,
+        }
+    }
+
+    /// The change of the current tick
+    #[inline]
+    #[must_use]
+    pub fn tick_change(&self) -> I24 {
+        self.tick_after - self.tick_before
+    }
+
+    /// The change of the in-range liquidity
+    #[inline]
+    #[must_use]
+    pub const fn liquidity_change(&self) -> i128 {
+        self.liquidity_after as i128 - self.liquidity_before as i128
+    }
+
+    /// Whether the state of the pool is unchanged, i.e. no swap nor liquidity change happened
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sqrt_price_x96_before == self.sqrt_price_x96_after
+            && self.liquidity_before == self.liquidity_after
+            && self.fee_growth_global0_delta_x128.is_zero()
+            && self.fee_growth_global1_delta_x128.is_zero()
+            && self.ticks_initialized.is_empty()
+            && self.ticks_cleared.is_empty()
+            && self.ticks_updated.is_empty()
+    }
+}
+
+/// Fetches the state of a pool and all its initialized ticks at a block
+///
+/// ## Arguments
+///
+/// * `pool`: The pool address
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_pool_state<N, P>(
+    pool: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PoolState, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let results = multicall3_aggregate(
+        [
+            (pool, IUniswapV3Pool::slot0Call {}.abi_encode()),
+            (pool, IUniswapV3Pool::liquidityCall {}.abi_encode()),
+            (
+                pool,
+                IUniswapV3Pool::feeGrowthGlobal0X128Call {}.abi_encode(),
+            ),
+            (
+                pool,
+                IUniswapV3Pool::feeGrowthGlobal1X128Call {}.abi_encode(),
+            ),
+        ]
+        .map(|(target, call_data)| (target, call_data.into())),
+        provider.root(),
+        block_id,
+    )
+    .await?;
+    let slot0 = decode_returns::<IUniswapV3Pool::slot0Call>(&results[0])?;
+    let ticks = EphemeralTickDataProvider::<I24>::new(pool, provider, None, None, block_id)
+        .await?
+        .ticks;
+    Ok(PoolState {
+        sqrt_price_x96: slot0.sqrtPriceX96,
+        tick: slot0.tick,
+        liquidity: decode_returns::<IUniswapV3Pool::liquidityCall>(&results[1])?._0,
+        fee_growth_global0_x128: decode_returns::<IUniswapV3Pool::feeGrowthGlobal0X128Call>(
+            &results[2],
+        )?
+        ._0,
+        fee_growth_global1_x128: decode_returns::<IUniswapV3Pool::feeGrowthGlobal1X128Call>(
+            &results[3],
+        )?
+        ._0,
+        ticks,
+    })
+}
+
+/// Fetches the state of a pool at two blocks and computes the changes between them
+///
+/// ## Arguments
+///
+/// * `pool`: The pool address
+/// * `provider`: The alloy provider
+/// * `block_before`: The first block
+/// * `block_after`: The second block
+#[inline]
+pub async fn get_pool_diff<N, P>(
+    pool: Address,
+    provider: P,
+    block_before: BlockId,
+    block_after: BlockId,
+) -> Result<PoolDiff, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let before = get_pool_state(pool, provider.root(), Some(block_before)).await?;
+    let after = get_pool_state(pool, provider, Some(block_after)).await?;
+    Ok(PoolDiff::new(&before, &after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    fn tick(index: i32, liquidity_gross: u128, liquidity_net: i128) -> Tick<I24> {
+        Tick::new(
+            I24::try_from(index).unwrap(),
+            liquidity_gross,
+            liquidity_net,
+        )
+    }
+
+    #[test]
+    fn test_pool_diff() {
+        let before = PoolState {
+            sqrt_price_x96: encode_sqrt_ratio_x96(1, 1),
+            tick: I24::ZERO,
+            liquidity: 1000,
+            fee_growth_global0_x128: U256::MAX,
+            fee_growth_global1_x128: U256::from(5),
+            ticks: vec![
+                tick(-120, 500, 500),
+                tick(-60, 1000, 1000),
+                tick(60, 1000, -1000),
+                tick(120, 500, -500),
+            ],
+        };
+        let after = PoolState {
+            sqrt_price_x96: encode_sqrt_ratio_x96(4, 1),
+            tick: I24::try_from(13863).unwrap(),
+            liquidity: 200,
+            fee_growth_global0_x128: U256::from(2),
+            fee_growth_global1_x128: U256::from(5),
+            ticks: vec![
+                tick(-60, 1200, 1200),
+                tick(60, 1000, -1000),
+                tick(120, 500, -500),
+                tick(13800, 200, -200),
+            ],
+        };
+        let diff = PoolDiff::new(&before, &after);
+        assert_eq!(diff.tick_change(), I24::try_from(13863).unwrap());
+        assert_eq!(diff.liquidity_change(), -800);
+        // the fee growth wraps around
+        assert_eq!(diff.fee_growth_global0_delta_x128, U256::from(3));
+        assert_eq!(diff.fee_growth_global1_delta_x128, U256::ZERO);
+        assert_eq!(diff.ticks_initialized, vec![tick(13800, 200, -200)]);
+        assert_eq!(diff.ticks_cleared, vec![tick(-120, 500, 500)]);
+        assert_eq!(
+            diff.ticks_updated,
+            vec![(tick(-60, 1000, 1000), tick(-60, 1200, 1200))]
+        );
+        assert!(!diff.is_empty());
+        assert!(PoolDiff::new(&after, &after).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_pool_diff() {
+        let diff = get_pool_diff(
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"),
+            PROVIDER.clone(),
+            BlockId::from(16_999_000),
+            BLOCK_ID.unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_ne!(diff.sqrt_price_x96_before, diff.sqrt_price_x96_after);
+        assert!(!diff.fee_growth_global0_delta_x128.is_zero());
+        assert!(!diff.fee_growth_global1_delta_x128.is_zero());
+        assert!(!diff.is_empty());
+    }
+}
@@ -0,0 +1,475 @@
+//! ## Pool Events
+//! Decoders of the logs emitted by a pool into typed events, and [`Pool::apply_event`] to advance
+//! the state of a pool with them, e.g. to follow a pool from its logs without polling its state.
+
+use crate::prelude::{Error, *};
+use alloy::contract::Error as ContractError;
+use alloy_primitives::{aliases::I24, Address, Log, I256, U160, U256};
+use alloy_sol_types::SolEvent;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+
+/// An `Initialize` log of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitializeLog {
+    pub sqrt_price_x96: U160,
+    pub tick: I24,
+}
+
+/// A `Mint` log of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintLog {
+    pub sender: Address,
+    pub owner: Address,
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    /// The liquidity minted
+    pub amount: u128,
+    pub amount0: U256,
+    pub amount1: U256,
+}
+
+/// A `Burn` log of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BurnLog {
+    pub owner: Address,
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    /// The liquidity burned
+    pub amount: u128,
+    pub amount0: U256,
+    pub amount1: U256,
+}
+
+/// A `Swap` log of a pool, see [`SwapEvent`] for a swap replayed by [`backtest`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapLog {
+    pub sender: Address,
+    pub recipient: Address,
+    /// The amount of token0 into the pool, negative if out of the pool
+    pub amount0: I256,
+    /// The amount of token1 into the pool, negative if out of the pool
+    pub amount1: I256,
+    /// The sqrt price of the pool after the swap
+    pub sqrt_price_x96: U160,
+    /// The active liquidity of the pool after the swap
+    pub liquidity: u128,
+    /// The tick of the pool after the swap
+    pub tick: I24,
+}
+
+/// A `Collect` log of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollectLog {
+    pub owner: Address,
+    pub recipient: Address,
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    pub amount0: u128,
+    pub amount1: u128,
+}
+
+/// A `Flash` log of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlashLog {
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount0: U256,
+    pub amount1: U256,
+    /// The amount of token0 paid back, including the fee
+    pub paid0: U256,
+    /// The amount of token1 paid back, including the fee
+    pub paid1: U256,
+}
+
+/// An event emitted by a pool that changes its state or moves tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolEvent {
+    Initialize(InitializeLog),
+    Mint(MintLog),
+    Burn(BurnLog),
+    Swap(SwapLog),
+    Collect(CollectLog),
+    Flash(FlashLog),
+}
+
+impl PoolEvent {
+    /// Decodes a log emitted by a pool, e.g. the `inner` log of an RPC log
+    ///
+    /// ## Arguments
+    ///
+    /// * `log`: The log to decode
+    ///
+    /// ## Returns
+    ///
+    /// The event, or [`Error::UnknownEvent`] if the log is not one of the events above
+    #[inline]
+    pub fn decode_log(log: &Log) -> Result<Self, Error> {
+        fn decode<E: SolEvent>(log: &Log) -> Result<E, Error> {
+            E::decode_log_data(&log.data, true)
+                .map_err(|e| Error::ContractError(ContractError::AbiError(e.into())))
+        }
+
+        let Some(&topic0) = log.topics().first() else {
+            return Err(Error::UnknownEvent);
+        };
+        Ok(match topic0 {
+            IUniswapV3Pool::Initialize::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Initialize = decode(log)?;
+                Self::Initialize(InitializeLog {
+                    sqrt_price_x96: event.sqrtPriceX96,
+                    tick: event.tick,
+                })
+            }
+            IUniswapV3Pool::Mint::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Mint = decode(log)?;
+                Self::Mint(MintLog {
+                    sender: event.sender,
+                    owner: event.owner,
+                    tick_lower: event.tickLower,
+                    tick_upper: event.tickUpper,
+                    amount: event.amount,
+                    amount0: event.amount0,
+                    amount1: event.amount1,
+                })
+            }
+            IUniswapV3Pool::Burn::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Burn = decode(log)?;
+                Self::Burn(BurnLog {
+                    owner: event.owner,
+                    tick_lower: event.tickLower,
+                    tick_upper: event.tickUpper,
+                    amount: event.amount,
+                    amount0: event.amount0,
+                    amount1: event.amount1,
+                })
+            }
+            IUniswapV3Pool::Swap::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Swap = decode(log)?;
+                Self::Swap(SwapLog {
+                    sender: event.sender,
+                    recipient: event.recipient,
+                    amount0: event.amount0,
+                    amount1: event.amount1,
+                    sqrt_price_x96: event.sqrtPriceX96,
+                    liquidity: event.liquidity,
+                    tick: event.tick,
+                })
+            }
+            IUniswapV3Pool::Collect::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Collect = decode(log)?;
+                Self::Collect(CollectLog {
+                    owner: event.owner,
+                    recipient: event.recipient,
+                    tick_lower: event.tickLower,
+                    tick_upper: event.tickUpper,
+                    amount0: event.amount0,
+                    amount1: event.amount1,
+                })
+            }
+            IUniswapV3Pool::Flash::SIGNATURE_HASH => {
+                let event: IUniswapV3Pool::Flash = decode(log)?;
+                Self::Flash(FlashLog {
+                    sender: event.sender,
+                    recipient: event.recipient,
+                    amount0: event.amount0,
+                    amount1: event.amount1,
+                    paid0: event.paid0,
+                    paid1: event.paid1,
+                })
+            }
+            _ => return Err(Error::UnknownEvent),
+        })
+    }
+}
+
+impl<TP: TickDataProvider> Pool<TP> {
+    /// Advances the price, the in-range liquidity and the current tick of the pool by an event,
+    /// as the pool contract does when emitting it.
+    ///
+    /// ## Notes
+    ///
+    /// The ticks of the tick data provider are not updated by mints and burns, see
+    /// [`Pool::apply_event_with_ticks`] for a pool backed by a [`TickMap`]. `Collect` and `Flash`
+    /// events leave the state unchanged.
+    ///
+    /// ## Arguments
+    ///
+    /// * `event`: The event emitted by the pool
+    #[inline]
+    pub fn apply_event(&mut self, event: &PoolEvent) -> Result<(), Error> {
+        match event {
+            PoolEvent::Initialize(initialize) => {
+                self.sqrt_ratio_x96 = initialize.sqrt_price_x96;
+                self.tick_current = TP::Index::from_i24(initialize.tick);
+            }
+            PoolEvent::Swap(swap) => {
+                self.sqrt_ratio_x96 = swap.sqrt_price_x96;
+                self.liquidity = swap.liquidity;
+                self.tick_current = TP::Index::from_i24(swap.tick);
+            }
+            PoolEvent::Mint(mint) => {
+                self.add_in_range_liquidity(mint.tick_lower, mint.tick_upper, mint.amount, false)?;
+            }
+            PoolEvent::Burn(burn) => {
+                self.add_in_range_liquidity(burn.tick_lower, burn.tick_upper, burn.amount, true)?;
+            }
+            PoolEvent::Collect(_) | PoolEvent::Flash(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Adds the liquidity of a mint to the in-range liquidity, or removes that of a burn
+    fn add_in_range_liquidity(
+        &mut self,
+        tick_lower: I24,
+        tick_upper: I24,
+        amount: u128,
+        burn: bool,
+    ) -> Result<(), Error> {
+        let tick_current = self.tick_current.to_i24();
+        if tick_lower <= tick_current && tick_current < tick_upper {
+            self.liquidity = add_delta(self.liquidity, liquidity_delta(amount, burn)?)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: TickIndex> Pool<TickMap<I>> {
+    /// Advances the state of the pool by an event as [`Pool::apply_event`], and updates the ticks
+    /// of the tick map by mints and burns
+    ///
+    /// ## Arguments
+    ///
+    /// * `event`: The event emitted by the pool
+    #[inline]
+    pub fn apply_event_with_ticks(&mut self, event: &PoolEvent) -> Result<(), Error> {
+        self.apply_event(event)?;
+        let (tick_lower, tick_upper, delta) = match event {
+            PoolEvent::Mint(mint) => (
+                mint.tick_lower,
+                mint.tick_upper,
+                liquidity_delta(mint.amount, false)?,
+            ),
+            PoolEvent::Burn(burn) => (
+                burn.tick_lower,
+                burn.tick_upper,
+                liquidity_delta(burn.amount, true)?,
+            ),
+            _ => return Ok(()),
+        };
+        let tick_map = &mut self.tick_data_provider;
+        update_tick(tick_map, I::from_i24(tick_lower), delta, false)?;
+        update_tick(tick_map, I::from_i24(tick_upper), delta, true)
+    }
+}
+
+/// The signed liquidity delta of a mint, or of a burn
+fn liquidity_delta(amount: u128, burn: bool) -> Result<i128, Error> {
+    let delta = i128::try_from(amount).map_err(|_| Error::AddDeltaOverflow)?;
+    Ok(if burn { -delta } else { delta })
+}
+
+/// Updates a tick by a liquidity delta as `Tick.update` of the pool contract, flipping its bit in
+/// the tick bitmap when it is initialized or cleared
+fn update_tick<I: TickIndex>(
+    tick_map: &mut TickMap<I>,
+    index: I,
+    liquidity_delta: i128,
+    upper: bool,
+) -> Result<(), Error> {
+    let tick = tick_map
+        .inner
+        .get(&index)
+        .copied()
+        .unwrap_or_else(|| Tick::new(index, 0, 0));
+    let liquidity_gross = add_delta(tick.liquidity_gross, liquidity_delta)?;
+    let liquidity_net = if upper {
+        tick.liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        tick.liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or(Error::AddDeltaOverflow)?;
+    if (liquidity_gross == 0) != (tick.liquidity_gross == 0) {
+        let (word_pos, bit_pos) = index.compress(tick_map.tick_spacing).position();
+        let word = tick_map.bitmap.entry(word_pos).or_insert(U256::ZERO);
+        *word ^= U256::from(1) << bit_pos;
+        if word.is_zero() {
+            tick_map.bitmap.remove(&word_pos);
+        }
+    }
+    if liquidity_gross == 0 {
+        tick_map.inner.remove(&index);
+    } else {
+        tick_map
+            .inner
+            .insert(index, Tick::new(index, liquidity_gross, liquidity_net));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::LogData;
+
+    fn log<E: SolEvent>(event: &E) -> Log {
+        Log {
+            address: Address::ZERO,
+            data: event.encode_log_data(),
+        }
+    }
+
+    fn mint(tick_lower: i32, tick_upper: i32, amount: u128) -> PoolEvent {
+        PoolEvent::Mint(MintLog {
+            sender: Address::ZERO,
+            owner: Address::ZERO,
+            tick_lower: I24::try_from(tick_lower).unwrap(),
+            tick_upper: I24::try_from(tick_upper).unwrap(),
+            amount,
+            amount0: U256::ZERO,
+            amount1: U256::ZERO,
+        })
+    }
+
+    fn burn(tick_lower: i32, tick_upper: i32, amount: u128) -> PoolEvent {
+        PoolEvent::Burn(BurnLog {
+            owner: Address::ZERO,
+            tick_lower: I24::try_from(tick_lower).unwrap(),
+            tick_upper: I24::try_from(tick_upper).unwrap(),
+            amount,
+            amount0: U256::ZERO,
+            amount1: U256::ZERO,
+        })
+    }
+
+    fn tick_map_pool() -> Pool<TickMap<i32>> {
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            1000,
+            TickMap::new(
+                vec![
+                    Tick::new(-887220, 1000, 1000),
+                    Tick::new(887220, 1000, -1000),
+                ],
+                60,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let event = IUniswapV3Pool::Mint {
+            sender: Address::ZERO,
+            owner: Address::ZERO,
+            tickLower: I24::try_from(-60).unwrap(),
+            tickUpper: I24::from_limbs([60]),
+            amount: 100,
+            amount0: U256::from(1),
+            amount1: U256::from(2),
+        };
+        assert!(matches!(
+            PoolEvent::decode_log(&log(&event)).unwrap(),
+            PoolEvent::Mint(mint) if mint.amount == 100 && mint.amount1 == U256::from(2)
+        ));
+        let event = IUniswapV3Pool::Swap {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: I256::ONE,
+            amount1: I256::MINUS_ONE,
+            sqrtPriceX96: encode_sqrt_ratio_x96(1, 1),
+            liquidity: 100,
+            tick: I24::ZERO,
+        };
+        assert_eq!(
+            PoolEvent::decode_log(&log(&event)).unwrap(),
+            PoolEvent::Swap(SwapLog {
+                sender: Address::ZERO,
+                recipient: Address::ZERO,
+                amount0: I256::ONE,
+                amount1: I256::MINUS_ONE,
+                sqrt_price_x96: encode_sqrt_ratio_x96(1, 1),
+                liquidity: 100,
+                tick: I24::ZERO,
+            })
+        );
+        let event = IUniswapV3Pool::Flash {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: U256::from(10),
+            amount1: U256::ZERO,
+            paid0: U256::from(1),
+            paid1: U256::ZERO,
+        };
+        assert!(matches!(
+            PoolEvent::decode_log(&log(&event)).unwrap(),
+            PoolEvent::Flash(flash) if flash.paid0 == U256::from(1)
+        ));
+        let unknown = Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(vec![], Default::default()),
+        };
+        assert!(matches!(
+            PoolEvent::decode_log(&unknown),
+            Err(Error::UnknownEvent)
+        ));
+    }
+
+    #[test]
+    fn test_apply_event() {
+        let mut pool = tick_map_pool();
+        // in range
+        pool.apply_event_with_ticks(&mint(-60, 60, 500)).unwrap();
+        assert_eq!(pool.liquidity, 1500);
+        let ticks = &pool.tick_data_provider;
+        assert_eq!(ticks.get_tick(-60).unwrap().liquidity_net, 500);
+        assert_eq!(ticks.get_tick(60).unwrap().liquidity_net, -500);
+        // out of range
+        pool.apply_event_with_ticks(&mint(60, 120, 500)).unwrap();
+        assert_eq!(pool.liquidity, 1500);
+        let ticks = &pool.tick_data_provider;
+        assert_eq!(ticks.get_tick(60).unwrap().liquidity_net, 0);
+        assert_eq!(ticks.get_tick(60).unwrap().liquidity_gross, 1000);
+        // the lower tick is cleared
+        pool.apply_event_with_ticks(&burn(-60, 60, 500)).unwrap();
+        assert_eq!(pool.liquidity, 1000);
+        let ticks = &pool.tick_data_provider;
+        assert!(ticks.get_tick(-60).is_err());
+        assert_eq!(
+            ticks
+                .next_initialized_tick_within_one_word(-1, true, 60)
+                .unwrap(),
+            (-15360, false)
+        );
+        assert_eq!(
+            ticks
+                .next_initialized_tick_within_one_word(0, false, 60)
+                .unwrap(),
+            (60, true)
+        );
+
+        pool.apply_event(&PoolEvent::Swap(SwapLog {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: I256::MINUS_ONE,
+            amount1: I256::ONE,
+            sqrt_price_x96: get_sqrt_ratio_at_tick(I24::from_limbs([90])).unwrap(),
+            liquidity: 1500,
+            tick: I24::from_limbs([90]),
+        }))
+        .unwrap();
+        assert_eq!(pool.tick_current, 90);
+        assert_eq!(pool.liquidity, 1500);
+        // out of range, so the in-range liquidity is unchanged
+        pool.apply_event(&burn(-60, 60, 2000)).unwrap();
+        assert_eq!(pool.liquidity, 1500);
+        assert!(matches!(
+            pool.apply_event(&burn(60, 120, 2000)),
+            Err(Error::AddDeltaOverflow)
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+//! ## Pool Launch
+//! A builder of the calldata that creates and initializes a pool at a human-readable price and
+//! mints its first position in a single multicall of the nonfungible position manager, refusing to
+//! mint if the pool already exists at a price too far from the intended one.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{Address, U160, U256};
+use num_traits::Signed;
+use uniswap_sdk_core::prelude::*;
+
+/// Builds the calldata for launching a pool and seeding it with liquidity, see
+/// [`PoolLaunchBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolLaunchBuilder {
+    token0: Token,
+    token1: Token,
+    fee: FeeAmount,
+    sqrt_price_x96: U160,
+    existing_sqrt_price_x96: Option<U160>,
+    price_tolerance: Percent,
+}
+
+impl PoolLaunchBuilder {
+    /// Creates a builder for a pool initialized at a price, which is parsed by [`parse_price`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `base_token`: The base token of the price
+    /// * `quote_token`: The quote token of the price
+    /// * `fee`: The fee tier of the pool
+    /// * `price`: The amount of `quote_token` that is worth the same as 1 `base_token`
+    #[inline]
+    pub fn new(
+        base_token: Token,
+        quote_token: Token,
+        fee: FeeAmount,
+        price: &str,
+    ) -> Result<Self, Error> {
+        let price = parse_price(base_token.clone(), quote_token.clone(), price)?;
+        let (token0, token1, sqrt_price_x96) = if base_token.sorts_before(&quote_token)? {
+            (
+                base_token,
+                quote_token,
+                encode_sqrt_ratio_x96(price.numerator, price.denominator),
+            )
+        } else {
+            (
+                quote_token,
+                base_token,
+                encode_sqrt_ratio_x96(price.denominator, price.numerator),
+            )
+        };
+        Ok(Self {
+            token0,
+            token1,
+            fee,
+            sqrt_price_x96,
+            existing_sqrt_price_x96: None,
+            price_tolerance: Percent::new(1, 100),
+        })
+    }
+
+    /// Sets the price of the pool if it already exists, in which case the position is minted at
+    /// this price instead of the intended one.
+    #[inline]
+    #[must_use]
+    pub const fn existing_pool_price(mut self, sqrt_price_x96: U160) -> Self {
+        self.existing_sqrt_price_x96 = Some(sqrt_price_x96);
+        self
+    }
+
+    /// Sets how far the price of an existing pool may deviate from the intended price, by default
+    /// 1%.
+    #[inline]
+    #[must_use]
+    pub fn price_tolerance(mut self, price_tolerance: Percent) -> Self {
+        self.price_tolerance = price_tolerance;
+        self
+    }
+
+    /// The square root of the intended price of token1 in token0 as a Q64.96
+    #[inline]
+    #[must_use]
+    pub const fn sqrt_price_x96(&self) -> U160 {
+        self.sqrt_price_x96
+    }
+
+    /// Returns the pool at the price of the existing pool if set, otherwise at the intended price.
+    ///
+    /// ## Returns
+    ///
+    /// [`Error::InvalidPrice`] if the price of the existing pool deviates from the intended price by
+    /// more than the tolerance
+    #[inline]
+    pub fn pool(&self) -> Result<Pool, Error> {
+        let sqrt_price_x96 = match self.existing_sqrt_price_x96 {
+            Some(existing) => {
+                let intended = self.sqrt_price_x96.to_big_int().pow(2);
+                let existing_price = existing.to_big_int().pow(2);
+                let deviation = (existing_price - &intended).abs();
+                if deviation * &self.price_tolerance.denominator
+                    > intended * &self.price_tolerance.numerator
+                {
+                    return Err(Error::InvalidPrice);
+                }
+                existing
+            }
+            None => self.sqrt_price_x96,
+        };
+        Pool::new(
+            self.token0.clone(),
+            self.token1.clone(),
+            self.fee,
+            sqrt_price_x96,
+            0,
+        )
+    }
+
+    /// Produces the calldata that creates and initializes the pool if necessary and mints the
+    /// position with the maximum liquidity for the amounts, see [`add_call_parameters`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    /// * `amount0`: The maximum amount of token0 to deposit
+    /// * `amount1`: The maximum amount of token1 to deposit
+    /// * `recipient`: The account that should receive the minted NFT
+    /// * `options`: Options for the calldata, whose specific options are replaced by a mint to
+    ///   `recipient` that creates the pool
+    #[inline]
+    pub fn build(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0: U256,
+        amount1: U256,
+        recipient: Address,
+        mut options: AddLiquidityOptions,
+    ) -> Result<MethodParameters, Error> {
+        let mut position =
+            Position::from_amounts(self.pool()?, tick_lower, tick_upper, amount0, amount1, true)?;
+        options.specific_opts = AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+            recipient,
+            create_pool: true,
+        });
+        add_call_parameters(&mut position, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    fn options() -> AddLiquidityOptions {
+        AddLiquidityOptions {
+            slippage_tolerance: Percent::new(1, 100),
+            deadline: U256::from(123),
+            use_native: None,
+            token0_permit: None,
+            token1_permit: None,
+            specific_opts: AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions {
+                token_id: U256::from(1),
+            }),
+            chain_id: None,
+            deployment: None,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let builder =
+            PoolLaunchBuilder::new(TOKEN0.clone(), TOKEN1.clone(), FeeAmount::MEDIUM, "4").unwrap();
+        assert_eq!(builder.sqrt_price_x96(), encode_sqrt_ratio_x96(4, 1));
+        // the price of token1 in token0 is inverted
+        let builder =
+            PoolLaunchBuilder::new(TOKEN1.clone(), TOKEN0.clone(), FeeAmount::MEDIUM, "4").unwrap();
+        assert_eq!(builder.sqrt_price_x96(), encode_sqrt_ratio_x96(1, 4));
+        assert!(
+            PoolLaunchBuilder::new(TOKEN0.clone(), TOKEN1.clone(), FeeAmount::MEDIUM, "x").is_err()
+        );
+    }
+
+    #[test]
+    fn test_build() {
+        let builder =
+            PoolLaunchBuilder::new(TOKEN0.clone(), TOKEN1.clone(), FeeAmount::MEDIUM, "1").unwrap();
+        let MethodParameters { calldata, .. } = builder
+            .build(
+                -60,
+                60,
+                U256::from(1000),
+                U256::from(1000),
+                RECIPIENT,
+                options(),
+            )
+            .unwrap();
+        let calls = decode_position_manager_call(calldata).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0],
+            PositionManagerCall::CreateAndInitializePoolIfNecessary {
+                token0: TOKEN0.address(),
+                token1: TOKEN1.address(),
+                fee: FeeAmount::MEDIUM,
+                sqrt_price_x96: encode_sqrt_ratio_x96(1, 1),
+            }
+        );
+        let PositionManagerCall::Mint(params) = &calls[1] else {
+            panic!("expected a mint");
+        };
+        assert_eq!(params.recipient, RECIPIENT);
+        assert!(params.amount0Desired <= U256::from(1000));
+        assert!(params.amount1Desired <= U256::from(1000));
+    }
+
+    #[test]
+    fn test_existing_pool_price() {
+        let builder =
+            PoolLaunchBuilder::new(TOKEN0.clone(), TOKEN1.clone(), FeeAmount::MEDIUM, "1").unwrap();
+        // a price 0.5% away is within the default tolerance
+        let pool = builder
+            .clone()
+            .existing_pool_price(encode_sqrt_ratio_x96(1005, 1000))
+            .pool()
+            .unwrap();
+        assert_eq!(pool.sqrt_ratio_x96, encode_sqrt_ratio_x96(1005, 1000));
+        // a price 5% away is not
+        let builder = builder.existing_pool_price(encode_sqrt_ratio_x96(105, 100));
+        assert!(matches!(builder.pool(), Err(Error::InvalidPrice)));
+        assert!(matches!(
+            builder.build(
+                -60,
+                60,
+                U256::from(1000),
+                U256::from(1000),
+                RECIPIENT,
+                options()
+            ),
+            Err(Error::InvalidPrice)
+        ));
+        let builder = builder.price_tolerance(Percent::new(10, 100));
+        assert!(builder.pool().is_ok());
+    }
+}
@@ -0,0 +1,244 @@
+//! ## Pool TVL
+//! Computes the total value locked in a pool by summing the token amounts of the liquidity between
+//! every pair of consecutive initialized ticks, not just the liquidity in range.
+
+use crate::prelude::{Error, *};
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{Address, ChainId, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// The amounts of token0 and token1 locked in the liquidity of a pool, see [`get_pool_tvl`].
+#[derive(Clone, Debug)]
+pub struct PoolTvl {
+    pub amount0: CurrencyAmount<Token>,
+    pub amount1: CurrencyAmount<Token>,
+}
+
+impl PoolTvl {
+    /// Values the locked amounts in a numeraire.
+    ///
+    /// ## Arguments
+    ///
+    /// * `price0`: The price of token0 in the numeraire
+    /// * `price1`: The price of token1 in the numeraire, e.g. the identity price to value the
+    ///   amounts in token1 with the [`Pool::token0_price`] as `price0`
+    #[inline]
+    pub fn value<TNumeraire: BaseCurrency>(
+        &self,
+        price0: &Price<Token, TNumeraire>,
+        price1: &Price<Token, TNumeraire>,
+    ) -> Result<CurrencyAmount<TNumeraire>, Error> {
+        Ok(price0
+            .quote(&self.amount0)?
+            .add(&price1.quote(&self.amount1)?)?)
+    }
+}
+
+/// Computes the amounts of token0 and token1 locked in the liquidity of a pool at its current
+/// price, walking all the initialized ticks of its tick data provider from the lowest to the
+/// highest.
+///
+/// ## Notes
+///
+/// The amounts are rounded down in each range between two initialized ticks and exclude the fees
+/// owed to positions and the protocol, so the token balances of the pool are slightly higher.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool with a tick data provider of all its initialized ticks, e.g. a [`TickMap`]
+#[inline]
+pub fn get_pool_tvl<TP: TickDataProvider>(pool: &Pool<TP>) -> Result<PoolTvl, Error> {
+    let tick_spacing = pool.tick_spacing();
+    let min_tick = nearest_usable_tick(TP::Index::from_i24(MIN_TICK), tick_spacing);
+    let max_tick = nearest_usable_tick(TP::Index::from_i24(MAX_TICK), tick_spacing);
+    let mut amount0 = U256::ZERO;
+    let mut amount1 = U256::ZERO;
+    let mut liquidity = 0_u128;
+    let mut tick_lower = min_tick;
+    let mut tick = min_tick - TP::Index::ONE;
+    while tick < max_tick {
+        let (tick_next, initialized) = pool
+            .tick_data_provider
+            .next_initialized_tick_within_one_word(tick, false, tick_spacing)?;
+        let tick_next = tick_next.min(max_tick);
+        if initialized {
+            if liquidity > 0 {
+                // the amounts of the liquidity between the previous and the next initialized tick
+                let sqrt_ratio_lower_x96 = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+                let sqrt_ratio_upper_x96 = get_sqrt_ratio_at_tick(tick_next.to_i24())?;
+                if pool.tick_current < tick_lower {
+                    amount0 += get_amount_0_delta(
+                        sqrt_ratio_lower_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?;
+                } else if pool.tick_current < tick_next {
+                    amount0 += get_amount_0_delta(
+                        pool.sqrt_ratio_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?;
+                    amount1 += get_amount_1_delta(
+                        sqrt_ratio_lower_x96,
+                        pool.sqrt_ratio_x96,
+                        liquidity,
+                        false,
+                    )?;
+                } else {
+                    amount1 += get_amount_1_delta(
+                        sqrt_ratio_lower_x96,
+                        sqrt_ratio_upper_x96,
+                        liquidity,
+                        false,
+                    )?;
+                }
+            }
+            let liquidity_net = pool.tick_data_provider.get_tick(tick_next)?.liquidity_net;
+            liquidity = add_delta(liquidity, liquidity_net)?;
+            tick_lower = tick_next;
+        }
+        tick = tick_next;
+    }
+    Ok(PoolTvl {
+        amount0: CurrencyAmount::from_raw_amount(pool.token0.clone(), amount0.to_big_int())?,
+        amount1: CurrencyAmount::from_raw_amount(pool.token1.clone(), amount1.to_big_int())?,
+    })
+}
+
+/// Fetches a pool with all its initialized ticks and computes its TVL, see [`get_pool_tvl`].
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `factory`: The factory address
+/// * `token_a`: One of the tokens in the pool
+/// * `token_b`: The other token in the pool
+/// * `fee`: Fee tier of the pool
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_pool_tvl<N, P>(
+    chain_id: ChainId,
+    factory: Address,
+    token_a: Address,
+    token_b: Address,
+    fee: FeeAmount,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PoolTvl, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let pool = Pool::<EphemeralTickMapDataProvider>::from_pool_key_with_tick_data_provider(
+        chain_id, factory, token_a, token_b, fee, provider, block_id,
+    )
+    .await?;
+    get_pool_tvl(&pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use num_traits::Signed;
+
+    fn pool_with_positions() -> Pool<TickListDataProvider> {
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            3_000_000,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(-1200, 1_000_000, 1_000_000),
+                    Tick::new(-600, 2_000_000, 2_000_000),
+                    Tick::new(600, 2_000_000, -2_000_000),
+                    Tick::new(1200, 1_000_000, -1_000_000),
+                    Tick::new(1800, 5_000_000, 5_000_000),
+                    Tick::new(2400, 5_000_000, -5_000_000),
+                ],
+                60,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_pool_tvl_full_range() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let tvl = get_pool_tvl(&pool).unwrap();
+        let position = Position::new(
+            pool.clone(),
+            pool.liquidity,
+            nearest_usable_tick(MIN_TICK_I32, 60),
+            nearest_usable_tick(MAX_TICK_I32, 60),
+        );
+        assert_eq!(
+            tvl.amount0.quotient(),
+            position.amount0().unwrap().quotient()
+        );
+        assert_eq!(
+            tvl.amount1.quotient(),
+            position.amount1().unwrap().quotient()
+        );
+    }
+
+    #[test]
+    fn test_get_pool_tvl() {
+        let pool = pool_with_positions();
+        let tvl = get_pool_tvl(&pool).unwrap();
+        let mut amount0 = BigInt::ZERO;
+        let mut amount1 = BigInt::ZERO;
+        for (liquidity, tick_lower, tick_upper) in [
+            (1_000_000, -1200, 1200),
+            (2_000_000, -600, 600),
+            (5_000_000, 1800, 2400),
+        ] {
+            let position = Position::new(pool.clone(), liquidity, tick_lower, tick_upper);
+            amount0 += position.amount0().unwrap().quotient();
+            amount1 += position.amount1().unwrap().quotient();
+        }
+        // the out of range position is included, up to rounding in each range
+        let error = |a: BigInt, b: BigInt| (a - b).abs() <= BigInt::from(4);
+        assert!(error(tvl.amount0.quotient(), amount0));
+        assert!(error(tvl.amount1.quotient(), amount1));
+        assert!(tvl.amount0.quotient() > tvl.amount1.quotient());
+    }
+
+    #[test]
+    fn test_value() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let tvl = get_pool_tvl(&pool).unwrap();
+        let value = tvl
+            .value(
+                &pool.token0_price(),
+                &Price::new(TOKEN1.clone(), TOKEN1.clone(), 1, 1),
+            )
+            .unwrap();
+        assert_eq!(
+            value.quotient(),
+            tvl.amount0.quotient() + tvl.amount1.quotient()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pool_tvl() {
+        let tvl = fetch_pool_tvl(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(tvl.amount0.quotient() > BigInt::ZERO);
+        assert!(tvl.amount1.quotient() > BigInt::ZERO);
+    }
+}
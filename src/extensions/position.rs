@@ -3,28 +3,35 @@
 //! and pool for all positions of the specified owner by deploying an ephemeral contract via
 //! `eth_call`, etc.
 
+use super::multicall3::decode_returns;
 use crate::prelude::{Error, *};
+use alloc::vec::Vec;
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
     network::Network,
     providers::Provider,
 };
 use alloy_primitives::{Address, ChainId, U256};
+use alloy_sol_types::SolCall;
 use anyhow::Result;
 use base64::{engine::general_purpose, Engine};
+#[cfg(feature = "bigdecimal")]
 use num_bigint::ToBigInt;
 use uniswap_lens::{
     bindings::{
         ephemeralallpositionsbyowner::EphemeralAllPositionsByOwner,
         ephemeralgetposition::EphemeralGetPosition,
         iuniswapv3nonfungiblepositionmanager::IUniswapV3NonfungiblePositionManager::{
-            positionsReturn, IUniswapV3NonfungiblePositionManagerInstance,
+            self, positionsReturn, IUniswapV3NonfungiblePositionManagerInstance,
         },
     },
     position_lens,
 };
 use uniswap_sdk_core::{prelude::*, token};
 
+/// The maximum number of calls batched in a single Multicall3 call
+const MULTICALL_CHUNK_SIZE: usize = 500;
+
 #[inline]
 pub const fn get_nonfungible_position_manager_contract<N, P>(
     nonfungible_position_manager: Address,
@@ -118,14 +125,7 @@ impl Position {
         N: Network,
         P: Provider<N>,
     {
-        let EphemeralGetPosition::PositionState {
-            position,
-            slot0,
-            activeLiquidity: active_liquidity,
-            decimals0,
-            decimals1,
-            ..
-        } = position_lens::get_position_details(
+        let state = position_lens::get_position_details(
             nonfungible_position_manager,
             token_id,
             provider,
@@ -133,22 +133,87 @@ impl Position {
         )
         .await
         .map_err(Error::LensError)?;
-        let pool = Pool::new(
-            token!(chain_id, position.token0, decimals0),
-            token!(chain_id, position.token1, decimals1),
-            position.fee.into(),
-            slot0.sqrtPriceX96,
-            active_liquidity,
-        )?;
-        Ok(Self::new(
-            pool,
-            position.liquidity,
-            position.tickLower.as_i32(),
-            position.tickUpper.as_i32(),
-        ))
+        position_from_state(chain_id, &state)
     }
 }
 
+/// A [`Position`] hydrated together with its owner and uncollected fees.
+#[derive(Clone, Debug)]
+pub struct HydratedPosition {
+    /// The token id of the position
+    pub token_id: U256,
+    /// The owner of the position
+    pub owner: Address,
+    /// The position, including the pool state at the queried block
+    pub position: Position,
+    /// The amount of token0 collectable, including uncollected fees
+    pub tokens_owed0: U256,
+    /// The amount of token1 collectable, including uncollected fees
+    pub tokens_owed1: U256,
+}
+
+/// Get the position, the pool's `slot0` and active liquidity, and the collectable token amounts
+/// for the token id in a single `eth_call` by deploying an ephemeral contract.
+///
+/// The ephemeral lens settles the fees accrued since the last poke, so the returned tokens owed
+/// match what `collect` would pay out at the queried block.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_id`: The token id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn hydrate_position<N, P>(
+    chain_id: ChainId,
+    nonfungible_position_manager: Address,
+    token_id: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<HydratedPosition, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let state = position_lens::get_position_details(
+        nonfungible_position_manager,
+        token_id,
+        provider,
+        block_id,
+    )
+    .await
+    .map_err(Error::LensError)?;
+    Ok(HydratedPosition {
+        token_id,
+        owner: state.owner,
+        position: position_from_state(chain_id, &state)?,
+        tokens_owed0: U256::from(state.position.tokensOwed0),
+        tokens_owed1: U256::from(state.position.tokensOwed1),
+    })
+}
+
+/// Builds a [`Position`] from the position state returned by the ephemeral position lens
+fn position_from_state(
+    chain_id: ChainId,
+    state: &EphemeralGetPosition::PositionState,
+) -> Result<Position, Error> {
+    let pool = Pool::new(
+        token!(chain_id, state.position.token0, state.decimals0),
+        token!(chain_id, state.position.token1, state.decimals1),
+        state.position.fee.into(),
+        state.slot0.sqrtPriceX96,
+        state.activeLiquidity,
+    )?;
+    Ok(Position::new(
+        pool,
+        state.position.liquidity,
+        state.position.tickLower.as_i32(),
+        state.position.tickUpper.as_i32(),
+    ))
+}
+
 impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
     /// Get a [`Position`] struct from the token id with tick data provider in a single call
     ///
@@ -204,7 +269,7 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
             pool,
             position.liquidity,
             position.tick_lower.try_into().unwrap(),
-            position.tick_upper.try_into().unwrap(),
+            position.tick_upper,
         ))
     }
 }
@@ -244,6 +309,178 @@ where
     .map_err(Error::LensError)
 }
 
+/// Get all positions of the specified owner with the token metadata of their pools resolved,
+/// batching the `tokenOfOwnerByIndex` and `positions` calls via Multicall3 in chunks of at most 500
+/// calls.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `owner`: The owner address
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The positions in the order of `tokenOfOwnerByIndex`
+#[inline]
+pub async fn get_positions_for_owner<N, P>(
+    chain_id: ChainId,
+    nonfungible_position_manager: Address,
+    owner: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Vec<Position>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let balance =
+        get_nonfungible_position_manager_contract(nonfungible_position_manager, provider.root())
+            .balanceOf(owner)
+            .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+            .call()
+            .await?
+            .balance;
+    let balance = usize::try_from(balance).map_err(|_| Error::InvalidAmounts)?;
+
+    let mut token_ids = Vec::new();
+    for start in (0..balance).step_by(MULTICALL_CHUNK_SIZE) {
+        let end = balance.min(start.saturating_add(MULTICALL_CHUNK_SIZE));
+        for data in multicall3_aggregate(
+            (start..end).map(|index| {
+                (
+                    nonfungible_position_manager,
+                    IUniswapV3NonfungiblePositionManager::tokenOfOwnerByIndexCall {
+                        owner,
+                        index: U256::from(index),
+                    }
+                    .abi_encode()
+                    .into(),
+                )
+            }),
+            provider.root(),
+            block_id,
+        )
+        .await?
+        {
+            token_ids.push(
+                decode_returns::<IUniswapV3NonfungiblePositionManager::tokenOfOwnerByIndexCall>(
+                    &data,
+                )?
+                .tokenId,
+            );
+        }
+    }
+
+    get_positions(
+        chain_id,
+        nonfungible_position_manager,
+        &token_ids,
+        provider,
+        block_id,
+    )
+    .await
+}
+
+/// Get the positions of many token ids with the token metadata and state of their pools resolved,
+/// batching the `factory` and `positions` calls in Multicall3 calls of at most 500 calls each and
+/// the pool state and token metadata of the distinct pools in another.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_ids`: The token ids of the positions
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The positions in the order of `token_ids`
+#[inline]
+pub async fn get_positions<N, P>(
+    chain_id: ChainId,
+    nonfungible_position_manager: Address,
+    token_ids: &[U256],
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Vec<Position>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if token_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut factory = Address::ZERO;
+    let mut positions: Vec<positionsReturn> = Vec::with_capacity(token_ids.len());
+    for (i, chunk) in token_ids.chunks(MULTICALL_CHUNK_SIZE).enumerate() {
+        // The factory is fetched along with the first chunk of positions.
+        let results = multicall3_aggregate(
+            (i == 0)
+                .then(|| IUniswapV3NonfungiblePositionManager::factoryCall {}.abi_encode())
+                .into_iter()
+                .chain(chunk.iter().map(|&token_id| {
+                    IUniswapV3NonfungiblePositionManager::positionsCall { tokenId: token_id }
+                        .abi_encode()
+                }))
+                .map(|call_data| (nonfungible_position_manager, call_data.into())),
+            provider.root(),
+            block_id,
+        )
+        .await?;
+        let results = if i == 0 {
+            factory =
+                decode_returns::<IUniswapV3NonfungiblePositionManager::factoryCall>(&results[0])?
+                    ._0;
+            &results[1..]
+        } else {
+            &results[..]
+        };
+        for data in results {
+            positions.push(decode_returns::<
+                IUniswapV3NonfungiblePositionManager::positionsCall,
+            >(data)?);
+        }
+    }
+
+    let mut pool_keys = Vec::new();
+    for position in &positions {
+        let pool_key = (
+            position.token0,
+            position.token1,
+            FeeAmount::from(position.fee),
+        );
+        if !pool_keys.contains(&pool_key) {
+            pool_keys.push(pool_key);
+        }
+    }
+    let pools = Pool::from_pool_keys(chain_id, factory, &pool_keys, provider, block_id).await?;
+    positions
+        .into_iter()
+        .map(|position| {
+            let pool_key = (
+                position.token0,
+                position.token1,
+                FeeAmount::from(position.fee),
+            );
+            let pool = pool_keys
+                .iter()
+                .position(|key| key == &pool_key)
+                .and_then(|index| pools.get(index))
+                .ok_or(Error::PoolNotFound)?;
+            Ok(Position::new(
+                pool.clone(),
+                position.liquidity,
+                position.tickLower.as_i32(),
+                position.tickUpper.as_i32(),
+            ))
+        })
+        .collect()
+}
+
 /// Get the real-time collectable token amounts.
 ///
 /// ## Arguments
@@ -259,7 +496,7 @@ where
 /// A tuple of the collectable token amounts.
 #[inline]
 pub async fn get_collectable_token_amounts<N, P>(
-    _chain_id: ChainId,
+    chain_id: ChainId,
     nonfungible_position_manager: Address,
     token_id: U256,
     provider: P,
@@ -280,6 +517,7 @@ where
         .call()
         .await?;
     let pool_contract = get_pool_contract(
+        chain_id,
         factory,
         position.token0,
         position.token1,
@@ -304,43 +542,28 @@ where
         .block(block_id_)
         .call()
         .await?;
-    let fee_growth_outside_0x128_lower = tick_info_lower.feeGrowthOutside0X128;
-    let fee_growth_outside_1x128_lower = tick_info_lower.feeGrowthOutside1X128;
     let tick_info_upper = pool_contract
         .ticks(position.tickUpper)
         .block(block_id_)
         .call()
         .await?;
-    let fee_growth_outside_0x128_upper = tick_info_upper.feeGrowthOutside0X128;
-    let fee_growth_outside_1x128_upper = tick_info_upper.feeGrowthOutside1X128;
-
-    // https://github.com/Uniswap/v4-core/blob/f630c8ca8c669509d958353200953762fd15761a/contracts/libraries/Pool.sol#L566
-    let (fee_growth_inside_0x128, fee_growth_inside_1x128) = if tick < position.tickLower {
-        (
-            fee_growth_outside_0x128_lower - fee_growth_outside_0x128_upper,
-            fee_growth_outside_1x128_lower - fee_growth_outside_1x128_upper,
-        )
-    } else if tick >= position.tickUpper {
-        (
-            fee_growth_outside_0x128_upper - fee_growth_outside_0x128_lower,
-            fee_growth_outside_1x128_upper - fee_growth_outside_1x128_lower,
-        )
-    } else {
-        (
-            fee_growth_global_0x128
-                - fee_growth_outside_0x128_lower
-                - fee_growth_outside_0x128_upper,
-            fee_growth_global_1x128
-                - fee_growth_outside_1x128_lower
-                - fee_growth_outside_1x128_upper,
-        )
-    };
-    let (tokens_owed_0, tokens_owed_1) = get_tokens_owed(
+    let (tokens_owed_0, tokens_owed_1) = get_unclaimed_fees(
+        position.liquidity,
         position.feeGrowthInside0LastX128,
         position.feeGrowthInside1LastX128,
-        position.liquidity,
-        fee_growth_inside_0x128,
-        fee_growth_inside_1x128,
+        FeeGrowthOutside {
+            fee_growth_outside0_x128: tick_info_lower.feeGrowthOutside0X128,
+            fee_growth_outside1_x128: tick_info_lower.feeGrowthOutside1X128,
+        },
+        FeeGrowthOutside {
+            fee_growth_outside0_x128: tick_info_upper.feeGrowthOutside0X128,
+            fee_growth_outside1_x128: tick_info_upper.feeGrowthOutside1X128,
+        },
+        position.tickLower,
+        position.tickUpper,
+        tick,
+        fee_growth_global_0x128,
+        fee_growth_global_1x128,
     );
     Ok((
         U256::from(position.tokensOwed0) + tokens_owed_0,
@@ -382,6 +605,104 @@ where
     Ok(image[1..image.len() - 1].to_string())
 }
 
+/// The metadata of a position NFT, see [`get_position_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionMetadata {
+    pub name: String,
+    pub description: String,
+    /// The data URI of the image
+    pub image: String,
+    /// The image decoded from its data URI, an SVG for the positions of Uniswap V3
+    pub svg: String,
+    /// The attributes as `(trait_type, value)` pairs, or the `key: value` lines of the description
+    /// if the metadata has no attributes, e.g. the pool and token addresses, the fee tier and the
+    /// token id
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Decodes the metadata of a position NFT from its `tokenURI`, a base64 encoded JSON data URI
+/// whose image is a base64 encoded SVG data URI.
+///
+/// ## Arguments
+///
+/// * `token_uri`: The token URI of the position
+#[inline]
+pub fn decode_token_uri(token_uri: &str) -> Result<PositionMetadata> {
+    let json = general_purpose::STANDARD.decode(
+        token_uri
+            .strip_prefix("data:application/json;base64,")
+            .ok_or_else(|| anyhow::anyhow!("Invalid token URI"))?,
+    )?;
+    let json = serde_json::from_slice::<serde_json::Value>(&json)?;
+    let field = |key: &str| -> Result<String> {
+        Ok(json
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing {key}"))?
+            .to_string())
+    };
+    let (name, description, image) = (field("name")?, field("description")?, field("image")?);
+    let svg = match image.split_once(";base64,") {
+        Some((_, data)) => String::from_utf8(general_purpose::STANDARD.decode(data)?)?,
+        None => String::new(),
+    };
+    let attributes = match json.get("attributes").and_then(serde_json::Value::as_array) {
+        Some(attributes) => attributes
+            .iter()
+            .filter_map(|attribute| {
+                let trait_type = attribute.get("trait_type")?.as_str()?.to_string();
+                let value = match attribute.get("value")? {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                Some((trait_type, value))
+            })
+            .collect(),
+        None => description
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(": ")?;
+                Some((key.trim().to_string(), value.trim().to_string()))
+            })
+            .collect(),
+    };
+    Ok(PositionMetadata {
+        name,
+        description,
+        image,
+        svg,
+        attributes,
+    })
+}
+
+/// Fetches the `tokenURI` of a position NFT and decodes its metadata, see [`decode_token_uri`].
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_id`: The token id
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_position_metadata<N, P>(
+    nonfungible_position_manager: Address,
+    token_id: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PositionMetadata>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let uri = get_nonfungible_position_manager_contract(nonfungible_position_manager, provider)
+        .tokenURI(token_id)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        ._0;
+    decode_token_uri(&uri)
+}
+
 /// Predict the position after rebalance assuming the pool price remains the same.
 ///
 /// ## Arguments
@@ -389,6 +710,7 @@ where
 /// * `position`: Position info before rebalance.
 /// * `new_tick_lower`: The new lower tick.
 /// * `new_tick_upper`: The new upper tick.
+#[cfg(feature = "bigdecimal")]
 #[inline]
 pub fn get_rebalanced_position<TP>(
     position: &mut Position<TP>,
@@ -405,11 +727,7 @@ where
         .add(&position.amount1_cached()?)?;
     let equity_before = fraction_to_big_decimal(&equity_in_token1_before);
     let price = fraction_to_big_decimal(&price);
-    let token0_ratio = token0_price_to_ratio(
-        price.clone(),
-        new_tick_lower.to_i24(),
-        new_tick_upper.to_i24(),
-    )?;
+    let token0_ratio = token0_price_to_ratio(price.clone(), new_tick_lower, new_tick_upper)?;
     let amount1_after = (BigDecimal::from(1) - token0_ratio) * &equity_before;
     // token0's equity denominated in token1 divided by the price
     let amount0_after = (equity_before - &amount1_after) / price;
@@ -429,6 +747,7 @@ where
 ///
 /// * `position`: Current position
 /// * `new_price`: The new pool price
+#[cfg(feature = "bigdecimal")]
 #[inline]
 pub fn get_position_at_price<TP>(
     position: Position<TP>,
@@ -437,7 +756,7 @@ pub fn get_position_at_price<TP>(
 where
     TP: TickDataProvider,
 {
-    let sqrt_price_x96 = price_to_sqrt_ratio_x96(new_price);
+    let sqrt_price_x96 = price_to_sqrt_ratio_x96(new_price)?;
     let pool_at_new_price = Pool::new_with_tick_data_provider(
         position.pool.token0,
         position.pool.token1,
@@ -462,6 +781,7 @@ where
 /// * `new_price`: The new pool price
 /// * `new_tick_lower`: The new lower tick.
 /// * `new_tick_upper`: The new upper tick.
+#[cfg(feature = "bigdecimal")]
 #[inline]
 pub fn get_rebalanced_position_at_price<TP>(
     position: Position<TP>,
@@ -484,7 +804,9 @@ mod tests {
     use super::*;
     use crate::tests::PROVIDER;
     use alloy_primitives::{address, uint};
+    #[cfg(feature = "bigdecimal")]
     use core::str::FromStr;
+    #[cfg(feature = "bigdecimal")]
     use num_traits::{Signed, Zero};
 
     const NPM: Address = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
@@ -519,6 +841,23 @@ mod tests {
         assert_eq!(tick.liquidity_net, 456406095307);
     }
 
+    #[tokio::test]
+    async fn test_hydrate_position() {
+        let hydrated = hydrate_position(1, NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(hydrated.token_id, uint!(4_U256));
+        assert_eq!(hydrated.position.liquidity, 34399999543676);
+        assert_eq!(hydrated.position.tick_lower, 253320);
+        assert_eq!(hydrated.position.tick_upper, 264600);
+        let (tokens_owed_0, tokens_owed_1) =
+            get_collectable_token_amounts(1, NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
+                .await
+                .unwrap();
+        assert_eq!(hydrated.tokens_owed0, tokens_owed_0);
+        assert_eq!(hydrated.tokens_owed1, tokens_owed_1);
+    }
+
     #[tokio::test]
     async fn test_get_all_positions_by_owner() {
         let provider = PROVIDER.clone();
@@ -549,6 +888,47 @@ mod tests {
         // });
     }
 
+    #[tokio::test]
+    async fn test_get_positions_for_owner() {
+        let owner = address!("4bD047CA72fa05F0B89ad08FE5Ba5ccdC07DFFBF");
+        let positions = get_positions_for_owner(1, NPM, owner, PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        let expected = get_all_positions_by_owner(NPM, owner, PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(positions.len(), expected.len());
+        for (position, expected) in positions.iter().zip(expected) {
+            assert_eq!(position.liquidity, expected.position.liquidity);
+            assert_eq!(position.tick_lower, expected.position.tickLower.as_i32());
+            assert_eq!(position.tick_upper, expected.position.tickUpper.as_i32());
+            assert!(position.pool.token0.symbol.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_positions() {
+        let token_ids = [uint!(4_U256), uint!(5_U256), uint!(4_U256)];
+        let positions = get_positions(1, NPM, &token_ids, PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(positions.len(), token_ids.len());
+        for (position, token_id) in positions.iter().zip(token_ids) {
+            let expected = Position::from_token_id(1, NPM, token_id, PROVIDER.clone(), BLOCK_ID)
+                .await
+                .unwrap();
+            assert_eq!(position.liquidity, expected.liquidity);
+            assert_eq!(position.tick_lower, expected.tick_lower);
+            assert_eq!(position.tick_upper, expected.tick_upper);
+            assert_eq!(position.pool.sqrt_ratio_x96, expected.pool.sqrt_ratio_x96);
+            assert!(position.pool.token0.symbol.is_some());
+        }
+        assert!(get_positions(1, NPM, &[], PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_collectable_token_amounts() {
         let (tokens_owed_0, tokens_owed_1) =
@@ -570,6 +950,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_token_uri() {
+        let svg = "<svg></svg>";
+        let json = serde_json::json!({
+            "name": "Uniswap - 0.3% - USDC/WETH - 1000<>2000",
+            "description": "This NFT represents a liquidity position.\n\nFee Tier: 0.3%\nToken ID: 4",
+            "image": format!(
+                "data:image/svg+xml;base64,{}",
+                general_purpose::STANDARD.encode(svg)
+            ),
+        });
+        let uri = format!(
+            "data:application/json;base64,{}",
+            general_purpose::STANDARD.encode(json.to_string())
+        );
+        let metadata = decode_token_uri(&uri).unwrap();
+        assert_eq!(metadata.name, "Uniswap - 0.3% - USDC/WETH - 1000<>2000");
+        assert_eq!(metadata.svg, svg);
+        assert_eq!(
+            metadata.attributes,
+            vec![
+                ("Fee Tier".to_string(), "0.3%".to_string()),
+                ("Token ID".to_string(), "4".to_string()),
+            ]
+        );
+        assert!(decode_token_uri("data:text/plain,").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_position_metadata() {
+        let metadata = get_position_metadata(NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        assert!(metadata.name.starts_with("Uniswap - "));
+        assert!(metadata.svg.starts_with("<svg"));
+        assert!(metadata
+            .attributes
+            .contains(&("Token ID".to_string(), "4".to_string())));
+    }
+
+    #[cfg(feature = "bigdecimal")]
     #[tokio::test]
     async fn test_get_rebalanced_position() {
         let mut position = get_position(1, NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
@@ -594,6 +1015,7 @@ mod tests {
         assert!(position.liquidity - reverted_position.liquidity < position.liquidity / 1000000);
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[tokio::test]
     async fn test_get_position_at_price() {
         let position = get_position(1, NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
@@ -623,7 +1045,7 @@ mod tests {
                 &tick_to_price(
                     position.pool.token0,
                     position.pool.token1,
-                    position.tick_upper.try_into().unwrap(),
+                    position.tick_upper,
                 )
                 .unwrap(),
             ),
@@ -640,6 +1062,7 @@ mod tests {
         assert!(rebalanced_position.amount1().unwrap().quotient().is_zero());
     }
 
+    #[cfg(feature = "bigdecimal")]
     #[tokio::test]
     async fn test_get_rebalanced_position_at_price() {
         let mut position = get_position(1, NPM, uint!(4_U256), PROVIDER.clone(), BLOCK_ID)
@@ -653,7 +1076,7 @@ mod tests {
         let price_upper = tick_to_price(
             position.pool.token0.clone(),
             position.pool.token1.clone(),
-            position.tick_upper.try_into().unwrap(),
+            position.tick_upper,
         )
         .unwrap();
         let position_rebalanced_at_tick_upper = get_rebalanced_position_at_price(
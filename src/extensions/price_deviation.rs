@@ -0,0 +1,288 @@
+//! ## Price Deviation
+//! Checks the spot or time-weighted average price of a pool against an external reference price,
+//! read from a [Chainlink](https://docs.chain.link/data-feeds) aggregator or supplied by the
+//! caller, e.g. to reject a manipulated pool price before a liquidation or a settlement.
+
+use crate::prelude::{Error, *};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+};
+use alloy_primitives::Address;
+use alloy_sol_types::sol;
+use num_traits::{Signed, ToPrimitive};
+use uniswap_sdk_core::prelude::*;
+
+sol! {
+    #[sol(rpc)]
+    interface IChainlinkAggregator {
+        function decimals() external view returns (uint8);
+        function latestRoundData()
+            external
+            view
+            returns (
+                uint80 roundId,
+                int256 answer,
+                uint256 startedAt,
+                uint256 updatedAt,
+                uint80 answeredInRound
+            );
+    }
+}
+
+/// The price of a pool to compare against the reference price, see [`check_pool_price`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolPriceSource {
+    /// The current price of the pool
+    Spot,
+    /// The time-weighted average price of the pool over a window ending at the block
+    Twap {
+        /// The length of the window in seconds
+        seconds_ago: u32,
+    },
+}
+
+/// The reference price to compare the price of a pool against, see [`check_pool_price`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReferencePrice {
+    /// A price supplied by the caller, of the base token in the quote token adjusted for decimals
+    Fixed(BigDecimal),
+    /// The latest answer of a Chainlink aggregator, whose feed must quote the base token in the
+    /// quote token, e.g. ETH / USD for a WETH / USDC pool
+    Chainlink(Address),
+}
+
+/// The latest answer of a Chainlink aggregator, see [`get_chainlink_price`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainlinkPrice {
+    /// The answer adjusted for the decimals of the aggregator
+    pub price: BigDecimal,
+    /// The timestamp of the round of the answer
+    pub updated_at: u64,
+}
+
+/// The deviation of the price of a pool from a reference price, see [`get_price_deviation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceDeviation {
+    /// The price of the base token in the quote token in the pool, adjusted for decimals
+    pub pool_price: BigDecimal,
+    /// The reference price of the base token in the quote token, adjusted for decimals
+    pub reference_price: BigDecimal,
+    /// The timestamp of the reference price, if read from a Chainlink aggregator
+    pub reference_updated_at: Option<u64>,
+    /// The deviation of the pool price from the reference price in basis points, positive if the
+    /// pool price is above it, rounded towards zero and saturated to the range of `i128`
+    pub deviation_bps: i128,
+    /// Whether the deviation is within the threshold in either direction
+    pub within_threshold: bool,
+}
+
+/// Computes the deviation of the price of a pool from a reference price.
+///
+/// ## Arguments
+///
+/// * `pool_price`: The price of the base token in the quote token in the pool
+/// * `reference_price`: The reference price of the base token in the quote token, adjusted for
+///   decimals
+/// * `max_deviation_bps`: The maximum deviation in basis points in either direction
+#[inline]
+pub fn get_price_deviation(
+    pool_price: &Price<Token, Token>,
+    reference_price: BigDecimal,
+    max_deviation_bps: u32,
+) -> Result<PriceDeviation, Error> {
+    if !reference_price.is_positive() {
+        return Err(Error::InvalidPrice);
+    }
+    let pool_price = pool_price.adjusted_for_decimals().to_decimal();
+    let deviation_bps = ((&pool_price - &reference_price) * BigDecimal::from(10000)
+        / &reference_price)
+        .with_scale(0)
+        .to_i128()
+        .unwrap_or(if pool_price > reference_price {
+            i128::MAX
+        } else {
+            i128::MIN
+        });
+    Ok(PriceDeviation {
+        pool_price,
+        reference_price,
+        reference_updated_at: None,
+        deviation_bps,
+        within_threshold: deviation_bps.unsigned_abs() <= u128::from(max_deviation_bps),
+    })
+}
+
+/// Fetches the latest answer of a Chainlink aggregator.
+///
+/// ## Arguments
+///
+/// * `aggregator`: The address of the aggregator
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_chainlink_price<N, P>(
+    aggregator: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ChainlinkPrice, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let aggregator = IChainlinkAggregator::new(aggregator, provider);
+    let decimals = aggregator.decimals().block(block_id).call().await?._0;
+    let round = aggregator.latestRoundData().block(block_id).call().await?;
+    if !round.answer.is_positive() {
+        return Err(Error::InvalidPrice);
+    }
+    Ok(ChainlinkPrice {
+        price: BigDecimal::new(round.answer.to_big_int(), decimals.into()),
+        updated_at: round.updatedAt.saturating_to(),
+    })
+}
+
+/// Fetches the spot or time-weighted average price of a pool and the reference price, and computes
+/// the deviation between them, see [`get_price_deviation`].
+///
+/// ## Arguments
+///
+/// * `pool`: The pool, whose address is derived by [`Pool::address`]
+/// * `base_token`: The token of the pool to price in the other token
+/// * `source`: Whether to compare the spot or the time-weighted average price of the pool
+/// * `reference`: The reference price
+/// * `max_deviation_bps`: The maximum deviation in basis points in either direction
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn check_pool_price<TP, N, P>(
+    pool: &Pool<TP>,
+    base_token: &Token,
+    source: PoolPriceSource,
+    reference: ReferencePrice,
+    max_deviation_bps: u32,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PriceDeviation, Error>
+where
+    TP: TickDataProvider,
+    N: Network,
+    P: Provider<N>,
+{
+    let quote_token = if base_token.equals(&pool.token0) {
+        &pool.token1
+    } else if base_token.equals(&pool.token1) {
+        &pool.token0
+    } else {
+        return Err(Error::InvalidToken);
+    };
+    let pool_price = match source {
+        PoolPriceSource::Spot => pool.price_of(base_token)?,
+        PoolPriceSource::Twap { seconds_ago } => get_time_weighted_averages(
+            pool.address(None, None),
+            seconds_ago,
+            0,
+            provider.root(),
+            block_id,
+        )
+        .await?
+        .price(base_token.clone(), quote_token.clone())?,
+    };
+    match reference {
+        ReferencePrice::Fixed(price) => get_price_deviation(&pool_price, price, max_deviation_bps),
+        ReferencePrice::Chainlink(aggregator) => {
+            let ChainlinkPrice { price, updated_at } =
+                get_chainlink_price(aggregator, provider, block_id).await?;
+            Ok(PriceDeviation {
+                reference_updated_at: Some(updated_at),
+                ..get_price_deviation(&pool_price, price, max_deviation_bps)?
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+    use core::str::FromStr;
+
+    const ETH_USD: Address = address!("5f4eC3Df9cbd43714FE2740f5E3616155c5b8419");
+
+    #[test]
+    fn test_get_price_deviation() {
+        // 2000 USDC per WETH
+        let pool_price = Price::new(
+            WETH.clone(),
+            USDC.clone(),
+            10_u128.pow(18),
+            2_000_000_000_u128,
+        );
+        let deviation =
+            get_price_deviation(&pool_price, BigDecimal::from_str("1980").unwrap(), 100).unwrap();
+        assert_eq!(deviation.pool_price, BigDecimal::from(2000));
+        assert_eq!(deviation.deviation_bps, 101);
+        assert!(!deviation.within_threshold);
+        let deviation =
+            get_price_deviation(&pool_price, BigDecimal::from_str("2010").unwrap(), 100).unwrap();
+        assert_eq!(deviation.deviation_bps, -49);
+        assert!(deviation.within_threshold);
+        assert!(matches!(
+            get_price_deviation(&pool_price, BigDecimal::from(0), 100),
+            Err(Error::InvalidPrice)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_pool_price() {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let chainlink = get_chainlink_price(ETH_USD, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        for source in [
+            PoolPriceSource::Spot,
+            PoolPriceSource::Twap { seconds_ago: 1800 },
+        ] {
+            let deviation = check_pool_price(
+                &pool,
+                &WETH,
+                source,
+                ReferencePrice::Chainlink(ETH_USD),
+                100,
+                PROVIDER.clone(),
+                *BLOCK_ID,
+            )
+            .await
+            .unwrap();
+            assert_eq!(deviation.reference_price, chainlink.price);
+            assert_eq!(deviation.reference_updated_at, Some(chainlink.updated_at));
+            assert!(deviation.within_threshold);
+        }
+        let deviation = check_pool_price(
+            &pool,
+            &WETH,
+            PoolPriceSource::Spot,
+            ReferencePrice::Fixed(chainlink.price * BigDecimal::from(2)),
+            100,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(!deviation.within_threshold);
+        assert!(deviation.deviation_bps < -4000);
+    }
+}
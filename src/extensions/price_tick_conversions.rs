@@ -3,14 +3,17 @@
 //! [`Price`] prices. Ported from [uniswap-v3-automation-sdk](https://github.com/Aperture-Finance/uniswap-v3-automation-sdk/blob/8bc54456753f454848d25029631f4e64ff573e12/price.ts).
 
 use crate::prelude::{Error, *};
-use alloc::format;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use alloy_primitives::{aliases::I24, U160};
-use anyhow::{bail, Result};
 use core::str::FromStr;
+#[cfg(feature = "bigdecimal")]
 use num_bigint::ToBigInt;
+#[cfg(feature = "bigdecimal")]
 use num_traits::{Signed, Zero};
 use once_cell::sync::Lazy;
-use regex::Regex;
 use uniswap_sdk_core::prelude::*;
 
 pub static MIN_PRICE: Lazy<Fraction> =
@@ -22,8 +25,35 @@ pub static MAX_PRICE: Lazy<Fraction> = Lazy::new(|| {
     )
 });
 
+/// The largest magnitude of the exponent of a price accepted by [`parse_price`]
+pub const MAX_PRICE_EXPONENT: i32 = 255;
+
+/// Strips the `_` or `,` thousands separators of the integer part of a price, or returns `None` if
+/// they don't group its digits by three.
+fn strip_thousands_separators(whole: &str) -> Option<String> {
+    let Some(separator) = whole.chars().find(|c| matches!(c, '_' | ',')) else {
+        return Some(whole.to_string());
+    };
+    let mut groups = whole.split(separator);
+    let mut digits = String::from(groups.next()?);
+    if !(1..=3).contains(&digits.len()) {
+        return None;
+    }
+    for group in groups {
+        if group.len() != 3 {
+            return None;
+        }
+        digits.push_str(group);
+    }
+    Some(digits)
+}
+
 /// Parses the specified price string for the price of `base_token` denominated in `quote_token`.
 ///
+/// The price is a decimal number with an optional leading `+`, `_` or `,` thousands separators in
+/// the integer part, and an optional exponent of at most [`MAX_PRICE_EXPONENT`] in magnitude, e.g.
+/// `"1,234.5"` or `"1.5e-7"`.
+///
 /// ## Arguments
 ///
 /// * `base_token`: The base token.
@@ -32,7 +62,8 @@ pub static MAX_PRICE: Lazy<Fraction> = Lazy::new(|| {
 ///
 /// ## Returns
 ///
-/// The parsed price as an instance of [`Price`] in [`uniswap_sdk_core`].
+/// The parsed price as an instance of [`Price`] in [`uniswap_sdk_core`], or
+/// [`Error::InvalidPriceString`] if the string is not a valid price.
 ///
 /// ## Examples
 ///
@@ -52,27 +83,47 @@ pub fn parse_price<TBase, TQuote>(
     base_token: TBase,
     quote_token: TQuote,
     price: &str,
-) -> Result<Price<TBase, TQuote>>
+) -> Result<Price<TBase, TQuote>, Error>
 where
     TBase: BaseCurrency,
     TQuote: BaseCurrency,
 {
-    // Check whether `price` is a valid string of decimal number.
-    // This regex matches any number of digits optionally followed by '.' which is then followed by
-    // at least one digit.
-    let re = Regex::new(r"^\d*\.?\d+$").unwrap();
-    if !re.is_match(price) {
-        bail!("Invalid price string");
-    }
-
-    let (whole, fraction) = match price.split_once('.') {
-        Some((whole, fraction)) => (whole, fraction),
-        None => (price, ""),
+    let price = price.trim();
+    let price = price.strip_prefix('+').unwrap_or(price);
+    let (mantissa, exponent) = match price.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<i32>()
+                .map_err(|_| Error::InvalidPriceString)?,
+        ),
+        None => (price, 0),
     };
-    let decimals = fraction.len();
-    let without_decimals = BigInt::from_str(&format!("{}{}", whole, fraction))?;
-    let numerator = without_decimals * BigInt::from(10).pow(quote_token.decimals() as u32);
-    let denominator = BigInt::from(10).pow(decimals as u32 + base_token.decimals() as u32);
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let whole = strip_thousands_separators(whole).ok_or(Error::InvalidPriceString)?;
+    if whole.len() + fraction.len() == 0
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        || exponent.unsigned_abs() > MAX_PRICE_EXPONENT.unsigned_abs()
+    {
+        return Err(Error::InvalidPriceString);
+    }
+    let without_decimals =
+        BigInt::from_str(&format!("{whole}{fraction}")).map_err(|_| Error::InvalidPriceString)?;
+    let mut numerator = without_decimals * BigInt::from(10).pow(quote_token.decimals() as u32);
+    let mut denominator = BigInt::from(10).pow(base_token.decimals() as u32);
+    // shift the decimal point by the exponent
+    let scale = i32::try_from(fraction.len())
+        .ok()
+        .and_then(|decimals| exponent.checked_sub(decimals))
+        .ok_or(Error::InvalidPriceString)?;
+    if scale >= 0 {
+        numerator *= BigInt::from(10).pow(scale.unsigned_abs());
+    } else {
+        denominator *= BigInt::from(10).pow(scale.unsigned_abs());
+    }
     Ok(Price::new(base_token, quote_token, denominator, numerator))
 }
 
@@ -200,26 +251,27 @@ pub fn price_to_closest_usable_tick(
 /// ## Examples
 ///
 /// ```
-/// use alloy_primitives::aliases::I24;
 /// use bigdecimal::BigDecimal;
 /// use num_traits::{FromPrimitive, Pow, ToPrimitive};
 /// use uniswap_v3_sdk::prelude::*;
 ///
 /// assert_eq!(
-///     tick_to_big_price(I24::from_limbs([100]))
+///     tick_to_big_price(100)
 ///         .unwrap()
 ///         .to_f32()
 ///         .unwrap(),
 ///     1.0001f64.pow(100i32).to_f32().unwrap()
 /// );
 /// ```
+#[cfg(feature = "bigdecimal")]
 #[inline]
-pub fn tick_to_big_price(tick: I24) -> Result<BigDecimal, Error> {
-    let sqrt_ratio_x96 = get_sqrt_ratio_at_tick(tick)?;
+pub fn tick_to_big_price<I: TickIndex>(tick: I) -> Result<BigDecimal, Error> {
+    let sqrt_ratio_x96 = get_sqrt_ratio_at_tick(tick.to_i24())?;
     Ok(BigDecimal::from(sqrt_ratio_x96.to_big_int().pow(2)) / Q192.to_big_decimal())
 }
 
 /// Convert a [`FractionBase`] object to a [`BigDecimal`].
+#[cfg(feature = "bigdecimal")]
 #[inline]
 pub fn fraction_to_big_decimal<M, F>(price: &F) -> BigDecimal
 where
@@ -237,7 +289,8 @@ where
 ///
 /// ## Returns
 ///
-/// The sqrt ratio of token1/token0, as a [`U160`].
+/// The sqrt ratio of token1/token0, as a [`U160`], or [`Error::NegativePrice`] if the price is
+/// negative.
 ///
 /// ## Examples
 ///
@@ -246,26 +299,57 @@ where
 /// use uniswap_v3_sdk::prelude::*;
 ///
 /// let price: BigDecimal = tick_to_big_price(MAX_TICK).unwrap();
-/// assert_eq!(price_to_sqrt_ratio_x96(&price), MAX_SQRT_RATIO);
+/// assert_eq!(price_to_sqrt_ratio_x96(&price).unwrap(), MAX_SQRT_RATIO);
 /// ```
+#[cfg(feature = "bigdecimal")]
 #[inline]
-#[must_use]
-pub fn price_to_sqrt_ratio_x96(price: &BigDecimal) -> U160 {
-    assert!(!price.is_negative(), "Invalid price: must be non-negative");
+pub fn price_to_sqrt_ratio_x96(price: &BigDecimal) -> Result<U160, Error> {
+    if price.is_negative() {
+        return Err(Error::NegativePrice);
+    }
     let price_x192 = price * Q192.to_big_decimal();
     let sqrt_ratio_x96 = price_x192.to_bigint().unwrap().sqrt();
-    if sqrt_ratio_x96 < MIN_SQRT_RATIO.to_big_int() {
+    Ok(if sqrt_ratio_x96 < MIN_SQRT_RATIO.to_big_int() {
         MIN_SQRT_RATIO
     } else if sqrt_ratio_x96 > MAX_SQRT_RATIO.to_big_int() {
         MAX_SQRT_RATIO
     } else {
         U160::from_big_int(sqrt_ratio_x96)
+    })
+}
+
+/// Splits a non-negative [`BigDecimal`] into an exact fraction of integers.
+#[cfg(feature = "bigdecimal")]
+fn big_decimal_to_fraction(value: &BigDecimal) -> (BigInt, BigInt) {
+    let (numerator, scale) = value.as_bigint_and_exponent();
+    if scale < 0 {
+        (
+            numerator * BigInt::from(10).pow(scale.unsigned_abs() as u32),
+            BigInt::from(1),
+        )
+    } else {
+        (numerator, BigInt::from(10).pow(scale as u32))
+    }
+}
+
+/// Returns the square root of a non-negative integer rounded to the nearest integer.
+#[cfg(feature = "bigdecimal")]
+fn sqrt_rounded(value: &BigInt) -> BigInt {
+    let root = value.sqrt();
+    if value - &root * &root > root {
+        root + 1
+    } else {
+        root
     }
 }
 
 /// For a given tick range from `tick_lower` to `tick_upper`, and a given proportion of the position
 /// value that is held in token0, calculate the price of token0 denominated in token1.
 ///
+/// The quadratic in the square root price is solved in Q64.96 integer math, so the square root of
+/// the returned price is within one unit of a Q64.96 of the exact solution, i.e. the relative error
+/// of the price is below `2^-95 / sqrt(price)` regardless of the width of the range or the ratio.
+///
 /// ## Arguments
 ///
 /// * `token0_ratio`: The proportion of the position value that is held in token0, as a
@@ -276,44 +360,48 @@ pub fn price_to_sqrt_ratio_x96(price: &BigDecimal) -> U160 {
 /// ## Returns
 ///
 /// The price of token0 denominated in token1 for the specified tick range and token0 value
-/// proportion.
+/// proportion, [`Error::InvalidRange`] if `tick_upper` is not above `tick_lower`, or
+/// [`Error::InvalidToken0Ratio`] if the ratio is not between 0 and 1.
+#[cfg(feature = "bigdecimal")]
 #[inline]
-pub fn token0_ratio_to_price(
+pub fn token0_ratio_to_price<I: TickIndex>(
     token0_ratio: BigDecimal,
-    tick_lower: I24,
-    tick_upper: I24,
+    tick_lower: I,
+    tick_upper: I,
 ) -> Result<BigDecimal, Error> {
+    let (tick_lower, tick_upper) = (tick_lower.to_i24(), tick_upper.to_i24());
     let one = BigDecimal::from(1);
-    assert!(
-        tick_upper > tick_lower,
-        "Invalid tick range: tickUpper must be greater than tickLower"
-    );
-    assert!(
-        !(token0_ratio.is_negative() || token0_ratio > one),
-        "Invalid token0ValueProportion: must be a value between 0 and 1, inclusive"
-    );
+    if tick_upper <= tick_lower {
+        return Err(Error::InvalidRange);
+    }
+    if token0_ratio.is_negative() || token0_ratio > one {
+        return Err(Error::InvalidToken0Ratio);
+    }
     if token0_ratio.is_zero() {
         return tick_to_big_price(tick_upper);
     }
     if token0_ratio == one {
         return tick_to_big_price(tick_lower);
     }
-    let sqrt_ratio_lower_x96 = get_sqrt_ratio_at_tick(tick_lower)?;
-    let sqrt_ratio_upper_x96 = get_sqrt_ratio_at_tick(tick_upper)?;
-    let l = sqrt_ratio_lower_x96.to_big_decimal() / Q96.to_big_decimal();
-    let u = sqrt_ratio_upper_x96.to_big_decimal() / Q96.to_big_decimal();
-    let r = token0_ratio;
-    let a = &r - one.clone();
-    let b = &u * (one - BigDecimal::from(2) * &r);
-    let c = r * l * u;
-    let numerator = &b + (b.square() - BigDecimal::from(4) * &a * c).sqrt().unwrap();
-    let denominator = BigDecimal::from(-2) * a;
-    Ok((numerator / denominator).square())
+    // With the token0 ratio `r = p / q` and the square root prices `l` and `u` of the range, the
+    // square root price `s` solves `(1 - r) * s^2 + (2 * r - 1) * u * s - r * l * u = 0`,
+    // multiplied here by `q` and evaluated with the Q64.96 square root prices.
+    let (p, q) = big_decimal_to_fraction(&token0_ratio);
+    let l = get_sqrt_ratio_at_tick(tick_lower)?.to_big_int();
+    let u = get_sqrt_ratio_at_tick(tick_upper)?.to_big_int();
+    let b = (&q - &p * 2) * &u;
+    let discriminant = &b * &b + (&q - &p) * &p * l * &u * 4;
+    let sqrt_price_x96 = (b + discriminant.sqrt()) / ((q - p) * 2);
+    Ok(BigDecimal::from(sqrt_price_x96.pow(2)) / Q192.to_big_decimal())
 }
 
 /// Given a price ratio of token1/token0, calculate the proportion of the position value that is
 /// held in token0 for a given tick range. Inverse of [`token0_ratio_to_price`].
 ///
+/// The square root of the price is rounded to the nearest Q64.96, from which the proportion is
+/// computed exactly, so prices returned by [`token0_ratio_to_price`] round trip to within the
+/// precision of that Q64.96.
+///
 /// ## Arguments
 ///
 /// * `price`: The price ratio of token1/token0, as a [`BigDecimal`].
@@ -323,46 +411,47 @@ pub fn token0_ratio_to_price(
 /// ## Returns
 ///
 /// The proportion of the position value that is held in token0, as a [`BigDecimal`] between 0 and
-/// 1, inclusive.
+/// 1, inclusive, [`Error::InvalidRange`] if `tick_upper` is not above `tick_lower`, or
+/// [`Error::NegativePrice`] if the price is negative.
+#[cfg(feature = "bigdecimal")]
 #[inline]
-pub fn token0_price_to_ratio(
+pub fn token0_price_to_ratio<I: TickIndex>(
     price: BigDecimal,
-    tick_lower: I24,
-    tick_upper: I24,
+    tick_lower: I,
+    tick_upper: I,
 ) -> Result<BigDecimal, Error> {
+    let (tick_lower, tick_upper) = (tick_lower.to_i24(), tick_upper.to_i24());
     if tick_upper <= tick_lower {
         return Err(Error::InvalidRange);
     }
-    let sqrt_price_x96 = price_to_sqrt_ratio_x96(&price);
-    let tick = sqrt_price_x96.get_tick_at_sqrt_ratio()?;
+    if price.is_negative() {
+        return Err(Error::NegativePrice);
+    }
+    let price_x192 = (price * Q192.to_big_decimal()).with_scale_round(0, RoundingMode::HalfUp);
+    let s = sqrt_rounded(&price_x192.as_bigint_and_exponent().0);
+    let l = get_sqrt_ratio_at_tick(tick_lower)?.to_big_int();
+    let u = get_sqrt_ratio_at_tick(tick_upper)?.to_big_int();
     // only token0
-    if tick < tick_lower {
+    if s <= l {
         Ok(BigDecimal::from(1))
     }
     // only token1
-    else if tick >= tick_upper {
+    else if s >= u {
         Ok(BigDecimal::zero())
     } else {
-        let liquidity = 2_u128 << 96;
-        let amount0 = get_amount_0_delta(
-            sqrt_price_x96,
-            get_sqrt_ratio_at_tick(tick_upper)?,
-            liquidity,
-            false,
-        )?;
-        let amount1 = get_amount_1_delta(
-            get_sqrt_ratio_at_tick(tick_lower)?,
-            sqrt_price_x96,
-            liquidity,
-            false,
-        )?;
-        let value0 = amount0.to_big_decimal() * price;
-        Ok(&value0 / (&value0 + amount1.to_big_decimal()))
+        // per unit of liquidity, the value of token0 in token1 is `s * (u - s) / u` and the
+        // amount of token1 is `s - l`
+        let value0 = &s * (&u - &s);
+        let value1 = u * (s - l);
+        Ok(BigDecimal::from(value0.clone()) / BigDecimal::from(value0 + value1))
     }
 }
 
 /// Returns the tick range for a position ratio and range width.
 ///
+/// The lower square root price is solved in Q64.96 integer math and rounded down to its tick, so
+/// the range is exact up to the tick rounding.
+///
 /// ## Arguments
 ///
 /// * `width`: The width of the range.
@@ -372,19 +461,19 @@ pub fn token0_price_to_ratio(
 ///
 /// ## Returns
 ///
-/// The tick range as a tuple of `(tick_lower, tick_upper)`.
+/// The tick range as a tuple of `(tick_lower, tick_upper)`, [`Error::InvalidRange`] if the width is
+/// not positive, or [`Error::InvalidToken0Ratio`] if the ratio is not between 0 and 1.
 ///
 /// ## Examples
 ///
 /// ```
-/// use alloy_primitives::aliases::I24;
 /// use bigdecimal::BigDecimal;
 /// use uniswap_v3_sdk::prelude::*;
 ///
-/// let tick_current = I24::from_limbs([200000]);
+/// let tick_current = 200000;
 /// let price = tick_to_big_price(tick_current).unwrap();
 /// let token0_ratio = "0.3".parse::<BigDecimal>().unwrap();
-/// let width = I24::from_limbs([1000]);
+/// let width = 1000;
 /// let (tick_lower, tick_upper) =
 ///     tick_range_from_width_and_ratio(width, tick_current, token0_ratio.clone()).unwrap();
 /// assert_eq!(tick_upper - tick_lower, width);
@@ -397,41 +486,188 @@ pub fn token0_price_to_ratio(
 /// let ratio = &value0 / (&value0 + amount1);
 /// assert!((ratio - token0_ratio).abs() < "0.001".parse::<BigDecimal>().unwrap());
 /// ```
+#[cfg(feature = "bigdecimal")]
 #[inline]
-pub fn tick_range_from_width_and_ratio(
-    width: I24,
-    tick_current: I24,
+pub fn tick_range_from_width_and_ratio<I: TickIndex>(
+    width: I,
+    tick_current: I,
     token0_ratio: BigDecimal,
-) -> Result<(I24, I24), Error> {
+) -> Result<(I, I), Error> {
+    let (width, tick_current) = (width.to_i24(), tick_current.to_i24());
     let one = BigDecimal::from(1);
-    let two = BigDecimal::from(2);
-    assert!(
-        !(token0_ratio.is_negative() || token0_ratio > one),
-        "Invalid token0ValueProportion: must be a value between 0 and 1, inclusive"
-    );
+    if width <= I24::ZERO {
+        return Err(Error::InvalidRange);
+    }
+    if token0_ratio.is_negative() || token0_ratio > one {
+        return Err(Error::InvalidToken0Ratio);
+    }
     let (tick_lower, tick_upper) = if token0_ratio.is_zero() {
         (tick_current - width, tick_current)
     } else if token0_ratio == one {
         (tick_current, tick_current + width)
     } else {
-        let price = tick_to_big_price(tick_current)?;
-        let a = token0_ratio;
-        let b = (one.clone() - &a * two.clone()) * price.sqrt().unwrap();
-        let c = &price * (&a - one) / tick_to_big_price(width)?.sqrt().unwrap();
-        let price_lower_sqrt =
-            ((&b * &b - &a * &c * BigDecimal::from(4)).sqrt().unwrap() - &b) / (&a * two);
-        let sqrt_ratio_lower_x96 = price_lower_sqrt * Q96.to_big_decimal();
-        let tick_lower = U160::from_big_int(sqrt_ratio_lower_x96.to_bigint().unwrap())
-            .get_tick_at_sqrt_ratio()?;
+        // With the token0 ratio `r = p / q`, the current square root price `s` and the square
+        // root `w` of the price at the width, the lower square root price `l` solves
+        // `r * w * l^2 + (1 - 2 * r) * s * w * l - (1 - r) * s^2 = 0`, multiplied here by `q`
+        // and evaluated with the Q64.96 square root prices.
+        let (p, q) = big_decimal_to_fraction(&token0_ratio);
+        let s = get_sqrt_ratio_at_tick(tick_current)?.to_big_int();
+        let w = get_sqrt_ratio_at_tick(width)?.to_big_int();
+        let b = (&q - &p * 2) * &w;
+        let discriminant = &b * &b + (q - &p) * &p * &w * Q96.to_big_int() * 4;
+        let sqrt_ratio_lower_x96 = s * (discriminant.sqrt() - b) / (p * w * 2);
+        let tick_lower = U160::from_big_int(sqrt_ratio_lower_x96).get_tick_at_sqrt_ratio()?;
         (tick_lower, tick_lower + width)
     };
-    Ok((tick_lower, tick_upper))
+    Ok((I::from_i24(tick_lower), I::from_i24(tick_upper)))
+}
+
+/// Returns the usable tick range that spans a percentage below and above the price at the current
+/// tick, i.e. from `price * (1 - percent_down)` to `price * (1 + percent_up)`.
+///
+/// The range is widened to the usable ticks around it, clamped to the usable tick bounds, and
+/// spans at least one tick spacing.
+///
+/// ## Arguments
+///
+/// * `tick_current`: The current tick of the pool
+/// * `percent_down`: How far below the current price the range starts, where 100% or more extends
+///   it to the lowest usable tick
+/// * `percent_up`: How far above the current price the range ends
+/// * `tick_spacing`: The tick spacing of the pool
+///
+/// ## Examples
+///
+/// ```
+/// use uniswap_sdk_core::prelude::*;
+/// use uniswap_v3_sdk::prelude::*;
+///
+/// let (tick_lower, tick_upper) =
+///     tick_range_around_price(0, Percent::new(5, 100), Percent::new(5, 100), 60).unwrap();
+/// assert_eq!(tick_lower, -540);
+/// assert_eq!(tick_upper, 540);
+/// ```
+#[inline]
+pub fn tick_range_around_price<I: TickIndex>(
+    tick_current: I,
+    percent_down: Percent,
+    percent_up: Percent,
+    tick_spacing: I,
+) -> Result<(I, I), Error> {
+    let (tick_current, tick_spacing) = (tick_current.to_i24(), tick_spacing.to_i24());
+    let ratio_x192 = get_sqrt_ratio_at_tick(tick_current)?.to_big_int().pow(2);
+    let tick_at_ratio = |ratio_x192: BigInt| {
+        let sqrt_ratio_x96 = ratio_x192.sqrt();
+        if sqrt_ratio_x96 < MIN_SQRT_RATIO.to_big_int() {
+            Ok(MIN_TICK)
+        } else if sqrt_ratio_x96 >= MAX_SQRT_RATIO.to_big_int() {
+            Ok(MAX_TICK)
+        } else {
+            U160::from_big_int(sqrt_ratio_x96).get_tick_at_sqrt_ratio()
+        }
+    };
+    let tick_lower = if percent_down.numerator >= percent_down.denominator {
+        MIN_TICK
+    } else {
+        tick_at_ratio(
+            &ratio_x192 * (&percent_down.denominator - &percent_down.numerator)
+                / &percent_down.denominator,
+        )?
+    };
+    let tick_upper = tick_at_ratio(
+        &ratio_x192 * (&percent_up.denominator + &percent_up.numerator) / &percent_up.denominator,
+    )?;
+    // round the lower tick down and the upper tick up to the usable ticks
+    let min_usable_tick = nearest_usable_tick(MIN_TICK, tick_spacing);
+    let max_usable_tick = nearest_usable_tick(MAX_TICK, tick_spacing);
+    let mut tick_lower = (tick_lower.compress(tick_spacing) * tick_spacing).max(min_usable_tick);
+    let mut tick_upper =
+        (-(-tick_upper).compress(tick_spacing) * tick_spacing).min(max_usable_tick);
+    if tick_lower >= tick_upper {
+        if tick_lower + tick_spacing <= max_usable_tick {
+            tick_upper = tick_lower + tick_spacing;
+        } else {
+            tick_lower = tick_upper - tick_spacing;
+        }
+    }
+    Ok((I::from_i24(tick_lower), I::from_i24(tick_upper)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_parse_price() {
+        let parse = |price: &str| parse_price(TOKEN0.clone(), TOKEN1.clone(), price);
+        for (price, numerator, denominator) in [
+            ("10.23", 1023, 100),
+            ("+10.23", 1023, 100),
+            (".5", 1, 2),
+            ("1.5e-7", 15, 100_000_000),
+            ("1.5E+3", 1500, 1),
+            ("1_000", 1000, 1),
+            ("1,234.5", 12345, 10),
+            ("12,345,678", 12345678, 1),
+            ("2.5e2", 250, 1),
+        ] {
+            let parsed = parse(price).unwrap();
+            assert_eq!(
+                parsed.numerator * BigInt::from(denominator),
+                parsed.denominator * BigInt::from(numerator),
+                "{price}"
+            );
+        }
+        for price in [
+            "",
+            "abc",
+            "1.2.3",
+            "-1",
+            "e5",
+            "1e",
+            "1.5e-",
+            "0x10",
+            "1,2,3",
+            ",.",
+            "1,",
+            ",100",
+            "1_000,000",
+            "0.000,1",
+            "1e256",
+            "1e-2147483648",
+        ] {
+            assert!(
+                matches!(parse(price), Err(Error::InvalidPriceString)),
+                "{price}"
+            );
+        }
+        assert!(parse("1e255").is_ok());
+        assert!(parse("1e-255").is_ok());
+    }
+
+    #[test]
+    fn test_tick_range_around_price() {
+        let range = |tick: i32, down: u64, up: u64, tick_spacing: i32| {
+            tick_range_around_price(
+                tick,
+                Percent::new(down, 100),
+                Percent::new(up, 100),
+                tick_spacing,
+            )
+            .unwrap()
+        };
+        assert_eq!(range(0, 5, 5, 60), (-540, 540));
+        assert_eq!(range(200000, 10, 20, 10), (198940, 201830));
+        // the range spans at least one tick spacing
+        assert_eq!(range(600, 0, 0, 60), (600, 660));
+        assert_eq!(range(887220, 0, 50, 60), (887160, 887220));
+        // the range is clamped to the usable ticks
+        assert_eq!(range(0, 100, 5, 60), (-887220, 540));
+        assert_eq!(range(887000, 5, 100, 60), (886440, 887220));
+    }
 
+    #[cfg(feature = "bigdecimal")]
     #[test]
     fn test_token0_ratio_to_price_conversion() {
         let tick_lower = I24::from_limbs([253320]);
@@ -449,14 +685,65 @@ mod tests {
                 .unwrap();
         assert_eq!(
             price.with_scale_round(30, RoundingMode::HalfUp).to_string(),
-            "226996287752.678057810335753063814266625941"
+            "226996287752.678057810335753063814263501199"
         );
         let token0_ratio = token0_price_to_ratio(price, tick_lower, tick_upper).unwrap();
         assert_eq!(
             token0_ratio
                 .with_scale_round(30, RoundingMode::HalfUp)
                 .to_string(),
-            "0.299999999999999999999998780740"
+            "0.300000000000000000000000000000"
         );
     }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_invalid_inputs() {
+        let tick_lower = I24::from_limbs([253320]);
+        let tick_upper = I24::from_limbs([264600]);
+        let half = BigDecimal::from_str("0.5").unwrap();
+        assert!(matches!(
+            price_to_sqrt_ratio_x96(&BigDecimal::from(-1)),
+            Err(Error::NegativePrice)
+        ));
+        assert!(matches!(
+            token0_price_to_ratio(BigDecimal::from(-1), tick_lower, tick_upper),
+            Err(Error::NegativePrice)
+        ));
+        assert!(matches!(
+            token0_ratio_to_price(half.clone(), tick_upper, tick_lower),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            token0_ratio_to_price(BigDecimal::from(2), tick_lower, tick_upper),
+            Err(Error::InvalidToken0Ratio)
+        ));
+        assert!(matches!(
+            tick_range_from_width_and_ratio(I24::ZERO, tick_lower, half),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            tick_range_from_width_and_ratio(tick_lower, tick_lower, BigDecimal::from(-1)),
+            Err(Error::InvalidToken0Ratio)
+        ));
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_token0_ratio_round_trip() {
+        for (token0_ratio, tick_lower, tick_upper, tolerance) in [
+            ("0.000001", -887220, 887220, "1e-45"),
+            ("0.333333333333333333333333333333", -100, 100, "1e-26"),
+            ("0.5", 253320, 264600, "1e-30"),
+        ] {
+            let token0_ratio = BigDecimal::from_str(token0_ratio).unwrap();
+            let price =
+                token0_ratio_to_price(token0_ratio.clone(), tick_lower, tick_upper).unwrap();
+            let round_trip = token0_price_to_ratio(price, tick_lower, tick_upper).unwrap();
+            assert!(
+                (round_trip - &token0_ratio).abs() < BigDecimal::from_str(tolerance).unwrap(),
+                "{token0_ratio}"
+            );
+        }
+    }
 }
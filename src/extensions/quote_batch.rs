@@ -0,0 +1,137 @@
+//! ## Quote Batch
+//! Quotes many candidate routes and amounts with the quoter in a single `eth_call` to
+//! [Multicall3](https://github.com/mds1/multicall) instead of one round trip per candidate, e.g. to
+//! enumerate the routes of a trade.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy::{
+    contract::Error as ContractError, eips::BlockId, network::Network, providers::Provider,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// The quote of a candidate route and amount, see [`quote_routes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteQuote {
+    /// The path of the route, encoded as by [`encode_route_to_path`] in the direction of the trade
+    /// type, which identifies the route
+    pub path: Bytes,
+    /// The amount that was quoted, either an amount in, or an amount out
+    pub amount: U256,
+    /// The quote, or `None` if the quoter reverted, e.g. when a pool of the route has not enough
+    /// liquidity
+    pub quote: Option<Quote>,
+}
+
+/// Quotes each candidate route and amount with the quoter in a single `eth_call`.
+///
+/// ## Arguments
+///
+/// * `quoter`: The address of the quoter, matching [`QuoteOptions::use_quoter_v2`]
+/// * `candidates`: The routes and the amount to quote on each
+/// * `trade_type`: The trade type, either exact input or exact output
+/// * `options`: The optional params including price limit and Quoter contract switch
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The quote of each candidate, in the order of the candidates
+#[inline]
+pub async fn quote_routes<TInput, TOutput, TP, C, N, P>(
+    quoter: Address,
+    candidates: &[(Route<TInput, TOutput, TP>, CurrencyAmount<C>)],
+    trade_type: TradeType,
+    options: Option<QuoteOptions>,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<Vec<RouteQuote>, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+    C: BaseCurrency,
+    N: Network,
+    P: Provider<N>,
+{
+    let results = multicall3_try_aggregate(
+        candidates.iter().map(|(route, amount)| {
+            (
+                quoter,
+                quote_call_parameters(route, amount, trade_type, options).calldata,
+            )
+        }),
+        provider,
+        block_id,
+    )
+    .await?;
+    candidates
+        .iter()
+        .zip(results)
+        .map(|((route, amount), result)| {
+            let quote = result
+                .map(|data| decode_quote_result(route, trade_type, options, data))
+                .transpose()
+                .map_err(|e| Error::ContractError(ContractError::AbiError(e.into())))?;
+            Ok(RouteQuote {
+                path: encode_route_to_path(route, trade_type == TradeType::ExactOutput),
+                amount: U256::from_big_int(amount.quotient()),
+                quote,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const QUOTER: Address = address!("b27308f9F90D607463bb33eA1BeBb41C27CE5AB6");
+
+    fn route(token_out: &Token, fee: FeeAmount) -> Route<Token, Token, NoTickDataProvider> {
+        let pool = Pool::new(
+            USDC.clone(),
+            token_out.clone(),
+            fee,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        Route::new(vec![pool], USDC.clone(), token_out.clone())
+    }
+
+    #[tokio::test]
+    async fn test_quote_routes() {
+        let amount = CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000_000).unwrap();
+        let candidates = [
+            (route(&WETH, FeeAmount::LOW), amount.clone()),
+            (route(&WETH, FeeAmount::MEDIUM), amount.clone()),
+            // the pool is not deployed, so the quoter reverts
+            (route(&TOKEN0, FeeAmount::MEDIUM), amount),
+        ];
+        let quotes = quote_routes(
+            QUOTER,
+            &candidates,
+            TradeType::ExactInput,
+            None,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(quotes.len(), 3);
+        for (quote, (route, _)) in quotes.iter().zip(&candidates) {
+            assert_eq!(quote.path, encode_route_to_path(route, false));
+            assert_eq!(quote.amount, U256::from(1_000_000_000));
+        }
+        let low = quotes[0].quote.as_ref().unwrap().amount;
+        let medium = quotes[1].quote.as_ref().unwrap().amount;
+        assert!(!low.is_zero() && !medium.is_zero());
+        // the quotes of both fee tiers are within 1% of each other
+        assert!(low.abs_diff(medium) < low / U256::from(100));
+        assert!(quotes[2].quote.is_none());
+    }
+}
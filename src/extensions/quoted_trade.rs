@@ -0,0 +1,122 @@
+//! ## Quoted Trade
+//! Builds a [`Trade`] from the quote of the on-chain quoter instead of simulating the swap locally,
+//! so that integrators who trust the quoter can produce the calldata of a trade with slippage
+//! protection without fetching any tick data.
+
+use crate::prelude::{Error, *};
+use alloy::{
+    contract::Error as ContractError,
+    eips::{BlockId, BlockNumberOrTag},
+    network::{Network, TransactionBuilder},
+    providers::Provider,
+};
+use alloy_primitives::Address;
+use uniswap_sdk_core::prelude::*;
+
+/// Quotes a route with the quoter and constructs the trade from the quote, see
+/// [`Trade::from_quote`].
+///
+/// ## Arguments
+///
+/// * `quoter`: The address of the quoter, matching [`QuoteOptions::use_quoter_v2`]
+/// * `route`: The route to quote, whose pools need no tick data
+/// * `amount`: The amount specified, either input or output, depending on `trade_type`
+/// * `trade_type`: Whether the trade is an exact input or exact output swap
+/// * `options`: The optional params including price limit and Quoter contract switch
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+///
+/// ## Returns
+///
+/// The trade with its execution price computed, and the quote
+#[inline]
+pub async fn get_quoted_trade<TInput, TOutput, TP, N, P>(
+    quoter: Address,
+    route: Route<TInput, TOutput, TP>,
+    amount: CurrencyAmount<impl BaseCurrency>,
+    trade_type: TradeType,
+    options: Option<QuoteOptions>,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<(Trade<TInput, TOutput, TP>, Quote), Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+    N: Network,
+    P: Provider<N>,
+{
+    let tx = N::TransactionRequest::default()
+        .with_to(quoter)
+        .with_input(quote_call_parameters(&route, &amount, trade_type, options).calldata);
+    let data = provider
+        .call(&tx)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .await?;
+    let quote = decode_quote_result(&route, trade_type, options, data)
+        .map_err(|e| Error::ContractError(ContractError::AbiError(e.into())))?;
+    let mut trade = Trade::from_quote(route, amount, quote.amount, trade_type)?;
+    trade.execution_price_cached()?;
+    Ok((trade, quote))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, U256};
+
+    const QUOTER_V2: Address = address!("61fFE014bA17989E743c5F6cB21bF9697530B21e");
+
+    #[tokio::test]
+    async fn test_get_quoted_trade() {
+        let pool = Pool::from_pool_key(
+            1,
+            FACTORY_ADDRESS,
+            USDC.address(),
+            WETH.address(),
+            FeeAmount::LOW,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let route = Route::new(vec![pool], USDC.clone(), WETH.clone());
+        let amount_in = CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000_000).unwrap();
+        let (trade, quote) = get_quoted_trade(
+            QUOTER_V2,
+            route,
+            amount_in.clone(),
+            TradeType::ExactInput,
+            Some(QuoteOptions {
+                use_quoter_v2: true,
+                ..Default::default()
+            }),
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(!quote.gas_estimate.is_zero());
+        assert_eq!(trade.input_amount().unwrap(), amount_in);
+        assert_eq!(
+            U256::from_big_int(trade.output_amount().unwrap().quotient()),
+            quote.amount
+        );
+        // the calldata is built with slippage protection without any tick data
+        let MethodParameters { calldata, .. } = swap_call_parameters(
+            &mut [trade],
+            SwapOptions {
+                slippage_tolerance: Percent::new(1, 100),
+                recipient: Address::ZERO,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let calls = decode_swap_call_parameters(calldata).unwrap();
+        let SwapRouterCall::ExactInputSingle(params) = &calls[0] else {
+            panic!("expected exactInputSingle");
+        };
+        assert!(params.amountOutMinimum < quote.amount);
+    }
+}
@@ -0,0 +1,196 @@
+//! ## Range Optimizer
+//! Searches for the width of a price range that maximizes the expected fees minus the impermanent
+//! loss of a position, given the expected volatility of the price and an estimate of the fee APR.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::aliases::I24;
+use core::str::FromStr;
+use num_traits::{ToPrimitive, Zero};
+use uniswap_sdk_core::prelude::*;
+
+/// The inputs of [`optimize_range_width`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeOptimizerParams {
+    /// The annualized volatility of the price, e.g. 0.8 for 80%
+    pub volatility: BigDecimal,
+    /// The estimated fee APR of a full range position, e.g. from [`get_fee_apr`]
+    pub fee_apr: BigDecimal,
+    /// The holding period in years
+    pub horizon: BigDecimal,
+    /// The proportion of the position value to hold in token0 at the current price, between 0 and
+    /// 1, inclusive
+    pub token0_ratio: BigDecimal,
+}
+
+/// A candidate range with its projected metrics over the holding period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeCandidate {
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    /// The price of token0 in token1 below which the position only holds token0
+    pub price_lower: BigDecimal,
+    /// The price of token0 in token1 above which the position only holds token1
+    pub price_upper: BigDecimal,
+    /// The expected fees as a proportion of the position value
+    pub expected_fees: BigDecimal,
+    /// The expected impermanent loss as a proportion of the position value, non-positive
+    pub expected_impermanent_loss: BigDecimal,
+    /// The expected fees plus the expected impermanent loss
+    pub expected_return: BigDecimal,
+}
+
+/// The value of a unit of liquidity in the range at the sqrt price, in token1
+fn unit_liquidity_value(
+    sqrt_price: &BigDecimal,
+    tick_lower: I24,
+    tick_upper: I24,
+) -> Result<BigDecimal, Error> {
+    let q96 = Q96.to_big_decimal();
+    let sqrt_price_lower = get_sqrt_ratio_at_tick(tick_lower)?.to_big_decimal() / &q96;
+    let sqrt_price_upper = get_sqrt_ratio_at_tick(tick_upper)?.to_big_decimal() / &q96;
+    let clamped = sqrt_price
+        .clone()
+        .clamp(sqrt_price_lower.clone(), sqrt_price_upper.clone());
+    let one = BigDecimal::from(1);
+    let amount0 = &one / &clamped - one / sqrt_price_upper;
+    let amount1 = clamped - sqrt_price_lower;
+    Ok(amount0 * sqrt_price.square() + amount1)
+}
+
+/// Searches the width of a range around the current tick that maximizes the expected fees minus
+/// the expected impermanent loss over the holding period.
+///
+/// The model assumes that the fees earned per unit of liquidity are the same for any range while
+/// the price is in range, so a range earns the full range fee APR scaled by its capital efficiency
+/// and by the proportion of the time the price is expected to stay in range. The price is expected
+/// to move by one standard deviation, `volatility * sqrt(horizon)` in log terms, and the expected
+/// impermanent loss is the average of the losses for a move up and a move down.
+///
+/// ## Arguments
+///
+/// * `tick_current`: The current tick of the pool
+/// * `fee`: The fee tier of the pool
+/// * `params`: The volatility, fee APR, holding period and token0 ratio
+///
+/// ## Returns
+///
+/// The candidate ranges sorted by the expected return in descending order
+#[inline]
+pub fn optimize_range_width(
+    tick_current: I24,
+    fee: FeeAmount,
+    params: &RangeOptimizerParams,
+) -> Result<Vec<RangeCandidate>, Error> {
+    let tick_spacing = fee.tick_spacing();
+    let ln_tick_base = BigDecimal::from_str("0.000099995000333308").unwrap();
+    let one = BigDecimal::from(1);
+    // the expected move of the price in ticks
+    let move_ticks = (&params.volatility * params.horizon.sqrt().unwrap() / &ln_tick_base)
+        .to_i32()
+        .unwrap_or(MAX_TICK_I32)
+        .clamp(0, MAX_TICK_I32);
+    let tick_at = |tick: i32| I24::try_from(tick.clamp(MIN_TICK_I32, MAX_TICK_I32)).unwrap();
+    let sqrt_price_current_x96 = get_sqrt_ratio_at_tick(tick_current)?;
+    let sqrt_price_up_x96 = get_sqrt_ratio_at_tick(tick_at(tick_current.as_i32() + move_ticks))?;
+    let sqrt_price_down_x96 = get_sqrt_ratio_at_tick(tick_at(tick_current.as_i32() - move_ticks))?;
+    let sqrt_price = sqrt_price_current_x96.to_big_decimal() / Q96.to_big_decimal();
+    let (min_usable_tick, max_usable_tick) = (
+        nearest_usable_tick(MIN_TICK, tick_spacing),
+        nearest_usable_tick(MAX_TICK, tick_spacing),
+    );
+    let full_range_value = unit_liquidity_value(&sqrt_price, min_usable_tick, max_usable_tick)?;
+
+    // widths doubling from twice the tick spacing, and the full range
+    let mut ranges = Vec::new();
+    let mut width = tick_spacing * I24::from_limbs([2]);
+    while width <= MAX_TICK {
+        if let Ok((tick_lower, tick_upper)) =
+            tick_range_from_width_and_ratio(width, tick_current, params.token0_ratio.clone())
+        {
+            let tick_lower = nearest_usable_tick(tick_lower.max(MIN_TICK), tick_spacing);
+            let tick_upper = nearest_usable_tick(tick_upper.min(MAX_TICK), tick_spacing);
+            if tick_lower < tick_upper && !ranges.contains(&(tick_lower, tick_upper)) {
+                ranges.push((tick_lower, tick_upper));
+            }
+        }
+        width = width * I24::from_limbs([2]);
+    }
+    ranges.push((min_usable_tick, max_usable_tick));
+
+    let mut candidates = Vec::with_capacity(ranges.len());
+    for (tick_lower, tick_upper) in ranges {
+        let capital_efficiency =
+            &full_range_value / unit_liquidity_value(&sqrt_price, tick_lower, tick_upper)?;
+        let half_width = BigDecimal::from((tick_upper - tick_lower).as_i32()) / BigDecimal::from(2);
+        let time_in_range = if move_ticks == 0 {
+            one.clone()
+        } else {
+            (half_width / BigDecimal::from(move_ticks)).min(one.clone())
+        };
+        let expected_fees = &params.fee_apr * capital_efficiency * time_in_range * &params.horizon;
+        let impermanent_loss = |sqrt_price_x96| {
+            get_impermanent_loss(
+                tick_lower,
+                tick_upper,
+                sqrt_price_current_x96,
+                sqrt_price_x96,
+            )
+            .map(|il| BigDecimal::from(il.numerator) / BigDecimal::from(il.denominator))
+        };
+        let expected_impermanent_loss = (impermanent_loss(sqrt_price_up_x96)?
+            + impermanent_loss(sqrt_price_down_x96)?)
+            / BigDecimal::from(2);
+        candidates.push(RangeCandidate {
+            tick_lower,
+            tick_upper,
+            price_lower: token0_ratio_to_price(one.clone(), tick_lower, tick_upper)?,
+            price_upper: token0_ratio_to_price(BigDecimal::zero(), tick_lower, tick_upper)?,
+            expected_return: &expected_fees + &expected_impermanent_loss,
+            expected_fees,
+            expected_impermanent_loss,
+        });
+    }
+    candidates.sort_by(|a, b| b.expected_return.cmp(&a.expected_return));
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Signed;
+
+    fn params(volatility: &str, fee_apr: &str) -> RangeOptimizerParams {
+        RangeOptimizerParams {
+            volatility: BigDecimal::from_str(volatility).unwrap(),
+            fee_apr: BigDecimal::from_str(fee_apr).unwrap(),
+            horizon: BigDecimal::from_str("0.25").unwrap(),
+            token0_ratio: BigDecimal::from_str("0.5").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_candidates_sorted() {
+        let candidates =
+            optimize_range_width(I24::ZERO, FeeAmount::MEDIUM, &params("0.5", "0.1")).unwrap();
+        assert!(candidates.len() > 1);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].expected_return >= pair[1].expected_return));
+        for candidate in &candidates {
+            assert!(candidate.tick_lower < candidate.tick_upper);
+            assert!(candidate.price_lower < candidate.price_upper);
+            assert!(!candidate.expected_impermanent_loss.is_positive());
+        }
+    }
+
+    #[test]
+    fn test_volatility_widens_range() {
+        let width = |candidate: &RangeCandidate| candidate.tick_upper - candidate.tick_lower;
+        let calm =
+            optimize_range_width(I24::ZERO, FeeAmount::MEDIUM, &params("0.01", "0.1")).unwrap();
+        let volatile =
+            optimize_range_width(I24::ZERO, FeeAmount::MEDIUM, &params("2", "0.01")).unwrap();
+        assert!(width(&volatile[0]) > width(&calm[0]));
+    }
+}
@@ -0,0 +1,187 @@
+//! ## Rebalance
+//! Policies deciding when a keeper should rebalance a position, from the current state of its pool
+//! and optionally the time-weighted averages of the pool from its oracle observations.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::aliases::I24;
+use uniswap_sdk_core::prelude::*;
+
+/// When to rebalance a position, see [`RebalancePolicy::should_rebalance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebalancePolicy {
+    /// Rebalance when the current price is within a number of basis points of a bound of the
+    /// position, or beyond it
+    NearBound { bips: u32 },
+    /// Rebalance when the position has been out of range for a number of seconds, i.e. both the
+    /// current tick and the arithmetic mean tick over the window are beyond the same bound
+    OutOfRangeFor { seconds: u32 },
+}
+
+/// Why a position should be rebalanced, see [`Decision`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebalanceTrigger {
+    /// The price is in range within the threshold of the lower bound
+    NearLower,
+    /// The price is in range within the threshold of the upper bound
+    NearUpper,
+    /// The price is below the lower bound
+    BelowRange,
+    /// The price is at or above the upper bound
+    AboveRange,
+}
+
+/// The decision of a [`RebalancePolicy`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Decision {
+    Hold,
+    Rebalance(RebalanceTrigger),
+}
+
+impl RebalancePolicy {
+    /// The window of the time-weighted averages that the policy needs, in seconds ago, see
+    /// [`get_time_weighted_averages`]
+    #[inline]
+    #[must_use]
+    pub const fn observation_window(&self) -> Option<u32> {
+        match self {
+            Self::NearBound { .. } => None,
+            Self::OutOfRangeFor { seconds } => Some(*seconds),
+        }
+    }
+
+    /// Decides whether to rebalance a position given the current state of its pool.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The current state of the pool of the position
+    /// * `position`: The position
+    /// * `averages`: The time-weighted averages of the pool over the
+    ///   [`RebalancePolicy::observation_window`], required by [`RebalancePolicy::OutOfRangeFor`]
+    ///   which holds without them
+    #[inline]
+    pub fn should_rebalance<TP: TickDataProvider>(
+        &self,
+        pool: &Pool<TP>,
+        position: &Position<TP>,
+        averages: Option<&TimeWeightedAverages>,
+    ) -> Result<Decision, Error> {
+        let tick_lower = position.tick_lower.to_i24();
+        let tick_upper = position.tick_upper.to_i24();
+        let out_of_range = |tick: I24| {
+            if tick < tick_lower {
+                Some(RebalanceTrigger::BelowRange)
+            } else if tick >= tick_upper {
+                Some(RebalanceTrigger::AboveRange)
+            } else {
+                None
+            }
+        };
+        let current = out_of_range(pool.tick_current.to_i24());
+        match self {
+            Self::NearBound { bips } => {
+                if let Some(trigger) = current {
+                    return Ok(Decision::Rebalance(trigger));
+                }
+                let price = pool.sqrt_ratio_x96.to_big_int().pow(2);
+                let price_lower = get_sqrt_ratio_at_tick(tick_lower)?.to_big_int().pow(2);
+                let price_upper = get_sqrt_ratio_at_tick(tick_upper)?.to_big_int().pow(2);
+                // the relative distance of the price to each bound in basis points
+                let threshold = &price * BigInt::from(*bips);
+                Ok(if (&price - price_lower) * 10000 <= threshold {
+                    Decision::Rebalance(RebalanceTrigger::NearLower)
+                } else if (price_upper - &price) * 10000 <= threshold {
+                    Decision::Rebalance(RebalanceTrigger::NearUpper)
+                } else {
+                    Decision::Hold
+                })
+            }
+            Self::OutOfRangeFor { .. } => Ok(
+                match (
+                    current,
+                    averages.map(|a| out_of_range(a.arithmetic_mean_tick)),
+                ) {
+                    (Some(trigger), Some(Some(mean))) if trigger == mean => {
+                        Decision::Rebalance(trigger)
+                    }
+                    _ => Decision::Hold,
+                },
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn pool_at(tick: i32) -> Pool {
+        Pool::new(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            get_sqrt_ratio_at_tick(I24::try_from(tick).unwrap()).unwrap(),
+            0,
+        )
+        .unwrap()
+    }
+
+    fn averages(tick: i32) -> TimeWeightedAverages {
+        TimeWeightedAverages {
+            arithmetic_mean_tick: I24::try_from(tick).unwrap(),
+            harmonic_mean_liquidity: 0,
+        }
+    }
+
+    #[test]
+    fn test_near_bound() {
+        let position = Position::new(pool_at(0), 1, -600, 600);
+        let policy = RebalancePolicy::NearBound { bips: 100 };
+        assert_eq!(policy.observation_window(), None);
+        let decide = |tick| {
+            policy
+                .should_rebalance(&pool_at(tick), &position, None)
+                .unwrap()
+        };
+        assert_eq!(decide(0), Decision::Hold);
+        // about 50 bips from the upper bound
+        assert_eq!(
+            decide(550),
+            Decision::Rebalance(RebalanceTrigger::NearUpper)
+        );
+        assert_eq!(
+            decide(-550),
+            Decision::Rebalance(RebalanceTrigger::NearLower)
+        );
+        assert_eq!(decide(-450), Decision::Hold);
+        assert_eq!(
+            decide(600),
+            Decision::Rebalance(RebalanceTrigger::AboveRange)
+        );
+        assert_eq!(
+            decide(-601),
+            Decision::Rebalance(RebalanceTrigger::BelowRange)
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_for() {
+        let position = Position::new(pool_at(0), 1, -600, 600);
+        let policy = RebalancePolicy::OutOfRangeFor { seconds: 3600 };
+        assert_eq!(policy.observation_window(), Some(3600));
+        let decide = |tick, mean: Option<i32>| {
+            policy
+                .should_rebalance(&pool_at(tick), &position, mean.map(averages).as_ref())
+                .unwrap()
+        };
+        assert_eq!(
+            decide(700, Some(650)),
+            Decision::Rebalance(RebalanceTrigger::AboveRange)
+        );
+        // the price has only just left the range
+        assert_eq!(decide(700, Some(300)), Decision::Hold);
+        assert_eq!(decide(0, Some(700)), Decision::Hold);
+        assert_eq!(decide(-700, Some(700)), Decision::Hold);
+        assert_eq!(decide(700, None), Decision::Hold);
+    }
+}
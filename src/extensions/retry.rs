@@ -0,0 +1,231 @@
+//! ## Retry
+//! A retry policy with exponential backoff, jitter and a call budget for the RPC calls of the
+//! extensions, e.g. the bursts of tick lens calls of [`EphemeralTickDataProvider::new_chunked`],
+//! which public RPC endpoints tend to rate limit. Only transient errors are retried, see
+//! [`Error::is_transient`].
+//!
+//! The policy is agnostic of the async runtime, so the functions taking it also take the sleep
+//! function of the runtime, e.g. `tokio::time::sleep`, or `gloo_timers::future::sleep` in a
+//! browser with the `wasm` feature.
+
+use crate::prelude::Error;
+use core::{
+    future::Future,
+    num::NonZeroU32,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// How to retry the RPC calls that fail with a transient error, see [`RetryPolicy::call`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// The maximum number of retries of a call after its first attempt
+    pub max_retries: u32,
+    /// The backoff before the first retry, which doubles with each retry
+    pub initial_backoff: Duration,
+    /// The maximum backoff before a retry
+    pub max_backoff: Duration,
+    /// Whether to randomize each backoff between half of it and all of it, so that concurrent
+    /// callers do not retry in lockstep
+    pub jitter: bool,
+    /// The maximum number of calls per second. [`RetryPolicy::call`] keeps no state between calls,
+    /// so it only spaces the retries of a call by at least [`RetryPolicy::call_interval`], and the
+    /// callers making consecutive calls space them, e.g.
+    /// [`EphemeralTickDataProvider::new_chunked_with_retry`]
+    pub calls_per_second: Option<NonZeroU32>,
+}
+
+impl Default for RetryPolicy {
+    /// Up to 5 retries with a backoff from 100 ms to 10 s, with jitter and no call budget
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            calls_per_second: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries nor spaces calls
+    pub const NONE: Self = Self {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        jitter: false,
+        calls_per_second: None,
+    };
+
+    /// The minimum interval between consecutive calls under the call budget
+    #[inline]
+    #[must_use]
+    pub fn call_interval(&self) -> Duration {
+        self.calls_per_second
+            .map_or(Duration::ZERO, |calls| Duration::from_secs(1) / calls.get())
+    }
+
+    /// The backoff before a retry, which is at least the [`RetryPolicy::call_interval`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `retry`: The number of retries so far
+    #[inline]
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1_u32.checked_shl(retry).unwrap_or(u32::MAX))
+            .min(self.max_backoff);
+        let backoff = if self.jitter {
+            let nanos = backoff.as_nanos().min(u64::MAX as u128) as u64;
+            Duration::from_nanos(nanos - random() % (nanos / 2 + 1))
+        } else {
+            backoff
+        };
+        backoff.max(self.call_interval())
+    }
+
+    /// Makes a call, retrying it after a backoff while it fails with a transient error.
+    ///
+    /// ## Notes
+    ///
+    /// The first attempt is made immediately, so consecutive calls must be spaced by the caller to
+    /// stay within [`RetryPolicy::calls_per_second`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `call`: Makes an attempt of the call
+    /// * `sleep`: The sleep function of the async runtime
+    ///
+    /// ## Returns
+    ///
+    /// The result of the first attempt that succeeds or fails with an error that is not transient,
+    /// or the transient error of the last attempt once the retries are exhausted
+    #[inline]
+    pub async fn call<T, F, Fut, S, SFut>(&self, mut call: F, sleep: S) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+        S: Fn(Duration) -> SFut,
+        SFut: Future<Output = ()>,
+    {
+        let mut retry = 0;
+        loop {
+            match call().await {
+                Err(error) if retry < self.max_retries && error.is_transient() => {
+                    let backoff = self.backoff(retry);
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(retry, ?backoff, %error, "retrying after a transient error");
+                    sleep(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A pseudorandom number for the jitter by SplitMix64
+fn random() -> u64 {
+    static STATE: AtomicU64 = AtomicU64::new(0x853c_49e6_748f_ea9b);
+    let mut z = STATE
+        .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+        .wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{contract::Error as ContractError, transports::TransportErrorKind};
+    use core::cell::Cell;
+
+    fn transient_error() -> Error {
+        Error::ContractError(ContractError::TransportError(
+            TransportErrorKind::backend_gone(),
+        ))
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(100), Duration::from_secs(10));
+        let policy = RetryPolicy::default();
+        for retry in 0..8 {
+            let backoff = policy.backoff(retry);
+            let max = Duration::from_millis(100 << retry).min(Duration::from_secs(10));
+            assert!(backoff <= max && backoff >= max / 2, "{retry}");
+        }
+        let policy = RetryPolicy {
+            calls_per_second: NonZeroU32::new(4),
+            ..RetryPolicy::NONE
+        };
+        assert_eq!(policy.call_interval(), Duration::from_millis(250));
+        assert_eq!(policy.backoff(0), Duration::from_millis(250));
+        assert_eq!(RetryPolicy::NONE.call_interval(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_call() {
+        let policy = RetryPolicy::default();
+        let attempts = Cell::new(0);
+        let sleeps = Cell::new(0);
+        let sleep = |_: Duration| {
+            sleeps.set(sleeps.get() + 1);
+            core::future::ready(())
+        };
+        // succeeds on the third attempt
+        let result = policy
+            .call(
+                || {
+                    attempts.set(attempts.get() + 1);
+                    core::future::ready(if attempts.get() < 3 {
+                        Err(transient_error())
+                    } else {
+                        Ok(attempts.get())
+                    })
+                },
+                sleep,
+            )
+            .await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(sleeps.get(), 2);
+        // the retries are exhausted
+        attempts.set(0);
+        let result: Result<(), _> = policy
+            .call(
+                || {
+                    attempts.set(attempts.get() + 1);
+                    core::future::ready(Err(transient_error()))
+                },
+                sleep,
+            )
+            .await;
+        assert!(result.unwrap_err().is_transient());
+        assert_eq!(attempts.get(), 6);
+        // errors that are not transient are not retried
+        attempts.set(0);
+        let result: Result<(), _> = policy
+            .call(
+                || {
+                    attempts.set(attempts.get() + 1);
+                    core::future::ready(Err(Error::InvalidPrice))
+                },
+                sleep,
+            )
+            .await;
+        assert!(matches!(result, Err(Error::InvalidPrice)));
+        assert_eq!(attempts.get(), 1);
+    }
+}
@@ -0,0 +1,366 @@
+//! ## Router
+//! A simple smart order router that searches multi-hop routes between a token pair among a set of
+//! candidate pools and splits the input amount across routes to maximize the output.
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use num_traits::ToPrimitive;
+use uniswap_sdk_core::prelude::*;
+
+/// Configuration of the route search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouterConfig {
+    /// The maximum number of hops of a route
+    pub max_hops: usize,
+    /// The maximum number of routes to split the input amount across
+    pub max_splits: usize,
+    /// The granularity of the split in percent, e.g. 25 splits the amount in 25% increments and
+    /// 30 in 30% increments completed by a 10% or 40% remainder
+    pub distribution_percent: u8,
+}
+
+impl Default for RouterConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_hops: 3,
+            max_splits: 3,
+            distribution_percent: 25,
+        }
+    }
+}
+
+/// Enumerates all routes from the input token to the output token among the pools, without
+/// using a pool twice in the same route.
+///
+/// ## Arguments
+///
+/// * `pools`: The candidate pools
+/// * `token_in`: The input token
+/// * `token_out`: The output token
+/// * `max_hops`: The maximum number of hops of a route
+#[inline]
+pub fn compute_all_routes<TP>(
+    pools: &[Pool<TP>],
+    token_in: &Token,
+    token_out: &Token,
+    max_hops: usize,
+) -> Vec<Route<Token, Token, TP>>
+where
+    TP: Clone + TickDataProvider,
+{
+    fn visit<TP: Clone + TickDataProvider>(
+        pools: &[Pool<TP>],
+        current: &Token,
+        token_in: &Token,
+        token_out: &Token,
+        max_hops: usize,
+        path: &mut Vec<usize>,
+        routes: &mut Vec<Route<Token, Token, TP>>,
+    ) {
+        for (i, pool) in pools.iter().enumerate() {
+            if path.contains(&i) || !pool.involves_token(current) {
+                continue;
+            }
+            let next = if pool.token0.equals(current) {
+                &pool.token1
+            } else {
+                &pool.token0
+            };
+            path.push(i);
+            if next.equals(token_out) {
+                routes.push(Route::new(
+                    path.iter().map(|&j| pools[j].clone()).collect(),
+                    token_in.clone(),
+                    token_out.clone(),
+                ));
+            } else if path.len() < max_hops && !next.equals(token_in) {
+                visit(pools, next, token_in, token_out, max_hops, path, routes);
+            }
+            path.pop();
+        }
+    }
+
+    let mut routes = Vec::new();
+    if max_hops > 0 && !token_in.equals(token_out) {
+        visit(
+            pools,
+            token_in,
+            token_in,
+            token_out,
+            max_hops,
+            &mut Vec::with_capacity(max_hops),
+            &mut routes,
+        );
+    }
+    routes
+}
+
+/// The best combination of routes and percentages found so far by [`search_splits`].
+struct Split {
+    output: BigInt,
+    /// The route index and the percentage index of each split
+    legs: Vec<(usize, usize)>,
+}
+
+/// The quotes of every route for every percentage of the input amount, and for each of the
+/// rounding dusts the first leg of a split can receive.
+struct Quotes {
+    percents: Vec<u32>,
+    /// The quotes indexed by route, percentage and dust
+    quotes: Vec<Vec<Vec<Option<BigInt>>>>,
+    total: BigInt,
+}
+
+impl Quotes {
+    /// The output of a complete split, with the rounding dust given to the first leg
+    fn output(&self, legs: &[(usize, usize)]) -> Option<BigInt> {
+        let (&(i, k), rest) = legs.split_first()?;
+        let dust = split_dust(&self.total, legs.iter().map(|&(_, k)| self.percents[k]));
+        let first = self.quotes[i][k].get(dust.to_usize()?)?.as_ref()?;
+        rest.iter().try_fold(first.clone(), |output, &(i, k)| {
+            Some(output + self.quotes[i][k][0].as_ref()?)
+        })
+    }
+}
+
+fn search_splits<TP: TickDataProvider>(
+    routes: &[Route<Token, Token, TP>],
+    quotes: &Quotes,
+    remaining: u32,
+    start: usize,
+    max_splits: usize,
+    legs: &mut Vec<(usize, usize)>,
+    best: &mut Option<Split>,
+) {
+    if remaining == 0 {
+        let Some(output) = quotes.output(legs) else {
+            return;
+        };
+        if !matches!(best, Some(best) if output <= best.output) {
+            *best = Some(Split {
+                output,
+                legs: legs.clone(),
+            });
+        }
+        return;
+    }
+    if legs.len() == max_splits {
+        return;
+    }
+    for (i, route) in routes.iter().enumerate().skip(start) {
+        // routes in the same trade must not share a pool
+        if legs.iter().any(|&(j, _)| {
+            routes[j]
+                .pools
+                .iter()
+                .any(|used| route.pools.iter().any(|pool| pool == used))
+        }) {
+            continue;
+        }
+        for (k, &percent) in quotes.percents.iter().enumerate() {
+            if percent > remaining {
+                break;
+            }
+            if quotes.quotes[i][k].iter().all(Option::is_none) {
+                continue;
+            }
+            legs.push((i, k));
+            search_splits(
+                routes,
+                quotes,
+                remaining - percent,
+                i + 1,
+                max_splits,
+                legs,
+                best,
+            );
+            legs.pop();
+        }
+    }
+}
+
+/// Finds the trade that maximizes the output for an exact input amount by searching multi-hop
+/// routes among the candidate pools and splitting the amount across routes.
+///
+/// ## Arguments
+///
+/// * `pools`: The candidate pools
+/// * `amount_in`: The exact amount of input token to spend
+/// * `token_out`: The desired output token
+/// * `config`: The route search configuration
+///
+/// ## Notes
+///
+/// The amount is split in increments of [`RouterConfig::distribution_percent`], completed to 100%
+/// by a remainder if the increment does not divide 100, see [`split_percents`]. The rounding dust
+/// of a split goes to its first leg and is included in the quotes the splits are compared by.
+#[inline]
+pub fn best_split_trade_exact_in<TP>(
+    pools: &[Pool<TP>],
+    amount_in: &CurrencyAmount<Token>,
+    token_out: &Token,
+    config: RouterConfig,
+) -> Result<Trade<Token, Token, TP>, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let percents = split_percents(config.distribution_percent);
+    let routes = compute_all_routes(pools, &amount_in.currency, token_out, config.max_hops);
+    if routes.is_empty() {
+        return Err(Error::NoRouteFound);
+    }
+    let max_splits = config.max_splits.max(1);
+    let total = amount_in.quotient();
+
+    // quote every route for every percentage of the input amount plus every possible dust, which
+    // is less than the number of legs
+    let mut quotes = Vec::with_capacity(routes.len());
+    for route in &routes {
+        let mut route_quotes = Vec::with_capacity(percents.len());
+        for &percent in &percents {
+            let amount = split_amount(&total, percent);
+            let mut percent_quotes = Vec::with_capacity(max_splits);
+            for dust in 0..max_splits {
+                let amount = &amount + BigInt::from(dust);
+                if amount == BigInt::ZERO {
+                    percent_quotes.push(None);
+                    continue;
+                }
+                let amount = CurrencyAmount::from_raw_amount(amount_in.currency.clone(), amount)?;
+                let amount_out = route
+                    .pools
+                    .iter()
+                    .try_fold(amount, |amount, pool| pool.get_output_amount(&amount, None));
+                percent_quotes.push(match amount_out {
+                    Ok(amount_out) => Some(amount_out.quotient()),
+                    Err(Error::InsufficientLiquidity) => None,
+                    Err(e) => return Err(e),
+                });
+            }
+            route_quotes.push(percent_quotes);
+        }
+        quotes.push(route_quotes);
+    }
+    let quotes = Quotes {
+        percents,
+        quotes,
+        total,
+    };
+
+    let mut best = None;
+    search_splits(
+        &routes,
+        &quotes,
+        100,
+        0,
+        max_splits,
+        &mut Vec::with_capacity(max_splits),
+        &mut best,
+    );
+    let Some(best) = best else {
+        return Err(Error::InsufficientLiquidity);
+    };
+
+    let leg_percents: Vec<u32> = best.legs.iter().map(|&(_, k)| quotes.percents[k]).collect();
+    let amounts = split_amounts(&quotes.total, &leg_percents);
+    let mut splits = Vec::with_capacity(best.legs.len());
+    for ((i, _), amount) in best.legs.into_iter().zip(amounts) {
+        splits.push((
+            CurrencyAmount::from_raw_amount(amount_in.currency.clone(), amount)?,
+            routes[i].clone(),
+        ));
+    }
+    Trade::from_routes(splits, TradeType::ExactInput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_compute_all_routes() {
+        let pools = vec![
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            make_pool(TOKEN0.clone(), TOKEN2.clone()),
+            make_pool(TOKEN2.clone(), TOKEN1.clone()),
+            make_pool(TOKEN1.clone(), TOKEN3.clone()),
+        ];
+        let routes = compute_all_routes(&pools, &TOKEN0, &TOKEN1, 3);
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].pools.len(), 1);
+        assert_eq!(routes[1].pools.len(), 2);
+        assert_eq!(compute_all_routes(&pools, &TOKEN0, &TOKEN1, 1).len(), 1);
+        assert_eq!(compute_all_routes(&pools, &TOKEN0, &TOKEN3, 3).len(), 2);
+    }
+
+    #[test]
+    fn test_best_split_trade_exact_in() {
+        let pools = vec![
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            make_pool(TOKEN0.clone(), TOKEN2.clone()),
+            make_pool(TOKEN2.clone(), TOKEN1.clone()),
+        ];
+        let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let trade = best_split_trade_exact_in(&pools, &amount_in, &TOKEN1, RouterConfig::default())
+            .unwrap();
+        assert_eq!(trade.swaps.len(), 2);
+        assert_eq!(
+            trade.input_amount().unwrap().quotient(),
+            amount_in.quotient()
+        );
+
+        let mut best_trades = vec![];
+        Trade::best_trade_exact_in(
+            pools,
+            &amount_in,
+            &TOKEN1,
+            BestTradeOptions::default(),
+            vec![],
+            None,
+            &mut best_trades,
+        )
+        .unwrap();
+        assert!(
+            trade.output_amount().unwrap().quotient()
+                > best_trades[0].output_amount().unwrap().quotient()
+        );
+    }
+
+    #[test]
+    fn test_best_split_trade_exact_in_uneven_distribution() {
+        let pools = vec![
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            make_pool(TOKEN0.clone(), TOKEN2.clone()),
+            make_pool(TOKEN2.clone(), TOKEN1.clone()),
+        ];
+        let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_001).unwrap();
+        let trade = best_split_trade_exact_in(
+            &pools,
+            &amount_in,
+            &TOKEN1,
+            RouterConfig {
+                distribution_percent: 30,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // 30% increments still combine with a remainder
+        assert_eq!(trade.swaps.len(), 2);
+        assert_eq!(
+            trade.input_amount().unwrap().quotient(),
+            amount_in.quotient()
+        );
+    }
+
+    #[test]
+    fn test_best_split_trade_exact_in_no_route() {
+        let pools = vec![make_pool(TOKEN0.clone(), TOKEN1.clone())];
+        let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+        assert!(matches!(
+            best_split_trade_exact_in(&pools, &amount_in, &TOKEN2, RouterConfig::default()),
+            Err(Error::NoRouteFound)
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+//! ## Slippage Curve
+//! Quotes a pool or a route for many input amounts and returns the output and price impact of each
+//! size. The amounts are simulated in increasing order, and each simulation resumes from the last
+//! tick crossed by the previous one instead of starting over from the current price.
+
+use crate::{
+    entities::pool::after_transfer_fee,
+    prelude::{Error, *},
+};
+use alloc::vec::Vec;
+use alloy_primitives::{I256, U160, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// A point of a slippage curve: the input amount, the output amount and the price impact
+pub type SlippagePoint<TInput, TOutput> =
+    (CurrencyAmount<TInput>, CurrencyAmount<TOutput>, Percent);
+
+/// The state of a pool at the last tick crossed by the previous simulation, with the amounts
+/// swapped to reach it
+#[derive(Clone, Copy, Debug)]
+struct Checkpoint<I> {
+    sqrt_price_x96: U160,
+    tick_current: I,
+    liquidity: u128,
+    amount_in: U256,
+    amount_out: U256,
+}
+
+impl<I: TickIndex> Checkpoint<I> {
+    fn new<TP: TickDataProvider<Index = I>>(pool: &Pool<TP>) -> Self {
+        Self {
+            sqrt_price_x96: pool.sqrt_ratio_x96,
+            tick_current: pool.tick_current,
+            liquidity: pool.liquidity,
+            amount_in: U256::ZERO,
+            amount_out: U256::ZERO,
+        }
+    }
+
+    /// Simulates an exact input swap of `amount_in` from the current price, resuming from the
+    /// checkpoint, which must have been reached by a swap of at most `amount_in`, and advances the
+    /// checkpoint. Returns `None` if the pool cannot fill the swap.
+    ///
+    /// Every step of a swap but the last one swaps to the next tick, which a larger swap does as
+    /// well, so that resuming from the start of the last step is exact.
+    fn output<TP: TickDataProvider<Index = I>>(
+        &mut self,
+        pool: &Pool<TP>,
+        zero_for_one: bool,
+        amount_in: U256,
+    ) -> Result<Option<U256>, Error> {
+        let SwapTrace { steps, state } = v3_swap_with_trace(
+            pool.fee.into(),
+            self.sqrt_price_x96,
+            self.tick_current,
+            self.liquidity,
+            pool.tick_spacing(),
+            &pool.tick_data_provider,
+            zero_for_one,
+            I256::from_raw(amount_in - self.amount_in),
+            None,
+        )?;
+        if !state.amount_specified_remaining.is_zero() {
+            return Ok(None);
+        }
+        let amount_out = self.amount_out + (-state.amount_calculated).into_raw();
+        if let Some((_, full_steps)) = steps.split_last() {
+            for step in full_steps {
+                self.sqrt_price_x96 = step.sqrt_price_x96;
+                self.tick_current = if zero_for_one {
+                    step.tick_next - I::ONE
+                } else {
+                    step.tick_next
+                };
+                self.liquidity = step.liquidity;
+                self.amount_in += step.amount_in + step.fee_amount;
+                self.amount_out += step.amount_out;
+            }
+        }
+        Ok(Some(amount_out))
+    }
+}
+
+/// The price impact of a swap relative to the output at the mid price
+fn price_impact<T: BaseCurrency>(
+    spot_output_amount: &CurrencyAmount<T>,
+    output_amount: &CurrencyAmount<T>,
+) -> Result<Percent, Error> {
+    if spot_output_amount.quotient().is_zero() {
+        return Ok(Percent::default());
+    }
+    let price_impact = spot_output_amount
+        .subtract(output_amount)?
+        .divide(spot_output_amount)?;
+    Ok(Percent::new(
+        price_impact.numerator,
+        price_impact.denominator,
+    ))
+}
+
+/// Sorts the amounts in ascending order
+fn sorted<T: BaseCurrency>(amounts: &[CurrencyAmount<T>]) -> Vec<&CurrencyAmount<T>> {
+    let mut amounts: Vec<_> = amounts.iter().collect();
+    amounts.sort_by_key(|amount| amount.quotient());
+    amounts
+}
+
+impl<TP: TickDataProvider> Pool<TP> {
+    /// Computes the output amount and the price impact of an exact input swap of each amount.
+    ///
+    /// ## Notes
+    ///
+    /// The points are sorted by input amount, and the amounts that the pool cannot fill are left
+    /// out. The outputs match [`Pool::get_output_amount_with_transfer_fees`], i.e. net of the
+    /// transfer fees of the tokens.
+    ///
+    /// ## Arguments
+    ///
+    /// * `amounts`: The input amounts, all of the same token of the pool
+    #[inline]
+    pub fn slippage_curve(
+        &self,
+        amounts: &[CurrencyAmount<Token>],
+    ) -> Result<Vec<SlippagePoint<Token, Token>>, Error> {
+        let Some(first) = amounts.first() else {
+            return Ok(Vec::new());
+        };
+        if !self.involves_token(&first.currency) {
+            return Err(Error::InvalidToken);
+        }
+        let zero_for_one = first.currency.equals(&self.token0);
+        let (input_token, output_token) = if zero_for_one {
+            (&self.token0, &self.token1)
+        } else {
+            (&self.token1, &self.token0)
+        };
+        let mid_price = self.price_of(input_token)?;
+        let mut checkpoint = Checkpoint::new(self);
+        let mut curve = Vec::with_capacity(amounts.len());
+        for amount in sorted(amounts) {
+            if !amount.currency.equals(input_token) {
+                return Err(Error::InvalidToken);
+            }
+            let amount_in =
+                after_transfer_fee(amount.quotient(), input_token.sell_fee_bps.as_ref());
+            let Some(amount_out) =
+                checkpoint.output(self, zero_for_one, U256::from_big_int(amount_in))?
+            else {
+                break;
+            };
+            let amount_out = CurrencyAmount::from_raw_amount(
+                output_token.clone(),
+                after_transfer_fee(amount_out.to_big_int(), output_token.buy_fee_bps.as_ref()),
+            )?;
+            let impact = price_impact(&mid_price.quote(amount)?, &amount_out)?;
+            curve.push((amount.clone(), amount_out, impact));
+        }
+        Ok(curve)
+    }
+}
+
+impl<TInput, TOutput, TP> Route<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    /// Computes the output amount and the price impact of an exact input trade of each amount
+    /// along the route.
+    ///
+    /// ## Notes
+    ///
+    /// The points are sorted by input amount, and the amounts that the route cannot fill are left
+    /// out. The outputs match [`Trade::from_route`] for tokens without transfer fees, and are net
+    /// of the transfer fees of the tokens like [`Pool::get_output_amount_with_transfer_fees`]
+    /// otherwise.
+    ///
+    /// ## Arguments
+    ///
+    /// * `amounts`: The input amounts
+    #[inline]
+    pub fn slippage_curve(
+        &self,
+        amounts: &[CurrencyAmount<TInput>],
+    ) -> Result<Vec<SlippagePoint<TInput, TOutput>>, Error> {
+        let token_path = self.token_path();
+        let mid_price = self.mid_price()?;
+        let mut checkpoints: Vec<_> = self.pools.iter().map(Checkpoint::new).collect();
+        let mut curve = Vec::with_capacity(amounts.len());
+        'amounts: for amount in sorted(amounts) {
+            let mut amount_out = amount.quotient();
+            for ((pool, checkpoint), tokens) in self
+                .pools
+                .iter()
+                .zip(&mut checkpoints)
+                .zip(token_path.windows(2))
+            {
+                let (input_token, output_token) = (&tokens[0], &tokens[1]);
+                let amount_in = after_transfer_fee(amount_out, input_token.sell_fee_bps.as_ref());
+                let Some(raw_amount_out) = checkpoint.output(
+                    pool,
+                    input_token.equals(&pool.token0),
+                    U256::from_big_int(amount_in),
+                )?
+                else {
+                    break 'amounts;
+                };
+                amount_out = after_transfer_fee(
+                    raw_amount_out.to_big_int(),
+                    output_token.buy_fee_bps.as_ref(),
+                );
+            }
+            let amount_out = CurrencyAmount::from_raw_amount(self.output.clone(), amount_out)?;
+            let impact = price_impact(&mid_price.quote(amount)?, &amount_out)?;
+            curve.push((amount.clone(), amount_out, impact));
+        }
+        Ok(curve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn pool(token0: &Token, token1: &Token) -> Pool<TickListDataProvider> {
+        Pool::new_with_tick_data_provider(
+            token0.clone(),
+            token1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            1_000_000,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(-887220, 1_000_000, 1_000_000),
+                    Tick::new(-120, 4_000_000, 4_000_000),
+                    Tick::new(-60, 2_000_000, 2_000_000),
+                    Tick::new(60, 2_000_000, -2_000_000),
+                    Tick::new(120, 4_000_000, -4_000_000),
+                    Tick::new(887220, 1_000_000, -1_000_000),
+                ],
+                60,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pool_slippage_curve() {
+        let pool = pool(&TOKEN0, &TOKEN1);
+        let amounts: Vec<_> = [100_000, 10, 1_000, 20_000, 50_000, 1_000_000, 10_000]
+            .into_iter()
+            .map(|amount| CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount).unwrap())
+            .collect();
+        let curve = pool.slippage_curve(&amounts).unwrap();
+        assert_eq!(curve.len(), amounts.len());
+        let mut last_impact = Percent::default();
+        for (amount_in, amount_out, impact) in &curve {
+            // each point matches a simulation from the current price
+            let expected = pool
+                .get_output_amount_with_transfer_fees(amount_in, None)
+                .unwrap();
+            assert_eq!(amount_out.quotient(), expected.quotient());
+            assert!(impact >= &last_impact);
+            last_impact = impact.clone();
+        }
+        assert!(curve
+            .windows(2)
+            .all(|w| w[0].0.quotient() < w[1].0.quotient()));
+        // the pool cannot fill more than its liquidity
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10u128.pow(30)).unwrap();
+        assert_eq!(
+            pool.slippage_curve(&[amounts[0].clone(), amount])
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_route_slippage_curve() {
+        let route = Route::new(
+            vec![pool(&TOKEN0, &TOKEN1), pool(&TOKEN1, &TOKEN2)],
+            TOKEN0.clone(),
+            TOKEN2.clone(),
+        );
+        let amounts: Vec<_> = [10, 1_000, 10_000, 50_000, 200_000]
+            .into_iter()
+            .map(|amount| CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount).unwrap())
+            .collect();
+        let curve = route.slippage_curve(&amounts).unwrap();
+        assert_eq!(curve.len(), amounts.len());
+        for (amount_in, amount_out, impact) in curve {
+            let trade = Trade::from_route(route.clone(), amount_in, TradeType::ExactInput).unwrap();
+            assert_eq!(
+                amount_out.quotient(),
+                trade.output_amount().unwrap().quotient()
+            );
+            assert_eq!(impact, trade.price_impact().unwrap());
+        }
+    }
+}
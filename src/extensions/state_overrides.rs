@@ -4,7 +4,10 @@
 use crate::prelude::Error;
 use alloc::vec::Vec;
 use alloy::{
-    eips::eip2930::{AccessList, AccessListItem},
+    eips::{
+        eip2930::{AccessList, AccessListItem},
+        BlockId, BlockNumberOrTag,
+    },
     network::{Network, TransactionBuilder},
     providers::Provider,
     rpc::types::state::{AccountOverride, StateOverride},
@@ -16,13 +19,26 @@ use alloy_primitives::{
 use alloy_sol_types::SolCall;
 use uniswap_lens::bindings::ierc20::IERC20;
 
+/// Generate the state overrides that set the balance of an owner and its allowance to a spender of
+/// an ERC20 token, found from the storage slots in the access lists of `balanceOf` and
+/// `allowance`.
+///
+/// ## Arguments
+///
+/// * `token`: The ERC20 token
+/// * `owner`: The owner of the balance
+/// * `spender`: The spender of the allowance
+/// * `amount`: The balance and allowance to set
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
 #[inline]
 pub async fn get_erc20_state_overrides<N, P>(
     token: Address,
     owner: Address,
     spender: Address,
     amount: U256,
-    provider: &P,
+    provider: P,
+    block_id: Option<BlockId>,
 ) -> Result<StateOverride, Error>
 where
     N: Network,
@@ -36,9 +52,15 @@ where
         .with_to(token)
         .with_gas_limit(0x11E1A300)
         .with_input(IERC20::allowanceCall { owner, spender }.abi_encode());
-    let balance_access_list = provider.create_access_list(&balance_tx).await?.access_list;
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let balance_access_list = provider
+        .create_access_list(&balance_tx)
+        .block_id(block_id)
+        .await?
+        .access_list;
     let allowance_access_list = provider
         .create_access_list(&allowance_tx)
+        .block_id(block_id)
         .await?
         .access_list;
     // tokens on L2 and those with a proxy will have more than one access list entry
@@ -101,9 +123,10 @@ mod tests {
         let owner = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
         let npm = *NONFUNGIBLE_POSITION_MANAGER_ADDRESSES.get(&1).unwrap();
         let amount = U256::from(1_000_000);
-        let overrides = get_erc20_state_overrides(USDC.address(), owner, npm, amount, &provider)
-            .await
-            .unwrap();
+        let overrides =
+            get_erc20_state_overrides(USDC.address(), owner, npm, amount, &provider, None)
+                .await
+                .unwrap();
         let usdc = IERC20::new(USDC.address(), provider);
         let balance = usdc
             .balanceOf(owner)
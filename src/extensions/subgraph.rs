@@ -0,0 +1,953 @@
+//! ## Subgraph
+//! A client for the [Uniswap V3 subgraph](https://github.com/Uniswap/v3-subgraph) with typed
+//! queries for the daily data, volume and fees of a pool, the snapshot of its ticks, its swaps and
+//! the positions of an owner or in a pool, returning SDK types where possible.
+
+use crate::prelude::{Error, *};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy::transports::http::reqwest::{Client, Url};
+use alloy_primitives::{aliases::I24, Address, ChainId, I256, U160, U256};
+use core::str::FromStr;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use uniswap_sdk_core::{prelude::*, token};
+
+/// The maximum number of entities returned by a subgraph query
+const PAGE_SIZE: usize = 1000;
+
+/// A client for a deployment of the Uniswap V3 subgraph.
+#[derive(Clone, Debug)]
+pub struct SubgraphClient {
+    pub url: Url,
+    client: Client,
+}
+
+/// The data of a pool aggregated over a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolDayData {
+    /// The timestamp of the start of the day
+    pub date: u64,
+    /// The active liquidity at the end of the day
+    pub liquidity: u128,
+    /// The sqrt price at the end of the day
+    pub sqrt_price_x96: U160,
+    /// The tick at the end of the day, if the pool is initialized
+    pub tick: Option<I24>,
+    /// The price of token0 in token1 at the end of the day, adjusted for decimals
+    pub token0_price: BigDecimal,
+    /// The price of token1 in token0 at the end of the day, adjusted for decimals
+    pub token1_price: BigDecimal,
+    pub volume_token0: BigDecimal,
+    pub volume_token1: BigDecimal,
+    pub volume_usd: BigDecimal,
+    pub fees_usd: BigDecimal,
+    pub tvl_usd: BigDecimal,
+    pub tx_count: u64,
+    /// The opening price of token0 in token1
+    pub open: BigDecimal,
+    /// The highest price of token0 in token1
+    pub high: BigDecimal,
+    /// The lowest price of token0 in token1
+    pub low: BigDecimal,
+    /// The closing price of token0 in token1
+    pub close: BigDecimal,
+}
+
+/// The cumulative volume and fees of a pool since its creation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolVolume {
+    pub volume_token0: BigDecimal,
+    pub volume_token1: BigDecimal,
+    pub volume_usd: BigDecimal,
+    pub fees_usd: BigDecimal,
+    pub tvl_usd: BigDecimal,
+    pub tx_count: u64,
+}
+
+/// A position of the nonfungible position manager as indexed by the subgraph.
+#[derive(Clone, Debug)]
+pub struct SubgraphPosition {
+    pub token_id: U256,
+    pub owner: Address,
+    pub pool: Address,
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    pub liquidity: u128,
+    /// The amount of token0 deposited over the life of the position, adjusted for decimals
+    pub deposited_token0: BigDecimal,
+    /// The amount of token1 deposited over the life of the position, adjusted for decimals
+    pub deposited_token1: BigDecimal,
+    /// The amount of token0 withdrawn over the life of the position, adjusted for decimals
+    pub withdrawn_token0: BigDecimal,
+    /// The amount of token1 withdrawn over the life of the position, adjusted for decimals
+    pub withdrawn_token1: BigDecimal,
+    /// The fees of token0 collected over the life of the position, adjusted for decimals
+    pub collected_fees_token0: BigDecimal,
+    /// The fees of token1 collected over the life of the position, adjusted for decimals
+    pub collected_fees_token1: BigDecimal,
+    /// The position in the pool at its price and liquidity at the time of the query, unless the
+    /// pool is not initialized or the ticks are not aligned to the tick spacing of its fee tier
+    pub position: Option<Position>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPoolDayData {
+    date: u64,
+    liquidity: String,
+    sqrt_price: String,
+    tick: Option<String>,
+    token0_price: String,
+    token1_price: String,
+    volume_token0: String,
+    volume_token1: String,
+    #[serde(rename = "volumeUSD")]
+    volume_usd: String,
+    #[serde(rename = "feesUSD")]
+    fees_usd: String,
+    #[serde(rename = "tvlUSD")]
+    tvl_usd: String,
+    tx_count: String,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPoolVolume {
+    volume_token0: String,
+    volume_token1: String,
+    #[serde(rename = "volumeUSD")]
+    volume_usd: String,
+    #[serde(rename = "feesUSD")]
+    fees_usd: String,
+    #[serde(rename = "totalValueLockedUSD")]
+    tvl_usd: String,
+    tx_count: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTick {
+    tick_idx: String,
+    liquidity_gross: String,
+    liquidity_net: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSwap {
+    id: String,
+    timestamp: String,
+    transaction: RawTransaction,
+    log_index: String,
+    amount0: String,
+    amount1: String,
+    #[serde(rename = "sqrtPriceX96")]
+    sqrt_price_x96: String,
+    tick: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTransaction {
+    block_number: String,
+}
+
+#[derive(Deserialize)]
+struct RawToken {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    decimals: String,
+}
+
+#[derive(Deserialize)]
+struct RawPoolTokens {
+    token0: RawToken,
+    token1: RawToken,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPoolHourData {
+    period_start_unix: u64,
+    liquidity: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTickIdx {
+    tick_idx: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPositionPool {
+    id: String,
+    fee_tier: String,
+    sqrt_price: String,
+    liquidity: String,
+    token0: RawToken,
+    token1: RawToken,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPosition {
+    id: String,
+    owner: String,
+    pool: RawPositionPool,
+    tick_lower: RawTickIdx,
+    tick_upper: RawTickIdx,
+    liquidity: String,
+    deposited_token0: String,
+    deposited_token1: String,
+    withdrawn_token0: String,
+    withdrawn_token1: String,
+    collected_fees_token0: String,
+    collected_fees_token1: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PoolDayDatas {
+    pool_day_datas: Vec<RawPoolDayData>,
+}
+
+#[derive(Deserialize)]
+struct PoolEntity {
+    pool: Option<RawPoolVolume>,
+}
+
+#[derive(Deserialize)]
+struct Ticks {
+    ticks: Vec<RawTick>,
+}
+
+#[derive(Deserialize)]
+struct Swaps {
+    swaps: Vec<RawSwap>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PoolHourDatas {
+    pool_hour_datas: Vec<RawPoolHourData>,
+}
+
+#[derive(Deserialize)]
+struct PoolTokens {
+    pool: Option<RawPoolTokens>,
+}
+
+#[derive(Deserialize)]
+struct Positions {
+    positions: Vec<RawPosition>,
+}
+
+/// Parses a number returned by the subgraph as a string
+fn parse<T: FromStr>(value: &str) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::SubgraphError(format!("invalid number: {value}")))
+}
+
+/// Parses an address returned by the subgraph
+fn parse_address(value: &str) -> Result<Address, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::SubgraphError(format!("invalid address: {value}")))
+}
+
+/// Parses the body of a GraphQL response, failing on any error returned by the subgraph
+fn parse_response<T: DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    let response: GraphQlResponse<T> =
+        serde_json::from_slice(body).map_err(|e| Error::SubgraphError(e.to_string()))?;
+    if let Some(error) = response.errors.and_then(|errors| errors.into_iter().next()) {
+        return Err(Error::SubgraphError(error.message));
+    }
+    response
+        .data
+        .ok_or_else(|| Error::SubgraphError("missing data".to_string()))
+}
+
+/// Parses a tick index returned by the subgraph
+fn parse_tick(value: &str) -> Result<I24, Error> {
+    let tick: I24 = parse(value)?;
+    if tick < MIN_TICK || tick > MAX_TICK {
+        return Err(Error::InvalidTick(tick));
+    }
+    Ok(tick)
+}
+
+impl RawPoolDayData {
+    fn parse(self) -> Result<PoolDayData, Error> {
+        Ok(PoolDayData {
+            date: self.date,
+            liquidity: parse(&self.liquidity)?,
+            sqrt_price_x96: parse(&self.sqrt_price)?,
+            tick: self.tick.as_deref().map(parse_tick).transpose()?,
+            token0_price: parse(&self.token0_price)?,
+            token1_price: parse(&self.token1_price)?,
+            volume_token0: parse(&self.volume_token0)?,
+            volume_token1: parse(&self.volume_token1)?,
+            volume_usd: parse(&self.volume_usd)?,
+            fees_usd: parse(&self.fees_usd)?,
+            tvl_usd: parse(&self.tvl_usd)?,
+            tx_count: parse(&self.tx_count)?,
+            open: parse(&self.open)?,
+            high: parse(&self.high)?,
+            low: parse(&self.low)?,
+            close: parse(&self.close)?,
+        })
+    }
+}
+
+impl RawPoolVolume {
+    fn parse(self) -> Result<PoolVolume, Error> {
+        Ok(PoolVolume {
+            volume_token0: parse(&self.volume_token0)?,
+            volume_token1: parse(&self.volume_token1)?,
+            volume_usd: parse(&self.volume_usd)?,
+            fees_usd: parse(&self.fees_usd)?,
+            tvl_usd: parse(&self.tvl_usd)?,
+            tx_count: parse(&self.tx_count)?,
+        })
+    }
+}
+
+impl RawTick {
+    fn parse<I: TickIndex>(self) -> Result<Tick<I>, Error> {
+        Ok(Tick::new(
+            I::from_i24(parse_tick(&self.tick_idx)?),
+            parse(&self.liquidity_gross)?,
+            parse(&self.liquidity_net)?,
+        ))
+    }
+}
+
+impl RawToken {
+    fn parse(&self, chain_id: ChainId) -> Result<Token, Error> {
+        let address = parse_address(self.id.as_deref().unwrap_or_default())?;
+        Ok(token!(
+            chain_id,
+            address,
+            parse::<u8>(&self.decimals)?,
+            self.symbol.clone().unwrap_or_default(),
+            self.name.clone().unwrap_or_default()
+        ))
+    }
+}
+
+impl RawPositionPool {
+    /// Parses the pool, or `None` if it is not initialized
+    fn parse(&self, chain_id: ChainId) -> Result<Option<Pool>, Error> {
+        let sqrt_price_x96: U160 = parse(&self.sqrt_price)?;
+        if sqrt_price_x96.is_zero() {
+            return Ok(None);
+        }
+        Pool::new(
+            self.token0.parse(chain_id)?,
+            self.token1.parse(chain_id)?,
+            FeeAmount::from(parse::<u32>(&self.fee_tier)?),
+            sqrt_price_x96,
+            parse(&self.liquidity)?,
+        )
+        .map(Some)
+    }
+}
+
+impl RawPosition {
+    fn parse(self, chain_id: ChainId) -> Result<SubgraphPosition, Error> {
+        let tick_lower = parse_tick(&self.tick_lower.tick_idx)?;
+        let tick_upper = parse_tick(&self.tick_upper.tick_idx)?;
+        let liquidity = parse(&self.liquidity)?;
+        let position = self.pool.parse(chain_id)?.and_then(|pool| {
+            let tick_spacing = pool.tick_spacing();
+            let (tick_lower, tick_upper) = (tick_lower.as_i32(), tick_upper.as_i32());
+            (tick_spacing > 0
+                && tick_lower < tick_upper
+                && tick_lower % tick_spacing == 0
+                && tick_upper % tick_spacing == 0)
+                .then(|| Position::new(pool, liquidity, tick_lower, tick_upper))
+        });
+        Ok(SubgraphPosition {
+            token_id: parse(&self.id)?,
+            owner: parse_address(&self.owner)?,
+            pool: parse_address(&self.pool.id)?,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            deposited_token0: parse(&self.deposited_token0)?,
+            deposited_token1: parse(&self.deposited_token1)?,
+            withdrawn_token0: parse(&self.withdrawn_token0)?,
+            withdrawn_token1: parse(&self.withdrawn_token1)?,
+            collected_fees_token0: parse(&self.collected_fees_token0)?,
+            collected_fees_token1: parse(&self.collected_fees_token1)?,
+            position,
+        })
+    }
+}
+
+/// Parses a decimal token amount returned by the subgraph into its raw amount
+fn parse_amount(value: &str, decimals: i64) -> Result<I256, Error> {
+    let amount: BigDecimal = parse(value)?;
+    let raw = (amount * BigDecimal::new(BigInt::from(1), -decimals)).with_scale(0);
+    Ok(I256::from_big_int(raw.into_bigint_and_exponent().0))
+}
+
+impl RawSwap {
+    /// Parses a swap, with the liquidity of the pool at the end of the hour of the swap
+    fn parse(
+        &self,
+        decimals0: i64,
+        decimals1: i64,
+        hour_datas: &[(u64, u128)],
+    ) -> Result<SwapEvent, Error> {
+        let timestamp: u64 = parse(&self.timestamp)?;
+        // the liquidity of the last hour starting at or before the swap
+        let liquidity = match hour_datas.partition_point(|&(start, _)| start <= timestamp) {
+            0 => 0,
+            i => hour_datas[i - 1].1,
+        };
+        Ok(SwapEvent {
+            block_number: parse(&self.transaction.block_number)?,
+            timestamp,
+            amount0: parse_amount(&self.amount0, decimals0)?,
+            amount1: parse_amount(&self.amount1, decimals1)?,
+            sqrt_price_x96: parse(&self.sqrt_price_x96)?,
+            liquidity,
+            tick: parse_tick(&self.tick)?,
+        })
+    }
+}
+
+impl SubgraphClient {
+    /// Creates a client for the subgraph deployed at a URL, including the API key if any
+    #[inline]
+    #[must_use]
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    /// Sends a GraphQL query to the subgraph and decodes its data
+    ///
+    /// ## Arguments
+    ///
+    /// * `query`: The GraphQL query
+    /// * `variables`: The variables of the query
+    #[inline]
+    pub async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, Error> {
+        let body = self
+            .client
+            .post(self.url.clone())
+            .header("content-type", "application/json")
+            .body(json!({ "query": query, "variables": variables }).to_string())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::SubgraphError(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| Error::SubgraphError(e.to_string()))?;
+        parse_response(&body)
+    }
+
+    /// Fetches the daily data of a pool, from the most recent day
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `days`: The number of days to fetch, at most 1000
+    #[inline]
+    pub async fn get_pool_day_data(
+        &self,
+        pool: Address,
+        days: usize,
+    ) -> Result<Vec<PoolDayData>, Error> {
+        assert!(days <= PAGE_SIZE, "DAYS");
+        let data: PoolDayDatas = self
+            .query(
+                r"query PoolDayDatas($pool: String!, $first: Int!) {
+                    poolDayDatas(
+                        first: $first
+                        where: { pool: $pool }
+                        orderBy: date
+                        orderDirection: desc
+                    ) {
+                        date liquidity sqrtPrice tick token0Price token1Price volumeToken0
+                        volumeToken1 volumeUSD feesUSD tvlUSD txCount open high low close
+                    }
+                }",
+                json!({ "pool": format!("{pool:#x}"), "first": days }),
+            )
+            .await?;
+        data.pool_day_datas
+            .into_iter()
+            .map(RawPoolDayData::parse)
+            .collect()
+    }
+
+    /// Fetches the cumulative volume and fees of a pool
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `block_number`: Optional block number to query
+    #[inline]
+    pub async fn get_pool_volume(
+        &self,
+        pool: Address,
+        block_number: Option<u64>,
+    ) -> Result<PoolVolume, Error> {
+        let data: PoolEntity = self
+            .query(
+                &format!(
+                    r"query Pool($pool: ID!) {{
+                        pool(id: $pool{}) {{
+                            volumeToken0 volumeToken1 volumeUSD feesUSD totalValueLockedUSD txCount
+                        }}
+                    }}",
+                    block_filter(block_number)
+                ),
+                json!({ "pool": format!("{pool:#x}") }),
+            )
+            .await?;
+        data.pool.ok_or(Error::PoolNotFound)?.parse()
+    }
+
+    /// Fetches the initialized ticks of a pool, in ascending order, e.g. to build a [`TickMap`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `block_number`: Optional block number to query
+    #[inline]
+    pub async fn get_ticks<I: TickIndex>(
+        &self,
+        pool: Address,
+        block_number: Option<u64>,
+    ) -> Result<Vec<Tick<I>>, Error> {
+        let query = format!(
+            r"query Ticks($pool: String!, $cursor: BigInt!, $first: Int!) {{
+                ticks(
+                    first: $first
+                    where: {{ pool: $pool, tickIdx_gt: $cursor, liquidityGross_gt: 0 }}
+                    orderBy: tickIdx
+                    orderDirection: asc{}
+                ) {{
+                    tickIdx liquidityGross liquidityNet
+                }}
+            }}",
+            block_filter(block_number)
+        );
+        let mut ticks: Vec<Tick<I>> = Vec::new();
+        let mut cursor = MIN_TICK_I32 - 1;
+        loop {
+            let page: Ticks = self
+                .query(
+                    &query,
+                    json!({
+                        "pool": format!("{pool:#x}"),
+                        "cursor": cursor.to_string(),
+                        "first": PAGE_SIZE,
+                    }),
+                )
+                .await?;
+            let len = page.ticks.len();
+            for tick in page.ticks {
+                let tick = tick.parse::<I>()?;
+                cursor = tick.index.to_i24().as_i32();
+                ticks.push(tick);
+            }
+            if len < PAGE_SIZE {
+                return Ok(ticks);
+            }
+        }
+    }
+
+    /// Fetches the swaps of a pool in chronological order, e.g. to [`backtest`] a strategy.
+    ///
+    /// ## Notes
+    ///
+    /// The subgraph records neither the raw amounts nor the liquidity of the swaps. The amounts are
+    /// converted with the decimals of the tokens and the liquidity is the active liquidity at the
+    /// end of the hour of each swap.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool address
+    /// * `timestamp_start`: The timestamp of the first swap, inclusive
+    /// * `timestamp_end`: The timestamp of the last swap, exclusive
+    #[inline]
+    pub async fn get_swaps(
+        &self,
+        pool: Address,
+        timestamp_start: u64,
+        timestamp_end: u64,
+    ) -> Result<Vec<SwapEvent>, Error> {
+        let pool_id = format!("{pool:#x}");
+        let tokens: PoolTokens = self
+            .query(
+                r"query Pool($pool: ID!) {
+                    pool(id: $pool) { token0 { decimals } token1 { decimals } }
+                }",
+                json!({ "pool": pool_id }),
+            )
+            .await?;
+        let tokens = tokens.pool.ok_or(Error::PoolNotFound)?;
+        let decimals0: i64 = parse(&tokens.token0.decimals)?;
+        let decimals1: i64 = parse(&tokens.token1.decimals)?;
+
+        let query = r"query Swaps($pool: String!, $cursor: BigInt!, $end: BigInt!, $first: Int!) {
+            swaps(
+                first: $first
+                where: { pool: $pool, timestamp_gte: $cursor, timestamp_lt: $end }
+                orderBy: timestamp
+                orderDirection: asc
+            ) {
+                id timestamp logIndex amount0 amount1 sqrtPriceX96 tick
+                transaction { blockNumber }
+            }
+        }";
+        let mut raw_swaps: Vec<RawSwap> = Vec::new();
+        let mut cursor = timestamp_start;
+        loop {
+            let page: Swaps = self
+                .query(
+                    query,
+                    json!({
+                        "pool": pool_id,
+                        "cursor": cursor.to_string(),
+                        "end": timestamp_end.to_string(),
+                        "first": PAGE_SIZE,
+                    }),
+                )
+                .await?;
+            let len = page.swaps.len();
+            // the pages overlap at the timestamp of the cursor
+            let seen = raw_swaps.len();
+            for swap in page.swaps {
+                if raw_swaps[..seen].iter().all(|raw| raw.id != swap.id) {
+                    raw_swaps.push(swap);
+                }
+            }
+            let next_cursor = match raw_swaps.last() {
+                Some(swap) => parse(&swap.timestamp)?,
+                None => cursor,
+            };
+            if len < PAGE_SIZE || next_cursor == cursor {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        // the liquidity at the end of the hours of the swaps
+        let mut hours: Vec<u64> = raw_swaps
+            .iter()
+            .map(|swap| parse::<u64>(&swap.timestamp).map(|timestamp| timestamp / 3600 * 3600))
+            .collect::<Result<_, _>>()?;
+        hours.dedup();
+        let mut hour_datas: Vec<(u64, u128)> = Vec::with_capacity(hours.len());
+        for chunk in hours.chunks(PAGE_SIZE) {
+            let data: PoolHourDatas = self
+                .query(
+                    r"query PoolHourDatas($pool: String!, $hours: [Int!]!, $first: Int!) {
+                        poolHourDatas(
+                            first: $first
+                            where: { pool: $pool, periodStartUnix_in: $hours }
+                        ) {
+                            periodStartUnix liquidity
+                        }
+                    }",
+                    json!({ "pool": pool_id, "hours": chunk, "first": PAGE_SIZE }),
+                )
+                .await?;
+            for hour_data in data.pool_hour_datas {
+                hour_datas.push((hour_data.period_start_unix, parse(&hour_data.liquidity)?));
+            }
+        }
+        hour_datas.sort_unstable();
+
+        let mut swaps: Vec<(u64, SwapEvent)> = raw_swaps
+            .iter()
+            .map(|swap| {
+                Ok((
+                    parse(&swap.log_index)?,
+                    swap.parse(decimals0, decimals1, &hour_datas)?,
+                ))
+            })
+            .collect::<Result<_, Error>>()?;
+        swaps.sort_by_key(|&(log_index, swap)| (swap.block_number, log_index));
+        Ok(swaps.into_iter().map(|(_, swap)| swap).collect())
+    }
+
+    /// Fetches the positions of an owner, including the closed ones, e.g. to complement the
+    /// positions fetched on-chain by [`get_positions_for_owner`] with their history
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id of the subgraph deployment
+    /// * `owner`: The owner of the positions
+    /// * `block_number`: Optional block number to query
+    #[inline]
+    pub async fn get_positions_by_owner(
+        &self,
+        chain_id: ChainId,
+        owner: Address,
+        block_number: Option<u64>,
+    ) -> Result<Vec<SubgraphPosition>, Error> {
+        self.get_positions(chain_id, "owner", owner, block_number)
+            .await
+    }
+
+    /// Fetches the positions in a pool, including the closed ones
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id of the subgraph deployment
+    /// * `pool`: The pool address
+    /// * `block_number`: Optional block number to query
+    #[inline]
+    pub async fn get_positions_by_pool(
+        &self,
+        chain_id: ChainId,
+        pool: Address,
+        block_number: Option<u64>,
+    ) -> Result<Vec<SubgraphPosition>, Error> {
+        self.get_positions(chain_id, "pool", pool, block_number)
+            .await
+    }
+
+    /// Fetches the positions matching an address field, in pages ordered by id
+    async fn get_positions(
+        &self,
+        chain_id: ChainId,
+        field: &str,
+        address: Address,
+        block_number: Option<u64>,
+    ) -> Result<Vec<SubgraphPosition>, Error> {
+        let query = format!(
+            r"query Positions($address: String!, $cursor: ID!, $first: Int!) {{
+                positions(
+                    first: $first
+                    where: {{ {field}: $address, id_gt: $cursor }}
+                    orderBy: id
+                    orderDirection: asc{}
+                ) {{
+                    id owner liquidity depositedToken0 depositedToken1 withdrawnToken0
+                    withdrawnToken1 collectedFeesToken0 collectedFeesToken1
+                    tickLower {{ tickIdx }} tickUpper {{ tickIdx }}
+                    pool {{
+                        id feeTier sqrtPrice liquidity
+                        token0 {{ id symbol name decimals }} token1 {{ id symbol name decimals }}
+                    }}
+                }}
+            }}",
+            block_filter(block_number)
+        );
+        let mut positions = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let page: Positions = self
+                .query(
+                    &query,
+                    json!({
+                        "address": format!("{address:#x}"),
+                        "cursor": cursor,
+                        "first": PAGE_SIZE,
+                    }),
+                )
+                .await?;
+            let len = page.positions.len();
+            if let Some(last) = page.positions.last() {
+                cursor.clone_from(&last.id);
+            }
+            for position in page.positions {
+                positions.push(position.parse(chain_id)?);
+            }
+            if len < PAGE_SIZE {
+                return Ok(positions);
+            }
+        }
+    }
+}
+
+/// The argument of a query at a block, if any
+fn block_filter(block_number: Option<u64>) -> String {
+    block_number.map_or_else(String::new, |number| {
+        format!(", block: {{ number: {number} }}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool_day_data() {
+        let body = br#"{"data":{"poolDayDatas":[{"date":1681171200,"liquidity":"22138232385767347584",
+            "sqrtPrice":"1893528599011532418346521306637585","tick":"200756","token0Price":"1751.25",
+            "token1Price":"0.000571","volumeToken0":"82150.5","volumeToken1":"46.9",
+            "volumeUSD":"82150.5","feesUSD":"41.07","tvlUSD":"261843213.19","txCount":"1034",
+            "open":"1902.1","high":"1923.7","low":"1891.2","close":"1913.4"}]}}"#;
+        let data: PoolDayDatas = parse_response(body).unwrap();
+        let data: Vec<PoolDayData> = data
+            .pool_day_datas
+            .into_iter()
+            .map(RawPoolDayData::parse)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].date, 1681171200);
+        assert_eq!(data[0].liquidity, 22138232385767347584);
+        assert_eq!(
+            data[0].sqrt_price_x96,
+            U160::from_str("1893528599011532418346521306637585").unwrap()
+        );
+        assert_eq!(data[0].tick, Some(I24::try_from(200756).unwrap()));
+        assert_eq!(data[0].fees_usd, BigDecimal::from_str("41.07").unwrap());
+        assert_eq!(data[0].tx_count, 1034);
+    }
+
+    #[test]
+    fn test_parse_ticks() {
+        let body = br#"{"data":{"ticks":[
+            {"tickIdx":"-887270","liquidityGross":"100","liquidityNet":"100"},
+            {"tickIdx":"887270","liquidityGross":"100","liquidityNet":"-100"}]}}"#;
+        let data: Ticks = parse_response(body).unwrap();
+        let ticks: Vec<Tick> = data
+            .ticks
+            .into_iter()
+            .map(RawTick::parse)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            ticks,
+            vec![Tick::new(-887270, 100, 100), Tick::new(887270, 100, -100)]
+        );
+    }
+
+    #[test]
+    fn test_parse_swaps() {
+        let body = br#"{"data":{"swaps":[{"id":"0xabc#1","timestamp":"1681171300","logIndex":"7",
+            "amount0":"-1913.4","amount1":"1.000000000000000001",
+            "sqrtPriceX96":"1893528599011532418346521306637585","tick":"200756",
+            "transaction":{"blockNumber":"17020000"}}]}}"#;
+        let data: Swaps = parse_response(body).unwrap();
+        let hour_datas = [(1681167600, 100), (1681171200, 200), (1681174800, 300)];
+        let swap = data.swaps[0].parse(6, 18, &hour_datas).unwrap();
+        assert_eq!(swap.block_number, 17020000);
+        assert_eq!(swap.timestamp, 1681171300);
+        assert_eq!(swap.amount0, I256::try_from(-1_913_400_000_i64).unwrap());
+        assert_eq!(
+            swap.amount1,
+            I256::try_from(1_000_000_000_000_000_001_i128).unwrap()
+        );
+        assert_eq!(swap.tick, I24::try_from(200756).unwrap());
+        // the liquidity of the hour of the swap
+        assert_eq!(swap.liquidity, 200);
+    }
+
+    #[test]
+    fn test_parse_positions() {
+        let body = br#"{"data":{"positions":[{"id":"4","owner":"0x1111111111111111111111111111111111111111",
+            "liquidity":"1000","depositedToken0":"1.5","depositedToken1":"0.1","withdrawnToken0":"0",
+            "withdrawnToken1":"0","collectedFeesToken0":"0.01","collectedFeesToken1":"0",
+            "tickLower":{"tickIdx":"-60"},"tickUpper":{"tickIdx":"60"},
+            "pool":{"id":"0x8ad599c3a0ff1de082011efddc58f1908eb6e6d8","feeTier":"3000",
+            "sqrtPrice":"79228162514264337593543950336","liquidity":"1000",
+            "token0":{"id":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","symbol":"USDC","name":"USD Coin","decimals":"6"},
+            "token1":{"id":"0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2","symbol":"WETH","name":"Wrapped Ether","decimals":"18"}}},
+            {"id":"5","owner":"0x1111111111111111111111111111111111111111",
+            "liquidity":"0","depositedToken0":"0","depositedToken1":"0","withdrawnToken0":"0",
+            "withdrawnToken1":"0","collectedFeesToken0":"0","collectedFeesToken1":"0",
+            "tickLower":{"tickIdx":"-10"},"tickUpper":{"tickIdx":"10"},
+            "pool":{"id":"0x8ad599c3a0ff1de082011efddc58f1908eb6e6d8","feeTier":"3000",
+            "sqrtPrice":"79228162514264337593543950336","liquidity":"1000",
+            "token0":{"id":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","symbol":"USDC","name":"USD Coin","decimals":"6"},
+            "token1":{"id":"0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2","symbol":"WETH","name":"Wrapped Ether","decimals":"18"}}}]}}"#;
+        let data: Positions = parse_response(body).unwrap();
+        let positions: Vec<SubgraphPosition> = data
+            .positions
+            .into_iter()
+            .map(|position| position.parse(1))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(positions[0].token_id, U256::from(4));
+        assert_eq!(positions[0].tick_lower, I24::try_from(-60).unwrap());
+        assert_eq!(
+            positions[0].collected_fees_token0,
+            BigDecimal::from_str("0.01").unwrap()
+        );
+        let position = positions[0].position.as_ref().unwrap();
+        assert_eq!(position.liquidity, 1000);
+        assert_eq!(position.pool.token0.symbol.as_deref(), Some("USDC"));
+        assert_eq!(position.pool.fee, FeeAmount::MEDIUM);
+        // the ticks are not aligned to the tick spacing of the fee tier
+        assert!(positions[1].position.is_none());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let body = br#"{"errors":[{"message":"indexing error"}]}"#;
+        assert!(matches!(
+            parse_response::<Ticks>(body),
+            Err(Error::SubgraphError(message)) if message == "indexing error"
+        ));
+        let body =
+            br#"{"data":{"ticks":[{"tickIdx":"x","liquidityGross":"0","liquidityNet":"0"}]}}"#;
+        let data: Ticks = parse_response(body).unwrap();
+        assert!(data
+            .ticks
+            .into_iter()
+            .next()
+            .unwrap()
+            .parse::<i32>()
+            .is_err());
+        let body =
+            br#"{"data":{"ticks":[{"tickIdx":"887273","liquidityGross":"0","liquidityNet":"0"}]}}"#;
+        let data: Ticks = parse_response(body).unwrap();
+        assert!(matches!(
+            data.ticks.into_iter().next().unwrap().parse::<i32>(),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[test]
+    fn test_block_filter() {
+        assert_eq!(block_filter(None), "");
+        assert_eq!(
+            block_filter(Some(17000000)),
+            ", block: { number: 17000000 }"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! ## Tick Export
+//! Export and import of the initialized ticks of a pool as CSV or JSON, e.g. to analyze them in a
+//! notebook, and to load them back into a [`TickListDataProvider`] or [`TickMap`] for a replay.
+//!
+//! Both formats have the columns `tick`, `liquidity_gross`, `liquidity_net`,
+//! `fee_growth_outside0_x128` and `fee_growth_outside1_x128`, where the fee growth is optional.
+//! The 128-bit and 256-bit integers are written as decimal strings in JSON, since most JSON
+//! parsers read numbers as doubles, but both strings and numbers are accepted on import.
+
+use crate::prelude::{Error, *};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy::{eips::BlockId, network::Network, providers::Provider};
+use alloy_primitives::{aliases::I24, Address, U256};
+use core::{fmt::Write, str::FromStr};
+use serde_json::{json, Map, Value};
+use uniswap_lens::pool_lens;
+
+const COLUMNS: [&str; 5] = [
+    "tick",
+    "liquidity_gross",
+    "liquidity_net",
+    "fee_growth_outside0_x128",
+    "fee_growth_outside1_x128",
+];
+
+/// An initialized tick of a pool with its fee growth, as a row of a CSV or JSON export
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickRecord<I = I24> {
+    pub tick: Tick<I>,
+    /// The fee growth outside the tick, if known
+    pub fee_growth_outside: Option<FeeGrowthOutside<256, 4>>,
+}
+
+impl<I> From<Tick<I>> for TickRecord<I> {
+    #[inline]
+    fn from(tick: Tick<I>) -> Self {
+        Self {
+            tick,
+            fee_growth_outside: None,
+        }
+    }
+}
+
+/// Fetches the initialized ticks of a pool in a range with their fee growth using the ephemeral
+/// contract of [`EphemeralTickDataProvider`]
+///
+/// ## Arguments
+///
+/// * `pool`: The pool address
+/// * `provider`: The alloy provider
+/// * `tick_lower`: The lower tick of the range, defaults to [`MIN_TICK`]
+/// * `tick_upper`: The upper tick of the range, defaults to [`MAX_TICK`]
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_tick_records<I, N, P>(
+    pool: Address,
+    provider: P,
+    tick_lower: Option<I>,
+    tick_upper: Option<I>,
+    block_id: Option<BlockId>,
+) -> Result<Vec<TickRecord<I>>, Error>
+where
+    I: TickIndex,
+    N: Network,
+    P: Provider<N>,
+{
+    let (ticks, _) = pool_lens::get_populated_ticks_in_range(
+        pool,
+        tick_lower.map_or(MIN_TICK, I::to_i24),
+        tick_upper.map_or(MAX_TICK, I::to_i24),
+        provider,
+        block_id,
+    )
+    .await
+    .map_err(Error::LensError)?;
+    Ok(ticks
+        .into_iter()
+        .map(|tick| TickRecord {
+            tick: Tick::new(
+                I::from_i24(tick.tick),
+                tick.liquidityGross,
+                tick.liquidityNet,
+            ),
+            fee_growth_outside: Some(FeeGrowthOutside {
+                fee_growth_outside0_x128: tick.feeGrowthOutside0X128,
+                fee_growth_outside1_x128: tick.feeGrowthOutside1X128,
+            }),
+        })
+        .collect())
+}
+
+/// Writes tick records as CSV with a header row
+#[inline]
+#[must_use]
+pub fn ticks_to_csv<I: TickIndex>(records: &[TickRecord<I>]) -> String {
+    let mut csv = COLUMNS.join(",");
+    csv.push('\n');
+    for record in records {
+        let index: i32 = record.tick.index.to_i24().as_i32();
+        write!(
+            csv,
+            "{index},{},{},",
+            record.tick.liquidity_gross, record.tick.liquidity_net
+        )
+        .unwrap();
+        if let Some(fee_growth) = &record.fee_growth_outside {
+            write!(
+                csv,
+                "{},{}",
+                fee_growth.fee_growth_outside0_x128, fee_growth.fee_growth_outside1_x128
+            )
+            .unwrap();
+        } else {
+            csv.push(',');
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Reads tick records from CSV with a header row naming the columns in any order, where the fee
+/// growth columns may be missing or empty
+#[inline]
+pub fn ticks_from_csv<I: TickIndex>(csv: &str) -> Result<Vec<TickRecord<I>>, Error> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<&str> = header.split(',').map(str::trim).collect();
+    let position = |column: &str| header.iter().position(|&name| name == column);
+    let columns = COLUMNS.map(position);
+    if columns[..3].iter().any(Option::is_none) {
+        return Err(invalid(0, "missing column"));
+    }
+    lines
+        .enumerate()
+        .map(|(i, row)| {
+            let cells: Vec<&str> = row.split(',').map(str::trim).collect();
+            let cell = |column: usize| -> Option<&str> {
+                columns[column]
+                    .and_then(|position| cells.get(position).copied())
+                    .filter(|cell| !cell.is_empty())
+            };
+            record(i + 1, |column| Ok(cell(column).map(ToString::to_string)))
+        })
+        .collect()
+}
+
+/// Writes tick records as a JSON array of objects
+#[inline]
+#[must_use]
+pub fn ticks_to_json<I: TickIndex>(records: &[TickRecord<I>]) -> String {
+    let records: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let mut object = json!({
+                "tick": record.tick.index.to_i24().as_i32(),
+                "liquidity_gross": record.tick.liquidity_gross.to_string(),
+                "liquidity_net": record.tick.liquidity_net.to_string(),
+            });
+            if let Some(fee_growth) = &record.fee_growth_outside {
+                object[COLUMNS[3]] = fee_growth.fee_growth_outside0_x128.to_string().into();
+                object[COLUMNS[4]] = fee_growth.fee_growth_outside1_x128.to_string().into();
+            }
+            object
+        })
+        .collect();
+    Value::Array(records).to_string()
+}
+
+/// Reads tick records from a JSON array of objects, where the fee growth fields may be missing or
+/// `null`
+#[inline]
+pub fn ticks_from_json<I: TickIndex>(json: &str) -> Result<Vec<TickRecord<I>>, Error> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|error| invalid(0, &error.to_string()))?;
+    let Value::Array(values) = value else {
+        return Err(invalid(0, "expected an array"));
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let object: &Map<String, Value> = value
+                .as_object()
+                .ok_or_else(|| invalid(i + 1, "expected an object"))?;
+            record(i + 1, |column| match object.get(COLUMNS[column]) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(value)) => Ok(Some(value.clone())),
+                Some(Value::Number(value)) => Ok(Some(value.to_string())),
+                Some(_) => Err(invalid(i + 1, COLUMNS[column])),
+            })
+        })
+        .collect()
+}
+
+/// Parses the `n`-th tick record from its fields by column index in [`COLUMNS`]
+fn record<I, F>(n: usize, field: F) -> Result<TickRecord<I>, Error>
+where
+    I: TickIndex,
+    F: Fn(usize) -> Result<Option<String>, Error>,
+{
+    let required = |column: usize| field(column)?.ok_or_else(|| invalid(n, COLUMNS[column]));
+    let index: i32 = parse_value(n, 0, &required(0)?)?;
+    let index = I24::try_from(index).map_err(|_| invalid(n, COLUMNS[0]))?;
+    if !(MIN_TICK..=MAX_TICK).contains(&index) {
+        return Err(Error::InvalidTick(index));
+    }
+    let tick = Tick::new(
+        I::from_i24(index),
+        parse_value(n, 1, &required(1)?)?,
+        parse_value(n, 2, &required(2)?)?,
+    );
+    let fee_growth_outside = match (field(3)?, field(4)?) {
+        (None, None) => None,
+        (Some(fee_growth0), Some(fee_growth1)) => Some(FeeGrowthOutside {
+            fee_growth_outside0_x128: parse_value::<U256>(n, 3, &fee_growth0)?,
+            fee_growth_outside1_x128: parse_value::<U256>(n, 4, &fee_growth1)?,
+        }),
+        (None, _) => return Err(invalid(n, COLUMNS[3])),
+        (_, None) => return Err(invalid(n, COLUMNS[4])),
+    };
+    Ok(TickRecord {
+        tick,
+        fee_growth_outside,
+    })
+}
+
+fn parse_value<T: FromStr>(n: usize, column: usize, value: &str) -> Result<T, Error> {
+    value.parse().map_err(|_| invalid(n, COLUMNS[column]))
+}
+
+/// The error of the `n`-th record, or of the whole input if `n` is zero
+fn invalid(n: usize, reason: &str) -> Error {
+    Error::InvalidTickData(if n == 0 {
+        reason.to_string()
+    } else {
+        format!("record {n}: {reason}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, uint};
+
+    fn records() -> Vec<TickRecord<i32>> {
+        vec![
+            TickRecord {
+                tick: Tick::new(-887220, 1_000_000, 1_000_000),
+                fee_growth_outside: Some(FeeGrowthOutside {
+                    fee_growth_outside0_x128: U256::MAX,
+                    fee_growth_outside1_x128: uint!(340282366920938463463374607431768211456_U256),
+                }),
+            },
+            Tick::new(887220, 1_000_000, -1_000_000).into(),
+        ]
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = ticks_to_csv(&records());
+        assert_eq!(csv.lines().nth(2).unwrap(), "887220,1000000,-1000000,,");
+        assert_eq!(ticks_from_csv::<i32>(&csv).unwrap(), records());
+        // the columns may be reordered and the fee growth omitted
+        let csv = "liquidity_net,tick,liquidity_gross\n1,-60,1\n-1,60,1\n";
+        let records = ticks_from_csv::<i32>(csv).unwrap();
+        assert_eq!(records[1], Tick::new(60, 1, -1).into());
+        let provider = TickListDataProvider::new(records.iter().map(|r| r.tick).collect(), 60);
+        assert_eq!(provider.len(), 2);
+        assert!(matches!(
+            ticks_from_csv::<i32>("tick,liquidity_gross\n0,1\n"),
+            Err(Error::InvalidTickData(_))
+        ));
+        assert!(matches!(
+            ticks_from_csv::<i32>("tick,liquidity_gross,liquidity_net\n0,x,1\n"),
+            Err(Error::InvalidTickData(_))
+        ));
+        assert!(matches!(
+            ticks_from_csv::<i32>("tick,liquidity_gross,liquidity_net\n887280,1,1\n"),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[test]
+    fn test_json() {
+        let json = ticks_to_json(&records());
+        assert_eq!(ticks_from_json::<i32>(&json).unwrap(), records());
+        let json = r#"[{"tick": -60, "liquidity_gross": 1, "liquidity_net": "1"},
+            {"tick": 60, "liquidity_gross": 1, "liquidity_net": -1, "fee_growth_outside0_x128": null}]"#;
+        let records = ticks_from_json::<I24>(json).unwrap();
+        assert_eq!(records[0].tick.liquidity_net, 1);
+        assert_eq!(records[1].fee_growth_outside, None);
+        assert!(matches!(
+            ticks_from_json::<i32>(
+                r#"[{"tick": 0, "liquidity_gross": 1, "liquidity_net": 1, "fee_growth_outside0_x128": "1"}]"#
+            ),
+            Err(Error::InvalidTickData(_))
+        ));
+        assert!(matches!(
+            ticks_from_json::<i32>("{}"),
+            Err(Error::InvalidTickData(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_tick_records() {
+        let records = get_tick_records::<I24, _, _>(
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"),
+            PROVIDER.clone(),
+            Some(I24::ZERO),
+            Some(I24::from_limbs([1000])),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(!records.is_empty());
+        assert!(records
+            .iter()
+            .all(|record| record.fee_growth_outside.is_some()));
+        let csv = ticks_to_csv(&records);
+        assert_eq!(ticks_from_csv::<I24>(&csv).unwrap(), records);
+    }
+}
@@ -1,18 +1,26 @@
 //! ## Tick Map
 //! [`TickMap`] provides a way to access tick data directly from a hashmap, supposedly more
-//! efficient than [`TickList`].
+//! efficient than [`TickList`]. Initialized ticks are tracked in a [`TickBitMap`] with the same
+//! layout as the on-chain tick bitmap, so that finding the next initialized tick is a single word
+//! lookup instead of a search in a sorted list.
 
 use crate::prelude::*;
 use alloy::uint;
 use alloy_primitives::{aliases::I24, map::rustc_hash::FxHashMap, U256};
 
+/// A tick data provider backed by a tick bitmap mirroring the on-chain layout and a hashmap of the
+/// initialized ticks.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TickMap<I = I24> {
     pub bitmap: TickBitMap<I>,
     pub inner: FxHashMap<I, Tick<I>>,
     pub tick_spacing: I,
 }
 
+/// Alias of [`TickMap`], the tick data provider backed by a tick bitmap.
+pub type TickBitmapDataProvider<I = I24> = TickMap<I>;
+
 impl<I: TickIndex> TickMap<I> {
     #[inline]
     #[must_use]
@@ -54,3 +62,43 @@ impl<I: TickIndex> TickDataProvider for TickMap<I> {
             .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_initialized_tick_within_one_word() {
+        let tick_map = TickBitmapDataProvider::new(
+            vec![
+                Tick::new(-887220, 10, 10),
+                Tick::new(-60, 5, 5),
+                Tick::new(120, 5, -5),
+                Tick::new(887220, 10, -10),
+            ],
+            60,
+        );
+        assert_eq!(tick_map.bitmap.len(), 4);
+        assert_eq!(
+            tick_map
+                .next_initialized_tick_within_one_word(-1, true, 60)
+                .unwrap(),
+            (-60, true)
+        );
+        assert_eq!(
+            tick_map
+                .next_initialized_tick_within_one_word(0, false, 60)
+                .unwrap(),
+            (120, true)
+        );
+        // the word of tick -60 ends at tick -15360
+        assert_eq!(
+            tick_map
+                .next_initialized_tick_within_one_word(-120, true, 60)
+                .unwrap(),
+            (-15360, false)
+        );
+        assert_eq!(tick_map.get_tick(120).unwrap().liquidity_net, -5);
+        assert!(tick_map.get_tick(60).is_err());
+    }
+}
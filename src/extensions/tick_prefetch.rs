@@ -0,0 +1,298 @@
+//! ## Tick Prefetch
+//! Heuristics deciding how many tick bitmap words around the current tick an RPC-backed tick data
+//! provider should prefetch for a swap, based on the swap amount, the fee tier and the current
+//! in-range liquidity. [`AdaptivePrefetch`] additionally tunes itself from the ticks actually
+//! crossed by past swaps.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{
+    aliases::{I24, U24},
+    uint, U256,
+};
+use uniswap_sdk_core::prelude::*;
+
+/// Heuristic estimating the number of tick bitmap words a swap walks through, assuming the current
+/// in-range liquidity stays constant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrefetchHeuristic {
+    /// The multiplier applied to the estimated number of words, in percent
+    pub multiplier_pct: u32,
+    /// The minimum number of words to prefetch
+    pub min_words: u32,
+    /// The maximum number of words to prefetch
+    pub max_words: u32,
+}
+
+impl Default for PrefetchHeuristic {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            multiplier_pct: 200,
+            min_words: 1,
+            max_words: 32,
+        }
+    }
+}
+
+impl PrefetchHeuristic {
+    /// Estimate the number of words walked by a swap before applying the multiplier, or `None`
+    /// if the pool price would run out of bounds at the current liquidity.
+    fn raw_words<TP: TickDataProvider>(
+        pool: &Pool<TP>,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<Option<u32>, Error> {
+        if !pool.involves_token(&amount_in.currency) {
+            return Err(Error::InvalidToken);
+        }
+        let zero_for_one = amount_in.currency.equals(&pool.token0);
+        let fee = U256::from(U24::from(pool.fee).into_limbs()[0]);
+        let amount_less_fee = U256::from_big_int(amount_in.quotient())
+            * (uint!(1_000_000_U256) - fee)
+            / uint!(1_000_000_U256);
+        let Ok(sqrt_price_next) = get_next_sqrt_price_from_input(
+            pool.sqrt_ratio_x96,
+            pool.liquidity,
+            amount_less_fee,
+            zero_for_one,
+        ) else {
+            return Ok(None);
+        };
+        let sqrt_price_next = sqrt_price_next.clamp(MIN_SQRT_RATIO, MAX_SQRT_RATIO - uint!(1_U160));
+        let tick_next = sqrt_price_next.get_tick_at_sqrt_ratio()?.as_i32();
+        Ok(Some(words_crossed(
+            pool.tick_current.to_i24().as_i32(),
+            tick_next,
+            pool.tick_spacing().to_i24().as_i32(),
+        )))
+    }
+
+    /// Estimate the number of tick bitmap words to prefetch for a swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to swap in
+    /// * `amount_in`: The input amount of the swap
+    #[inline]
+    pub fn estimate_words<TP: TickDataProvider>(
+        &self,
+        pool: &Pool<TP>,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<u32, Error> {
+        Ok(match Self::raw_words(pool, amount_in)? {
+            Some(words) => words
+                .saturating_mul(self.multiplier_pct)
+                .div_ceil(100)
+                .clamp(self.min_words, self.max_words),
+            None => self.max_words,
+        })
+    }
+}
+
+/// A [`PrefetchHeuristic`] that adapts its multiplier from the ticks crossed by past swaps.
+///
+/// The multiplier jumps up with some headroom when a swap walks further than prefetched, and
+/// decays slowly towards the observed ratio otherwise. It is deliberately not `Copy` so that the
+/// tuned state is not lost to an accidental copy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdaptivePrefetch {
+    pub heuristic: PrefetchHeuristic,
+}
+
+impl AdaptivePrefetch {
+    #[inline]
+    #[must_use]
+    pub const fn new(heuristic: PrefetchHeuristic) -> Self {
+        Self { heuristic }
+    }
+
+    /// Estimate the number of tick bitmap words to prefetch for a swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to swap in
+    /// * `amount_in`: The input amount of the swap
+    #[inline]
+    pub fn estimate_words<TP: TickDataProvider>(
+        &self,
+        pool: &Pool<TP>,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<u32, Error> {
+        self.heuristic.estimate_words(pool, amount_in)
+    }
+
+    /// Record the outcome of a swap to tune the multiplier
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool before the swap
+    /// * `amount_in`: The input amount of the swap
+    /// * `tick_after`: The current tick of the pool after the swap
+    #[inline]
+    pub fn record<TP: TickDataProvider>(
+        &mut self,
+        pool: &Pool<TP>,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+        tick_after: TP::Index,
+    ) -> Result<(), Error> {
+        let Some(estimated) = PrefetchHeuristic::raw_words(pool, amount_in)? else {
+            return Ok(());
+        };
+        let crossed = words_crossed(
+            pool.tick_current.to_i24().as_i32(),
+            tick_after.to_i24().as_i32(),
+            pool.tick_spacing().to_i24().as_i32(),
+        );
+        let observed_pct = crossed.saturating_mul(100).div_ceil(estimated).max(100);
+        let multiplier_pct = &mut self.heuristic.multiplier_pct;
+        *multiplier_pct = if observed_pct > *multiplier_pct {
+            observed_pct.saturating_mul(5) / 4
+        } else {
+            (*multiplier_pct * 7 + observed_pct) / 8
+        };
+        Ok(())
+    }
+}
+
+/// How many tick bitmap words around the current tick to prefetch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum PrefetchRadius {
+    /// Fetch the full tick range
+    #[default]
+    Full,
+    /// Fetch a fixed number of words in the swap direction
+    Words(u32),
+    /// Estimate the number of words with a fixed heuristic
+    Heuristic(PrefetchHeuristic),
+    /// Estimate the number of words with a heuristic tuned from past swaps, which
+    /// [`EphemeralTickMapDataProvider::prefetch`] records after each fetch
+    Auto(AdaptivePrefetch),
+}
+
+impl PrefetchRadius {
+    /// Get the tick range to prefetch for a swap. One word on the opposite side of the swap
+    /// direction is included as slack.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to swap in
+    /// * `amount_in`: The input amount of the swap
+    ///
+    /// ## Returns
+    ///
+    /// The lower and upper ticks of the range
+    #[inline]
+    pub fn tick_range<TP: TickDataProvider>(
+        &self,
+        pool: &Pool<TP>,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<(TP::Index, TP::Index), Error> {
+        let words = match self {
+            Self::Full => {
+                return Ok((TP::Index::from_i24(MIN_TICK), TP::Index::from_i24(MAX_TICK)));
+            }
+            Self::Words(words) => *words,
+            Self::Heuristic(heuristic) => heuristic.estimate_words(pool, amount_in)?,
+            Self::Auto(adaptive) => adaptive.estimate_words(pool, amount_in)?,
+        };
+        let zero_for_one = amount_in.currency.equals(&pool.token0);
+        let tick_current = i64::from(pool.tick_current.to_i24().as_i32());
+        let word_span = i64::from(pool.tick_spacing().to_i24().as_i32() << 8);
+        let (below, above) = if zero_for_one {
+            (i64::from(words), 1)
+        } else {
+            (1, i64::from(words))
+        };
+        let tick_lower = (tick_current - below * word_span).max(i64::from(MIN_TICK_I32));
+        let tick_upper = (tick_current + above * word_span).min(i64::from(MAX_TICK_I32));
+        Ok((
+            TP::Index::from_i24(I24::try_from(tick_lower).unwrap()),
+            TP::Index::from_i24(I24::try_from(tick_upper).unwrap()),
+        ))
+    }
+}
+
+/// Returns the number of tick bitmap words between two ticks, inclusive of both ends
+///
+/// ## Arguments
+///
+/// * `tick_before`: The tick before the swap
+/// * `tick_after`: The tick after the swap
+/// * `tick_spacing`: The tick spacing of the pool
+#[inline]
+#[must_use]
+pub fn words_crossed(tick_before: i32, tick_after: i32, tick_spacing: i32) -> u32 {
+    let (word_before, _) = tick_before.compress(tick_spacing).position();
+    let (word_after, _) = tick_after.compress(tick_spacing).position();
+    word_before.abs_diff(word_after) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_words_crossed() {
+        assert_eq!(words_crossed(0, 0, 60), 1);
+        assert_eq!(words_crossed(0, 15359, 60), 1);
+        assert_eq!(words_crossed(0, 15360, 60), 2);
+        assert_eq!(words_crossed(0, -1, 60), 2);
+    }
+
+    #[test]
+    fn test_estimate_words() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let heuristic = PrefetchHeuristic::default();
+        let small = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+        // a zero for one swap at tick 0 walks into the word to the left
+        assert_eq!(heuristic.estimate_words(&pool, &small).unwrap(), 4);
+        // the price moves by ~1000x, i.e. ~138k ticks or 9 words with a tick spacing of 60
+        let large = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 1_000_000_000).unwrap();
+        assert_eq!(heuristic.estimate_words(&pool, &large).unwrap(), 18);
+        let huge = CurrencyAmount::from_raw_amount(TOKEN1.clone(), u128::MAX).unwrap();
+        assert_eq!(
+            heuristic.estimate_words(&pool, &huge).unwrap(),
+            heuristic.max_words
+        );
+        let invalid = CurrencyAmount::from_raw_amount(TOKEN2.clone(), 100).unwrap();
+        assert!(matches!(
+            heuristic.estimate_words(&pool, &invalid),
+            Err(Error::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_adaptive_prefetch() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+        let mut adaptive = AdaptivePrefetch::default();
+        // the swap walks 6 words while the raw estimate is 2 words
+        adaptive.record(&pool, &amount, -5 * 15360).unwrap();
+        assert_eq!(adaptive.heuristic.multiplier_pct, 375);
+        assert_eq!(adaptive.estimate_words(&pool, &amount).unwrap(), 8);
+        // swaps within the estimate decay the multiplier
+        adaptive.record(&pool, &amount, 0).unwrap();
+        assert_eq!(adaptive.heuristic.multiplier_pct, 340);
+    }
+
+    #[test]
+    fn test_tick_range() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+        assert_eq!(
+            PrefetchRadius::Full.tick_range(&pool, &amount).unwrap(),
+            (MIN_TICK_I32, MAX_TICK_I32)
+        );
+        assert_eq!(
+            PrefetchRadius::Words(3).tick_range(&pool, &amount).unwrap(),
+            (-3 * 15360, 15360)
+        );
+        let amount = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100).unwrap();
+        assert_eq!(
+            PrefetchRadius::Heuristic(PrefetchHeuristic::default())
+                .tick_range(&pool, &amount)
+                .unwrap(),
+            (-15360, 2 * 15360)
+        );
+    }
+}
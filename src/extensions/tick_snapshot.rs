@@ -0,0 +1,355 @@
+//! ## Tick Snapshot
+//! A compact binary snapshot of a pool and its initialized ticks at a block, encoded with
+//! [`bincode`], so that the ticks of a dense pool can be shipped between machines and loaded in
+//! milliseconds instead of being fetched again.
+
+use crate::prelude::{Error, *};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{
+    aliases::{I24, U24},
+    Address, ChainId, U160, U256,
+};
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+use uniswap_sdk_core::prelude::*;
+
+/// The version of the binary format, bumped on incompatible changes
+const VERSION: u8 = 1;
+
+/// A pool and its initialized ticks at a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickSnapshot<I = I24> {
+    /// The block number at which the snapshot was taken
+    pub block_number: u64,
+    /// The metadata and state of the pool, without its ticks
+    pub pool: Pool,
+    /// The initialized ticks of the pool, sorted by index
+    pub ticks: Vec<Tick<I>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TokenRepr {
+    address: [u8; 20],
+    decimals: u8,
+    symbol: Option<String>,
+    name: Option<String>,
+    buy_fee_bps: Option<String>,
+    sell_fee_bps: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotRepr {
+    version: u8,
+    block_number: u64,
+    chain_id: ChainId,
+    token0: TokenRepr,
+    token1: TokenRepr,
+    fee: u32,
+    tick_spacing: u32,
+    sqrt_ratio_x96: [u8; 20],
+    liquidity: u128,
+    ticks: Vec<(i32, u128, i128)>,
+}
+
+impl TokenRepr {
+    fn new(token: &Token) -> Self {
+        Self {
+            address: token.address().into(),
+            decimals: token.decimals(),
+            symbol: token.symbol().map(ToString::to_string),
+            name: token.name().map(ToString::to_string),
+            buy_fee_bps: token.buy_fee_bps.as_ref().map(ToString::to_string),
+            sell_fee_bps: token.sell_fee_bps.as_ref().map(ToString::to_string),
+        }
+    }
+
+    fn token(self, chain_id: ChainId) -> Result<Token, Error> {
+        let parse_bps = |bps: Option<String>| {
+            bps.map(|bps| BigUint::from_str(&bps).map_err(|e| Error::SnapshotError(e.to_string())))
+                .transpose()
+        };
+        Ok(Token::new(
+            chain_id,
+            Address::from(self.address),
+            self.decimals,
+            self.symbol,
+            self.name,
+            parse_bps(self.buy_fee_bps)?,
+            parse_bps(self.sell_fee_bps)?,
+        ))
+    }
+}
+
+impl<I: TickIndex> TickSnapshot<I> {
+    /// Takes a snapshot of a pool and its initialized ticks
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool
+    /// * `ticks`: The initialized ticks of the pool in any order
+    /// * `block_number`: The block number at which the state of the pool was fetched
+    #[inline]
+    pub fn new<TP>(
+        pool: &Pool<TP>,
+        mut ticks: Vec<Tick<I>>,
+        block_number: u64,
+    ) -> Result<Self, Error>
+    where
+        TP: TickDataProvider,
+    {
+        ticks.sort_unstable_by_key(|tick| tick.index);
+        Ok(Self {
+            block_number,
+            pool: Pool::new(
+                pool.token0.clone(),
+                pool.token1.clone(),
+                pool.fee,
+                pool.sqrt_ratio_x96,
+                pool.liquidity,
+            )?,
+            ticks,
+        })
+    }
+
+    /// Takes a snapshot of a pool backed by a [`TickMap`]
+    #[inline]
+    pub fn from_tick_map_pool(pool: &Pool<TickMap<I>>, block_number: u64) -> Result<Self, Error> {
+        let ticks = pool.tick_data_provider.inner.values().copied().collect();
+        Self::new(pool, ticks, block_number)
+    }
+
+    /// Takes a snapshot of a pool backed by a [`TickListDataProvider`]
+    #[inline]
+    pub fn from_tick_list_pool(
+        pool: &Pool<TickListDataProvider<I>>,
+        block_number: u64,
+    ) -> Result<Self, Error> {
+        Self::new(pool, pool.tick_data_provider.to_vec(), block_number)
+    }
+
+    /// The tick spacing of the pool
+    #[inline]
+    #[must_use]
+    pub fn tick_spacing(&self) -> I {
+        I::from_i24(self.pool.tick_spacing().to_i24())
+    }
+
+    /// Encodes the snapshot into its binary format
+    #[inline]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let ticks = self
+            .ticks
+            .iter()
+            .map(|tick| {
+                let index: i32 = tick.index.try_into().unwrap();
+                (index, tick.liquidity_gross, tick.liquidity_net)
+            })
+            .collect();
+        let repr = SnapshotRepr {
+            version: VERSION,
+            block_number: self.block_number,
+            chain_id: self.pool.chain_id(),
+            token0: TokenRepr::new(&self.pool.token0),
+            token1: TokenRepr::new(&self.pool.token1),
+            fee: U24::from(self.pool.fee).to(),
+            tick_spacing: self.pool.tick_spacing() as u32,
+            sqrt_ratio_x96: self.pool.sqrt_ratio_x96.to_be_bytes(),
+            liquidity: self.pool.liquidity,
+            ticks,
+        };
+        bincode::serde::encode_to_vec(&repr, bincode::config::standard())
+            .map_err(|e| Error::SnapshotError(e.to_string()))
+    }
+
+    /// Decodes a snapshot from its binary format, validating the ticks
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (repr, _): (SnapshotRepr, _) =
+            bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                .map_err(|e| Error::SnapshotError(e.to_string()))?;
+        if repr.version != VERSION {
+            return Err(Error::SnapshotError(alloc::format!(
+                "unsupported snapshot version {}",
+                repr.version
+            )));
+        }
+        let pool = Pool::new(
+            repr.token0.token(repr.chain_id)?,
+            repr.token1.token(repr.chain_id)?,
+            FeeAmount::from(repr.fee),
+            U160::from_be_bytes(repr.sqrt_ratio_x96),
+            repr.liquidity,
+        )?;
+        let invalid_tick_spacing =
+            || Error::SnapshotError(alloc::format!("invalid tick spacing {}", repr.tick_spacing));
+        let pool = pool
+            .with_tick_spacing(
+                I24::try_from(repr.tick_spacing).map_err(|_| invalid_tick_spacing())?,
+            )
+            .map_err(|_| invalid_tick_spacing())?;
+        let tick_spacing = pool.tick_spacing();
+        let mut liquidity = 0_u128;
+        let mut ticks: Vec<Tick<I>> = Vec::with_capacity(repr.ticks.len());
+        for (index, liquidity_gross, liquidity_net) in repr.ticks {
+            let invalid = || Error::SnapshotError(alloc::format!("invalid tick {index}"));
+            let tick = I24::try_from(index).map_err(|_| invalid())?;
+            if !(MIN_TICK..=MAX_TICK).contains(&tick)
+                || index % tick_spacing != 0
+                || ticks
+                    .last()
+                    .is_some_and(|last| last.index >= I::from_i24(tick))
+            {
+                return Err(invalid());
+            }
+            liquidity = liquidity
+                .checked_add_signed(liquidity_net)
+                .ok_or_else(invalid)?;
+            ticks.push(Tick::new(I::from_i24(tick), liquidity_gross, liquidity_net));
+        }
+        if liquidity != 0 {
+            return Err(Error::SnapshotError(
+                "the net liquidity is not zero".to_string(),
+            ));
+        }
+        Ok(Self {
+            block_number: repr.block_number,
+            pool,
+            ticks,
+        })
+    }
+
+    /// Restores the pool backed by a [`TickMap`]
+    #[inline]
+    pub fn tick_map_pool(&self) -> Result<Pool<TickMap<I>>, Error> {
+        let tick_spacing = self.tick_spacing();
+        let mut bitmap = TickBitMap::default();
+        for tick in &self.ticks {
+            let (word_pos, bit_pos) = tick.index.compress(tick_spacing).position();
+            let word = bitmap.entry(word_pos).or_insert(U256::ZERO);
+            *word |= U256::from(1) << bit_pos;
+        }
+        self.restore(TickMap {
+            bitmap,
+            inner: self.ticks.iter().map(|tick| (tick.index, *tick)).collect(),
+            tick_spacing,
+        })
+    }
+
+    /// Restores the pool backed by a [`TickListDataProvider`]
+    #[inline]
+    pub fn tick_list_pool(&self) -> Result<Pool<TickListDataProvider<I>>, Error> {
+        self.restore(if self.ticks.is_empty() {
+            TickListDataProvider::default()
+        } else {
+            TickListDataProvider::new(self.ticks.clone(), self.tick_spacing())
+        })
+    }
+
+    fn restore<TP>(&self, tick_data_provider: TP) -> Result<Pool<TP>, Error>
+    where
+        TP: TickDataProvider<Index = I>,
+    {
+        let mut pool = Pool::new_with_tick_data_provider(
+            self.pool.token0.clone(),
+            self.pool.token1.clone(),
+            self.pool.fee,
+            self.pool.sqrt_ratio_x96,
+            self.pool.liquidity,
+            tick_data_provider,
+        )?;
+        pool.custom_tick_spacing = self.pool.custom_tick_spacing;
+        Ok(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn tick_map_pool() -> Pool<TickMap<i32>> {
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            1_000_000,
+            TickMap::new(
+                vec![
+                    Tick::new(-887220, 1_000_000, 1_000_000),
+                    Tick::new(-60, 500, 500),
+                    Tick::new(120, 500, -500),
+                    Tick::new(887220, 1_000_000, -1_000_000),
+                ],
+                60,
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let pool = tick_map_pool();
+        let snapshot = TickSnapshot::from_tick_map_pool(&pool, 17_000_000).unwrap();
+        assert_eq!(snapshot.ticks.len(), 4);
+        assert!(snapshot.ticks.windows(2).all(|w| w[0].index < w[1].index));
+        let bytes = snapshot.to_bytes().unwrap();
+        let decoded = TickSnapshot::<i32>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, snapshot);
+        assert_eq!(decoded.block_number, 17_000_000);
+        assert_eq!(decoded.pool.token0.symbol, TOKEN0.symbol);
+
+        let restored = decoded.tick_map_pool().unwrap();
+        assert_eq!(
+            restored.tick_data_provider.bitmap,
+            pool.tick_data_provider.bitmap
+        );
+        let list_pool = decoded.tick_list_pool().unwrap();
+        assert_eq!(
+            TickSnapshot::from_tick_list_pool(&list_pool, 17_000_000).unwrap(),
+            snapshot
+        );
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10_000).unwrap();
+        let expected = pool.get_output_amount(&amount, None).unwrap().quotient();
+        assert_eq!(
+            restored
+                .get_output_amount(&amount, None)
+                .unwrap()
+                .quotient(),
+            expected
+        );
+        assert_eq!(
+            list_pool
+                .get_output_amount(&amount, None)
+                .unwrap()
+                .quotient(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let snapshot = TickSnapshot::from_tick_map_pool(&tick_map_pool(), 1).unwrap();
+        let bytes = snapshot.to_bytes().unwrap();
+        assert!(matches!(
+            TickSnapshot::<i32>::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::SnapshotError(_))
+        ));
+        let mut bytes = bytes;
+        bytes[0] = VERSION + 1;
+        assert!(matches!(
+            TickSnapshot::<i32>::from_bytes(&bytes),
+            Err(Error::SnapshotError(_))
+        ));
+        // the net liquidity of the ticks must be zero
+        let mut snapshot = snapshot;
+        snapshot.ticks.pop();
+        assert!(matches!(
+            TickSnapshot::<i32>::from_bytes(&snapshot.to_bytes().unwrap()),
+            Err(Error::SnapshotError(_))
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! ## Token List
+//! Parses a [Uniswap token list](https://github.com/Uniswap/token-lists) into [`Token`]s, with
+//! lookups by chain, address and symbol, and across chains by the bridge information of the
+//! tokens.
+
+use crate::prelude::{Error, *};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{Address, ChainId};
+use serde::Deserialize;
+use uniswap_sdk_core::prelude::*;
+
+/// A token list, see the [schema](https://github.com/Uniswap/token-lists/blob/main/src/tokenlist.schema.json).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenList {
+    pub name: String,
+    /// The ISO 8601 timestamp of the list
+    pub timestamp: String,
+    pub version: TokenListVersion,
+    pub tokens: Vec<TokenInfo>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(rename = "logoURI", default)]
+    pub logo_uri: Option<String>,
+}
+
+/// The semantic version of a token list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub struct TokenListVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// A token of a token list.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub chain_id: ChainId,
+    pub address: Address,
+    pub decimals: u8,
+    pub symbol: String,
+    pub name: String,
+    #[serde(rename = "logoURI", default)]
+    pub logo_uri: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub extensions: TokenInfoExtensions,
+}
+
+/// The extensions of a token of a token list, of which only the bridge information is parsed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfoExtensions {
+    /// The address of the bridged token on each other chain
+    #[serde(default)]
+    pub bridge_info: BTreeMap<ChainId, BridgeInfo>,
+}
+
+/// The bridged token of a token on another chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeInfo {
+    pub token_address: Address,
+}
+
+impl TokenInfo {
+    /// Returns the token
+    #[inline]
+    #[must_use]
+    pub fn to_token(&self) -> Token {
+        Token::new(
+            self.chain_id,
+            self.address,
+            self.decimals,
+            Some(self.symbol.clone()),
+            Some(self.name.clone()),
+            None,
+            None,
+        )
+    }
+}
+
+impl TokenList {
+    /// Reads a token list from JSON
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::TokenListError(e.to_string()))
+    }
+
+    /// Returns the tokens of the list on a chain
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    #[inline]
+    pub fn tokens_on_chain(&self, chain_id: ChainId) -> impl Iterator<Item = Token> + '_ {
+        self.tokens
+            .iter()
+            .filter(move |info| info.chain_id == chain_id)
+            .map(TokenInfo::to_token)
+    }
+
+    /// Returns the token of the list at an address on a chain, if any
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `address`: The address of the token
+    #[inline]
+    #[must_use]
+    pub fn get_token(&self, chain_id: ChainId, address: Address) -> Option<Token> {
+        self.get_token_info(chain_id, address)
+            .map(TokenInfo::to_token)
+    }
+
+    /// Returns the tokens of the list with a symbol on a chain, compared case-insensitively, since
+    /// a symbol is not unique
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `symbol`: The symbol of the tokens
+    #[inline]
+    #[must_use]
+    pub fn get_tokens_by_symbol(&self, chain_id: ChainId, symbol: &str) -> Vec<Token> {
+        self.tokens
+            .iter()
+            .filter(|info| info.chain_id == chain_id && info.symbol.eq_ignore_ascii_case(symbol))
+            .map(TokenInfo::to_token)
+            .collect()
+    }
+
+    /// Returns the bridged token of a token on another chain, from the bridge information of
+    /// either token in the list
+    ///
+    /// ## Notes
+    ///
+    /// The bridged token keeps the decimals, symbol and name of the token if it is not itself in
+    /// the list.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token`: The token to bridge
+    /// * `chain_id`: The chain id of the bridged token
+    #[inline]
+    #[must_use]
+    pub fn get_bridged_token(&self, token: &Token, chain_id: ChainId) -> Option<Token> {
+        if token.chain_id() == chain_id {
+            return Some(token.clone());
+        }
+        let info = self.get_token_info(token.chain_id(), token.address());
+        let bridged_address = info
+            .and_then(|info| info.extensions.bridge_info.get(&chain_id))
+            .map(|bridge| bridge.token_address);
+        match bridged_address {
+            Some(address) => Some(self.get_token(chain_id, address).unwrap_or_else(|| {
+                Token::new(
+                    chain_id,
+                    address,
+                    token.decimals(),
+                    token.symbol().cloned(),
+                    token.name().cloned(),
+                    None,
+                    None,
+                )
+            })),
+            // the token may be the bridged token of a token on the other chain
+            None => self
+                .tokens
+                .iter()
+                .find(|info| {
+                    info.chain_id == chain_id
+                        && info
+                            .extensions
+                            .bridge_info
+                            .get(&token.chain_id())
+                            .is_some_and(|bridge| bridge.token_address == token.address())
+                })
+                .map(TokenInfo::to_token),
+        }
+    }
+
+    fn get_token_info(&self, chain_id: ChainId, address: Address) -> Option<&TokenInfo> {
+        self.tokens
+            .iter()
+            .find(|info| info.chain_id == chain_id && info.address == address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const JSON: &str = r#"{
+        "name": "Test List",
+        "timestamp": "2024-01-01T00:00:00.000Z",
+        "version": { "major": 1, "minor": 2, "patch": 3 },
+        "tokens": [
+            {
+                "chainId": 1,
+                "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "name": "USDCoin",
+                "symbol": "USDC",
+                "decimals": 6,
+                "logoURI": "https://example.com/usdc.png",
+                "extensions": {
+                    "bridgeInfo": {
+                        "10": { "tokenAddress": "0x7F5c764cBc14f9669B88837ca1490cCa17c31607" },
+                        "137": { "tokenAddress": "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174" }
+                    }
+                }
+            },
+            {
+                "chainId": 10,
+                "address": "0x7F5c764cBc14f9669B88837ca1490cCa17c31607",
+                "name": "USDCoin (Optimism)",
+                "symbol": "USDC.e",
+                "decimals": 6,
+                "tags": ["bridged"]
+            },
+            {
+                "chainId": 1,
+                "address": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "name": "Wrapped Ether",
+                "symbol": "WETH",
+                "decimals": 18
+            }
+        ]
+    }"#;
+
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const USDC_OPTIMISM: Address = address!("7F5c764cBc14f9669B88837ca1490cCa17c31607");
+    const USDC_POLYGON: Address = address!("2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
+
+    #[test]
+    fn test_from_json() {
+        let list = TokenList::from_json(JSON).unwrap();
+        assert_eq!(list.name, "Test List");
+        assert_eq!(
+            list.version,
+            TokenListVersion {
+                major: 1,
+                minor: 2,
+                patch: 3
+            }
+        );
+        assert_eq!(list.tokens.len(), 3);
+        assert_eq!(list.tokens[1].tags, ["bridged"]);
+        assert_eq!(list.tokens[0].extensions.bridge_info.len(), 2);
+        assert!(list.tokens[2].extensions.bridge_info.is_empty());
+        assert!(matches!(
+            TokenList::from_json(&JSON.replace("\"decimals\": 6", "\"decimals\": -6")),
+            Err(Error::TokenListError(_))
+        ));
+    }
+
+    #[test]
+    fn test_lookups() {
+        let list = TokenList::from_json(JSON).unwrap();
+        let usdc = list.get_token(1, USDC).unwrap();
+        assert_eq!(usdc.decimals(), 6);
+        assert_eq!(usdc.symbol().unwrap(), "USDC");
+        assert!(list.get_token(10, USDC).is_none());
+        assert_eq!(list.tokens_on_chain(1).count(), 2);
+        assert_eq!(list.get_tokens_by_symbol(1, "weth").len(), 1);
+        assert!(list.get_tokens_by_symbol(10, "WETH").is_empty());
+    }
+
+    #[test]
+    fn test_get_bridged_token() {
+        let list = TokenList::from_json(JSON).unwrap();
+        let usdc = list.get_token(1, USDC).unwrap();
+        // in the list
+        let bridged = list.get_bridged_token(&usdc, 10).unwrap();
+        assert_eq!(bridged.address(), USDC_OPTIMISM);
+        assert_eq!(bridged.symbol().unwrap(), "USDC.e");
+        // not in the list
+        let bridged = list.get_bridged_token(&usdc, 137).unwrap();
+        assert_eq!(bridged.address(), USDC_POLYGON);
+        assert_eq!(bridged.chain_id(), 137);
+        assert_eq!(bridged.decimals(), 6);
+        // from the bridge information of the token on the other chain
+        let usdc_optimism = list.get_token(10, USDC_OPTIMISM).unwrap();
+        assert_eq!(list.get_bridged_token(&usdc_optimism, 1).unwrap(), usdc);
+        assert!(list.get_bridged_token(&usdc, 42161).is_none());
+    }
+}
@@ -0,0 +1,240 @@
+//! ## Volatility
+//! Estimates the annualized realized volatility of the price of a pool from the ticks of its swaps
+//! or from its oracle observations over a window.
+
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+};
+use alloc::vec::Vec;
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+};
+use alloy_primitives::{
+    aliases::{I24, I56},
+    Address,
+};
+use core::str::FromStr;
+use uniswap_lens::bindings::iuniswapv3pool::IUniswapV3Pool;
+use uniswap_sdk_core::prelude::*;
+
+/// Estimates the annualized realized volatility of the price from ticks sampled at increasing
+/// timestamps.
+///
+/// The log return between two samples is the difference of their ticks times `ln(1.0001)`, and the
+/// realized variance is the sum of the squared log returns over the elapsed time, annualized.
+///
+/// ## Arguments
+///
+/// * `samples`: The timestamp and tick of each sample in chronological order
+///
+/// ## Returns
+///
+/// The annualized volatility, e.g. 0.8 for 80%, comparable to
+/// [`RangeOptimizerParams::volatility`], or [`Error::InvalidInterval`] if there are fewer than two
+/// samples, or [`Error::InvalidTimeRange`] if the timestamps are not in chronological order or do
+/// not span any time
+#[inline]
+pub fn realized_volatility(samples: &[(u64, I24)]) -> Result<BigDecimal, Error> {
+    if samples.len() < 2 {
+        return Err(Error::InvalidInterval);
+    }
+    let mut sum_of_squares = 0_u128;
+    for window in samples.windows(2) {
+        if window[1].0 < window[0].0 {
+            return Err(Error::InvalidTimeRange);
+        }
+        let delta = i64::from(window[1].1.as_i32()) - i64::from(window[0].1.as_i32());
+        sum_of_squares += u128::from(delta.unsigned_abs()).pow(2);
+    }
+    let elapsed = samples[samples.len() - 1].0 - samples[0].0;
+    if elapsed == 0 {
+        return Err(Error::InvalidTimeRange);
+    }
+    let ln_tick_base = BigDecimal::from_str("0.000099995000333308").unwrap();
+    let variance = BigDecimal::from(sum_of_squares)
+        * ln_tick_base.square()
+        * BigDecimal::from(SECONDS_PER_YEAR)
+        / BigDecimal::from(elapsed);
+    Ok(variance.sqrt().unwrap())
+}
+
+/// Estimates the annualized realized volatility of the price from decoded `Swap` events, see
+/// [`realized_volatility`].
+///
+/// ## Arguments
+///
+/// * `swaps`: The block timestamp and decoded event of each swap in chronological order
+#[inline]
+pub fn realized_volatility_from_swaps(
+    swaps: &[(u64, IUniswapV3Pool::Swap)],
+) -> Result<BigDecimal, Error> {
+    let samples: Vec<(u64, I24)> = swaps
+        .iter()
+        .map(|(timestamp, swap)| (*timestamp, swap.tick))
+        .collect();
+    realized_volatility(&samples)
+}
+
+/// Estimates the annualized realized volatility of the price from tick cumulatives sampled at a
+/// fixed interval, where each sample is the arithmetic mean tick between two consecutive
+/// cumulatives. See [`realized_volatility`].
+///
+/// ## Arguments
+///
+/// * `tick_cumulatives`: The tick cumulatives in chronological order
+/// * `sample_interval`: The number of seconds between two consecutive tick cumulatives
+#[inline]
+pub fn realized_volatility_from_tick_cumulatives(
+    tick_cumulatives: &[I56],
+    sample_interval: u32,
+) -> Result<BigDecimal, Error> {
+    let samples = tick_cumulatives
+        .windows(2)
+        .enumerate()
+        .map(|(i, window)| {
+            Ok((
+                i as u64 * u64::from(sample_interval),
+                get_arithmetic_mean_tick(window[0], window[1], sample_interval)?,
+            ))
+        })
+        .collect::<Result<Vec<(u64, I24)>, Error>>()?;
+    realized_volatility(&samples)
+}
+
+/// Fetches the annualized realized volatility of the price of a pool over a window from its oracle
+/// observations, in a single `observe` call.
+///
+/// ## Notes
+///
+/// The observation array of the pool must be large enough to cover the window, see
+/// [`get_observation_cardinality`].
+///
+/// ## Arguments
+///
+/// * `pool`: The address of the pool
+/// * `window`: The length of the window in seconds, ending at the block
+/// * `sample_interval`: The number of seconds over which each mean tick is sampled, which must
+///   divide the window
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_realized_volatility<N, P>(
+    pool: Address,
+    window: u32,
+    sample_interval: u32,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<BigDecimal, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if sample_interval == 0 || window % sample_interval != 0 || window / sample_interval <= 1 {
+        return Err(Error::InvalidInterval);
+    }
+    let seconds_agos: Vec<u32> = (0..=window / sample_interval)
+        .rev()
+        .map(|i| i * sample_interval)
+        .collect();
+    let tick_cumulatives = IUniswapV3Pool::new(pool, provider)
+        .observe(seconds_agos)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        .tickCumulatives;
+    realized_volatility_from_tick_cumulatives(&tick_cumulatives, sample_interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, I256, U160};
+    use num_traits::ToPrimitive;
+
+    const POOL: Address = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+
+    fn tick(tick: i32) -> I24 {
+        I24::try_from(tick).unwrap()
+    }
+
+    #[test]
+    fn test_realized_volatility() {
+        // the price moves by 100 ticks, about 1%, every minute
+        let samples: Vec<(u64, I24)> = (0..=60)
+            .map(|i| (i * 60, tick(if i % 2 == 0 { 0 } else { 100 })))
+            .collect();
+        let volatility = realized_volatility(&samples).unwrap().to_f64().unwrap();
+        // 0.0099995 * sqrt(525600)
+        assert!((volatility - 7.2494).abs() < 1e-3, "{volatility}");
+        // a constant price has no volatility
+        let samples = [(0, tick(5)), (60, tick(5)), (120, tick(5))];
+        assert_eq!(realized_volatility(&samples).unwrap(), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn test_realized_volatility_invalid_samples() {
+        assert!(matches!(
+            realized_volatility(&[(0, tick(0))]),
+            Err(Error::InvalidInterval)
+        ));
+        assert!(matches!(
+            realized_volatility(&[]),
+            Err(Error::InvalidInterval)
+        ));
+        assert!(matches!(
+            realized_volatility(&[(60, tick(0)), (60, tick(10))]),
+            Err(Error::InvalidTimeRange)
+        ));
+        assert!(matches!(
+            realized_volatility(&[
+                (0, tick(0)),
+                (120, tick(10)),
+                (60, tick(20)),
+                (180, tick(0))
+            ]),
+            Err(Error::InvalidTimeRange)
+        ));
+    }
+
+    #[test]
+    fn test_realized_volatility_from_swaps() {
+        let swap = |tick_: i32| IUniswapV3Pool::Swap {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: I256::ZERO,
+            amount1: I256::ZERO,
+            sqrtPriceX96: U160::ZERO,
+            liquidity: 0,
+            tick: tick(tick_),
+        };
+        let swaps = [(0, swap(10)), (30, swap(-20)), (90, swap(40))];
+        assert_eq!(
+            realized_volatility_from_swaps(&swaps).unwrap(),
+            realized_volatility(&[(0, tick(10)), (30, tick(-20)), (90, tick(40))]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_realized_volatility_from_tick_cumulatives() {
+        // mean ticks of 10, 30 and -20 over 60 seconds each
+        let tick_cumulatives = [0, 600, 2400, 1200].map(|c: i64| I56::try_from(c).unwrap());
+        assert_eq!(
+            realized_volatility_from_tick_cumulatives(&tick_cumulatives, 60).unwrap(),
+            realized_volatility(&[(0, tick(10)), (60, tick(30)), (120, tick(-20))]).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_realized_volatility() {
+        let volatility = get_realized_volatility(POOL, 86400, 3600, PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        // the annualized volatility of ETH is typically between 20% and 200%
+        let volatility = volatility.to_f64().unwrap();
+        assert!(volatility > 0.1 && volatility < 3.0, "{volatility}");
+    }
+}
@@ -0,0 +1,784 @@
+//! ## Zap
+//! Utilities for entering a position from arbitrary token holdings, swapping the excess of one token
+//! in the pool of the position so that the balances match the ratio of the range, and for exiting a
+//! position to a single token.
+
+use crate::{
+    prelude::{Error, *},
+    swap_router::ADDRESS_THIS,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Bytes, I256, U160, U256};
+use alloy_sol_types::SolCall;
+use core::cmp::Ordering;
+use num_traits::ToPrimitive;
+use uniswap_sdk_core::prelude::*;
+
+/// The swap that leaves the balances in the ratio of a range, see [`get_optimal_swap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptimalSwap<I = i32> {
+    /// Whether token0 is swapped for token1
+    pub zero_for_one: bool,
+    /// The amount of the input token to swap
+    pub amount_in: U256,
+    /// The amount of the output token received
+    pub amount_out: U256,
+    /// The Q64.96 sqrt price of the pool after the swap
+    pub sqrt_price_x96_after: U160,
+    /// The current tick of the pool after the swap
+    pub tick_after: I,
+    /// The active liquidity of the pool after the swap
+    pub liquidity_after: u128,
+}
+
+/// Compares the ratio of the balances with the ratio of the range at the sqrt price, i.e.
+/// `amount0 / amount1` with `(1 / sp - 1 / sb) / (sp - sa)` where `sp` is clamped to `[sa, sb]`
+fn compare_ratio(
+    amount0: U256,
+    amount1: U256,
+    sqrt_price_x96: U160,
+    sqrt_price_lower_x96: U160,
+    sqrt_price_upper_x96: U160,
+) -> Ordering {
+    let sqrt_price = sqrt_price_x96
+        .clamp(sqrt_price_lower_x96, sqrt_price_upper_x96)
+        .to_big_int();
+    let sqrt_price_lower = sqrt_price_lower_x96.to_big_int();
+    let sqrt_price_upper = sqrt_price_upper_x96.to_big_int();
+    (amount0.to_big_int() * (&sqrt_price - sqrt_price_lower) * &sqrt_price * &sqrt_price_upper)
+        .cmp(&(amount1.to_big_int() * Q192.to_big_int() * (sqrt_price_upper - sqrt_price)))
+}
+
+/// Computes the swap in the pool that leaves the balances in the ratio required by
+/// [`Position::from_amounts`] for the range at the price after the swap, accounting for the price
+/// impact and the fee of the swap.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool of the position, with a tick data provider to simulate the swap
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `amount0`: The balance of token0 to enter the position with
+/// * `amount1`: The balance of token1 to enter the position with
+///
+/// ## Returns
+///
+/// The smallest swap after which the balances no longer hold an excess of the input token
+#[inline]
+pub fn get_optimal_swap<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    amount0: U256,
+    amount1: U256,
+) -> Result<OptimalSwap<TP::Index>, Error> {
+    if tick_lower >= tick_upper {
+        return Err(Error::InvalidRange);
+    }
+    let sqrt_price_lower_x96 = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+    let sqrt_price_upper_x96 = get_sqrt_ratio_at_tick(tick_upper.to_i24())?;
+    let compare = |amount0, amount1, sqrt_price_x96| {
+        compare_ratio(
+            amount0,
+            amount1,
+            sqrt_price_x96,
+            sqrt_price_lower_x96,
+            sqrt_price_upper_x96,
+        )
+    };
+    let ordering = compare(amount0, amount1, pool.sqrt_ratio_x96);
+    let zero_for_one = ordering == Ordering::Greater;
+    let balance_in = if zero_for_one { amount0 } else { amount1 };
+    if ordering == Ordering::Equal || balance_in.is_zero() {
+        return Ok(OptimalSwap {
+            zero_for_one,
+            amount_in: U256::ZERO,
+            amount_out: U256::ZERO,
+            sqrt_price_x96_after: pool.sqrt_ratio_x96,
+            tick_after: pool.tick_current,
+            liquidity_after: pool.liquidity,
+        });
+    }
+
+    // Simulates swapping `amount_in` of the input token and returns whether the balances still
+    // hold an excess of it afterwards.
+    let simulate = |amount_in: U256| -> Result<(OptimalSwap<TP::Index>, bool), Error> {
+        let state = pool._swap(zero_for_one, I256::from_raw(amount_in), None)?;
+        if !state.amount_specified_remaining.is_zero() {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let amount_out = (-state.amount_calculated).into_raw();
+        let still_excess = if zero_for_one {
+            compare(
+                amount0 - amount_in,
+                amount1 + amount_out,
+                state.sqrt_price_x96,
+            ) == Ordering::Greater
+        } else {
+            compare(
+                amount0 + amount_out,
+                amount1 - amount_in,
+                state.sqrt_price_x96,
+            ) == Ordering::Less
+        };
+        let swap = OptimalSwap {
+            zero_for_one,
+            amount_in,
+            amount_out,
+            sqrt_price_x96_after: state.sqrt_price_x96,
+            tick_after: state.tick_current,
+            liquidity_after: state.liquidity,
+        };
+        Ok((swap, still_excess))
+    };
+
+    // the excess holds for `lo` and does not hold for `hi`, which swaps the whole balance
+    let (mut lo, mut hi) = (U256::ZERO, balance_in);
+    while hi - lo > U256::from(1) {
+        let mid = lo + ((hi - lo) >> 1);
+        match simulate(mid) {
+            Ok((_, true)) => lo = mid,
+            // a swap running out of liquidity is never an excess
+            Ok(_) | Err(Error::InsufficientLiquidity) => hi = mid,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(simulate(hi)?.0)
+}
+
+/// Options for producing the calldata to enter a position from a single token, see
+/// [`zap_in_call_parameters`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZapInOptions {
+    /// How much the swap output and the amounts added to the position are allowed to fall short
+    /// of the simulated amounts.
+    pub slippage_tolerance: Percent,
+    /// Whether to mint a new position or increase the liquidity of an existing one. The pool must
+    /// already exist, so `create_pool` is not supported.
+    pub specific_opts: AddLiquiditySpecificOptions,
+    /// The account that receives the tokens left over after adding the liquidity.
+    pub recipient: Address,
+    /// The optional permit parameters for spending the input token.
+    pub input_token_permit: Option<PermitOptions>,
+}
+
+/// Produces the calldata of a [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/SwapRouter02.sol)
+/// multicall that enters a position from a single token in one transaction.
+///
+/// The router swaps the amount given by [`get_optimal_swap`] in the pool of the position and keeps
+/// the output, pulls the rest of the input token, then adds the liquidity from its balances through
+/// [`ApproveAndCall`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/base/ApproveAndCall.sol)
+/// and sweeps the leftovers to the recipient.
+///
+/// ## Notes
+///
+/// The minimum output of the swap is derived from the slippage tolerance, and the minimum amounts
+/// of the liquidity are computed from the balances at that minimum output, so that both legs
+/// revert if the execution is worse than the tolerance.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool of the position, with a tick data provider to simulate the swap
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `amount_in`: The amount of token0 or token1 of the pool to enter the position with
+/// * `options`: Options for the calldata
+#[inline]
+pub fn zap_in_call_parameters<TP>(
+    pool: &Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    amount_in: &CurrencyAmount<Token>,
+    options: ZapInOptions,
+) -> Result<MethodParameters, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let ZapInOptions {
+        slippage_tolerance,
+        specific_opts,
+        recipient,
+        input_token_permit,
+    } = options;
+    let token_in = &amount_in.currency;
+    if !pool.involves_token(token_in) {
+        return Err(Error::InvalidToken);
+    }
+    let amount = U256::from_big_int(amount_in.quotient());
+    let (amount0, amount1) = if token_in.equals(&pool.token0) {
+        (amount, U256::ZERO)
+    } else {
+        (U256::ZERO, amount)
+    };
+
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(8);
+    if let Some(input_token_permit) = input_token_permit {
+        calldatas.push(encode_permit(token_in, input_token_permit));
+    }
+    encode_zap_in(
+        pool,
+        tick_lower,
+        tick_upper,
+        amount0,
+        amount1,
+        &slippage_tolerance,
+        specific_opts,
+        recipient,
+        &mut calldatas,
+    )?;
+
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value: U256::ZERO,
+    })
+}
+
+/// Encodes the `SwapRouter02` calls that add liquidity from balances of both tokens held by the
+/// sender, swapping the excess given by [`get_optimal_swap`] first.
+///
+/// ## Returns
+///
+/// The swap simulated in the pool
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn encode_zap_in<TP>(
+    pool: &Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    amount0: U256,
+    amount1: U256,
+    slippage_tolerance: &Percent,
+    specific_opts: AddLiquiditySpecificOptions,
+    recipient: Address,
+    calldatas: &mut Vec<Bytes>,
+) -> Result<OptimalSwap<TP::Index>, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let swap = get_optimal_swap(pool, tick_lower, tick_upper, amount0, amount1)?;
+    let (token_in, token_out) = if swap.zero_for_one {
+        (&pool.token0, &pool.token1)
+    } else {
+        (&pool.token1, &pool.token0)
+    };
+    let amount_out_minimum = if swap.amount_in.is_zero() {
+        U256::ZERO
+    } else {
+        U256::from_big_int(
+            CurrencyAmount::from_raw_amount(token_out.clone(), swap.amount_out.to_big_int())?
+                .multiply(&(Percent::new(1, 1) + slippage_tolerance.clone()).invert())?
+                .quotient(),
+        )
+    };
+
+    // the balances of the sender left after paying for the swap
+    let (pull0, pull1) = if swap.zero_for_one {
+        (amount0 - swap.amount_in, amount1)
+    } else {
+        (amount0, amount1 - swap.amount_in)
+    };
+    // the balances of the router after the swap in the worst case allowed by the slippage
+    let (balance0, balance1) = if swap.zero_for_one {
+        (pull0, pull1 + amount_out_minimum)
+    } else {
+        (pull0 + amount_out_minimum, pull1)
+    };
+    let pool_after = Pool::new_with_tick_data_provider(
+        pool.token0.clone(),
+        pool.token1.clone(),
+        pool.fee,
+        swap.sqrt_price_x96_after,
+        swap.liquidity_after,
+        pool.tick_data_provider.clone(),
+    )?;
+    let mut position =
+        Position::from_amounts(pool_after, tick_lower, tick_upper, balance0, balance1, true)?;
+    if position.liquidity == 0 {
+        return Err(Error::InvalidAmounts);
+    }
+    let MintAmounts {
+        amount0: amount0_min,
+        amount1: amount1_min,
+    } = position.mint_amounts_with_slippage(slippage_tolerance)?;
+
+    // swap, paid by the sender and keeping the output in the router
+    if !swap.amount_in.is_zero() {
+        calldatas.push(
+            IV3SwapRouter::exactInputSingleCall {
+                params: IV3SwapRouter::ExactInputSingleParams {
+                    tokenIn: token_in.address(),
+                    tokenOut: token_out.address(),
+                    fee: pool.fee.into(),
+                    recipient: ADDRESS_THIS,
+                    amountIn: swap.amount_in,
+                    amountOutMinimum: amount_out_minimum,
+                    sqrtPriceLimitX96: U160::ZERO,
+                },
+            }
+            .abi_encode()
+            .into(),
+        );
+    }
+
+    // pull the rest of the balances
+    let token0 = pool.token0.address();
+    let token1 = pool.token1.address();
+    for (token, value) in [(token0, pull0), (token1, pull1)] {
+        if !value.is_zero() {
+            calldatas.push(
+                IPeripheryPaymentsExtended::pullCall { token, value }
+                    .abi_encode()
+                    .into(),
+            );
+        }
+    }
+
+    // add the liquidity from the balances of the router
+    calldatas.push(
+        IApproveAndCall::approveMaxCall { token: token0 }
+            .abi_encode()
+            .into(),
+    );
+    calldatas.push(
+        IApproveAndCall::approveMaxCall { token: token1 }
+            .abi_encode()
+            .into(),
+    );
+    calldatas.push(match specific_opts {
+        AddLiquiditySpecificOptions::Mint(opts) => {
+            if opts.create_pool {
+                return Err(Error::CreatePoolNotSupported);
+            }
+            IApproveAndCall::mintCall {
+                params: IApproveAndCall::MintParams {
+                    token0,
+                    token1,
+                    fee: pool.fee.into(),
+                    tickLower: tick_lower.to_i24(),
+                    tickUpper: tick_upper.to_i24(),
+                    amount0Min: amount0_min,
+                    amount1Min: amount1_min,
+                    recipient: opts.recipient,
+                },
+            }
+            .abi_encode()
+            .into()
+        }
+        AddLiquiditySpecificOptions::Increase(opts) => IApproveAndCall::increaseLiquidityCall {
+            params: IApproveAndCall::IncreaseLiquidityParams {
+                token0,
+                token1,
+                tokenId: opts.token_id,
+                amount0Min: amount0_min,
+                amount1Min: amount1_min,
+            },
+        }
+        .abi_encode()
+        .into(),
+    });
+
+    // return the leftovers
+    calldatas.push(encode_sweep_token(token0, U256::ZERO, recipient, None));
+    calldatas.push(encode_sweep_token(token1, U256::ZERO, recipient, None));
+
+    Ok(swap)
+}
+
+/// The calldata to exit a position to a single currency, see [`zap_out_call_parameters`].
+#[derive(Clone, Debug)]
+pub struct ZapOutParameters<TOutput: BaseCurrency> {
+    /// The calldata of the `NonfungiblePositionManager` multicall that removes the liquidity and
+    /// collects the tokens to the recipient
+    pub remove: MethodParameters,
+    /// The calldata of the `SwapRouter02` multicall that swaps the collected amount of the other
+    /// token into the output currency, if there is anything to swap
+    pub swap: Option<MethodParameters>,
+    /// The minimum amount of the output currency received from both transactions, with the
+    /// slippage tolerance applied to the removed liquidity and to the swap
+    pub amount_out_minimum: CurrencyAmount<TOutput>,
+}
+
+/// Produces the calldata for completely or partially exiting a position to a single currency.
+///
+/// The liquidity is removed and collected to the recipient of the collect options through the
+/// `NonfungiblePositionManager` as in [`remove_call_parameters`], then the minimum collected
+/// amount of the other token is swapped into the output currency in the pool of the position as
+/// in [`swap_call_parameters`], unwrapping WETH9 if the output is native.
+///
+/// ## Notes
+///
+/// The two contracts cannot be called in the same multicall, so the exit takes two transactions
+/// sent by the recipient, which must approve the router to spend the token being swapped. The
+/// swap is simulated in the pool before the liquidity is removed.
+///
+/// ## Arguments
+///
+/// * `position`: The position to exit, with a tick data provider to simulate the swap
+/// * `output`: The currency to exit to, either token of the pool or the native currency
+/// * `options`: Options for removing the liquidity, whose slippage tolerance also applies to the
+///   swap
+#[inline]
+pub fn zap_out_call_parameters<Currency0, Currency1, TOutput, TP>(
+    position: &Position<TP>,
+    output: &TOutput,
+    options: RemoveLiquidityOptions<Currency0, Currency1>,
+) -> Result<ZapOutParameters<TOutput>, Error>
+where
+    Currency0: BaseCurrency,
+    Currency1: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: Clone + TickDataProvider,
+{
+    let pool = &position.pool;
+    if !pool.involves_token(output.wrapped()) {
+        return Err(Error::InvalidToken);
+    }
+    let output_is_token0 = output.wrapped().equals(&pool.token0);
+
+    // the amounts collected in the worst case allowed by the slippage
+    let partial_position = Position::new(
+        pool.clone(),
+        (options.liquidity_percentage.clone() * Percent::new(position.liquidity, 1))
+            .quotient()
+            .to_u128()
+            .ok_or(Error::InvalidAmounts)?,
+        position.tick_lower,
+        position.tick_upper,
+    );
+    let (amount0_min, amount1_min) =
+        partial_position.burn_amounts_with_slippage(&options.slippage_tolerance)?;
+    let collected0 =
+        amount0_min.to_big_int() + options.collect_options.expected_currency_owed0.quotient();
+    let collected1 =
+        amount1_min.to_big_int() + options.collect_options.expected_currency_owed1.quotient();
+    let (mut amount_out_minimum, amount_to_swap, token_to_swap, swap_is_native) =
+        if output_is_token0 {
+            (
+                collected0,
+                collected1,
+                &pool.token1,
+                options
+                    .collect_options
+                    .expected_currency_owed1
+                    .currency
+                    .is_native(),
+            )
+        } else {
+            (
+                collected1,
+                collected0,
+                &pool.token0,
+                options
+                    .collect_options
+                    .expected_currency_owed0
+                    .currency
+                    .is_native(),
+            )
+        };
+
+    let swap = if amount_to_swap > BigInt::ZERO {
+        if swap_is_native {
+            return Err(Error::InvalidToken);
+        }
+        let slippage_tolerance = options.slippage_tolerance.clone();
+        let mut trade = Trade::exact_in(
+            Route::new(vec![pool.clone()], token_to_swap.clone(), output.clone()),
+            CurrencyAmount::from_raw_amount(token_to_swap.clone(), amount_to_swap)?,
+        )?;
+        amount_out_minimum += trade
+            .minimum_amount_out(slippage_tolerance.clone(), None)?
+            .quotient();
+        Some(swap_call_parameters(
+            core::slice::from_mut(&mut trade),
+            SwapOptions {
+                slippage_tolerance,
+                recipient: options.collect_options.recipient,
+                ..Default::default()
+            },
+        )?)
+    } else {
+        None
+    };
+
+    Ok(ZapOutParameters {
+        remove: remove_call_parameters(position, options)?,
+        swap,
+        amount_out_minimum: CurrencyAmount::from_raw_amount(output.clone(), amount_out_minimum)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn assert_little_dust(amount0: u64, amount1: u64) {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let (amount0, amount1) = (U256::from(amount0), U256::from(amount1));
+        let swap = get_optimal_swap(&pool, -600, 600, amount0, amount1).unwrap();
+        let (amount0, amount1) = if swap.zero_for_one {
+            (amount0 - swap.amount_in, amount1 + swap.amount_out)
+        } else {
+            (amount0 + swap.amount_out, amount1 - swap.amount_in)
+        };
+        let pool = Pool::new_with_tick_data_provider(
+            pool.token0,
+            pool.token1,
+            pool.fee,
+            swap.sqrt_price_x96_after,
+            swap.liquidity_after,
+            pool.tick_data_provider,
+        )
+        .unwrap();
+        let position = Position::from_amounts(pool, -600, 600, amount0, amount1, true).unwrap();
+        let MintAmounts {
+            amount0: used0,
+            amount1: used1,
+        } = position.mint_amounts().unwrap();
+        assert!(amount0 - used0 <= U256::from(10));
+        assert!(amount1 - used1 <= U256::from(10));
+    }
+
+    #[test]
+    fn test_zap_from_token0() {
+        assert_little_dust(100_000, 0);
+    }
+
+    #[test]
+    fn test_zap_from_token1() {
+        assert_little_dust(0, 100_000);
+    }
+
+    #[test]
+    fn test_zap_from_both_tokens() {
+        assert_little_dust(100_000, 20_000);
+        assert_little_dust(20_000, 100_000);
+    }
+
+    #[test]
+    fn test_out_of_range_swaps_everything() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        // the range is below the price so the position only holds token1
+        let swap = get_optimal_swap(&pool, -1200, -600, U256::from(1000), U256::ZERO).unwrap();
+        assert!(swap.zero_for_one);
+        assert_eq!(swap.amount_in, U256::from(1000));
+    }
+
+    mod zap_in_call_parameters {
+        use super::*;
+        use alloy_primitives::address;
+
+        const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+        fn options(specific_opts: AddLiquiditySpecificOptions) -> ZapInOptions {
+            ZapInOptions {
+                slippage_tolerance: Percent::new(1, 100),
+                specific_opts,
+                recipient: RECIPIENT,
+                input_token_permit: None,
+            }
+        }
+
+        const MINT: AddLiquiditySpecificOptions =
+            AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                recipient: RECIPIENT,
+                create_pool: false,
+            });
+
+        #[test]
+        fn test_mint() {
+            let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+            let swap = get_optimal_swap(&pool, -600, 600, U256::from(100_000), U256::ZERO).unwrap();
+            let params =
+                zap_in_call_parameters(&pool, -600, 600, &amount_in, options(MINT)).unwrap();
+            assert_eq!(params.value, U256::ZERO);
+
+            let calldatas: Vec<Bytes> = decode_multicall(&params.calldata).unwrap();
+            assert_eq!(calldatas.len(), 7);
+            let swap_params = IV3SwapRouter::exactInputSingleCall::abi_decode(&calldatas[0], true)
+                .unwrap()
+                .params;
+            assert_eq!(swap_params.recipient, ADDRESS_THIS);
+            assert_eq!(swap_params.amountIn, swap.amount_in);
+            assert!(swap_params.amountOutMinimum > U256::ZERO);
+            assert!(swap_params.amountOutMinimum < swap.amount_out);
+
+            // the swap is paid by the sender, so only the rest is pulled
+            let pull =
+                IPeripheryPaymentsExtended::pullCall::abi_decode(&calldatas[1], true).unwrap();
+            assert_eq!(pull.token, TOKEN0.address());
+            assert_eq!(pull.value, U256::from(100_000) - swap.amount_in);
+
+            let mint_params = IApproveAndCall::mintCall::abi_decode(&calldatas[4], true)
+                .unwrap()
+                .params;
+            assert_eq!(mint_params.recipient, RECIPIENT);
+            assert_eq!(mint_params.tickLower.as_i32(), -600);
+            assert_eq!(mint_params.tickUpper.as_i32(), 600);
+            // the minimum amounts do not exceed the balances in the worst case of the swap
+            assert!(mint_params.amount0Min > U256::ZERO);
+            assert!(mint_params.amount0Min <= U256::from(100_000) - swap.amount_in);
+            assert!(mint_params.amount1Min > U256::ZERO);
+            assert!(mint_params.amount1Min <= swap_params.amountOutMinimum);
+        }
+
+        #[test]
+        fn test_increase_liquidity() {
+            let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100_000).unwrap();
+            let params = zap_in_call_parameters(
+                &pool,
+                -600,
+                600,
+                &amount_in,
+                options(AddLiquiditySpecificOptions::Increase(
+                    IncreaseSpecificOptions {
+                        token_id: U256::from(1),
+                    },
+                )),
+            )
+            .unwrap();
+            let calldatas: Vec<Bytes> = decode_multicall(&params.calldata).unwrap();
+            assert_eq!(calldatas.len(), 7);
+            let swap_params = IV3SwapRouter::exactInputSingleCall::abi_decode(&calldatas[0], true)
+                .unwrap()
+                .params;
+            assert_eq!(swap_params.tokenIn, TOKEN1.address());
+            let increase_params =
+                IApproveAndCall::increaseLiquidityCall::abi_decode(&calldatas[4], true)
+                    .unwrap()
+                    .params;
+            assert_eq!(increase_params.tokenId, U256::from(1));
+        }
+
+        #[test]
+        fn test_invalid_parameters() {
+            let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+            assert!(matches!(
+                zap_in_call_parameters(&pool, 600, -600, &amount_in, options(MINT)),
+                Err(Error::InvalidRange)
+            ));
+            let other = CurrencyAmount::from_raw_amount(TOKEN2.clone(), 100_000).unwrap();
+            assert!(matches!(
+                zap_in_call_parameters(&pool, -600, 600, &other, options(MINT)),
+                Err(Error::InvalidToken)
+            ));
+            let create_pool = AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                recipient: RECIPIENT,
+                create_pool: true,
+            });
+            assert!(matches!(
+                zap_in_call_parameters(&pool, -600, 600, &amount_in, options(create_pool)),
+                Err(Error::CreatePoolNotSupported)
+            ));
+        }
+
+        #[test]
+        fn test_out_of_range_skips_swap() {
+            let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+            // the range is above the price so the position only holds token0
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100_000).unwrap();
+            let params =
+                zap_in_call_parameters(&pool, 600, 1200, &amount_in, options(MINT)).unwrap();
+            let calldatas: Vec<Bytes> = decode_multicall(&params.calldata).unwrap();
+            assert_eq!(calldatas.len(), 6);
+            assert!(IApproveAndCall::mintCall::abi_decode(&calldatas[3], true).is_ok());
+        }
+    }
+
+    mod zap_out_call_parameters {
+        use super::*;
+        use alloy_primitives::address;
+
+        const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+        fn options<Currency0: BaseCurrency, Currency1: BaseCurrency>(
+            currency0: Currency0,
+            currency1: Currency1,
+        ) -> RemoveLiquidityOptions<Currency0, Currency1> {
+            RemoveLiquidityOptions {
+                token_id: U256::from(1),
+                liquidity_percentage: Percent::new(1, 1),
+                slippage_tolerance: Percent::new(1, 100),
+                deadline: U256::from(123),
+                burn_token: true,
+                permit: None,
+                collect_options: CollectOptions {
+                    token_id: U256::from(1),
+                    expected_currency_owed0: CurrencyAmount::from_raw_amount(currency0, 0).unwrap(),
+                    expected_currency_owed1: CurrencyAmount::from_raw_amount(currency1, 0).unwrap(),
+                    recipient: RECIPIENT,
+                },
+                chain_id: None,
+                deployment: None,
+            }
+        }
+
+        #[test]
+        fn test_exit_to_token0() {
+            let position = Position::new(
+                make_pool(TOKEN0.clone(), TOKEN1.clone()),
+                100_000,
+                -600,
+                600,
+            );
+            let options = options(TOKEN0.clone(), TOKEN1.clone());
+            let params = zap_out_call_parameters(&position, &*TOKEN0, options.clone()).unwrap();
+            assert_eq!(
+                params.remove,
+                remove_call_parameters(&position, options.clone()).unwrap()
+            );
+
+            let (amount0_min, amount1_min) = position
+                .burn_amounts_with_slippage(&options.slippage_tolerance)
+                .unwrap();
+            let swap = params.swap.unwrap();
+            let swap_params = IV3SwapRouter::exactInputSingleCall::abi_decode(&swap.calldata, true)
+                .unwrap()
+                .params;
+            assert_eq!(swap_params.tokenIn, TOKEN1.address());
+            assert_eq!(swap_params.amountIn, amount1_min);
+            assert_eq!(swap_params.recipient, RECIPIENT);
+            assert_eq!(
+                params.amount_out_minimum.quotient(),
+                (amount0_min + swap_params.amountOutMinimum).to_big_int()
+            );
+        }
+
+        #[test]
+        fn test_exit_to_native() {
+            let position =
+                Position::new(make_pool(TOKEN1.clone(), WETH.clone()), 100_000, -600, 600);
+            let options = options(TOKEN1.clone(), ETHER.clone());
+            let params = zap_out_call_parameters(&position, &*ETHER, options).unwrap();
+            assert!(params.amount_out_minimum.currency.is_native());
+            // the router keeps the output and unwraps it to the recipient
+            let calldatas: Vec<Bytes> = decode_multicall(&params.swap.unwrap().calldata).unwrap();
+            assert_eq!(calldatas.len(), 2);
+            let unwrap =
+                IPeripheryPaymentsWithFee::unwrapWETH9Call::abi_decode(&calldatas[1], true)
+                    .unwrap();
+            assert_eq!(unwrap.recipient, RECIPIENT);
+        }
+
+        #[test]
+        fn test_out_of_range_skips_swap() {
+            // the range is above the price so the position only holds token0
+            let position = Position::new(
+                make_pool(TOKEN0.clone(), TOKEN1.clone()),
+                100_000,
+                600,
+                1200,
+            );
+            let params = zap_out_call_parameters(
+                &position,
+                &*TOKEN0,
+                options(TOKEN0.clone(), TOKEN1.clone()),
+            )
+            .unwrap();
+            assert!(params.swap.is_none());
+            assert!(params.amount_out_minimum.quotient() > BigInt::ZERO);
+        }
+    }
+}
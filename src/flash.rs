@@ -0,0 +1,161 @@
+//! ## Flash
+//! Produces the calldata of `IUniswapV3Pool.flash` for borrowing the tokens of a pool within a
+//! transaction, the fees owed to the pool in `uniswapV3FlashCallback`, and the callback data.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::U24, Address, Bytes, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use uniswap_sdk_core::prelude::*;
+
+/// Computes the fee of a flash loan of an amount from a pool, rounded up as by the pool.
+///
+/// ## Arguments
+///
+/// * `amount`: The amount of a token borrowed
+/// * `fee`: The fee tier of the pool
+#[inline]
+pub fn get_flash_fee(amount: U256, fee: FeeAmount) -> Result<U256, Error> {
+    amount.mul_div_rounding_up(U256::from(U24::from(fee)), U256::from(1_000_000))
+}
+
+/// Computes the amounts to repay to a pool at the end of the flash callback, i.e. the borrowed
+/// amounts plus the fees.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to borrow from
+/// * `amount0`: The amount of token0 borrowed
+/// * `amount1`: The amount of token1 borrowed
+#[inline]
+pub fn get_flash_repayment<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    amount0: U256,
+    amount1: U256,
+) -> Result<(U256, U256), Error> {
+    Ok((
+        amount0 + get_flash_fee(amount0, pool.fee)?,
+        amount1 + get_flash_fee(amount1, pool.fee)?,
+    ))
+}
+
+/// Produces the calldata for a flash loan from a pool, which calls back `msg.sender` with
+/// `uniswapV3FlashCallback` and requires the borrowed amounts plus the fees to be paid back by the
+/// end of the callback.
+///
+/// ## Arguments
+///
+/// * `recipient`: The recipient of the borrowed amounts
+/// * `amount0`: The amount of token0 to borrow
+/// * `amount1`: The amount of token1 to borrow
+/// * `data`: The callback data, e.g. from [`encode_flash_callback_data`]
+#[inline]
+#[must_use]
+pub fn flash_call_parameters(
+    recipient: Address,
+    amount0: U256,
+    amount1: U256,
+    data: Bytes,
+) -> MethodParameters {
+    MethodParameters {
+        calldata: IUniswapV3PoolActions::flashCall {
+            recipient,
+            amount0,
+            amount1,
+            data,
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// Encodes the callback data of a flash loan as the borrowed amounts and the payer followed by the
+/// pool key.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to borrow from
+/// * `amount0`: The amount of token0 borrowed
+/// * `amount1`: The amount of token1 borrowed
+/// * `payer`: The account that repays the loan in the callback
+#[inline]
+#[must_use]
+pub fn encode_flash_callback_data<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    amount0: U256,
+    amount1: U256,
+    payer: Address,
+) -> Bytes {
+    FlashCallbackData {
+        amount0,
+        amount1,
+        payer,
+        token0: pool.token0.address(),
+        token1: pool.token1.address(),
+        fee: pool.fee.into(),
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the callback data of [`encode_flash_callback_data`]
+#[inline]
+pub fn decode_flash_callback_data(
+    data: impl AsRef<[u8]>,
+) -> Result<FlashCallbackData, alloy_sol_types::Error> {
+    FlashCallbackData::abi_decode(data.as_ref(), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    #[test]
+    fn test_get_flash_fee() {
+        assert_eq!(
+            get_flash_fee(U256::from(1_000_000), FeeAmount::MEDIUM).unwrap(),
+            U256::from(3000)
+        );
+        // rounded up
+        assert_eq!(
+            get_flash_fee(U256::from(1), FeeAmount::LOWEST).unwrap(),
+            U256::from(1)
+        );
+        assert_eq!(
+            get_flash_fee(U256::ZERO, FeeAmount::HIGH).unwrap(),
+            U256::ZERO
+        );
+        assert_eq!(
+            get_flash_repayment(&POOL_0_1, U256::from(1000), U256::from(333_334)).unwrap(),
+            (U256::from(1003), U256::from(334_335))
+        );
+    }
+
+    #[test]
+    fn test_flash_call_parameters() {
+        let data = encode_flash_callback_data(&POOL_0_1, U256::from(1), U256::from(2), RECIPIENT);
+        let MethodParameters { calldata, value } =
+            flash_call_parameters(RECIPIENT, U256::from(1), U256::from(2), data.clone());
+        assert_eq!(value, U256::ZERO);
+        let call = IUniswapV3PoolActions::flashCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(call.recipient, RECIPIENT);
+        assert_eq!(call.amount0, U256::from(1));
+        assert_eq!(call.amount1, U256::from(2));
+        assert_eq!(call.data, data);
+        assert_eq!(
+            decode_flash_callback_data(data).unwrap(),
+            FlashCallbackData {
+                amount0: U256::from(1),
+                amount1: U256::from(2),
+                payer: RECIPIENT,
+                token0: TOKEN0.address(),
+                token1: TOKEN1.address(),
+                fee: U24::from(3000),
+            }
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! ## Intents
+//! A high-level entry point over the lower-level calldata builders. An [`Intent`] describes what
+//! the user wants, e.g. a swap or providing liquidity, and is resolved against a [`ChainContext`]
+//! into a validated [`TransactionPlan`].
+
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::{aliases::I24, ChainId, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// The on-chain context an [`Intent`] is resolved against.
+#[derive(Clone, Debug)]
+pub struct ChainContext<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    /// The chain id
+    pub chain_id: ChainId,
+    /// The swap router address
+    pub swap_router: Address,
+    /// The nonfungible position manager address
+    pub nonfungible_position_manager: Address,
+    /// The account that receives the output of the transactions
+    pub recipient: Address,
+    /// When the transactions expire, in epoch seconds
+    pub deadline: U256,
+    /// The pools to route swaps through and to provide liquidity to
+    pub pools: Vec<Pool<TP>>,
+}
+
+impl<TP: TickDataProvider> ChainContext<TP> {
+    /// Creates a context targeting the contracts of a deployment, e.g. of a fork
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `deployment`: The deployment, see [`DeploymentConfig::get`]
+    /// * `recipient`: The account that receives the output of the transactions
+    /// * `deadline`: When the transactions expire, in epoch seconds
+    /// * `pools`: The pools to route swaps through and to provide liquidity to
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        chain_id: ChainId,
+        deployment: &DeploymentConfig,
+        recipient: Address,
+        deadline: U256,
+        pools: Vec<Pool<TP>>,
+    ) -> Self {
+        Self {
+            chain_id,
+            swap_router: deployment.swap_router,
+            nonfungible_position_manager: deployment.nonfungible_position_manager,
+            recipient,
+            deadline,
+            pools,
+        }
+    }
+}
+
+/// The price range of a position to open.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeSpec<I = i32> {
+    /// The full price range
+    FullRange,
+    /// An explicit tick range
+    Ticks { tick_lower: I, tick_upper: I },
+    /// A range of the given number of ticks on each side of the current tick
+    Width(I),
+}
+
+impl<I: TickIndex> RangeSpec<I> {
+    /// Resolve the range to usable ticks of the pool
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to open the position in
+    #[inline]
+    pub fn ticks<TP>(&self, pool: &Pool<TP>) -> Result<(I, I), Error>
+    where
+        TP: TickDataProvider<Index = I>,
+    {
+        let tick_spacing = pool.tick_spacing();
+        let usable = |tick: i32| {
+            nearest_usable_tick(
+                I::from_i24(I24::try_from(tick.clamp(MIN_TICK_I32, MAX_TICK_I32)).unwrap()),
+                tick_spacing,
+            )
+        };
+        let (tick_lower, tick_upper) = match *self {
+            Self::FullRange => (usable(MIN_TICK_I32), usable(MAX_TICK_I32)),
+            Self::Ticks {
+                tick_lower,
+                tick_upper,
+            } => (tick_lower, tick_upper),
+            Self::Width(width) => {
+                let tick_current = pool.tick_current.to_i24().as_i32();
+                let width = width.to_i24().as_i32();
+                if width <= 0 {
+                    return Err(Error::InvalidRange);
+                }
+                (
+                    usable(tick_current.checked_sub(width).ok_or(Error::InvalidRange)?),
+                    usable(tick_current.checked_add(width).ok_or(Error::InvalidRange)?),
+                )
+            }
+        };
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidRange);
+        }
+        Ok((tick_lower, tick_upper))
+    }
+}
+
+/// What the user wants to do.
+#[derive(Clone, Debug)]
+pub enum Intent<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    /// Sell an exact amount of a token for another token
+    Swap {
+        sell: Token,
+        buy: Token,
+        amount: U256,
+        max_slippage: Percent,
+    },
+    /// Provide liquidity to a pool by minting a new position
+    ProvideLiquidity {
+        pair: (Token, Token),
+        fee: FeeAmount,
+        /// The amounts of the tokens of the pair to provide, in the order of the pair
+        value: (U256, U256),
+        range_spec: RangeSpec<TP::Index>,
+        max_slippage: Percent,
+    },
+    /// Withdraw all liquidity and fees of a position and burn it
+    Exit {
+        token_id: U256,
+        position: Position<TP>,
+        /// The fees owed to the position in token0 and token1, collected with the withdrawn
+        /// liquidity
+        fees_owed: (U256, U256),
+        max_slippage: Percent,
+    },
+}
+
+/// A validated transaction resolved from an [`Intent`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionPlan {
+    /// The contract to call
+    pub to: Address,
+    /// The calldata and value of the transaction
+    pub parameters: MethodParameters,
+}
+
+impl<TP: Clone + TickDataProvider> Intent<TP> {
+    /// Resolve the intent into a transaction plan
+    ///
+    /// ## Arguments
+    ///
+    /// * `context`: The on-chain context to resolve the intent against
+    #[inline]
+    pub fn resolve(&self, context: &ChainContext<TP>) -> Result<TransactionPlan, Error> {
+        match self {
+            Self::Swap {
+                sell,
+                buy,
+                amount,
+                max_slippage,
+            } => {
+                if sell.chain_id() != context.chain_id || buy.chain_id() != context.chain_id {
+                    return Err(Error::InvalidToken);
+                }
+                if context.pools.is_empty() {
+                    return Err(Error::NoRouteFound);
+                }
+                let amount_in = CurrencyAmount::from_raw_amount(sell.clone(), amount.to_big_int())?;
+                let mut best_trades = Vec::with_capacity(1);
+                Trade::best_trade_exact_in(
+                    context.pools.clone(),
+                    &amount_in,
+                    buy,
+                    BestTradeOptions {
+                        max_num_results: Some(1),
+                        max_hops: None,
+                    },
+                    Vec::new(),
+                    None,
+                    &mut best_trades,
+                )?;
+                let Some(trade) = best_trades.pop() else {
+                    return Err(Error::NoRouteFound);
+                };
+                let parameters = swap_call_parameters(
+                    &mut [trade],
+                    SwapOptions {
+                        slippage_tolerance: max_slippage.clone(),
+                        recipient: context.recipient,
+                        chain_id: Some(context.chain_id),
+                        ..Default::default()
+                    },
+                )?;
+                Ok(TransactionPlan {
+                    to: context.swap_router,
+                    parameters,
+                })
+            }
+            Self::ProvideLiquidity {
+                pair: (token_a, token_b),
+                fee,
+                value: (amount_a, amount_b),
+                range_spec,
+                max_slippage,
+            } => {
+                let pool = context
+                    .pools
+                    .iter()
+                    .find(|pool| {
+                        pool.fee == *fee
+                            && pool.involves_token(token_a)
+                            && pool.involves_token(token_b)
+                    })
+                    .ok_or(Error::PoolNotFound)?;
+                let (tick_lower, tick_upper) = range_spec.ticks(pool)?;
+                let (amount0, amount1) = if pool.token0.equals(token_a) {
+                    (*amount_a, *amount_b)
+                } else {
+                    (*amount_b, *amount_a)
+                };
+                let mut position = Position::from_amounts(
+                    pool.clone(),
+                    tick_lower,
+                    tick_upper,
+                    amount0,
+                    amount1,
+                    true,
+                )?;
+                if position.liquidity == 0 {
+                    return Err(Error::InsufficientLiquidity);
+                }
+                let parameters = add_call_parameters(
+                    &mut position,
+                    AddLiquidityOptions {
+                        slippage_tolerance: max_slippage.clone(),
+                        deadline: context.deadline,
+                        use_native: None,
+                        token0_permit: None,
+                        token1_permit: None,
+                        specific_opts: AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                            recipient: context.recipient,
+                            create_pool: false,
+                        }),
+                        chain_id: Some(context.chain_id),
+                        deployment: None,
+                    },
+                )?;
+                Ok(TransactionPlan {
+                    to: context.nonfungible_position_manager,
+                    parameters,
+                })
+            }
+            Self::Exit {
+                token_id,
+                position,
+                fees_owed: (fees_owed0, fees_owed1),
+                max_slippage,
+            } => {
+                if position.liquidity == 0 {
+                    return Err(Error::InsufficientLiquidity);
+                }
+                let parameters = remove_call_parameters(
+                    position,
+                    RemoveLiquidityOptions {
+                        token_id: *token_id,
+                        liquidity_percentage: Percent::new(1, 1),
+                        slippage_tolerance: max_slippage.clone(),
+                        deadline: context.deadline,
+                        burn_token: true,
+                        permit: None,
+                        // the minimum amounts of the burned liquidity are added to the fees owed
+                        collect_options: CollectOptions {
+                            token_id: *token_id,
+                            expected_currency_owed0: CurrencyAmount::from_raw_amount(
+                                position.pool.token0.clone(),
+                                fees_owed0.to_big_int(),
+                            )?,
+                            expected_currency_owed1: CurrencyAmount::from_raw_amount(
+                                position.pool.token1.clone(),
+                                fees_owed1.to_big_int(),
+                            )?,
+                            recipient: context.recipient,
+                        },
+                        chain_id: Some(context.chain_id),
+                        deployment: None,
+                    },
+                )?;
+                Ok(TransactionPlan {
+                    to: context.nonfungible_position_manager,
+                    parameters,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::address;
+    use once_cell::sync::Lazy;
+
+    const SWAP_ROUTER: Address = address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45");
+    const NPM: Address = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    static CONTEXT: Lazy<ChainContext<TickListDataProvider>> = Lazy::new(|| ChainContext {
+        chain_id: 1,
+        swap_router: SWAP_ROUTER,
+        nonfungible_position_manager: NPM,
+        recipient: RECIPIENT,
+        deadline: U256::from(123),
+        pools: vec![
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            make_pool(TOKEN1.clone(), TOKEN2.clone()),
+        ],
+    });
+
+    #[test]
+    fn chain_context_new() {
+        let deployment = DeploymentConfig::get(Fork::UniswapV3, 1).unwrap();
+        let context = ChainContext::new(
+            1,
+            &deployment,
+            RECIPIENT,
+            U256::from(123),
+            CONTEXT.pools.clone(),
+        );
+        assert_eq!(context.swap_router, SWAP_ROUTER);
+        assert_eq!(context.nonfungible_position_manager, NPM);
+    }
+
+    #[test]
+    fn range_spec_ticks() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        assert_eq!(
+            RangeSpec::FullRange.ticks(&pool).unwrap(),
+            (-887220, 887220)
+        );
+        assert_eq!(RangeSpec::Width(100).ticks(&pool).unwrap(), (-120, 120));
+        assert!(matches!(
+            RangeSpec::Ticks {
+                tick_lower: 60,
+                tick_upper: -60
+            }
+            .ticks(&pool),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            RangeSpec::Width(-100).ticks(&pool),
+            Err(Error::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn resolve_swap() {
+        let plan = Intent::Swap {
+            sell: TOKEN0.clone(),
+            buy: TOKEN2.clone(),
+            amount: U256::from(100),
+            max_slippage: Percent::new(1, 100),
+        }
+        .resolve(&CONTEXT)
+        .unwrap();
+        assert_eq!(plan.to, SWAP_ROUTER);
+        assert_eq!(plan.parameters.value, U256::ZERO);
+        assert_eq!(
+            plan.parameters.calldata[..4],
+            IV3SwapRouter::exactInputCall::SELECTOR
+        );
+    }
+
+    #[test]
+    fn resolve_swap_without_route() {
+        let result = Intent::Swap {
+            sell: TOKEN0.clone(),
+            buy: TOKEN3.clone(),
+            amount: U256::from(100),
+            max_slippage: Percent::new(1, 100),
+        }
+        .resolve(&CONTEXT);
+        assert!(matches!(result, Err(Error::NoRouteFound)));
+    }
+
+    #[test]
+    fn resolve_provide_liquidity() {
+        let plan = Intent::ProvideLiquidity {
+            pair: (TOKEN1.clone(), TOKEN0.clone()),
+            fee: FeeAmount::MEDIUM,
+            value: (U256::from(100), U256::from(100)),
+            range_spec: RangeSpec::FullRange,
+            max_slippage: Percent::new(1, 100),
+        }
+        .resolve(&CONTEXT)
+        .unwrap();
+        assert_eq!(plan.to, NPM);
+        assert_eq!(
+            plan.parameters.calldata[..4],
+            INonfungiblePositionManager::mintCall::SELECTOR
+        );
+        let result = Intent::ProvideLiquidity {
+            pair: (TOKEN1.clone(), TOKEN0.clone()),
+            fee: FeeAmount::LOW,
+            value: (U256::from(100), U256::from(100)),
+            range_spec: RangeSpec::FullRange,
+            max_slippage: Percent::new(1, 100),
+        }
+        .resolve(&CONTEXT);
+        assert!(matches!(result, Err(Error::PoolNotFound)));
+    }
+
+    #[test]
+    fn resolve_exit() {
+        let plan = Intent::Exit {
+            token_id: U256::from(1),
+            position: Position::new(make_pool(TOKEN0.clone(), TOKEN1.clone()), 100, -60, 60),
+            fees_owed: (U256::from(10), U256::from(20)),
+            max_slippage: Percent::new(1, 100),
+        }
+        .resolve(&CONTEXT)
+        .unwrap();
+        assert_eq!(plan.to, NPM);
+        assert_eq!(
+            plan.parameters.calldata[..4],
+            IMulticall::multicallCall::SELECTOR
+        );
+    }
+}
@@ -56,12 +56,18 @@
 extern crate alloc;
 
 pub mod abi;
+pub mod chains;
 pub mod constants;
+pub mod deployment;
 pub mod entities;
 pub mod error;
+pub mod flash;
+pub mod intents;
+pub mod migrator;
 pub mod multicall;
 pub mod nonfungible_position_manager;
 pub mod payments;
+pub mod pool_swap;
 pub mod quoter;
 pub mod self_permit;
 pub mod staker;
@@ -76,8 +82,9 @@ mod tests;
 
 pub mod prelude {
     pub use crate::{
-        abi::*, constants::*, entities::*, error::*, multicall::*, nonfungible_position_manager::*,
-        payments::*, quoter::*, self_permit::*, staker::*, swap_router::*, utils::*,
+        abi::*, chains::*, constants::*, deployment::*, entities::*, error::*, flash::*,
+        intents::*, migrator::*, multicall::*, nonfungible_position_manager::*, payments::*,
+        pool_swap::*, quoter::*, self_permit::*, staker::*, swap_router::*, utils::*,
     };
 
     pub use uniswap_sdk_core as sdk_core;
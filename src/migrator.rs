@@ -0,0 +1,247 @@
+use crate::{
+    nonfungible_position_manager::encode_create,
+    prelude::{Error, *},
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Bytes, U256};
+use alloy_sol_types::SolCall;
+use uniswap_sdk_core::prelude::*;
+
+/// Options for producing the calldata to migrate V2 liquidity to V3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrateOptions {
+    /// The V2 pair to migrate from, as the token of its liquidity shares.
+    pub pair: Token,
+    /// The amount of V2 liquidity tokens to migrate.
+    pub liquidity_to_migrate: U256,
+    /// The percentage of the withdrawn amounts to add to the V3 position, from 1 to 100. The rest
+    /// is refunded to the sender.
+    pub percentage_to_migrate: u8,
+    /// How much the pool price is allowed to move.
+    pub slippage_tolerance: Percent,
+    /// The account that should receive the minted NFT.
+    pub recipient: Address,
+    /// When the transaction expires, in epoch seconds.
+    pub deadline: U256,
+    /// Whether the WETH9 left over should be refunded as ETH.
+    pub refund_as_eth: bool,
+    /// Creates pool if not initialized before migrating.
+    pub create_pool: bool,
+    /// The optional permit parameters for spending the V2 liquidity tokens.
+    pub pair_permit: Option<PermitOptions>,
+}
+
+/// Computes the V3 position minted by migrating V2 liquidity to a range, given the reserves of the
+/// V2 pair.
+///
+/// ## Arguments
+///
+/// * `pool`: The V3 pool to migrate to, with the same tokens as the pair
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `reserve0`: The reserve of token0 of the V2 pair
+/// * `reserve1`: The reserve of token1 of the V2 pair
+/// * `total_supply`: The total supply of the V2 liquidity tokens
+/// * `options`: The amount and percentage of the liquidity to migrate
+///
+/// ## Returns
+///
+/// The position with the maximum liquidity for the amounts withdrawn from the pair
+#[inline]
+pub fn get_migration_position<TP: TickDataProvider>(
+    pool: Pool<TP>,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+    reserve0: U256,
+    reserve1: U256,
+    total_supply: U256,
+    options: &MigrateOptions,
+) -> Result<Position<TP>, Error> {
+    assert!(
+        options.percentage_to_migrate > 0 && options.percentage_to_migrate <= 100,
+        "PERCENTAGE"
+    );
+    assert!(options.liquidity_to_migrate <= total_supply, "LIQUIDITY");
+    // the amounts burned from the pair, rounded down as by `UniswapV2Pair.burn`
+    let percentage = U256::from(options.percentage_to_migrate);
+    let amount0 =
+        options.liquidity_to_migrate * reserve0 / total_supply * percentage / U256::from(100);
+    let amount1 =
+        options.liquidity_to_migrate * reserve1 / total_supply * percentage / U256::from(100);
+    Position::from_amounts(pool, tick_lower, tick_upper, amount0, amount1, true)
+}
+
+/// Produces the calldata for migrating V2 liquidity to a V3 position through the
+/// [`V3Migrator`](https://github.com/Uniswap/v3-periphery/blob/main/contracts/V3Migrator.sol).
+///
+/// ## Arguments
+///
+/// * `position`: The position to mint, see [`get_migration_position`]
+/// * `options`: Options for the calldata
+#[inline]
+pub fn migrate_call_parameters<TP: TickDataProvider>(
+    position: &mut Position<TP>,
+    options: MigrateOptions,
+) -> Result<MethodParameters, Error> {
+    assert!(position.liquidity > 0, "ZERO_LIQUIDITY");
+    assert!(
+        options.percentage_to_migrate > 0 && options.percentage_to_migrate <= 100,
+        "PERCENTAGE"
+    );
+
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(3);
+
+    // permit the V2 liquidity tokens if necessary
+    if let Some(permit) = options.pair_permit {
+        calldatas.push(encode_permit(&options.pair, permit));
+    }
+
+    // create pool if needed
+    if options.create_pool {
+        calldatas.push(encode_create(&position.pool));
+    }
+
+    // adjust for slippage
+    let MintAmounts {
+        amount0: amount0_min,
+        amount1: amount1_min,
+    } = position.mint_amounts_with_slippage(&options.slippage_tolerance)?;
+
+    // migrate
+    calldatas.push(
+        IV3Migrator::migrateCall {
+            params: IV3Migrator::MigrateParams {
+                pair: options.pair.address(),
+                liquidityToMigrate: options.liquidity_to_migrate,
+                percentageToMigrate: options.percentage_to_migrate,
+                token0: position.pool.token0.address(),
+                token1: position.pool.token1.address(),
+                fee: position.pool.fee.into(),
+                tickLower: position.tick_lower.to_i24(),
+                tickUpper: position.tick_upper.to_i24(),
+                amount0Min: amount0_min,
+                amount1Min: amount1_min,
+                recipient: options.recipient,
+                deadline: options.deadline,
+                refundAsETH: options.refund_as_eth,
+            },
+        }
+        .abi_encode()
+        .into(),
+    );
+
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value: U256::ZERO,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, uint};
+    use once_cell::sync::Lazy;
+    use uniswap_sdk_core::token;
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+    const DEADLINE: U256 = uint!(123_U256);
+    static MIGRATE_OPTIONS: Lazy<MigrateOptions> = Lazy::new(|| MigrateOptions {
+        pair: token!(1, "0000000000000000000000000000000000000005", 18),
+        liquidity_to_migrate: uint!(1000_U256),
+        percentage_to_migrate: 100,
+        slippage_tolerance: Percent::new(1, 100),
+        recipient: RECIPIENT,
+        deadline: DEADLINE,
+        refund_as_eth: false,
+        create_pool: false,
+        pair_permit: None,
+    });
+
+    fn position() -> Position<TickListDataProvider> {
+        get_migration_position(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            -600,
+            600,
+            uint!(100000_U256),
+            uint!(100000_U256),
+            uint!(10000_U256),
+            &MIGRATE_OPTIONS,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_migration_position() {
+        let position = position();
+        let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+        // a tenth of the pair is withdrawn
+        assert!(amount0 <= uint!(10000_U256));
+        assert!(amount1 <= uint!(10000_U256));
+        assert!(amount0 == uint!(10000_U256) || amount1 == uint!(10000_U256));
+    }
+
+    #[test]
+    #[should_panic(expected = "PERCENTAGE")]
+    fn test_get_migration_position_zero_percentage() {
+        get_migration_position(
+            make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            -600,
+            600,
+            uint!(100000_U256),
+            uint!(100000_U256),
+            uint!(10000_U256),
+            &MigrateOptions {
+                percentage_to_migrate: 0,
+                ..MIGRATE_OPTIONS.clone()
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_migrate_call_parameters() {
+        let mut position = position();
+        let MethodParameters { calldata, value } =
+            migrate_call_parameters(&mut position, MIGRATE_OPTIONS.clone()).unwrap();
+        assert_eq!(value, U256::ZERO);
+        let params = IV3Migrator::migrateCall::abi_decode(&calldata, true)
+            .unwrap()
+            .params;
+        assert_eq!(params.pair, MIGRATE_OPTIONS.pair.address());
+        assert_eq!(params.liquidityToMigrate, uint!(1000_U256));
+        assert_eq!(params.percentageToMigrate, 100);
+        assert_eq!(params.token0, TOKEN0.address());
+        assert_eq!(params.token1, TOKEN1.address());
+        assert_eq!(params.tickLower.as_i32(), -600);
+        assert_eq!(params.tickUpper.as_i32(), 600);
+        let MintAmounts {
+            amount0: amount0_min,
+            amount1: amount1_min,
+        } = position
+            .mint_amounts_with_slippage(&Percent::new(1, 100))
+            .unwrap();
+        assert_eq!(params.amount0Min, amount0_min);
+        assert_eq!(params.amount1Min, amount1_min);
+        assert_eq!(params.recipient, RECIPIENT);
+        assert_eq!(params.deadline, DEADLINE);
+        assert!(!params.refundAsETH);
+    }
+
+    #[test]
+    fn test_migrate_call_parameters_create_pool() {
+        let mut position = position();
+        let MethodParameters { calldata, .. } = migrate_call_parameters(
+            &mut position,
+            MigrateOptions {
+                create_pool: true,
+                ..MIGRATE_OPTIONS.clone()
+            },
+        )
+        .unwrap();
+        let calldatas: Vec<Bytes> = decode_multicall(&calldata).unwrap();
+        assert_eq!(calldatas.len(), 2);
+        assert_eq!(calldatas[0], encode_create(&position.pool));
+        assert!(IV3Migrator::migrateCall::abi_decode(&calldatas[1], true).is_ok());
+    }
+}
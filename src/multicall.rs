@@ -1,6 +1,6 @@
 use crate::prelude::*;
 use alloc::vec::Vec;
-use alloy_primitives::Bytes;
+use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::{Error, SolCall};
 
 #[inline]
@@ -17,6 +17,24 @@ pub fn encode_multicall<B: Into<Bytes>>(data: Vec<B>) -> Bytes {
     }
 }
 
+/// Encodes the calls into a `multicall` which reverts past the deadline, see
+/// [`IMulticallExtended`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/interfaces/IMulticallExtended.sol).
+///
+/// ## Arguments
+///
+/// * `deadline`: The timestamp past which the `multicall` reverts
+/// * `data`: The calldata of each call
+#[inline]
+#[must_use]
+pub fn encode_multicall_with_deadline<B: Into<Bytes>>(deadline: U256, data: Vec<B>) -> Bytes {
+    IMulticallExtended::multicallCall {
+        deadline,
+        data: data.into_iter().map(Into::into).collect(),
+    }
+    .abi_encode()
+    .into()
+}
+
 #[inline]
 pub fn decode_multicall<B, E>(encoded: E) -> Result<Vec<B>, Error>
 where
@@ -27,6 +45,147 @@ where
         .map(|decoded| decoded.data.into_iter().map(Into::into).collect())
 }
 
+/// Decodes calldata into its calls, unwrapping the nested `multicall`s, with or without a
+/// deadline, if any.
+///
+/// ## Arguments
+///
+/// * `calldata`: The calldata of a call or of a `multicall`
+#[inline]
+pub fn flatten_multicall<E: AsRef<[u8]>>(calldata: E) -> Result<Vec<Bytes>, Error> {
+    let calldata = calldata.as_ref();
+    let data = if calldata.starts_with(&IMulticall::multicallCall::SELECTOR) {
+        IMulticall::multicallCall::abi_decode(calldata, true)?.data
+    } else if calldata.starts_with(&IMulticallExtended::multicallCall::SELECTOR) {
+        IMulticallExtended::multicallCall::abi_decode(calldata, true)?.data
+    } else {
+        return Ok(Vec::from([Bytes::copy_from_slice(calldata)]));
+    };
+    let mut calls = Vec::new();
+    for call in data {
+        calls.extend(flatten_multicall(call)?);
+    }
+    Ok(calls)
+}
+
+/// Decodes the return data of a `multicall` into the return data of each call.
+///
+/// ## Notes
+///
+/// [`encode_multicall`] does not wrap a single call in a `multicall`, whose return data is then
+/// the return data of the call itself.
+///
+/// ## Arguments
+///
+/// * `encoded`: The return data of the `multicall`
+#[inline]
+pub fn decode_multicall_results<B, E>(encoded: E) -> Result<Vec<B>, Error>
+where
+    E: AsRef<[u8]>,
+    B: From<Bytes>,
+{
+    IMulticall::multicallCall::abi_decode_returns(encoded.as_ref(), true)
+        .map(|decoded| decoded.results.into_iter().map(Into::into).collect())
+}
+
+/// Decodes the return data of a `multicall` of calls to the same function into their typed
+/// returns, e.g. of [`IQuoterV2::quoteExactInputSingleCall`] for a batch of quotes or of
+/// `positions` for a batch of positions.
+///
+/// ## Arguments
+///
+/// * `encoded`: The return data of the `multicall`
+#[inline]
+pub fn decode_multicall_returns<C, E>(encoded: E) -> Result<Vec<C::Return>, Error>
+where
+    C: SolCall,
+    E: AsRef<[u8]>,
+{
+    decode_multicall_results::<Bytes, E>(encoded)?
+        .iter()
+        .map(|result| C::abi_decode_returns(result, true))
+        .collect()
+}
+
+/// Encodes the calls into a Multicall3 `aggregate3`, where each call may be allowed to revert
+/// without reverting the batch.
+///
+/// ## Arguments
+///
+/// * `calls`: The target, whether the call is allowed to fail, and the calldata of each call
+#[inline]
+#[must_use]
+pub fn encode_aggregate3<B: Into<Bytes>>(
+    calls: impl IntoIterator<Item = (Address, bool, B)>,
+) -> Bytes {
+    IMulticall3::aggregate3Call {
+        calls: calls
+            .into_iter()
+            .map(|(target, allow_failure, call_data)| IMulticall3::Call3 {
+                target,
+                allowFailure: allow_failure,
+                callData: call_data.into(),
+            })
+            .collect(),
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the return data of a Multicall3 `aggregate3` into the return data of each call.
+///
+/// ## Arguments
+///
+/// * `encoded`: The return data of the `aggregate3`
+///
+/// ## Returns
+///
+/// The return data of each call, or `None` if it failed, in the order of the calls
+#[inline]
+pub fn decode_aggregate3_results<B, E>(encoded: E) -> Result<Vec<Option<B>>, Error>
+where
+    E: AsRef<[u8]>,
+    B: From<Bytes>,
+{
+    IMulticall3::aggregate3Call::abi_decode_returns(encoded.as_ref(), true).map(|decoded| {
+        decoded
+            .returnData
+            .into_iter()
+            .map(|result| result.success.then(|| result.returnData.into()))
+            .collect()
+    })
+}
+
+/// Decodes the return data of a Multicall3 `aggregate3` of calls to the same function into their
+/// typed returns, e.g. of `slot0` for a batch of pools.
+///
+/// ## Notes
+///
+/// A call to an address without code succeeds without return data, e.g. to a pool that is not
+/// deployed, and is treated as failed.
+///
+/// ## Arguments
+///
+/// * `encoded`: The return data of the `aggregate3`
+///
+/// ## Returns
+///
+/// The typed return of each call, or `None` if it failed, in the order of the calls
+#[inline]
+pub fn decode_aggregate3_returns<C, E>(encoded: E) -> Result<Vec<Option<C::Return>>, Error>
+where
+    C: SolCall,
+    E: AsRef<[u8]>,
+{
+    decode_aggregate3_results::<Bytes, E>(encoded)?
+        .into_iter()
+        .map(|result| match result {
+            Some(data) if !data.is_empty() => C::abi_decode_returns(&data, true).map(Some),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
 pub trait Multicall: Sized {
     fn encode_multicall(self) -> Bytes;
 
@@ -82,6 +241,8 @@ mod tests {
 
     mod decode {
         use super::*;
+        use alloy_primitives::U256;
+        use alloy_sol_types::SolValue;
 
         #[test]
         fn test_string_array_len_2() {
@@ -98,5 +259,91 @@ mod tests {
             let decoded_calldata = <Vec<Vec<u8>>>::decode_multicall(encoded).unwrap();
             assert_eq!(decoded_calldata, calldata_list);
         }
+
+        #[test]
+        fn test_flatten_nested() {
+            let inner = encode_multicall(vec![vec![0x01], vec![0x02]]);
+            let encoded = encode_multicall(vec![inner.to_vec(), vec![0x03]]);
+            assert_eq!(
+                flatten_multicall(encoded).unwrap(),
+                vec![
+                    Bytes::from(vec![0x01]),
+                    Bytes::from(vec![0x02]),
+                    Bytes::from(vec![0x03])
+                ]
+            );
+            assert_eq!(
+                flatten_multicall([0x01]).unwrap(),
+                vec![Bytes::from(vec![0x01])]
+            );
+            let encoded =
+                encode_multicall_with_deadline(U256::from(1), vec![inner.to_vec(), vec![0x03]]);
+            assert_eq!(
+                flatten_multicall(encoded).unwrap(),
+                vec![
+                    Bytes::from(vec![0x01]),
+                    Bytes::from(vec![0x02]),
+                    Bytes::from(vec![0x03])
+                ]
+            );
+        }
+
+        #[test]
+        fn test_results() {
+            let results = vec![
+                Bytes::from(U256::from(1).abi_encode()),
+                Bytes::from(U256::from(2).abi_encode()),
+            ];
+            let encoded = IMulticall::multicallCall::abi_encode_returns(&(results.clone(),));
+            assert_eq!(
+                decode_multicall_results::<Bytes, _>(&encoded).unwrap(),
+                results
+            );
+            let amounts: Vec<U256> =
+                decode_multicall_returns::<IQuoter::quoteExactInputSingleCall, _>(&encoded)
+                    .unwrap()
+                    .into_iter()
+                    .map(|decoded| decoded.amountOut)
+                    .collect();
+            assert_eq!(amounts, vec![U256::from(1), U256::from(2)]);
+        }
+
+        #[test]
+        fn test_aggregate3() {
+            let target = Address::repeat_byte(0x11);
+            let encoded =
+                encode_aggregate3([(target, false, vec![0x01]), (target, true, vec![0x02])]);
+            let decoded = IMulticall3::aggregate3Call::abi_decode(&encoded, true).unwrap();
+            assert_eq!(decoded.calls.len(), 2);
+            assert_eq!(decoded.calls[0].target, target);
+            assert!(!decoded.calls[0].allowFailure);
+            assert!(decoded.calls[1].allowFailure);
+            assert_eq!(decoded.calls[1].callData, Bytes::from(vec![0x02]));
+
+            let result = |success: bool, data: Vec<u8>| IMulticall3::Call3Result {
+                success,
+                returnData: data.into(),
+            };
+            let encoded = IMulticall3::aggregate3Call::abi_encode_returns(&(vec![
+                result(true, U256::from(1).abi_encode()),
+                result(false, vec![0x08, 0xc3, 0x79, 0xa0]),
+                result(true, vec![]),
+            ],));
+            assert_eq!(
+                decode_aggregate3_results::<Bytes, _>(&encoded).unwrap(),
+                vec![
+                    Some(Bytes::from(U256::from(1).abi_encode())),
+                    None,
+                    Some(Bytes::new())
+                ]
+            );
+            let amounts: Vec<Option<U256>> =
+                decode_aggregate3_returns::<IQuoter::quoteExactInputSingleCall, _>(&encoded)
+                    .unwrap()
+                    .into_iter()
+                    .map(|decoded| decoded.map(|decoded| decoded.amountOut))
+                    .collect();
+            assert_eq!(amounts, vec![Some(U256::from(1)), None, None]);
+        }
     }
 }
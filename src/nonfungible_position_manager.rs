@@ -1,6 +1,6 @@
 use crate::prelude::{Error, *};
-use alloy_primitives::{Bytes, PrimitiveSignature, B256, U256};
-use alloy_sol_types::{eip712_domain, Eip712Domain, SolCall, SolStruct};
+use alloy_primitives::{Address, Bytes, ChainId, PrimitiveSignature, B256, U160, U256};
+use alloy_sol_types::{eip712_domain, Eip712Domain, SolCall, SolInterface, SolStruct};
 use num_traits::ToPrimitive;
 use uniswap_sdk_core::prelude::*;
 
@@ -33,12 +33,34 @@ pub struct AddLiquidityOptions {
     pub deadline: U256,
     /// Whether to spend ether. If true, one of the pool tokens must be WETH, by default false
     pub use_native: Option<Ether>,
-    /// The optional permit parameters for spending token0
+    /// The optional permit parameters for spending token0, encoded as a `selfPermit` or
+    /// `selfPermitAllowed` ahead of the `mint` or `increaseLiquidity`
     pub token0_permit: Option<PermitOptions>,
-    /// The optional permit parameters for spending token1
+    /// The optional permit parameters for spending token1, which may be set along with
+    /// [`AddLiquidityOptions::token0_permit`] to approve both tokens in the same multicall.
+    ///
+    /// The position manager pulls the tokens with `transferFrom` and cannot consume Permit2
+    /// signatures, so only EIP-2612 and DAI-style permits are supported.
     pub token1_permit: Option<PermitOptions>,
     /// [`MintSpecificOptions`] or [`IncreaseSpecificOptions`]
     pub specific_opts: AddLiquiditySpecificOptions,
+    /// The optional chain id, from which [`AddLiquidityOptions::target`] resolves the position
+    /// manager
+    pub chain_id: Option<ChainId>,
+    /// The optional deployment, e.g. of a fork, whose position manager
+    /// [`AddLiquidityOptions::target`] resolves ahead of the chain id
+    pub deployment: Option<DeploymentConfig>,
+}
+
+impl AddLiquidityOptions {
+    /// Returns the position manager to send the calldata to: the position manager of the
+    /// deployment if set, or else of the chain if the chain id is set and known, see
+    /// [`get_chain_addresses`]
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> Option<Address> {
+        position_manager(self.deployment.as_ref(), self.chain_id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -108,10 +130,46 @@ pub struct RemoveLiquidityOptions<Currency0: BaseCurrency, Currency1: BaseCurren
     pub permit: Option<NFTPermitOptions>,
     /// Parameters to be passed on to collect
     pub collect_options: CollectOptions<Currency0, Currency1>,
+    /// The optional chain id, from which [`RemoveLiquidityOptions::target`] resolves the position
+    /// manager
+    pub chain_id: Option<ChainId>,
+    /// The optional deployment, e.g. of a fork, whose position manager
+    /// [`RemoveLiquidityOptions::target`] resolves ahead of the chain id
+    pub deployment: Option<DeploymentConfig>,
+}
+
+impl<Currency0: BaseCurrency, Currency1: BaseCurrency>
+    RemoveLiquidityOptions<Currency0, Currency1>
+{
+    /// Returns the position manager to send the calldata to: the position manager of the
+    /// deployment if set, or else of the chain if the chain id is set and known, see
+    /// [`get_chain_addresses`]
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> Option<Address> {
+        position_manager(self.deployment.as_ref(), self.chain_id)
+    }
+}
+
+/// Resolves the position manager of a deployment, or else of a chain
+const fn position_manager(
+    deployment: Option<&DeploymentConfig>,
+    chain_id: Option<ChainId>,
+) -> Option<Address> {
+    if let Some(deployment) = deployment {
+        return Some(deployment.nonfungible_position_manager);
+    }
+    match chain_id {
+        Some(chain_id) => match get_chain_addresses(chain_id) {
+            Some(addresses) => Some(addresses.nonfungible_position_manager),
+            None => None,
+        },
+        None => None,
+    }
 }
 
 #[inline]
-fn encode_create<TP: TickDataProvider>(pool: &Pool<TP>) -> Bytes {
+pub(crate) fn encode_create<TP: TickDataProvider>(pool: &Pool<TP>) -> Bytes {
     INonfungiblePositionManager::createAndInitializePoolIfNecessaryCall {
         token0: pool.token0.address(),
         token1: pool.token1.address(),
@@ -297,6 +355,55 @@ pub fn collect_call_parameters<Currency0: BaseCurrency, Currency1: BaseCurrency>
     }
 }
 
+/// Produces the calldata to collect all the tokens owed to a position, its fees and any withdrawn
+/// principal, without touching its liquidity.
+///
+/// ## Notes
+///
+/// Each token is collected up to `u128::MAX`. If the native currency is unwrapped, the tokens are
+/// collected to the position manager, which unwraps and sweeps all of them to the recipient with
+/// a minimum of zero.
+///
+/// ## Arguments
+///
+/// * `token_id`: The ID of the token of the position
+/// * `recipient`: The account that receives the tokens
+/// * `sweep_token`: If one of the tokens of the pool is the wrapped native currency to unwrap,
+///   the other token of the pool
+#[inline]
+#[must_use]
+pub fn collect_fees_call_parameters(
+    token_id: U256,
+    recipient: Address,
+    sweep_token: Option<Address>,
+) -> MethodParameters {
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(3);
+    calldatas.push(
+        INonfungiblePositionManager::collectCall {
+            params: INonfungiblePositionManager::CollectParams {
+                tokenId: token_id,
+                recipient: if sweep_token.is_some() {
+                    Address::ZERO
+                } else {
+                    recipient
+                },
+                amount0Max: u128::MAX,
+                amount1Max: u128::MAX,
+            },
+        }
+        .abi_encode()
+        .into(),
+    );
+    if let Some(token) = sweep_token {
+        calldatas.push(encode_unwrap_weth9(U256::ZERO, recipient, None));
+        calldatas.push(encode_sweep_token(token, U256::ZERO, recipient, None));
+    }
+    MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value: U256::ZERO,
+    }
+}
+
 /// Produces the calldata for completely or partially exiting a position
 ///
 /// ## Arguments
@@ -308,6 +415,27 @@ pub fn remove_call_parameters<Currency0, Currency1, TP>(
     position: &Position<TP>,
     options: RemoveLiquidityOptions<Currency0, Currency1>,
 ) -> Result<MethodParameters, Error>
+where
+    Currency0: BaseCurrency,
+    Currency1: BaseCurrency,
+    TP: TickDataProvider,
+{
+    let (calldatas, _) = encode_remove(position, options)?;
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value: U256::ZERO,
+    })
+}
+
+/// Encodes the calls for exiting a position, see [`remove_call_parameters`].
+///
+/// ## Returns
+///
+/// The calls and the slippage-adjusted amounts of token0 and token1 removed from the position
+pub(crate) fn encode_remove<Currency0, Currency1, TP>(
+    position: &Position<TP>,
+    options: RemoveLiquidityOptions<Currency0, Currency1>,
+) -> Result<(Vec<Bytes>, (U256, U256)), Error>
 where
     Currency0: BaseCurrency,
     Currency1: BaseCurrency,
@@ -401,15 +529,213 @@ where
         assert!(!options.burn_token, "CANNOT_BURN");
     }
 
-    Ok(MethodParameters {
-        calldata: encode_multicall(calldatas),
-        value: U256::ZERO,
-    })
+    Ok((calldatas, (amount0_min, amount1_min)))
+}
+
+/// How much liquidity a [`PartialExitBuilder`] withdraws
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ExitAmount {
+    Percentage(Percent),
+    Liquidity(u128),
+}
+
+/// A fluent builder of the calldata to withdraw part of the liquidity of a position and collect
+/// the withdrawn principal with the fees owed in a single multicall, see
+/// [`remove_liquidity_partial`].
+#[derive(Clone, Debug)]
+pub struct PartialExitBuilder<'a, TP: TickDataProvider> {
+    position: &'a Position<TP>,
+    token_id: U256,
+    recipient: Address,
+    deadline: U256,
+    amount: Option<ExitAmount>,
+    slippage_tolerance: Percent,
+    fees_owed: (U256, U256),
+    use_native: Option<Ether>,
+    permit: Option<NFTPermitOptions>,
 }
 
+/// Starts building the calldata to withdraw part of the liquidity of a position, which encodes a
+/// `decreaseLiquidity` followed by a `collect` of the principal and the fees, and an
+/// `unwrapWETH9` and a `sweepToken` if the wrapped native currency is unwrapped.
+///
+/// ## Arguments
+///
+/// * `position`: The position to withdraw from
+/// * `token_id`: The ID of the token of the position
+/// * `recipient`: The account that receives the tokens
+/// * `deadline`: When the transaction expires, in epoch seconds
+///
+/// ## Examples
+///
+/// ```
+/// use alloy_primitives::{address, U256};
+/// use uniswap_sdk_core::{prelude::Percent, token};
+/// use uniswap_v3_sdk::prelude::*;
+///
+/// let usdc = token!(1, "A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", 6);
+/// let dai = token!(1, "6B175474E89094C44Da98b954EedeAC495271d0F", 18);
+/// let pool = Pool::new(dai, usdc, FeeAmount::LOW, encode_sqrt_ratio_x96(1, 1), 0).unwrap();
+/// let position = Position::new(pool, 1_000_000, -100, 100);
+/// let recipient = address!("0000000000000000000000000000000000000001");
+/// let parameters = remove_liquidity_partial(&position, U256::from(1), recipient, U256::MAX)
+///     .percentage(Percent::new(25, 100))
+///     .slippage_tolerance(Percent::new(5, 1000))
+///     .build()
+///     .unwrap();
+/// assert_eq!(parameters.value, U256::ZERO);
+/// ```
 #[inline]
-pub fn safe_transfer_from_parameters(options: SafeTransferOptions) -> MethodParameters {
-    let calldata = if options.data.is_empty() {
+#[must_use]
+pub fn remove_liquidity_partial<TP: TickDataProvider>(
+    position: &Position<TP>,
+    token_id: U256,
+    recipient: Address,
+    deadline: U256,
+) -> PartialExitBuilder<'_, TP> {
+    PartialExitBuilder {
+        position,
+        token_id,
+        recipient,
+        deadline,
+        amount: None,
+        slippage_tolerance: Percent::default(),
+        fees_owed: (U256::ZERO, U256::ZERO),
+        use_native: None,
+        permit: None,
+    }
+}
+
+impl<TP: TickDataProvider> PartialExitBuilder<'_, TP> {
+    /// Withdraws a percentage of the liquidity of the position.
+    #[inline]
+    #[must_use]
+    pub fn percentage(mut self, percentage: Percent) -> Self {
+        self.amount = Some(ExitAmount::Percentage(percentage));
+        self
+    }
+
+    /// Withdraws an exact amount of liquidity from the position.
+    #[inline]
+    #[must_use]
+    pub fn liquidity(mut self, liquidity: u128) -> Self {
+        self.amount = Some(ExitAmount::Liquidity(liquidity));
+        self
+    }
+
+    /// Sets how much the pool price is allowed to move, which only lowers the minimum amounts of
+    /// the withdrawn liquidity and not the fees, by default zero.
+    #[inline]
+    #[must_use]
+    pub fn slippage_tolerance(mut self, slippage_tolerance: Percent) -> Self {
+        self.slippage_tolerance = slippage_tolerance;
+        self
+    }
+
+    /// Sets the fees owed to the position in token0 and token1, which are swept with the
+    /// withdrawn principal if the native currency is unwrapped, by default zero.
+    #[inline]
+    #[must_use]
+    pub const fn fees_owed(mut self, amount0: U256, amount1: U256) -> Self {
+        self.fees_owed = (amount0, amount1);
+        self
+    }
+
+    /// Unwraps the wrapped native currency of the pool to the native currency.
+    #[inline]
+    #[must_use]
+    pub fn use_native(mut self, native: Ether) -> Self {
+        self.use_native = Some(native);
+        self
+    }
+
+    /// Sets the permit of the token ID, in case the transaction is sent by an account that does
+    /// not own the NFT.
+    #[inline]
+    #[must_use]
+    pub const fn permit(mut self, permit: NFTPermitOptions) -> Self {
+        self.permit = Some(permit);
+        self
+    }
+
+    /// Validates the amount to withdraw and produces the calldata.
+    ///
+    /// ## Returns
+    ///
+    /// * [`Error::InvalidAmounts`] if no amount is set, or it is zero or more than the liquidity
+    ///   of the position
+    /// * [`Error::InvalidToken`] if the native currency is unwrapped but its wrapped token is not
+    ///   in the pool
+    #[inline]
+    pub fn build(self) -> Result<MethodParameters, Error> {
+        let position = self.position;
+        if position.liquidity == 0 {
+            return Err(Error::InvalidAmounts);
+        }
+        let liquidity_percentage = match self.amount.ok_or(Error::InvalidAmounts)? {
+            ExitAmount::Percentage(percentage) => percentage,
+            ExitAmount::Liquidity(liquidity) => Percent::new(liquidity, position.liquidity),
+        };
+        let liquidity =
+            (liquidity_percentage.clone() * Percent::new(position.liquidity, 1)).quotient();
+        if liquidity_percentage > Percent::new(1, 1) || liquidity <= BigInt::ZERO {
+            return Err(Error::InvalidAmounts);
+        }
+
+        let pool = &position.pool;
+        let (currency0, currency1) = match self.use_native {
+            None => (
+                Currency::Token(pool.token0.clone()),
+                Currency::Token(pool.token1.clone()),
+            ),
+            Some(native) if pool.token0.equals(native.wrapped()) => (
+                Currency::NativeCurrency(native),
+                Currency::Token(pool.token1.clone()),
+            ),
+            Some(native) if pool.token1.equals(native.wrapped()) => (
+                Currency::Token(pool.token0.clone()),
+                Currency::NativeCurrency(native),
+            ),
+            Some(_) => return Err(Error::InvalidToken),
+        };
+        let (fees_owed0, fees_owed1) = self.fees_owed;
+        remove_call_parameters(
+            position,
+            RemoveLiquidityOptions {
+                token_id: self.token_id,
+                liquidity_percentage,
+                slippage_tolerance: self.slippage_tolerance,
+                deadline: self.deadline,
+                burn_token: false,
+                permit: self.permit,
+                collect_options: CollectOptions {
+                    token_id: self.token_id,
+                    expected_currency_owed0: CurrencyAmount::from_raw_amount(
+                        currency0,
+                        fees_owed0.to_big_int(),
+                    )?,
+                    expected_currency_owed1: CurrencyAmount::from_raw_amount(
+                        currency1,
+                        fees_owed1.to_big_int(),
+                    )?,
+                    recipient: self.recipient,
+                },
+                chain_id: None,
+                deployment: None,
+            },
+        )
+    }
+}
+
+/// Encodes the `safeTransferFrom` of a position, to batch it in a multicall with other calls
+///
+/// ## Arguments
+///
+/// * `options`: The sender, recipient and token id of the transfer with the optional data
+#[inline]
+#[must_use]
+pub fn encode_safe_transfer_from(options: SafeTransferOptions) -> Bytes {
+    if options.data.is_empty() {
         INonfungiblePositionManager::safeTransferFrom_0Call {
             from: options.sender,
             to: options.recipient,
@@ -424,13 +750,106 @@ pub fn safe_transfer_from_parameters(options: SafeTransferOptions) -> MethodPara
             data: options.data,
         }
         .abi_encode()
-    };
+    }
+    .into()
+}
+
+#[inline]
+#[must_use]
+pub fn safe_transfer_from_parameters(options: SafeTransferOptions) -> MethodParameters {
+    MethodParameters {
+        calldata: encode_safe_transfer_from(options),
+        value: U256::ZERO,
+    }
+}
+
+/// Encodes the approval of an account to transfer a position, to batch it in a multicall with
+/// other calls
+///
+/// ## Arguments
+///
+/// * `spender`: The account to approve, or the zero address to revoke the approval
+/// * `token_id`: The id of the position
+#[inline]
+#[must_use]
+pub fn encode_approve(spender: Address, token_id: U256) -> Bytes {
+    INonfungiblePositionManager::approveCall {
+        to: spender,
+        tokenId: token_id,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Produces the calldata to approve an account to transfer a position, e.g. a vault or a migrator
+///
+/// ## Arguments
+///
+/// * `spender`: The account to approve, or the zero address to revoke the approval
+/// * `token_id`: The id of the position
+#[inline]
+#[must_use]
+pub fn approve_call_parameters(spender: Address, token_id: U256) -> MethodParameters {
     MethodParameters {
-        calldata: calldata.into(),
+        calldata: encode_approve(spender, token_id),
         value: U256::ZERO,
     }
 }
 
+/// Encodes the approval of an operator to transfer all the positions of the sender, to batch it
+/// in a multicall with other calls
+///
+/// ## Arguments
+///
+/// * `operator`: The operator to approve or revoke
+/// * `approved`: Whether to approve or revoke the operator
+#[inline]
+#[must_use]
+pub fn encode_set_approval_for_all(operator: Address, approved: bool) -> Bytes {
+    INonfungiblePositionManager::setApprovalForAllCall { operator, approved }
+        .abi_encode()
+        .into()
+}
+
+/// Produces the calldata to approve or revoke an operator to transfer all the positions of the
+/// sender
+///
+/// ## Arguments
+///
+/// * `operator`: The operator to approve or revoke
+/// * `approved`: Whether to approve or revoke the operator
+#[inline]
+#[must_use]
+pub fn set_approval_for_all_call_parameters(operator: Address, approved: bool) -> MethodParameters {
+    MethodParameters {
+        calldata: encode_set_approval_for_all(operator, approved),
+        value: U256::ZERO,
+    }
+}
+
+/// Batches the calls of the position manager in a single multicall, e.g. an approval produced by
+/// [`approve_call_parameters`] alongside the operation it gates. The nested multicalls are
+/// flattened, and the values are summed.
+///
+/// ## Arguments
+///
+/// * `parameters`: The calldata and value of each call, in the order of execution
+#[inline]
+pub fn batch_call_parameters(
+    parameters: &[MethodParameters],
+) -> Result<MethodParameters, alloy_sol_types::Error> {
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(parameters.len());
+    let mut value = U256::ZERO;
+    for params in parameters {
+        calldatas.extend(flatten_multicall(&params.calldata)?);
+        value += params.value;
+    }
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value,
+    })
+}
+
 /// Prepares the parameters for EIP712 signing
 ///
 /// ## Arguments
@@ -493,6 +912,127 @@ pub const fn get_permit_data(
     }
 }
 
+/// A call of the nonfungible position manager, see [`decode_position_manager_call`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionManagerCall {
+    CreateAndInitializePoolIfNecessary {
+        token0: Address,
+        token1: Address,
+        fee: FeeAmount,
+        sqrt_price_x96: U160,
+    },
+    Mint(INonfungiblePositionManager::MintParams),
+    IncreaseLiquidity(INonfungiblePositionManager::IncreaseLiquidityParams),
+    DecreaseLiquidity(INonfungiblePositionManager::DecreaseLiquidityParams),
+    Collect(INonfungiblePositionManager::CollectParams),
+    Burn {
+        token_id: U256,
+    },
+    SafeTransferFrom(SafeTransferOptions),
+    Approve {
+        spender: Address,
+        token_id: U256,
+    },
+    SetApprovalForAll {
+        operator: Address,
+        approved: bool,
+    },
+    /// The permit of a position with its token id
+    Permit {
+        token_id: U256,
+        permit: NFTPermitOptions,
+    },
+    /// The permit of a token with its address
+    SelfPermit(Address, PermitOptions),
+    Payments(PaymentsCall),
+}
+
+/// Decodes the calldata of the nonfungible position manager, e.g. produced by
+/// [`add_call_parameters`] or [`remove_call_parameters`], into its calls, unwrapping the nested
+/// multicalls.
+///
+/// ## Arguments
+///
+/// * `calldata`: The calldata of a call or a multicall of the position manager
+#[inline]
+pub fn decode_position_manager_call(
+    calldata: impl AsRef<[u8]>,
+) -> Result<Vec<PositionManagerCall>, alloy_sol_types::Error> {
+    use INonfungiblePositionManager::INonfungiblePositionManagerCalls as Calls;
+
+    flatten_multicall(calldata)?
+        .iter()
+        .map(|call| {
+            let selector = call
+                .get(..4)
+                .and_then(|selector| selector.try_into().ok())
+                .unwrap_or_default();
+            if Calls::valid_selector(selector) {
+                Ok(match Calls::abi_decode(call, true)? {
+                    Calls::createAndInitializePoolIfNecessary(call) => {
+                        PositionManagerCall::CreateAndInitializePoolIfNecessary {
+                            token0: call.token0,
+                            token1: call.token1,
+                            fee: call.fee.into(),
+                            sqrt_price_x96: call.sqrtPriceX96,
+                        }
+                    }
+                    Calls::mint(call) => PositionManagerCall::Mint(call.params),
+                    Calls::increaseLiquidity(call) => {
+                        PositionManagerCall::IncreaseLiquidity(call.params)
+                    }
+                    Calls::decreaseLiquidity(call) => {
+                        PositionManagerCall::DecreaseLiquidity(call.params)
+                    }
+                    Calls::collect(call) => PositionManagerCall::Collect(call.params),
+                    Calls::burn(call) => PositionManagerCall::Burn {
+                        token_id: call.tokenId,
+                    },
+                    Calls::safeTransferFrom_0(call) => {
+                        PositionManagerCall::SafeTransferFrom(SafeTransferOptions {
+                            sender: call.from,
+                            recipient: call.to,
+                            token_id: call.tokenId,
+                            data: Bytes::new(),
+                        })
+                    }
+                    Calls::safeTransferFrom_1(call) => {
+                        PositionManagerCall::SafeTransferFrom(SafeTransferOptions {
+                            sender: call.from,
+                            recipient: call.to,
+                            token_id: call.tokenId,
+                            data: call.data,
+                        })
+                    }
+                    Calls::approve(call) => PositionManagerCall::Approve {
+                        spender: call.to,
+                        token_id: call.tokenId,
+                    },
+                    Calls::setApprovalForAll(call) => PositionManagerCall::SetApprovalForAll {
+                        operator: call.operator,
+                        approved: call.approved,
+                    },
+                })
+            } else if selector == IERC721Permit::permitCall::SELECTOR {
+                let call = IERC721Permit::permitCall::abi_decode(call, true)?;
+                Ok(PositionManagerCall::Permit {
+                    token_id: call.tokenId,
+                    permit: NFTPermitOptions {
+                        signature: decode_signature(call.v, call.r, call.s),
+                        deadline: call.deadline,
+                        spender: call.spender,
+                    },
+                })
+            } else if ISelfPermit::ISelfPermitCalls::valid_selector(selector) {
+                let (token, permit) = decode_permit(call)?;
+                Ok(PositionManagerCall::SelfPermit(token, permit))
+            } else {
+                decode_payments_call(call).map(PositionManagerCall::Payments)
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -528,6 +1068,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_create_call_parameters() {
+        let MethodParameters { calldata, .. } = create_call_parameters(&POOL_0_1);
+        assert_eq!(
+            decode_position_manager_call(calldata).unwrap(),
+            vec![PositionManagerCall::CreateAndInitializePoolIfNecessary {
+                token0: TOKEN0.address(),
+                token1: TOKEN1.address(),
+                fee: FeeAmount::MEDIUM,
+                sqrt_price_x96: POOL_0_1.sqrt_ratio_x96,
+            }]
+        );
+    }
+
     #[test]
     #[should_panic(expected = "ZERO_LIQUIDITY")]
     fn test_add_call_parameters_zero_liquidity() {
@@ -549,6 +1103,8 @@ mod tests {
                     recipient: RECIPIENT,
                     create_pool: false,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -575,6 +1131,8 @@ mod tests {
                     recipient: RECIPIENT,
                     create_pool: false,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -600,6 +1158,8 @@ mod tests {
                     recipient: RECIPIENT,
                     create_pool: false,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -629,6 +1189,8 @@ mod tests {
                 specific_opts: AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions {
                     token_id: TOKEN_ID,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -639,6 +1201,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_call_parameters_permits() {
+        let mut position = Position::new(
+            POOL_0_1.clone(),
+            1,
+            -FeeAmount::MEDIUM.tick_spacing().as_i32(),
+            FeeAmount::MEDIUM.tick_spacing().as_i32(),
+        );
+        let token0_permit = PermitOptions::Standard(StandardPermitArguments::new(
+            U256::from(1),
+            U256::from(2),
+            false,
+            U256::from(3),
+            DEADLINE,
+        ));
+        let token1_permit = PermitOptions::Allowed(AllowedPermitArguments::new(
+            U256::from(4),
+            U256::from(5),
+            true,
+            U256::from(6),
+            DEADLINE,
+        ));
+        let MethodParameters { calldata, value } = add_call_parameters(
+            &mut position,
+            AddLiquidityOptions {
+                slippage_tolerance: SLIPPAGE_TOLERANCE.clone(),
+                deadline: DEADLINE,
+                use_native: None,
+                token0_permit: Some(token0_permit),
+                token1_permit: Some(token1_permit),
+                specific_opts: AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions {
+                    token_id: TOKEN_ID,
+                }),
+                chain_id: None,
+                deployment: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(value, U256::ZERO);
+        let calls = decode_position_manager_call(calldata).unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(
+            calls[0],
+            PositionManagerCall::SelfPermit(TOKEN0.address(), token0_permit)
+        );
+        assert_eq!(
+            calls[1],
+            PositionManagerCall::SelfPermit(TOKEN1.address(), token1_permit)
+        );
+        assert!(matches!(
+            calls[2],
+            PositionManagerCall::IncreaseLiquidity(_)
+        ));
+    }
+
     #[test]
     fn test_add_call_parameters_create_pool() {
         let mut position = Position::new(
@@ -659,6 +1276,8 @@ mod tests {
                     recipient: RECIPIENT,
                     create_pool: true,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -689,6 +1308,8 @@ mod tests {
                     recipient: RECIPIENT,
                     create_pool: false,
                 }),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -742,11 +1363,49 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
     }
 
+    #[test]
+    fn test_remove_liquidity_options_target() {
+        let options = RemoveLiquidityOptions {
+            token_id: TOKEN_ID,
+            liquidity_percentage: Percent::new(1, 1),
+            slippage_tolerance: SLIPPAGE_TOLERANCE.clone(),
+            deadline: DEADLINE,
+            burn_token: false,
+            permit: None,
+            collect_options: COLLECT_OPTIONS.clone(),
+            chain_id: Some(56),
+            deployment: None,
+        };
+        assert_eq!(
+            options.target(),
+            Some(BNB_ADDRESSES.nonfungible_position_manager)
+        );
+        let deployment = DeploymentConfig::get(Fork::PancakeSwapV3, 56).unwrap();
+        assert_eq!(
+            RemoveLiquidityOptions {
+                deployment: Some(deployment),
+                ..options.clone()
+            }
+            .target(),
+            Some(deployment.nonfungible_position_manager)
+        );
+        assert_eq!(
+            RemoveLiquidityOptions {
+                chain_id: None,
+                ..options
+            }
+            .target(),
+            None
+        );
+    }
+
     #[test]
     #[should_panic(expected = "ZERO_LIQUIDITY")]
     fn test_remove_call_parameters_small_percentage() {
@@ -765,6 +1424,8 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -788,6 +1449,8 @@ mod tests {
                 burn_token: true,
                 permit: None,
                 collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -810,6 +1473,8 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -818,6 +1483,26 @@ mod tests {
             calldata.to_vec(),
             hex!("ac9650d8000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000012000000000000000000000000000000000000000000000000000000000000000a40c49ccbe0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000084fc6f78650000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000ffffffffffffffffffffffffffffffff00000000000000000000000000000000ffffffffffffffffffffffffffffffff00000000000000000000000000000000000000000000000000000000")
         );
+        assert_eq!(
+            decode_position_manager_call(calldata).unwrap(),
+            vec![
+                PositionManagerCall::DecreaseLiquidity(
+                    INonfungiblePositionManager::DecreaseLiquidityParams {
+                        tokenId: TOKEN_ID,
+                        liquidity: 100,
+                        amount0Min: U256::ZERO,
+                        amount1Min: U256::ZERO,
+                        deadline: DEADLINE,
+                    }
+                ),
+                PositionManagerCall::Collect(INonfungiblePositionManager::CollectParams {
+                    tokenId: TOKEN_ID,
+                    recipient: RECIPIENT,
+                    amount0Max: u128::MAX,
+                    amount1Max: u128::MAX,
+                }),
+            ]
+        );
     }
 
     #[test]
@@ -837,6 +1522,8 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -864,6 +1551,8 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS2.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -891,6 +1580,8 @@ mod tests {
                 burn_token: false,
                 permit: None,
                 collect_options: COLLECT_OPTIONS2.clone(),
+                chain_id: None,
+                deployment: None,
             },
         )
         .unwrap();
@@ -932,4 +1623,207 @@ mod tests {
             hex!("b88d4fde000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000140000000000000000000000000000000000009004000000000000000000000000")
         );
     }
+
+    #[test]
+    fn test_approve_call_parameters() {
+        let MethodParameters { calldata, value } = approve_call_parameters(RECIPIENT, TOKEN_ID);
+        assert_eq!(value, U256::ZERO);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("095ea7b300000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            decode_position_manager_call(calldata).unwrap(),
+            vec![PositionManagerCall::Approve {
+                spender: RECIPIENT,
+                token_id: TOKEN_ID,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_set_approval_for_all_call_parameters() {
+        let MethodParameters { calldata, value } =
+            set_approval_for_all_call_parameters(RECIPIENT, true);
+        assert_eq!(value, U256::ZERO);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("a22cb46500000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            decode_position_manager_call(calldata).unwrap(),
+            vec![PositionManagerCall::SetApprovalForAll {
+                operator: RECIPIENT,
+                approved: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collect_fees_call_parameters() {
+        let MethodParameters { calldata, value } =
+            collect_fees_call_parameters(TOKEN_ID, RECIPIENT, None);
+        assert_eq!(value, U256::ZERO);
+        assert_eq!(
+            calldata,
+            collect_call_parameters(&*COLLECT_OPTIONS).calldata
+        );
+
+        let MethodParameters { calldata, value } =
+            collect_fees_call_parameters(TOKEN_ID, RECIPIENT, Some(TOKEN1.address()));
+        assert_eq!(value, U256::ZERO);
+        assert_eq!(
+            calldata,
+            collect_call_parameters(&*COLLECT_OPTIONS2).calldata
+        );
+        assert_eq!(
+            decode_position_manager_call(calldata).unwrap(),
+            vec![
+                PositionManagerCall::Collect(INonfungiblePositionManager::CollectParams {
+                    tokenId: TOKEN_ID,
+                    recipient: Address::ZERO,
+                    amount0Max: u128::MAX,
+                    amount1Max: u128::MAX,
+                }),
+                PositionManagerCall::Payments(PaymentsCall::UnwrapWETH9 {
+                    amount_minimum: U256::ZERO,
+                    recipient: RECIPIENT,
+                    fee_options: None,
+                }),
+                PositionManagerCall::Payments(PaymentsCall::SweepToken {
+                    token: TOKEN1.address(),
+                    amount_minimum: U256::ZERO,
+                    recipient: RECIPIENT,
+                    fee_options: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_liquidity_partial() {
+        let position = Position::new(
+            POOL_0_1.clone(),
+            100,
+            -FeeAmount::MEDIUM.tick_spacing().as_i32(),
+            FeeAmount::MEDIUM.tick_spacing().as_i32(),
+        );
+        let expected = remove_call_parameters(
+            &position,
+            RemoveLiquidityOptions {
+                token_id: TOKEN_ID,
+                liquidity_percentage: Percent::new(1, 2),
+                slippage_tolerance: SLIPPAGE_TOLERANCE.clone(),
+                deadline: DEADLINE,
+                burn_token: false,
+                permit: None,
+                collect_options: COLLECT_OPTIONS.clone(),
+                chain_id: None,
+                deployment: None,
+            },
+        )
+        .unwrap();
+        let builder = remove_liquidity_partial(&position, TOKEN_ID, RECIPIENT, DEADLINE)
+            .slippage_tolerance(SLIPPAGE_TOLERANCE.clone());
+        assert_eq!(
+            builder
+                .clone()
+                .percentage(Percent::new(1, 2))
+                .build()
+                .unwrap(),
+            expected
+        );
+        assert_eq!(builder.clone().liquidity(50).build().unwrap(), expected);
+        for builder in [
+            builder.clone(),
+            builder.clone().liquidity(0),
+            builder.clone().liquidity(101),
+            builder.clone().percentage(Percent::new(3, 2)),
+        ] {
+            assert!(matches!(builder.build(), Err(Error::InvalidAmounts)));
+        }
+        assert!(matches!(
+            builder.liquidity(50).use_native(ETHER.clone()).build(),
+            Err(Error::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_remove_liquidity_partial_eth() {
+        let position = Position::new(
+            POOL_1_WETH.clone(),
+            100,
+            -FeeAmount::MEDIUM.tick_spacing().as_i32(),
+            FeeAmount::MEDIUM.tick_spacing().as_i32(),
+        );
+        let parameters = remove_liquidity_partial(&position, TOKEN_ID, RECIPIENT, DEADLINE)
+            .percentage(Percent::new(1, 2))
+            .fees_owed(U256::from(7), U256::from(9))
+            .use_native(ETHER.clone())
+            .build()
+            .unwrap();
+        let expected = remove_call_parameters(
+            &position,
+            RemoveLiquidityOptions {
+                token_id: TOKEN_ID,
+                liquidity_percentage: Percent::new(1, 2),
+                slippage_tolerance: Percent::default(),
+                deadline: DEADLINE,
+                burn_token: false,
+                permit: None,
+                collect_options: CollectOptions {
+                    token_id: TOKEN_ID,
+                    expected_currency_owed0: CurrencyAmount::from_raw_amount(TOKEN1.clone(), 7)
+                        .unwrap(),
+                    expected_currency_owed1: CurrencyAmount::from_raw_amount(ETHER.clone(), 9)
+                        .unwrap(),
+                    recipient: RECIPIENT,
+                },
+                chain_id: None,
+                deployment: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(parameters, expected);
+        let calls = decode_position_manager_call(parameters.calldata).unwrap();
+        assert_eq!(calls.len(), 4);
+        assert!(matches!(
+            calls[0],
+            PositionManagerCall::DecreaseLiquidity(_)
+        ));
+        assert!(matches!(
+            calls[2],
+            PositionManagerCall::Payments(PaymentsCall::UnwrapWETH9 { .. })
+        ));
+        assert!(matches!(
+            calls[3],
+            PositionManagerCall::Payments(PaymentsCall::SweepToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_batch_call_parameters() {
+        let collect = collect_call_parameters(&*COLLECT_OPTIONS2);
+        let MethodParameters { calldata, value } = batch_call_parameters(&[
+            approve_call_parameters(RECIPIENT, TOKEN_ID),
+            collect.clone(),
+            MethodParameters {
+                calldata: encode_refund_eth(),
+                value: U256::from(1),
+            },
+        ])
+        .unwrap();
+        assert_eq!(value, U256::from(1));
+        let calls = decode_position_manager_call(calldata).unwrap();
+        let collect_calls = decode_position_manager_call(collect.calldata).unwrap();
+        assert_eq!(calls.len(), 2 + collect_calls.len());
+        assert_eq!(
+            calls[0],
+            PositionManagerCall::Approve {
+                spender: RECIPIENT,
+                token_id: TOKEN_ID,
+            }
+        );
+        assert_eq!(&calls[1..=collect_calls.len()], collect_calls.as_slice());
+    }
 }
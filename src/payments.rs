@@ -1,6 +1,6 @@
-use crate::prelude::{FromBig, IPeripheryPaymentsWithFee};
+use crate::prelude::{FromBig, IPeripheryPaymentsWithFee, ToBig};
 use alloy_primitives::{Address, Bytes, U256};
-use alloy_sol_types::SolCall;
+use alloy_sol_types::{SolCall, SolInterface};
 use uniswap_sdk_core::prelude::{FractionBase, Percent};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,6 +11,33 @@ pub struct FeeOptions {
     pub recipient: Address,
 }
 
+impl FeeOptions {
+    /// Creates the options of a fee in basis points of the output, which the routers require to be
+    /// between 1 and 100, i.e. at most 1%.
+    ///
+    /// ## Arguments
+    ///
+    /// * `bips`: The fee in basis points
+    /// * `recipient`: The recipient of the fee
+    #[inline]
+    #[must_use]
+    pub fn from_bips(bips: u16, recipient: Address) -> Self {
+        assert!((1..=100).contains(&bips), "FEE_BIPS");
+        Self {
+            fee: Percent::new(i32::from(bips), 10000),
+            recipient,
+        }
+    }
+
+    /// The fee in basis points, as encoded in the calldata of `unwrapWETH9WithFee` and
+    /// `sweepTokenWithFee`
+    #[inline]
+    #[must_use]
+    pub fn bips(&self) -> U256 {
+        encode_fee_bips(self.fee.clone())
+    }
+}
+
 #[inline]
 fn encode_fee_bips(fee: Percent) -> U256 {
     U256::from_big_int((fee * Percent::new(10000, 1)).quotient())
@@ -77,6 +104,65 @@ pub fn encode_refund_eth() -> Bytes {
         .into()
 }
 
+/// A call to the periphery payments of the swap router or the position manager, see
+/// [`decode_payments_call`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentsCall {
+    UnwrapWETH9 {
+        amount_minimum: U256,
+        recipient: Address,
+        fee_options: Option<FeeOptions>,
+    },
+    SweepToken {
+        token: Address,
+        amount_minimum: U256,
+        recipient: Address,
+        fee_options: Option<FeeOptions>,
+    },
+    RefundETH,
+}
+
+/// Decodes the calldata of `unwrapWETH9`, `sweepToken`, their variants with a fee, or `refundETH`,
+/// the inverse of [`encode_unwrap_weth9`], [`encode_sweep_token`] and [`encode_refund_eth`].
+#[inline]
+pub fn decode_payments_call(
+    calldata: impl AsRef<[u8]>,
+) -> Result<PaymentsCall, alloy_sol_types::Error> {
+    use IPeripheryPaymentsWithFee::IPeripheryPaymentsWithFeeCalls as Calls;
+
+    let fee_options = |fee_bips: U256, recipient: Address| {
+        Some(FeeOptions {
+            fee: Percent::new(fee_bips.to_big_int(), 10000),
+            recipient,
+        })
+    };
+    Ok(match Calls::abi_decode(calldata.as_ref(), true)? {
+        Calls::unwrapWETH9(call) => PaymentsCall::UnwrapWETH9 {
+            amount_minimum: call.amountMinimum,
+            recipient: call.recipient,
+            fee_options: None,
+        },
+        Calls::unwrapWETH9WithFee(call) => PaymentsCall::UnwrapWETH9 {
+            amount_minimum: call.amountMinimum,
+            recipient: call.recipient,
+            fee_options: fee_options(call.feeBips, call.feeRecipient),
+        },
+        Calls::sweepToken(call) => PaymentsCall::SweepToken {
+            token: call.token,
+            amount_minimum: call.amountMinimum,
+            recipient: call.recipient,
+            fee_options: None,
+        },
+        Calls::sweepTokenWithFee(call) => PaymentsCall::SweepToken {
+            token: call.token,
+            amount_minimum: call.amountMinimum,
+            recipient: call.recipient,
+            fee_options: fee_options(call.feeBips, call.feeRecipient),
+        },
+        Calls::refundETH(_) => PaymentsCall::RefundETH,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,9 +213,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fee_options_from_bips() {
+        let fee_options = FeeOptions::from_bips(10, FEE_OPTIONS.recipient);
+        assert_eq!(fee_options.bips(), FEE_OPTIONS.bips());
+        assert_eq!(
+            encode_sweep_token(TOKEN, AMOUNT, RECIPIENT, Some(fee_options)),
+            encode_sweep_token(TOKEN, AMOUNT, RECIPIENT, Some(FEE_OPTIONS.clone()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "FEE_BIPS")]
+    fn test_fee_options_from_bips_too_high() {
+        let _ = FeeOptions::from_bips(101, RECIPIENT);
+    }
+
     #[test]
     fn test_encode_refund_eth() {
         let calldata = encode_refund_eth();
         assert_eq!(calldata.to_vec(), hex!("12210e8a"));
     }
+
+    #[test]
+    fn test_decode_payments_call() {
+        assert_eq!(
+            decode_payments_call(encode_unwrap_weth9(AMOUNT, RECIPIENT, None)).unwrap(),
+            PaymentsCall::UnwrapWETH9 {
+                amount_minimum: AMOUNT,
+                recipient: RECIPIENT,
+                fee_options: None,
+            }
+        );
+        let PaymentsCall::SweepToken {
+            token, fee_options, ..
+        } = decode_payments_call(encode_sweep_token(
+            TOKEN,
+            AMOUNT,
+            RECIPIENT,
+            Some(FEE_OPTIONS.clone()),
+        ))
+        .unwrap()
+        else {
+            panic!("expected a sweep");
+        };
+        assert_eq!(token, TOKEN);
+        assert_eq!(fee_options.unwrap().bips(), FEE_OPTIONS.bips());
+        assert_eq!(
+            decode_payments_call(encode_refund_eth()).unwrap(),
+            PaymentsCall::RefundETH
+        );
+    }
 }
@@ -0,0 +1,161 @@
+//! ## Pool Swap
+//! Produces the calldata of `IUniswapV3Pool.swap` for swapping in a pool directly, and the callback
+//! data that the pool passes back to the caller in `uniswapV3SwapCallback`.
+
+use crate::prelude::*;
+use alloy_primitives::{Address, Bytes, I256, U160, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use uniswap_sdk_core::prelude::*;
+
+/// Produces the calldata for swapping in a pool directly, which calls back `msg.sender` with
+/// `uniswapV3SwapCallback` to pay the input.
+///
+/// ## Arguments
+///
+/// * `recipient`: The recipient of the output
+/// * `zero_for_one`: Whether the input is token0, otherwise token1
+/// * `amount_specified`: The exact input if positive, or the exact output if negative
+/// * `sqrt_price_limit_x96`: The price beyond which the swap stops, or no limit if `None`
+/// * `data`: The callback data, e.g. from [`encode_pool_callback_data`] or
+///   [`encode_swap_callback_data`]
+#[inline]
+#[must_use]
+pub fn pool_swap_call_parameters(
+    recipient: Address,
+    zero_for_one: bool,
+    amount_specified: I256,
+    sqrt_price_limit_x96: Option<U160>,
+    data: Bytes,
+) -> MethodParameters {
+    let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or(if zero_for_one {
+        MIN_SQRT_RATIO + ONE
+    } else {
+        MAX_SQRT_RATIO - ONE
+    });
+    MethodParameters {
+        calldata: IUniswapV3PoolActions::swapCall {
+            recipient,
+            zeroForOne: zero_for_one,
+            amountSpecified: amount_specified,
+            sqrtPriceLimitX96: sqrt_price_limit_x96,
+            data,
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// Encodes the callback data of a swap in a single pool as the pool key followed by the payer, the
+/// layout of `PoolAddress.PoolKey` used by the periphery contracts.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to swap in
+/// * `payer`: The account that pays the input in the callback
+#[inline]
+#[must_use]
+pub fn encode_pool_callback_data<TP: TickDataProvider>(pool: &Pool<TP>, payer: Address) -> Bytes {
+    PoolCallbackData {
+        token0: pool.token0.address(),
+        token1: pool.token1.address(),
+        fee: pool.fee.into(),
+        payer,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Encodes the callback data of a swap along a path as by `SwapRouter`, where the callback pays
+/// the first pool of the path and swaps the rest of an exact output path.
+///
+/// ## Arguments
+///
+/// * `path`: The packed path, see [`encode_route_to_path`]
+/// * `payer`: The account that pays the input in the callback
+#[inline]
+#[must_use]
+pub fn encode_swap_callback_data(path: Bytes, payer: Address) -> Bytes {
+    SwapCallbackData { path, payer }.abi_encode().into()
+}
+
+/// Decodes the callback data of [`encode_pool_callback_data`]
+#[inline]
+pub fn decode_pool_callback_data(
+    data: impl AsRef<[u8]>,
+) -> Result<PoolCallbackData, alloy_sol_types::Error> {
+    PoolCallbackData::abi_decode(data.as_ref(), true)
+}
+
+/// Decodes the callback data of [`encode_swap_callback_data`]
+#[inline]
+pub fn decode_swap_callback_data(
+    data: impl AsRef<[u8]>,
+) -> Result<SwapCallbackData, alloy_sol_types::Error> {
+    SwapCallbackData::abi_decode(data.as_ref(), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, aliases::U24};
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    #[test]
+    fn test_pool_swap_call_parameters() {
+        let data = encode_pool_callback_data(&POOL_0_1, RECIPIENT);
+        let MethodParameters { calldata, value } = pool_swap_call_parameters(
+            RECIPIENT,
+            true,
+            I256::try_from(100).unwrap(),
+            None,
+            data.clone(),
+        );
+        assert_eq!(value, U256::ZERO);
+        let call = IUniswapV3PoolActions::swapCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(call.recipient, RECIPIENT);
+        assert!(call.zeroForOne);
+        assert_eq!(call.amountSpecified, I256::try_from(100).unwrap());
+        assert_eq!(call.sqrtPriceLimitX96, MIN_SQRT_RATIO + ONE);
+        assert_eq!(call.data, data);
+
+        let MethodParameters { calldata, .. } = pool_swap_call_parameters(
+            RECIPIENT,
+            false,
+            I256::try_from(-100).unwrap(),
+            None,
+            Bytes::new(),
+        );
+        let call = IUniswapV3PoolActions::swapCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(call.sqrtPriceLimitX96, MAX_SQRT_RATIO - ONE);
+    }
+
+    #[test]
+    fn test_callback_data() {
+        let data =
+            decode_pool_callback_data(encode_pool_callback_data(&POOL_0_1, RECIPIENT)).unwrap();
+        assert_eq!(
+            data,
+            PoolCallbackData {
+                token0: TOKEN0.address(),
+                token1: TOKEN1.address(),
+                fee: U24::from(3000),
+                payer: RECIPIENT,
+            }
+        );
+
+        let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone());
+        let path = encode_route_to_path(&route, true);
+        let data =
+            decode_swap_callback_data(encode_swap_callback_data(path.clone(), RECIPIENT)).unwrap();
+        assert_eq!(
+            data,
+            SwapCallbackData {
+                path,
+                payer: RECIPIENT
+            }
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::{vec, vec::Vec};
 use alloy_primitives::{U160, U256};
 use alloy_sol_types::SolCall;
 use uniswap_sdk_core::prelude::*;
@@ -12,6 +13,40 @@ pub struct QuoteOptions {
     pub use_quoter_v2: bool,
 }
 
+/// The decoded result of a quote.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Quote {
+    /// The amount out for an exact input quote, or the amount in for an exact output quote
+    pub amount: U256,
+    /// The sqrt price of each pool after the swap, only returned by QuoterV2
+    pub sqrt_price_x96_after_list: Vec<U160>,
+    /// The number of initialized ticks crossed in each pool, only returned by QuoterV2
+    pub initialized_ticks_crossed_list: Vec<u32>,
+    /// The estimated gas used by the swap, only returned by QuoterV2
+    pub gas_estimate: U256,
+}
+
+impl Quote {
+    /// Returns the quoted amount adjusted for the gas cost of the swap, i.e. the amount out less
+    /// the gas cost for an exact input quote, or the amount in plus the gas cost for an exact
+    /// output quote.
+    ///
+    /// ## Arguments
+    ///
+    /// * `gas_price`: The gas price denominated in the quoted token, i.e. the output token for an
+    ///   exact input quote, or the input token for an exact output quote
+    /// * `trade_type`: The trade type of the quote
+    #[inline]
+    #[must_use]
+    pub fn gas_adjusted_amount(&self, gas_price: U256, trade_type: TradeType) -> U256 {
+        let gas_cost = self.gas_estimate.saturating_mul(gas_price);
+        match trade_type {
+            TradeType::ExactInput => self.amount.saturating_sub(gas_cost),
+            TradeType::ExactOutput => self.amount.saturating_add(gas_cost),
+        }
+    }
+}
+
 /// Produces the on-chain method name of the appropriate function within QuoterV2,
 /// and the relevant hex encoded parameters.
 ///
@@ -91,13 +126,23 @@ where
             "MULTIHOP_PRICE_LIMIT"
         );
         let path = encode_route_to_path(route, trade_type == TradeType::ExactOutput);
-        match trade_type {
-            TradeType::ExactInput => IQuoter::quoteExactInputCall {
+        match (trade_type, options.use_quoter_v2) {
+            (TradeType::ExactInput, false) => IQuoter::quoteExactInputCall {
                 path,
                 amountIn: quote_amount,
             }
             .abi_encode(),
-            TradeType::ExactOutput => IQuoter::quoteExactOutputCall {
+            (TradeType::ExactInput, true) => IQuoterV2::quoteExactInputCall {
+                path,
+                amountIn: quote_amount,
+            }
+            .abi_encode(),
+            (TradeType::ExactOutput, false) => IQuoter::quoteExactOutputCall {
+                path,
+                amountOut: quote_amount,
+            }
+            .abi_encode(),
+            (TradeType::ExactOutput, true) => IQuoterV2::quoteExactOutputCall {
                 path,
                 amountOut: quote_amount,
             }
@@ -110,6 +155,106 @@ where
     }
 }
 
+/// Decodes the return data of a call produced by [`quote_call_parameters`] with the same route,
+/// trade type and options.
+///
+/// ## Arguments
+///
+/// * `route`: The swap route that was quoted
+/// * `trade_type`: The trade type, either exact input or exact output
+/// * `options`: The optional params including price limit and Quoter contract switch
+/// * `data`: The return data of the quoter call
+#[inline]
+pub fn decode_quote_result<TInput, TOutput, TP>(
+    route: &Route<TInput, TOutput, TP>,
+    trade_type: TradeType,
+    options: Option<QuoteOptions>,
+    data: impl AsRef<[u8]>,
+) -> Result<Quote, alloy_sol_types::Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    let options = options.unwrap_or_default();
+    let data = data.as_ref();
+    let single_hop = route.pools.len() == 1;
+    Ok(match (trade_type, options.use_quoter_v2, single_hop) {
+        (TradeType::ExactInput, false, true) => Quote {
+            amount: IQuoter::quoteExactInputSingleCall::abi_decode_returns(data, true)?.amountOut,
+            ..Default::default()
+        },
+        (TradeType::ExactInput, false, false) => Quote {
+            amount: IQuoter::quoteExactInputCall::abi_decode_returns(data, true)?.amountOut,
+            ..Default::default()
+        },
+        (TradeType::ExactOutput, false, true) => Quote {
+            amount: IQuoter::quoteExactOutputSingleCall::abi_decode_returns(data, true)?.amountIn,
+            ..Default::default()
+        },
+        (TradeType::ExactOutput, false, false) => Quote {
+            amount: IQuoter::quoteExactOutputCall::abi_decode_returns(data, true)?.amountIn,
+            ..Default::default()
+        },
+        (TradeType::ExactInput, true, true) => {
+            let IQuoterV2::quoteExactInputSingleReturn {
+                amountOut,
+                sqrtPriceX96After,
+                initializedTicksCrossed,
+                gasEstimate,
+            } = IQuoterV2::quoteExactInputSingleCall::abi_decode_returns(data, true)?;
+            Quote {
+                amount: amountOut,
+                sqrt_price_x96_after_list: vec![sqrtPriceX96After],
+                initialized_ticks_crossed_list: vec![initializedTicksCrossed],
+                gas_estimate: gasEstimate,
+            }
+        }
+        (TradeType::ExactInput, true, false) => {
+            let IQuoterV2::quoteExactInputReturn {
+                amountOut,
+                sqrtPriceX96AfterList,
+                initializedTicksCrossedList,
+                gasEstimate,
+            } = IQuoterV2::quoteExactInputCall::abi_decode_returns(data, true)?;
+            Quote {
+                amount: amountOut,
+                sqrt_price_x96_after_list: sqrtPriceX96AfterList,
+                initialized_ticks_crossed_list: initializedTicksCrossedList,
+                gas_estimate: gasEstimate,
+            }
+        }
+        (TradeType::ExactOutput, true, true) => {
+            let IQuoterV2::quoteExactOutputSingleReturn {
+                amountIn,
+                sqrtPriceX96After,
+                initializedTicksCrossed,
+                gasEstimate,
+            } = IQuoterV2::quoteExactOutputSingleCall::abi_decode_returns(data, true)?;
+            Quote {
+                amount: amountIn,
+                sqrt_price_x96_after_list: vec![sqrtPriceX96After],
+                initialized_ticks_crossed_list: vec![initializedTicksCrossed],
+                gas_estimate: gasEstimate,
+            }
+        }
+        (TradeType::ExactOutput, true, false) => {
+            let IQuoterV2::quoteExactOutputReturn {
+                amountIn,
+                sqrtPriceX96AfterList,
+                initializedTicksCrossedList,
+                gasEstimate,
+            } = IQuoterV2::quoteExactOutputCall::abi_decode_returns(data, true)?;
+            Quote {
+                amount: amountIn,
+                sqrt_price_x96_after_list: sqrtPriceX96AfterList,
+                initialized_ticks_crossed_list: initializedTicksCrossedList,
+                gas_estimate: gasEstimate,
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,4 +437,81 @@ mod tests {
             );
         }
     }
+
+    mod decode_quote_result {
+        use super::*;
+        use alloy_primitives::uint;
+
+        static ROUTE: Lazy<Route<Token, Token, TickListDataProvider>> =
+            Lazy::new(|| Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()));
+        static MULTI_HOP_ROUTE: Lazy<Route<Token, Token, TickListDataProvider>> = Lazy::new(|| {
+            Route::new(
+                vec![POOL_0_1.clone(), POOL_1_WETH.clone()],
+                TOKEN0.clone(),
+                WETH.clone(),
+            )
+        });
+        const V2: Option<QuoteOptions> = Some(QuoteOptions {
+            sqrt_price_limit_x96: U160::ZERO,
+            use_quoter_v2: true,
+        });
+
+        #[test]
+        fn quoter_v1_single_hop() {
+            let data = IQuoter::quoteExactInputSingleCall::abi_encode_returns(&(U256::from(98),));
+            let quote = decode_quote_result(&ROUTE, TradeType::ExactInput, None, data).unwrap();
+            assert_eq!(
+                quote,
+                Quote {
+                    amount: U256::from(98),
+                    ..Default::default()
+                }
+            );
+        }
+
+        #[test]
+        fn quoter_v2_single_hop() {
+            let sqrt_price_x96_after = uint!(79228162514264337593543950336_U160);
+            let data = IQuoterV2::quoteExactOutputSingleCall::abi_encode_returns(&(
+                U256::from(100),
+                sqrt_price_x96_after,
+                1,
+                U256::from(80_000),
+            ));
+            let quote = decode_quote_result(&ROUTE, TradeType::ExactOutput, V2, data).unwrap();
+            assert_eq!(quote.amount, U256::from(100));
+            assert_eq!(quote.sqrt_price_x96_after_list, vec![sqrt_price_x96_after]);
+            assert_eq!(quote.initialized_ticks_crossed_list, vec![1]);
+            assert_eq!(quote.gas_estimate, U256::from(80_000));
+            assert_eq!(
+                quote.gas_adjusted_amount(U256::from(2), TradeType::ExactOutput),
+                U256::from(160_100)
+            );
+        }
+
+        #[test]
+        fn quoter_v2_multi_hop() {
+            let sqrt_prices = vec![U160::from(1), U160::from(2)];
+            let data = IQuoterV2::quoteExactInputCall::abi_encode_returns(&(
+                U256::from(97),
+                sqrt_prices.clone(),
+                vec![0, 2],
+                U256::from(150_000),
+            ));
+            let quote =
+                decode_quote_result(&MULTI_HOP_ROUTE, TradeType::ExactInput, V2, data).unwrap();
+            assert_eq!(quote.amount, U256::from(97));
+            assert_eq!(quote.sqrt_price_x96_after_list, sqrt_prices);
+            assert_eq!(quote.initialized_ticks_crossed_list, vec![0, 2]);
+            assert_eq!(
+                quote.gas_adjusted_amount(U256::from(1), TradeType::ExactInput),
+                U256::ZERO
+            );
+        }
+
+        #[test]
+        fn invalid_data() {
+            assert!(decode_quote_result(&ROUTE, TradeType::ExactInput, V2, [0_u8; 32]).is_err());
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use super::abi::ISelfPermit;
 use alloy_primitives::{Bytes, PrimitiveSignature, B256, U256};
-use alloy_sol_types::{eip712_domain, Eip712Domain, SolCall, SolStruct};
+use alloy_sol_types::{eip712_domain, Eip712Domain, SolCall, SolInterface, SolStruct};
 use uniswap_sdk_core::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -171,6 +171,47 @@ pub fn encode_permit(token: &impl BaseCurrency, options: PermitOptions) -> Bytes
     .into()
 }
 
+/// Decodes the signature of a permit call, where `v` is 27 or 28, or the parity itself
+#[inline]
+pub(crate) fn decode_signature(v: u8, r: B256, s: B256) -> PrimitiveSignature {
+    PrimitiveSignature::new(
+        U256::from_be_bytes(r.0),
+        U256::from_be_bytes(s.0),
+        v == 1 || v == 28,
+    )
+}
+
+/// Decodes the calldata of `selfPermit` or `selfPermitAllowed`, the inverse of [`encode_permit`].
+///
+/// ## Returns
+///
+/// The address of the token and the permit options
+#[inline]
+pub fn decode_permit(
+    calldata: impl AsRef<[u8]>,
+) -> Result<(Address, PermitOptions), alloy_sol_types::Error> {
+    Ok(
+        match ISelfPermit::ISelfPermitCalls::abi_decode(calldata.as_ref(), true)? {
+            ISelfPermit::ISelfPermitCalls::selfPermit(call) => (
+                call.token,
+                PermitOptions::Standard(StandardPermitArguments {
+                    signature: decode_signature(call.v, call.r, call.s),
+                    amount: call.value,
+                    deadline: call.deadline,
+                }),
+            ),
+            ISelfPermit::ISelfPermitCalls::selfPermitAllowed(call) => (
+                call.token,
+                PermitOptions::Allowed(AllowedPermitArguments {
+                    signature: decode_signature(call.v, call.r, call.s),
+                    nonce: call.nonce,
+                    expiry: call.expiry,
+                }),
+            ),
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +235,13 @@ mod tests {
             &TOKEN.clone(),
             PermitOptions::Standard(standard_permit_options),
         );
+        assert_eq!(
+            decode_permit(&calldata).unwrap(),
+            (
+                TOKEN.address(),
+                PermitOptions::Standard(standard_permit_options)
+            )
+        );
         assert_eq!(calldata, hex!("f3995c670000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000007b000000000000000000000000000000000000000000000000000000000000007b000000000000000000000000000000000000000000000000000000000000001b00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002").to_vec());
     }
 
@@ -210,6 +258,13 @@ mod tests {
             &TOKEN.clone(),
             PermitOptions::Allowed(allowed_permit_options),
         );
+        assert_eq!(
+            decode_permit(&calldata).unwrap(),
+            (
+                TOKEN.address(),
+                PermitOptions::Allowed(allowed_permit_options)
+            )
+        );
         assert_eq!(calldata, hex!("4659a4940000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000007b000000000000000000000000000000000000000000000000000000000000007b000000000000000000000000000000000000000000000000000000000000001b00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002").to_vec());
     }
 }
@@ -1,7 +1,11 @@
-use crate::prelude::*;
+use crate::{
+    constants::SECONDS_PER_YEAR,
+    prelude::{Error, *},
+};
 use alloc::vec::Vec;
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U160, U256};
 use alloy_sol_types::{SolCall, SolValue};
+use uniswap_sdk_core::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FullWithdrawOptions {
@@ -175,6 +179,158 @@ pub fn encode_deposit<TP: TickDataProvider>(incentive_keys: &[IncentiveKey<TP>])
     .into()
 }
 
+/// Computes the id of an incentive, i.e. the key of the incentive in the storage of the staker, as
+/// by `IncentiveId.compute`.
+///
+/// ## Arguments
+///
+/// * `incentive_key`: The unique identifier of a staking program.
+#[inline]
+#[must_use]
+pub fn incentive_key_id<TP: TickDataProvider>(incentive_key: &IncentiveKey<TP>) -> B256 {
+    keccak256(encode_incentive_key(incentive_key).abi_encode())
+}
+
+/// Deposits a position in the staker and stakes it in one or more programs in one transaction, by
+/// transferring the NFT to the staker with the incentive keys in the `data` of `safeTransferFrom`.
+///
+/// ## Arguments
+///
+/// * `incentive_keys`: The staking programs to stake the position in, see [`encode_deposit`].
+/// * `owner`: The owner of the position, who can later withdraw it.
+/// * `staker`: The address of the staker.
+/// * `token_id`: The id of the NFT.
+#[inline]
+pub fn deposit_call_parameters<TP: TickDataProvider>(
+    incentive_keys: &[IncentiveKey<TP>],
+    owner: Address,
+    staker: Address,
+    token_id: U256,
+) -> MethodParameters {
+    safe_transfer_from_parameters(SafeTransferOptions {
+        sender: owner,
+        recipient: staker,
+        token_id,
+        data: encode_deposit(incentive_keys),
+    })
+}
+
+/// Computes the reward of a stake accrued since it was staked and the seconds it spent in range,
+/// as by `RewardMath.computeRewardAmount` of the staker, from the storage of the incentive and the
+/// stake.
+///
+/// The unclaimed reward is distributed evenly over the unclaimed seconds of the incentive, which
+/// run until `current_time` if the incentive has ended, so the reward of a stake keeps decreasing
+/// after the end time as if the stake were out of range.
+///
+/// ## Arguments
+///
+/// * `total_reward_unclaimed`: The reward of the incentive not yet claimed
+/// * `total_seconds_claimed_x128`: The seconds claimed by the stakes of the incentive as a Q128.128
+/// * `start_time`: The time when the incentive begins
+/// * `end_time`: The time when the incentive ends
+/// * `liquidity`: The liquidity of the stake
+/// * `seconds_per_liquidity_inside_initial_x128`: The seconds per liquidity inside the range of
+///   the stake when it was staked
+/// * `seconds_per_liquidity_inside_x128`: The current seconds per liquidity inside the range of the
+///   stake, see `snapshotCumulativesInside` of the pool
+/// * `current_time`: The current time
+///
+/// ## Returns
+///
+/// The reward and the seconds spent in range as a Q128.128
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn compute_reward_amount(
+    total_reward_unclaimed: U256,
+    total_seconds_claimed_x128: U160,
+    start_time: U256,
+    end_time: U256,
+    liquidity: u128,
+    seconds_per_liquidity_inside_initial_x128: U160,
+    seconds_per_liquidity_inside_x128: U160,
+    current_time: U256,
+) -> Result<(U256, U160), Error> {
+    // this should never be called before the start time
+    assert!(current_time >= start_time, "START_TIME");
+
+    // the staker is compiled without overflow checks
+    let seconds_inside_x128 = seconds_per_liquidity_inside_x128
+        .wrapping_sub(seconds_per_liquidity_inside_initial_x128)
+        .wrapping_mul(U160::from(liquidity));
+
+    // if the incentive has ended, the unclaimed seconds run until the current time
+    let total_seconds_unclaimed_x128 =
+        ((end_time.max(current_time) - start_time) << 128) - U256::from(total_seconds_claimed_x128);
+
+    let reward = total_reward_unclaimed.mul_div(
+        U256::from(seconds_inside_x128),
+        total_seconds_unclaimed_x128,
+    )?;
+    Ok((reward, seconds_inside_x128))
+}
+
+/// Estimates the annualized reward APR of a position staked in an incentive, assuming the reward
+/// is distributed evenly over the duration of the incentive and the in-range liquidity stays
+/// constant.
+///
+/// The staker distributes the reward per second among the in-range liquidity of the pool, so a
+/// position earns the share of its liquidity in the active liquidity while it is in range, and
+/// nothing otherwise. The reward and the position are valued in the quote currency of
+/// `reward_price`, which must be a token of the pool.
+///
+/// ## Arguments
+///
+/// * `incentive_key`: The incentive
+/// * `total_reward`: The total reward of the incentive
+/// * `position`: The staked position, whose liquidity and range are used
+/// * `active_liquidity`: The in-range liquidity sharing the reward, including the position, by
+///   default the liquidity of the pool of the incentive
+/// * `reward_price`: The price of the reward token in token0 or token1 of the pool
+///
+/// ## Returns
+///
+/// The reward APR as a [`Percent`]
+#[inline]
+pub fn get_incentive_apr<TP: TickDataProvider>(
+    incentive_key: &IncentiveKey<TP>,
+    total_reward: U256,
+    position: &Position<TP>,
+    active_liquidity: Option<u128>,
+    reward_price: &Price<Token, Token>,
+) -> Result<Percent, Error> {
+    assert_eq!(
+        reward_price.base_currency.address(),
+        incentive_key.reward_token,
+        "REWARD_TOKEN"
+    );
+    assert!(
+        incentive_key.end_time > incentive_key.start_time,
+        "TIME_ORDER"
+    );
+    let active_liquidity = active_liquidity.unwrap_or(incentive_key.pool.liquidity);
+    let value = position.value_in_token(&reward_price.quote_currency, U256::ZERO, U256::ZERO)?;
+    if position.range_status() != RangeStatus::InRange
+        || active_liquidity == 0
+        || value.quotient().is_zero()
+    {
+        return Ok(Percent::default());
+    }
+    let duration = (incentive_key.end_time - incentive_key.start_time).to_big_int();
+    let reward_per_year = CurrencyAmount::from_fractional_amount(
+        reward_price.base_currency.clone(),
+        total_reward.to_big_int()
+            * BigInt::from(SECONDS_PER_YEAR)
+            * BigInt::from(position.liquidity),
+        duration * BigInt::from(active_liquidity),
+    )?;
+    let reward_value = reward_price.quote(&reward_per_year)?;
+    Ok(Percent::new(
+        reward_value.numerator * value.denominator,
+        reward_value.denominator * value.numerator,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,4 +487,144 @@ mod tests {
             hex!("b88d4fde000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000001f9840a85d5af5bf1d1762f925bdaddc4201f9840000000000000000000000004fa63b0dea87d2cd519f3b67a5ddb145779b7bd2000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000000c80000000000000000000000000000000000000000000000000000000000000001")
         );
     }
+
+    #[test]
+    fn test_deposit_call_parameters() {
+        let parameters =
+            deposit_call_parameters(&[INCENTIVE_KEY.clone()], SENDER, RECIPIENT, TOKEN_ID);
+        assert_eq!(
+            parameters,
+            safe_transfer_from_parameters(SafeTransferOptions {
+                sender: SENDER,
+                recipient: RECIPIENT,
+                token_id: TOKEN_ID,
+                data: encode_deposit(&[INCENTIVE_KEY.clone()]),
+            })
+        );
+        let parameters = deposit_call_parameters(&INCENTIVE_KEYS, SENDER, RECIPIENT, TOKEN_ID);
+        match decode_position_manager_call(parameters.calldata)
+            .unwrap()
+            .as_slice()
+        {
+            [PositionManagerCall::SafeTransferFrom(options)] => {
+                assert_eq!(options.data, encode_deposit(&INCENTIVE_KEYS));
+            }
+            calls => panic!("unexpected calls: {calls:?}"),
+        }
+    }
+
+    #[test]
+    fn test_incentive_key_id() {
+        assert_eq!(
+            incentive_key_id(&INCENTIVE_KEY),
+            keccak256(encode_deposit(&[INCENTIVE_KEY.clone()]))
+        );
+        assert_ne!(
+            incentive_key_id(&INCENTIVE_KEYS[0]),
+            incentive_key_id(&INCENTIVE_KEYS[1])
+        );
+    }
+
+    #[test]
+    fn test_compute_reward_amount() {
+        // half the liquidity in range over 20% of the duration
+        let (reward, seconds_inside_x128) = compute_reward_amount(
+            U256::from(1000),
+            U160::ZERO,
+            U256::from(100),
+            U256::from(200),
+            5,
+            U160::ZERO,
+            (U160::from(20) << 128) / U160::from(10),
+            U256::from(120),
+        )
+        .unwrap();
+        assert_eq!(reward, U256::from(100));
+        assert_eq!(seconds_inside_x128, U160::from(10) << 128);
+
+        // all the liquidity for the duration and none after the end time
+        let (reward, seconds_inside_x128) = compute_reward_amount(
+            U256::from(1000),
+            U160::ZERO,
+            U256::from(100),
+            U256::from(200),
+            100,
+            U160::ZERO,
+            (U160::from(100) << 128) / U160::from(100),
+            U256::from(300),
+        )
+        .unwrap();
+        assert_eq!(reward, U256::from(500));
+        assert_eq!(seconds_inside_x128, U160::from(100) << 128);
+
+        // the seconds already claimed are excluded
+        let (reward, _) = compute_reward_amount(
+            U256::from(500),
+            U160::from(50) << 128,
+            U256::from(100),
+            U256::from(200),
+            10,
+            U160::ZERO,
+            (U160::from(25) << 128) / U160::from(10),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(reward, U256::from(250));
+    }
+
+    #[test]
+    fn test_get_incentive_apr() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000, -60, 60);
+        // a raw unit of the reward is worth 2 of token1
+        let reward_price = Price::new(REWARD.clone(), TOKEN1.clone(), 1, 2);
+        let apr = get_incentive_apr(
+            &INCENTIVE_KEY,
+            U256::from(1000),
+            &position,
+            Some(2_000_000),
+            &reward_price,
+        )
+        .unwrap();
+        // half of 1000 per 100 seconds, valued at 2
+        let value = position
+            .value_in_token(&TOKEN1, U256::ZERO, U256::ZERO)
+            .unwrap();
+        let expected = Percent::new(
+            BigInt::from(10 * SECONDS_PER_YEAR) * value.denominator,
+            value.numerator,
+        );
+        assert_eq!(
+            apr.numerator * expected.denominator,
+            expected.numerator * apr.denominator
+        );
+        // the position earns nothing out of range
+        let position = Position::new(POOL_0_1.clone(), 1_000_000, 60, 120);
+        assert_eq!(
+            get_incentive_apr(
+                &INCENTIVE_KEY,
+                U256::from(1000),
+                &position,
+                Some(2_000_000),
+                &reward_price,
+            )
+            .unwrap()
+            .numerator,
+            BigInt::ZERO
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "START_TIME")]
+    fn test_compute_reward_amount_before_start_time() {
+        let _ = compute_reward_amount(
+            U256::from(1000),
+            U160::ZERO,
+            U256::from(100),
+            U256::from(200),
+            5,
+            U160::ZERO,
+            U160::ZERO,
+            U256::from(99),
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::prelude::{Error, *};
-use alloy_primitives::{Bytes, U160, U256};
-use alloy_sol_types::SolCall;
+use alloy_primitives::{Bytes, ChainId, U160, U256};
+use alloy_sol_types::{SolCall, SolInterface};
 use uniswap_sdk_core::prelude::*;
 
 /// Options for producing the arguments to send calls to the router.
@@ -14,10 +14,56 @@ pub struct SwapOptions {
     pub input_token_permit: Option<PermitOptions>,
     /// The optional price limit for the trade.
     pub sqrt_price_limit_x96: Option<U160>,
-    /// Optional information for taking a fee on output.
+    /// The optional tolerance of the pool price movement of single-hop swaps, from which the price
+    /// limit is derived if `sqrt_price_limit_x96` is not set.
+    pub price_limit_tolerance: Option<Percent>,
+    /// Optional information for taking a fee on output. If set, the router takes custody of the
+    /// output and pays it out with `unwrapWETH9WithFee` or `sweepTokenWithFee`, see
+    /// [`FeeOptions::from_bips`].
     pub fee: Option<FeeOptions>,
+    /// The optional slippage tolerance of each hop of multi-hop exact input swaps. If set, the
+    /// swaps are split into sequential single-hop calls, each enforcing a minimum output. The
+    /// intermediate outputs are paid to the sender, from which the next hop pulls exactly the
+    /// minimum output of the previous one, so the router must be approved to spend them.
+    pub per_hop_slippage_tolerance: Option<Percent>,
+    /// The optional maximum price impact of each trade, see [`Trade::price_impact`]. If set,
+    /// [`swap_call_parameters`] fails with [`Error::PriceImpactTooHigh`] when it is exceeded.
+    pub max_price_impact: Option<Percent>,
+    /// The optional chain id, from which [`SwapOptions::target`] resolves the router.
+    pub chain_id: Option<ChainId>,
+    /// The optional deployment, e.g. of a fork, whose router [`SwapOptions::target`] resolves
+    /// ahead of the chain id
+    pub deployment: Option<DeploymentConfig>,
 }
 
+impl SwapOptions {
+    /// Returns the [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/SwapRouter02.sol)
+    /// to send the calldata to: the router of the deployment if set, or else of the chain if the
+    /// chain id is set and known, see [`get_chain_addresses`]
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> Option<Address> {
+        if let Some(deployment) = &self.deployment {
+            return Some(deployment.swap_router);
+        }
+        match self.chain_id {
+            Some(chain_id) => match get_chain_addresses(chain_id) {
+                Some(addresses) => Some(addresses.swap_router02),
+                None => None,
+            },
+            None => None,
+        }
+    }
+}
+
+/// The recipient for the swap router to keep the output of a swap, see
+/// [`Constants`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/libraries/Constants.sol).
+pub(crate) const ADDRESS_THIS: Address = Address::with_last_byte(2);
+
+/// The recipient for the swap router to pay the output of a swap to the sender of the call, see
+/// [`Constants`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/libraries/Constants.sol).
+pub(crate) const MSG_SENDER: Address = Address::with_last_byte(1);
+
 /// Produces the on-chain method name to call and the hex encoded parameters to pass as arguments
 /// for a given trade on [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/SwapRouter02.sol).
 ///
@@ -34,6 +80,86 @@ pub fn swap_call_parameters<TInput, TOutput, TP>(
     trades: &mut [Trade<TInput, TOutput, TP>],
     options: SwapOptions,
 ) -> Result<MethodParameters, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    let (mut calldatas, value, must_refund) = encode_swap_calls(trades, options)?;
+
+    // refund
+    if must_refund {
+        calldatas.push(encode_refund_eth());
+    }
+
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value: U256::from_big_int(value),
+    })
+}
+
+/// Produces the calldata and value of a single [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/SwapRouter02.sol)
+/// multicall executing several independent trades, e.g. of different pairs, each with its own
+/// options, before a shared deadline.
+///
+/// ## Notes
+///
+/// The calls of each trade are encoded as by [`swap_call_parameters`] and follow each other in
+/// the order of the trades: the permit of its input, its swaps and the unwrap or sweep of its
+/// output if the router takes custody of it, so that a trade never pays out the output of another.
+/// The leftover ether of the exact output trades of ether is refunded once, after all the trades
+/// have spent their input. The value of the multicall is the total ether input of the trades.
+///
+/// The currencies of the trades are erased to [`Currency`], so that trades of ether and of tokens
+/// can be batched together.
+///
+/// ## Arguments
+///
+/// * `trades`: The trades to execute, each with the options of its call parameters
+/// * `deadline`: The deadline of the multicall, past which it reverts
+#[inline]
+pub fn swap_batch_call_parameters<TP>(
+    trades: &mut [(Trade<Currency, Currency, TP>, SwapOptions)],
+    deadline: U256,
+) -> Result<MethodParameters, Error>
+where
+    TP: TickDataProvider,
+{
+    if trades.is_empty() {
+        return Err(Error::NoTrades);
+    }
+    let mut calldatas: Vec<Bytes> = Vec::new();
+    let mut total_value = BigInt::ZERO;
+    let mut must_refund = false;
+    for (trade, options) in trades.iter_mut() {
+        let (trade_calldatas, value, trade_must_refund) =
+            encode_swap_calls(core::slice::from_mut(trade), options.clone())?;
+        calldatas.extend(trade_calldatas);
+        total_value += value;
+        must_refund |= trade_must_refund;
+    }
+
+    // refund
+    if must_refund {
+        calldatas.push(encode_refund_eth());
+    }
+
+    Ok(MethodParameters {
+        calldata: encode_multicall_with_deadline(deadline, calldatas),
+        value: U256::from_big_int(total_value),
+    })
+}
+
+/// Encodes the calls of trades sharing their input and output currencies, without the refund of
+/// leftover ether
+///
+/// ## Returns
+///
+/// The calldatas, the total ether input and whether the leftover ether must be refunded
+fn encode_swap_calls<TInput, TOutput, TP>(
+    trades: &mut [Trade<TInput, TOutput, TP>],
+    options: SwapOptions,
+) -> Result<(Vec<Bytes>, BigInt, bool), Error>
 where
     TInput: BaseCurrency,
     TOutput: BaseCurrency,
@@ -44,7 +170,11 @@ where
         recipient,
         input_token_permit,
         sqrt_price_limit_x96,
+        price_limit_tolerance,
         fee,
+        per_hop_slippage_tolerance,
+        max_price_impact,
+        ..
     } = options;
     let sample_trade = &trades[0];
     let input_currency = sample_trade.input_currency();
@@ -68,6 +198,14 @@ where
         );
     }
 
+    if let Some(max_price_impact) = &max_price_impact {
+        for trade in trades.iter_mut() {
+            if &trade.price_impact_cached()? > max_price_impact {
+                return Err(Error::PriceImpactTooHigh);
+            }
+        }
+    }
+
     let num_swaps = trades.iter().map(|trade| trade.swaps.len()).sum::<usize>();
 
     let mut calldatas: Vec<Bytes> = Vec::with_capacity(num_swaps + 3);
@@ -101,11 +239,19 @@ where
     }
 
     for trade in trades.iter() {
-        for Swap {
-            route,
-            input_amount,
-            output_amount,
-        } in &trade.swaps
+        // the expected output of each hop, for the per-hop minimum outputs
+        let hop_breakdown = match (&per_hop_slippage_tolerance, trade.trade_type) {
+            (Some(_), TradeType::ExactInput) => Some(trade.hop_breakdown()?),
+            _ => None,
+        };
+        for (
+            i,
+            Swap {
+                route,
+                input_amount,
+                output_amount,
+            },
+        ) in trade.swaps.iter().enumerate()
         {
             let amount_in = U256::from_big_int(
                 trade
@@ -119,6 +265,18 @@ where
             );
 
             if route.pools.len() == 1 {
+                let pool = &route.pools[0];
+                let sqrt_price_limit_x96 = sqrt_price_limit_x96
+                    .or_else(|| {
+                        price_limit_tolerance.as_ref().map(|tolerance| {
+                            sqrt_price_limit_from_slippage(
+                                pool.sqrt_ratio_x96,
+                                route.input.wrapped().equals(&pool.token0),
+                                tolerance,
+                            )
+                        })
+                    })
+                    .unwrap_or_default();
                 calldatas.push(match trade.trade_type {
                     TradeType::ExactInput => IV3SwapRouter::exactInputSingleCall {
                         params: IV3SwapRouter::ExactInputSingleParams {
@@ -132,7 +290,7 @@ where
                             },
                             amountIn: amount_in,
                             amountOutMinimum: amount_out,
-                            sqrtPriceLimitX96: sqrt_price_limit_x96.unwrap_or_default(),
+                            sqrtPriceLimitX96: sqrt_price_limit_x96,
                         },
                     }
                     .abi_encode()
@@ -149,12 +307,57 @@ where
                             },
                             amountOut: amount_out,
                             amountInMaximum: amount_in,
-                            sqrtPriceLimitX96: sqrt_price_limit_x96.unwrap_or_default(),
+                            sqrtPriceLimitX96: sqrt_price_limit_x96,
                         },
                     }
                     .abi_encode()
                     .into(),
                 });
+            } else if let (Some(per_hop_slippage_tolerance), Some(hop_breakdown)) =
+                (&per_hop_slippage_tolerance, &hop_breakdown)
+            {
+                assert!(sqrt_price_limit_x96.is_none(), "MULTIHOP_PRICE_LIMIT");
+
+                // the intermediate outputs are paid to the sender and the next hop pulls exactly
+                // the minimum output of the previous one, so that it never spends the balance of
+                // the router, which may hold the custody of other swaps of the multicall
+                let hops = &hop_breakdown[i];
+                let mut hop_amount_in = amount_in;
+                for (j, (pool, hop)) in route.pools.iter().zip(hops).enumerate() {
+                    let is_last_hop = j == hops.len() - 1;
+                    let mut amount_out_minimum = U256::from_big_int(
+                        hop.output_amount
+                            .multiply(
+                                &(Percent::new(1, 1) + per_hop_slippage_tolerance.clone()).invert(),
+                            )?
+                            .quotient(),
+                    );
+                    if is_last_hop {
+                        amount_out_minimum = amount_out_minimum.max(amount_out);
+                    }
+                    calldatas.push(
+                        IV3SwapRouter::exactInputSingleCall {
+                            params: IV3SwapRouter::ExactInputSingleParams {
+                                tokenIn: hop.input_amount.currency.address(),
+                                tokenOut: hop.output_amount.currency.address(),
+                                fee: pool.fee.into(),
+                                recipient: if !is_last_hop {
+                                    MSG_SENDER
+                                } else if router_must_custody {
+                                    Address::ZERO
+                                } else {
+                                    recipient
+                                },
+                                amountIn: hop_amount_in,
+                                amountOutMinimum: amount_out_minimum,
+                                sqrtPriceLimitX96: U160::ZERO,
+                            },
+                        }
+                        .abi_encode()
+                        .into(),
+                    );
+                    hop_amount_in = amount_out_minimum;
+                }
             } else {
                 assert!(sqrt_price_limit_x96.is_none(), "MULTIHOP_PRICE_LIMIT");
 
@@ -208,15 +411,57 @@ where
         }
     }
 
-    // refund
-    if must_refund {
-        calldatas.push(encode_refund_eth());
-    }
+    Ok((calldatas, total_value, must_refund))
+}
 
-    Ok(MethodParameters {
-        calldata: encode_multicall(calldatas),
-        value: U256::from_big_int(total_value),
-    })
+/// A call of the swap router, see [`decode_swap_call_parameters`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwapRouterCall {
+    ExactInputSingle(IV3SwapRouter::ExactInputSingleParams),
+    ExactInput(IV3SwapRouter::ExactInputParams),
+    ExactOutputSingle(IV3SwapRouter::ExactOutputSingleParams),
+    ExactOutput(IV3SwapRouter::ExactOutputParams),
+    /// The permit of the input token with its address
+    SelfPermit(Address, PermitOptions),
+    Payments(PaymentsCall),
+}
+
+/// Decodes the calldata of the swap router, e.g. produced by [`swap_call_parameters`] or
+/// [`swap_batch_call_parameters`], into its calls, unwrapping the nested multicalls.
+///
+/// ## Arguments
+///
+/// * `calldata`: The calldata of a call or a multicall of the swap router
+#[inline]
+pub fn decode_swap_call_parameters(
+    calldata: impl AsRef<[u8]>,
+) -> Result<Vec<SwapRouterCall>, alloy_sol_types::Error> {
+    use IV3SwapRouter::IV3SwapRouterCalls as Calls;
+
+    flatten_multicall(calldata)?
+        .iter()
+        .map(|call| {
+            let selector = call
+                .get(..4)
+                .and_then(|selector| selector.try_into().ok())
+                .unwrap_or_default();
+            if Calls::valid_selector(selector) {
+                Ok(match Calls::abi_decode(call, true)? {
+                    Calls::exactInputSingle(call) => SwapRouterCall::ExactInputSingle(call.params),
+                    Calls::exactInput(call) => SwapRouterCall::ExactInput(call.params),
+                    Calls::exactOutputSingle(call) => {
+                        SwapRouterCall::ExactOutputSingle(call.params)
+                    }
+                    Calls::exactOutput(call) => SwapRouterCall::ExactOutput(call.params),
+                })
+            } else if ISelfPermit::ISelfPermitCalls::valid_selector(selector) {
+                let (token, permit) = decode_permit(call)?;
+                Ok(SwapRouterCall::SelfPermit(token, permit))
+            } else {
+                decode_payments_call(call).map(SwapRouterCall::Payments)
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -248,9 +493,37 @@ mod tests {
         recipient: RECIPIENT,
         input_token_permit: None,
         sqrt_price_limit_x96: None,
+        price_limit_tolerance: None,
         fee: None,
+        per_hop_slippage_tolerance: None,
+        max_price_impact: None,
+        chain_id: None,
+        deployment: None,
     });
 
+    #[test]
+    fn test_target() {
+        assert_eq!(SWAP_OPTIONS.target(), None);
+        let options = SwapOptions {
+            chain_id: Some(8453),
+            ..SWAP_OPTIONS.clone()
+        };
+        assert_eq!(options.target(), Some(BASE_ADDRESSES.swap_router02));
+        let options = SwapOptions {
+            chain_id: Some(0),
+            ..SWAP_OPTIONS.clone()
+        };
+        assert_eq!(options.target(), None);
+        // the deployment of a fork takes precedence over the chain
+        let deployment = DeploymentConfig::get(Fork::PancakeSwapV3, 56).unwrap();
+        let options = SwapOptions {
+            chain_id: Some(56),
+            deployment: Some(deployment),
+            ..SWAP_OPTIONS.clone()
+        };
+        assert_eq!(options.target(), Some(deployment.swap_router));
+    }
+
     mod single_trade_input {
         use super::*;
 
@@ -268,6 +541,59 @@ mod tests {
             assert_eq!(value, U256::ZERO);
         }
 
+        #[test]
+        fn max_price_impact() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let options = |max_price_impact| SwapOptions {
+                max_price_impact: Some(max_price_impact),
+                ..SWAP_OPTIONS.clone()
+            };
+            assert!(matches!(
+                swap_call_parameters(&mut [trade.clone()], options(Percent::new(5, 1000))),
+                Err(Error::PriceImpactTooHigh)
+            ));
+            assert_eq!(
+                swap_call_parameters(&mut [trade.clone()], options(Percent::new(5, 100))).unwrap(),
+                swap_call_parameters(&mut [trade], SWAP_OPTIONS.clone()).unwrap()
+            );
+        }
+
+        #[test]
+        fn decode_single_hop_exact_input() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_1_WETH.clone()], TOKEN1.clone(), ETHER.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let MethodParameters { calldata, .. } =
+                swap_call_parameters(&mut [trade], SWAP_OPTIONS.clone()).unwrap();
+            let calls = decode_swap_call_parameters(calldata).unwrap();
+            assert_eq!(calls.len(), 2);
+            let SwapRouterCall::ExactInputSingle(params) = &calls[0] else {
+                panic!("expected exactInputSingle");
+            };
+            assert_eq!(params.tokenIn, TOKEN1.address());
+            assert_eq!(params.tokenOut, WETH.address());
+            assert_eq!(params.amountIn, U256::from(100));
+            assert_eq!(params.amountOutMinimum, U256::from(97));
+            // the router keeps the output to unwrap it
+            assert_eq!(params.recipient, Address::ZERO);
+            assert_eq!(
+                calls[1],
+                SwapRouterCall::Payments(PaymentsCall::UnwrapWETH9 {
+                    amount_minimum: U256::from(97),
+                    recipient: RECIPIENT,
+                    fee_options: None,
+                })
+            );
+        }
+
         #[test]
         fn single_hop_exact_output() {
             let trade = Trade::from_route(
@@ -300,6 +626,61 @@ mod tests {
             assert_eq!(value, U256::ZERO);
         }
 
+        #[test]
+        fn multi_hop_exact_input_with_per_hop_slippage() {
+            let trade = Trade::from_route(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_WETH.clone()],
+                    TOKEN0.clone(),
+                    WETH.clone(),
+                ),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let hops = trade.hop_breakdown().unwrap().remove(0);
+            let minimum_amount_out = trade
+                .minimum_amount_out(SLIPPAGE_TOLERANCE.clone(), None)
+                .unwrap()
+                .quotient();
+            let MethodParameters { calldata, value } = swap_call_parameters(
+                &mut [trade],
+                SwapOptions {
+                    per_hop_slippage_tolerance: Some(Percent::new(5, 1000)),
+                    ..SWAP_OPTIONS.clone()
+                },
+            )
+            .unwrap();
+            assert_eq!(value, U256::ZERO);
+            let calls: Vec<Bytes> = decode_multicall(calldata).unwrap();
+            assert_eq!(calls.len(), 2);
+            let first = IV3SwapRouter::exactInputSingleCall::abi_decode(&calls[0], true)
+                .unwrap()
+                .params;
+            assert_eq!(first.tokenIn, TOKEN0.address());
+            assert_eq!(first.tokenOut, TOKEN1.address());
+            assert_eq!(first.recipient, MSG_SENDER);
+            assert_eq!(first.amountIn, U256::from(100));
+            assert_eq!(
+                first.amountOutMinimum,
+                U256::from_big_int(
+                    hops[0]
+                        .output_amount
+                        .multiply(&Percent::new(1005, 1000).invert())
+                        .unwrap()
+                        .quotient()
+                )
+            );
+            let last = IV3SwapRouter::exactInputSingleCall::abi_decode(&calls[1], true)
+                .unwrap()
+                .params;
+            assert_eq!(last.tokenIn, TOKEN1.address());
+            assert_eq!(last.tokenOut, WETH.address());
+            assert_eq!(last.recipient, RECIPIENT);
+            assert_eq!(last.amountIn, first.amountOutMinimum);
+            assert!(last.amountOutMinimum >= U256::from_big_int(minimum_amount_out));
+        }
+
         #[test]
         fn multi_hop_exact_output() {
             let trade = Trade::from_route(
@@ -394,6 +775,32 @@ mod tests {
             assert_eq!(value, U256::ZERO);
         }
 
+        #[test]
+        fn price_limit_tolerance() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let tolerance = Percent::new(1, 100);
+            let MethodParameters { calldata, .. } = swap_call_parameters(
+                &mut [trade],
+                SwapOptions {
+                    price_limit_tolerance: Some(tolerance.clone()),
+                    ..SWAP_OPTIONS.clone()
+                },
+            )
+            .unwrap();
+            let params = IV3SwapRouter::exactInputSingleCall::abi_decode(&calldata, true)
+                .unwrap()
+                .params;
+            assert_eq!(
+                params.sqrtPriceLimitX96,
+                sqrt_price_limit_from_slippage(POOL_0_1.sqrt_ratio_x96, true, &tolerance)
+            );
+        }
+
         #[test]
         fn fee_with_eth_out() {
             let trade = Trade::from_route(
@@ -1096,4 +1503,103 @@ mod tests {
             assert_eq!(value, U256::ZERO);
         }
     }
+
+    mod batch {
+        use super::*;
+
+        #[test]
+        fn no_trades() {
+            assert!(matches!(
+                swap_batch_call_parameters::<TickListDataProvider>(&mut [], uint!(123_U256)),
+                Err(Error::NoTrades)
+            ));
+        }
+
+        #[test]
+        fn independent_trades() {
+            let trade = |pool: &Pool<TickListDataProvider>,
+                         input: Currency,
+                         output: Currency,
+                         amount: Currency,
+                         trade_type| {
+                Trade::from_route(
+                    Route::new(vec![pool.clone()], input, output),
+                    CurrencyAmount::from_raw_amount(amount, 100).unwrap(),
+                    trade_type,
+                )
+                .unwrap()
+            };
+            let ether = Currency::NativeCurrency(ETHER.clone());
+            let token0 = Currency::Token(TOKEN0.clone());
+            let token1 = Currency::Token(TOKEN1.clone());
+            let mut trades = [
+                (
+                    trade(
+                        &POOL_1_WETH,
+                        ether.clone(),
+                        token1.clone(),
+                        token1.clone(),
+                        TradeType::ExactOutput,
+                    ),
+                    SWAP_OPTIONS.clone(),
+                ),
+                (
+                    trade(
+                        &POOL_1_WETH,
+                        token1.clone(),
+                        ether.clone(),
+                        token1.clone(),
+                        TradeType::ExactInput,
+                    ),
+                    SWAP_OPTIONS.clone(),
+                ),
+                (
+                    trade(
+                        &POOL_0_1,
+                        token0.clone(),
+                        token1,
+                        token0,
+                        TradeType::ExactInput,
+                    ),
+                    SWAP_OPTIONS.clone(),
+                ),
+            ];
+            let MethodParameters { calldata, value } =
+                swap_batch_call_parameters(&mut trades, uint!(123_U256)).unwrap();
+            assert_eq!(value, uint!(0x67_U256));
+            let multicall = IMulticallExtended::multicallCall::abi_decode(&calldata, true).unwrap();
+            assert_eq!(multicall.deadline, uint!(123_U256));
+
+            // the calls of each trade in order, with a single refund at the end
+            let mut expected = Vec::new();
+            for (trade, options) in &mut trades {
+                let MethodParameters { calldata, .. } =
+                    swap_call_parameters(core::slice::from_mut(trade), options.clone()).unwrap();
+                expected.extend(
+                    decode_swap_call_parameters(calldata)
+                        .unwrap()
+                        .into_iter()
+                        .filter(|call| *call != SwapRouterCall::Payments(PaymentsCall::RefundETH)),
+                );
+            }
+            expected.push(SwapRouterCall::Payments(PaymentsCall::RefundETH));
+            let calls = decode_swap_call_parameters(&calldata).unwrap();
+            assert_eq!(calls, expected);
+            assert_eq!(calls.len(), 5);
+            assert!(matches!(calls[0], SwapRouterCall::ExactOutputSingle(_)));
+            assert_eq!(
+                calls[2],
+                SwapRouterCall::Payments(PaymentsCall::UnwrapWETH9 {
+                    amount_minimum: uint!(0x61_U256),
+                    recipient: RECIPIENT,
+                    fee_options: None,
+                })
+            );
+            assert!(matches!(
+                &calls[3],
+                SwapRouterCall::ExactInputSingle(params)
+                    if params.recipient == RECIPIENT && params.amountIn == uint!(100_U256)
+            ));
+        }
+    }
 }
@@ -1,5 +1,5 @@
-use crate::constants::{FeeAmount, POOL_INIT_CODE_HASH};
-use alloy_primitives::{aliases::U24, b256, keccak256, Address, B256};
+use crate::constants::{FeeAmount, POOL_INIT_CODE_HASH, ZKSYNC_POOL_INIT_CODE_HASH};
+use alloy_primitives::{aliases::U24, keccak256, Address, B256};
 use alloy_sol_types::SolValue;
 use uniswap_sdk_core::prelude::{
     compute_zksync_create2_address::compute_zksync_create2_address, ChainId,
@@ -15,6 +15,8 @@ use uniswap_sdk_core::prelude::{
 /// * `fee`: The fee tier of the pool
 /// * `init_code_hash_manual_override`: Override the init code hash used to compute the pool address
 ///   if necessary
+/// * `chain_id`: The optional chain id. On zkSync Era, the address is derived by the zkSync
+///   `CREATE2` scheme with [`ZKSYNC_POOL_INIT_CODE_HASH`] by default
 ///
 /// ## Returns
 ///
@@ -75,9 +77,7 @@ pub fn compute_pool_address(
     match chain_id {
         Some(ZKSYNC_CHAIN_ID) => compute_zksync_create2_address(
             factory,
-            init_code_hash_manual_override.unwrap_or(b256!(
-                "010013f177ea1fcbc4520f9a3ca7cd2d1d77959e05aa66484027cb38e712aeed"
-            )),
+            init_code_hash_manual_override.unwrap_or(ZKSYNC_POOL_INIT_CODE_HASH),
             salt,
             None,
         ),
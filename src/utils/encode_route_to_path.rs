@@ -1,5 +1,5 @@
-use crate::prelude::*;
-use alloy_primitives::{aliases::U24, Bytes};
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::U24, Address, Bytes};
 use alloy_sol_types::SolValue;
 use uniswap_sdk_core::prelude::*;
 
@@ -56,11 +56,130 @@ where
     path.into()
 }
 
+/// The length of an address in a packed path
+const ADDR_SIZE: usize = 20;
+/// The length of a fee in a packed path
+const FEE_SIZE: usize = 3;
+
+/// A decoded path of a swap, i.e. the tokens in the order of the path and the fee tiers of the
+/// pools between them.
+///
+/// The path of an exact output swap is encoded from the output to the input, see
+/// [`Path::reversed`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Path {
+    /// The tokens of the path, one more than the fees
+    pub tokens: Vec<Address>,
+    /// The fee tiers of the pools between consecutive tokens
+    pub fees: Vec<FeeAmount>,
+}
+
+impl Path {
+    /// Decodes a packed path, e.g. the `path` of `exactInput` or `exactOutput`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The packed path
+    #[inline]
+    pub fn decode(path: &[u8]) -> Result<Self, Error> {
+        if path.len() < ADDR_SIZE || (path.len() - ADDR_SIZE) % (ADDR_SIZE + FEE_SIZE) != 0 {
+            return Err(Error::InvalidPath);
+        }
+        let hops = (path.len() - ADDR_SIZE) / (ADDR_SIZE + FEE_SIZE);
+        let mut tokens = Vec::with_capacity(hops + 1);
+        let mut fees = Vec::with_capacity(hops);
+        tokens.push(Address::from_slice(&path[..ADDR_SIZE]));
+        for hop in path[ADDR_SIZE..].chunks_exact(ADDR_SIZE + FEE_SIZE) {
+            let (fee, token) = hop.split_at(FEE_SIZE);
+            fees.push(FeeAmount::from(U24::from_be_slice(fee)));
+            tokens.push(Address::from_slice(token));
+        }
+        Ok(Self { tokens, fees })
+    }
+
+    /// Encodes the path into its packed form
+    #[inline]
+    #[must_use]
+    pub fn encode(&self) -> Bytes {
+        let mut path = Vec::with_capacity(ADDR_SIZE + (ADDR_SIZE + FEE_SIZE) * self.fees.len());
+        for (token, fee) in self.tokens.iter().zip(&self.fees) {
+            path.extend((*token, U24::from(*fee)).abi_encode_packed());
+        }
+        if let Some(token) = self.tokens.last() {
+            path.extend(token.abi_encode_packed());
+        }
+        path.into()
+    }
+
+    /// The number of pools in the path
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.fees.len()
+    }
+
+    /// Whether the path has no pool
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.fees.is_empty()
+    }
+
+    /// The first token of the path, which is the output of an exact output path
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<Address> {
+        self.tokens.first().copied()
+    }
+
+    /// The last token of the path, which is the input of an exact output path
+    #[inline]
+    #[must_use]
+    pub fn last(&self) -> Option<Address> {
+        self.tokens.last().copied()
+    }
+
+    /// Iterates over the hops of the path as `(token_in, fee, token_out)` in the order of the path
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Address, FeeAmount, Address)> + '_ {
+        self.fees
+            .iter()
+            .zip(self.tokens.windows(2))
+            .map(|(fee, tokens)| (tokens[0], *fee, tokens[1]))
+    }
+
+    /// Reverses the path, e.g. to turn the path of an exact output swap into the order of the
+    /// swaps
+    #[inline]
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        Self {
+            tokens: self.tokens.iter().rev().copied().collect(),
+            fees: self.fees.iter().rev().copied().collect(),
+        }
+    }
+}
+
+/// Decodes a packed path into its hops, the inverse of [`encode_route_to_path`].
+///
+/// ## Arguments
+///
+/// * `path`: The packed path
+///
+/// ## Returns
+///
+/// The hops as `(token_in, fee, token_out)` in the order of the path, which is from the output to
+/// the input for an exact output path
+#[inline]
+pub fn decode_path(path: &[u8]) -> Result<Vec<(Address, FeeAmount, Address)>, Error> {
+    Ok(Path::decode(path)?.iter().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::*;
-    use alloy_primitives::hex;
+    use alloy_primitives::{aliases::I24, hex};
     use once_cell::sync::Lazy;
 
     static POOL_1_2_LOW: Lazy<Pool> = Lazy::new(|| {
@@ -111,6 +230,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pack_them_for_custom_tick_spacing() {
+        let pool = Pool::new(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::HIGH,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap()
+        .with_tick_spacing(I24::from_limbs([100]))
+        .unwrap();
+        let route = Route::new(vec![pool], TOKEN0.clone(), TOKEN1.clone());
+        assert_eq!(
+            encode_route_to_path(&route, false).to_vec(),
+            hex!("00000000000000000000000000000000000000010027100000000000000000000000000000000000000002")
+        );
+    }
+
     #[test]
     fn pack_them_for_exact_output_single_hop() {
         assert_eq!(
@@ -191,6 +329,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_multihop() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, false);
+        assert_eq!(
+            decode_path(&path).unwrap(),
+            vec![
+                (TOKEN0.address(), FeeAmount::MEDIUM, TOKEN1.address()),
+                (TOKEN1.address(), FeeAmount::LOW, TOKEN2.address()),
+            ]
+        );
+        let decoded = Path::decode(&path).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded.encode(), path);
+        // the exact output path is the exact input path in reverse
+        let exact_output = Path::decode(&encode_route_to_path(&ROUTE_0_1_2, true)).unwrap();
+        assert_eq!(exact_output.reversed(), decoded);
+        assert_eq!(exact_output.first(), Some(TOKEN2.address()));
+        assert_eq!(exact_output.last(), Some(TOKEN0.address()));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert!(matches!(decode_path(&[]), Err(Error::InvalidPath)));
+        let path = encode_route_to_path(&ROUTE_0_1, false);
+        assert!(matches!(
+            decode_path(&path[..path.len() - 1]),
+            Err(Error::InvalidPath)
+        ));
+    }
+
     #[test]
     fn wrap_ether_output_for_exact_output_multihop() {
         assert_eq!(
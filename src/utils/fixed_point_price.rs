@@ -0,0 +1,305 @@
+//! ## Fixed-point price conversions
+//! Fixed-point variants of the price and token0 ratio conversions in the `extensions` that need no
+//! `BigDecimal`, behind the `fixed_point` feature.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::I24, Uint, U160, U256};
+
+/// Wide enough for the products of the quadratics in the token0 ratio conversions
+type U768 = Uint<768, 12>;
+
+/// Returns the square root of an integer rounded down.
+fn sqrt(value: U768) -> U768 {
+    if value.is_zero() {
+        return value;
+    }
+    // Newton's method from a power of two above the root
+    let mut root = U768::from(1) << value.bit_len().div_ceil(2);
+    loop {
+        let next = (root + value / root) >> 1;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// Given a sqrt ratio, returns the price of token0 denominated in token1 as a Q96 fixed-point
+/// number, rounded down.
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_x96`: The sqrt ratio of token1/token0 as a Q64.96
+#[inline]
+pub fn sqrt_ratio_x96_to_price_x96(sqrt_ratio_x96: U160) -> Result<U256, Error> {
+    let sqrt_ratio_x96 = U256::from(sqrt_ratio_x96);
+    sqrt_ratio_x96.mul_div(sqrt_ratio_x96, Q96)
+}
+
+/// Given a price of token0 denominated in token1 as a Q96 fixed-point number, returns the sqrt
+/// ratio rounded down and clamped to [`MIN_SQRT_RATIO`] and [`MAX_SQRT_RATIO`].
+///
+/// ## Arguments
+///
+/// * `price_x96`: The price ratio of token1/token0 as a Q96
+#[inline]
+#[must_use]
+pub fn price_x96_to_sqrt_ratio_x96(price_x96: U256) -> U160 {
+    let sqrt_ratio_x96 = sqrt(U768::from(price_x96) << 96);
+    if sqrt_ratio_x96 < U768::from(MIN_SQRT_RATIO) {
+        MIN_SQRT_RATIO
+    } else if sqrt_ratio_x96 > U768::from(MAX_SQRT_RATIO) {
+        MAX_SQRT_RATIO
+    } else {
+        sqrt_ratio_x96.to()
+    }
+}
+
+/// For a given tick range and a given proportion of the position value that is held in token0,
+/// calculates the sqrt price of token0 denominated in token1. Fixed-point variant of
+/// `token0_ratio_to_price` in the `extensions`.
+///
+/// ## Arguments
+///
+/// * `token0_ratio_x96`: The proportion of the position value that is held in token0, as a Q96
+///   between 0 and 1, inclusive
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+///
+/// ## Returns
+///
+/// The sqrt price as a Q64.96, rounded down from the exact solution, [`Error::InvalidRange`] if
+/// `tick_upper` is not above `tick_lower`, or [`Error::InvalidToken0Ratio`] if the ratio is above 1
+#[inline]
+pub fn token0_ratio_to_sqrt_price_x96<I: TickIndex>(
+    token0_ratio_x96: U256,
+    tick_lower: I,
+    tick_upper: I,
+) -> Result<U160, Error> {
+    let (tick_lower, tick_upper) = (tick_lower.to_i24(), tick_upper.to_i24());
+    if tick_upper <= tick_lower {
+        return Err(Error::InvalidRange);
+    }
+    if token0_ratio_x96 > Q96 {
+        return Err(Error::InvalidToken0Ratio);
+    }
+    if token0_ratio_x96.is_zero() {
+        return get_sqrt_ratio_at_tick(tick_upper);
+    }
+    if token0_ratio_x96 == Q96 {
+        return get_sqrt_ratio_at_tick(tick_lower);
+    }
+    // With the token0 ratio `r = p / q`, the sqrt price `s` solves
+    // `(q - p) * s^2 + (2 * p - q) * u * s - p * l * u = 0`
+    let p = U768::from(token0_ratio_x96);
+    let q = U768::from(Q96);
+    let l = U768::from(get_sqrt_ratio_at_tick(tick_lower)?);
+    let u = U768::from(get_sqrt_ratio_at_tick(tick_upper)?);
+    let two_p = p << 1;
+    let root = |b: U768| sqrt(b * b + (q - p) * p * l * u * U768::from(4));
+    let numerator = if q >= two_p {
+        let b = (q - two_p) * u;
+        root(b) + b
+    } else {
+        let b = (two_p - q) * u;
+        root(b) - b
+    };
+    Ok((numerator / ((q - p) << 1)).to())
+}
+
+/// Given a sqrt price, calculates the proportion of the position value that is held in token0 for
+/// a given tick range. Fixed-point variant of `token0_price_to_ratio` in the `extensions`.
+///
+/// ## Arguments
+///
+/// * `sqrt_price_x96`: The sqrt price of token1/token0 as a Q64.96
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+///
+/// ## Returns
+///
+/// The proportion of the position value that is held in token0, as a Q96 between 0 and 1,
+/// inclusive, rounded down
+#[inline]
+pub fn token0_sqrt_price_to_ratio_x96<I: TickIndex>(
+    sqrt_price_x96: U160,
+    tick_lower: I,
+    tick_upper: I,
+) -> Result<U256, Error> {
+    let (tick_lower, tick_upper) = (tick_lower.to_i24(), tick_upper.to_i24());
+    if tick_upper <= tick_lower {
+        return Err(Error::InvalidRange);
+    }
+    let s = U768::from(sqrt_price_x96);
+    let l = U768::from(get_sqrt_ratio_at_tick(tick_lower)?);
+    let u = U768::from(get_sqrt_ratio_at_tick(tick_upper)?);
+    // only token0
+    if s <= l {
+        Ok(Q96)
+    }
+    // only token1
+    else if s >= u {
+        Ok(U256::ZERO)
+    } else {
+        // per unit of liquidity, the value of token0 in token1 is `s * (u - s) / u` and the
+        // amount of token1 is `s - l`
+        let value0 = s * (u - s);
+        let value1 = u * (s - l);
+        Ok((value0 * U768::from(Q96) / (value0 + value1)).to())
+    }
+}
+
+/// Returns the tick range for a position ratio and range width. Fixed-point variant of
+/// `tick_range_from_width_and_ratio` in the `extensions`.
+///
+/// ## Arguments
+///
+/// * `width`: The width of the range
+/// * `tick_current`: The current tick of the pool
+/// * `token0_ratio_x96`: The proportion of the position value that is held in token0, as a Q96
+///   between 0 and 1, inclusive
+///
+/// ## Returns
+///
+/// The tick range as a tuple of `(tick_lower, tick_upper)`, [`Error::InvalidRange`] if the width is
+/// not positive, or [`Error::InvalidToken0Ratio`] if the ratio is above 1
+#[inline]
+pub fn tick_range_from_width_and_ratio_x96<I: TickIndex>(
+    width: I,
+    tick_current: I,
+    token0_ratio_x96: U256,
+) -> Result<(I, I), Error> {
+    let (width, tick_current) = (width.to_i24(), tick_current.to_i24());
+    if width <= I24::ZERO {
+        return Err(Error::InvalidRange);
+    }
+    if token0_ratio_x96 > Q96 {
+        return Err(Error::InvalidToken0Ratio);
+    }
+    let (tick_lower, tick_upper) = if token0_ratio_x96.is_zero() {
+        (tick_current - width, tick_current)
+    } else if token0_ratio_x96 == Q96 {
+        (tick_current, tick_current + width)
+    } else {
+        // With the token0 ratio `r = p / q` and the sqrt price `w` at the width, the lower sqrt
+        // price `l` solves `p * w * l^2 + (q - 2 * p) * s * w * l - (q - p) * s^2 = 0`
+        let p = U768::from(token0_ratio_x96);
+        let q = U768::from(Q96);
+        let s = U768::from(get_sqrt_ratio_at_tick(tick_current)?);
+        let w = U768::from(get_sqrt_ratio_at_tick(width)?);
+        let two_p = p << 1;
+        let root = |b: U768| sqrt(b * b + (q - p) * p * w * q * U768::from(4));
+        let numerator = if q >= two_p {
+            let b = (q - two_p) * w;
+            root(b) - b
+        } else {
+            let b = (two_p - q) * w;
+            root(b) + b
+        };
+        let sqrt_ratio_lower_x96: U160 = (s * numerator / ((p * w) << 1)).to();
+        let tick_lower = sqrt_ratio_lower_x96.get_tick_at_sqrt_ratio()?;
+        (tick_lower, tick_lower + width)
+    };
+    Ok((I::from_i24(tick_lower), I::from_i24(tick_upper)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point_price_test() {
+        let sqrt_ratio_x96 = get_sqrt_ratio_at_tick(I24::from_limbs([200000])).unwrap();
+        let price_x96 = sqrt_ratio_x96_to_price_x96(sqrt_ratio_x96).unwrap();
+        assert_eq!(
+            price_x96,
+            U256::from_str_radix("38400329974042030913961448288716162795", 10).unwrap()
+        );
+        // rounded down
+        assert_eq!(
+            price_x96_to_sqrt_ratio_x96(price_x96),
+            sqrt_ratio_x96 - U160::from(1)
+        );
+        assert_eq!(price_x96_to_sqrt_ratio_x96(Q96), Q96.to::<U160>());
+        assert_eq!(price_x96_to_sqrt_ratio_x96(U256::ZERO), MIN_SQRT_RATIO);
+        assert_eq!(price_x96_to_sqrt_ratio_x96(U256::MAX), MAX_SQRT_RATIO);
+    }
+
+    #[test]
+    fn fixed_point_token0_ratio_test() {
+        let tick_lower = I24::from_limbs([253320]);
+        let tick_upper = I24::from_limbs([264600]);
+        let token0_ratio_x96 = Q96 * U256::from(3) / U256::from(10);
+        assert_eq!(
+            token0_ratio_to_sqrt_price_x96(U256::ZERO, tick_lower, tick_upper).unwrap(),
+            get_sqrt_ratio_at_tick(tick_upper).unwrap()
+        );
+        assert_eq!(
+            token0_ratio_to_sqrt_price_x96(Q96, tick_lower, tick_upper).unwrap(),
+            get_sqrt_ratio_at_tick(tick_lower).unwrap()
+        );
+        let sqrt_price_x96 =
+            token0_ratio_to_sqrt_price_x96(token0_ratio_x96, tick_lower, tick_upper).unwrap();
+        assert_eq!(
+            sqrt_price_x96,
+            U160::from_str_radix("37747566700102270108893835008841059", 10).unwrap()
+        );
+        assert_eq!(
+            token0_sqrt_price_to_ratio_x96(sqrt_price_x96, tick_lower, tick_upper).unwrap(),
+            token0_ratio_x96
+        );
+        assert_eq!(
+            token0_sqrt_price_to_ratio_x96(MIN_SQRT_RATIO, tick_lower, tick_upper).unwrap(),
+            Q96
+        );
+        assert_eq!(
+            token0_sqrt_price_to_ratio_x96(MAX_SQRT_RATIO, tick_lower, tick_upper).unwrap(),
+            U256::ZERO
+        );
+        assert!(matches!(
+            token0_sqrt_price_to_ratio_x96(sqrt_price_x96, tick_upper, tick_lower),
+            Err(Error::InvalidRange)
+        ));
+        // the widest range with a ratio above one half
+        let token0_ratio_x96 = Q96 * U256::from(2) / U256::from(3);
+        let sqrt_price_x96 =
+            token0_ratio_to_sqrt_price_x96(token0_ratio_x96, MIN_TICK, MAX_TICK).unwrap();
+        assert_eq!(sqrt_price_x96, U160::from(8590257478_u64));
+        assert_eq!(
+            token0_sqrt_price_to_ratio_x96(sqrt_price_x96, MIN_TICK, MAX_TICK).unwrap(),
+            token0_ratio_x96
+        );
+    }
+
+    #[test]
+    fn tick_range_from_width_and_ratio_x96_test() {
+        let width = I24::from_limbs([1000]);
+        let tick_current = I24::from_limbs([200000]);
+        let range = |token0_ratio_x96| {
+            tick_range_from_width_and_ratio_x96(width, tick_current, token0_ratio_x96).unwrap()
+        };
+        assert_eq!(
+            range(Q96 * U256::from(3) / U256::from(10)),
+            (I24::from_limbs([199297]), I24::from_limbs([200297]))
+        );
+        assert_eq!(
+            range(Q96 * U256::from(7) / U256::from(10)),
+            (I24::from_limbs([199702]), I24::from_limbs([200702]))
+        );
+        assert_eq!(range(U256::ZERO), (tick_current - width, tick_current));
+        assert_eq!(range(Q96), (tick_current, tick_current + width));
+    }
+
+    #[test]
+    fn i32_ticks_test() {
+        assert_eq!(
+            tick_range_from_width_and_ratio_x96(1000, 200000, Q96 * U256::from(3) / U256::from(10))
+                .unwrap(),
+            (199297, 200297)
+        );
+        assert_eq!(
+            token0_sqrt_price_to_ratio_x96(MIN_SQRT_RATIO, 253320, 264600).unwrap(),
+            Q96
+        );
+    }
+}
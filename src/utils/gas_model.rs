@@ -0,0 +1,75 @@
+//! ## Gas Model
+//! Per-chain constants to estimate the gas cost of a swap offchain from the number of hops and
+//! initialized ticks crossed, without a round trip to the quoter.
+
+use alloy_primitives::ChainId;
+
+/// The gas cost model of a swap, i.e.
+/// `base_cost + cost_per_hop * hops + cost_per_initialized_tick * initialized_ticks_crossed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapGasModel {
+    /// The fixed cost of a swap transaction through the router
+    pub base_cost: u64,
+    /// The cost of each pool in the route
+    pub cost_per_hop: u64,
+    /// The cost of each initialized tick crossed
+    pub cost_per_initialized_tick: u64,
+}
+
+impl SwapGasModel {
+    /// Returns the gas model of the chain, falling back to the mainnet costs for unknown chains
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    #[inline]
+    #[must_use]
+    pub const fn for_chain(chain_id: ChainId) -> Self {
+        match chain_id {
+            // Arbitrum One, Arbitrum Nova
+            42161 | 42170 => Self {
+                base_cost: 5000,
+                cost_per_hop: 80000,
+                cost_per_initialized_tick: 31000,
+            },
+            // Celo
+            42220 => Self {
+                base_cost: 2000,
+                cost_per_hop: 80000,
+                cost_per_initialized_tick: 38000,
+            },
+            _ => Self {
+                base_cost: 2000,
+                cost_per_hop: 80000,
+                cost_per_initialized_tick: 31000,
+            },
+        }
+    }
+
+    /// Estimate the gas cost of a swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `hops`: The number of pools in the route
+    /// * `initialized_ticks_crossed`: The number of initialized ticks crossed in all pools
+    #[inline]
+    #[must_use]
+    pub const fn estimate(&self, hops: u64, initialized_ticks_crossed: u64) -> u64 {
+        self.base_cost
+            + self.cost_per_hop * hops
+            + self.cost_per_initialized_tick * initialized_ticks_crossed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        let model = SwapGasModel::for_chain(1);
+        assert_eq!(model.estimate(1, 0), 82000);
+        assert_eq!(model.estimate(2, 3), 255000);
+        assert_eq!(SwapGasModel::for_chain(42161).estimate(1, 1), 116000);
+    }
+}
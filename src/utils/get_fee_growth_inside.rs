@@ -1,11 +1,52 @@
+//! ## Fee Growth
+//! The fee growth math of the pool contract, for computing the fees earned by a position from the
+//! raw storage of a pool, e.g. by an indexer, see [`get_unclaimed_fees`](super::get_unclaimed_fees).
+//!
+//! The fee growth values are allowed to overflow as in the contracts, so their differences wrap
+//! modulo `2^256`, see [`fee_growth_sub`].
+
 use alloy_primitives::Uint;
 
+/// The fee growth per unit of liquidity on the other side of a tick from the current tick, as the
+/// `feeGrowthOutside0X128` and `feeGrowthOutside1X128` of the `ticks` of the pool
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FeeGrowthOutside<const BITS: usize, const LIMBS: usize> {
     pub fee_growth_outside0_x128: Uint<BITS, LIMBS>,
     pub fee_growth_outside1_x128: Uint<BITS, LIMBS>,
 }
 
+/// Subtracts a fee growth from another, wrapping around on underflow as the unchecked arithmetic
+/// of the contracts, since the fee growth values are only meaningful in their differences.
+///
+/// ## Arguments
+///
+/// * `a`: The fee growth to subtract from, e.g. the latest fee growth inside a range
+/// * `b`: The fee growth to subtract, e.g. the fee growth inside a range at the last poke
+#[inline]
+#[must_use]
+pub const fn fee_growth_sub<const BITS: usize, const LIMBS: usize>(
+    a: Uint<BITS, LIMBS>,
+    b: Uint<BITS, LIMBS>,
+) -> Uint<BITS, LIMBS> {
+    a.wrapping_sub(b)
+}
+
+/// Computes the fee growth per unit of liquidity inside a tick range as `Tick.getFeeGrowthInside`
+/// of the pool contract, wrapping around on underflow, see [`fee_growth_sub`].
+///
+/// ## Arguments
+///
+/// * `lower`: The fee growth outside the lower tick
+/// * `upper`: The fee growth outside the upper tick
+/// * `tick_lower`: The lower tick of the range
+/// * `tick_upper`: The upper tick of the range
+/// * `tick_current`: The current tick of the pool
+/// * `fee_growth_global0_x128`: The `feeGrowthGlobal0X128` of the pool
+/// * `fee_growth_global1_x128`: The `feeGrowthGlobal1X128` of the pool
+///
+/// ## Returns
+///
+/// The fee growth inside the range of token0 and token1
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
 pub fn get_fee_growth_inside<const BITS: usize, const LIMBS: usize, T: PartialOrd>(
@@ -20,18 +61,32 @@ pub fn get_fee_growth_inside<const BITS: usize, const LIMBS: usize, T: PartialOr
     let fee_growth_inside0_x128;
     let fee_growth_inside1_x128;
     if tick_current < tick_lower {
-        fee_growth_inside0_x128 = lower.fee_growth_outside0_x128 - upper.fee_growth_outside0_x128;
-        fee_growth_inside1_x128 = lower.fee_growth_outside1_x128 - upper.fee_growth_outside1_x128;
+        fee_growth_inside0_x128 = fee_growth_sub(
+            lower.fee_growth_outside0_x128,
+            upper.fee_growth_outside0_x128,
+        );
+        fee_growth_inside1_x128 = fee_growth_sub(
+            lower.fee_growth_outside1_x128,
+            upper.fee_growth_outside1_x128,
+        );
     } else if tick_current >= tick_upper {
-        fee_growth_inside0_x128 = upper.fee_growth_outside0_x128 - lower.fee_growth_outside0_x128;
-        fee_growth_inside1_x128 = upper.fee_growth_outside1_x128 - lower.fee_growth_outside1_x128;
+        fee_growth_inside0_x128 = fee_growth_sub(
+            upper.fee_growth_outside0_x128,
+            lower.fee_growth_outside0_x128,
+        );
+        fee_growth_inside1_x128 = fee_growth_sub(
+            upper.fee_growth_outside1_x128,
+            lower.fee_growth_outside1_x128,
+        );
     } else {
-        fee_growth_inside0_x128 = fee_growth_global0_x128
-            - lower.fee_growth_outside0_x128
-            - upper.fee_growth_outside0_x128;
-        fee_growth_inside1_x128 = fee_growth_global1_x128
-            - lower.fee_growth_outside1_x128
-            - upper.fee_growth_outside1_x128;
+        fee_growth_inside0_x128 = fee_growth_sub(
+            fee_growth_sub(fee_growth_global0_x128, lower.fee_growth_outside0_x128),
+            upper.fee_growth_outside0_x128,
+        );
+        fee_growth_inside1_x128 = fee_growth_sub(
+            fee_growth_sub(fee_growth_global1_x128, lower.fee_growth_outside1_x128),
+            upper.fee_growth_outside1_x128,
+        );
     }
     (fee_growth_inside0_x128, fee_growth_inside1_x128)
 }
@@ -85,4 +140,18 @@ mod tests {
         assert_eq!(fee_growth_inside0_x128, q127);
         assert_eq!(fee_growth_inside1_x128, q127);
     }
+
+    #[test]
+    fn test_wrapping() {
+        // the fee growth outside the lower tick exceeds the global fee growth after an overflow
+        let lower = FeeGrowthOutside {
+            fee_growth_outside0_x128: U256::MAX - Q128 + U256::from(1),
+            fee_growth_outside1_x128: U256::ZERO,
+        };
+        let (fee_growth_inside0_x128, fee_growth_inside1_x128) =
+            get_fee_growth_inside(lower, FeeGrowthOutside::default(), -1, 1, 0, Q128, Q128);
+        assert_eq!(fee_growth_inside0_x128, Q128 << 1);
+        assert_eq!(fee_growth_inside1_x128, Q128);
+        assert_eq!(fee_growth_sub(U256::ZERO, U256::from(1)), U256::MAX);
+    }
 }
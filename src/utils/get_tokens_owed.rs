@@ -1,5 +1,5 @@
-use super::Q128;
-use alloy_primitives::Uint;
+use super::{fee_growth_sub, get_fee_growth_inside, mul_div, FeeGrowthOutside, Q128};
+use alloy_primitives::{Uint, U256};
 
 /// Computes the amount of fees owed to a position
 #[inline]
@@ -14,16 +14,77 @@ pub fn get_tokens_owed<const BITS: usize, const LIMBS: usize>(
     let liquidity = Uint::from(liquidity);
     let q128 = Uint::from(Q128);
     let tokens_owed_0 =
-        (fee_growth_inside_0_x128 - fee_growth_inside_0_last_x128) * liquidity / q128;
+        fee_growth_sub(fee_growth_inside_0_x128, fee_growth_inside_0_last_x128) * liquidity / q128;
     let tokens_owed_1 =
-        (fee_growth_inside_1_x128 - fee_growth_inside_1_last_x128) * liquidity / q128;
+        fee_growth_sub(fee_growth_inside_1_x128, fee_growth_inside_1_last_x128) * liquidity / q128;
+    (tokens_owed_0, tokens_owed_1)
+}
+
+/// Computes the fees accrued to a position since it was last poked from the fee growth values of
+/// the pool, e.g. ingested from storage by an indexer, without simulating a `collect` call
+///
+/// ## Arguments
+///
+/// * `liquidity`: The liquidity of the position
+/// * `fee_growth_inside_0_last_x128`: The `feeGrowthInside0LastX128` of the position
+/// * `fee_growth_inside_1_last_x128`: The `feeGrowthInside1LastX128` of the position
+/// * `lower`: The fee growth outside the lower tick of the position
+/// * `upper`: The fee growth outside the upper tick of the position
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `tick_current`: The current tick of the pool
+/// * `fee_growth_global0_x128`: The `feeGrowthGlobal0X128` of the pool
+/// * `fee_growth_global1_x128`: The `feeGrowthGlobal1X128` of the pool
+///
+/// ## Returns
+///
+/// The accrued fees of token0 and token1, to be added to the `tokensOwed0` and `tokensOwed1` of
+/// the position to get the collectable amounts
+#[inline]
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn get_unclaimed_fees<T: PartialOrd>(
+    liquidity: u128,
+    fee_growth_inside_0_last_x128: U256,
+    fee_growth_inside_1_last_x128: U256,
+    lower: FeeGrowthOutside<256, 4>,
+    upper: FeeGrowthOutside<256, 4>,
+    tick_lower: T,
+    tick_upper: T,
+    tick_current: T,
+    fee_growth_global0_x128: U256,
+    fee_growth_global1_x128: U256,
+) -> (U256, U256) {
+    let (fee_growth_inside_0_x128, fee_growth_inside_1_x128) = get_fee_growth_inside(
+        lower,
+        upper,
+        tick_lower,
+        tick_upper,
+        tick_current,
+        fee_growth_global0_x128,
+        fee_growth_global1_x128,
+    );
+    let liquidity = U256::from(liquidity);
+    // the fee growth is allowed to overflow, and the result of `mulDiv` always fits in 256 bits
+    // since the liquidity is below 2^128
+    let tokens_owed_0 = mul_div(
+        fee_growth_sub(fee_growth_inside_0_x128, fee_growth_inside_0_last_x128),
+        liquidity,
+        Q128,
+    )
+    .unwrap();
+    let tokens_owed_1 = mul_div(
+        fee_growth_sub(fee_growth_inside_1_x128, fee_growth_inside_1_last_x128),
+        liquidity,
+        Q128,
+    )
+    .unwrap();
     (tokens_owed_0, tokens_owed_1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::U256;
 
     #[test]
     fn test_get_tokens_owed() {
@@ -31,4 +92,68 @@ mod tests {
         assert_eq!(tokens_owed_0, U256::from(1));
         assert_eq!(tokens_owed_1, U256::from(1));
     }
+
+    #[test]
+    fn test_get_unclaimed_fees_in_range() {
+        let (fees0, fees1) = get_unclaimed_fees(
+            2,
+            Q128,
+            U256::ZERO,
+            FeeGrowthOutside::default(),
+            FeeGrowthOutside::default(),
+            -1,
+            1,
+            0,
+            Q128 * U256::from(3),
+            Q128,
+        );
+        assert_eq!(fees0, U256::from(4));
+        assert_eq!(fees1, U256::from(2));
+    }
+
+    #[test]
+    fn test_get_unclaimed_fees_out_of_range() {
+        let lower = FeeGrowthOutside {
+            fee_growth_outside0_x128: Q128 * U256::from(3),
+            fee_growth_outside1_x128: Q128 * U256::from(3),
+        };
+        let upper = FeeGrowthOutside {
+            fee_growth_outside0_x128: Q128,
+            fee_growth_outside1_x128: Q128,
+        };
+        // below the range the fee growth inside only depends on the fee growth outside the ticks
+        let (fees0, fees1) = get_unclaimed_fees(
+            1,
+            U256::ZERO,
+            U256::ZERO,
+            lower,
+            upper,
+            -1,
+            1,
+            -2,
+            Q128 * U256::from(5),
+            Q128 * U256::from(5),
+        );
+        assert_eq!(fees0, U256::from(2));
+        assert_eq!(fees1, U256::from(2));
+    }
+
+    #[test]
+    fn test_get_unclaimed_fees_overflow() {
+        // the fee growth inside wraps around below the last value
+        let (fees0, fees1) = get_unclaimed_fees(
+            u128::MAX,
+            U256::MAX - Q128 + U256::from(1),
+            U256::ZERO,
+            FeeGrowthOutside::default(),
+            FeeGrowthOutside::default(),
+            -1,
+            1,
+            0,
+            U256::ZERO,
+            U256::ZERO,
+        );
+        assert_eq!(fees0, U256::from(u128::MAX));
+        assert_eq!(fees1, U256::ZERO);
+    }
 }
@@ -1,6 +1,9 @@
-use super::ToBig;
+use super::{get_amount_0_delta, get_amount_1_delta, ToBig};
+use crate::error::Error;
 use alloy_primitives::{Uint, U256};
 use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::Zero;
 
 #[inline]
 fn sort_to_big_uint<const BITS: usize, const LIMBS: usize>(
@@ -14,12 +17,24 @@ fn sort_to_big_uint<const BITS: usize, const LIMBS: usize>(
     }
 }
 
+/// Divides rounding up or down
+#[inline]
+fn div_rounding(numerator: BigUint, denominator: &BigUint, round_up: bool) -> BigUint {
+    let (quotient, remainder) = numerator.div_rem(denominator);
+    if round_up && !remainder.is_zero() {
+        quotient + 1_u32
+    } else {
+        quotient
+    }
+}
+
 /// Returns an imprecise maximum amount of liquidity received for a given amount of token 0.
 ///
 /// This function is available to accommodate LiquidityAmounts#getLiquidityForAmount0 in the v3
 /// periphery, which could be more precise by at least 32 bits by dividing by Q64 instead of Q96 in
 /// the intermediate step, and shifting the subtracted ratio left by 32 bits. This imprecise
-/// calculation will likely be replaced in a future v3 router contract.
+/// calculation will likely be replaced in a future v3 router contract. It matches the liquidity
+/// minted by the nonfungible position manager, rounded down.
 ///
 /// ## Arguments
 ///
@@ -59,15 +74,11 @@ pub fn max_liquidity_for_amount0_precise<const BITS: usize, const LIMBS: usize>(
     sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
     amount0: U256,
 ) -> BigUint {
-    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = sort_to_big_uint(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
-
-    let numerator = amount0.to_big_uint() * &sqrt_ratio_a_x96 * &sqrt_ratio_b_x96;
-    let denominator = (sqrt_ratio_b_x96 - sqrt_ratio_a_x96) << 96;
-
-    numerator / denominator
+    liquidity_for_amount0(sqrt_ratio_a_x96, sqrt_ratio_b_x96, amount0, false)
 }
 
-/// Computes the maximum amount of liquidity received for a given amount of token1
+/// Computes the maximum amount of liquidity received for a given amount of token1, which matches
+/// LiquidityAmounts#getLiquidityForAmount1 in the v3 periphery, rounded down
 ///
 /// ## Arguments
 ///
@@ -83,9 +94,7 @@ pub fn max_liquidity_for_amount1<const BITS: usize, const LIMBS: usize>(
     sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
     amount1: U256,
 ) -> BigUint {
-    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = sort_to_big_uint(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
-
-    (amount1.to_big_uint() << 96) / (sqrt_ratio_b_x96 - sqrt_ratio_a_x96)
+    liquidity_for_amount1(sqrt_ratio_a_x96, sqrt_ratio_b_x96, amount1, false)
 }
 
 /// Computes the maximum amount of liquidity received for a given amount of token0, token1,
@@ -99,9 +108,10 @@ pub fn max_liquidity_for_amount1<const BITS: usize, const LIMBS: usize>(
 /// * `amount0`: The token0 amount
 /// * `amount1`: The token1 amount
 /// * `use_full_precision`: if false, liquidity will be maximized according to what the router can
-///   calculate, not what core can theoretically support
+///   calculate, not what core can theoretically support, which matches the liquidity minted by the
+///   nonfungible position manager
 ///
-/// returns: maximum liquidity for the given amounts
+/// returns: maximum liquidity for the given amounts, rounded down
 #[inline]
 #[must_use]
 pub fn max_liquidity_for_amounts<const BITS: usize, const LIMBS: usize>(
@@ -141,6 +151,140 @@ pub fn max_liquidity_for_amounts<const BITS: usize, const LIMBS: usize>(
     }
 }
 
+/// Computes the exact amount of liquidity for a given amount of token0 with an explicit rounding
+/// direction.
+///
+/// Rounding down gives the maximum liquidity that the amount can pay for, as minted by the pool
+/// when the liquidity is computed off-chain, while rounding up gives the minimum liquidity whose
+/// withdrawal yields at least the amount.
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_a_x96`: The price at the lower boundary
+/// * `sqrt_ratio_b_x96`: The price at the upper boundary
+/// * `amount0`: The token0 amount
+/// * `round_up`: Whether to round the liquidity up or down
+///
+/// returns: liquidity for amount0
+#[inline]
+#[must_use]
+pub fn liquidity_for_amount0<const BITS: usize, const LIMBS: usize>(
+    sqrt_ratio_a_x96: Uint<BITS, LIMBS>,
+    sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
+    amount0: U256,
+    round_up: bool,
+) -> BigUint {
+    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = sort_to_big_uint(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+
+    let numerator = amount0.to_big_uint() * &sqrt_ratio_a_x96 * &sqrt_ratio_b_x96;
+    let denominator = (sqrt_ratio_b_x96 - sqrt_ratio_a_x96) << 96;
+
+    div_rounding(numerator, &denominator, round_up)
+}
+
+/// Computes the exact amount of liquidity for a given amount of token1 with an explicit rounding
+/// direction, see [`liquidity_for_amount0`].
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_a_x96`: The price at the lower boundary
+/// * `sqrt_ratio_b_x96`: The price at the upper boundary
+/// * `amount1`: The token1 amount
+/// * `round_up`: Whether to round the liquidity up or down
+///
+/// returns: liquidity for amount1
+#[inline]
+#[must_use]
+pub fn liquidity_for_amount1<const BITS: usize, const LIMBS: usize>(
+    sqrt_ratio_a_x96: Uint<BITS, LIMBS>,
+    sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
+    amount1: U256,
+    round_up: bool,
+) -> BigUint {
+    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = sort_to_big_uint(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+
+    div_rounding(
+        amount1.to_big_uint() << 96,
+        &(sqrt_ratio_b_x96 - sqrt_ratio_a_x96),
+        round_up,
+    )
+}
+
+/// Computes the exact amount of liquidity for a given amount of token0, token1, and the prices at
+/// the tick boundaries with an explicit rounding direction, the lesser of the liquidity for each
+/// amount within the range.
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_current_x96`: The current price
+/// * `sqrt_ratio_a_x96`: The price at the lower boundary
+/// * `sqrt_ratio_b_x96`: The price at the upper boundary
+/// * `amount0`: The token0 amount
+/// * `amount1`: The token1 amount
+/// * `round_up`: Whether to round the liquidity up or down
+///
+/// returns: liquidity for the given amounts
+#[inline]
+#[must_use]
+pub fn liquidity_for_amounts<const BITS: usize, const LIMBS: usize>(
+    sqrt_ratio_current_x96: Uint<BITS, LIMBS>,
+    mut sqrt_ratio_a_x96: Uint<BITS, LIMBS>,
+    mut sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
+    amount0: U256,
+    amount1: U256,
+    round_up: bool,
+) -> BigUint {
+    if sqrt_ratio_a_x96 > sqrt_ratio_b_x96 {
+        (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = (sqrt_ratio_b_x96, sqrt_ratio_a_x96);
+    }
+
+    if sqrt_ratio_current_x96 <= sqrt_ratio_a_x96 {
+        liquidity_for_amount0(sqrt_ratio_a_x96, sqrt_ratio_b_x96, amount0, round_up)
+    } else if sqrt_ratio_current_x96 < sqrt_ratio_b_x96 {
+        let liquidity0 =
+            liquidity_for_amount0(sqrt_ratio_current_x96, sqrt_ratio_b_x96, amount0, round_up);
+        let liquidity1 =
+            liquidity_for_amount1(sqrt_ratio_a_x96, sqrt_ratio_current_x96, amount1, round_up);
+        liquidity0.min(liquidity1)
+    } else {
+        liquidity_for_amount1(sqrt_ratio_a_x96, sqrt_ratio_b_x96, amount1, round_up)
+    }
+}
+
+/// Computes the token amounts of a given amount of liquidity at the current price and the prices
+/// at the tick boundaries with an explicit rounding direction.
+///
+/// Rounding up matches the amounts the pool pulls on mint, and rounding down matches the amounts
+/// it owes on burn, so the minimum amounts of a mint must be computed rounding up and those of a
+/// burn rounding down to avoid reverting by one wei.
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_current_x96`: The current price
+/// * `sqrt_ratio_a_x96`: The price at the lower boundary
+/// * `sqrt_ratio_b_x96`: The price at the upper boundary
+/// * `liquidity`: The liquidity
+/// * `round_up`: Whether to round the amounts up or down
+///
+/// returns: the amounts of token0 and token1
+#[inline]
+pub fn amounts_for_liquidity<const BITS: usize, const LIMBS: usize>(
+    sqrt_ratio_current_x96: Uint<BITS, LIMBS>,
+    mut sqrt_ratio_a_x96: Uint<BITS, LIMBS>,
+    mut sqrt_ratio_b_x96: Uint<BITS, LIMBS>,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(U256, U256), Error> {
+    if sqrt_ratio_a_x96 > sqrt_ratio_b_x96 {
+        (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = (sqrt_ratio_b_x96, sqrt_ratio_a_x96);
+    }
+    let sqrt_ratio_x96 = sqrt_ratio_current_x96.clamp(sqrt_ratio_a_x96, sqrt_ratio_b_x96);
+    Ok((
+        get_amount_0_delta(sqrt_ratio_x96, sqrt_ratio_b_x96, liquidity, round_up)?,
+        get_amount_1_delta(sqrt_ratio_a_x96, sqrt_ratio_x96, liquidity, round_up)?,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +574,52 @@ mod tests {
             2097_u64.into()
         );
     }
+
+    #[test]
+    fn liquidity_for_amounts_rounding() {
+        let (current, a, b): (U256, U256, U256) = (
+            encode_sqrt_ratio_x96(1, 1),
+            encode_sqrt_ratio_x96(100, 110),
+            encode_sqrt_ratio_x96(110, 100),
+        );
+        let (amount0, amount1) = (U256::from(100), U256::from(200));
+        let down = liquidity_for_amounts(current, a, b, amount0, amount1, false);
+        let up = liquidity_for_amounts(current, a, b, amount0, amount1, true);
+        assert_eq!(
+            down,
+            max_liquidity_for_amounts(current, a, b, amount0, amount1, true)
+        );
+        assert_eq!(up, &down + 1_u32);
+        // exact divisions are not rounded up
+        let liquidity = liquidity_for_amount1(a, b, U256::from(1) << 96, false);
+        assert_eq!(
+            liquidity_for_amount1(a, b, U256::from(1) << 96, true),
+            liquidity
+        );
+    }
+
+    #[test]
+    fn amounts_for_liquidity_rounding() {
+        let (current, a, b): (U256, U256, U256) = (
+            encode_sqrt_ratio_x96(1, 1),
+            encode_sqrt_ratio_x96(100, 110),
+            encode_sqrt_ratio_x96(110, 100),
+        );
+        let (amount0, amount1) = (U256::from(100), U256::from(200));
+        let liquidity = liquidity_for_amounts(current, a, b, amount0, amount1, false)
+            .try_into()
+            .unwrap();
+        let (mint0, mint1) = amounts_for_liquidity(current, a, b, liquidity, true).unwrap();
+        let (burn0, burn1) = amounts_for_liquidity(current, a, b, liquidity, false).unwrap();
+        // the liquidity rounded down is paid for by the amounts
+        assert!(mint0 <= amount0 && mint1 <= amount1);
+        assert!(burn0 <= mint0 && mint0 - burn0 <= U256::from(1));
+        assert!(burn1 <= mint1 && mint1 - burn1 <= U256::from(1));
+        // the range holds only token0 below it and only token1 above it
+        let (amount0, amount1) =
+            amounts_for_liquidity(a - U256::from(1), a, b, liquidity, true).unwrap();
+        assert!(!amount0.is_zero() && amount1.is_zero());
+        let (amount0, amount1) = amounts_for_liquidity(b, a, b, liquidity, true).unwrap();
+        assert!(amount0.is_zero() && !amount1.is_zero());
+    }
 }
@@ -2,34 +2,50 @@ pub mod bit_math;
 pub mod compute_pool_address;
 pub mod encode_route_to_path;
 pub mod encode_sqrt_ratio_x96;
+#[cfg(feature = "fixed_point")]
+pub mod fixed_point_price;
 pub mod full_math;
+pub mod gas_model;
 pub mod get_fee_growth_inside;
 pub mod get_tokens_owed;
 pub mod liquidity_math;
 pub mod max_liquidity_for_amounts;
 pub mod nearest_usable_tick;
 pub mod price_tick_conversions;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+pub mod split;
+pub mod sqrt_price_limit;
 pub mod sqrt_price_math;
 pub mod swap_math;
 pub mod tick_list;
 pub mod tick_math;
+pub mod tick_math_f64;
 mod types;
 
 pub use bit_math::*;
 pub use compute_pool_address::compute_pool_address;
-pub use encode_route_to_path::encode_route_to_path;
+pub use encode_route_to_path::{decode_path, encode_route_to_path, Path};
 pub use encode_sqrt_ratio_x96::encode_sqrt_ratio_x96;
+#[cfg(feature = "fixed_point")]
+pub use fixed_point_price::*;
 pub use full_math::*;
+pub use gas_model::SwapGasModel;
 pub use get_fee_growth_inside::*;
-pub use get_tokens_owed::get_tokens_owed;
+pub use get_tokens_owed::{get_tokens_owed, get_unclaimed_fees};
 pub use liquidity_math::add_delta;
 pub use max_liquidity_for_amounts::*;
 pub use nearest_usable_tick::nearest_usable_tick;
 pub use price_tick_conversions::*;
+#[cfg(feature = "serde")]
+pub use serde_helpers::{CurrencyRepr, SerdeCurrency};
+pub use split::*;
+pub use sqrt_price_limit::sqrt_price_limit_from_slippage;
 pub use sqrt_price_math::*;
 pub use swap_math::*;
 pub use tick_list::TickList;
 pub use tick_math::*;
+pub use tick_math_f64::{get_sqrt_ratio_at_tick_f64, price_to_tick_f64, tick_to_price_f64};
 pub use types::*;
 
 use alloy_primitives::{uint, Bytes, U160, U256};
@@ -1,7 +1,11 @@
 //! ## Price and tick conversions
-//! Utility functions for converting between [`I24`] ticks and SDK Core [`Price`] prices.
+//! Utility functions for converting between ticks and SDK Core [`Price`] prices.
 
 use crate::prelude::{Error, *};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use alloy_primitives::{aliases::I24, U160};
 use uniswap_sdk_core::prelude::*;
 
@@ -15,12 +19,12 @@ use uniswap_sdk_core::prelude::*;
 /// * `quote_token`: the quote token of the price
 /// * `tick`: the tick for which to return the price
 #[inline]
-pub fn tick_to_price(
+pub fn tick_to_price<I: TickIndex>(
     base_token: Token,
     quote_token: Token,
-    tick: I24,
+    tick: I,
 ) -> Result<Price<Token, Token>, Error> {
-    let sqrt_ratio_x96 = get_sqrt_ratio_at_tick(tick)?;
+    let sqrt_ratio_x96 = get_sqrt_ratio_at_tick(tick.to_i24())?;
     let ratio_x192 = sqrt_ratio_x96.to_big_uint().pow(2);
     let q192 = Q192.to_big_uint();
     Ok(if base_token.sorts_before(&quote_token)? {
@@ -64,6 +68,85 @@ pub fn price_to_closest_tick(price: &Price<Token, Token>) -> Result<I24, Error>
     })
 }
 
+/// The smallest price displayed in decimal notation by [`format_price`]
+const MIN_DECIMAL_EXPONENT: i32 = -6;
+/// The smallest power of ten displayed in scientific notation by [`format_price`]
+const MAX_DECIMAL_EXPONENT: i32 = 15;
+
+/// Formats a price for display, adjusted for the decimals of its currencies, in decimal notation
+/// with significant digits, or in scientific notation like `1.23e-9` if it is tiny or huge.
+///
+/// ## Arguments
+///
+/// * `price`: The price to format
+/// * `significant_digits`: The number of significant digits
+/// * `rounding`: How to round the last significant digit
+#[inline]
+pub fn format_price<TBase: BaseCurrency, TQuote: BaseCurrency>(
+    price: &Price<TBase, TQuote>,
+    significant_digits: u8,
+    rounding: Rounding,
+) -> Result<String, Error> {
+    let price = price.adjusted_for_decimals();
+    if price.numerator == BigInt::ZERO {
+        return Ok("0".to_string());
+    }
+    // the decimal exponent of the price, i.e. the floor of its base 10 logarithm
+    let ten = BigInt::from(10);
+    let digits = |n: &BigInt| n.to_string().trim_start_matches('-').len() as i32;
+    let mut exponent = digits(&price.numerator) - digits(&price.denominator);
+    let scaled = |exponent: i32| {
+        if exponent >= 0 {
+            Fraction::new(
+                price.numerator.clone(),
+                &price.denominator * ten.pow(exponent.unsigned_abs()),
+            )
+        } else {
+            Fraction::new(
+                &price.numerator * ten.pow(exponent.unsigned_abs()),
+                price.denominator.clone(),
+            )
+        }
+    };
+    if scaled(exponent).numerator < scaled(exponent).denominator {
+        exponent -= 1;
+    }
+    if (MIN_DECIMAL_EXPONENT..MAX_DECIMAL_EXPONENT).contains(&exponent) {
+        return Ok(price.to_significant(significant_digits, Some(rounding))?);
+    }
+    let mut mantissa = scaled(exponent).to_significant(significant_digits, Some(rounding))?;
+    // rounding may carry the mantissa over to the next power of ten
+    if mantissa == "10" {
+        mantissa = "1".to_string();
+        exponent += 1;
+    }
+    Ok(format!("{mantissa}e{exponent}"))
+}
+
+/// Formats the price of a tick for display, see [`tick_to_price`] and [`format_price`].
+///
+/// ## Arguments
+///
+/// * `tick`: The tick for which to format the price
+/// * `base_token`: The base token of the price
+/// * `quote_token`: The quote token of the price
+/// * `significant_digits`: The number of significant digits
+/// * `rounding`: How to round the last significant digit
+#[inline]
+pub fn tick_to_price_string<I: TickIndex>(
+    tick: I,
+    base_token: Token,
+    quote_token: Token,
+    significant_digits: u8,
+    rounding: Rounding,
+) -> Result<String, Error> {
+    format_price(
+        &tick_to_price(base_token, quote_token, tick)?,
+        significant_digits,
+        rounding,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,4 +434,110 @@ mod tests {
             -I24::from_limbs([276225])
         );
     }
+
+    #[test]
+    fn format_price_test() {
+        let price = |numerator: u64, denominator: u64| {
+            Price::new(TOKEN0.clone(), TOKEN1.clone(), denominator, numerator)
+        };
+        assert_eq!(
+            format_price(&price(18001, 10), 5, Rounding::RoundHalfUp).unwrap(),
+            "1800.1"
+        );
+        assert_eq!(
+            format_price(&price(0, 1), 5, Rounding::RoundHalfUp).unwrap(),
+            "0"
+        );
+        assert_eq!(
+            format_price(&price(123456, 100_000_000_000), 3, Rounding::RoundHalfUp).unwrap(),
+            "0.00000123"
+        );
+        assert_eq!(
+            format_price(&price(123456, 1_000_000_000_000), 3, Rounding::RoundHalfUp).unwrap(),
+            "1.23e-7"
+        );
+        assert_eq!(
+            format_price(&price(123456, 1_000_000_000_000), 3, Rounding::RoundUp).unwrap(),
+            "1.24e-7"
+        );
+        assert_eq!(
+            format_price(&price(99999, 10_000_000_000_000), 3, Rounding::RoundHalfUp).unwrap(),
+            "1e-8"
+        );
+        assert_eq!(
+            format_price(&price(123_000_000_000_000_000, 1), 3, Rounding::RoundHalfUp).unwrap(),
+            "1.23e17"
+        );
+        // the decimals of the tokens are adjusted for
+        assert_eq!(
+            format_price(
+                &Price::new(
+                    TOKEN2_6DECIMALS.clone(),
+                    TOKEN1.clone(),
+                    1,
+                    1_000_000_000_000_u64
+                ),
+                5,
+                Rounding::RoundHalfUp
+            )
+            .unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn tick_to_price_string_test() {
+        assert_eq!(
+            tick_to_price_string(
+                -I24::from_limbs([74959]),
+                TOKEN1.clone(),
+                TOKEN0.clone(),
+                5,
+                Rounding::RoundHalfUp
+            )
+            .unwrap(),
+            "1800"
+        );
+        assert_eq!(
+            tick_to_price_string(
+                MIN_TICK,
+                TOKEN0.clone(),
+                TOKEN1.clone(),
+                3,
+                Rounding::RoundHalfUp
+            )
+            .unwrap(),
+            "2.94e-39"
+        );
+    }
+
+    #[test]
+    fn i32_ticks_test() {
+        assert_eq!(
+            tick_to_price(TOKEN1.clone(), TOKEN0.clone(), -74959).unwrap(),
+            tick_to_price(TOKEN1.clone(), TOKEN0.clone(), -I24::from_limbs([74959])).unwrap()
+        );
+        assert_eq!(
+            tick_to_price_string(
+                -74959,
+                TOKEN1.clone(),
+                TOKEN0.clone(),
+                5,
+                Rounding::RoundHalfUp
+            )
+            .unwrap(),
+            tick_to_price_string(
+                -I24::from_limbs([74959]),
+                TOKEN1.clone(),
+                TOKEN0.clone(),
+                5,
+                Rounding::RoundHalfUp
+            )
+            .unwrap()
+        );
+        assert!(matches!(
+            tick_to_price(TOKEN1.clone(), TOKEN0.clone(), MAX_TICK_I32 + 1),
+            Err(Error::InvalidTick(_))
+        ));
+    }
 }
@@ -0,0 +1,231 @@
+//! ## Serde Helpers
+//! Serialization of the `uniswap-sdk-core` types embedded in the entities, such as [`Token`] and
+//! [`CurrencyAmount`], which do not implement [`serde`] themselves. Used through the
+//! `#[serde(with = "...")]` attributes of the entities when the `serde` feature is enabled.
+
+use alloc::string::{String, ToString};
+use alloy_primitives::{Address, ChainId};
+use core::str::FromStr;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use uniswap_sdk_core::prelude::*;
+
+/// The serialized form of a currency
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrencyRepr {
+    pub chain_id: ChainId,
+    /// The address of the token, or `None` for the native currency
+    pub address: Option<Address>,
+    pub decimals: u8,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_fee_bps: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_fee_bps: Option<String>,
+}
+
+impl CurrencyRepr {
+    #[inline]
+    #[must_use]
+    pub fn new(currency: &impl BaseCurrency) -> Self {
+        let (address, buy_fee_bps, sell_fee_bps) = if currency.is_native() {
+            (None, None, None)
+        } else {
+            let token = currency.wrapped();
+            (
+                Some(token.address()),
+                token.buy_fee_bps.as_ref().map(ToString::to_string),
+                token.sell_fee_bps.as_ref().map(ToString::to_string),
+            )
+        };
+        Self {
+            chain_id: currency.chain_id(),
+            address,
+            decimals: currency.decimals(),
+            symbol: currency.symbol().map(ToString::to_string),
+            name: currency.name().map(ToString::to_string),
+            buy_fee_bps,
+            sell_fee_bps,
+        }
+    }
+
+    fn token(self) -> Result<Token, String> {
+        let Some(address) = self.address else {
+            return Err("expected a token, found the native currency".to_string());
+        };
+        let parse_bps = |bps: Option<String>| {
+            bps.map(|bps| BigUint::from_str(&bps).map_err(|e| e.to_string()))
+                .transpose()
+        };
+        Ok(Token::new(
+            self.chain_id,
+            address,
+            self.decimals,
+            self.symbol,
+            self.name,
+            parse_bps(self.buy_fee_bps)?,
+            parse_bps(self.sell_fee_bps)?,
+        ))
+    }
+}
+
+/// A currency that can be reconstructed from its serialized form
+pub trait SerdeCurrency: BaseCurrency + Sized {
+    /// Reconstruct the currency from its serialized form
+    fn from_repr(repr: CurrencyRepr) -> Result<Self, String>;
+}
+
+impl SerdeCurrency for Token {
+    #[inline]
+    fn from_repr(repr: CurrencyRepr) -> Result<Self, String> {
+        repr.token()
+    }
+}
+
+impl SerdeCurrency for Ether {
+    #[inline]
+    fn from_repr(repr: CurrencyRepr) -> Result<Self, String> {
+        if repr.address.is_some() {
+            return Err("expected the native currency, found a token".to_string());
+        }
+        Ok(Self::on_chain(repr.chain_id))
+    }
+}
+
+impl SerdeCurrency for Currency {
+    #[inline]
+    fn from_repr(repr: CurrencyRepr) -> Result<Self, String> {
+        Ok(match repr.address {
+            Some(_) => Self::Token(repr.token()?),
+            None => Self::NativeCurrency(Ether::on_chain(repr.chain_id)),
+        })
+    }
+}
+
+/// (De)serialize a currency through [`CurrencyRepr`]
+pub mod currency {
+    use super::*;
+
+    #[inline]
+    pub fn serialize<C, S>(currency: &C, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: BaseCurrency,
+        S: Serializer,
+    {
+        CurrencyRepr::new(currency).serialize(serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+    where
+        C: SerdeCurrency,
+        D: Deserializer<'de>,
+    {
+        C::from_repr(CurrencyRepr::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// (De)serialize a token through [`CurrencyRepr`]
+pub mod token {
+    use super::*;
+
+    #[inline]
+    pub fn serialize<S: Serializer>(token: &Token, serializer: S) -> Result<S::Ok, S::Error> {
+        currency::serialize(token, serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Token, D::Error> {
+        currency::deserialize(deserializer)
+    }
+}
+
+/// (De)serialize a currency amount as its currency and the numerator and denominator of the
+/// amount in decimal strings
+pub mod currency_amount {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct CurrencyAmountRepr {
+        currency: CurrencyRepr,
+        numerator: String,
+        denominator: String,
+    }
+
+    #[inline]
+    pub fn serialize<C, S>(amount: &CurrencyAmount<C>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: BaseCurrency,
+        S: Serializer,
+    {
+        CurrencyAmountRepr {
+            currency: CurrencyRepr::new(&amount.currency),
+            numerator: amount.numerator.to_string(),
+            denominator: amount.denominator.to_string(),
+        }
+        .serialize(serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, C, D>(deserializer: D) -> Result<CurrencyAmount<C>, D::Error>
+    where
+        C: SerdeCurrency,
+        D: Deserializer<'de>,
+    {
+        let repr = CurrencyAmountRepr::deserialize(deserializer)?;
+        let numerator = BigInt::from_str(&repr.numerator).map_err(D::Error::custom)?;
+        let denominator = BigInt::from_str(&repr.denominator).map_err(D::Error::custom)?;
+        let currency = C::from_repr(repr.currency).map_err(D::Error::custom)?;
+        CurrencyAmount::from_fractional_amount(currency, numerator, denominator)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Mirror of [`TradeType`] for `#[serde(with = "TradeTypeDef")]`
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(remote = "TradeType")]
+pub enum TradeTypeDef {
+    ExactInput,
+    ExactOutput,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+
+    #[test]
+    fn test_currency_repr_round_trip() {
+        let repr = CurrencyRepr::new(&*TOKEN0);
+        assert_eq!(repr.address, Some(TOKEN0.address()));
+        assert_eq!(Token::from_repr(repr.clone()).unwrap(), *TOKEN0);
+        assert!(Ether::from_repr(repr).is_err());
+
+        let repr = CurrencyRepr::new(&*ETHER);
+        assert_eq!(repr.address, None);
+        assert!(Ether::from_repr(repr.clone()).unwrap().equals(&*ETHER));
+        assert!(Token::from_repr(repr).is_err());
+    }
+
+    #[test]
+    fn test_pool_round_trip() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let json = serde_json::to_string(&pool).unwrap();
+        let deserialized: Pool<TickListDataProvider> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, pool);
+    }
+
+    #[test]
+    fn test_trade_round_trip() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let trade = Trade::exact_in(
+            Route::new(vec![pool], TOKEN0.clone(), TOKEN1.clone()),
+            CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&trade).unwrap();
+        let deserialized: Trade<Token, Token, TickListDataProvider> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, trade);
+    }
+}
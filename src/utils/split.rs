@@ -0,0 +1,111 @@
+//! ## Split
+//! The arithmetic of splitting an amount across routes in percentage increments, independent of
+//! how the routes are quoted.
+
+use alloc::vec::Vec;
+use uniswap_sdk_core::prelude::BigInt;
+
+/// Returns the percentages an amount can be split into in increments of `step` percent, i.e. the
+/// multiples of `step` and their complements to 100, so that any combination of multiples can be
+/// completed to 100% even if `step` does not divide 100.
+///
+/// ## Arguments
+///
+/// * `step`: The granularity of the split in percent, between 1 and 100
+///
+/// ## Returns
+///
+/// The percentages in ascending order, ending with 100
+#[inline]
+#[must_use]
+pub fn split_percents(step: u8) -> Vec<u32> {
+    assert!(step > 0 && step <= 100, "DISTRIBUTION_PERCENT");
+    let step = u32::from(step);
+    let mut percents: Vec<u32> = (1..=100 / step)
+        .flat_map(|i| [i * step, 100 - i * step])
+        .chain([100])
+        .filter(|&percent| percent > 0)
+        .collect();
+    percents.sort_unstable();
+    percents.dedup();
+    percents
+}
+
+/// Returns the part of an amount for a percentage, rounded down
+///
+/// ## Arguments
+///
+/// * `total`: The amount to split
+/// * `percent`: The percentage of the part
+#[inline]
+#[must_use]
+pub fn split_amount(total: &BigInt, percent: u32) -> BigInt {
+    total * BigInt::from(percent) / BigInt::from(100)
+}
+
+/// Returns the rounding dust of splitting an amount into percentages that sum up to 100, i.e. the
+/// amount less the sum of its rounded down parts, which is less than the number of parts.
+///
+/// ## Arguments
+///
+/// * `total`: The amount to split
+/// * `percents`: The percentages of the parts
+#[inline]
+#[must_use]
+pub fn split_dust(total: &BigInt, percents: impl IntoIterator<Item = u32>) -> BigInt {
+    percents.into_iter().fold(total.clone(), |dust, percent| {
+        dust - split_amount(total, percent)
+    })
+}
+
+/// Splits an amount into percentages that sum up to 100, giving the rounding dust to the first
+/// part so that the parts sum up to the amount.
+///
+/// ## Arguments
+///
+/// * `total`: The amount to split
+/// * `percents`: The percentages of the parts
+#[inline]
+#[must_use]
+pub fn split_amounts(total: &BigInt, percents: &[u32]) -> Vec<BigInt> {
+    let mut amounts: Vec<BigInt> = percents
+        .iter()
+        .map(|&percent| split_amount(total, percent))
+        .collect();
+    if let Some(first) = amounts.first_mut() {
+        *first += split_dust(total, percents.iter().copied());
+    }
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_percents() {
+        assert_eq!(split_percents(25), vec![25, 50, 75, 100]);
+        assert_eq!(split_percents(30), vec![10, 30, 40, 60, 70, 90, 100]);
+        assert_eq!(split_percents(100), vec![100]);
+    }
+
+    #[test]
+    #[should_panic(expected = "DISTRIBUTION_PERCENT")]
+    fn test_split_percents_zero() {
+        let _ = split_percents(0);
+    }
+
+    #[test]
+    fn test_split_amounts() {
+        let total = BigInt::from(1001);
+        assert_eq!(split_dust(&total, [30, 70]), BigInt::from(1));
+        assert_eq!(
+            split_amounts(&total, &[30, 70]),
+            vec![BigInt::from(301), BigInt::from(700)]
+        );
+        assert_eq!(
+            split_amounts(&BigInt::from(2), &[30, 30, 40]),
+            vec![BigInt::from(2), BigInt::ZERO, BigInt::ZERO]
+        );
+    }
+}
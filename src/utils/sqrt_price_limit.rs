@@ -0,0 +1,83 @@
+use super::ONE;
+use crate::prelude::*;
+use alloy_primitives::U160;
+use uniswap_sdk_core::prelude::{BigInt, Percent};
+
+/// Returns the `sqrt_price_limit_x96` of a single-hop swap such that the pool price does not move
+/// unfavorably by more than the slippage tolerance
+///
+/// ## Arguments
+///
+/// * `sqrt_ratio_x96`: The current Q64.96 sqrt price of the pool
+/// * `zero_for_one`: Whether the swap is from token0 to token1, i.e. the price decreases
+/// * `slippage_tolerance`: How much the pool price is allowed to move
+///
+/// ## Returns
+///
+/// The Q64.96 sqrt price limit, clamped to the valid range of the swap direction
+#[inline]
+#[must_use]
+pub fn sqrt_price_limit_from_slippage(
+    sqrt_ratio_x96: U160,
+    zero_for_one: bool,
+    slippage_tolerance: &Percent,
+) -> U160 {
+    assert!(
+        slippage_tolerance >= &Percent::default(),
+        "SLIPPAGE_TOLERANCE"
+    );
+    let numerator = &slippage_tolerance.numerator;
+    let denominator = &slippage_tolerance.denominator;
+    let sqrt_ratio = sqrt_ratio_x96.to_big_int();
+    let ratio_x192 = &sqrt_ratio * &sqrt_ratio;
+    // sqrt(P * (1 -/+ s)) in Q64.96, rounded down
+    if zero_for_one {
+        let min = MIN_SQRT_RATIO + ONE;
+        if numerator >= denominator {
+            return min;
+        }
+        let limit = (ratio_x192 * (denominator - numerator) / denominator).sqrt();
+        U160::from_big_int(limit).max(min)
+    } else {
+        let max = MAX_SQRT_RATIO - ONE;
+        let limit = (ratio_x192 * (denominator + numerator) / denominator).sqrt();
+        if limit >= max.to_big_int() {
+            max
+        } else {
+            U160::from_big_int(limit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_for_one() {
+        let sqrt_ratio_x96 = encode_sqrt_ratio_x96(1, 1);
+        // sqrt(0.99) * 2^96
+        assert_eq!(
+            sqrt_price_limit_from_slippage(sqrt_ratio_x96, true, &Percent::new(1, 100)),
+            U160::from_big_int((BigInt::from(99) << 192_u32).sqrt() / BigInt::from(10))
+        );
+        assert_eq!(
+            sqrt_price_limit_from_slippage(sqrt_ratio_x96, true, &Percent::new(1, 1)),
+            MIN_SQRT_RATIO + ONE
+        );
+    }
+
+    #[test]
+    fn test_one_for_zero() {
+        let sqrt_ratio_x96 = encode_sqrt_ratio_x96(1, 1);
+        // sqrt(1.01) * 2^96
+        assert_eq!(
+            sqrt_price_limit_from_slippage(sqrt_ratio_x96, false, &Percent::new(1, 100)),
+            U160::from_big_int((BigInt::from(101) << 192_u32).sqrt() / BigInt::from(10))
+        );
+        assert_eq!(
+            sqrt_price_limit_from_slippage(MAX_SQRT_RATIO - ONE, false, &Percent::new(1, 100)),
+            MAX_SQRT_RATIO - ONE
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::vec::Vec;
 use alloy_primitives::{aliases::U24, Uint, I256, U160, U256};
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -10,6 +11,47 @@ pub struct SwapState<I = i32> {
     pub liquidity: u128,
 }
 
+/// A step of a swap, i.e. the part of the swap within a single tick bitmap word or tick range
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapStep<I = i32> {
+    /// The Q64.96 sqrt price at the start of the step
+    pub sqrt_price_start_x96: U160,
+    /// The Q64.96 sqrt price at the end of the step
+    pub sqrt_price_x96: U160,
+    /// The next tick to swap to from the current tick in the swap direction
+    pub tick_next: I,
+    /// Whether `tick_next` is initialized and was crossed in this step
+    pub crossed: bool,
+    /// The in-range liquidity at the end of the step, i.e. after crossing `tick_next`
+    pub liquidity: u128,
+    /// The amount swapped in during the step, excluding the fee
+    pub amount_in: U256,
+    /// The amount swapped out during the step
+    pub amount_out: U256,
+    /// The fee accrued during the step, in the input token
+    pub fee_amount: U256,
+}
+
+/// The trace of a simulated swap, see [`v3_swap_with_trace`]
+#[derive(Clone, Debug, Default)]
+pub struct SwapTrace<I = i32> {
+    /// The steps of the swap in order
+    pub steps: Vec<SwapStep<I>>,
+    /// The state at the end of the swap, including the final sqrt price
+    pub state: SwapState<I>,
+}
+
+impl<I> SwapTrace<I> {
+    /// Returns the total fee accrued during the swap, in the input token
+    #[inline]
+    #[must_use]
+    pub fn fee_amount(&self) -> U256 {
+        self.steps
+            .iter()
+            .fold(U256::ZERO, |fee_amount, step| fee_amount + step.fee_amount)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct StepComputations<I = i32> {
     sqrt_price_start_x96: U160,
@@ -165,6 +207,68 @@ pub fn v3_swap<TP: TickDataProvider>(
     zero_for_one: bool,
     amount_specified: I256,
     sqrt_price_limit_x96: Option<U160>,
+) -> Result<SwapState<TP::Index>, Error> {
+    _v3_swap(
+        fee,
+        sqrt_price_x96,
+        tick_current,
+        liquidity,
+        tick_spacing,
+        tick_data_provider,
+        zero_for_one,
+        amount_specified,
+        sqrt_price_limit_x96,
+        |_| {},
+    )
+}
+
+/// Same as [`v3_swap`], but also records each step of the swap
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn v3_swap_with_trace<TP: TickDataProvider>(
+    fee: U24,
+    sqrt_price_x96: U160,
+    tick_current: TP::Index,
+    liquidity: u128,
+    tick_spacing: TP::Index,
+    tick_data_provider: &TP,
+    zero_for_one: bool,
+    amount_specified: I256,
+    sqrt_price_limit_x96: Option<U160>,
+) -> Result<SwapTrace<TP::Index>, Error> {
+    let mut steps = Vec::new();
+    let state = _v3_swap(
+        fee,
+        sqrt_price_x96,
+        tick_current,
+        liquidity,
+        tick_spacing,
+        tick_data_provider,
+        zero_for_one,
+        amount_specified,
+        sqrt_price_limit_x96,
+        |step| steps.push(step),
+    )?;
+    Ok(SwapTrace { steps, state })
+}
+
+#[inline]
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip_all, fields(zero_for_one = zero_for_one, %amount_specified))
+)]
+pub(crate) fn _v3_swap<TP: TickDataProvider>(
+    fee: U24,
+    sqrt_price_x96: U160,
+    tick_current: TP::Index,
+    liquidity: u128,
+    tick_spacing: TP::Index,
+    tick_data_provider: &TP,
+    zero_for_one: bool,
+    amount_specified: I256,
+    sqrt_price_limit_x96: Option<U160>,
+    mut on_step: impl FnMut(SwapStep<TP::Index>),
 ) -> Result<SwapState<TP::Index>, Error> {
     let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or_else(|| {
         if zero_for_one {
@@ -192,6 +296,8 @@ pub fn v3_swap<TP: TickDataProvider>(
         tick_current,
         liquidity,
     };
+    #[cfg(feature = "tracing")]
+    let mut initialized_ticks_crossed = 0_u32;
 
     // start swap while loop
     while !state.amount_specified_remaining.is_zero()
@@ -213,7 +319,7 @@ pub fn v3_swap<TP: TickDataProvider>(
             )?;
 
         step.tick_next = TP::Index::from_i24(step.tick_next.to_i24().clamp(MIN_TICK, MAX_TICK));
-        step.sqrt_price_next_x96 = get_sqrt_ratio_at_tick(step.tick_next.to_i24())?;
+        step.sqrt_price_next_x96 = tick_data_provider.sqrt_ratio_at_tick(step.tick_next)?;
 
         (
             state.sqrt_price_x96,
@@ -246,6 +352,7 @@ pub fn v3_swap<TP: TickDataProvider>(
             );
         }
 
+        let crossed = state.sqrt_price_x96 == step.sqrt_price_next_x96 && step.initialized;
         if state.sqrt_price_x96 == step.sqrt_price_next_x96 {
             // if the tick is initialized, run the tick transition
             if step.initialized {
@@ -256,6 +363,10 @@ pub fn v3_swap<TP: TickDataProvider>(
                     liquidity_net = -liquidity_net;
                 }
                 state.liquidity = add_delta(state.liquidity, liquidity_net)?;
+                #[cfg(feature = "tracing")]
+                {
+                    initialized_ticks_crossed += 1;
+                }
             }
             state.tick_current = if zero_for_one {
                 step.tick_next - TP::Index::ONE
@@ -268,8 +379,24 @@ pub fn v3_swap<TP: TickDataProvider>(
             state.tick_current =
                 TP::Index::from_i24(state.sqrt_price_x96.get_tick_at_sqrt_ratio()?);
         }
+        on_step(SwapStep {
+            sqrt_price_start_x96: step.sqrt_price_start_x96,
+            sqrt_price_x96: state.sqrt_price_x96,
+            tick_next: step.tick_next,
+            crossed,
+            liquidity: state.liquidity,
+            amount_in: step.amount_in,
+            amount_out: step.amount_out,
+            fee_amount: step.fee_amount,
+        });
     }
 
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        ticks_crossed = initialized_ticks_crossed,
+        tick = ?state.tick_current,
+        "swap simulated"
+    );
     Ok(state)
 }
 
@@ -1,8 +1,13 @@
 //! ## Tick Math Library in Rust
 //! This library is a Rust port of the [TickMath library](https://github.com/uniswap/v3-core/blob/main/contracts/libraries/TickMath.sol) in Solidity,
 //! with custom optimizations presented in [uni-v3-lib](https://github.com/Aperture-Finance/uni-v3-lib/blob/main/src/TickMath.sol).
+//!
+//! [`get_sqrt_ratio_at_tick`] multiplies the precomputed factors of the set bits of a tick only,
+//! while [`get_tick_at_sqrt_ratio`] keeps the unrolled log2 approximation of the Solidity library,
+//! which has no table to precompute. Neither is a `const fn`, as the `Uint` arithmetic they are
+//! built on is not const.
 
-use super::most_significant_bit;
+use super::{most_significant_bit, Q128};
 use crate::error::Error;
 use alloy_primitives::{aliases::I24, uint, Uint, U160, U256};
 use core::ops::{Shl, Shr, Sub};
@@ -25,6 +30,31 @@ pub const MAX_SQRT_RATIO: U160 = uint!(14614467034852101032872730522039888223787
 const MAX_SQRT_RATIO_MINUS_MIN_SQRT_RATIO_MINUS_ONE: U160 =
     uint!(1461446703485210103287273052203988822374428841602_U160);
 
+/// `2**128 / sqrt(1.0001)^(2**i)` for each bit `i` of the absolute value of a tick, as in
+/// `TickMath.getSqrtRatioAtTick`
+const SQRT_RATIO_FACTORS_X128: [U256; 20] = [
+    uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256),
+    uint!(0xfff97272373d413259a46990580e213a_U256),
+    uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256),
+    uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256),
+    uint!(0xffcb9843d60f6159c9db58835c926644_U256),
+    uint!(0xff973b41fa98c081472e6896dfb254c0_U256),
+    uint!(0xff2ea16466c96a3843ec78b326b52861_U256),
+    uint!(0xfe5dee046a99a2a811c461f1969c3053_U256),
+    uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256),
+    uint!(0xf987a7253ac413176f2b074cf7815e54_U256),
+    uint!(0xf3392b0822b70005940c7a398e4b70f3_U256),
+    uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256),
+    uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256),
+    uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256),
+    uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256),
+    uint!(0x31be135f97d08fd981231505542fcfa6_U256),
+    uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256),
+    uint!(0x5d6af8dedb81196699c329225ee604_U256),
+    uint!(0x2216e584f5fa1ea926041bedfe98_U256),
+    uint!(0x48a170391f7dc42444e8fa2_U256),
+];
+
 /// Trait to provide tick math functions for [`Uint`] types.
 pub trait TickMath: Sized {
     fn get_sqrt_ratio_at_tick(tick: I24) -> Result<Self, Error>;
@@ -63,74 +93,18 @@ pub fn get_sqrt_ratio_at_tick(tick: I24) -> Result<U160, Error> {
 
     // Equivalent: ratio = 2**128 / sqrt(1.0001) if abs_tick & 0x1 else 1 << 128
     let mut ratio = if abs_tick & 0x1 != 0 {
-        uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256)
+        SQRT_RATIO_FACTORS_X128[0]
     } else {
-        U256::from_limbs([0, 0, 1, 0])
+        Q128
     };
 
-    // Iterate through 1th to 19th bit of abs_tick because MAX_TICK < 2**20
-    // Equivalent to:
-    // for i in 1..20 {
-    //     if abs_tick & (1 << i) != 0 {
-    //         ratio = (ratio * ((1 << 128) / 1.0001.pow(1 << (i - 1)))) >> 128;
-    //     }
-    // }
-    if abs_tick & 0x2 != 0 {
-        ratio = (ratio * uint!(0xfff97272373d413259a46990580e213a_U256)) >> 128;
-    }
-    if abs_tick & 0x4 != 0 {
-        ratio = (ratio * uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256)) >> 128;
-    };
-    if abs_tick & 0x8 != 0 {
-        ratio = (ratio * uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256)) >> 128;
-    }
-    if abs_tick & 0x10 != 0 {
-        ratio = (ratio * uint!(0xffcb9843d60f6159c9db58835c926644_U256)) >> 128;
-    }
-    if abs_tick & 0x20 != 0 {
-        ratio = (ratio * uint!(0xff973b41fa98c081472e6896dfb254c0_U256)) >> 128;
-    }
-    if abs_tick & 0x40 != 0 {
-        ratio = (ratio * uint!(0xff2ea16466c96a3843ec78b326b52861_U256)) >> 128;
-    }
-    if abs_tick & 0x80 != 0 {
-        ratio = (ratio * uint!(0xfe5dee046a99a2a811c461f1969c3053_U256)) >> 128;
-    }
-    if abs_tick & 0x100 != 0 {
-        ratio = (ratio * uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256)) >> 128;
-    }
-    if abs_tick & 0x200 != 0 {
-        ratio = (ratio * uint!(0xf987a7253ac413176f2b074cf7815e54_U256)) >> 128;
-    }
-    if abs_tick & 0x400 != 0 {
-        ratio = (ratio * uint!(0xf3392b0822b70005940c7a398e4b70f3_U256)) >> 128;
-    }
-    if abs_tick & 0x800 != 0 {
-        ratio = (ratio * uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256)) >> 128;
-    }
-    if abs_tick & 0x1000 != 0 {
-        ratio = (ratio * uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256)) >> 128;
-    }
-    if abs_tick & 0x2000 != 0 {
-        ratio = (ratio * uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256)) >> 128;
-    }
-    if abs_tick & 0x4000 != 0 {
-        ratio = (ratio * uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256)) >> 128;
-    }
-    if abs_tick & 0x8000 != 0 {
-        ratio = (ratio * uint!(0x31be135f97d08fd981231505542fcfa6_U256)) >> 128;
-    }
-    if abs_tick & 0x10000 != 0 {
-        ratio = (ratio * uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256)) >> 128;
-    }
-    if abs_tick & 0x20000 != 0 {
-        ratio = (ratio * uint!(0x5d6af8dedb81196699c329225ee604_U256)) >> 128;
-    }
-    if abs_tick & 0x40000 != 0 {
-        ratio = (ratio * uint!(0x2216e584f5fa1ea926041bedfe98_U256)) >> 128;
-    }
-    if abs_tick & 0x80000 != 0 {
-        ratio = (ratio * uint!(0x48a170391f7dc42444e8fa2_U256)) >> 128;
+    // Walk the set bits among the 1th to 19th bit of abs_tick because MAX_TICK < 2**20, in
+    // ascending order as the rounding of each step depends on the previous ones
+    let mut bits = (abs_tick as u32) >> 1;
+    while bits != 0 {
+        let i = bits.trailing_zeros() as usize + 1;
+        ratio = (ratio * SQRT_RATIO_FACTORS_X128[i]) >> 128;
+        bits &= bits - 1;
     }
 
     if tick.is_positive() {
@@ -335,4 +309,29 @@ mod tests {
             MAX_TICK - I24::ONE
         );
     }
+
+    #[test]
+    fn returns_correct_values_for_ticks() {
+        for (tick, sqrt_ratio_x96) in [
+            (-887271, uint!(4295343490_U160)),
+            (-100000, uint!(533968626430936354154228408_U160)),
+            (-50, uint!(79030349367926598376800521322_U160)),
+            (-1, uint!(79224201403219477170569942574_U160)),
+            (1, uint!(79232123823359799118286999568_U160)),
+            (50, uint!(79426470787362580746886972461_U160)),
+            (100000, uint!(11755562826496067164730007768450_U160)),
+            (
+                887271,
+                uint!(1461373636630004318706518188784493106690254656249_U160),
+            ),
+        ] {
+            let tick = I24::try_from(tick).unwrap();
+            assert_eq!(get_sqrt_ratio_at_tick(tick).unwrap(), sqrt_ratio_x96);
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio_x96).unwrap(), tick);
+            assert_eq!(
+                get_tick_at_sqrt_ratio(sqrt_ratio_x96 - U160::from(1)).unwrap(),
+                tick - I24::ONE
+            );
+        }
+    }
 }
@@ -0,0 +1,207 @@
+//! ## Approximate Tick Math
+//! Fast `f64` approximations of the tick math for hot paths where the exact Q64.96 math is not
+//! needed, e.g. rendering prices, scanning many candidate ranges, and heuristics.
+//!
+//! The results are **approximate**: the relative error of the prices is around `1e-13` at the
+//! extreme ticks, and [`price_to_tick_f64`] may be off by one tick for prices within that error of
+//! a tick boundary. Use [`get_sqrt_ratio_at_tick`](super::get_sqrt_ratio_at_tick) and
+//! [`get_tick_at_sqrt_ratio`](super::get_tick_at_sqrt_ratio) where the result must match the
+//! contracts.
+
+use super::{MAX_TICK_I32, MIN_TICK_I32};
+use crate::error::Error;
+use alloy_primitives::aliases::I24;
+use core::f64::consts::{LN_2, SQRT_2};
+
+/// `sqrt(1.0001)^(2^i)` for each bit `i` of a tick
+const SQRT_RATIO_POWERS: [f64; 20] = [
+    1.0000499987500624,
+    1.0001,
+    1.00020001,
+    1.0004000600040002,
+    1.000800280056007,
+    1.001601200560182,
+    1.0032049649635981,
+    1.0064202017276138,
+    1.012881622445451,
+    1.0259291810877293,
+    1.052530684607339,
+    1.1078208420399935,
+    1.2272670180582004,
+    1.5061843336134675,
+    2.268591246822645,
+    5.146506245160322,
+    26.486526531474198,
+    701.5360877024866,
+    492152.882348911,
+    242214459604.34106,
+];
+
+/// `ln(1.0001)`
+pub(crate) const LN_TICK_BASE: f64 = 9.999500033330834e-05;
+
+/// Returns the approximate square root of the price of token0 denominated in token1 at a tick, i.e.
+/// `sqrt(1.0001)^tick`, not scaled by `2^96` unlike
+/// [`get_sqrt_ratio_at_tick`](super::get_sqrt_ratio_at_tick).
+///
+/// ## Arguments
+///
+/// * `tick`: The tick for which to compute the sqrt ratio
+#[inline]
+pub fn get_sqrt_ratio_at_tick_f64(tick: i32) -> Result<f64, Error> {
+    if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        let tick = I24::try_from(tick).unwrap_or(if tick < 0 { I24::MIN } else { I24::MAX });
+        return Err(Error::InvalidTick(tick));
+    }
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = 1.0;
+    for (i, power) in SQRT_RATIO_POWERS.iter().enumerate() {
+        if abs_tick & (1 << i) != 0 {
+            ratio *= power;
+        }
+    }
+    Ok(if tick < 0 { 1.0 / ratio } else { ratio })
+}
+
+/// Returns the approximate price of token0 denominated in token1 at a tick, i.e. `1.0001^tick`, in
+/// the raw units of the tokens. Multiply by `10^(decimals0 - decimals1)` for the human-readable
+/// price.
+///
+/// ## Arguments
+///
+/// * `tick`: The tick for which to compute the price
+#[inline]
+pub fn tick_to_price_f64(tick: i32) -> Result<f64, Error> {
+    let sqrt_ratio = get_sqrt_ratio_at_tick_f64(tick)?;
+    Ok(sqrt_ratio * sqrt_ratio)
+}
+
+/// Returns the approximate greatest tick whose price is less than or equal to a price, the `f64`
+/// counterpart of [`get_tick_at_sqrt_ratio`](super::get_tick_at_sqrt_ratio) for a price instead of
+/// a sqrt ratio.
+///
+/// ## Arguments
+///
+/// * `price`: The price of token0 denominated in token1 in the raw units of the tokens
+///
+/// ## Returns
+///
+/// The tick, or [`Error::InvalidPrice`] if the price is not positive or outside the prices of
+/// [`MIN_TICK`](super::MIN_TICK) and [`MAX_TICK`](super::MAX_TICK)
+#[inline]
+pub fn price_to_tick_f64(price: f64) -> Result<i32, Error> {
+    if !(price > 0.0 && price.is_finite()) {
+        return Err(Error::InvalidPrice);
+    }
+    let tick = ln(price) / LN_TICK_BASE;
+    // round towards negative infinity, as the casts round towards zero
+    let truncated = tick as i32;
+    let tick = if (truncated as f64) > tick {
+        truncated - 1
+    } else {
+        truncated
+    };
+    if (MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        Ok(tick)
+    } else {
+        Err(Error::InvalidPrice)
+    }
+}
+
+/// Returns the natural logarithm of a positive normal number, which is unavailable in `core`.
+fn ln(x: f64) -> f64 {
+    let bits = x.to_bits();
+    let mut exponent = ((bits >> 52) & 0x7ff) as i32 - 1023;
+    // the mantissa in [1, 2), moved to [sqrt(2) / 2, sqrt(2)) for a faster converging series
+    let mut mantissa = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+    if mantissa > SQRT_2 {
+        mantissa /= 2.0;
+        exponent += 1;
+    }
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3 / 3 + z^5 / 5 + ...) with z = (m - 1) / (m + 1)
+    let z = (mantissa - 1.0) / (mantissa + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    for n in (1..=25).step_by(2) {
+        sum += term / n as f64;
+        term *= z2;
+    }
+    exponent as f64 * LN_2 + 2.0 * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_sqrt_ratio_at_tick;
+    use alloc::string::ToString;
+
+    const TICKS: [i32; 9] = [
+        MIN_TICK_I32,
+        -500000,
+        -74959,
+        -1,
+        0,
+        1,
+        74959,
+        500000,
+        MAX_TICK_I32,
+    ];
+
+    fn relative_error(a: f64, b: f64) -> f64 {
+        let error = (a - b) / b;
+        if error < 0.0 {
+            -error
+        } else {
+            error
+        }
+    }
+
+    #[test]
+    fn test_get_sqrt_ratio_at_tick_f64() {
+        for tick in TICKS {
+            let exact = get_sqrt_ratio_at_tick(I24::try_from(tick).unwrap())
+                .unwrap()
+                .to_string()
+                .parse::<f64>()
+                .unwrap()
+                / (1_u128 << 96) as f64;
+            let approximate = get_sqrt_ratio_at_tick_f64(tick).unwrap();
+            assert!(relative_error(approximate, exact) < 1e-12, "{tick}");
+        }
+        assert!(matches!(
+            get_sqrt_ratio_at_tick_f64(MAX_TICK_I32 + 1),
+            Err(Error::InvalidTick(_))
+        ));
+        assert!(matches!(
+            tick_to_price_f64(MIN_TICK_I32 - 1),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[test]
+    fn test_tick_to_price_f64() {
+        assert_eq!(tick_to_price_f64(0).unwrap(), 1.0);
+        assert!(relative_error(tick_to_price_f64(1).unwrap(), 1.0001) < 1e-15);
+        assert!(relative_error(tick_to_price_f64(-74959).unwrap(), 1.0 / 1800.0) < 1e-4);
+    }
+
+    #[test]
+    fn test_price_to_tick_f64() {
+        for tick in TICKS {
+            let price = tick_to_price_f64(tick).unwrap();
+            // away from the tick boundaries
+            assert_eq!(price_to_tick_f64(price * (1.0 + 1e-9)).unwrap(), tick);
+            if tick != MIN_TICK_I32 {
+                assert_eq!(price_to_tick_f64(price * (1.0 - 1e-9)).unwrap(), tick - 1);
+            }
+        }
+        assert!(relative_error(ln(10.0), core::f64::consts::LN_10) < 1e-15);
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-50, 1e50] {
+            assert!(
+                matches!(price_to_tick_f64(price), Err(Error::InvalidPrice)),
+                "{price}"
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use alloy_primitives::{Signed, Uint};
+#[cfg(feature = "bigdecimal")]
 use bigdecimal::BigDecimal;
 use num_bigint::{BigInt, BigUint, Sign};
 
@@ -7,6 +8,7 @@ pub trait ToBig {
 
     fn to_big_int(&self) -> BigInt;
 
+    #[cfg(feature = "bigdecimal")]
     #[inline]
     fn to_big_decimal(&self) -> BigDecimal {
         BigDecimal::from(self.to_big_int())
@@ -85,6 +87,7 @@ mod tests {
             + (BigUint::from(4_u64) << 192);
         assert_eq!(x.to_big_uint(), y);
         assert_eq!(x.to_big_int(), BigInt::from_biguint(Sign::Plus, y.clone()));
+        #[cfg(feature = "bigdecimal")]
         assert_eq!(
             x.to_big_decimal(),
             BigDecimal::from(BigInt::from_biguint(Sign::Plus, y))
@@ -100,12 +103,14 @@ mod tests {
             + (BigInt::from(4) << 192);
         assert_eq!(x.to_big_uint(), y.to_biguint().unwrap());
         assert_eq!(x.to_big_int(), y);
+        #[cfg(feature = "bigdecimal")]
         assert_eq!(x.to_big_decimal(), BigDecimal::from(y.clone()));
 
         let x = -x;
         let z: BigInt = (BigInt::from(1) << 256) - y.clone();
         assert_eq!(x.to_big_uint(), z.to_biguint().unwrap());
         assert_eq!(x.to_big_int(), -y.clone());
+        #[cfg(feature = "bigdecimal")]
         assert_eq!(x.to_big_decimal(), BigDecimal::from(-y));
     }
 